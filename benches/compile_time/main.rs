@@ -0,0 +1,230 @@
+//! Compile-time benchmark for the type-level machinery.
+//!
+//! Generates one throw-away crate per (scenario, size), type-checks it against the
+//! local besedarium sources and records how long `cargo check` takes. Only the
+//! generated crate is re-checked per data point; besedarium itself is checked once
+//! in a warm-up step, so the numbers isolate trait-solving cost in user code.
+//!
+//! Usage: `cargo bench --bench compile_time -- [sizes...]`
+//!   sizes default to: 4 8 16 32
+//!
+//! Output: a markdown report at compile-time/report.md under the cargo target directory
+//! (`CARGO_TARGET_DIR`, else target/), also printed.
+
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Instant;
+
+const DEFAULT_SIZES: [usize; 4] = [4, 8, 16, 32];
+
+/// What a generated crate checks.
+#[derive(Clone, Copy)]
+enum Scenario {
+    /// A chain of interactions projected onto its first role with the given strategy.
+    Projection(Strategy),
+    /// Two chains over distinct roles checked with `assert_disjoint!`.
+    Disjointness,
+    /// A chain with distinct labels checked with `assert_unique_labels!`.
+    Labels,
+}
+
+/// The projection strategy passed to `ProjectRole`.
+#[derive(Clone, Copy)]
+enum Strategy {
+    Permissive,
+    Strict,
+}
+
+const SCENARIOS: [Scenario; 4] = [
+    Scenario::Projection(Strategy::Permissive),
+    Scenario::Projection(Strategy::Strict),
+    Scenario::Disjointness,
+    Scenario::Labels,
+];
+
+impl Scenario {
+    fn name(self) -> &'static str {
+        match self {
+            Scenario::Projection(_) => "projection",
+            Scenario::Disjointness => "disjointness",
+            Scenario::Labels => "labels",
+        }
+    }
+
+    fn strategy(self) -> Option<Strategy> {
+        match self {
+            Scenario::Projection(strategy) => Some(strategy),
+            _ => None,
+        }
+    }
+
+    /// The name of the generated crate for size `n`.
+    fn crate_name(self, n: usize) -> String {
+        match self.strategy() {
+            Some(strategy) => format!("{}_{}_{n}", self.name(), strategy.name().to_lowercase()),
+            None => format!("{}_{n}", self.name()),
+        }
+    }
+}
+
+impl Strategy {
+    fn name(self) -> &'static str {
+        match self {
+            Strategy::Permissive => "Permissive",
+            Strategy::Strict => "Strict",
+        }
+    }
+}
+
+// Emit role and label declarations shared by all scenarios
+fn emit_prelude(src: &mut String, n: usize) {
+    src.push_str("#![allow(dead_code)]\n");
+    src.push_str("use besedarium::*;\n");
+    for i in 0..n {
+        writeln!(src, "pub struct R{i}; impl Role for R{i} {{}}").unwrap();
+        writeln!(src, "pub struct L{i}; impl ProtocolLabel for L{i} {{}}").unwrap();
+    }
+    // RoleEq is needed by projection and LabelEq by the label checks; emit
+    // the full n x n tables
+    for i in 0..n {
+        for j in 0..n {
+            let output = if i == j { "True" } else { "False" };
+            writeln!(
+                src,
+                "impl RoleEq<R{j}> for R{i} {{ type Output = {output}; }}"
+            )
+            .unwrap();
+            writeln!(
+                src,
+                "impl LabelEq<L{j}> for L{i} {{ type Output = {output}; }}"
+            )
+            .unwrap();
+        }
+    }
+}
+
+// A chain of n interactions alternating between two roles, labelled L0..L(n-1)
+fn chain(n: usize, first: &str, second: &str) -> String {
+    (0..n).rev().fold("TEnd<Http>".to_string(), |body, i| {
+        let role = if i % 2 == 1 { second } else { first };
+        format!("TInteract<Http, L{i}, {role}, Message, {body}>")
+    })
+}
+
+fn emit_scenario(scenario: Scenario, n: usize) -> String {
+    let mut src = String::new();
+    emit_prelude(&mut src, n);
+    match scenario {
+        Scenario::Projection(strategy) => {
+            writeln!(src, "pub type Global = {};", chain(n, "R0", "R1")).unwrap();
+            writeln!(
+                src,
+                "pub type Local = <() as ProjectRole<R0, Http, Global, {}>>::Out;",
+                strategy.name()
+            )
+            .unwrap();
+            src.push_str(
+                "pub fn force() -> core::marker::PhantomData<Local> { core::marker::PhantomData }\n",
+            );
+        }
+        Scenario::Disjointness => {
+            let half = n / 2;
+            let (left, right) = (0..half).rev().fold(
+                ("TEnd<Http>".to_string(), "TEnd<Http>".to_string()),
+                |(left, right), i| {
+                    (
+                        format!("TInteract<Http, L{i}, R{i}, Message, {left}>"),
+                        format!("TInteract<Http, L{i}, R{}, Message, {right}>", i + half),
+                    )
+                },
+            );
+            writeln!(src, "pub type Left = {left};").unwrap();
+            writeln!(src, "pub type Right = {right};").unwrap();
+            src.push_str("assert_disjoint!(Left, Right);\n");
+        }
+        Scenario::Labels => {
+            writeln!(src, "pub type Global = {};", chain(n, "R0", "R1")).unwrap();
+            src.push_str("assert_unique_labels!(Global);\n");
+        }
+    }
+    src
+}
+
+// Create (or refresh) the generated crate and return the elapsed check time in ms
+fn measure(root: &Path, out: &Path, scenario: Scenario, n: usize) -> u128 {
+    let name = scenario.crate_name(n);
+    let dir = out.join("crates").join(&name);
+    fs::create_dir_all(dir.join("src")).expect("create the generated crate");
+    let manifest = format!(
+        "[package]\nname = \"bench_{name}\"\nversion = \"0.0.0\"\nedition = \"2021\"\n\n\
+         [dependencies]\nbesedarium = {{ path = {:?} }}\n\n[workspace]\n",
+        root.display().to_string()
+    );
+    fs::write(dir.join("Cargo.toml"), manifest).expect("write the generated manifest");
+    fs::write(dir.join("src/lib.rs"), emit_scenario(scenario, n))
+        .expect("write the generated source");
+
+    let start = Instant::now();
+    let status = Command::new(std::env::var("CARGO").unwrap_or_else(|_| "cargo".into()))
+        .args(["check", "--quiet", "--manifest-path"])
+        .arg(dir.join("Cargo.toml"))
+        .env("CARGO_TARGET_DIR", out.join("target"))
+        .status()
+        .expect("run cargo check");
+    assert!(status.success(), "cargo check failed for {name}");
+    start.elapsed().as_millis()
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    // `cargo test --benches` runs this binary without `--bench`; only
+    // `cargo bench` takes the measurements
+    if !args.iter().any(|arg| arg == "--bench") {
+        return;
+    }
+    let mut sizes: Vec<usize> = args
+        .iter()
+        .filter(|arg| !arg.starts_with("--"))
+        .map(|arg| arg.parse().expect("sizes must be numbers"))
+        .collect();
+    if sizes.is_empty() {
+        sizes = DEFAULT_SIZES.to_vec();
+    }
+
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    // A relative target directory is relative to the package root
+    let target =
+        std::env::var_os("CARGO_TARGET_DIR").map_or_else(|| "target".into(), PathBuf::from);
+    let out = root.join(target).join("compile-time");
+    fs::create_dir_all(&out).expect("create the output directory");
+
+    println!("Warming up (checking besedarium once)...");
+    measure(&root, &out, Scenario::Labels, 2);
+
+    let rustc = Command::new("rustc")
+        .arg("--version")
+        .output()
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .unwrap_or_default();
+    let mut report = format!("# Compile-time benchmark\n\nrustc: {rustc}\n\n");
+    report.push_str("| scenario | strategy |");
+    for n in &sizes {
+        write!(report, " n={n} |").unwrap();
+    }
+    report.push_str("\n| --- | --- |");
+    report.push_str(&" --- |".repeat(sizes.len()));
+    report.push('\n');
+    for scenario in SCENARIOS {
+        let strategy = scenario.strategy().map_or("-", Strategy::name);
+        write!(report, "| {} | {strategy} |", scenario.name()).unwrap();
+        for &n in &sizes {
+            write!(report, " {} ms |", measure(&root, &out, scenario, n)).unwrap();
+        }
+        report.push('\n');
+    }
+
+    fs::write(out.join("report.md"), &report).expect("write the report");
+    print!("{report}");
+}
@@ -0,0 +1,83 @@
+//! # Besedarium Derive Macros
+//!
+//! Derive macros for the marker traits of `besedarium`, re-exported by it
+//! under the `derive` feature.
+//!
+//! Key components:
+//!
+//! - `#[derive(ProtocolLabel)]`: Implements `ProtocolLabel` with `NAME` set
+//! - `#[derive(Role)]`: Implements `Role` with `NAME` set
+//!
+//! `NAME` is the name of the type, unless overridden with
+//! `#[besedarium(name = "...")]`.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, DeriveInput, LitStr};
+
+/// Implements `besedarium::ProtocolLabel`, naming the label after the type.
+///
+/// ```ignore
+/// #[derive(ProtocolLabel)]
+/// #[besedarium(name = "LoginRequest")]
+/// struct Login;
+/// assert_eq!(Login::name(), "LoginRequest");
+/// ```
+#[proc_macro_derive(ProtocolLabel, attributes(besedarium))]
+pub fn derive_protocol_label(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input, quote!(::besedarium::ProtocolLabel))
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Implements `besedarium::Role`, naming the role after the type.
+///
+/// ```ignore
+/// #[derive(Role)]
+/// #[besedarium(name = "alice")]
+/// struct Alice;
+/// assert_eq!(Alice::name(), "alice");
+/// ```
+#[proc_macro_derive(Role, attributes(besedarium))]
+pub fn derive_role(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input, quote!(::besedarium::Role))
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: &DeriveInput, trait_path: TokenStream2) -> syn::Result<TokenStream2> {
+    let ident = &input.ident;
+    let name = match name_override(input)? {
+        Some(name) => name,
+        None => LitStr::new(&ident.to_string(), ident.span()),
+    };
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics #trait_path for #ident #ty_generics #where_clause {
+            const NAME: ::core::option::Option<&'static str> =
+                ::core::option::Option::Some(#name);
+        }
+    })
+}
+
+/// The name given by `#[besedarium(name = "...")]`, if any.
+fn name_override(input: &DeriveInput) -> syn::Result<Option<LitStr>> {
+    let mut name = None;
+    for attr in &input.attrs {
+        if !attr.path().is_ident("besedarium") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("name") {
+                name = Some(meta.value()?.parse::<LitStr>()?);
+                Ok(())
+            } else {
+                Err(meta.error("expected `name = \"...\"`"))
+            }
+        })?;
+    }
+    Ok(name)
+}
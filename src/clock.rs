@@ -0,0 +1,113 @@
+//! # Clocks for Timing Features
+//!
+//! This module provides the time source used by timing-related protocol
+//! features (timeouts, heartbeats, deadline checks).
+//!
+//! - `Clock`: The abstraction every timing feature reads time through
+//! - `MonotonicClock`: Default clock backed by `std::time::Instant`
+//! - `MockClock`: Manually advanced clock for deterministic tests
+//!
+//! Time is expressed as a `Duration` since the clock's own origin, so that
+//! real and mock clocks can be used interchangeably.
+
+use core::sync::atomic::{AtomicU64, Ordering};
+use core::time::Duration;
+use std::time::Instant;
+
+/// A source of monotonic time.
+///
+/// Implementations must never go backwards: two successive calls to `now`
+/// return non-decreasing values.
+pub trait Clock {
+    /// Time elapsed since this clock's origin.
+    fn now(&self) -> Duration;
+
+    /// Time elapsed since an earlier reading of this clock.
+    fn elapsed_since(&self, earlier: Duration) -> Duration {
+        self.now().saturating_sub(earlier)
+    }
+
+    /// Whether `budget` has been used up since `start`.
+    fn has_expired(&self, start: Duration, budget: Duration) -> bool {
+        self.elapsed_since(start) >= budget
+    }
+}
+
+/// Default clock backed by the operating system's monotonic clock.
+#[derive(Debug, Clone, Copy)]
+pub struct MonotonicClock {
+    origin: Instant,
+}
+
+impl MonotonicClock {
+    /// Create a clock whose origin is the current instant.
+    pub fn new() -> Self {
+        Self {
+            origin: Instant::now(),
+        }
+    }
+}
+
+impl Default for MonotonicClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for MonotonicClock {
+    fn now(&self) -> Duration {
+        self.origin.elapsed()
+    }
+}
+
+/// Clock that only moves when told to.
+///
+/// Lets time-dependent protocol behavior be tested without real sleeps.
+/// The clock can be shared between threads; all methods take `&self`.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// use std::time::Duration;
+///
+/// let clock = MockClock::new();
+/// let start = clock.now();
+/// clock.advance(Duration::from_millis(250));
+/// assert!(!clock.has_expired(start, Duration::from_millis(300)));
+/// clock.advance(Duration::from_millis(50));
+/// assert!(clock.has_expired(start, Duration::from_millis(300)));
+/// ```
+#[derive(Debug, Default)]
+pub struct MockClock {
+    nanos: AtomicU64,
+}
+
+impl MockClock {
+    /// Create a mock clock at time zero.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Move the clock forward by `by`.
+    pub fn advance(&self, by: Duration) {
+        let by = u64::try_from(by.as_nanos()).unwrap_or(u64::MAX);
+        // fetch_update keeps the addition saturating under concurrent advances
+        let _ = self
+            .nanos
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |now| {
+                Some(now.saturating_add(by))
+            });
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> Duration {
+        Duration::from_nanos(self.nanos.load(Ordering::SeqCst))
+    }
+}
+
+impl<C: Clock + ?Sized> Clock for &C {
+    fn now(&self) -> Duration {
+        (**self).now()
+    }
+}
@@ -8,51 +8,364 @@
 
 use crate::protocol;
 use crate::types;
+use core::marker::PhantomData;
+use core::time::Duration;
 
 /// Extracts the set of roles used in a protocol as a type-level list.
 ///
 /// - Implemented for all protocol combinators.
+/// - Lists senders and receivers, the role of every `TInteract`, and the
+///   roles of both branches of binary combinators, each role once, in order
+///   of first appearance.
+/// - Roles are compared with `RoleEq`, so the roles of a protocol must have
+///   `RoleEq` impls against each other.
 /// - Used for disjointness checks, macro expansion, and compile-time assertions.
 /// - See also: [`Disjoint`], [`extract_roles!`] macro.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Alice; struct Bob;
+/// impl Role for Alice {} impl Role for Bob {}
+/// impl RoleEq<Alice> for Alice { type Output = True; }
+/// impl RoleEq<Bob> for Alice   { type Output = False; }
+/// impl RoleEq<Alice> for Bob   { type Output = False; }
+/// impl RoleEq<Bob> for Bob     { type Output = True; }
+/// type PingPong = TMsg<Http, EmptyLabel, Alice, Bob, Message,
+///                 TMsg<Http, EmptyLabel, Bob, Alice, Response,
+///                 TInteract<Http, EmptyLabel, Bob, Message, TEnd<Http>>>>;
+/// assert_type_eq!(<PingPong as RolesOf>::Roles, tlist!(Alice, Bob));
+/// ```
 pub trait RolesOf {
     type Roles;
 }
+
+/// Prepends the roles of a list to a duplicate-free list of roles, dropping
+/// their later occurrences.
+///
+/// Joins the roles of a step with those of its continuation, and the roles
+/// of two branches, in [`RolesOf`]. Roles are compared with `RoleEq`.
+pub trait PrependRoles<Roles> {
+    type Output;
+}
+impl<Roles> PrependRoles<Roles> for protocol::Nil {
+    type Output = Roles;
+}
+impl<H, T, Roles> PrependRoles<Roles> for protocol::Cons<H, T>
+where
+    T: PrependRoles<Roles>,
+    <T as PrependRoles<Roles>>::Output: protocol::RemoveAll<protocol::ByRole, H>,
+{
+    type Output = protocol::Cons<
+        H,
+        <<T as PrependRoles<Roles>>::Output as protocol::RemoveAll<protocol::ByRole, H>>::Output,
+    >;
+}
+
+// The roles of a step followed by the new roles of its continuation
+type WithRoles<Own, Roles> = <Own as PrependRoles<Roles>>::Output;
+type One<R> = protocol::Cons<R, protocol::Nil>;
+type Two<A, B> = protocol::Cons<A, protocol::Cons<B, protocol::Nil>>;
+
 impl<IO, Lbl> RolesOf for protocol::TEnd<IO, Lbl> {
     type Roles = protocol::Nil;
 }
-impl<IO, Lbl: types::ProtocolLabel, R, H, T: protocol::TSession<IO> + RolesOf> RolesOf
-    for protocol::TInteract<IO, Lbl, R, H, T>
+impl<IO, Lbl, R, H, T> RolesOf for protocol::TInteract<IO, Lbl, R, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: protocol::TSession<IO> + RolesOf,
+    One<R>: PrependRoles<<T as RolesOf>::Roles>,
 {
-    type Roles = protocol::Cons<R, <T as RolesOf>::Roles>;
+    type Roles = WithRoles<One<R>, <T as RolesOf>::Roles>;
 }
-impl<
-        IO,
-        Lbl: types::ProtocolLabel,
-        L: protocol::TSession<IO> + RolesOf,
-        R: protocol::TSession<IO>,
-    > RolesOf for protocol::TChoice<IO, Lbl, L, R>
+impl<IO, Lbl, From, To, H, T> RolesOf for protocol::TMsg<IO, Lbl, From, To, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: protocol::TSession<IO> + RolesOf,
+    Two<From, To>: PrependRoles<<T as RolesOf>::Roles>,
 {
-    type Roles = <L as RolesOf>::Roles;
+    type Roles = WithRoles<Two<From, To>, <T as RolesOf>::Roles>;
 }
-impl<
-        IO,
-        Lbl: types::ProtocolLabel,
-        L: protocol::TSession<IO> + RolesOf,
-        R: protocol::TSession<IO>,
-        IsDisjoint,
-    > RolesOf for protocol::TPar<IO, Lbl, L, R, IsDisjoint>
+impl<IO, Lbl: types::ProtocolLabel, From, WorkerSet, H, T> RolesOf
+    for protocol::TAnycast<IO, Lbl, From, WorkerSet, H, T>
+where
+    T: protocol::TSession<IO> + RolesOf,
+    WorkerSet: PrependRoles<<T as RolesOf>::Roles>,
+    One<From>: PrependRoles<WithRoles<WorkerSet, <T as RolesOf>::Roles>>,
+{
+    type Roles = WithRoles<One<From>, WithRoles<WorkerSet, <T as RolesOf>::Roles>>;
+}
+impl<IO, Lbl: types::ProtocolLabel, A, B, Cont> RolesOf
+    for protocol::TDisconnect<IO, Lbl, A, B, Cont>
+where
+    Cont: protocol::TSession<IO> + RolesOf,
+    Two<A, B>: PrependRoles<<Cont as RolesOf>::Roles>,
+{
+    type Roles = WithRoles<Two<A, B>, <Cont as RolesOf>::Roles>;
+}
+impl<IO, Lbl: types::ProtocolLabel, A, B, Cont> RolesOf for protocol::TConnect<IO, Lbl, A, B, Cont>
+where
+    Cont: protocol::TSession<IO> + RolesOf,
+    Two<A, B>: PrependRoles<<Cont as RolesOf>::Roles>,
+{
+    type Roles = WithRoles<Two<A, B>, <Cont as RolesOf>::Roles>;
+}
+impl<IO, Lbl: types::ProtocolLabel, R, Cont> RolesOf for protocol::TEndFor<IO, Lbl, R, Cont>
+where
+    Cont: protocol::TSession<IO> + RolesOf,
+    One<R>: PrependRoles<<Cont as RolesOf>::Roles>,
+{
+    type Roles = WithRoles<One<R>, <Cont as RolesOf>::Roles>;
+}
+// The delegated endpoint's role is listed after the two parties to the hand-over
+impl<IO, Lbl: types::ProtocolLabel, From, To, DelegatedEp, T> RolesOf
+    for protocol::TDelegate<IO, Lbl, From, To, DelegatedEp, T>
+where
+    DelegatedEp: protocol::GetLocalRole,
+    T: protocol::TSession<IO> + RolesOf,
+    protocol::Cons<From, Two<To, <DelegatedEp as protocol::GetLocalRole>::Role>>:
+        PrependRoles<<T as RolesOf>::Roles>,
 {
-    type Roles = <L as RolesOf>::Roles;
+    type Roles = WithRoles<
+        protocol::Cons<From, Two<To, <DelegatedEp as protocol::GetLocalRole>::Role>>,
+        <T as RolesOf>::Roles,
+    >;
+}
+impl<IO, Lbl: types::ProtocolLabel, From, ToSet, H, T> RolesOf
+    for protocol::TBroadcast<IO, Lbl, From, ToSet, H, T>
+where
+    T: protocol::TSession<IO> + RolesOf,
+    ToSet: PrependRoles<<T as RolesOf>::Roles>,
+    One<From>: PrependRoles<WithRoles<ToSet, <T as RolesOf>::Roles>>,
+{
+    type Roles = WithRoles<One<From>, WithRoles<ToSet, <T as RolesOf>::Roles>>;
+}
+impl<IO, Lbl, L, R> RolesOf for protocol::TChoice<IO, Lbl, L, R>
+where
+    Lbl: types::ProtocolLabel,
+    L: protocol::TSession<IO> + RolesOf,
+    R: protocol::TSession<IO> + RolesOf,
+    <L as RolesOf>::Roles: PrependRoles<<R as RolesOf>::Roles>,
+{
+    type Roles = WithRoles<<L as RolesOf>::Roles, <R as RolesOf>::Roles>;
+}
+impl<IO, Lbl: types::ProtocolLabel, Branches: protocol::SessionList<IO> + RolesOfEach> RolesOf
+    for protocol::TChoiceN<IO, Lbl, Branches>
+{
+    type Roles = <Branches as RolesOfEach>::Roles;
+}
+impl<IO, Lbl, Chooser, Branches> RolesOf for protocol::TSelect<IO, Lbl, Chooser, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: protocol::SessionList<IO> + RolesOfEach,
+    One<Chooser>: PrependRoles<<Branches as RolesOfEach>::Roles>,
+{
+    type Roles = WithRoles<One<Chooser>, <Branches as RolesOfEach>::Roles>;
+}
+impl<IO, Lbl, Offeree, Branches> RolesOf for protocol::TOffer<IO, Lbl, Offeree, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: protocol::SessionList<IO> + RolesOfEach,
+    One<Offeree>: PrependRoles<<Branches as RolesOfEach>::Roles>,
+{
+    type Roles = WithRoles<One<Offeree>, <Branches as RolesOfEach>::Roles>;
+}
+impl<IO, Lbl: types::ProtocolLabel, Branches: protocol::SessionList<IO> + RolesOfEach> RolesOf
+    for protocol::TParN<IO, Lbl, Branches>
+{
+    type Roles = <Branches as RolesOfEach>::Roles;
+}
+impl<IO, Lbl: types::ProtocolLabel, Steps: protocol::SessionList<IO> + RolesOfEach> RolesOf
+    for protocol::TShuffle<IO, Lbl, Steps>
+{
+    type Roles = <Steps as RolesOfEach>::Roles;
+}
+impl<IO, Lbl, L, R, IsDisjoint> RolesOf for protocol::TPar<IO, Lbl, L, R, IsDisjoint>
+where
+    Lbl: types::ProtocolLabel,
+    L: protocol::TSession<IO> + RolesOf,
+    R: protocol::TSession<IO> + RolesOf,
+    <L as RolesOf>::Roles: PrependRoles<<R as RolesOf>::Roles>,
+{
+    type Roles = WithRoles<<L as RolesOf>::Roles, <R as RolesOf>::Roles>;
 }
 impl<IO, Lbl: types::ProtocolLabel, S: protocol::TSession<IO> + RolesOf> RolesOf
     for protocol::TRec<IO, Lbl, S>
 {
     type Roles = <S as RolesOf>::Roles;
 }
+impl<IO, Lbl: types::ProtocolLabel, Var, Body: protocol::TSession<IO> + RolesOf> RolesOf
+    for protocol::TRecX<IO, Lbl, Var, Body>
+{
+    type Roles = <Body as RolesOf>::Roles;
+}
+impl<Var> RolesOf for protocol::TVar<Var> {
+    type Roles = protocol::Nil;
+}
+impl<IO, Lbl, Dur, Body, OnTimeout> RolesOf for protocol::TTimeout<IO, Lbl, Dur, Body, OnTimeout>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    Body: protocol::TSession<IO> + RolesOf,
+    OnTimeout: protocol::TSession<IO> + RolesOf,
+    <Body as RolesOf>::Roles: PrependRoles<<OnTimeout as RolesOf>::Roles>,
+{
+    type Roles = WithRoles<<Body as RolesOf>::Roles, <OnTimeout as RolesOf>::Roles>;
+}
+impl<IO, Lbl, Decider, Body, Cont> RolesOf for protocol::TOptional<IO, Lbl, Decider, Body, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Body: protocol::TSession<IO> + RolesOf,
+    Cont: protocol::TSession<IO> + RolesOf,
+    <Body as RolesOf>::Roles: PrependRoles<<Cont as RolesOf>::Roles>,
+    One<Decider>: PrependRoles<WithRoles<<Body as RolesOf>::Roles, <Cont as RolesOf>::Roles>>,
+{
+    type Roles =
+        WithRoles<One<Decider>, WithRoles<<Body as RolesOf>::Roles, <Cont as RolesOf>::Roles>>;
+}
+impl<IO, Lbl: types::ProtocolLabel, R, Err> RolesOf for protocol::TThrow<IO, Lbl, R, Err> {
+    type Roles = One<R>;
+}
+impl<IO, Lbl, Body, Handler> RolesOf for protocol::TCatch<IO, Lbl, Body, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Body: protocol::TSession<IO> + RolesOf,
+    Handler: protocol::TSession<IO> + RolesOf,
+    <Body as RolesOf>::Roles: PrependRoles<<Handler as RolesOf>::Roles>,
+{
+    type Roles = WithRoles<<Body as RolesOf>::Roles, <Handler as RolesOf>::Roles>;
+}
+impl<IO, Lbl, High, Low> RolesOf for protocol::TPriorityChoice<IO, Lbl, High, Low>
+where
+    Lbl: types::ProtocolLabel,
+    High: protocol::TSession<IO> + RolesOf,
+    Low: protocol::TSession<IO> + RolesOf,
+    <High as RolesOf>::Roles: PrependRoles<<Low as RolesOf>::Roles>,
+{
+    type Roles = WithRoles<<High as RolesOf>::Roles, <Low as RolesOf>::Roles>;
+}
+impl<IO, Lbl, WeightL, L, WeightR, R> RolesOf for protocol::TProb<IO, Lbl, WeightL, L, WeightR, R>
+where
+    Lbl: types::ProtocolLabel,
+    WeightL: types::WeightMarker,
+    WeightR: types::WeightMarker,
+    L: protocol::TSession<IO> + RolesOf,
+    R: protocol::TSession<IO> + RolesOf,
+    <L as RolesOf>::Roles: PrependRoles<<R as RolesOf>::Roles>,
+{
+    type Roles = WithRoles<<L as RolesOf>::Roles, <R as RolesOf>::Roles>;
+}
+impl<Pred, Inner: RolesOf> RolesOf for protocol::TRefine<Pred, Inner> {
+    type Roles = <Inner as RolesOf>::Roles;
+}
+impl<Meta, Inner: RolesOf> RolesOf for protocol::TAnnotate<Meta, Inner> {
+    type Roles = <Inner as RolesOf>::Roles;
+}
+impl<IO, Lbl, Scope, Interruptor, Handler> RolesOf
+    for protocol::TInterrupt<IO, Lbl, Scope, Interruptor, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Scope: protocol::TSession<IO> + RolesOf,
+    Handler: protocol::TSession<IO> + RolesOf,
+    <Scope as RolesOf>::Roles: PrependRoles<<Handler as RolesOf>::Roles>,
+    One<Interruptor>:
+        PrependRoles<WithRoles<<Scope as RolesOf>::Roles, <Handler as RolesOf>::Roles>>,
+{
+    type Roles = WithRoles<
+        One<Interruptor>,
+        WithRoles<<Scope as RolesOf>::Roles, <Handler as RolesOf>::Roles>,
+    >;
+}
+impl<IO, Lbl, R, Dur, T> RolesOf for protocol::TDelay<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: protocol::TSession<IO> + RolesOf,
+    One<R>: PrependRoles<<T as RolesOf>::Roles>,
+{
+    type Roles = WithRoles<One<R>, <T as RolesOf>::Roles>;
+}
+impl<IO, Lbl, R, Dur, T> RolesOf for protocol::TDeadline<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: protocol::TSession<IO> + RolesOf,
+    One<R>: PrependRoles<<T as RolesOf>::Roles>,
+{
+    type Roles = WithRoles<One<R>, <T as RolesOf>::Roles>;
+}
+
+/// Extracts the roles used in a protocol as a duplicate-free type-level list.
+///
+/// - The [`RolesOf`] list, which already has each role once; the name states
+///   the set-style reading in bounds such as disjointness and coverage checks.
+/// - See also: [`Dedup`](crate::Dedup) for other lists.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Alice; struct Bob;
+/// impl Role for Alice {} impl Role for Bob {}
+/// impl RoleEq<Alice> for Alice { type Output = True; }
+/// impl RoleEq<Bob> for Alice   { type Output = False; }
+/// impl RoleEq<Alice> for Bob   { type Output = False; }
+/// impl RoleEq<Bob> for Bob     { type Output = True; }
+/// type PingPong = TMsg<Http, EmptyLabel, Alice, Bob, Message,
+///                 TMsg<Http, EmptyLabel, Bob, Alice, Response, TEnd<Http>>>;
+/// assert_type_eq!(<PingPong as UniqueRoles>::Roles, tlist!(Alice, Bob));
+/// ```
+pub trait UniqueRoles {
+    type Roles;
+}
+impl<G: RolesOf> UniqueRoles for G {
+    type Roles = <G as RolesOf>::Roles;
+}
+
+/// Extracts the communication graph of a protocol: who sends to whom.
+///
+/// - `Edges` is a type-level list of distinct `Channel<From, To>`s, in order
+///   of first use. A message or delegation uses one channel, a broadcast or
+///   anycast one per receiver; see [`ChannelsOf`](protocol::ChannelsOf).
+/// - The roles of a protocol must have `RoleEq` impls against each other.
+/// - [`ProtocolDesc::channels`](protocol::ProtocolDesc::channels) gives the
+///   same graph at runtime, with roles by name, such as to check firewall or
+///   service-mesh rules against a protocol.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Alice; struct Bob;
+/// impl Role for Alice {} impl Role for Bob {}
+/// impl RoleEq<Alice> for Alice { type Output = True; }
+/// impl RoleEq<Bob> for Alice   { type Output = False; }
+/// impl RoleEq<Alice> for Bob   { type Output = False; }
+/// impl RoleEq<Bob> for Bob     { type Output = True; }
+///
+/// type Chat = TMsg<Http, EmptyLabel, Alice, Bob, Message,
+///             TMsg<Http, EmptyLabel, Bob, Alice, Response,
+///             TMsg<Http, EmptyLabel, Alice, Bob, Message, TEnd<Http>>>>;
+/// assert_type_eq!(
+///     <Chat as CommunicationGraph>::Edges,
+///     tlist!(Channel<Alice, Bob>, Channel<Bob, Alice>)
+/// );
+/// assert_eq!(Chat::describe().channels().len(), 2);
+/// ```
+pub trait CommunicationGraph {
+    type Edges;
+}
+impl<G> CommunicationGraph for G
+where
+    G: protocol::ChannelsOf,
+    <G as protocol::ChannelsOf>::Channels: protocol::Dedup<protocol::ByChannel>,
+{
+    type Edges =
+        <<G as protocol::ChannelsOf>::Channels as protocol::Dedup<protocol::ByChannel>>::Output;
+}
 
 /// Extracts the set of protocol labels as a type-level list.
 ///
-/// - Implemented for all protocol combinators.
+/// - Implemented for all global combinators and local session types.
+/// - Both branches of binary combinators are included.
 /// - Used for uniqueness checks and macro expansion.
 /// - See also: [`UniqueList`], [`assert_unique_labels!`] macro.
 pub trait LabelsOf {
@@ -66,37 +379,5072 @@ impl<IO, Lbl: types::ProtocolLabel, R, H, T: protocol::TSession<IO> + LabelsOf>
 {
     type Labels = protocol::Cons<Lbl, <T as LabelsOf>::Labels>;
 }
+impl<IO, Lbl: types::ProtocolLabel, From, To, H, T: protocol::TSession<IO> + LabelsOf> LabelsOf
+    for protocol::TMsg<IO, Lbl, From, To, H, T>
+{
+    type Labels = protocol::Cons<Lbl, <T as LabelsOf>::Labels>;
+}
+impl<IO, Lbl: types::ProtocolLabel, From, WorkerSet, H, T: protocol::TSession<IO> + LabelsOf>
+    LabelsOf for protocol::TAnycast<IO, Lbl, From, WorkerSet, H, T>
+{
+    type Labels = protocol::Cons<Lbl, <T as LabelsOf>::Labels>;
+}
+impl<IO, Lbl: types::ProtocolLabel, A, B, Cont> LabelsOf
+    for protocol::TDisconnect<IO, Lbl, A, B, Cont>
+where
+    Cont: protocol::TSession<IO> + LabelsOf,
+{
+    type Labels = protocol::Cons<Lbl, <Cont as LabelsOf>::Labels>;
+}
+impl<IO, Lbl: types::ProtocolLabel, A, B, Cont> LabelsOf for protocol::TConnect<IO, Lbl, A, B, Cont>
+where
+    Cont: protocol::TSession<IO> + LabelsOf,
+{
+    type Labels = protocol::Cons<Lbl, <Cont as LabelsOf>::Labels>;
+}
+impl<IO, Lbl: types::ProtocolLabel, R, Cont> LabelsOf for protocol::TEndFor<IO, Lbl, R, Cont>
+where
+    Cont: protocol::TSession<IO> + LabelsOf,
+{
+    type Labels = protocol::Cons<Lbl, <Cont as LabelsOf>::Labels>;
+}
+impl<IO, Lbl: types::ProtocolLabel, From, To, DelegatedEp, T> LabelsOf
+    for protocol::TDelegate<IO, Lbl, From, To, DelegatedEp, T>
+where
+    T: protocol::TSession<IO> + LabelsOf,
+{
+    type Labels = protocol::Cons<Lbl, <T as LabelsOf>::Labels>;
+}
+impl<IO, Lbl: types::ProtocolLabel, From, ToSet, H, T: protocol::TSession<IO> + LabelsOf> LabelsOf
+    for protocol::TBroadcast<IO, Lbl, From, ToSet, H, T>
+{
+    type Labels = protocol::Cons<Lbl, <T as LabelsOf>::Labels>;
+}
+impl<IO, Lbl, L, R> LabelsOf for protocol::TChoice<IO, Lbl, L, R>
+where
+    Lbl: types::ProtocolLabel,
+    L: protocol::TSession<IO> + LabelsOf,
+    R: protocol::TSession<IO> + LabelsOf,
+    <L as LabelsOf>::Labels: protocol::Concat<<R as LabelsOf>::Labels>,
+{
+    type Labels = protocol::Cons<
+        Lbl,
+        <<L as LabelsOf>::Labels as protocol::Concat<<R as LabelsOf>::Labels>>::Output,
+    >;
+}
+impl<IO, Lbl: types::ProtocolLabel, Branches: protocol::SessionList<IO> + LabelsOfEach> LabelsOf
+    for protocol::TChoiceN<IO, Lbl, Branches>
+{
+    type Labels = protocol::Cons<Lbl, <Branches as LabelsOfEach>::Labels>;
+}
 impl<
         IO,
         Lbl: types::ProtocolLabel,
-        L: protocol::TSession<IO> + LabelsOf,
-        R: protocol::TSession<IO> + LabelsOf,
-    > LabelsOf for protocol::TChoice<IO, Lbl, L, R>
+        Chooser,
+        Branches: protocol::SessionList<IO> + LabelsOfEach,
+    > LabelsOf for protocol::TSelect<IO, Lbl, Chooser, Branches>
 {
-    type Labels = protocol::Cons<Lbl, <L as LabelsOf>::Labels>;
+    type Labels = protocol::Cons<Lbl, <Branches as LabelsOfEach>::Labels>;
 }
 impl<
         IO,
         Lbl: types::ProtocolLabel,
-        L: protocol::TSession<IO> + LabelsOf,
-        R: protocol::TSession<IO> + LabelsOf,
-        IsDisjoint,
-    > LabelsOf for protocol::TPar<IO, Lbl, L, R, IsDisjoint>
+        Offeree,
+        Branches: protocol::SessionList<IO> + LabelsOfEach,
+    > LabelsOf for protocol::TOffer<IO, Lbl, Offeree, Branches>
+{
+    type Labels = protocol::Cons<Lbl, <Branches as LabelsOfEach>::Labels>;
+}
+impl<IO, Lbl: types::ProtocolLabel, Branches: protocol::SessionList<IO> + LabelsOfEach> LabelsOf
+    for protocol::TParN<IO, Lbl, Branches>
 {
-    type Labels = protocol::Cons<Lbl, <L as LabelsOf>::Labels>;
+    type Labels = protocol::Cons<Lbl, <Branches as LabelsOfEach>::Labels>;
+}
+impl<IO, Lbl: types::ProtocolLabel, Steps: protocol::SessionList<IO> + LabelsOfEach> LabelsOf
+    for protocol::TShuffle<IO, Lbl, Steps>
+{
+    type Labels = protocol::Cons<Lbl, <Steps as LabelsOfEach>::Labels>;
+}
+impl<IO, Lbl, L, R, IsDisjoint> LabelsOf for protocol::TPar<IO, Lbl, L, R, IsDisjoint>
+where
+    Lbl: types::ProtocolLabel,
+    L: protocol::TSession<IO> + LabelsOf,
+    R: protocol::TSession<IO> + LabelsOf,
+    <L as LabelsOf>::Labels: protocol::Concat<<R as LabelsOf>::Labels>,
+{
+    type Labels = protocol::Cons<
+        Lbl,
+        <<L as LabelsOf>::Labels as protocol::Concat<<R as LabelsOf>::Labels>>::Output,
+    >;
 }
 impl<IO, Lbl: types::ProtocolLabel, S: protocol::TSession<IO> + LabelsOf> LabelsOf
     for protocol::TRec<IO, Lbl, S>
 {
     type Labels = protocol::Cons<Lbl, <S as LabelsOf>::Labels>;
 }
-impl LabelsOf for protocol::Nil {
+impl<IO, Lbl: types::ProtocolLabel, Var, Body: protocol::TSession<IO> + LabelsOf> LabelsOf
+    for protocol::TRecX<IO, Lbl, Var, Body>
+{
+    type Labels = protocol::Cons<Lbl, <Body as LabelsOf>::Labels>;
+}
+impl<Var> LabelsOf for protocol::TVar<Var> {
     type Labels = protocol::Nil;
 }
-impl<H, T> LabelsOf for protocol::Cons<H, T>
+impl<IO, Lbl, Dur, Body, OnTimeout> LabelsOf for protocol::TTimeout<IO, Lbl, Dur, Body, OnTimeout>
 where
-    H: LabelsOf,
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    Body: protocol::TSession<IO> + LabelsOf,
+    OnTimeout: protocol::TSession<IO> + LabelsOf,
+    <Body as LabelsOf>::Labels: protocol::Concat<<OnTimeout as LabelsOf>::Labels>,
+{
+    type Labels = protocol::Cons<
+        Lbl,
+        <<Body as LabelsOf>::Labels as protocol::Concat<<OnTimeout as LabelsOf>::Labels>>::Output,
+    >;
+}
+impl<IO, Lbl, Decider, Body, Cont> LabelsOf for protocol::TOptional<IO, Lbl, Decider, Body, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Body: protocol::TSession<IO> + LabelsOf,
+    Cont: protocol::TSession<IO> + LabelsOf,
+    <Body as LabelsOf>::Labels: protocol::Concat<<Cont as LabelsOf>::Labels>,
+{
+    type Labels = protocol::Cons<
+        Lbl,
+        <<Body as LabelsOf>::Labels as protocol::Concat<<Cont as LabelsOf>::Labels>>::Output,
+    >;
+}
+impl<IO, Lbl: types::ProtocolLabel, R, Err> LabelsOf for protocol::TThrow<IO, Lbl, R, Err> {
+    type Labels = protocol::Cons<Lbl, protocol::Nil>;
+}
+impl<IO, Lbl, Body, Handler> LabelsOf for protocol::TCatch<IO, Lbl, Body, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Body: protocol::TSession<IO> + LabelsOf,
+    Handler: protocol::TSession<IO> + LabelsOf,
+    <Body as LabelsOf>::Labels: protocol::Concat<<Handler as LabelsOf>::Labels>,
+{
+    type Labels = protocol::Cons<
+        Lbl,
+        <<Body as LabelsOf>::Labels as protocol::Concat<<Handler as LabelsOf>::Labels>>::Output,
+    >;
+}
+impl<IO, Lbl, High, Low> LabelsOf for protocol::TPriorityChoice<IO, Lbl, High, Low>
+where
+    Lbl: types::ProtocolLabel,
+    High: protocol::TSession<IO> + LabelsOf,
+    Low: protocol::TSession<IO> + LabelsOf,
+    <High as LabelsOf>::Labels: protocol::Concat<<Low as LabelsOf>::Labels>,
+{
+    type Labels = protocol::Cons<
+        Lbl,
+        <<High as LabelsOf>::Labels as protocol::Concat<<Low as LabelsOf>::Labels>>::Output,
+    >;
+}
+impl<IO, Lbl, WeightL, L, WeightR, R> LabelsOf for protocol::TProb<IO, Lbl, WeightL, L, WeightR, R>
+where
+    Lbl: types::ProtocolLabel,
+    WeightL: types::WeightMarker,
+    WeightR: types::WeightMarker,
+    L: protocol::TSession<IO> + LabelsOf,
+    R: protocol::TSession<IO> + LabelsOf,
+    <L as LabelsOf>::Labels: protocol::Concat<<R as LabelsOf>::Labels>,
+{
+    type Labels = protocol::Cons<
+        Lbl,
+        <<L as LabelsOf>::Labels as protocol::Concat<<R as LabelsOf>::Labels>>::Output,
+    >;
+}
+impl<Pred, Inner: LabelsOf> LabelsOf for protocol::TRefine<Pred, Inner> {
+    type Labels = <Inner as LabelsOf>::Labels;
+}
+impl<Meta, Inner: LabelsOf> LabelsOf for protocol::TAnnotate<Meta, Inner> {
+    type Labels = <Inner as LabelsOf>::Labels;
+}
+impl<IO, Lbl, Scope, Interruptor, Handler> LabelsOf
+    for protocol::TInterrupt<IO, Lbl, Scope, Interruptor, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Scope: protocol::TSession<IO> + LabelsOf,
+    Handler: protocol::TSession<IO> + LabelsOf,
+    <Scope as LabelsOf>::Labels: protocol::Concat<<Handler as LabelsOf>::Labels>,
+{
+    type Labels = protocol::Cons<
+        Lbl,
+        <<Scope as LabelsOf>::Labels as protocol::Concat<<Handler as LabelsOf>::Labels>>::Output,
+    >;
+}
+impl<IO, Lbl, R, Dur, T> LabelsOf for protocol::TDelay<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: protocol::TSession<IO> + LabelsOf,
+{
+    type Labels = protocol::Cons<Lbl, <T as LabelsOf>::Labels>;
+}
+impl<IO, Lbl, R, Dur, T> LabelsOf for protocol::TDeadline<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: protocol::TSession<IO> + LabelsOf,
+{
+    type Labels = protocol::Cons<Lbl, <T as LabelsOf>::Labels>;
+}
+// Local session types list their labels like the global combinators they
+// are projected from
+impl<IO, Lbl, R> LabelsOf for protocol::EpEnd<IO, Lbl, R>
+where
+    Lbl: types::ProtocolLabel,
+{
+    type Labels = protocol::Cons<Lbl, protocol::Nil>;
+}
+impl<IO, Lbl, R> LabelsOf for protocol::EpSkip<IO, Lbl, R>
+where
+    Lbl: types::ProtocolLabel,
+{
+    type Labels = protocol::Cons<Lbl, protocol::Nil>;
+}
+impl<IO, Lbl, R, H, T> LabelsOf for protocol::EpSend<IO, Lbl, R, H, T>
+where
+    Lbl: types::ProtocolLabel,
     T: LabelsOf,
 {
-    type Labels = <H as LabelsOf>::Labels;
+    type Labels = protocol::Cons<Lbl, <T as LabelsOf>::Labels>;
+}
+impl<IO, Lbl, R, H, T> LabelsOf for protocol::EpRecv<IO, Lbl, R, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: LabelsOf,
+{
+    type Labels = protocol::Cons<Lbl, <T as LabelsOf>::Labels>;
+}
+impl<IO, Lbl, Me, ToSet, H, T> LabelsOf for protocol::EpBroadcast<IO, Lbl, Me, ToSet, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: LabelsOf,
+{
+    type Labels = protocol::Cons<Lbl, <T as LabelsOf>::Labels>;
+}
+impl<IO, Lbl, Me, FromSet, H, T> LabelsOf for protocol::EpGather<IO, Lbl, Me, FromSet, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: LabelsOf,
+{
+    type Labels = protocol::Cons<Lbl, <T as LabelsOf>::Labels>;
+}
+impl<IO, Lbl, Me, DelegatedEp, Cont> LabelsOf
+    for protocol::EpDelegate<IO, Lbl, Me, DelegatedEp, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Cont: LabelsOf,
+{
+    type Labels = protocol::Cons<Lbl, <Cont as LabelsOf>::Labels>;
+}
+impl<IO, Lbl, Me, Branches> LabelsOf for protocol::EpChoiceN<IO, Lbl, Me, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: LabelsOfEach,
+{
+    type Labels = protocol::Cons<Lbl, <Branches as LabelsOfEach>::Labels>;
+}
+impl<IO, Lbl, Me, Branches> LabelsOf for protocol::EpSelect<IO, Lbl, Me, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: LabelsOfEach,
+{
+    type Labels = protocol::Cons<Lbl, <Branches as LabelsOfEach>::Labels>;
+}
+impl<IO, Lbl, Me, Branches> LabelsOf for protocol::EpOffer<IO, Lbl, Me, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: LabelsOfEach,
+{
+    type Labels = protocol::Cons<Lbl, <Branches as LabelsOfEach>::Labels>;
+}
+impl<IO, Lbl, Me, L, R> LabelsOf for protocol::EpPar<IO, Lbl, Me, L, R>
+where
+    Lbl: types::ProtocolLabel,
+    L: LabelsOf,
+    R: LabelsOf,
+    <L as LabelsOf>::Labels: protocol::Concat<<R as LabelsOf>::Labels>,
+{
+    type Labels = protocol::Cons<
+        Lbl,
+        <<L as LabelsOf>::Labels as protocol::Concat<<R as LabelsOf>::Labels>>::Output,
+    >;
+}
+impl<IO, Lbl, Me, Branches> LabelsOf for protocol::EpParN<IO, Lbl, Me, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: LabelsOfEach,
+{
+    type Labels = protocol::Cons<Lbl, <Branches as LabelsOfEach>::Labels>;
+}
+impl<IO, Lbl, Me, Steps> LabelsOf for protocol::EpShuffle<IO, Lbl, Me, Steps>
+where
+    Lbl: types::ProtocolLabel,
+    Steps: LabelsOfEach,
+{
+    type Labels = protocol::Cons<Lbl, <Steps as LabelsOfEach>::Labels>;
+}
+impl<IO, Lbl, Var, Body> LabelsOf for protocol::EpRec<IO, Lbl, Var, Body>
+where
+    Lbl: types::ProtocolLabel,
+    Body: LabelsOf,
+{
+    type Labels = protocol::Cons<Lbl, <Body as LabelsOf>::Labels>;
+}
+impl<Var> LabelsOf for protocol::EpVar<Var> {
+    type Labels = protocol::Nil;
+}
+impl<IO, Lbl, Me, Dur, Body, OnTimeout> LabelsOf
+    for protocol::EpTimeout<IO, Lbl, Me, Dur, Body, OnTimeout>
+where
+    Lbl: types::ProtocolLabel,
+    Body: LabelsOf,
+    OnTimeout: LabelsOf,
+    <Body as LabelsOf>::Labels: protocol::Concat<<OnTimeout as LabelsOf>::Labels>,
+{
+    type Labels = protocol::Cons<
+        Lbl,
+        <<Body as LabelsOf>::Labels as protocol::Concat<<OnTimeout as LabelsOf>::Labels>>::Output,
+    >;
+}
+impl<IO, Lbl, Me, Dur, T> LabelsOf for protocol::EpDelay<IO, Lbl, Me, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: LabelsOf,
+{
+    type Labels = protocol::Cons<Lbl, <T as LabelsOf>::Labels>;
+}
+impl<IO, Lbl, Me, Dur, T> LabelsOf for protocol::EpDeadline<IO, Lbl, Me, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: LabelsOf,
+{
+    type Labels = protocol::Cons<Lbl, <T as LabelsOf>::Labels>;
+}
+impl<IO, Lbl, Me, Scope, Handler> LabelsOf for protocol::EpInterrupt<IO, Lbl, Me, Scope, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Scope: LabelsOf,
+    Handler: LabelsOf,
+    <Scope as LabelsOf>::Labels: protocol::Concat<<Handler as LabelsOf>::Labels>,
+{
+    type Labels = protocol::Cons<
+        Lbl,
+        <<Scope as LabelsOf>::Labels as protocol::Concat<<Handler as LabelsOf>::Labels>>::Output,
+    >;
+}
+impl<IO, Lbl, Me, Scope, Handler> LabelsOf
+    for protocol::EpInterruptible<IO, Lbl, Me, Scope, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Scope: LabelsOf,
+    Handler: LabelsOf,
+    <Scope as LabelsOf>::Labels: protocol::Concat<<Handler as LabelsOf>::Labels>,
+{
+    type Labels = protocol::Cons<
+        Lbl,
+        <<Scope as LabelsOf>::Labels as protocol::Concat<<Handler as LabelsOf>::Labels>>::Output,
+    >;
+}
+impl<Pred, Inner: LabelsOf> LabelsOf for protocol::EpRefine<Pred, Inner> {
+    type Labels = <Inner as LabelsOf>::Labels;
+}
+impl<IO, Lbl, Me, Body, Cont> LabelsOf for protocol::EpOptionalSelect<IO, Lbl, Me, Body, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Body: LabelsOf,
+    Cont: LabelsOf,
+    <Body as LabelsOf>::Labels: protocol::Concat<<Cont as LabelsOf>::Labels>,
+{
+    type Labels = protocol::Cons<
+        Lbl,
+        <<Body as LabelsOf>::Labels as protocol::Concat<<Cont as LabelsOf>::Labels>>::Output,
+    >;
+}
+impl<IO, Lbl, Me, Body, Cont> LabelsOf for protocol::EpOptionalOffer<IO, Lbl, Me, Body, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Body: LabelsOf,
+    Cont: LabelsOf,
+    <Body as LabelsOf>::Labels: protocol::Concat<<Cont as LabelsOf>::Labels>,
+{
+    type Labels = protocol::Cons<
+        Lbl,
+        <<Body as LabelsOf>::Labels as protocol::Concat<<Cont as LabelsOf>::Labels>>::Output,
+    >;
+}
+impl<IO, Lbl, Me, Err> LabelsOf for protocol::EpThrow<IO, Lbl, Me, Err>
+where
+    Lbl: types::ProtocolLabel,
+{
+    type Labels = protocol::Cons<Lbl, protocol::Nil>;
+}
+impl<IO, Lbl, Me, Body, Handler> LabelsOf for protocol::EpCatch<IO, Lbl, Me, Body, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Body: LabelsOf,
+    Handler: LabelsOf,
+    <Body as LabelsOf>::Labels: protocol::Concat<<Handler as LabelsOf>::Labels>,
+{
+    type Labels = protocol::Cons<
+        Lbl,
+        <<Body as LabelsOf>::Labels as protocol::Concat<<Handler as LabelsOf>::Labels>>::Output,
+    >;
+}
+impl<IO, Lbl, Me, High, Low> LabelsOf for protocol::EpPriorityOffer<IO, Lbl, Me, High, Low>
+where
+    Lbl: types::ProtocolLabel,
+    High: LabelsOf,
+    Low: LabelsOf,
+    <High as LabelsOf>::Labels: protocol::Concat<<Low as LabelsOf>::Labels>,
+{
+    type Labels = protocol::Cons<
+        Lbl,
+        <<High as LabelsOf>::Labels as protocol::Concat<<Low as LabelsOf>::Labels>>::Output,
+    >;
+}
+impl<IO, Lbl, Me, Peer, Cont> LabelsOf for protocol::EpClose<IO, Lbl, Me, Peer, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Cont: LabelsOf,
+{
+    type Labels = protocol::Cons<Lbl, <Cont as LabelsOf>::Labels>;
+}
+impl<IO, Lbl, Me, Peer, Cont> LabelsOf for protocol::EpOpen<IO, Lbl, Me, Peer, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Cont: LabelsOf,
+{
+    type Labels = protocol::Cons<Lbl, <Cont as LabelsOf>::Labels>;
+}
+impl LabelsOf for protocol::Nil {
+    type Labels = protocol::Nil;
+}
+impl<H, T> LabelsOf for protocol::Cons<H, T>
+where
+    H: LabelsOf,
+    T: LabelsOf,
+{
+    type Labels = <H as LabelsOf>::Labels;
+}
+
+/// Extracts the labels of the steps of a protocol as a type-level list.
+///
+/// - Like [`LabelsOf`], but the labels of `TEnd`, `EpEnd` and `EpSkip` are left
+///   out: an end is not a step, and every branch that finishes names one.
+/// - Used for label uniqueness. See also: [`UniqueList`], [`assert_unique_labels!`].
+pub trait StepLabelsOf {
+    type Labels;
+}
+impl<IO, Lbl> StepLabelsOf for protocol::TEnd<IO, Lbl> {
+    type Labels = protocol::Nil;
+}
+impl<IO, Lbl: types::ProtocolLabel, R, H, T: protocol::TSession<IO> + StepLabelsOf> StepLabelsOf
+    for protocol::TInteract<IO, Lbl, R, H, T>
+{
+    type Labels = protocol::Cons<Lbl, <T as StepLabelsOf>::Labels>;
+}
+impl<IO, Lbl: types::ProtocolLabel, From, To, H, T: protocol::TSession<IO> + StepLabelsOf>
+    StepLabelsOf for protocol::TMsg<IO, Lbl, From, To, H, T>
+{
+    type Labels = protocol::Cons<Lbl, <T as StepLabelsOf>::Labels>;
+}
+impl<
+        IO,
+        Lbl: types::ProtocolLabel,
+        From,
+        WorkerSet,
+        H,
+        T: protocol::TSession<IO> + StepLabelsOf,
+    > StepLabelsOf for protocol::TAnycast<IO, Lbl, From, WorkerSet, H, T>
+{
+    type Labels = protocol::Cons<Lbl, <T as StepLabelsOf>::Labels>;
+}
+impl<IO, Lbl: types::ProtocolLabel, A, B, Cont> StepLabelsOf
+    for protocol::TDisconnect<IO, Lbl, A, B, Cont>
+where
+    Cont: protocol::TSession<IO> + StepLabelsOf,
+{
+    type Labels = protocol::Cons<Lbl, <Cont as StepLabelsOf>::Labels>;
+}
+impl<IO, Lbl: types::ProtocolLabel, A, B, Cont> StepLabelsOf
+    for protocol::TConnect<IO, Lbl, A, B, Cont>
+where
+    Cont: protocol::TSession<IO> + StepLabelsOf,
+{
+    type Labels = protocol::Cons<Lbl, <Cont as StepLabelsOf>::Labels>;
+}
+impl<IO, Lbl: types::ProtocolLabel, R, Cont> StepLabelsOf for protocol::TEndFor<IO, Lbl, R, Cont>
+where
+    Cont: protocol::TSession<IO> + StepLabelsOf,
+{
+    type Labels = protocol::Cons<Lbl, <Cont as StepLabelsOf>::Labels>;
+}
+impl<IO, Lbl: types::ProtocolLabel, From, To, DelegatedEp, T> StepLabelsOf
+    for protocol::TDelegate<IO, Lbl, From, To, DelegatedEp, T>
+where
+    T: protocol::TSession<IO> + StepLabelsOf,
+{
+    type Labels = protocol::Cons<Lbl, <T as StepLabelsOf>::Labels>;
+}
+impl<IO, Lbl: types::ProtocolLabel, From, ToSet, H, T: protocol::TSession<IO> + StepLabelsOf>
+    StepLabelsOf for protocol::TBroadcast<IO, Lbl, From, ToSet, H, T>
+{
+    type Labels = protocol::Cons<Lbl, <T as StepLabelsOf>::Labels>;
+}
+impl<IO, Lbl, L, R> StepLabelsOf for protocol::TChoice<IO, Lbl, L, R>
+where
+    Lbl: types::ProtocolLabel,
+    L: protocol::TSession<IO> + StepLabelsOf,
+    R: protocol::TSession<IO> + StepLabelsOf,
+    <L as StepLabelsOf>::Labels: protocol::Concat<<R as StepLabelsOf>::Labels>,
+{
+    type Labels = protocol::Cons<
+        Lbl,
+        <<L as StepLabelsOf>::Labels as protocol::Concat<<R as StepLabelsOf>::Labels>>::Output,
+    >;
+}
+impl<IO, Lbl: types::ProtocolLabel, Branches: protocol::SessionList<IO> + StepLabelsOfEach>
+    StepLabelsOf for protocol::TChoiceN<IO, Lbl, Branches>
+{
+    type Labels = protocol::Cons<Lbl, <Branches as StepLabelsOfEach>::Labels>;
+}
+impl<
+        IO,
+        Lbl: types::ProtocolLabel,
+        Chooser,
+        Branches: protocol::SessionList<IO> + StepLabelsOfEach,
+    > StepLabelsOf for protocol::TSelect<IO, Lbl, Chooser, Branches>
+{
+    type Labels = protocol::Cons<Lbl, <Branches as StepLabelsOfEach>::Labels>;
+}
+impl<
+        IO,
+        Lbl: types::ProtocolLabel,
+        Offeree,
+        Branches: protocol::SessionList<IO> + StepLabelsOfEach,
+    > StepLabelsOf for protocol::TOffer<IO, Lbl, Offeree, Branches>
+{
+    type Labels = protocol::Cons<Lbl, <Branches as StepLabelsOfEach>::Labels>;
+}
+impl<IO, Lbl: types::ProtocolLabel, Branches: protocol::SessionList<IO> + StepLabelsOfEach>
+    StepLabelsOf for protocol::TParN<IO, Lbl, Branches>
+{
+    type Labels = protocol::Cons<Lbl, <Branches as StepLabelsOfEach>::Labels>;
+}
+impl<IO, Lbl: types::ProtocolLabel, Steps: protocol::SessionList<IO> + StepLabelsOfEach>
+    StepLabelsOf for protocol::TShuffle<IO, Lbl, Steps>
+{
+    type Labels = protocol::Cons<Lbl, <Steps as StepLabelsOfEach>::Labels>;
+}
+impl<IO, Lbl, L, R, IsDisjoint> StepLabelsOf for protocol::TPar<IO, Lbl, L, R, IsDisjoint>
+where
+    Lbl: types::ProtocolLabel,
+    L: protocol::TSession<IO> + StepLabelsOf,
+    R: protocol::TSession<IO> + StepLabelsOf,
+    <L as StepLabelsOf>::Labels: protocol::Concat<<R as StepLabelsOf>::Labels>,
+{
+    type Labels = protocol::Cons<
+        Lbl,
+        <<L as StepLabelsOf>::Labels as protocol::Concat<<R as StepLabelsOf>::Labels>>::Output,
+    >;
+}
+impl<IO, Lbl: types::ProtocolLabel, S: protocol::TSession<IO> + StepLabelsOf> StepLabelsOf
+    for protocol::TRec<IO, Lbl, S>
+{
+    type Labels = protocol::Cons<Lbl, <S as StepLabelsOf>::Labels>;
+}
+impl<IO, Lbl: types::ProtocolLabel, Var, Body: protocol::TSession<IO> + StepLabelsOf> StepLabelsOf
+    for protocol::TRecX<IO, Lbl, Var, Body>
+{
+    type Labels = protocol::Cons<Lbl, <Body as StepLabelsOf>::Labels>;
+}
+impl<Var> StepLabelsOf for protocol::TVar<Var> {
+    type Labels = protocol::Nil;
+}
+impl<IO, Lbl, Dur, Body, OnTimeout> StepLabelsOf
+    for protocol::TTimeout<IO, Lbl, Dur, Body, OnTimeout>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    Body: protocol::TSession<IO> + StepLabelsOf,
+    OnTimeout: protocol::TSession<IO> + StepLabelsOf,
+    <Body as StepLabelsOf>::Labels: protocol::Concat<<OnTimeout as StepLabelsOf>::Labels>,
+{
+    type Labels =
+        protocol::Cons<
+            Lbl,
+            <<Body as StepLabelsOf>::Labels as protocol::Concat<
+                <OnTimeout as StepLabelsOf>::Labels,
+            >>::Output,
+        >;
+}
+impl<IO, Lbl, Decider, Body, Cont> StepLabelsOf
+    for protocol::TOptional<IO, Lbl, Decider, Body, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Body: protocol::TSession<IO> + StepLabelsOf,
+    Cont: protocol::TSession<IO> + StepLabelsOf,
+    <Body as StepLabelsOf>::Labels: protocol::Concat<<Cont as StepLabelsOf>::Labels>,
+{
+    type Labels = protocol::Cons<
+        Lbl,
+        <<Body as StepLabelsOf>::Labels as protocol::Concat<<Cont as StepLabelsOf>::Labels>>::Output,
+    >;
+}
+impl<IO, Lbl: types::ProtocolLabel, R, Err> StepLabelsOf for protocol::TThrow<IO, Lbl, R, Err> {
+    type Labels = protocol::Cons<Lbl, protocol::Nil>;
+}
+impl<IO, Lbl, Body, Handler> StepLabelsOf for protocol::TCatch<IO, Lbl, Body, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Body: protocol::TSession<IO> + StepLabelsOf,
+    Handler: protocol::TSession<IO> + StepLabelsOf,
+    <Body as StepLabelsOf>::Labels: protocol::Concat<<Handler as StepLabelsOf>::Labels>,
+{
+    type Labels =
+        protocol::Cons<
+            Lbl,
+            <<Body as StepLabelsOf>::Labels as protocol::Concat<
+                <Handler as StepLabelsOf>::Labels,
+            >>::Output,
+        >;
+}
+impl<IO, Lbl, High, Low> StepLabelsOf for protocol::TPriorityChoice<IO, Lbl, High, Low>
+where
+    Lbl: types::ProtocolLabel,
+    High: protocol::TSession<IO> + StepLabelsOf,
+    Low: protocol::TSession<IO> + StepLabelsOf,
+    <High as StepLabelsOf>::Labels: protocol::Concat<<Low as StepLabelsOf>::Labels>,
+{
+    type Labels = protocol::Cons<
+        Lbl,
+        <<High as StepLabelsOf>::Labels as protocol::Concat<<Low as StepLabelsOf>::Labels>>::Output,
+    >;
+}
+impl<IO, Lbl, WeightL, L, WeightR, R> StepLabelsOf
+    for protocol::TProb<IO, Lbl, WeightL, L, WeightR, R>
+where
+    Lbl: types::ProtocolLabel,
+    WeightL: types::WeightMarker,
+    WeightR: types::WeightMarker,
+    L: protocol::TSession<IO> + StepLabelsOf,
+    R: protocol::TSession<IO> + StepLabelsOf,
+    <L as StepLabelsOf>::Labels: protocol::Concat<<R as StepLabelsOf>::Labels>,
+{
+    type Labels = protocol::Cons<
+        Lbl,
+        <<L as StepLabelsOf>::Labels as protocol::Concat<<R as StepLabelsOf>::Labels>>::Output,
+    >;
+}
+impl<Pred, Inner: StepLabelsOf> StepLabelsOf for protocol::TRefine<Pred, Inner> {
+    type Labels = <Inner as StepLabelsOf>::Labels;
+}
+impl<Meta, Inner: StepLabelsOf> StepLabelsOf for protocol::TAnnotate<Meta, Inner> {
+    type Labels = <Inner as StepLabelsOf>::Labels;
+}
+impl<IO, Lbl, Scope, Interruptor, Handler> StepLabelsOf
+    for protocol::TInterrupt<IO, Lbl, Scope, Interruptor, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Scope: protocol::TSession<IO> + StepLabelsOf,
+    Handler: protocol::TSession<IO> + StepLabelsOf,
+    <Scope as StepLabelsOf>::Labels: protocol::Concat<<Handler as StepLabelsOf>::Labels>,
+{
+    type Labels =
+        protocol::Cons<
+            Lbl,
+            <<Scope as StepLabelsOf>::Labels as protocol::Concat<
+                <Handler as StepLabelsOf>::Labels,
+            >>::Output,
+        >;
+}
+impl<IO, Lbl, R, Dur, T> StepLabelsOf for protocol::TDelay<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: protocol::TSession<IO> + StepLabelsOf,
+{
+    type Labels = protocol::Cons<Lbl, <T as StepLabelsOf>::Labels>;
+}
+impl<IO, Lbl, R, Dur, T> StepLabelsOf for protocol::TDeadline<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: protocol::TSession<IO> + StepLabelsOf,
+{
+    type Labels = protocol::Cons<Lbl, <T as StepLabelsOf>::Labels>;
+}
+impl<IO, Lbl, R> StepLabelsOf for protocol::EpEnd<IO, Lbl, R>
+where
+    Lbl: types::ProtocolLabel,
+{
+    type Labels = protocol::Nil;
+}
+impl<IO, Lbl, R> StepLabelsOf for protocol::EpSkip<IO, Lbl, R>
+where
+    Lbl: types::ProtocolLabel,
+{
+    type Labels = protocol::Nil;
+}
+impl<IO, Lbl, R, H, T> StepLabelsOf for protocol::EpSend<IO, Lbl, R, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: StepLabelsOf,
+{
+    type Labels = protocol::Cons<Lbl, <T as StepLabelsOf>::Labels>;
+}
+impl<IO, Lbl, R, H, T> StepLabelsOf for protocol::EpRecv<IO, Lbl, R, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: StepLabelsOf,
+{
+    type Labels = protocol::Cons<Lbl, <T as StepLabelsOf>::Labels>;
+}
+impl<IO, Lbl, Me, ToSet, H, T> StepLabelsOf for protocol::EpBroadcast<IO, Lbl, Me, ToSet, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: StepLabelsOf,
+{
+    type Labels = protocol::Cons<Lbl, <T as StepLabelsOf>::Labels>;
+}
+impl<IO, Lbl, Me, FromSet, H, T> StepLabelsOf for protocol::EpGather<IO, Lbl, Me, FromSet, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: StepLabelsOf,
+{
+    type Labels = protocol::Cons<Lbl, <T as StepLabelsOf>::Labels>;
+}
+impl<IO, Lbl, Me, DelegatedEp, Cont> StepLabelsOf
+    for protocol::EpDelegate<IO, Lbl, Me, DelegatedEp, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Cont: StepLabelsOf,
+{
+    type Labels = protocol::Cons<Lbl, <Cont as StepLabelsOf>::Labels>;
+}
+impl<IO, Lbl, Me, Branches> StepLabelsOf for protocol::EpChoiceN<IO, Lbl, Me, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: StepLabelsOfEach,
+{
+    type Labels = protocol::Cons<Lbl, <Branches as StepLabelsOfEach>::Labels>;
+}
+impl<IO, Lbl, Me, Branches> StepLabelsOf for protocol::EpSelect<IO, Lbl, Me, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: StepLabelsOfEach,
+{
+    type Labels = protocol::Cons<Lbl, <Branches as StepLabelsOfEach>::Labels>;
+}
+impl<IO, Lbl, Me, Branches> StepLabelsOf for protocol::EpOffer<IO, Lbl, Me, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: StepLabelsOfEach,
+{
+    type Labels = protocol::Cons<Lbl, <Branches as StepLabelsOfEach>::Labels>;
+}
+impl<IO, Lbl, Me, L, R> StepLabelsOf for protocol::EpPar<IO, Lbl, Me, L, R>
+where
+    Lbl: types::ProtocolLabel,
+    L: StepLabelsOf,
+    R: StepLabelsOf,
+    <L as StepLabelsOf>::Labels: protocol::Concat<<R as StepLabelsOf>::Labels>,
+{
+    type Labels = protocol::Cons<
+        Lbl,
+        <<L as StepLabelsOf>::Labels as protocol::Concat<<R as StepLabelsOf>::Labels>>::Output,
+    >;
+}
+impl<IO, Lbl, Me, Branches> StepLabelsOf for protocol::EpParN<IO, Lbl, Me, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: StepLabelsOfEach,
+{
+    type Labels = protocol::Cons<Lbl, <Branches as StepLabelsOfEach>::Labels>;
+}
+impl<IO, Lbl, Me, Steps> StepLabelsOf for protocol::EpShuffle<IO, Lbl, Me, Steps>
+where
+    Lbl: types::ProtocolLabel,
+    Steps: StepLabelsOfEach,
+{
+    type Labels = protocol::Cons<Lbl, <Steps as StepLabelsOfEach>::Labels>;
+}
+impl<IO, Lbl, Var, Body> StepLabelsOf for protocol::EpRec<IO, Lbl, Var, Body>
+where
+    Lbl: types::ProtocolLabel,
+    Body: StepLabelsOf,
+{
+    type Labels = protocol::Cons<Lbl, <Body as StepLabelsOf>::Labels>;
+}
+impl<Var> StepLabelsOf for protocol::EpVar<Var> {
+    type Labels = protocol::Nil;
+}
+impl<IO, Lbl, Me, Dur, Body, OnTimeout> StepLabelsOf
+    for protocol::EpTimeout<IO, Lbl, Me, Dur, Body, OnTimeout>
+where
+    Lbl: types::ProtocolLabel,
+    Body: StepLabelsOf,
+    OnTimeout: StepLabelsOf,
+    <Body as StepLabelsOf>::Labels: protocol::Concat<<OnTimeout as StepLabelsOf>::Labels>,
+{
+    type Labels =
+        protocol::Cons<
+            Lbl,
+            <<Body as StepLabelsOf>::Labels as protocol::Concat<
+                <OnTimeout as StepLabelsOf>::Labels,
+            >>::Output,
+        >;
+}
+impl<IO, Lbl, Me, Dur, T> StepLabelsOf for protocol::EpDelay<IO, Lbl, Me, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: StepLabelsOf,
+{
+    type Labels = protocol::Cons<Lbl, <T as StepLabelsOf>::Labels>;
+}
+impl<IO, Lbl, Me, Dur, T> StepLabelsOf for protocol::EpDeadline<IO, Lbl, Me, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: StepLabelsOf,
+{
+    type Labels = protocol::Cons<Lbl, <T as StepLabelsOf>::Labels>;
+}
+impl<IO, Lbl, Me, Scope, Handler> StepLabelsOf
+    for protocol::EpInterrupt<IO, Lbl, Me, Scope, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Scope: StepLabelsOf,
+    Handler: StepLabelsOf,
+    <Scope as StepLabelsOf>::Labels: protocol::Concat<<Handler as StepLabelsOf>::Labels>,
+{
+    type Labels =
+        protocol::Cons<
+            Lbl,
+            <<Scope as StepLabelsOf>::Labels as protocol::Concat<
+                <Handler as StepLabelsOf>::Labels,
+            >>::Output,
+        >;
+}
+impl<IO, Lbl, Me, Scope, Handler> StepLabelsOf
+    for protocol::EpInterruptible<IO, Lbl, Me, Scope, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Scope: StepLabelsOf,
+    Handler: StepLabelsOf,
+    <Scope as StepLabelsOf>::Labels: protocol::Concat<<Handler as StepLabelsOf>::Labels>,
+{
+    type Labels =
+        protocol::Cons<
+            Lbl,
+            <<Scope as StepLabelsOf>::Labels as protocol::Concat<
+                <Handler as StepLabelsOf>::Labels,
+            >>::Output,
+        >;
+}
+impl<Pred, Inner: StepLabelsOf> StepLabelsOf for protocol::EpRefine<Pred, Inner> {
+    type Labels = <Inner as StepLabelsOf>::Labels;
+}
+impl<IO, Lbl, Me, Body, Cont> StepLabelsOf for protocol::EpOptionalSelect<IO, Lbl, Me, Body, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Body: StepLabelsOf,
+    Cont: StepLabelsOf,
+    <Body as StepLabelsOf>::Labels: protocol::Concat<<Cont as StepLabelsOf>::Labels>,
+{
+    type Labels = protocol::Cons<
+        Lbl,
+        <<Body as StepLabelsOf>::Labels as protocol::Concat<<Cont as StepLabelsOf>::Labels>>::Output,
+    >;
+}
+impl<IO, Lbl, Me, Body, Cont> StepLabelsOf for protocol::EpOptionalOffer<IO, Lbl, Me, Body, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Body: StepLabelsOf,
+    Cont: StepLabelsOf,
+    <Body as StepLabelsOf>::Labels: protocol::Concat<<Cont as StepLabelsOf>::Labels>,
+{
+    type Labels = protocol::Cons<
+        Lbl,
+        <<Body as StepLabelsOf>::Labels as protocol::Concat<<Cont as StepLabelsOf>::Labels>>::Output,
+    >;
+}
+impl<IO, Lbl, Me, Err> StepLabelsOf for protocol::EpThrow<IO, Lbl, Me, Err>
+where
+    Lbl: types::ProtocolLabel,
+{
+    type Labels = protocol::Cons<Lbl, protocol::Nil>;
+}
+impl<IO, Lbl, Me, Body, Handler> StepLabelsOf for protocol::EpCatch<IO, Lbl, Me, Body, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Body: StepLabelsOf,
+    Handler: StepLabelsOf,
+    <Body as StepLabelsOf>::Labels: protocol::Concat<<Handler as StepLabelsOf>::Labels>,
+{
+    type Labels =
+        protocol::Cons<
+            Lbl,
+            <<Body as StepLabelsOf>::Labels as protocol::Concat<
+                <Handler as StepLabelsOf>::Labels,
+            >>::Output,
+        >;
+}
+impl<IO, Lbl, Me, High, Low> StepLabelsOf for protocol::EpPriorityOffer<IO, Lbl, Me, High, Low>
+where
+    Lbl: types::ProtocolLabel,
+    High: StepLabelsOf,
+    Low: StepLabelsOf,
+    <High as StepLabelsOf>::Labels: protocol::Concat<<Low as StepLabelsOf>::Labels>,
+{
+    type Labels = protocol::Cons<
+        Lbl,
+        <<High as StepLabelsOf>::Labels as protocol::Concat<<Low as StepLabelsOf>::Labels>>::Output,
+    >;
+}
+impl<IO, Lbl, Me, Peer, Cont> StepLabelsOf for protocol::EpClose<IO, Lbl, Me, Peer, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Cont: StepLabelsOf,
+{
+    type Labels = protocol::Cons<Lbl, <Cont as StepLabelsOf>::Labels>;
+}
+impl<IO, Lbl, Me, Peer, Cont> StepLabelsOf for protocol::EpOpen<IO, Lbl, Me, Peer, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Cont: StepLabelsOf,
+{
+    type Labels = protocol::Cons<Lbl, <Cont as StepLabelsOf>::Labels>;
+}
+impl StepLabelsOf for protocol::Nil {
+    type Labels = protocol::Nil;
+}
+impl<H, T> StepLabelsOf for protocol::Cons<H, T>
+where
+    H: StepLabelsOf,
+    T: StepLabelsOf,
+{
+    type Labels = <H as StepLabelsOf>::Labels;
+}
+
+/// Extracts the message types a protocol sends as a type-level list.
+///
+/// - Lists the payload of every message, broadcast and anycast, and the error
+///   payload of every `TThrow`, in protocol order; both branches of binary
+///   combinators are included.
+/// - A delegated endpoint is a session, not a message, and is not listed.
+/// - Used for wire-level separability checks. See also: [`DisjointMessages`].
+pub trait MessagesOf {
+    type Messages;
+}
+impl<IO, Lbl> MessagesOf for protocol::TEnd<IO, Lbl> {
+    type Messages = protocol::Nil;
+}
+impl<IO, Lbl: types::ProtocolLabel, R, H, T: protocol::TSession<IO> + MessagesOf> MessagesOf
+    for protocol::TInteract<IO, Lbl, R, H, T>
+{
+    type Messages = protocol::Cons<H, <T as MessagesOf>::Messages>;
+}
+impl<IO, Lbl: types::ProtocolLabel, From, To, H, T: protocol::TSession<IO> + MessagesOf> MessagesOf
+    for protocol::TMsg<IO, Lbl, From, To, H, T>
+{
+    type Messages = protocol::Cons<H, <T as MessagesOf>::Messages>;
+}
+impl<IO, Lbl: types::ProtocolLabel, From, WorkerSet, H, T: protocol::TSession<IO> + MessagesOf>
+    MessagesOf for protocol::TAnycast<IO, Lbl, From, WorkerSet, H, T>
+{
+    type Messages = protocol::Cons<H, <T as MessagesOf>::Messages>;
+}
+impl<IO, Lbl: types::ProtocolLabel, From, ToSet, H, T: protocol::TSession<IO> + MessagesOf>
+    MessagesOf for protocol::TBroadcast<IO, Lbl, From, ToSet, H, T>
+{
+    type Messages = protocol::Cons<H, <T as MessagesOf>::Messages>;
+}
+impl<IO, Lbl: types::ProtocolLabel, From, To, DelegatedEp, T> MessagesOf
+    for protocol::TDelegate<IO, Lbl, From, To, DelegatedEp, T>
+where
+    T: protocol::TSession<IO> + MessagesOf,
+{
+    type Messages = <T as MessagesOf>::Messages;
+}
+impl<IO, Lbl: types::ProtocolLabel, A, B, Cont> MessagesOf
+    for protocol::TDisconnect<IO, Lbl, A, B, Cont>
+where
+    Cont: protocol::TSession<IO> + MessagesOf,
+{
+    type Messages = <Cont as MessagesOf>::Messages;
+}
+impl<IO, Lbl: types::ProtocolLabel, A, B, Cont> MessagesOf
+    for protocol::TConnect<IO, Lbl, A, B, Cont>
+where
+    Cont: protocol::TSession<IO> + MessagesOf,
+{
+    type Messages = <Cont as MessagesOf>::Messages;
+}
+impl<IO, Lbl: types::ProtocolLabel, R, Cont> MessagesOf for protocol::TEndFor<IO, Lbl, R, Cont>
+where
+    Cont: protocol::TSession<IO> + MessagesOf,
+{
+    type Messages = <Cont as MessagesOf>::Messages;
+}
+impl<IO, Lbl, L, R> MessagesOf for protocol::TChoice<IO, Lbl, L, R>
+where
+    Lbl: types::ProtocolLabel,
+    L: protocol::TSession<IO> + MessagesOf,
+    R: protocol::TSession<IO> + MessagesOf,
+    <L as MessagesOf>::Messages: protocol::Concat<<R as MessagesOf>::Messages>,
+{
+    type Messages =
+        <<L as MessagesOf>::Messages as protocol::Concat<<R as MessagesOf>::Messages>>::Output;
+}
+impl<IO, Lbl: types::ProtocolLabel, Branches: protocol::SessionList<IO> + MessagesOfEach> MessagesOf
+    for protocol::TChoiceN<IO, Lbl, Branches>
+{
+    type Messages = <Branches as MessagesOfEach>::Messages;
+}
+impl<IO, Lbl: types::ProtocolLabel, Chooser, Branches> MessagesOf
+    for protocol::TSelect<IO, Lbl, Chooser, Branches>
+where
+    Branches: protocol::SessionList<IO> + MessagesOfEach,
+{
+    type Messages = <Branches as MessagesOfEach>::Messages;
+}
+impl<IO, Lbl: types::ProtocolLabel, Offeree, Branches> MessagesOf
+    for protocol::TOffer<IO, Lbl, Offeree, Branches>
+where
+    Branches: protocol::SessionList<IO> + MessagesOfEach,
+{
+    type Messages = <Branches as MessagesOfEach>::Messages;
+}
+impl<IO, Lbl: types::ProtocolLabel, Branches: protocol::SessionList<IO> + MessagesOfEach> MessagesOf
+    for protocol::TParN<IO, Lbl, Branches>
+{
+    type Messages = <Branches as MessagesOfEach>::Messages;
+}
+impl<IO, Lbl: types::ProtocolLabel, Steps: protocol::SessionList<IO> + MessagesOfEach> MessagesOf
+    for protocol::TShuffle<IO, Lbl, Steps>
+{
+    type Messages = <Steps as MessagesOfEach>::Messages;
+}
+impl<IO, Lbl, L, R, IsDisjoint> MessagesOf for protocol::TPar<IO, Lbl, L, R, IsDisjoint>
+where
+    Lbl: types::ProtocolLabel,
+    L: protocol::TSession<IO> + MessagesOf,
+    R: protocol::TSession<IO> + MessagesOf,
+    <L as MessagesOf>::Messages: protocol::Concat<<R as MessagesOf>::Messages>,
+{
+    type Messages =
+        <<L as MessagesOf>::Messages as protocol::Concat<<R as MessagesOf>::Messages>>::Output;
+}
+impl<IO, Lbl: types::ProtocolLabel, S: protocol::TSession<IO> + MessagesOf> MessagesOf
+    for protocol::TRec<IO, Lbl, S>
+{
+    type Messages = <S as MessagesOf>::Messages;
+}
+impl<IO, Lbl: types::ProtocolLabel, Var, Body: protocol::TSession<IO> + MessagesOf> MessagesOf
+    for protocol::TRecX<IO, Lbl, Var, Body>
+{
+    type Messages = <Body as MessagesOf>::Messages;
+}
+impl<Var> MessagesOf for protocol::TVar<Var> {
+    type Messages = protocol::Nil;
+}
+impl<IO, Lbl, Dur, Body, OnTimeout> MessagesOf for protocol::TTimeout<IO, Lbl, Dur, Body, OnTimeout>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    Body: protocol::TSession<IO> + MessagesOf,
+    OnTimeout: protocol::TSession<IO> + MessagesOf,
+    <Body as MessagesOf>::Messages: protocol::Concat<<OnTimeout as MessagesOf>::Messages>,
+{
+    type Messages = <<Body as MessagesOf>::Messages as protocol::Concat<
+        <OnTimeout as MessagesOf>::Messages,
+    >>::Output;
+}
+impl<IO, Lbl, Decider, Body, Cont> MessagesOf for protocol::TOptional<IO, Lbl, Decider, Body, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Body: protocol::TSession<IO> + MessagesOf,
+    Cont: protocol::TSession<IO> + MessagesOf,
+    <Body as MessagesOf>::Messages: protocol::Concat<<Cont as MessagesOf>::Messages>,
+{
+    type Messages = <<Body as MessagesOf>::Messages as protocol::Concat<
+        <Cont as MessagesOf>::Messages,
+    >>::Output;
+}
+impl<IO, Lbl: types::ProtocolLabel, R, Err> MessagesOf for protocol::TThrow<IO, Lbl, R, Err> {
+    type Messages = protocol::Cons<Err, protocol::Nil>;
+}
+impl<IO, Lbl, Body, Handler> MessagesOf for protocol::TCatch<IO, Lbl, Body, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Body: protocol::TSession<IO> + MessagesOf,
+    Handler: protocol::TSession<IO> + MessagesOf,
+    <Body as MessagesOf>::Messages: protocol::Concat<<Handler as MessagesOf>::Messages>,
+{
+    type Messages = <<Body as MessagesOf>::Messages as protocol::Concat<
+        <Handler as MessagesOf>::Messages,
+    >>::Output;
+}
+impl<IO, Lbl, High, Low> MessagesOf for protocol::TPriorityChoice<IO, Lbl, High, Low>
+where
+    Lbl: types::ProtocolLabel,
+    High: protocol::TSession<IO> + MessagesOf,
+    Low: protocol::TSession<IO> + MessagesOf,
+    <High as MessagesOf>::Messages: protocol::Concat<<Low as MessagesOf>::Messages>,
+{
+    type Messages =
+        <<High as MessagesOf>::Messages as protocol::Concat<<Low as MessagesOf>::Messages>>::Output;
+}
+impl<IO, Lbl, WeightL, L, WeightR, R> MessagesOf
+    for protocol::TProb<IO, Lbl, WeightL, L, WeightR, R>
+where
+    Lbl: types::ProtocolLabel,
+    WeightL: types::WeightMarker,
+    WeightR: types::WeightMarker,
+    L: protocol::TSession<IO> + MessagesOf,
+    R: protocol::TSession<IO> + MessagesOf,
+    <L as MessagesOf>::Messages: protocol::Concat<<R as MessagesOf>::Messages>,
+{
+    type Messages =
+        <<L as MessagesOf>::Messages as protocol::Concat<<R as MessagesOf>::Messages>>::Output;
+}
+impl<Pred, Inner: MessagesOf> MessagesOf for protocol::TRefine<Pred, Inner> {
+    type Messages = <Inner as MessagesOf>::Messages;
+}
+impl<Meta, Inner: MessagesOf> MessagesOf for protocol::TAnnotate<Meta, Inner> {
+    type Messages = <Inner as MessagesOf>::Messages;
+}
+impl<IO, Lbl, Scope, Interruptor, Handler> MessagesOf
+    for protocol::TInterrupt<IO, Lbl, Scope, Interruptor, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Scope: protocol::TSession<IO> + MessagesOf,
+    Handler: protocol::TSession<IO> + MessagesOf,
+    <Scope as MessagesOf>::Messages: protocol::Concat<<Handler as MessagesOf>::Messages>,
+{
+    type Messages = <<Scope as MessagesOf>::Messages as protocol::Concat<
+        <Handler as MessagesOf>::Messages,
+    >>::Output;
+}
+impl<IO, Lbl, R, Dur, T> MessagesOf for protocol::TDelay<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: protocol::TSession<IO> + MessagesOf,
+{
+    type Messages = <T as MessagesOf>::Messages;
+}
+impl<IO, Lbl, R, Dur, T> MessagesOf for protocol::TDeadline<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: protocol::TSession<IO> + MessagesOf,
+{
+    type Messages = <T as MessagesOf>::Messages;
+}
+
+/// Type-level equality for IO markers.
+///
+/// Implemented for the crate's markers; implement it for every ordered pair
+/// of custom markers that [`IOsOf`] may meet.
+#[diagnostic::on_unimplemented(
+    message = "no `IoEq` impl compares the IO markers `{Self}` and `{Other}`",
+    label = "cannot tell `{Self}` and `{Other}` apart",
+    note = "implement `IoEq` for every ordered pair of IO markers in the protocol"
+)]
+pub trait IoEq<Other> {
+    type Output: types::Bool;
+}
+
+// An IO marker equals itself and differs from the other markers listed
+macro_rules! io_eq {
+    ($IO:ident: $($Other:ident),*) => {
+        impl IoEq<types::$IO> for types::$IO {
+            type Output = types::True;
+        }
+        $(
+            impl IoEq<types::$Other> for types::$IO {
+                type Output = types::False;
+            }
+        )*
+    };
+}
+
+io_eq!(Http: Db, Mqtt, Cache, Mixed);
+io_eq!(Db: Http, Mqtt, Cache, Mixed);
+io_eq!(Mqtt: Http, Db, Cache, Mixed);
+io_eq!(Cache: Http, Db, Mqtt, Mixed);
+io_eq!(Mixed: Http, Db, Mqtt, Cache);
+
+/// Appends the IO marker `IO` to a type-level list of markers, unless the
+/// list already contains it.
+pub trait AddIo<IO> {
+    type Output;
+}
+impl<IO> AddIo<IO> for protocol::Nil {
+    type Output = protocol::Cons<IO, protocol::Nil>;
+}
+impl<IO, H, T> AddIo<IO> for protocol::Cons<H, T>
+where
+    H: IoEq<IO>,
+    (): AddIoCase<IO, H, T, <H as IoEq<IO>>::Output>,
+{
+    type Output = <() as AddIoCase<IO, H, T, <H as IoEq<IO>>::Output>>::Output;
+}
+
+/// Helper trait for `AddIo`, dispatching on whether the head of the list is
+/// `IO`.
+pub trait AddIoCase<IO, H, T, Same> {
+    type Output;
+}
+impl<IO, H, T> AddIoCase<IO, H, T, types::True> for () {
+    type Output = protocol::Cons<H, T>;
+}
+impl<IO, H, T: AddIo<IO>> AddIoCase<IO, H, T, types::False> for () {
+    type Output = protocol::Cons<H, <T as AddIo<IO>>::Output>;
+}
+
+/// Appends every IO marker of the list `IOs` that is not yet present.
+pub trait AddIos<IOs> {
+    type Output;
+}
+impl<S> AddIos<protocol::Nil> for S {
+    type Output = S;
+}
+impl<S, H, T> AddIos<protocol::Cons<H, T>> for S
+where
+    S: AddIo<H>,
+    <S as AddIo<H>>::Output: AddIos<T>,
+{
+    type Output = <<S as AddIo<H>>::Output as AddIos<T>>::Output;
+}
+
+// The marker of a node followed by the new markers of its continuation
+type WithIos<IO, IOs> = <protocol::Cons<IO, protocol::Nil> as AddIos<IOs>>::Output;
+
+/// Extracts the IO markers a protocol runs over as a type-level list.
+///
+/// - Lists the protocol's own marker, then the markers of delegated
+///   endpoints, each marker once, in order of first appearance.
+/// - Implemented for global combinators, local session types and
+///   `IoSession` entries; use [`IOsOfEach`] on the session list of a
+///   multi-protocol role, such as one given to `ProjectMixed`.
+/// - Markers are compared with [`IoEq`].
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// type Reply = EpSend<Mqtt, EmptyLabel, TServer, Response, EpEnd<Mqtt, EmptyLabel, TServer>>;
+/// type HandOff = TDelegate<Http, EmptyLabel, TBroker, TWorker, Reply, TEnd<Http>>;
+/// assert_type_eq!(<HandOff as IOsOf>::IOs, tlist!(Http, Mqtt));
+/// ```
+pub trait IOsOf {
+    type IOs;
+}
+impl<IO, Lbl> IOsOf for protocol::TEnd<IO, Lbl> {
+    type IOs = protocol::Cons<IO, protocol::Nil>;
+}
+impl<IO, Lbl, R, H, T> IOsOf for protocol::TInteract<IO, Lbl, R, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: protocol::TSession<IO> + IOsOf,
+    protocol::Cons<IO, protocol::Nil>: AddIos<<T as IOsOf>::IOs>,
+{
+    type IOs = WithIos<IO, <T as IOsOf>::IOs>;
+}
+impl<IO, Lbl, From, To, H, T> IOsOf for protocol::TMsg<IO, Lbl, From, To, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: protocol::TSession<IO> + IOsOf,
+    protocol::Cons<IO, protocol::Nil>: AddIos<<T as IOsOf>::IOs>,
+{
+    type IOs = WithIos<IO, <T as IOsOf>::IOs>;
+}
+impl<IO, Lbl, From, ToSet, H, T> IOsOf for protocol::TBroadcast<IO, Lbl, From, ToSet, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: protocol::TSession<IO> + IOsOf,
+    protocol::Cons<IO, protocol::Nil>: AddIos<<T as IOsOf>::IOs>,
+{
+    type IOs = WithIos<IO, <T as IOsOf>::IOs>;
+}
+impl<IO, Lbl, From, WorkerSet, H, T> IOsOf for protocol::TAnycast<IO, Lbl, From, WorkerSet, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: protocol::TSession<IO> + IOsOf,
+    protocol::Cons<IO, protocol::Nil>: AddIos<<T as IOsOf>::IOs>,
+{
+    type IOs = WithIos<IO, <T as IOsOf>::IOs>;
+}
+impl<IO, Lbl, From, To, DelegatedEp, T> IOsOf
+    for protocol::TDelegate<IO, Lbl, From, To, DelegatedEp, T>
+where
+    Lbl: types::ProtocolLabel,
+    DelegatedEp: IOsOf,
+    T: protocol::TSession<IO> + IOsOf,
+    protocol::Cons<IO, protocol::Nil>: AddIos<<DelegatedEp as IOsOf>::IOs>,
+    WithIos<IO, <DelegatedEp as IOsOf>::IOs>: AddIos<<T as IOsOf>::IOs>,
+{
+    type IOs = <WithIos<IO, <DelegatedEp as IOsOf>::IOs> as AddIos<<T as IOsOf>::IOs>>::Output;
+}
+impl<IO, Lbl, A, B, Cont> IOsOf for protocol::TDisconnect<IO, Lbl, A, B, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Cont: protocol::TSession<IO> + IOsOf,
+    protocol::Cons<IO, protocol::Nil>: AddIos<<Cont as IOsOf>::IOs>,
+{
+    type IOs = WithIos<IO, <Cont as IOsOf>::IOs>;
+}
+impl<IO, Lbl, A, B, Cont> IOsOf for protocol::TConnect<IO, Lbl, A, B, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Cont: protocol::TSession<IO> + IOsOf,
+    protocol::Cons<IO, protocol::Nil>: AddIos<<Cont as IOsOf>::IOs>,
+{
+    type IOs = WithIos<IO, <Cont as IOsOf>::IOs>;
+}
+impl<IO, Lbl, R, Cont> IOsOf for protocol::TEndFor<IO, Lbl, R, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Cont: protocol::TSession<IO> + IOsOf,
+    protocol::Cons<IO, protocol::Nil>: AddIos<<Cont as IOsOf>::IOs>,
+{
+    type IOs = WithIos<IO, <Cont as IOsOf>::IOs>;
+}
+impl<IO, Lbl, L, R> IOsOf for protocol::TChoice<IO, Lbl, L, R>
+where
+    Lbl: types::ProtocolLabel,
+    L: protocol::TSession<IO> + IOsOf,
+    R: protocol::TSession<IO> + IOsOf,
+    protocol::Cons<IO, protocol::Nil>: AddIos<<L as IOsOf>::IOs>,
+    WithIos<IO, <L as IOsOf>::IOs>: AddIos<<R as IOsOf>::IOs>,
+{
+    type IOs = <WithIos<IO, <L as IOsOf>::IOs> as AddIos<<R as IOsOf>::IOs>>::Output;
+}
+impl<IO, Lbl, Branches> IOsOf for protocol::TChoiceN<IO, Lbl, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: protocol::SessionList<IO> + IOsOfEach,
+    protocol::Cons<IO, protocol::Nil>: AddIos<<Branches as IOsOfEach>::IOs>,
+{
+    type IOs = WithIos<IO, <Branches as IOsOfEach>::IOs>;
+}
+impl<IO, Lbl, Chooser, Branches> IOsOf for protocol::TSelect<IO, Lbl, Chooser, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: protocol::SessionList<IO> + IOsOfEach,
+    protocol::Cons<IO, protocol::Nil>: AddIos<<Branches as IOsOfEach>::IOs>,
+{
+    type IOs = WithIos<IO, <Branches as IOsOfEach>::IOs>;
+}
+impl<IO, Lbl, Offeree, Branches> IOsOf for protocol::TOffer<IO, Lbl, Offeree, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: protocol::SessionList<IO> + IOsOfEach,
+    protocol::Cons<IO, protocol::Nil>: AddIos<<Branches as IOsOfEach>::IOs>,
+{
+    type IOs = WithIos<IO, <Branches as IOsOfEach>::IOs>;
+}
+impl<IO, Lbl, High, Low> IOsOf for protocol::TPriorityChoice<IO, Lbl, High, Low>
+where
+    Lbl: types::ProtocolLabel,
+    High: protocol::TSession<IO> + IOsOf,
+    Low: protocol::TSession<IO> + IOsOf,
+    protocol::Cons<IO, protocol::Nil>: AddIos<<High as IOsOf>::IOs>,
+    WithIos<IO, <High as IOsOf>::IOs>: AddIos<<Low as IOsOf>::IOs>,
+{
+    type IOs = <WithIos<IO, <High as IOsOf>::IOs> as AddIos<<Low as IOsOf>::IOs>>::Output;
+}
+impl<IO, Lbl, WeightL, L, WeightR, R> IOsOf for protocol::TProb<IO, Lbl, WeightL, L, WeightR, R>
+where
+    Lbl: types::ProtocolLabel,
+    WeightL: types::WeightMarker,
+    WeightR: types::WeightMarker,
+    L: protocol::TSession<IO> + IOsOf,
+    R: protocol::TSession<IO> + IOsOf,
+    protocol::Cons<IO, protocol::Nil>: AddIos<<L as IOsOf>::IOs>,
+    WithIos<IO, <L as IOsOf>::IOs>: AddIos<<R as IOsOf>::IOs>,
+{
+    type IOs = <WithIos<IO, <L as IOsOf>::IOs> as AddIos<<R as IOsOf>::IOs>>::Output;
+}
+impl<IO, Lbl, L, R, IsDisjoint> IOsOf for protocol::TPar<IO, Lbl, L, R, IsDisjoint>
+where
+    Lbl: types::ProtocolLabel,
+    L: protocol::TSession<IO> + IOsOf,
+    R: protocol::TSession<IO> + IOsOf,
+    protocol::Cons<IO, protocol::Nil>: AddIos<<L as IOsOf>::IOs>,
+    WithIos<IO, <L as IOsOf>::IOs>: AddIos<<R as IOsOf>::IOs>,
+{
+    type IOs = <WithIos<IO, <L as IOsOf>::IOs> as AddIos<<R as IOsOf>::IOs>>::Output;
+}
+impl<IO, Lbl, Branches> IOsOf for protocol::TParN<IO, Lbl, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: protocol::SessionList<IO> + IOsOfEach,
+    protocol::Cons<IO, protocol::Nil>: AddIos<<Branches as IOsOfEach>::IOs>,
+{
+    type IOs = WithIos<IO, <Branches as IOsOfEach>::IOs>;
+}
+impl<IO, Lbl, Steps> IOsOf for protocol::TShuffle<IO, Lbl, Steps>
+where
+    Lbl: types::ProtocolLabel,
+    Steps: protocol::SessionList<IO> + IOsOfEach,
+    protocol::Cons<IO, protocol::Nil>: AddIos<<Steps as IOsOfEach>::IOs>,
+{
+    type IOs = WithIos<IO, <Steps as IOsOfEach>::IOs>;
+}
+impl<IO, Lbl, S> IOsOf for protocol::TRec<IO, Lbl, S>
+where
+    Lbl: types::ProtocolLabel,
+    S: protocol::TSession<IO> + IOsOf,
+    protocol::Cons<IO, protocol::Nil>: AddIos<<S as IOsOf>::IOs>,
+{
+    type IOs = WithIos<IO, <S as IOsOf>::IOs>;
+}
+impl<IO, Lbl, Var, Body> IOsOf for protocol::TRecX<IO, Lbl, Var, Body>
+where
+    Lbl: types::ProtocolLabel,
+    Body: protocol::TSession<IO> + IOsOf,
+    protocol::Cons<IO, protocol::Nil>: AddIos<<Body as IOsOf>::IOs>,
+{
+    type IOs = WithIos<IO, <Body as IOsOf>::IOs>;
+}
+impl<IO, Lbl, Dur, Body, OnTimeout> IOsOf for protocol::TTimeout<IO, Lbl, Dur, Body, OnTimeout>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    Body: protocol::TSession<IO> + IOsOf,
+    OnTimeout: protocol::TSession<IO> + IOsOf,
+    protocol::Cons<IO, protocol::Nil>: AddIos<<Body as IOsOf>::IOs>,
+    WithIos<IO, <Body as IOsOf>::IOs>: AddIos<<OnTimeout as IOsOf>::IOs>,
+{
+    type IOs = <WithIos<IO, <Body as IOsOf>::IOs> as AddIos<<OnTimeout as IOsOf>::IOs>>::Output;
+}
+impl<IO, Lbl, R, Dur, T> IOsOf for protocol::TDelay<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: protocol::TSession<IO> + IOsOf,
+    protocol::Cons<IO, protocol::Nil>: AddIos<<T as IOsOf>::IOs>,
+{
+    type IOs = WithIos<IO, <T as IOsOf>::IOs>;
+}
+impl<IO, Lbl, R, Dur, T> IOsOf for protocol::TDeadline<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: protocol::TSession<IO> + IOsOf,
+    protocol::Cons<IO, protocol::Nil>: AddIos<<T as IOsOf>::IOs>,
+{
+    type IOs = WithIos<IO, <T as IOsOf>::IOs>;
+}
+impl<IO, Lbl, Scope, Interruptor, Handler> IOsOf
+    for protocol::TInterrupt<IO, Lbl, Scope, Interruptor, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Scope: protocol::TSession<IO> + IOsOf,
+    Handler: protocol::TSession<IO> + IOsOf,
+    protocol::Cons<IO, protocol::Nil>: AddIos<<Scope as IOsOf>::IOs>,
+    WithIos<IO, <Scope as IOsOf>::IOs>: AddIos<<Handler as IOsOf>::IOs>,
+{
+    type IOs = <WithIos<IO, <Scope as IOsOf>::IOs> as AddIos<<Handler as IOsOf>::IOs>>::Output;
+}
+impl<IO, Lbl, Decider, Body, Cont> IOsOf for protocol::TOptional<IO, Lbl, Decider, Body, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Body: protocol::TSession<IO> + IOsOf,
+    Cont: protocol::TSession<IO> + IOsOf,
+    protocol::Cons<IO, protocol::Nil>: AddIos<<Body as IOsOf>::IOs>,
+    WithIos<IO, <Body as IOsOf>::IOs>: AddIos<<Cont as IOsOf>::IOs>,
+{
+    type IOs = <WithIos<IO, <Body as IOsOf>::IOs> as AddIos<<Cont as IOsOf>::IOs>>::Output;
+}
+impl<IO, Lbl, R, Err> IOsOf for protocol::TThrow<IO, Lbl, R, Err>
+where
+    Lbl: types::ProtocolLabel,
+{
+    type IOs = protocol::Cons<IO, protocol::Nil>;
+}
+impl<IO, Lbl, Body, Handler> IOsOf for protocol::TCatch<IO, Lbl, Body, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Body: protocol::TSession<IO> + IOsOf,
+    Handler: protocol::TSession<IO> + IOsOf,
+    protocol::Cons<IO, protocol::Nil>: AddIos<<Body as IOsOf>::IOs>,
+    WithIos<IO, <Body as IOsOf>::IOs>: AddIos<<Handler as IOsOf>::IOs>,
+{
+    type IOs = <WithIos<IO, <Body as IOsOf>::IOs> as AddIos<<Handler as IOsOf>::IOs>>::Output;
+}
+impl<Var> IOsOf for protocol::TVar<Var> {
+    type IOs = protocol::Nil;
+}
+impl<Pred, Inner: IOsOf> IOsOf for protocol::TRefine<Pred, Inner> {
+    type IOs = <Inner as IOsOf>::IOs;
+}
+impl<Meta, Inner: IOsOf> IOsOf for protocol::TAnnotate<Meta, Inner> {
+    type IOs = <Inner as IOsOf>::IOs;
+}
+// Local session types, as found in delegations
+impl<IO, Lbl, R> IOsOf for protocol::EpEnd<IO, Lbl, R>
+where
+    Lbl: types::ProtocolLabel,
+{
+    type IOs = protocol::Cons<IO, protocol::Nil>;
+}
+impl<IO, Lbl, R> IOsOf for protocol::EpSkip<IO, Lbl, R>
+where
+    Lbl: types::ProtocolLabel,
+{
+    type IOs = protocol::Cons<IO, protocol::Nil>;
+}
+impl<IO, Lbl, R, H, T> IOsOf for protocol::EpSend<IO, Lbl, R, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: IOsOf,
+    protocol::Cons<IO, protocol::Nil>: AddIos<<T as IOsOf>::IOs>,
+{
+    type IOs = WithIos<IO, <T as IOsOf>::IOs>;
+}
+impl<IO, Lbl, R, H, T> IOsOf for protocol::EpRecv<IO, Lbl, R, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: IOsOf,
+    protocol::Cons<IO, protocol::Nil>: AddIos<<T as IOsOf>::IOs>,
+{
+    type IOs = WithIos<IO, <T as IOsOf>::IOs>;
+}
+impl<IO, Lbl, Me, ToSet, H, T> IOsOf for protocol::EpBroadcast<IO, Lbl, Me, ToSet, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: IOsOf,
+    protocol::Cons<IO, protocol::Nil>: AddIos<<T as IOsOf>::IOs>,
+{
+    type IOs = WithIos<IO, <T as IOsOf>::IOs>;
+}
+impl<IO, Lbl, Me, FromSet, H, T> IOsOf for protocol::EpGather<IO, Lbl, Me, FromSet, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: IOsOf,
+    protocol::Cons<IO, protocol::Nil>: AddIos<<T as IOsOf>::IOs>,
+{
+    type IOs = WithIos<IO, <T as IOsOf>::IOs>;
+}
+impl<IO, Lbl, Me, DelegatedEp, Cont> IOsOf for protocol::EpDelegate<IO, Lbl, Me, DelegatedEp, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    DelegatedEp: IOsOf,
+    Cont: IOsOf,
+    protocol::Cons<IO, protocol::Nil>: AddIos<<DelegatedEp as IOsOf>::IOs>,
+    WithIos<IO, <DelegatedEp as IOsOf>::IOs>: AddIos<<Cont as IOsOf>::IOs>,
+{
+    type IOs = <WithIos<IO, <DelegatedEp as IOsOf>::IOs> as AddIos<<Cont as IOsOf>::IOs>>::Output;
+}
+impl<IO, Lbl, Me, Branches> IOsOf for protocol::EpChoiceN<IO, Lbl, Me, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: IOsOfEach,
+    protocol::Cons<IO, protocol::Nil>: AddIos<<Branches as IOsOfEach>::IOs>,
+{
+    type IOs = WithIos<IO, <Branches as IOsOfEach>::IOs>;
+}
+impl<IO, Lbl, Me, Branches> IOsOf for protocol::EpSelect<IO, Lbl, Me, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: IOsOfEach,
+    protocol::Cons<IO, protocol::Nil>: AddIos<<Branches as IOsOfEach>::IOs>,
+{
+    type IOs = WithIos<IO, <Branches as IOsOfEach>::IOs>;
+}
+impl<IO, Lbl, Me, Branches> IOsOf for protocol::EpOffer<IO, Lbl, Me, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: IOsOfEach,
+    protocol::Cons<IO, protocol::Nil>: AddIos<<Branches as IOsOfEach>::IOs>,
+{
+    type IOs = WithIos<IO, <Branches as IOsOfEach>::IOs>;
+}
+impl<IO, Lbl, Me, L, R> IOsOf for protocol::EpPar<IO, Lbl, Me, L, R>
+where
+    Lbl: types::ProtocolLabel,
+    L: IOsOf,
+    R: IOsOf,
+    protocol::Cons<IO, protocol::Nil>: AddIos<<L as IOsOf>::IOs>,
+    WithIos<IO, <L as IOsOf>::IOs>: AddIos<<R as IOsOf>::IOs>,
+{
+    type IOs = <WithIos<IO, <L as IOsOf>::IOs> as AddIos<<R as IOsOf>::IOs>>::Output;
+}
+impl<IO, Lbl, Me, Branches> IOsOf for protocol::EpParN<IO, Lbl, Me, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: IOsOfEach,
+    protocol::Cons<IO, protocol::Nil>: AddIos<<Branches as IOsOfEach>::IOs>,
+{
+    type IOs = WithIos<IO, <Branches as IOsOfEach>::IOs>;
+}
+impl<IO, Lbl, Me, Steps> IOsOf for protocol::EpShuffle<IO, Lbl, Me, Steps>
+where
+    Lbl: types::ProtocolLabel,
+    Steps: IOsOfEach,
+    protocol::Cons<IO, protocol::Nil>: AddIos<<Steps as IOsOfEach>::IOs>,
+{
+    type IOs = WithIos<IO, <Steps as IOsOfEach>::IOs>;
+}
+impl<IO, Lbl, Var, Body> IOsOf for protocol::EpRec<IO, Lbl, Var, Body>
+where
+    Lbl: types::ProtocolLabel,
+    Body: IOsOf,
+    protocol::Cons<IO, protocol::Nil>: AddIos<<Body as IOsOf>::IOs>,
+{
+    type IOs = WithIos<IO, <Body as IOsOf>::IOs>;
+}
+impl<IO, Lbl, Me, Dur, Body, OnTimeout> IOsOf
+    for protocol::EpTimeout<IO, Lbl, Me, Dur, Body, OnTimeout>
+where
+    Lbl: types::ProtocolLabel,
+    Body: IOsOf,
+    OnTimeout: IOsOf,
+    protocol::Cons<IO, protocol::Nil>: AddIos<<Body as IOsOf>::IOs>,
+    WithIos<IO, <Body as IOsOf>::IOs>: AddIos<<OnTimeout as IOsOf>::IOs>,
+{
+    type IOs = <WithIos<IO, <Body as IOsOf>::IOs> as AddIos<<OnTimeout as IOsOf>::IOs>>::Output;
+}
+impl<IO, Lbl, Me, Dur, T> IOsOf for protocol::EpDelay<IO, Lbl, Me, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: IOsOf,
+    protocol::Cons<IO, protocol::Nil>: AddIos<<T as IOsOf>::IOs>,
+{
+    type IOs = WithIos<IO, <T as IOsOf>::IOs>;
+}
+impl<IO, Lbl, Me, Dur, T> IOsOf for protocol::EpDeadline<IO, Lbl, Me, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: IOsOf,
+    protocol::Cons<IO, protocol::Nil>: AddIos<<T as IOsOf>::IOs>,
+{
+    type IOs = WithIos<IO, <T as IOsOf>::IOs>;
+}
+impl<IO, Lbl, Me, Scope, Handler> IOsOf for protocol::EpInterrupt<IO, Lbl, Me, Scope, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Scope: IOsOf,
+    Handler: IOsOf,
+    protocol::Cons<IO, protocol::Nil>: AddIos<<Scope as IOsOf>::IOs>,
+    WithIos<IO, <Scope as IOsOf>::IOs>: AddIos<<Handler as IOsOf>::IOs>,
+{
+    type IOs = <WithIos<IO, <Scope as IOsOf>::IOs> as AddIos<<Handler as IOsOf>::IOs>>::Output;
+}
+impl<IO, Lbl, Me, Scope, Handler> IOsOf for protocol::EpInterruptible<IO, Lbl, Me, Scope, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Scope: IOsOf,
+    Handler: IOsOf,
+    protocol::Cons<IO, protocol::Nil>: AddIos<<Scope as IOsOf>::IOs>,
+    WithIos<IO, <Scope as IOsOf>::IOs>: AddIos<<Handler as IOsOf>::IOs>,
+{
+    type IOs = <WithIos<IO, <Scope as IOsOf>::IOs> as AddIos<<Handler as IOsOf>::IOs>>::Output;
+}
+impl<IO, Lbl, Me, Body, Cont> IOsOf for protocol::EpOptionalSelect<IO, Lbl, Me, Body, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Body: IOsOf,
+    Cont: IOsOf,
+    protocol::Cons<IO, protocol::Nil>: AddIos<<Body as IOsOf>::IOs>,
+    WithIos<IO, <Body as IOsOf>::IOs>: AddIos<<Cont as IOsOf>::IOs>,
+{
+    type IOs = <WithIos<IO, <Body as IOsOf>::IOs> as AddIos<<Cont as IOsOf>::IOs>>::Output;
+}
+impl<IO, Lbl, Me, Body, Cont> IOsOf for protocol::EpOptionalOffer<IO, Lbl, Me, Body, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Body: IOsOf,
+    Cont: IOsOf,
+    protocol::Cons<IO, protocol::Nil>: AddIos<<Body as IOsOf>::IOs>,
+    WithIos<IO, <Body as IOsOf>::IOs>: AddIos<<Cont as IOsOf>::IOs>,
+{
+    type IOs = <WithIos<IO, <Body as IOsOf>::IOs> as AddIos<<Cont as IOsOf>::IOs>>::Output;
+}
+impl<IO, Lbl, Me, Err> IOsOf for protocol::EpThrow<IO, Lbl, Me, Err>
+where
+    Lbl: types::ProtocolLabel,
+{
+    type IOs = protocol::Cons<IO, protocol::Nil>;
+}
+impl<IO, Lbl, Me, Body, Handler> IOsOf for protocol::EpCatch<IO, Lbl, Me, Body, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Body: IOsOf,
+    Handler: IOsOf,
+    protocol::Cons<IO, protocol::Nil>: AddIos<<Body as IOsOf>::IOs>,
+    WithIos<IO, <Body as IOsOf>::IOs>: AddIos<<Handler as IOsOf>::IOs>,
+{
+    type IOs = <WithIos<IO, <Body as IOsOf>::IOs> as AddIos<<Handler as IOsOf>::IOs>>::Output;
+}
+impl<IO, Lbl, Me, High, Low> IOsOf for protocol::EpPriorityOffer<IO, Lbl, Me, High, Low>
+where
+    Lbl: types::ProtocolLabel,
+    High: IOsOf,
+    Low: IOsOf,
+    protocol::Cons<IO, protocol::Nil>: AddIos<<High as IOsOf>::IOs>,
+    WithIos<IO, <High as IOsOf>::IOs>: AddIos<<Low as IOsOf>::IOs>,
+{
+    type IOs = <WithIos<IO, <High as IOsOf>::IOs> as AddIos<<Low as IOsOf>::IOs>>::Output;
+}
+impl<IO, Lbl, Me, Peer, Cont> IOsOf for protocol::EpClose<IO, Lbl, Me, Peer, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Cont: IOsOf,
+    protocol::Cons<IO, protocol::Nil>: AddIos<<Cont as IOsOf>::IOs>,
+{
+    type IOs = WithIos<IO, <Cont as IOsOf>::IOs>;
+}
+impl<IO, Lbl, Me, Peer, Cont> IOsOf for protocol::EpOpen<IO, Lbl, Me, Peer, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Cont: IOsOf,
+    protocol::Cons<IO, protocol::Nil>: AddIos<<Cont as IOsOf>::IOs>,
+{
+    type IOs = WithIos<IO, <Cont as IOsOf>::IOs>;
+}
+impl<Var> IOsOf for protocol::EpVar<Var> {
+    type IOs = protocol::Nil;
+}
+impl<Pred, Inner: IOsOf> IOsOf for protocol::EpRefine<Pred, Inner> {
+    type IOs = <Inner as IOsOf>::IOs;
+}
+impl<IO, G> IOsOf for protocol::IoSession<IO, G>
+where
+    G: protocol::TSession<IO> + IOsOf,
+{
+    type IOs = <G as IOsOf>::IOs;
+}
+
+/// Reads a type-level natural built from [`Zero`](crate::Zero) and
+/// [`Succ`](crate::Succ) back as a constant.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// assert_eq!(<Zero as Nat>::VALUE, 0);
+/// assert_eq!(<Succ<Succ<Zero>> as Nat>::VALUE, 2);
+/// ```
+pub trait Nat {
+    /// The number this natural stands for.
+    const VALUE: usize;
+}
+impl Nat for protocol::Zero {
+    const VALUE: usize = 0;
+}
+impl<N: Nat> Nat for protocol::Succ<N> {
+    const VALUE: usize = N::VALUE + 1;
+}
+
+/// Adds the type-level natural `Rhs` to this one.
+pub trait NatAdd<Rhs> {
+    type Output;
+}
+impl<Rhs> NatAdd<Rhs> for protocol::Zero {
+    type Output = Rhs;
+}
+impl<N: NatAdd<Rhs>, Rhs> NatAdd<Rhs> for protocol::Succ<N> {
+    type Output = protocol::Succ<<N as NatAdd<Rhs>>::Output>;
+}
+
+/// The greater of this type-level natural and `Rhs`.
+pub trait NatMax<Rhs> {
+    type Output;
+}
+impl<Rhs> NatMax<Rhs> for protocol::Zero {
+    type Output = Rhs;
+}
+impl<N> NatMax<protocol::Zero> for protocol::Succ<N> {
+    type Output = protocol::Succ<N>;
+}
+impl<N: NatMax<M>, M> NatMax<protocol::Succ<M>> for protocol::Succ<N> {
+    type Output = protocol::Succ<<N as NatMax<M>>::Output>;
+}
+
+/// `N` if this type-level boolean is `True`, zero otherwise.
+pub trait NatIf<N> {
+    type Output;
+}
+impl<N> NatIf<N> for types::True {
+    type Output = N;
+}
+impl<N> NatIf<N> for types::False {
+    type Output = protocol::Zero;
+}
+
+/// The length of a type-level list, as a type-level natural.
+pub trait ListLength {
+    type Length;
+}
+impl ListLength for protocol::Nil {
+    type Length = protocol::Zero;
+}
+impl<H, T: ListLength> ListLength for protocol::Cons<H, T> {
+    type Length = protocol::Succ<<T as ListLength>::Length>;
+}
+
+/// Measures the nesting depth of a protocol as a type-level natural.
+///
+/// - Every choice, parallel composition, loop, timeout, interrupt, optional
+///   body and catch opens a scope; `Depth` is the deepest nesting of scopes.
+///   Sequencing does not nest, so a protocol without scopes has depth `Zero`.
+/// - Implemented for global combinators and local session types. A delegated
+///   endpoint is a session of its own and is not measured.
+/// - Read the depth as a constant with [`Nat`], for example to keep a
+///   protocol within a budget.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// type Poll = TRec<Http, EmptyLabel, TChoice<Http, EmptyLabel,
+///     TMsg<Http, EmptyLabel, TClient, TServer, Message, TEnd<Http>>,
+///     TEnd<Http>>>;
+/// assert_type_eq!(<Poll as DepthOf>::Depth, Succ<Succ<Zero>>);
+/// const _: () = assert!(<<Poll as DepthOf>::Depth as Nat>::VALUE <= 4);
+/// ```
+pub trait DepthOf {
+    type Depth;
+}
+impl<IO, Lbl> DepthOf for protocol::TEnd<IO, Lbl> {
+    type Depth = protocol::Zero;
+}
+impl<IO, Lbl, R, H, T> DepthOf for protocol::TInteract<IO, Lbl, R, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: protocol::TSession<IO> + DepthOf,
+{
+    type Depth = <T as DepthOf>::Depth;
+}
+impl<IO, Lbl, From, To, H, T> DepthOf for protocol::TMsg<IO, Lbl, From, To, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: protocol::TSession<IO> + DepthOf,
+{
+    type Depth = <T as DepthOf>::Depth;
+}
+impl<IO, Lbl, From, ToSet, H, T> DepthOf for protocol::TBroadcast<IO, Lbl, From, ToSet, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: protocol::TSession<IO> + DepthOf,
+{
+    type Depth = <T as DepthOf>::Depth;
+}
+impl<IO, Lbl, From, WorkerSet, H, T> DepthOf for protocol::TAnycast<IO, Lbl, From, WorkerSet, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: protocol::TSession<IO> + DepthOf,
+{
+    type Depth = <T as DepthOf>::Depth;
+}
+impl<IO, Lbl, From, To, DelegatedEp, T> DepthOf
+    for protocol::TDelegate<IO, Lbl, From, To, DelegatedEp, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: protocol::TSession<IO> + DepthOf,
+{
+    type Depth = <T as DepthOf>::Depth;
+}
+impl<IO, Lbl, A, B, Cont> DepthOf for protocol::TDisconnect<IO, Lbl, A, B, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Cont: protocol::TSession<IO> + DepthOf,
+{
+    type Depth = <Cont as DepthOf>::Depth;
+}
+impl<IO, Lbl, A, B, Cont> DepthOf for protocol::TConnect<IO, Lbl, A, B, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Cont: protocol::TSession<IO> + DepthOf,
+{
+    type Depth = <Cont as DepthOf>::Depth;
+}
+impl<IO, Lbl, R, Cont> DepthOf for protocol::TEndFor<IO, Lbl, R, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Cont: protocol::TSession<IO> + DepthOf,
+{
+    type Depth = <Cont as DepthOf>::Depth;
+}
+impl<IO, Lbl, L, R> DepthOf for protocol::TChoice<IO, Lbl, L, R>
+where
+    Lbl: types::ProtocolLabel,
+    L: protocol::TSession<IO> + DepthOf,
+    R: protocol::TSession<IO> + DepthOf,
+    <L as DepthOf>::Depth: NatMax<<R as DepthOf>::Depth>,
+{
+    type Depth = protocol::Succ<<<L as DepthOf>::Depth as NatMax<<R as DepthOf>::Depth>>::Output>;
+}
+impl<IO, Lbl, Branches> DepthOf for protocol::TChoiceN<IO, Lbl, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: protocol::SessionList<IO> + DepthOfEach,
+{
+    type Depth = protocol::Succ<<Branches as DepthOfEach>::Depth>;
+}
+impl<IO, Lbl, Chooser, Branches> DepthOf for protocol::TSelect<IO, Lbl, Chooser, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: protocol::SessionList<IO> + DepthOfEach,
+{
+    type Depth = protocol::Succ<<Branches as DepthOfEach>::Depth>;
+}
+impl<IO, Lbl, Offeree, Branches> DepthOf for protocol::TOffer<IO, Lbl, Offeree, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: protocol::SessionList<IO> + DepthOfEach,
+{
+    type Depth = protocol::Succ<<Branches as DepthOfEach>::Depth>;
+}
+impl<IO, Lbl, High, Low> DepthOf for protocol::TPriorityChoice<IO, Lbl, High, Low>
+where
+    Lbl: types::ProtocolLabel,
+    High: protocol::TSession<IO> + DepthOf,
+    Low: protocol::TSession<IO> + DepthOf,
+    <High as DepthOf>::Depth: NatMax<<Low as DepthOf>::Depth>,
+{
+    type Depth =
+        protocol::Succ<<<High as DepthOf>::Depth as NatMax<<Low as DepthOf>::Depth>>::Output>;
+}
+impl<IO, Lbl, WeightL, L, WeightR, R> DepthOf for protocol::TProb<IO, Lbl, WeightL, L, WeightR, R>
+where
+    Lbl: types::ProtocolLabel,
+    WeightL: types::WeightMarker,
+    WeightR: types::WeightMarker,
+    L: protocol::TSession<IO> + DepthOf,
+    R: protocol::TSession<IO> + DepthOf,
+    <L as DepthOf>::Depth: NatMax<<R as DepthOf>::Depth>,
+{
+    type Depth = protocol::Succ<<<L as DepthOf>::Depth as NatMax<<R as DepthOf>::Depth>>::Output>;
+}
+impl<IO, Lbl, L, R, IsDisjoint> DepthOf for protocol::TPar<IO, Lbl, L, R, IsDisjoint>
+where
+    Lbl: types::ProtocolLabel,
+    L: protocol::TSession<IO> + DepthOf,
+    R: protocol::TSession<IO> + DepthOf,
+    <L as DepthOf>::Depth: NatMax<<R as DepthOf>::Depth>,
+{
+    type Depth = protocol::Succ<<<L as DepthOf>::Depth as NatMax<<R as DepthOf>::Depth>>::Output>;
+}
+impl<IO, Lbl, Branches> DepthOf for protocol::TParN<IO, Lbl, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: protocol::SessionList<IO> + DepthOfEach,
+{
+    type Depth = protocol::Succ<<Branches as DepthOfEach>::Depth>;
+}
+impl<IO, Lbl, Steps> DepthOf for protocol::TShuffle<IO, Lbl, Steps>
+where
+    Lbl: types::ProtocolLabel,
+    Steps: protocol::SessionList<IO> + DepthOfEach,
+{
+    type Depth = protocol::Succ<<Steps as DepthOfEach>::Depth>;
+}
+impl<IO, Lbl, S> DepthOf for protocol::TRec<IO, Lbl, S>
+where
+    Lbl: types::ProtocolLabel,
+    S: protocol::TSession<IO> + DepthOf,
+{
+    type Depth = protocol::Succ<<S as DepthOf>::Depth>;
+}
+impl<IO, Lbl, Var, Body> DepthOf for protocol::TRecX<IO, Lbl, Var, Body>
+where
+    Lbl: types::ProtocolLabel,
+    Body: protocol::TSession<IO> + DepthOf,
+{
+    type Depth = protocol::Succ<<Body as DepthOf>::Depth>;
+}
+impl<IO, Lbl, Dur, Body, OnTimeout> DepthOf for protocol::TTimeout<IO, Lbl, Dur, Body, OnTimeout>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    Body: protocol::TSession<IO> + DepthOf,
+    OnTimeout: protocol::TSession<IO> + DepthOf,
+    <Body as DepthOf>::Depth: NatMax<<OnTimeout as DepthOf>::Depth>,
+{
+    type Depth =
+        protocol::Succ<<<Body as DepthOf>::Depth as NatMax<<OnTimeout as DepthOf>::Depth>>::Output>;
+}
+impl<IO, Lbl, R, Dur, T> DepthOf for protocol::TDelay<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: protocol::TSession<IO> + DepthOf,
+{
+    type Depth = <T as DepthOf>::Depth;
+}
+impl<IO, Lbl, R, Dur, T> DepthOf for protocol::TDeadline<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: protocol::TSession<IO> + DepthOf,
+{
+    type Depth = <T as DepthOf>::Depth;
+}
+impl<IO, Lbl, Scope, Interruptor, Handler> DepthOf
+    for protocol::TInterrupt<IO, Lbl, Scope, Interruptor, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Scope: protocol::TSession<IO> + DepthOf,
+    Handler: protocol::TSession<IO> + DepthOf,
+    <Scope as DepthOf>::Depth: NatMax<<Handler as DepthOf>::Depth>,
+{
+    type Depth =
+        protocol::Succ<<<Scope as DepthOf>::Depth as NatMax<<Handler as DepthOf>::Depth>>::Output>;
+}
+impl<IO, Lbl, Decider, Body, Cont> DepthOf for protocol::TOptional<IO, Lbl, Decider, Body, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Body: protocol::TSession<IO> + DepthOf,
+    Cont: protocol::TSession<IO> + DepthOf,
+    protocol::Succ<<Body as DepthOf>::Depth>: NatMax<<Cont as DepthOf>::Depth>,
+{
+    type Depth =
+        <protocol::Succ<<Body as DepthOf>::Depth> as NatMax<<Cont as DepthOf>::Depth>>::Output;
+}
+impl<IO, Lbl, R, Err> DepthOf for protocol::TThrow<IO, Lbl, R, Err>
+where
+    Lbl: types::ProtocolLabel,
+{
+    type Depth = protocol::Zero;
+}
+impl<IO, Lbl, Body, Handler> DepthOf for protocol::TCatch<IO, Lbl, Body, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Body: protocol::TSession<IO> + DepthOf,
+    Handler: protocol::TSession<IO> + DepthOf,
+    <Body as DepthOf>::Depth: NatMax<<Handler as DepthOf>::Depth>,
+{
+    type Depth =
+        protocol::Succ<<<Body as DepthOf>::Depth as NatMax<<Handler as DepthOf>::Depth>>::Output>;
+}
+impl<Var> DepthOf for protocol::TVar<Var> {
+    type Depth = protocol::Zero;
+}
+impl<Pred, Inner: DepthOf> DepthOf for protocol::TRefine<Pred, Inner> {
+    type Depth = <Inner as DepthOf>::Depth;
+}
+impl<Meta, Inner: DepthOf> DepthOf for protocol::TAnnotate<Meta, Inner> {
+    type Depth = <Inner as DepthOf>::Depth;
+}
+// Local session types, measured like the global combinators they are
+// projected from
+impl<IO, Lbl, R> DepthOf for protocol::EpEnd<IO, Lbl, R>
+where
+    Lbl: types::ProtocolLabel,
+{
+    type Depth = protocol::Zero;
+}
+impl<IO, Lbl, R> DepthOf for protocol::EpSkip<IO, Lbl, R>
+where
+    Lbl: types::ProtocolLabel,
+{
+    type Depth = protocol::Zero;
+}
+impl<IO, Lbl, R, H, T> DepthOf for protocol::EpSend<IO, Lbl, R, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: DepthOf,
+{
+    type Depth = <T as DepthOf>::Depth;
+}
+impl<IO, Lbl, R, H, T> DepthOf for protocol::EpRecv<IO, Lbl, R, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: DepthOf,
+{
+    type Depth = <T as DepthOf>::Depth;
+}
+impl<IO, Lbl, Me, ToSet, H, T> DepthOf for protocol::EpBroadcast<IO, Lbl, Me, ToSet, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: DepthOf,
+{
+    type Depth = <T as DepthOf>::Depth;
+}
+impl<IO, Lbl, Me, FromSet, H, T> DepthOf for protocol::EpGather<IO, Lbl, Me, FromSet, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: DepthOf,
+{
+    type Depth = <T as DepthOf>::Depth;
+}
+impl<IO, Lbl, Me, DelegatedEp, Cont> DepthOf
+    for protocol::EpDelegate<IO, Lbl, Me, DelegatedEp, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Cont: DepthOf,
+{
+    type Depth = <Cont as DepthOf>::Depth;
+}
+impl<IO, Lbl, Me, Branches> DepthOf for protocol::EpChoiceN<IO, Lbl, Me, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: DepthOfEach,
+{
+    type Depth = protocol::Succ<<Branches as DepthOfEach>::Depth>;
+}
+impl<IO, Lbl, Me, Branches> DepthOf for protocol::EpSelect<IO, Lbl, Me, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: DepthOfEach,
+{
+    type Depth = protocol::Succ<<Branches as DepthOfEach>::Depth>;
+}
+impl<IO, Lbl, Me, Branches> DepthOf for protocol::EpOffer<IO, Lbl, Me, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: DepthOfEach,
+{
+    type Depth = protocol::Succ<<Branches as DepthOfEach>::Depth>;
+}
+impl<IO, Lbl, Me, L, R> DepthOf for protocol::EpPar<IO, Lbl, Me, L, R>
+where
+    Lbl: types::ProtocolLabel,
+    L: DepthOf,
+    R: DepthOf,
+    <L as DepthOf>::Depth: NatMax<<R as DepthOf>::Depth>,
+{
+    type Depth = protocol::Succ<<<L as DepthOf>::Depth as NatMax<<R as DepthOf>::Depth>>::Output>;
+}
+impl<IO, Lbl, Me, Branches> DepthOf for protocol::EpParN<IO, Lbl, Me, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: DepthOfEach,
+{
+    type Depth = protocol::Succ<<Branches as DepthOfEach>::Depth>;
+}
+impl<IO, Lbl, Me, Steps> DepthOf for protocol::EpShuffle<IO, Lbl, Me, Steps>
+where
+    Lbl: types::ProtocolLabel,
+    Steps: DepthOfEach,
+{
+    type Depth = protocol::Succ<<Steps as DepthOfEach>::Depth>;
+}
+impl<IO, Lbl, Var, Body> DepthOf for protocol::EpRec<IO, Lbl, Var, Body>
+where
+    Lbl: types::ProtocolLabel,
+    Body: DepthOf,
+{
+    type Depth = protocol::Succ<<Body as DepthOf>::Depth>;
+}
+impl<IO, Lbl, Me, Dur, Body, OnTimeout> DepthOf
+    for protocol::EpTimeout<IO, Lbl, Me, Dur, Body, OnTimeout>
+where
+    Lbl: types::ProtocolLabel,
+    Body: DepthOf,
+    OnTimeout: DepthOf,
+    <Body as DepthOf>::Depth: NatMax<<OnTimeout as DepthOf>::Depth>,
+{
+    type Depth =
+        protocol::Succ<<<Body as DepthOf>::Depth as NatMax<<OnTimeout as DepthOf>::Depth>>::Output>;
+}
+impl<IO, Lbl, Me, Dur, T> DepthOf for protocol::EpDelay<IO, Lbl, Me, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: DepthOf,
+{
+    type Depth = <T as DepthOf>::Depth;
+}
+impl<IO, Lbl, Me, Dur, T> DepthOf for protocol::EpDeadline<IO, Lbl, Me, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: DepthOf,
+{
+    type Depth = <T as DepthOf>::Depth;
+}
+impl<IO, Lbl, Me, Scope, Handler> DepthOf for protocol::EpInterrupt<IO, Lbl, Me, Scope, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Scope: DepthOf,
+    Handler: DepthOf,
+    <Scope as DepthOf>::Depth: NatMax<<Handler as DepthOf>::Depth>,
+{
+    type Depth =
+        protocol::Succ<<<Scope as DepthOf>::Depth as NatMax<<Handler as DepthOf>::Depth>>::Output>;
+}
+impl<IO, Lbl, Me, Scope, Handler> DepthOf for protocol::EpInterruptible<IO, Lbl, Me, Scope, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Scope: DepthOf,
+    Handler: DepthOf,
+    <Scope as DepthOf>::Depth: NatMax<<Handler as DepthOf>::Depth>,
+{
+    type Depth =
+        protocol::Succ<<<Scope as DepthOf>::Depth as NatMax<<Handler as DepthOf>::Depth>>::Output>;
+}
+impl<IO, Lbl, Me, Body, Cont> DepthOf for protocol::EpOptionalSelect<IO, Lbl, Me, Body, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Body: DepthOf,
+    Cont: DepthOf,
+    protocol::Succ<<Body as DepthOf>::Depth>: NatMax<<Cont as DepthOf>::Depth>,
+{
+    type Depth =
+        <protocol::Succ<<Body as DepthOf>::Depth> as NatMax<<Cont as DepthOf>::Depth>>::Output;
+}
+impl<IO, Lbl, Me, Body, Cont> DepthOf for protocol::EpOptionalOffer<IO, Lbl, Me, Body, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Body: DepthOf,
+    Cont: DepthOf,
+    protocol::Succ<<Body as DepthOf>::Depth>: NatMax<<Cont as DepthOf>::Depth>,
+{
+    type Depth =
+        <protocol::Succ<<Body as DepthOf>::Depth> as NatMax<<Cont as DepthOf>::Depth>>::Output;
+}
+impl<IO, Lbl, Me, Err> DepthOf for protocol::EpThrow<IO, Lbl, Me, Err>
+where
+    Lbl: types::ProtocolLabel,
+{
+    type Depth = protocol::Zero;
+}
+impl<IO, Lbl, Me, Body, Handler> DepthOf for protocol::EpCatch<IO, Lbl, Me, Body, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Body: DepthOf,
+    Handler: DepthOf,
+    <Body as DepthOf>::Depth: NatMax<<Handler as DepthOf>::Depth>,
+{
+    type Depth =
+        protocol::Succ<<<Body as DepthOf>::Depth as NatMax<<Handler as DepthOf>::Depth>>::Output>;
+}
+impl<IO, Lbl, Me, High, Low> DepthOf for protocol::EpPriorityOffer<IO, Lbl, Me, High, Low>
+where
+    Lbl: types::ProtocolLabel,
+    High: DepthOf,
+    Low: DepthOf,
+    <High as DepthOf>::Depth: NatMax<<Low as DepthOf>::Depth>,
+{
+    type Depth =
+        protocol::Succ<<<High as DepthOf>::Depth as NatMax<<Low as DepthOf>::Depth>>::Output>;
+}
+impl<IO, Lbl, Me, Peer, Cont> DepthOf for protocol::EpClose<IO, Lbl, Me, Peer, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Cont: DepthOf,
+{
+    type Depth = <Cont as DepthOf>::Depth;
+}
+impl<IO, Lbl, Me, Peer, Cont> DepthOf for protocol::EpOpen<IO, Lbl, Me, Peer, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Cont: DepthOf,
+{
+    type Depth = <Cont as DepthOf>::Depth;
+}
+impl<Var> DepthOf for protocol::EpVar<Var> {
+    type Depth = protocol::Zero;
+}
+impl<Pred, Inner: DepthOf> DepthOf for protocol::EpRefine<Pred, Inner> {
+    type Depth = <Inner as DepthOf>::Depth;
+}
+
+/// Counts the interactions of a protocol as a type-level natural.
+///
+/// - Counts every message, broadcast, anycast, delegation, connect, disconnect
+///   and throw. The branches of choices and parallel compositions are added
+///   up, and a loop body is counted once.
+/// - Implemented for global combinators and local session types. The
+///   interactions of a delegated endpoint belong to its own session and are
+///   not counted.
+/// - Read the count as a constant with [`Nat`].
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// type Poll = TRec<Http, EmptyLabel, TChoice<Http, EmptyLabel,
+///     TMsg<Http, EmptyLabel, TClient, TServer, Message,
+///     TMsg<Http, EmptyLabel, TServer, TClient, Response, TEnd<Http>>>,
+///     TEnd<Http>>>;
+/// assert_eq!(<<Poll as LengthOf>::Length as Nat>::VALUE, 2);
+/// ```
+pub trait LengthOf {
+    type Length;
+}
+impl<IO, Lbl> LengthOf for protocol::TEnd<IO, Lbl> {
+    type Length = protocol::Zero;
+}
+impl<IO, Lbl, R, H, T> LengthOf for protocol::TInteract<IO, Lbl, R, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: protocol::TSession<IO> + LengthOf,
+{
+    type Length = protocol::Succ<<T as LengthOf>::Length>;
+}
+impl<IO, Lbl, From, To, H, T> LengthOf for protocol::TMsg<IO, Lbl, From, To, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: protocol::TSession<IO> + LengthOf,
+{
+    type Length = protocol::Succ<<T as LengthOf>::Length>;
+}
+impl<IO, Lbl, From, ToSet, H, T> LengthOf for protocol::TBroadcast<IO, Lbl, From, ToSet, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: protocol::TSession<IO> + LengthOf,
+{
+    type Length = protocol::Succ<<T as LengthOf>::Length>;
+}
+impl<IO, Lbl, From, WorkerSet, H, T> LengthOf for protocol::TAnycast<IO, Lbl, From, WorkerSet, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: protocol::TSession<IO> + LengthOf,
+{
+    type Length = protocol::Succ<<T as LengthOf>::Length>;
+}
+impl<IO, Lbl, From, To, DelegatedEp, T> LengthOf
+    for protocol::TDelegate<IO, Lbl, From, To, DelegatedEp, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: protocol::TSession<IO> + LengthOf,
+{
+    type Length = protocol::Succ<<T as LengthOf>::Length>;
+}
+impl<IO, Lbl, A, B, Cont> LengthOf for protocol::TDisconnect<IO, Lbl, A, B, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Cont: protocol::TSession<IO> + LengthOf,
+{
+    type Length = protocol::Succ<<Cont as LengthOf>::Length>;
+}
+impl<IO, Lbl, A, B, Cont> LengthOf for protocol::TConnect<IO, Lbl, A, B, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Cont: protocol::TSession<IO> + LengthOf,
+{
+    type Length = protocol::Succ<<Cont as LengthOf>::Length>;
+}
+impl<IO, Lbl, R, Cont> LengthOf for protocol::TEndFor<IO, Lbl, R, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Cont: protocol::TSession<IO> + LengthOf,
+{
+    type Length = <Cont as LengthOf>::Length;
+}
+impl<IO, Lbl, L, R> LengthOf for protocol::TChoice<IO, Lbl, L, R>
+where
+    Lbl: types::ProtocolLabel,
+    L: protocol::TSession<IO> + LengthOf,
+    R: protocol::TSession<IO> + LengthOf,
+    <L as LengthOf>::Length: NatAdd<<R as LengthOf>::Length>,
+{
+    type Length = <<L as LengthOf>::Length as NatAdd<<R as LengthOf>::Length>>::Output;
+}
+impl<IO, Lbl, Branches> LengthOf for protocol::TChoiceN<IO, Lbl, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: protocol::SessionList<IO> + LengthOfEach,
+{
+    type Length = <Branches as LengthOfEach>::Length;
+}
+impl<IO, Lbl, Chooser, Branches> LengthOf for protocol::TSelect<IO, Lbl, Chooser, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: protocol::SessionList<IO> + LengthOfEach,
+{
+    type Length = <Branches as LengthOfEach>::Length;
+}
+impl<IO, Lbl, Offeree, Branches> LengthOf for protocol::TOffer<IO, Lbl, Offeree, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: protocol::SessionList<IO> + LengthOfEach,
+{
+    type Length = <Branches as LengthOfEach>::Length;
+}
+impl<IO, Lbl, High, Low> LengthOf for protocol::TPriorityChoice<IO, Lbl, High, Low>
+where
+    Lbl: types::ProtocolLabel,
+    High: protocol::TSession<IO> + LengthOf,
+    Low: protocol::TSession<IO> + LengthOf,
+    <High as LengthOf>::Length: NatAdd<<Low as LengthOf>::Length>,
+{
+    type Length = <<High as LengthOf>::Length as NatAdd<<Low as LengthOf>::Length>>::Output;
+}
+impl<IO, Lbl, WeightL, L, WeightR, R> LengthOf for protocol::TProb<IO, Lbl, WeightL, L, WeightR, R>
+where
+    Lbl: types::ProtocolLabel,
+    WeightL: types::WeightMarker,
+    WeightR: types::WeightMarker,
+    L: protocol::TSession<IO> + LengthOf,
+    R: protocol::TSession<IO> + LengthOf,
+    <L as LengthOf>::Length: NatAdd<<R as LengthOf>::Length>,
+{
+    type Length = <<L as LengthOf>::Length as NatAdd<<R as LengthOf>::Length>>::Output;
+}
+impl<IO, Lbl, L, R, IsDisjoint> LengthOf for protocol::TPar<IO, Lbl, L, R, IsDisjoint>
+where
+    Lbl: types::ProtocolLabel,
+    L: protocol::TSession<IO> + LengthOf,
+    R: protocol::TSession<IO> + LengthOf,
+    <L as LengthOf>::Length: NatAdd<<R as LengthOf>::Length>,
+{
+    type Length = <<L as LengthOf>::Length as NatAdd<<R as LengthOf>::Length>>::Output;
+}
+impl<IO, Lbl, Branches> LengthOf for protocol::TParN<IO, Lbl, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: protocol::SessionList<IO> + LengthOfEach,
+{
+    type Length = <Branches as LengthOfEach>::Length;
+}
+impl<IO, Lbl, Steps> LengthOf for protocol::TShuffle<IO, Lbl, Steps>
+where
+    Lbl: types::ProtocolLabel,
+    Steps: protocol::SessionList<IO> + LengthOfEach,
+{
+    type Length = <Steps as LengthOfEach>::Length;
+}
+impl<IO, Lbl, S> LengthOf for protocol::TRec<IO, Lbl, S>
+where
+    Lbl: types::ProtocolLabel,
+    S: protocol::TSession<IO> + LengthOf,
+{
+    type Length = <S as LengthOf>::Length;
+}
+impl<IO, Lbl, Var, Body> LengthOf for protocol::TRecX<IO, Lbl, Var, Body>
+where
+    Lbl: types::ProtocolLabel,
+    Body: protocol::TSession<IO> + LengthOf,
+{
+    type Length = <Body as LengthOf>::Length;
+}
+impl<IO, Lbl, Dur, Body, OnTimeout> LengthOf for protocol::TTimeout<IO, Lbl, Dur, Body, OnTimeout>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    Body: protocol::TSession<IO> + LengthOf,
+    OnTimeout: protocol::TSession<IO> + LengthOf,
+    <Body as LengthOf>::Length: NatAdd<<OnTimeout as LengthOf>::Length>,
+{
+    type Length = <<Body as LengthOf>::Length as NatAdd<<OnTimeout as LengthOf>::Length>>::Output;
+}
+impl<IO, Lbl, R, Dur, T> LengthOf for protocol::TDelay<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: protocol::TSession<IO> + LengthOf,
+{
+    type Length = <T as LengthOf>::Length;
+}
+impl<IO, Lbl, R, Dur, T> LengthOf for protocol::TDeadline<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: protocol::TSession<IO> + LengthOf,
+{
+    type Length = <T as LengthOf>::Length;
+}
+impl<IO, Lbl, Scope, Interruptor, Handler> LengthOf
+    for protocol::TInterrupt<IO, Lbl, Scope, Interruptor, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Scope: protocol::TSession<IO> + LengthOf,
+    Handler: protocol::TSession<IO> + LengthOf,
+    <Scope as LengthOf>::Length: NatAdd<<Handler as LengthOf>::Length>,
+{
+    type Length = <<Scope as LengthOf>::Length as NatAdd<<Handler as LengthOf>::Length>>::Output;
+}
+impl<IO, Lbl, Decider, Body, Cont> LengthOf for protocol::TOptional<IO, Lbl, Decider, Body, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Body: protocol::TSession<IO> + LengthOf,
+    Cont: protocol::TSession<IO> + LengthOf,
+    <Body as LengthOf>::Length: NatAdd<<Cont as LengthOf>::Length>,
+{
+    type Length = <<Body as LengthOf>::Length as NatAdd<<Cont as LengthOf>::Length>>::Output;
+}
+impl<IO, Lbl, R, Err> LengthOf for protocol::TThrow<IO, Lbl, R, Err>
+where
+    Lbl: types::ProtocolLabel,
+{
+    type Length = protocol::Succ<protocol::Zero>;
+}
+impl<IO, Lbl, Body, Handler> LengthOf for protocol::TCatch<IO, Lbl, Body, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Body: protocol::TSession<IO> + LengthOf,
+    Handler: protocol::TSession<IO> + LengthOf,
+    <Body as LengthOf>::Length: NatAdd<<Handler as LengthOf>::Length>,
+{
+    type Length = <<Body as LengthOf>::Length as NatAdd<<Handler as LengthOf>::Length>>::Output;
+}
+impl<Var> LengthOf for protocol::TVar<Var> {
+    type Length = protocol::Zero;
+}
+impl<Pred, Inner: LengthOf> LengthOf for protocol::TRefine<Pred, Inner> {
+    type Length = <Inner as LengthOf>::Length;
+}
+impl<Meta, Inner: LengthOf> LengthOf for protocol::TAnnotate<Meta, Inner> {
+    type Length = <Inner as LengthOf>::Length;
+}
+// Local session types, measured like the global combinators they are
+// projected from
+impl<IO, Lbl, R> LengthOf for protocol::EpEnd<IO, Lbl, R>
+where
+    Lbl: types::ProtocolLabel,
+{
+    type Length = protocol::Zero;
+}
+impl<IO, Lbl, R> LengthOf for protocol::EpSkip<IO, Lbl, R>
+where
+    Lbl: types::ProtocolLabel,
+{
+    type Length = protocol::Zero;
+}
+impl<IO, Lbl, R, H, T> LengthOf for protocol::EpSend<IO, Lbl, R, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: LengthOf,
+{
+    type Length = protocol::Succ<<T as LengthOf>::Length>;
+}
+impl<IO, Lbl, R, H, T> LengthOf for protocol::EpRecv<IO, Lbl, R, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: LengthOf,
+{
+    type Length = protocol::Succ<<T as LengthOf>::Length>;
+}
+impl<IO, Lbl, Me, ToSet, H, T> LengthOf for protocol::EpBroadcast<IO, Lbl, Me, ToSet, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: LengthOf,
+{
+    type Length = protocol::Succ<<T as LengthOf>::Length>;
+}
+impl<IO, Lbl, Me, FromSet, H, T> LengthOf for protocol::EpGather<IO, Lbl, Me, FromSet, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: LengthOf,
+{
+    type Length = protocol::Succ<<T as LengthOf>::Length>;
+}
+impl<IO, Lbl, Me, DelegatedEp, Cont> LengthOf
+    for protocol::EpDelegate<IO, Lbl, Me, DelegatedEp, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Cont: LengthOf,
+{
+    type Length = protocol::Succ<<Cont as LengthOf>::Length>;
+}
+impl<IO, Lbl, Me, Branches> LengthOf for protocol::EpChoiceN<IO, Lbl, Me, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: LengthOfEach,
+{
+    type Length = <Branches as LengthOfEach>::Length;
+}
+impl<IO, Lbl, Me, Branches> LengthOf for protocol::EpSelect<IO, Lbl, Me, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: LengthOfEach,
+{
+    type Length = <Branches as LengthOfEach>::Length;
+}
+impl<IO, Lbl, Me, Branches> LengthOf for protocol::EpOffer<IO, Lbl, Me, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: LengthOfEach,
+{
+    type Length = <Branches as LengthOfEach>::Length;
+}
+impl<IO, Lbl, Me, L, R> LengthOf for protocol::EpPar<IO, Lbl, Me, L, R>
+where
+    Lbl: types::ProtocolLabel,
+    L: LengthOf,
+    R: LengthOf,
+    <L as LengthOf>::Length: NatAdd<<R as LengthOf>::Length>,
+{
+    type Length = <<L as LengthOf>::Length as NatAdd<<R as LengthOf>::Length>>::Output;
+}
+impl<IO, Lbl, Me, Branches> LengthOf for protocol::EpParN<IO, Lbl, Me, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: LengthOfEach,
+{
+    type Length = <Branches as LengthOfEach>::Length;
+}
+impl<IO, Lbl, Me, Steps> LengthOf for protocol::EpShuffle<IO, Lbl, Me, Steps>
+where
+    Lbl: types::ProtocolLabel,
+    Steps: LengthOfEach,
+{
+    type Length = <Steps as LengthOfEach>::Length;
+}
+impl<IO, Lbl, Var, Body> LengthOf for protocol::EpRec<IO, Lbl, Var, Body>
+where
+    Lbl: types::ProtocolLabel,
+    Body: LengthOf,
+{
+    type Length = <Body as LengthOf>::Length;
+}
+impl<IO, Lbl, Me, Dur, Body, OnTimeout> LengthOf
+    for protocol::EpTimeout<IO, Lbl, Me, Dur, Body, OnTimeout>
+where
+    Lbl: types::ProtocolLabel,
+    Body: LengthOf,
+    OnTimeout: LengthOf,
+    <Body as LengthOf>::Length: NatAdd<<OnTimeout as LengthOf>::Length>,
+{
+    type Length = <<Body as LengthOf>::Length as NatAdd<<OnTimeout as LengthOf>::Length>>::Output;
+}
+impl<IO, Lbl, Me, Dur, T> LengthOf for protocol::EpDelay<IO, Lbl, Me, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: LengthOf,
+{
+    type Length = <T as LengthOf>::Length;
+}
+impl<IO, Lbl, Me, Dur, T> LengthOf for protocol::EpDeadline<IO, Lbl, Me, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: LengthOf,
+{
+    type Length = <T as LengthOf>::Length;
+}
+impl<IO, Lbl, Me, Scope, Handler> LengthOf for protocol::EpInterrupt<IO, Lbl, Me, Scope, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Scope: LengthOf,
+    Handler: LengthOf,
+    <Scope as LengthOf>::Length: NatAdd<<Handler as LengthOf>::Length>,
+{
+    type Length = <<Scope as LengthOf>::Length as NatAdd<<Handler as LengthOf>::Length>>::Output;
+}
+impl<IO, Lbl, Me, Scope, Handler> LengthOf
+    for protocol::EpInterruptible<IO, Lbl, Me, Scope, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Scope: LengthOf,
+    Handler: LengthOf,
+    <Scope as LengthOf>::Length: NatAdd<<Handler as LengthOf>::Length>,
+{
+    type Length = <<Scope as LengthOf>::Length as NatAdd<<Handler as LengthOf>::Length>>::Output;
+}
+impl<IO, Lbl, Me, Body, Cont> LengthOf for protocol::EpOptionalSelect<IO, Lbl, Me, Body, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Body: LengthOf,
+    Cont: LengthOf,
+    <Body as LengthOf>::Length: NatAdd<<Cont as LengthOf>::Length>,
+{
+    type Length = <<Body as LengthOf>::Length as NatAdd<<Cont as LengthOf>::Length>>::Output;
+}
+impl<IO, Lbl, Me, Body, Cont> LengthOf for protocol::EpOptionalOffer<IO, Lbl, Me, Body, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Body: LengthOf,
+    Cont: LengthOf,
+    <Body as LengthOf>::Length: NatAdd<<Cont as LengthOf>::Length>,
+{
+    type Length = <<Body as LengthOf>::Length as NatAdd<<Cont as LengthOf>::Length>>::Output;
+}
+impl<IO, Lbl, Me, Err> LengthOf for protocol::EpThrow<IO, Lbl, Me, Err>
+where
+    Lbl: types::ProtocolLabel,
+{
+    type Length = protocol::Succ<protocol::Zero>;
+}
+impl<IO, Lbl, Me, Body, Handler> LengthOf for protocol::EpCatch<IO, Lbl, Me, Body, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Body: LengthOf,
+    Handler: LengthOf,
+    <Body as LengthOf>::Length: NatAdd<<Handler as LengthOf>::Length>,
+{
+    type Length = <<Body as LengthOf>::Length as NatAdd<<Handler as LengthOf>::Length>>::Output;
+}
+impl<IO, Lbl, Me, High, Low> LengthOf for protocol::EpPriorityOffer<IO, Lbl, Me, High, Low>
+where
+    Lbl: types::ProtocolLabel,
+    High: LengthOf,
+    Low: LengthOf,
+    <High as LengthOf>::Length: NatAdd<<Low as LengthOf>::Length>,
+{
+    type Length = <<High as LengthOf>::Length as NatAdd<<Low as LengthOf>::Length>>::Output;
+}
+impl<IO, Lbl, Me, Peer, Cont> LengthOf for protocol::EpClose<IO, Lbl, Me, Peer, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Cont: LengthOf,
+{
+    type Length = protocol::Succ<<Cont as LengthOf>::Length>;
+}
+impl<IO, Lbl, Me, Peer, Cont> LengthOf for protocol::EpOpen<IO, Lbl, Me, Peer, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Cont: LengthOf,
+{
+    type Length = protocol::Succ<<Cont as LengthOf>::Length>;
+}
+impl<Var> LengthOf for protocol::EpVar<Var> {
+    type Length = protocol::Zero;
+}
+impl<Pred, Inner: LengthOf> LengthOf for protocol::EpRefine<Pred, Inner> {
+    type Length = <Inner as LengthOf>::Length;
+}
+
+/// Whether role `Me` sends or receives in an interaction.
+///
+/// - Implemented for the interactions of global protocols: messages,
+///   broadcasts, anycasts, delegations, connects, disconnects, throws and
+///   `TInteract`. A role takes part in a broadcast or an anycast if it is the
+///   sender or one of the receivers.
+/// - Roles are compared with `RoleEq`, and with `InRoleSet` for role sets.
+pub trait TakesPart<Me> {
+    type Output: types::Bool;
+}
+impl<IO, Lbl, R, H, T, Me> TakesPart<Me> for protocol::TInteract<IO, Lbl, R, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: protocol::TSession<IO>,
+    R: protocol::RoleEq<Me>,
+    <R as protocol::RoleEq<Me>>::Output: types::Bool,
+{
+    type Output = <R as protocol::RoleEq<Me>>::Output;
+}
+impl<IO, Lbl, From, To, H, T, Me> TakesPart<Me> for protocol::TMsg<IO, Lbl, From, To, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: protocol::TSession<IO>,
+    From: protocol::RoleEq<Me>,
+    To: protocol::RoleEq<Me>,
+    <From as protocol::RoleEq<Me>>::Output: types::BoolOr<<To as protocol::RoleEq<Me>>::Output>,
+{
+    type Output =
+        types::Or<<From as protocol::RoleEq<Me>>::Output, <To as protocol::RoleEq<Me>>::Output>;
+}
+impl<IO, Lbl, From, ToSet, H, T, Me> TakesPart<Me>
+    for protocol::TBroadcast<IO, Lbl, From, ToSet, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: protocol::TSession<IO>,
+    From: protocol::RoleEq<Me>,
+    Me: protocol::InRoleSet<ToSet>,
+    <From as protocol::RoleEq<Me>>::Output:
+        types::BoolOr<<Me as protocol::InRoleSet<ToSet>>::Output>,
+{
+    type Output = types::Or<
+        <From as protocol::RoleEq<Me>>::Output,
+        <Me as protocol::InRoleSet<ToSet>>::Output,
+    >;
+}
+impl<IO, Lbl, From, WorkerSet, H, T, Me> TakesPart<Me>
+    for protocol::TAnycast<IO, Lbl, From, WorkerSet, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: protocol::TSession<IO>,
+    From: protocol::RoleEq<Me>,
+    Me: protocol::InRoleSet<WorkerSet>,
+    <From as protocol::RoleEq<Me>>::Output:
+        types::BoolOr<<Me as protocol::InRoleSet<WorkerSet>>::Output>,
+{
+    type Output = types::Or<
+        <From as protocol::RoleEq<Me>>::Output,
+        <Me as protocol::InRoleSet<WorkerSet>>::Output,
+    >;
+}
+impl<IO, Lbl, From, To, DelegatedEp, T, Me> TakesPart<Me>
+    for protocol::TDelegate<IO, Lbl, From, To, DelegatedEp, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: protocol::TSession<IO>,
+    From: protocol::RoleEq<Me>,
+    To: protocol::RoleEq<Me>,
+    <From as protocol::RoleEq<Me>>::Output: types::BoolOr<<To as protocol::RoleEq<Me>>::Output>,
+{
+    type Output =
+        types::Or<<From as protocol::RoleEq<Me>>::Output, <To as protocol::RoleEq<Me>>::Output>;
+}
+impl<IO, Lbl, A, B, Cont, Me> TakesPart<Me> for protocol::TDisconnect<IO, Lbl, A, B, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Cont: protocol::TSession<IO>,
+    A: protocol::RoleEq<Me>,
+    B: protocol::RoleEq<Me>,
+    <A as protocol::RoleEq<Me>>::Output: types::BoolOr<<B as protocol::RoleEq<Me>>::Output>,
+{
+    type Output =
+        types::Or<<A as protocol::RoleEq<Me>>::Output, <B as protocol::RoleEq<Me>>::Output>;
+}
+impl<IO, Lbl, A, B, Cont, Me> TakesPart<Me> for protocol::TConnect<IO, Lbl, A, B, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Cont: protocol::TSession<IO>,
+    A: protocol::RoleEq<Me>,
+    B: protocol::RoleEq<Me>,
+    <A as protocol::RoleEq<Me>>::Output: types::BoolOr<<B as protocol::RoleEq<Me>>::Output>,
+{
+    type Output =
+        types::Or<<A as protocol::RoleEq<Me>>::Output, <B as protocol::RoleEq<Me>>::Output>;
+}
+impl<IO, Lbl, R, Err, Me> TakesPart<Me> for protocol::TThrow<IO, Lbl, R, Err>
+where
+    Lbl: types::ProtocolLabel,
+    R: protocol::RoleEq<Me>,
+    <R as protocol::RoleEq<Me>>::Output: types::Bool,
+{
+    type Output = <R as protocol::RoleEq<Me>>::Output;
+}
+/// Lists the interactions that can be the first one role `Me` takes part in.
+///
+/// - `Actions` is a type-level list of interactions, each with its
+///   continuation replaced by `TEnd<IO>`, in protocol order. A protocol in
+///   which every run starts the same way for `Me` lists one interaction; each
+///   branch of a choice or parallel composition may add its own, and the
+///   handler of a timeout, interrupt or catch may run first. The list is empty
+///   if `Me` takes no part.
+/// - Used to generate connection-setup code and to check that the role
+///   expected to open a session does.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Alice; struct Bob; struct Carol;
+/// impl Role for Alice {} impl Role for Bob {} impl Role for Carol {}
+/// impl RoleEq<Bob> for Alice { type Output = False; }
+/// impl RoleEq<Bob> for Bob   { type Output = True; }
+/// impl RoleEq<Bob> for Carol { type Output = False; }
+///
+/// type Session = TConnect<Http, EmptyLabel, Alice, Bob,
+///     TMsg<Http, EmptyLabel, Alice, Carol, Message,
+///     TDisconnect<Http, EmptyLabel, Alice, Bob, TEnd<Http>>>>;
+/// assert_type_eq!(
+///     <Session as FirstActionOf<Bob>>::Actions,
+///     tlist!(TConnect<Http, EmptyLabel, Alice, Bob, TEnd<Http>>)
+/// );
+/// ```
+pub trait FirstActionOf<Me> {
+    type Actions;
+}
+
+/// Helper trait for `FirstActionOf`, dispatching on whether the role takes
+/// part in `Action`; otherwise its first actions are in `Rest`.
+pub trait FirstActionCase<TakesPart, Action, Rest> {
+    type Actions;
+}
+impl<Action, Rest> FirstActionCase<types::True, Action, Rest> for () {
+    type Actions = protocol::Cons<Action, protocol::Nil>;
+}
+impl<Action, Rest> FirstActionCase<types::False, Action, Rest> for () {
+    type Actions = Rest;
+}
+impl<IO, Lbl, Me> FirstActionOf<Me> for protocol::TEnd<IO, Lbl> {
+    type Actions = protocol::Nil;
+}
+impl<IO, Lbl, R, H, T, Me> FirstActionOf<Me> for protocol::TInteract<IO, Lbl, R, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: protocol::TSession<IO> + FirstActionOf<Me>,
+    Self: TakesPart<Me>,
+    (): FirstActionCase<
+        <Self as TakesPart<Me>>::Output,
+        protocol::TInteract<IO, Lbl, R, H, protocol::TEnd<IO>>,
+        <T as FirstActionOf<Me>>::Actions,
+    >,
+{
+    type Actions = <() as FirstActionCase<
+        <Self as TakesPart<Me>>::Output,
+        protocol::TInteract<IO, Lbl, R, H, protocol::TEnd<IO>>,
+        <T as FirstActionOf<Me>>::Actions,
+    >>::Actions;
+}
+impl<IO, Lbl, From, To, H, T, Me> FirstActionOf<Me> for protocol::TMsg<IO, Lbl, From, To, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: protocol::TSession<IO> + FirstActionOf<Me>,
+    Self: TakesPart<Me>,
+    (): FirstActionCase<
+        <Self as TakesPart<Me>>::Output,
+        protocol::TMsg<IO, Lbl, From, To, H, protocol::TEnd<IO>>,
+        <T as FirstActionOf<Me>>::Actions,
+    >,
+{
+    type Actions = <() as FirstActionCase<
+        <Self as TakesPart<Me>>::Output,
+        protocol::TMsg<IO, Lbl, From, To, H, protocol::TEnd<IO>>,
+        <T as FirstActionOf<Me>>::Actions,
+    >>::Actions;
+}
+impl<IO, Lbl, From, ToSet, H, T, Me> FirstActionOf<Me>
+    for protocol::TBroadcast<IO, Lbl, From, ToSet, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: protocol::TSession<IO> + FirstActionOf<Me>,
+    Self: TakesPart<Me>,
+    (): FirstActionCase<
+        <Self as TakesPart<Me>>::Output,
+        protocol::TBroadcast<IO, Lbl, From, ToSet, H, protocol::TEnd<IO>>,
+        <T as FirstActionOf<Me>>::Actions,
+    >,
+{
+    type Actions = <() as FirstActionCase<
+        <Self as TakesPart<Me>>::Output,
+        protocol::TBroadcast<IO, Lbl, From, ToSet, H, protocol::TEnd<IO>>,
+        <T as FirstActionOf<Me>>::Actions,
+    >>::Actions;
+}
+impl<IO, Lbl, From, WorkerSet, H, T, Me> FirstActionOf<Me>
+    for protocol::TAnycast<IO, Lbl, From, WorkerSet, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: protocol::TSession<IO> + FirstActionOf<Me>,
+    Self: TakesPart<Me>,
+    (): FirstActionCase<
+        <Self as TakesPart<Me>>::Output,
+        protocol::TAnycast<IO, Lbl, From, WorkerSet, H, protocol::TEnd<IO>>,
+        <T as FirstActionOf<Me>>::Actions,
+    >,
+{
+    type Actions = <() as FirstActionCase<
+        <Self as TakesPart<Me>>::Output,
+        protocol::TAnycast<IO, Lbl, From, WorkerSet, H, protocol::TEnd<IO>>,
+        <T as FirstActionOf<Me>>::Actions,
+    >>::Actions;
+}
+impl<IO, Lbl, From, To, DelegatedEp, T, Me> FirstActionOf<Me>
+    for protocol::TDelegate<IO, Lbl, From, To, DelegatedEp, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: protocol::TSession<IO> + FirstActionOf<Me>,
+    Self: TakesPart<Me>,
+    (): FirstActionCase<
+        <Self as TakesPart<Me>>::Output,
+        protocol::TDelegate<IO, Lbl, From, To, DelegatedEp, protocol::TEnd<IO>>,
+        <T as FirstActionOf<Me>>::Actions,
+    >,
+{
+    type Actions = <() as FirstActionCase<
+        <Self as TakesPart<Me>>::Output,
+        protocol::TDelegate<IO, Lbl, From, To, DelegatedEp, protocol::TEnd<IO>>,
+        <T as FirstActionOf<Me>>::Actions,
+    >>::Actions;
+}
+impl<IO, Lbl, A, B, Cont, Me> FirstActionOf<Me> for protocol::TDisconnect<IO, Lbl, A, B, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Cont: protocol::TSession<IO> + FirstActionOf<Me>,
+    Self: TakesPart<Me>,
+    (): FirstActionCase<
+        <Self as TakesPart<Me>>::Output,
+        protocol::TDisconnect<IO, Lbl, A, B, protocol::TEnd<IO>>,
+        <Cont as FirstActionOf<Me>>::Actions,
+    >,
+{
+    type Actions = <() as FirstActionCase<
+        <Self as TakesPart<Me>>::Output,
+        protocol::TDisconnect<IO, Lbl, A, B, protocol::TEnd<IO>>,
+        <Cont as FirstActionOf<Me>>::Actions,
+    >>::Actions;
+}
+impl<IO, Lbl, A, B, Cont, Me> FirstActionOf<Me> for protocol::TConnect<IO, Lbl, A, B, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Cont: protocol::TSession<IO> + FirstActionOf<Me>,
+    Self: TakesPart<Me>,
+    (): FirstActionCase<
+        <Self as TakesPart<Me>>::Output,
+        protocol::TConnect<IO, Lbl, A, B, protocol::TEnd<IO>>,
+        <Cont as FirstActionOf<Me>>::Actions,
+    >,
+{
+    type Actions = <() as FirstActionCase<
+        <Self as TakesPart<Me>>::Output,
+        protocol::TConnect<IO, Lbl, A, B, protocol::TEnd<IO>>,
+        <Cont as FirstActionOf<Me>>::Actions,
+    >>::Actions;
+}
+impl<IO, Lbl, R, Cont, Me> FirstActionOf<Me> for protocol::TEndFor<IO, Lbl, R, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Cont: protocol::TSession<IO> + FirstActionOf<Me>,
+{
+    type Actions = <Cont as FirstActionOf<Me>>::Actions;
+}
+impl<IO, Lbl, L, R, Me> FirstActionOf<Me> for protocol::TChoice<IO, Lbl, L, R>
+where
+    Lbl: types::ProtocolLabel,
+    L: protocol::TSession<IO> + FirstActionOf<Me>,
+    R: protocol::TSession<IO> + FirstActionOf<Me>,
+    <L as FirstActionOf<Me>>::Actions: protocol::Concat<<R as FirstActionOf<Me>>::Actions>,
+{
+    type Actions = <<L as FirstActionOf<Me>>::Actions as protocol::Concat<
+        <R as FirstActionOf<Me>>::Actions,
+    >>::Output;
+}
+impl<IO, Lbl, Branches, Me> FirstActionOf<Me> for protocol::TChoiceN<IO, Lbl, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: protocol::SessionList<IO> + FirstActionOfEach<Me>,
+{
+    type Actions = <Branches as FirstActionOfEach<Me>>::Actions;
+}
+impl<IO, Lbl, Chooser, Branches, Me> FirstActionOf<Me>
+    for protocol::TSelect<IO, Lbl, Chooser, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: protocol::SessionList<IO> + FirstActionOfEach<Me>,
+{
+    type Actions = <Branches as FirstActionOfEach<Me>>::Actions;
+}
+impl<IO, Lbl, Offeree, Branches, Me> FirstActionOf<Me>
+    for protocol::TOffer<IO, Lbl, Offeree, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: protocol::SessionList<IO> + FirstActionOfEach<Me>,
+{
+    type Actions = <Branches as FirstActionOfEach<Me>>::Actions;
+}
+impl<IO, Lbl, High, Low, Me> FirstActionOf<Me> for protocol::TPriorityChoice<IO, Lbl, High, Low>
+where
+    Lbl: types::ProtocolLabel,
+    High: protocol::TSession<IO> + FirstActionOf<Me>,
+    Low: protocol::TSession<IO> + FirstActionOf<Me>,
+    <High as FirstActionOf<Me>>::Actions: protocol::Concat<<Low as FirstActionOf<Me>>::Actions>,
+{
+    type Actions = <<High as FirstActionOf<Me>>::Actions as protocol::Concat<
+        <Low as FirstActionOf<Me>>::Actions,
+    >>::Output;
+}
+impl<IO, Lbl, WeightL, L, WeightR, R, Me> FirstActionOf<Me>
+    for protocol::TProb<IO, Lbl, WeightL, L, WeightR, R>
+where
+    Lbl: types::ProtocolLabel,
+    WeightL: types::WeightMarker,
+    WeightR: types::WeightMarker,
+    L: protocol::TSession<IO> + FirstActionOf<Me>,
+    R: protocol::TSession<IO> + FirstActionOf<Me>,
+    <L as FirstActionOf<Me>>::Actions: protocol::Concat<<R as FirstActionOf<Me>>::Actions>,
+{
+    type Actions = <<L as FirstActionOf<Me>>::Actions as protocol::Concat<
+        <R as FirstActionOf<Me>>::Actions,
+    >>::Output;
+}
+impl<IO, Lbl, L, R, IsDisjoint, Me> FirstActionOf<Me> for protocol::TPar<IO, Lbl, L, R, IsDisjoint>
+where
+    Lbl: types::ProtocolLabel,
+    L: protocol::TSession<IO> + FirstActionOf<Me>,
+    R: protocol::TSession<IO> + FirstActionOf<Me>,
+    <L as FirstActionOf<Me>>::Actions: protocol::Concat<<R as FirstActionOf<Me>>::Actions>,
+{
+    type Actions = <<L as FirstActionOf<Me>>::Actions as protocol::Concat<
+        <R as FirstActionOf<Me>>::Actions,
+    >>::Output;
+}
+impl<IO, Lbl, Branches, Me> FirstActionOf<Me> for protocol::TParN<IO, Lbl, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: protocol::SessionList<IO> + FirstActionOfEach<Me>,
+{
+    type Actions = <Branches as FirstActionOfEach<Me>>::Actions;
+}
+impl<IO, Lbl, Steps, Me> FirstActionOf<Me> for protocol::TShuffle<IO, Lbl, Steps>
+where
+    Lbl: types::ProtocolLabel,
+    Steps: protocol::SessionList<IO> + FirstActionOfEach<Me>,
+{
+    type Actions = <Steps as FirstActionOfEach<Me>>::Actions;
+}
+impl<IO, Lbl, S, Me> FirstActionOf<Me> for protocol::TRec<IO, Lbl, S>
+where
+    Lbl: types::ProtocolLabel,
+    S: protocol::TSession<IO> + FirstActionOf<Me>,
+{
+    type Actions = <S as FirstActionOf<Me>>::Actions;
+}
+impl<IO, Lbl, Var, Body, Me> FirstActionOf<Me> for protocol::TRecX<IO, Lbl, Var, Body>
+where
+    Lbl: types::ProtocolLabel,
+    Body: protocol::TSession<IO> + FirstActionOf<Me>,
+{
+    type Actions = <Body as FirstActionOf<Me>>::Actions;
+}
+impl<Var, Me> FirstActionOf<Me> for protocol::TVar<Var> {
+    type Actions = protocol::Nil;
+}
+impl<IO, Lbl, Dur, Body, OnTimeout, Me> FirstActionOf<Me>
+    for protocol::TTimeout<IO, Lbl, Dur, Body, OnTimeout>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    Body: protocol::TSession<IO> + FirstActionOf<Me>,
+    OnTimeout: protocol::TSession<IO> + FirstActionOf<Me>,
+    <Body as FirstActionOf<Me>>::Actions:
+        protocol::Concat<<OnTimeout as FirstActionOf<Me>>::Actions>,
+{
+    type Actions = <<Body as FirstActionOf<Me>>::Actions as protocol::Concat<
+        <OnTimeout as FirstActionOf<Me>>::Actions,
+    >>::Output;
+}
+impl<IO, Lbl, R, Dur, T, Me> FirstActionOf<Me> for protocol::TDelay<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: protocol::TSession<IO> + FirstActionOf<Me>,
+{
+    type Actions = <T as FirstActionOf<Me>>::Actions;
+}
+impl<IO, Lbl, R, Dur, T, Me> FirstActionOf<Me> for protocol::TDeadline<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: protocol::TSession<IO> + FirstActionOf<Me>,
+{
+    type Actions = <T as FirstActionOf<Me>>::Actions;
+}
+impl<IO, Lbl, Scope, Interruptor, Handler, Me> FirstActionOf<Me>
+    for protocol::TInterrupt<IO, Lbl, Scope, Interruptor, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Scope: protocol::TSession<IO> + FirstActionOf<Me>,
+    Handler: protocol::TSession<IO> + FirstActionOf<Me>,
+    <Scope as FirstActionOf<Me>>::Actions:
+        protocol::Concat<<Handler as FirstActionOf<Me>>::Actions>,
+{
+    type Actions = <<Scope as FirstActionOf<Me>>::Actions as protocol::Concat<
+        <Handler as FirstActionOf<Me>>::Actions,
+    >>::Output;
+}
+impl<IO, Lbl, Decider, Body, Cont, Me> FirstActionOf<Me>
+    for protocol::TOptional<IO, Lbl, Decider, Body, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Body: protocol::TSession<IO> + FirstActionOf<Me>,
+    Cont: protocol::TSession<IO> + FirstActionOf<Me>,
+    <Body as FirstActionOf<Me>>::Actions: protocol::Concat<<Cont as FirstActionOf<Me>>::Actions>,
+{
+    type Actions = <<Body as FirstActionOf<Me>>::Actions as protocol::Concat<
+        <Cont as FirstActionOf<Me>>::Actions,
+    >>::Output;
+}
+impl<Pred, Inner, Me> FirstActionOf<Me> for protocol::TRefine<Pred, Inner>
+where
+    Inner: FirstActionOf<Me>,
+{
+    type Actions = <Inner as FirstActionOf<Me>>::Actions;
+}
+impl<Meta, Inner, Me> FirstActionOf<Me> for protocol::TAnnotate<Meta, Inner>
+where
+    Inner: FirstActionOf<Me>,
+{
+    type Actions = <Inner as FirstActionOf<Me>>::Actions;
+}
+impl<IO, Lbl, R, Err, Me> FirstActionOf<Me> for protocol::TThrow<IO, Lbl, R, Err>
+where
+    Lbl: types::ProtocolLabel,
+    Self: TakesPart<Me>,
+    (): FirstActionCase<
+        <Self as TakesPart<Me>>::Output,
+        protocol::TThrow<IO, Lbl, R, Err>,
+        protocol::Nil,
+    >,
+{
+    type Actions = <() as FirstActionCase<
+        <Self as TakesPart<Me>>::Output,
+        protocol::TThrow<IO, Lbl, R, Err>,
+        protocol::Nil,
+    >>::Actions;
+}
+impl<IO, Lbl, Body, Handler, Me> FirstActionOf<Me> for protocol::TCatch<IO, Lbl, Body, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Body: protocol::TSession<IO> + FirstActionOf<Me>,
+    Handler: protocol::TSession<IO> + FirstActionOf<Me>,
+    <Body as FirstActionOf<Me>>::Actions: protocol::Concat<<Handler as FirstActionOf<Me>>::Actions>,
+{
+    type Actions = <<Body as FirstActionOf<Me>>::Actions as protocol::Concat<
+        <Handler as FirstActionOf<Me>>::Actions,
+    >>::Output;
+}
+/// Lists the interactions that can be the last one role `Me` takes part in.
+///
+/// - `Actions` is a type-level list of interactions, each with its
+///   continuation replaced by `TEnd<IO>`, in protocol order: those after
+///   which some run of the protocol ends without another action of `Me`.
+///   The list is empty if `Me` takes no part.
+/// - A loop may be left after any iteration, and a timeout, interrupt or
+///   catch may end its scope after any step, so their actions may be listed
+///   even where every run continues to another action of `Me`.
+/// - Used to generate teardown code and to check that the role expected to
+///   close a session does.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Alice; struct Bob; struct Carol;
+/// impl Role for Alice {} impl Role for Bob {} impl Role for Carol {}
+/// impl RoleEq<Bob> for Alice { type Output = False; }
+/// impl RoleEq<Bob> for Bob   { type Output = True; }
+/// impl RoleEq<Bob> for Carol { type Output = False; }
+///
+/// type Session = TConnect<Http, EmptyLabel, Alice, Bob,
+///     TMsg<Http, EmptyLabel, Alice, Carol, Message,
+///     TDisconnect<Http, EmptyLabel, Alice, Bob, TEnd<Http>>>>;
+/// assert_type_eq!(
+///     <Session as LastActionOf<Bob>>::Actions,
+///     tlist!(TDisconnect<Http, EmptyLabel, Alice, Bob, TEnd<Http>>)
+/// );
+/// ```
+pub trait LastActionOf<Me> {
+    type Actions;
+}
+impl<G, Me> LastActionOf<Me> for G
+where
+    G: LastActionsWith<Me, types::True>,
+{
+    type Actions = <G as LastActionsWith<Me, types::True>>::Actions;
+}
+
+/// The last actions of role `Me` in a protocol followed by more protocol.
+///
+/// - `Then` is `True` if what follows can end without an action of `Me`.
+/// - `Actions` are the last actions of `Me` within the protocol, as for
+///   [`LastActionOf`]; `Skips` is `True` if some run of the protocol and of
+///   what follows has no action of `Me`.
+pub trait LastActionsWith<Me, Then> {
+    type Actions;
+    type Skips: types::Bool;
+}
+
+/// Helper trait for `LastActionsWith`, dispatching on whether the role takes
+/// part in `Action` and whether the protocol after it, with the actions
+/// `Rest`, may end without another action of the role.
+pub trait LastActionCase<TakesPart, Action, Rest, RestSkips> {
+    type Actions;
+    type Skips: types::Bool;
+}
+impl<Action, Rest> LastActionCase<types::True, Action, Rest, types::True> for () {
+    type Actions = protocol::Cons<Action, Rest>;
+    type Skips = types::False;
+}
+impl<Action, Rest> LastActionCase<types::True, Action, Rest, types::False> for () {
+    type Actions = Rest;
+    type Skips = types::False;
+}
+impl<Action, Rest, RestSkips: types::Bool> LastActionCase<types::False, Action, Rest, RestSkips>
+    for ()
+{
+    type Actions = Rest;
+    type Skips = RestSkips;
+}
+impl<IO, Lbl, Me, Then: types::Bool> LastActionsWith<Me, Then> for protocol::TEnd<IO, Lbl> {
+    type Actions = protocol::Nil;
+    type Skips = Then;
+}
+impl<IO, Lbl, R, H, T, Me, Then: types::Bool> LastActionsWith<Me, Then>
+    for protocol::TInteract<IO, Lbl, R, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: protocol::TSession<IO> + LastActionsWith<Me, Then>,
+    Self: TakesPart<Me>,
+    (): LastActionCase<
+        <Self as TakesPart<Me>>::Output,
+        protocol::TInteract<IO, Lbl, R, H, protocol::TEnd<IO>>,
+        <T as LastActionsWith<Me, Then>>::Actions,
+        <T as LastActionsWith<Me, Then>>::Skips,
+    >,
+{
+    type Actions = <() as LastActionCase<
+        <Self as TakesPart<Me>>::Output,
+        protocol::TInteract<IO, Lbl, R, H, protocol::TEnd<IO>>,
+        <T as LastActionsWith<Me, Then>>::Actions,
+        <T as LastActionsWith<Me, Then>>::Skips,
+    >>::Actions;
+    type Skips = <() as LastActionCase<
+        <Self as TakesPart<Me>>::Output,
+        protocol::TInteract<IO, Lbl, R, H, protocol::TEnd<IO>>,
+        <T as LastActionsWith<Me, Then>>::Actions,
+        <T as LastActionsWith<Me, Then>>::Skips,
+    >>::Skips;
+}
+impl<IO, Lbl, From, To, H, T, Me, Then: types::Bool> LastActionsWith<Me, Then>
+    for protocol::TMsg<IO, Lbl, From, To, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: protocol::TSession<IO> + LastActionsWith<Me, Then>,
+    Self: TakesPart<Me>,
+    (): LastActionCase<
+        <Self as TakesPart<Me>>::Output,
+        protocol::TMsg<IO, Lbl, From, To, H, protocol::TEnd<IO>>,
+        <T as LastActionsWith<Me, Then>>::Actions,
+        <T as LastActionsWith<Me, Then>>::Skips,
+    >,
+{
+    type Actions = <() as LastActionCase<
+        <Self as TakesPart<Me>>::Output,
+        protocol::TMsg<IO, Lbl, From, To, H, protocol::TEnd<IO>>,
+        <T as LastActionsWith<Me, Then>>::Actions,
+        <T as LastActionsWith<Me, Then>>::Skips,
+    >>::Actions;
+    type Skips = <() as LastActionCase<
+        <Self as TakesPart<Me>>::Output,
+        protocol::TMsg<IO, Lbl, From, To, H, protocol::TEnd<IO>>,
+        <T as LastActionsWith<Me, Then>>::Actions,
+        <T as LastActionsWith<Me, Then>>::Skips,
+    >>::Skips;
+}
+impl<IO, Lbl, From, ToSet, H, T, Me, Then: types::Bool> LastActionsWith<Me, Then>
+    for protocol::TBroadcast<IO, Lbl, From, ToSet, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: protocol::TSession<IO> + LastActionsWith<Me, Then>,
+    Self: TakesPart<Me>,
+    (): LastActionCase<
+        <Self as TakesPart<Me>>::Output,
+        protocol::TBroadcast<IO, Lbl, From, ToSet, H, protocol::TEnd<IO>>,
+        <T as LastActionsWith<Me, Then>>::Actions,
+        <T as LastActionsWith<Me, Then>>::Skips,
+    >,
+{
+    type Actions = <() as LastActionCase<
+        <Self as TakesPart<Me>>::Output,
+        protocol::TBroadcast<IO, Lbl, From, ToSet, H, protocol::TEnd<IO>>,
+        <T as LastActionsWith<Me, Then>>::Actions,
+        <T as LastActionsWith<Me, Then>>::Skips,
+    >>::Actions;
+    type Skips = <() as LastActionCase<
+        <Self as TakesPart<Me>>::Output,
+        protocol::TBroadcast<IO, Lbl, From, ToSet, H, protocol::TEnd<IO>>,
+        <T as LastActionsWith<Me, Then>>::Actions,
+        <T as LastActionsWith<Me, Then>>::Skips,
+    >>::Skips;
+}
+impl<IO, Lbl, From, WorkerSet, H, T, Me, Then: types::Bool> LastActionsWith<Me, Then>
+    for protocol::TAnycast<IO, Lbl, From, WorkerSet, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: protocol::TSession<IO> + LastActionsWith<Me, Then>,
+    Self: TakesPart<Me>,
+    (): LastActionCase<
+        <Self as TakesPart<Me>>::Output,
+        protocol::TAnycast<IO, Lbl, From, WorkerSet, H, protocol::TEnd<IO>>,
+        <T as LastActionsWith<Me, Then>>::Actions,
+        <T as LastActionsWith<Me, Then>>::Skips,
+    >,
+{
+    type Actions = <() as LastActionCase<
+        <Self as TakesPart<Me>>::Output,
+        protocol::TAnycast<IO, Lbl, From, WorkerSet, H, protocol::TEnd<IO>>,
+        <T as LastActionsWith<Me, Then>>::Actions,
+        <T as LastActionsWith<Me, Then>>::Skips,
+    >>::Actions;
+    type Skips = <() as LastActionCase<
+        <Self as TakesPart<Me>>::Output,
+        protocol::TAnycast<IO, Lbl, From, WorkerSet, H, protocol::TEnd<IO>>,
+        <T as LastActionsWith<Me, Then>>::Actions,
+        <T as LastActionsWith<Me, Then>>::Skips,
+    >>::Skips;
+}
+impl<IO, Lbl, From, To, DelegatedEp, T, Me, Then: types::Bool> LastActionsWith<Me, Then>
+    for protocol::TDelegate<IO, Lbl, From, To, DelegatedEp, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: protocol::TSession<IO> + LastActionsWith<Me, Then>,
+    Self: TakesPart<Me>,
+    (): LastActionCase<
+        <Self as TakesPart<Me>>::Output,
+        protocol::TDelegate<IO, Lbl, From, To, DelegatedEp, protocol::TEnd<IO>>,
+        <T as LastActionsWith<Me, Then>>::Actions,
+        <T as LastActionsWith<Me, Then>>::Skips,
+    >,
+{
+    type Actions = <() as LastActionCase<
+        <Self as TakesPart<Me>>::Output,
+        protocol::TDelegate<IO, Lbl, From, To, DelegatedEp, protocol::TEnd<IO>>,
+        <T as LastActionsWith<Me, Then>>::Actions,
+        <T as LastActionsWith<Me, Then>>::Skips,
+    >>::Actions;
+    type Skips = <() as LastActionCase<
+        <Self as TakesPart<Me>>::Output,
+        protocol::TDelegate<IO, Lbl, From, To, DelegatedEp, protocol::TEnd<IO>>,
+        <T as LastActionsWith<Me, Then>>::Actions,
+        <T as LastActionsWith<Me, Then>>::Skips,
+    >>::Skips;
+}
+impl<IO, Lbl, A, B, Cont, Me, Then: types::Bool> LastActionsWith<Me, Then>
+    for protocol::TDisconnect<IO, Lbl, A, B, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Cont: protocol::TSession<IO> + LastActionsWith<Me, Then>,
+    Self: TakesPart<Me>,
+    (): LastActionCase<
+        <Self as TakesPart<Me>>::Output,
+        protocol::TDisconnect<IO, Lbl, A, B, protocol::TEnd<IO>>,
+        <Cont as LastActionsWith<Me, Then>>::Actions,
+        <Cont as LastActionsWith<Me, Then>>::Skips,
+    >,
+{
+    type Actions = <() as LastActionCase<
+        <Self as TakesPart<Me>>::Output,
+        protocol::TDisconnect<IO, Lbl, A, B, protocol::TEnd<IO>>,
+        <Cont as LastActionsWith<Me, Then>>::Actions,
+        <Cont as LastActionsWith<Me, Then>>::Skips,
+    >>::Actions;
+    type Skips = <() as LastActionCase<
+        <Self as TakesPart<Me>>::Output,
+        protocol::TDisconnect<IO, Lbl, A, B, protocol::TEnd<IO>>,
+        <Cont as LastActionsWith<Me, Then>>::Actions,
+        <Cont as LastActionsWith<Me, Then>>::Skips,
+    >>::Skips;
+}
+impl<IO, Lbl, A, B, Cont, Me, Then: types::Bool> LastActionsWith<Me, Then>
+    for protocol::TConnect<IO, Lbl, A, B, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Cont: protocol::TSession<IO> + LastActionsWith<Me, Then>,
+    Self: TakesPart<Me>,
+    (): LastActionCase<
+        <Self as TakesPart<Me>>::Output,
+        protocol::TConnect<IO, Lbl, A, B, protocol::TEnd<IO>>,
+        <Cont as LastActionsWith<Me, Then>>::Actions,
+        <Cont as LastActionsWith<Me, Then>>::Skips,
+    >,
+{
+    type Actions = <() as LastActionCase<
+        <Self as TakesPart<Me>>::Output,
+        protocol::TConnect<IO, Lbl, A, B, protocol::TEnd<IO>>,
+        <Cont as LastActionsWith<Me, Then>>::Actions,
+        <Cont as LastActionsWith<Me, Then>>::Skips,
+    >>::Actions;
+    type Skips = <() as LastActionCase<
+        <Self as TakesPart<Me>>::Output,
+        protocol::TConnect<IO, Lbl, A, B, protocol::TEnd<IO>>,
+        <Cont as LastActionsWith<Me, Then>>::Actions,
+        <Cont as LastActionsWith<Me, Then>>::Skips,
+    >>::Skips;
+}
+impl<IO, Lbl, R, Cont, Me, Then: types::Bool> LastActionsWith<Me, Then>
+    for protocol::TEndFor<IO, Lbl, R, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Cont: protocol::TSession<IO> + LastActionsWith<Me, Then>,
+{
+    type Actions = <Cont as LastActionsWith<Me, Then>>::Actions;
+    type Skips = <Cont as LastActionsWith<Me, Then>>::Skips;
+}
+impl<IO, Lbl, L, R, Me, Then: types::Bool> LastActionsWith<Me, Then>
+    for protocol::TChoice<IO, Lbl, L, R>
+where
+    Lbl: types::ProtocolLabel,
+    L: protocol::TSession<IO> + LastActionsWith<Me, Then>,
+    R: protocol::TSession<IO> + LastActionsWith<Me, Then>,
+    <L as LastActionsWith<Me, Then>>::Actions:
+        protocol::Concat<<R as LastActionsWith<Me, Then>>::Actions>,
+    <L as LastActionsWith<Me, Then>>::Skips: types::BoolOr<<R as LastActionsWith<Me, Then>>::Skips>,
+{
+    type Actions = <<L as LastActionsWith<Me, Then>>::Actions as protocol::Concat<
+        <R as LastActionsWith<Me, Then>>::Actions,
+    >>::Output;
+    type Skips =
+        types::Or<<L as LastActionsWith<Me, Then>>::Skips, <R as LastActionsWith<Me, Then>>::Skips>;
+}
+impl<IO, Lbl, Branches, Me, Then: types::Bool> LastActionsWith<Me, Then>
+    for protocol::TChoiceN<IO, Lbl, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: protocol::SessionList<IO> + LastActionsWithEach<Me, Then>,
+{
+    type Actions = <Branches as LastActionsWithEach<Me, Then>>::Actions;
+    type Skips = <Branches as LastActionsWithEach<Me, Then>>::AnySkips;
+}
+impl<IO, Lbl, Chooser, Branches, Me, Then: types::Bool> LastActionsWith<Me, Then>
+    for protocol::TSelect<IO, Lbl, Chooser, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: protocol::SessionList<IO> + LastActionsWithEach<Me, Then>,
+{
+    type Actions = <Branches as LastActionsWithEach<Me, Then>>::Actions;
+    type Skips = <Branches as LastActionsWithEach<Me, Then>>::AnySkips;
+}
+impl<IO, Lbl, Offeree, Branches, Me, Then: types::Bool> LastActionsWith<Me, Then>
+    for protocol::TOffer<IO, Lbl, Offeree, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: protocol::SessionList<IO> + LastActionsWithEach<Me, Then>,
+{
+    type Actions = <Branches as LastActionsWithEach<Me, Then>>::Actions;
+    type Skips = <Branches as LastActionsWithEach<Me, Then>>::AnySkips;
+}
+impl<IO, Lbl, High, Low, Me, Then: types::Bool> LastActionsWith<Me, Then>
+    for protocol::TPriorityChoice<IO, Lbl, High, Low>
+where
+    Lbl: types::ProtocolLabel,
+    High: protocol::TSession<IO> + LastActionsWith<Me, Then>,
+    Low: protocol::TSession<IO> + LastActionsWith<Me, Then>,
+    <High as LastActionsWith<Me, Then>>::Actions:
+        protocol::Concat<<Low as LastActionsWith<Me, Then>>::Actions>,
+    <High as LastActionsWith<Me, Then>>::Skips:
+        types::BoolOr<<Low as LastActionsWith<Me, Then>>::Skips>,
+{
+    type Actions = <<High as LastActionsWith<Me, Then>>::Actions as protocol::Concat<
+        <Low as LastActionsWith<Me, Then>>::Actions,
+    >>::Output;
+    type Skips = types::Or<
+        <High as LastActionsWith<Me, Then>>::Skips,
+        <Low as LastActionsWith<Me, Then>>::Skips,
+    >;
+}
+impl<IO, Lbl, WeightL, L, WeightR, R, Me, Then: types::Bool> LastActionsWith<Me, Then>
+    for protocol::TProb<IO, Lbl, WeightL, L, WeightR, R>
+where
+    Lbl: types::ProtocolLabel,
+    WeightL: types::WeightMarker,
+    WeightR: types::WeightMarker,
+    L: protocol::TSession<IO> + LastActionsWith<Me, Then>,
+    R: protocol::TSession<IO> + LastActionsWith<Me, Then>,
+    <L as LastActionsWith<Me, Then>>::Actions:
+        protocol::Concat<<R as LastActionsWith<Me, Then>>::Actions>,
+    <L as LastActionsWith<Me, Then>>::Skips: types::BoolOr<<R as LastActionsWith<Me, Then>>::Skips>,
+{
+    type Actions = <<L as LastActionsWith<Me, Then>>::Actions as protocol::Concat<
+        <R as LastActionsWith<Me, Then>>::Actions,
+    >>::Output;
+    type Skips =
+        types::Or<<L as LastActionsWith<Me, Then>>::Skips, <R as LastActionsWith<Me, Then>>::Skips>;
+}
+impl<IO, Lbl, L, R, IsDisjoint, Me, Then: types::Bool> LastActionsWith<Me, Then>
+    for protocol::TPar<IO, Lbl, L, R, IsDisjoint>
+where
+    Lbl: types::ProtocolLabel,
+    L: protocol::TSession<IO> + LastActionsWith<Me, Then>,
+    R: protocol::TSession<IO> + LastActionsWith<Me, Then>,
+    <L as LastActionsWith<Me, Then>>::Actions:
+        protocol::Concat<<R as LastActionsWith<Me, Then>>::Actions>,
+    <L as LastActionsWith<Me, Then>>::Skips:
+        types::BoolAnd<<R as LastActionsWith<Me, Then>>::Skips>,
+{
+    type Actions = <<L as LastActionsWith<Me, Then>>::Actions as protocol::Concat<
+        <R as LastActionsWith<Me, Then>>::Actions,
+    >>::Output;
+    type Skips = types::And<
+        <L as LastActionsWith<Me, Then>>::Skips,
+        <R as LastActionsWith<Me, Then>>::Skips,
+    >;
+}
+impl<IO, Lbl, Branches, Me, Then: types::Bool> LastActionsWith<Me, Then>
+    for protocol::TParN<IO, Lbl, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: protocol::SessionList<IO> + LastActionsWithEach<Me, Then>,
+{
+    type Actions = <Branches as LastActionsWithEach<Me, Then>>::Actions;
+    type Skips = <Branches as LastActionsWithEach<Me, Then>>::AllSkip;
+}
+impl<IO, Lbl, Steps, Me, Then: types::Bool> LastActionsWith<Me, Then>
+    for protocol::TShuffle<IO, Lbl, Steps>
+where
+    Lbl: types::ProtocolLabel,
+    Steps: protocol::SessionList<IO> + LastActionsWithEach<Me, Then>,
+{
+    type Actions = <Steps as LastActionsWithEach<Me, Then>>::Actions;
+    type Skips = <Steps as LastActionsWithEach<Me, Then>>::AllSkip;
+}
+impl<IO, Lbl, S, Me, Then: types::Bool> LastActionsWith<Me, Then> for protocol::TRec<IO, Lbl, S>
+where
+    Lbl: types::ProtocolLabel,
+    S: protocol::TSession<IO> + LastActionsWith<Me, Then>,
+{
+    type Actions = <S as LastActionsWith<Me, Then>>::Actions;
+    type Skips = <S as LastActionsWith<Me, Then>>::Skips;
+}
+impl<IO, Lbl, Var, Body, Me, Then: types::Bool> LastActionsWith<Me, Then>
+    for protocol::TRecX<IO, Lbl, Var, Body>
+where
+    Lbl: types::ProtocolLabel,
+    Body: protocol::TSession<IO> + LastActionsWith<Me, Then>,
+{
+    type Actions = <Body as LastActionsWith<Me, Then>>::Actions;
+    type Skips = <Body as LastActionsWith<Me, Then>>::Skips;
+}
+impl<Var, Me, Then: types::Bool> LastActionsWith<Me, Then> for protocol::TVar<Var> {
+    type Actions = protocol::Nil;
+    type Skips = types::True;
+}
+impl<IO, Lbl, Dur, Body, OnTimeout, Me, Then: types::Bool> LastActionsWith<Me, Then>
+    for protocol::TTimeout<IO, Lbl, Dur, Body, OnTimeout>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    OnTimeout: protocol::TSession<IO> + LastActionsWith<Me, Then>,
+    Then: types::BoolOr<<OnTimeout as LastActionsWith<Me, Then>>::Skips>,
+    Body: protocol::TSession<IO>
+        + LastActionsWith<Me, types::Or<Then, <OnTimeout as LastActionsWith<Me, Then>>::Skips>>,
+    <Body as LastActionsWith<
+        Me,
+        types::Or<Then, <OnTimeout as LastActionsWith<Me, Then>>::Skips>,
+    >>::Actions: protocol::Concat<<OnTimeout as LastActionsWith<Me, Then>>::Actions>,
+    <Body as LastActionsWith<
+        Me,
+        types::Or<Then, <OnTimeout as LastActionsWith<Me, Then>>::Skips>,
+    >>::Skips: types::BoolOr<<OnTimeout as LastActionsWith<Me, Then>>::Skips>,
+{
+    type Actions = <<Body as LastActionsWith<
+        Me,
+        types::Or<Then, <OnTimeout as LastActionsWith<Me, Then>>::Skips>,
+    >>::Actions as protocol::Concat<<OnTimeout as LastActionsWith<Me, Then>>::Actions>>::Output;
+    type Skips = types::Or<
+        <Body as LastActionsWith<
+            Me,
+            types::Or<Then, <OnTimeout as LastActionsWith<Me, Then>>::Skips>,
+        >>::Skips,
+        <OnTimeout as LastActionsWith<Me, Then>>::Skips,
+    >;
+}
+impl<IO, Lbl, R, Dur, T, Me, Then: types::Bool> LastActionsWith<Me, Then>
+    for protocol::TDelay<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: protocol::TSession<IO> + LastActionsWith<Me, Then>,
+{
+    type Actions = <T as LastActionsWith<Me, Then>>::Actions;
+    type Skips = <T as LastActionsWith<Me, Then>>::Skips;
+}
+impl<IO, Lbl, R, Dur, T, Me, Then: types::Bool> LastActionsWith<Me, Then>
+    for protocol::TDeadline<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: protocol::TSession<IO> + LastActionsWith<Me, Then>,
+{
+    type Actions = <T as LastActionsWith<Me, Then>>::Actions;
+    type Skips = <T as LastActionsWith<Me, Then>>::Skips;
+}
+impl<IO, Lbl, Scope, Interruptor, Handler, Me, Then: types::Bool> LastActionsWith<Me, Then>
+    for protocol::TInterrupt<IO, Lbl, Scope, Interruptor, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Handler: protocol::TSession<IO> + LastActionsWith<Me, Then>,
+    Then: types::BoolOr<<Handler as LastActionsWith<Me, Then>>::Skips>,
+    Scope: protocol::TSession<IO>
+        + LastActionsWith<Me, types::Or<Then, <Handler as LastActionsWith<Me, Then>>::Skips>>,
+    <Scope as LastActionsWith<
+        Me,
+        types::Or<Then, <Handler as LastActionsWith<Me, Then>>::Skips>,
+    >>::Actions: protocol::Concat<<Handler as LastActionsWith<Me, Then>>::Actions>,
+    <Scope as LastActionsWith<
+        Me,
+        types::Or<Then, <Handler as LastActionsWith<Me, Then>>::Skips>,
+    >>::Skips: types::BoolOr<<Handler as LastActionsWith<Me, Then>>::Skips>,
+{
+    type Actions = <<Scope as LastActionsWith<
+        Me,
+        types::Or<Then, <Handler as LastActionsWith<Me, Then>>::Skips>,
+    >>::Actions as protocol::Concat<<Handler as LastActionsWith<Me, Then>>::Actions>>::Output;
+    type Skips = types::Or<
+        <Scope as LastActionsWith<
+            Me,
+            types::Or<Then, <Handler as LastActionsWith<Me, Then>>::Skips>,
+        >>::Skips,
+        <Handler as LastActionsWith<Me, Then>>::Skips,
+    >;
+}
+impl<IO, Lbl, Decider, Body, Cont, Me, Then: types::Bool> LastActionsWith<Me, Then>
+    for protocol::TOptional<IO, Lbl, Decider, Body, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Cont: protocol::TSession<IO> + LastActionsWith<Me, Then>,
+    Body: protocol::TSession<IO> + LastActionsWith<Me, <Cont as LastActionsWith<Me, Then>>::Skips>,
+    <Body as LastActionsWith<Me, <Cont as LastActionsWith<Me, Then>>::Skips>>::Actions:
+        protocol::Concat<<Cont as LastActionsWith<Me, Then>>::Actions>,
+{
+    type Actions = <<Body as LastActionsWith<Me, <Cont as LastActionsWith<Me, Then>>::Skips>>::Actions as protocol::Concat<<Cont as LastActionsWith<Me, Then>>::Actions>>::Output;
+    type Skips = <Cont as LastActionsWith<Me, Then>>::Skips;
+}
+impl<Pred, Inner, Me, Then: types::Bool> LastActionsWith<Me, Then>
+    for protocol::TRefine<Pred, Inner>
+where
+    Inner: LastActionsWith<Me, Then>,
+{
+    type Actions = <Inner as LastActionsWith<Me, Then>>::Actions;
+    type Skips = <Inner as LastActionsWith<Me, Then>>::Skips;
+}
+impl<Meta, Inner, Me, Then: types::Bool> LastActionsWith<Me, Then>
+    for protocol::TAnnotate<Meta, Inner>
+where
+    Inner: LastActionsWith<Me, Then>,
+{
+    type Actions = <Inner as LastActionsWith<Me, Then>>::Actions;
+    type Skips = <Inner as LastActionsWith<Me, Then>>::Skips;
+}
+impl<IO, Lbl, R, Err, Me, Then: types::Bool> LastActionsWith<Me, Then>
+    for protocol::TThrow<IO, Lbl, R, Err>
+where
+    Lbl: types::ProtocolLabel,
+    Self: TakesPart<Me>,
+    (): LastActionCase<
+        <Self as TakesPart<Me>>::Output,
+        protocol::TThrow<IO, Lbl, R, Err>,
+        protocol::Nil,
+        Then,
+    >,
+{
+    type Actions = <() as LastActionCase<
+        <Self as TakesPart<Me>>::Output,
+        protocol::TThrow<IO, Lbl, R, Err>,
+        protocol::Nil,
+        Then,
+    >>::Actions;
+    type Skips = <() as LastActionCase<
+        <Self as TakesPart<Me>>::Output,
+        protocol::TThrow<IO, Lbl, R, Err>,
+        protocol::Nil,
+        Then,
+    >>::Skips;
+}
+impl<IO, Lbl, Body, Handler, Me, Then: types::Bool> LastActionsWith<Me, Then> for protocol::TCatch<IO, Lbl, Body, Handler>
+where
+Lbl: types::ProtocolLabel,
+Handler: protocol::TSession<IO> + LastActionsWith<Me, Then>,
+Then: types::BoolOr<<Handler as LastActionsWith<Me, Then>>::Skips>,
+Body: protocol::TSession<IO> + LastActionsWith<Me, types::Or<Then, <Handler as LastActionsWith<Me, Then>>::Skips>>,
+<Body as LastActionsWith<Me, types::Or<Then, <Handler as LastActionsWith<Me, Then>>::Skips>>>::Actions: protocol::Concat<<Handler as LastActionsWith<Me, Then>>::Actions>,
+<Body as LastActionsWith<Me, types::Or<Then, <Handler as LastActionsWith<Me, Then>>::Skips>>>::Skips: types::BoolOr<<Handler as LastActionsWith<Me, Then>>::Skips>,
+{
+type Actions = <<Body as LastActionsWith<Me, types::Or<Then, <Handler as LastActionsWith<Me, Then>>::Skips>>>::Actions as protocol::Concat<<Handler as LastActionsWith<Me, Then>>::Actions>>::Output;
+type Skips = types::Or<<Body as LastActionsWith<Me, types::Or<Then, <Handler as LastActionsWith<Me, Then>>::Skips>>>::Skips, <Handler as LastActionsWith<Me, Then>>::Skips>;
+}
+
+/// Counts the messages role `Me` sends and receives in a protocol, as
+/// type-level naturals.
+///
+/// - A message or a delegation counts once for its sender and once for its
+///   receiver. A broadcast counts once per receiver for its sender, and an
+///   anycast once for its sender and once for each worker that may take it.
+/// - Local actions, connects, disconnects and throws carry no message.
+/// - As for [`LengthOf`], the branches of choices and parallel compositions
+///   are added up and a loop body is counted once, so the counts are those of
+///   the protocol as written. Compare them across roles to find the busy ones.
+/// - Roles are compared with `RoleEq`, and with `InRoleSet` for role sets.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Alice; struct Bob;
+/// impl Role for Alice {} impl Role for Bob {}
+/// impl RoleEq<Alice> for Alice { type Output = True; }
+/// impl RoleEq<Alice> for Bob   { type Output = False; }
+///
+/// type G = TMsg<Http, EmptyLabel, Alice, Bob, Message,
+///     TMsg<Http, EmptyLabel, Bob, Alice, Response,
+///     TMsg<Http, EmptyLabel, Alice, Bob, Message, TEnd<Http>>>>;
+/// assert_eq!(<<G as MessageCountOf<Alice>>::Sent as Nat>::VALUE, 2);
+/// assert_eq!(<<G as MessageCountOf<Alice>>::Received as Nat>::VALUE, 1);
+/// ```
+pub trait MessageCountOf<Me> {
+    type Sent;
+    type Received;
+}
+impl<IO, Lbl, Me> MessageCountOf<Me> for protocol::TEnd<IO, Lbl> {
+    type Sent = protocol::Zero;
+    type Received = protocol::Zero;
+}
+impl<IO, Lbl, R, H, T, Me> MessageCountOf<Me> for protocol::TInteract<IO, Lbl, R, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: protocol::TSession<IO> + MessageCountOf<Me>,
+{
+    type Sent = <T as MessageCountOf<Me>>::Sent;
+    type Received = <T as MessageCountOf<Me>>::Received;
+}
+impl<IO, Lbl, From, To, H, T, Me> MessageCountOf<Me> for protocol::TMsg<IO, Lbl, From, To, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: protocol::TSession<IO> + MessageCountOf<Me>,
+    From: protocol::RoleEq<Me>,
+    <From as protocol::RoleEq<Me>>::Output: NatIf<protocol::Succ<protocol::Zero>>,
+    <<From as protocol::RoleEq<Me>>::Output as NatIf<protocol::Succ<protocol::Zero>>>::Output:
+        NatAdd<<T as MessageCountOf<Me>>::Sent>,
+    To: protocol::RoleEq<Me>,
+    <To as protocol::RoleEq<Me>>::Output: NatIf<protocol::Succ<protocol::Zero>>,
+    <<To as protocol::RoleEq<Me>>::Output as NatIf<protocol::Succ<protocol::Zero>>>::Output:
+        NatAdd<<T as MessageCountOf<Me>>::Received>,
+{
+    type Sent = <<<From as protocol::RoleEq<Me>>::Output as NatIf<protocol::Succ<protocol::Zero>>>::Output as NatAdd<<T as MessageCountOf<Me>>::Sent>>::Output;
+    type Received = <<<To as protocol::RoleEq<Me>>::Output as NatIf<
+        protocol::Succ<protocol::Zero>,
+    >>::Output as NatAdd<<T as MessageCountOf<Me>>::Received>>::Output;
+}
+impl<IO, Lbl, From, ToSet, H, T, Me> MessageCountOf<Me>
+    for protocol::TBroadcast<IO, Lbl, From, ToSet, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: protocol::TSession<IO> + MessageCountOf<Me>,
+    From: protocol::RoleEq<Me>,
+    ToSet: ListLength,
+    <From as protocol::RoleEq<Me>>::Output: NatIf<<ToSet as ListLength>::Length>,
+    <<From as protocol::RoleEq<Me>>::Output as NatIf<<ToSet as ListLength>::Length>>::Output:
+        NatAdd<<T as MessageCountOf<Me>>::Sent>,
+    Me: protocol::InRoleSet<ToSet>,
+    <Me as protocol::InRoleSet<ToSet>>::Output: NatIf<protocol::Succ<protocol::Zero>>,
+    <<Me as protocol::InRoleSet<ToSet>>::Output as NatIf<protocol::Succ<protocol::Zero>>>::Output:
+        NatAdd<<T as MessageCountOf<Me>>::Received>,
+{
+    type Sent = <<<From as protocol::RoleEq<Me>>::Output as NatIf<<ToSet as ListLength>::Length>>::Output as NatAdd<<T as MessageCountOf<Me>>::Sent>>::Output;
+    type Received = <<<Me as protocol::InRoleSet<ToSet>>::Output as NatIf<
+        protocol::Succ<protocol::Zero>,
+    >>::Output as NatAdd<<T as MessageCountOf<Me>>::Received>>::Output;
+}
+impl<IO, Lbl, From, WorkerSet, H, T, Me> MessageCountOf<Me> for protocol::TAnycast<IO, Lbl, From, WorkerSet, H, T>
+where
+Lbl: types::ProtocolLabel,
+T: protocol::TSession<IO> + MessageCountOf<Me>,
+From: protocol::RoleEq<Me>,
+<From as protocol::RoleEq<Me>>::Output: NatIf<protocol::Succ<protocol::Zero>>,
+<<From as protocol::RoleEq<Me>>::Output as NatIf<protocol::Succ<protocol::Zero>>>::Output: NatAdd<<T as MessageCountOf<Me>>::Sent>,
+Me: protocol::InRoleSet<WorkerSet>,
+<Me as protocol::InRoleSet<WorkerSet>>::Output: NatIf<protocol::Succ<protocol::Zero>>,
+<<Me as protocol::InRoleSet<WorkerSet>>::Output as NatIf<protocol::Succ<protocol::Zero>>>::Output: NatAdd<<T as MessageCountOf<Me>>::Received>,
+{
+type Sent = <<<From as protocol::RoleEq<Me>>::Output as NatIf<protocol::Succ<protocol::Zero>>>::Output as NatAdd<<T as MessageCountOf<Me>>::Sent>>::Output;
+type Received = <<<Me as protocol::InRoleSet<WorkerSet>>::Output as NatIf<protocol::Succ<protocol::Zero>>>::Output as NatAdd<<T as MessageCountOf<Me>>::Received>>::Output;
+}
+impl<IO, Lbl, From, To, DelegatedEp, T, Me> MessageCountOf<Me>
+    for protocol::TDelegate<IO, Lbl, From, To, DelegatedEp, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: protocol::TSession<IO> + MessageCountOf<Me>,
+    From: protocol::RoleEq<Me>,
+    <From as protocol::RoleEq<Me>>::Output: NatIf<protocol::Succ<protocol::Zero>>,
+    <<From as protocol::RoleEq<Me>>::Output as NatIf<protocol::Succ<protocol::Zero>>>::Output:
+        NatAdd<<T as MessageCountOf<Me>>::Sent>,
+    To: protocol::RoleEq<Me>,
+    <To as protocol::RoleEq<Me>>::Output: NatIf<protocol::Succ<protocol::Zero>>,
+    <<To as protocol::RoleEq<Me>>::Output as NatIf<protocol::Succ<protocol::Zero>>>::Output:
+        NatAdd<<T as MessageCountOf<Me>>::Received>,
+{
+    type Sent = <<<From as protocol::RoleEq<Me>>::Output as NatIf<protocol::Succ<protocol::Zero>>>::Output as NatAdd<<T as MessageCountOf<Me>>::Sent>>::Output;
+    type Received = <<<To as protocol::RoleEq<Me>>::Output as NatIf<
+        protocol::Succ<protocol::Zero>,
+    >>::Output as NatAdd<<T as MessageCountOf<Me>>::Received>>::Output;
+}
+impl<IO, Lbl, A, B, Cont, Me> MessageCountOf<Me> for protocol::TDisconnect<IO, Lbl, A, B, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Cont: protocol::TSession<IO> + MessageCountOf<Me>,
+{
+    type Sent = <Cont as MessageCountOf<Me>>::Sent;
+    type Received = <Cont as MessageCountOf<Me>>::Received;
+}
+impl<IO, Lbl, A, B, Cont, Me> MessageCountOf<Me> for protocol::TConnect<IO, Lbl, A, B, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Cont: protocol::TSession<IO> + MessageCountOf<Me>,
+{
+    type Sent = <Cont as MessageCountOf<Me>>::Sent;
+    type Received = <Cont as MessageCountOf<Me>>::Received;
+}
+impl<IO, Lbl, R, Cont, Me> MessageCountOf<Me> for protocol::TEndFor<IO, Lbl, R, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Cont: protocol::TSession<IO> + MessageCountOf<Me>,
+{
+    type Sent = <Cont as MessageCountOf<Me>>::Sent;
+    type Received = <Cont as MessageCountOf<Me>>::Received;
+}
+impl<IO, Lbl, L, R, Me> MessageCountOf<Me> for protocol::TChoice<IO, Lbl, L, R>
+where
+    Lbl: types::ProtocolLabel,
+    L: protocol::TSession<IO> + MessageCountOf<Me>,
+    R: protocol::TSession<IO> + MessageCountOf<Me>,
+    <L as MessageCountOf<Me>>::Sent: NatAdd<<R as MessageCountOf<Me>>::Sent>,
+    <L as MessageCountOf<Me>>::Received: NatAdd<<R as MessageCountOf<Me>>::Received>,
+{
+    type Sent =
+        <<L as MessageCountOf<Me>>::Sent as NatAdd<<R as MessageCountOf<Me>>::Sent>>::Output;
+    type Received = <<L as MessageCountOf<Me>>::Received as NatAdd<
+        <R as MessageCountOf<Me>>::Received,
+    >>::Output;
+}
+impl<IO, Lbl, Branches, Me> MessageCountOf<Me> for protocol::TChoiceN<IO, Lbl, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: protocol::SessionList<IO> + MessageCountOfEach<Me>,
+{
+    type Sent = <Branches as MessageCountOfEach<Me>>::Sent;
+    type Received = <Branches as MessageCountOfEach<Me>>::Received;
+}
+impl<IO, Lbl, Chooser, Branches, Me> MessageCountOf<Me>
+    for protocol::TSelect<IO, Lbl, Chooser, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: protocol::SessionList<IO> + MessageCountOfEach<Me>,
+{
+    type Sent = <Branches as MessageCountOfEach<Me>>::Sent;
+    type Received = <Branches as MessageCountOfEach<Me>>::Received;
+}
+impl<IO, Lbl, Offeree, Branches, Me> MessageCountOf<Me>
+    for protocol::TOffer<IO, Lbl, Offeree, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: protocol::SessionList<IO> + MessageCountOfEach<Me>,
+{
+    type Sent = <Branches as MessageCountOfEach<Me>>::Sent;
+    type Received = <Branches as MessageCountOfEach<Me>>::Received;
+}
+impl<IO, Lbl, High, Low, Me> MessageCountOf<Me> for protocol::TPriorityChoice<IO, Lbl, High, Low>
+where
+    Lbl: types::ProtocolLabel,
+    High: protocol::TSession<IO> + MessageCountOf<Me>,
+    Low: protocol::TSession<IO> + MessageCountOf<Me>,
+    <High as MessageCountOf<Me>>::Sent: NatAdd<<Low as MessageCountOf<Me>>::Sent>,
+    <High as MessageCountOf<Me>>::Received: NatAdd<<Low as MessageCountOf<Me>>::Received>,
+{
+    type Sent =
+        <<High as MessageCountOf<Me>>::Sent as NatAdd<<Low as MessageCountOf<Me>>::Sent>>::Output;
+    type Received = <<High as MessageCountOf<Me>>::Received as NatAdd<
+        <Low as MessageCountOf<Me>>::Received,
+    >>::Output;
+}
+impl<IO, Lbl, WeightL, L, WeightR, R, Me> MessageCountOf<Me>
+    for protocol::TProb<IO, Lbl, WeightL, L, WeightR, R>
+where
+    Lbl: types::ProtocolLabel,
+    WeightL: types::WeightMarker,
+    WeightR: types::WeightMarker,
+    L: protocol::TSession<IO> + MessageCountOf<Me>,
+    R: protocol::TSession<IO> + MessageCountOf<Me>,
+    <L as MessageCountOf<Me>>::Sent: NatAdd<<R as MessageCountOf<Me>>::Sent>,
+    <L as MessageCountOf<Me>>::Received: NatAdd<<R as MessageCountOf<Me>>::Received>,
+{
+    type Sent =
+        <<L as MessageCountOf<Me>>::Sent as NatAdd<<R as MessageCountOf<Me>>::Sent>>::Output;
+    type Received = <<L as MessageCountOf<Me>>::Received as NatAdd<
+        <R as MessageCountOf<Me>>::Received,
+    >>::Output;
+}
+impl<IO, Lbl, L, R, IsDisjoint, Me> MessageCountOf<Me> for protocol::TPar<IO, Lbl, L, R, IsDisjoint>
+where
+    Lbl: types::ProtocolLabel,
+    L: protocol::TSession<IO> + MessageCountOf<Me>,
+    R: protocol::TSession<IO> + MessageCountOf<Me>,
+    <L as MessageCountOf<Me>>::Sent: NatAdd<<R as MessageCountOf<Me>>::Sent>,
+    <L as MessageCountOf<Me>>::Received: NatAdd<<R as MessageCountOf<Me>>::Received>,
+{
+    type Sent =
+        <<L as MessageCountOf<Me>>::Sent as NatAdd<<R as MessageCountOf<Me>>::Sent>>::Output;
+    type Received = <<L as MessageCountOf<Me>>::Received as NatAdd<
+        <R as MessageCountOf<Me>>::Received,
+    >>::Output;
+}
+impl<IO, Lbl, Branches, Me> MessageCountOf<Me> for protocol::TParN<IO, Lbl, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: protocol::SessionList<IO> + MessageCountOfEach<Me>,
+{
+    type Sent = <Branches as MessageCountOfEach<Me>>::Sent;
+    type Received = <Branches as MessageCountOfEach<Me>>::Received;
+}
+impl<IO, Lbl, Steps, Me> MessageCountOf<Me> for protocol::TShuffle<IO, Lbl, Steps>
+where
+    Lbl: types::ProtocolLabel,
+    Steps: protocol::SessionList<IO> + MessageCountOfEach<Me>,
+{
+    type Sent = <Steps as MessageCountOfEach<Me>>::Sent;
+    type Received = <Steps as MessageCountOfEach<Me>>::Received;
+}
+impl<IO, Lbl, S, Me> MessageCountOf<Me> for protocol::TRec<IO, Lbl, S>
+where
+    Lbl: types::ProtocolLabel,
+    S: protocol::TSession<IO> + MessageCountOf<Me>,
+{
+    type Sent = <S as MessageCountOf<Me>>::Sent;
+    type Received = <S as MessageCountOf<Me>>::Received;
+}
+impl<IO, Lbl, Var, Body, Me> MessageCountOf<Me> for protocol::TRecX<IO, Lbl, Var, Body>
+where
+    Lbl: types::ProtocolLabel,
+    Body: protocol::TSession<IO> + MessageCountOf<Me>,
+{
+    type Sent = <Body as MessageCountOf<Me>>::Sent;
+    type Received = <Body as MessageCountOf<Me>>::Received;
+}
+impl<Var, Me> MessageCountOf<Me> for protocol::TVar<Var> {
+    type Sent = protocol::Zero;
+    type Received = protocol::Zero;
+}
+impl<IO, Lbl, Dur, Body, OnTimeout, Me> MessageCountOf<Me>
+    for protocol::TTimeout<IO, Lbl, Dur, Body, OnTimeout>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    Body: protocol::TSession<IO> + MessageCountOf<Me>,
+    OnTimeout: protocol::TSession<IO> + MessageCountOf<Me>,
+    <Body as MessageCountOf<Me>>::Sent: NatAdd<<OnTimeout as MessageCountOf<Me>>::Sent>,
+    <Body as MessageCountOf<Me>>::Received: NatAdd<<OnTimeout as MessageCountOf<Me>>::Received>,
+{
+    type Sent = <<Body as MessageCountOf<Me>>::Sent as NatAdd<
+        <OnTimeout as MessageCountOf<Me>>::Sent,
+    >>::Output;
+    type Received = <<Body as MessageCountOf<Me>>::Received as NatAdd<
+        <OnTimeout as MessageCountOf<Me>>::Received,
+    >>::Output;
+}
+impl<IO, Lbl, R, Dur, T, Me> MessageCountOf<Me> for protocol::TDelay<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: protocol::TSession<IO> + MessageCountOf<Me>,
+{
+    type Sent = <T as MessageCountOf<Me>>::Sent;
+    type Received = <T as MessageCountOf<Me>>::Received;
+}
+impl<IO, Lbl, R, Dur, T, Me> MessageCountOf<Me> for protocol::TDeadline<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: protocol::TSession<IO> + MessageCountOf<Me>,
+{
+    type Sent = <T as MessageCountOf<Me>>::Sent;
+    type Received = <T as MessageCountOf<Me>>::Received;
+}
+impl<IO, Lbl, Scope, Interruptor, Handler, Me> MessageCountOf<Me>
+    for protocol::TInterrupt<IO, Lbl, Scope, Interruptor, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Scope: protocol::TSession<IO> + MessageCountOf<Me>,
+    Handler: protocol::TSession<IO> + MessageCountOf<Me>,
+    <Scope as MessageCountOf<Me>>::Sent: NatAdd<<Handler as MessageCountOf<Me>>::Sent>,
+    <Scope as MessageCountOf<Me>>::Received: NatAdd<<Handler as MessageCountOf<Me>>::Received>,
+{
+    type Sent = <<Scope as MessageCountOf<Me>>::Sent as NatAdd<
+        <Handler as MessageCountOf<Me>>::Sent,
+    >>::Output;
+    type Received = <<Scope as MessageCountOf<Me>>::Received as NatAdd<
+        <Handler as MessageCountOf<Me>>::Received,
+    >>::Output;
+}
+impl<IO, Lbl, Decider, Body, Cont, Me> MessageCountOf<Me>
+    for protocol::TOptional<IO, Lbl, Decider, Body, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Body: protocol::TSession<IO> + MessageCountOf<Me>,
+    Cont: protocol::TSession<IO> + MessageCountOf<Me>,
+    <Body as MessageCountOf<Me>>::Sent: NatAdd<<Cont as MessageCountOf<Me>>::Sent>,
+    <Body as MessageCountOf<Me>>::Received: NatAdd<<Cont as MessageCountOf<Me>>::Received>,
+{
+    type Sent =
+        <<Body as MessageCountOf<Me>>::Sent as NatAdd<<Cont as MessageCountOf<Me>>::Sent>>::Output;
+    type Received = <<Body as MessageCountOf<Me>>::Received as NatAdd<
+        <Cont as MessageCountOf<Me>>::Received,
+    >>::Output;
+}
+impl<Pred, Inner, Me> MessageCountOf<Me> for protocol::TRefine<Pred, Inner>
+where
+    Inner: MessageCountOf<Me>,
+{
+    type Sent = <Inner as MessageCountOf<Me>>::Sent;
+    type Received = <Inner as MessageCountOf<Me>>::Received;
+}
+impl<Meta, Inner, Me> MessageCountOf<Me> for protocol::TAnnotate<Meta, Inner>
+where
+    Inner: MessageCountOf<Me>,
+{
+    type Sent = <Inner as MessageCountOf<Me>>::Sent;
+    type Received = <Inner as MessageCountOf<Me>>::Received;
+}
+impl<IO, Lbl, R, Err, Me> MessageCountOf<Me> for protocol::TThrow<IO, Lbl, R, Err>
+where
+    Lbl: types::ProtocolLabel,
+{
+    type Sent = protocol::Zero;
+    type Received = protocol::Zero;
+}
+impl<IO, Lbl, Body, Handler, Me> MessageCountOf<Me> for protocol::TCatch<IO, Lbl, Body, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Body: protocol::TSession<IO> + MessageCountOf<Me>,
+    Handler: protocol::TSession<IO> + MessageCountOf<Me>,
+    <Body as MessageCountOf<Me>>::Sent: NatAdd<<Handler as MessageCountOf<Me>>::Sent>,
+    <Body as MessageCountOf<Me>>::Received: NatAdd<<Handler as MessageCountOf<Me>>::Received>,
+{
+    type Sent = <<Body as MessageCountOf<Me>>::Sent as NatAdd<
+        <Handler as MessageCountOf<Me>>::Sent,
+    >>::Output;
+    type Received = <<Body as MessageCountOf<Me>>::Received as NatAdd<
+        <Handler as MessageCountOf<Me>>::Received,
+    >>::Output;
+}
+
+/// The longest causal chain role `Me` takes part in, as a type-level natural.
+///
+/// - A role acts in sequence, so its interactions along one run of the
+///   protocol form a causal chain. The length is the most interactions `Me`
+///   takes part in on any run, as decided by [`TakesPart`].
+/// - The longest branch of a choice is taken. The branches of a parallel
+///   composition are added up, as `Me` performs the actions of all of them.
+/// - A loop body is counted once. A scoped body and its handler, and an
+///   optional body and what follows it, are added up.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Alice; struct Bob; struct Carol;
+/// impl Role for Alice {} impl Role for Bob {} impl Role for Carol {}
+/// impl RoleEq<Alice> for Alice { type Output = True; }
+/// impl RoleEq<Alice> for Bob   { type Output = False; }
+/// impl RoleEq<Alice> for Carol { type Output = False; }
+///
+/// type G = TChoice<Http, EmptyLabel,
+///     TMsg<Http, EmptyLabel, Alice, Bob, Message,
+///     TMsg<Http, EmptyLabel, Bob, Carol, Message,
+///     TMsg<Http, EmptyLabel, Carol, Alice, Response, TEnd<Http>>>>,
+///     TMsg<Http, EmptyLabel, Bob, Alice, Message, TEnd<Http>>>;
+/// assert_eq!(<<G as CriticalPathOf<Alice>>::Length as Nat>::VALUE, 2);
+/// ```
+pub trait CriticalPathOf<Me> {
+    type Length;
+}
+impl<IO, Lbl, Me> CriticalPathOf<Me> for protocol::TEnd<IO, Lbl> {
+    type Length = protocol::Zero;
+}
+impl<IO, Lbl, R, H, T, Me> CriticalPathOf<Me> for protocol::TInteract<IO, Lbl, R, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: protocol::TSession<IO> + CriticalPathOf<Me>,
+    Self: TakesPart<Me>,
+    <Self as TakesPart<Me>>::Output: NatIf<protocol::Succ<protocol::Zero>>,
+    <<Self as TakesPart<Me>>::Output as NatIf<protocol::Succ<protocol::Zero>>>::Output:
+        NatAdd<<T as CriticalPathOf<Me>>::Length>,
+{
+    type Length = <<<Self as TakesPart<Me>>::Output as NatIf<protocol::Succ<protocol::Zero>>>::Output as NatAdd<<T as CriticalPathOf<Me>>::Length>>::Output;
+}
+impl<IO, Lbl, From, To, H, T, Me> CriticalPathOf<Me> for protocol::TMsg<IO, Lbl, From, To, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: protocol::TSession<IO> + CriticalPathOf<Me>,
+    Self: TakesPart<Me>,
+    <Self as TakesPart<Me>>::Output: NatIf<protocol::Succ<protocol::Zero>>,
+    <<Self as TakesPart<Me>>::Output as NatIf<protocol::Succ<protocol::Zero>>>::Output:
+        NatAdd<<T as CriticalPathOf<Me>>::Length>,
+{
+    type Length = <<<Self as TakesPart<Me>>::Output as NatIf<protocol::Succ<protocol::Zero>>>::Output as NatAdd<<T as CriticalPathOf<Me>>::Length>>::Output;
+}
+impl<IO, Lbl, From, ToSet, H, T, Me> CriticalPathOf<Me>
+    for protocol::TBroadcast<IO, Lbl, From, ToSet, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: protocol::TSession<IO> + CriticalPathOf<Me>,
+    Self: TakesPart<Me>,
+    <Self as TakesPart<Me>>::Output: NatIf<protocol::Succ<protocol::Zero>>,
+    <<Self as TakesPart<Me>>::Output as NatIf<protocol::Succ<protocol::Zero>>>::Output:
+        NatAdd<<T as CriticalPathOf<Me>>::Length>,
+{
+    type Length = <<<Self as TakesPart<Me>>::Output as NatIf<protocol::Succ<protocol::Zero>>>::Output as NatAdd<<T as CriticalPathOf<Me>>::Length>>::Output;
+}
+impl<IO, Lbl, From, WorkerSet, H, T, Me> CriticalPathOf<Me>
+    for protocol::TAnycast<IO, Lbl, From, WorkerSet, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: protocol::TSession<IO> + CriticalPathOf<Me>,
+    Self: TakesPart<Me>,
+    <Self as TakesPart<Me>>::Output: NatIf<protocol::Succ<protocol::Zero>>,
+    <<Self as TakesPart<Me>>::Output as NatIf<protocol::Succ<protocol::Zero>>>::Output:
+        NatAdd<<T as CriticalPathOf<Me>>::Length>,
+{
+    type Length = <<<Self as TakesPart<Me>>::Output as NatIf<protocol::Succ<protocol::Zero>>>::Output as NatAdd<<T as CriticalPathOf<Me>>::Length>>::Output;
+}
+impl<IO, Lbl, From, To, DelegatedEp, T, Me> CriticalPathOf<Me>
+    for protocol::TDelegate<IO, Lbl, From, To, DelegatedEp, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: protocol::TSession<IO> + CriticalPathOf<Me>,
+    Self: TakesPart<Me>,
+    <Self as TakesPart<Me>>::Output: NatIf<protocol::Succ<protocol::Zero>>,
+    <<Self as TakesPart<Me>>::Output as NatIf<protocol::Succ<protocol::Zero>>>::Output:
+        NatAdd<<T as CriticalPathOf<Me>>::Length>,
+{
+    type Length = <<<Self as TakesPart<Me>>::Output as NatIf<protocol::Succ<protocol::Zero>>>::Output as NatAdd<<T as CriticalPathOf<Me>>::Length>>::Output;
+}
+impl<IO, Lbl, A, B, Cont, Me> CriticalPathOf<Me> for protocol::TDisconnect<IO, Lbl, A, B, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Cont: protocol::TSession<IO> + CriticalPathOf<Me>,
+    Self: TakesPart<Me>,
+    <Self as TakesPart<Me>>::Output: NatIf<protocol::Succ<protocol::Zero>>,
+    <<Self as TakesPart<Me>>::Output as NatIf<protocol::Succ<protocol::Zero>>>::Output:
+        NatAdd<<Cont as CriticalPathOf<Me>>::Length>,
+{
+    type Length = <<<Self as TakesPart<Me>>::Output as NatIf<protocol::Succ<protocol::Zero>>>::Output as NatAdd<<Cont as CriticalPathOf<Me>>::Length>>::Output;
+}
+impl<IO, Lbl, A, B, Cont, Me> CriticalPathOf<Me> for protocol::TConnect<IO, Lbl, A, B, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Cont: protocol::TSession<IO> + CriticalPathOf<Me>,
+    Self: TakesPart<Me>,
+    <Self as TakesPart<Me>>::Output: NatIf<protocol::Succ<protocol::Zero>>,
+    <<Self as TakesPart<Me>>::Output as NatIf<protocol::Succ<protocol::Zero>>>::Output:
+        NatAdd<<Cont as CriticalPathOf<Me>>::Length>,
+{
+    type Length = <<<Self as TakesPart<Me>>::Output as NatIf<protocol::Succ<protocol::Zero>>>::Output as NatAdd<<Cont as CriticalPathOf<Me>>::Length>>::Output;
+}
+impl<IO, Lbl, R, Cont, Me> CriticalPathOf<Me> for protocol::TEndFor<IO, Lbl, R, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Cont: protocol::TSession<IO> + CriticalPathOf<Me>,
+{
+    type Length = <Cont as CriticalPathOf<Me>>::Length;
+}
+impl<IO, Lbl, L, R, Me> CriticalPathOf<Me> for protocol::TChoice<IO, Lbl, L, R>
+where
+    Lbl: types::ProtocolLabel,
+    L: protocol::TSession<IO> + CriticalPathOf<Me>,
+    R: protocol::TSession<IO> + CriticalPathOf<Me>,
+    <L as CriticalPathOf<Me>>::Length: NatMax<<R as CriticalPathOf<Me>>::Length>,
+{
+    type Length =
+        <<L as CriticalPathOf<Me>>::Length as NatMax<<R as CriticalPathOf<Me>>::Length>>::Output;
+}
+impl<IO, Lbl, Branches, Me> CriticalPathOf<Me> for protocol::TChoiceN<IO, Lbl, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: protocol::SessionList<IO> + CriticalPathOfEach<Me>,
+{
+    type Length = <Branches as CriticalPathOfEach<Me>>::Longest;
+}
+impl<IO, Lbl, Chooser, Branches, Me> CriticalPathOf<Me>
+    for protocol::TSelect<IO, Lbl, Chooser, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: protocol::SessionList<IO> + CriticalPathOfEach<Me>,
+{
+    type Length = <Branches as CriticalPathOfEach<Me>>::Longest;
+}
+impl<IO, Lbl, Offeree, Branches, Me> CriticalPathOf<Me>
+    for protocol::TOffer<IO, Lbl, Offeree, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: protocol::SessionList<IO> + CriticalPathOfEach<Me>,
+{
+    type Length = <Branches as CriticalPathOfEach<Me>>::Longest;
+}
+impl<IO, Lbl, High, Low, Me> CriticalPathOf<Me> for protocol::TPriorityChoice<IO, Lbl, High, Low>
+where
+    Lbl: types::ProtocolLabel,
+    High: protocol::TSession<IO> + CriticalPathOf<Me>,
+    Low: protocol::TSession<IO> + CriticalPathOf<Me>,
+    <High as CriticalPathOf<Me>>::Length: NatMax<<Low as CriticalPathOf<Me>>::Length>,
+{
+    type Length = <<High as CriticalPathOf<Me>>::Length as NatMax<
+        <Low as CriticalPathOf<Me>>::Length,
+    >>::Output;
+}
+impl<IO, Lbl, WeightL, L, WeightR, R, Me> CriticalPathOf<Me>
+    for protocol::TProb<IO, Lbl, WeightL, L, WeightR, R>
+where
+    Lbl: types::ProtocolLabel,
+    WeightL: types::WeightMarker,
+    WeightR: types::WeightMarker,
+    L: protocol::TSession<IO> + CriticalPathOf<Me>,
+    R: protocol::TSession<IO> + CriticalPathOf<Me>,
+    <L as CriticalPathOf<Me>>::Length: NatMax<<R as CriticalPathOf<Me>>::Length>,
+{
+    type Length =
+        <<L as CriticalPathOf<Me>>::Length as NatMax<<R as CriticalPathOf<Me>>::Length>>::Output;
+}
+impl<IO, Lbl, L, R, IsDisjoint, Me> CriticalPathOf<Me> for protocol::TPar<IO, Lbl, L, R, IsDisjoint>
+where
+    Lbl: types::ProtocolLabel,
+    L: protocol::TSession<IO> + CriticalPathOf<Me>,
+    R: protocol::TSession<IO> + CriticalPathOf<Me>,
+    <L as CriticalPathOf<Me>>::Length: NatAdd<<R as CriticalPathOf<Me>>::Length>,
+{
+    type Length =
+        <<L as CriticalPathOf<Me>>::Length as NatAdd<<R as CriticalPathOf<Me>>::Length>>::Output;
+}
+impl<IO, Lbl, Branches, Me> CriticalPathOf<Me> for protocol::TParN<IO, Lbl, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: protocol::SessionList<IO> + CriticalPathOfEach<Me>,
+{
+    type Length = <Branches as CriticalPathOfEach<Me>>::Total;
+}
+impl<IO, Lbl, Steps, Me> CriticalPathOf<Me> for protocol::TShuffle<IO, Lbl, Steps>
+where
+    Lbl: types::ProtocolLabel,
+    Steps: protocol::SessionList<IO> + CriticalPathOfEach<Me>,
+{
+    type Length = <Steps as CriticalPathOfEach<Me>>::Total;
+}
+impl<IO, Lbl, S, Me> CriticalPathOf<Me> for protocol::TRec<IO, Lbl, S>
+where
+    Lbl: types::ProtocolLabel,
+    S: protocol::TSession<IO> + CriticalPathOf<Me>,
+{
+    type Length = <S as CriticalPathOf<Me>>::Length;
+}
+impl<IO, Lbl, Var, Body, Me> CriticalPathOf<Me> for protocol::TRecX<IO, Lbl, Var, Body>
+where
+    Lbl: types::ProtocolLabel,
+    Body: protocol::TSession<IO> + CriticalPathOf<Me>,
+{
+    type Length = <Body as CriticalPathOf<Me>>::Length;
+}
+impl<Var, Me> CriticalPathOf<Me> for protocol::TVar<Var> {
+    type Length = protocol::Zero;
+}
+impl<IO, Lbl, Dur, Body, OnTimeout, Me> CriticalPathOf<Me>
+    for protocol::TTimeout<IO, Lbl, Dur, Body, OnTimeout>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    Body: protocol::TSession<IO> + CriticalPathOf<Me>,
+    OnTimeout: protocol::TSession<IO> + CriticalPathOf<Me>,
+    <Body as CriticalPathOf<Me>>::Length: NatAdd<<OnTimeout as CriticalPathOf<Me>>::Length>,
+{
+    type Length = <<Body as CriticalPathOf<Me>>::Length as NatAdd<
+        <OnTimeout as CriticalPathOf<Me>>::Length,
+    >>::Output;
+}
+impl<IO, Lbl, R, Dur, T, Me> CriticalPathOf<Me> for protocol::TDelay<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: protocol::TSession<IO> + CriticalPathOf<Me>,
+{
+    type Length = <T as CriticalPathOf<Me>>::Length;
+}
+impl<IO, Lbl, R, Dur, T, Me> CriticalPathOf<Me> for protocol::TDeadline<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: protocol::TSession<IO> + CriticalPathOf<Me>,
+{
+    type Length = <T as CriticalPathOf<Me>>::Length;
+}
+impl<IO, Lbl, Scope, Interruptor, Handler, Me> CriticalPathOf<Me>
+    for protocol::TInterrupt<IO, Lbl, Scope, Interruptor, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Scope: protocol::TSession<IO> + CriticalPathOf<Me>,
+    Handler: protocol::TSession<IO> + CriticalPathOf<Me>,
+    <Scope as CriticalPathOf<Me>>::Length: NatAdd<<Handler as CriticalPathOf<Me>>::Length>,
+{
+    type Length = <<Scope as CriticalPathOf<Me>>::Length as NatAdd<
+        <Handler as CriticalPathOf<Me>>::Length,
+    >>::Output;
+}
+impl<IO, Lbl, Decider, Body, Cont, Me> CriticalPathOf<Me>
+    for protocol::TOptional<IO, Lbl, Decider, Body, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Body: protocol::TSession<IO> + CriticalPathOf<Me>,
+    Cont: protocol::TSession<IO> + CriticalPathOf<Me>,
+    <Body as CriticalPathOf<Me>>::Length: NatAdd<<Cont as CriticalPathOf<Me>>::Length>,
+{
+    type Length = <<Body as CriticalPathOf<Me>>::Length as NatAdd<
+        <Cont as CriticalPathOf<Me>>::Length,
+    >>::Output;
+}
+impl<Pred, Inner, Me> CriticalPathOf<Me> for protocol::TRefine<Pred, Inner>
+where
+    Inner: CriticalPathOf<Me>,
+{
+    type Length = <Inner as CriticalPathOf<Me>>::Length;
+}
+impl<Meta, Inner, Me> CriticalPathOf<Me> for protocol::TAnnotate<Meta, Inner>
+where
+    Inner: CriticalPathOf<Me>,
+{
+    type Length = <Inner as CriticalPathOf<Me>>::Length;
+}
+impl<IO, Lbl, R, Err, Me> CriticalPathOf<Me> for protocol::TThrow<IO, Lbl, R, Err>
+where
+    Lbl: types::ProtocolLabel,
+    Self: TakesPart<Me>,
+    <Self as TakesPart<Me>>::Output: NatIf<protocol::Succ<protocol::Zero>>,
+    <<Self as TakesPart<Me>>::Output as NatIf<protocol::Succ<protocol::Zero>>>::Output:
+        NatAdd<protocol::Zero>,
+{
+    type Length = <<<Self as TakesPart<Me>>::Output as NatIf<protocol::Succ<protocol::Zero>>>::Output as NatAdd<protocol::Zero>>::Output;
+}
+impl<IO, Lbl, Body, Handler, Me> CriticalPathOf<Me> for protocol::TCatch<IO, Lbl, Body, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Body: protocol::TSession<IO> + CriticalPathOf<Me>,
+    Handler: protocol::TSession<IO> + CriticalPathOf<Me>,
+    <Body as CriticalPathOf<Me>>::Length: NatAdd<<Handler as CriticalPathOf<Me>>::Length>,
+{
+    type Length = <<Body as CriticalPathOf<Me>>::Length as NatAdd<
+        <Handler as CriticalPathOf<Me>>::Length,
+    >>::Output;
+}
+
+/// Collects the roles of every protocol in a type-level list, each role once,
+/// in order of first appearance.
+///
+/// - Used by n-ary combinators to gather roles from all of their branches.
+pub trait RolesOfEach {
+    type Roles;
+}
+impl RolesOfEach for protocol::Nil {
+    type Roles = protocol::Nil;
+}
+impl<H, T> RolesOfEach for protocol::Cons<H, T>
+where
+    H: RolesOf,
+    T: RolesOfEach,
+    <H as RolesOf>::Roles: PrependRoles<<T as RolesOfEach>::Roles>,
+{
+    type Roles = WithRoles<<H as RolesOf>::Roles, <T as RolesOfEach>::Roles>;
+}
+
+/// Collects the labels of every protocol in a type-level list, in order.
+///
+/// - Used by n-ary combinators to gather labels from all of their branches.
+pub trait LabelsOfEach {
+    type Labels;
+}
+impl LabelsOfEach for protocol::Nil {
+    type Labels = protocol::Nil;
+}
+impl<H, T> LabelsOfEach for protocol::Cons<H, T>
+where
+    H: LabelsOf,
+    T: LabelsOfEach,
+    <H as LabelsOf>::Labels: protocol::Concat<<T as LabelsOfEach>::Labels>,
+{
+    type Labels =
+        <<H as LabelsOf>::Labels as protocol::Concat<<T as LabelsOfEach>::Labels>>::Output;
+}
+
+/// Collects the step labels of every protocol in a type-level list, in order.
+///
+/// - Used by n-ary combinators to gather step labels from all of their branches.
+pub trait StepLabelsOfEach {
+    type Labels;
+}
+impl StepLabelsOfEach for protocol::Nil {
+    type Labels = protocol::Nil;
+}
+impl<H, T> StepLabelsOfEach for protocol::Cons<H, T>
+where
+    H: StepLabelsOf,
+    T: StepLabelsOfEach,
+    <H as StepLabelsOf>::Labels: protocol::Concat<<T as StepLabelsOfEach>::Labels>,
+{
+    type Labels =
+        <<H as StepLabelsOf>::Labels as protocol::Concat<<T as StepLabelsOfEach>::Labels>>::Output;
+}
+
+/// Collects the message types of every protocol in a type-level list, in order.
+///
+/// - Used by n-ary combinators to gather messages from all of their branches.
+pub trait MessagesOfEach {
+    type Messages;
+}
+impl MessagesOfEach for protocol::Nil {
+    type Messages = protocol::Nil;
+}
+impl<H, T> MessagesOfEach for protocol::Cons<H, T>
+where
+    H: MessagesOf,
+    T: MessagesOfEach,
+    <H as MessagesOf>::Messages: protocol::Concat<<T as MessagesOfEach>::Messages>,
+{
+    type Messages =
+        <<H as MessagesOf>::Messages as protocol::Concat<<T as MessagesOfEach>::Messages>>::Output;
+}
+
+/// Collects the IO markers of every protocol in a type-level list, each
+/// marker once.
+///
+/// - Used by n-ary combinators, and on lists of `IoSession` entries.
+pub trait IOsOfEach {
+    type IOs;
+}
+impl IOsOfEach for protocol::Nil {
+    type IOs = protocol::Nil;
+}
+impl<H, T> IOsOfEach for protocol::Cons<H, T>
+where
+    H: IOsOf,
+    T: IOsOfEach,
+    <H as IOsOf>::IOs: AddIos<<T as IOsOfEach>::IOs>,
+{
+    type IOs = <<H as IOsOf>::IOs as AddIos<<T as IOsOfEach>::IOs>>::Output;
+}
+
+/// The greatest nesting depth among the protocols in a type-level list.
+///
+/// - Used by n-ary combinators to measure their branches.
+pub trait DepthOfEach {
+    type Depth;
+}
+impl DepthOfEach for protocol::Nil {
+    type Depth = protocol::Zero;
+}
+impl<H, T> DepthOfEach for protocol::Cons<H, T>
+where
+    H: DepthOf,
+    T: DepthOfEach,
+    <H as DepthOf>::Depth: NatMax<<T as DepthOfEach>::Depth>,
+{
+    type Depth = <<H as DepthOf>::Depth as NatMax<<T as DepthOfEach>::Depth>>::Output;
+}
+
+/// Adds up the interactions of every protocol in a type-level list.
+///
+/// - Used by n-ary combinators to count the interactions of their branches.
+pub trait LengthOfEach {
+    type Length;
+}
+impl LengthOfEach for protocol::Nil {
+    type Length = protocol::Zero;
+}
+impl<H, T> LengthOfEach for protocol::Cons<H, T>
+where
+    H: LengthOf,
+    T: LengthOfEach,
+    <H as LengthOf>::Length: NatAdd<<T as LengthOfEach>::Length>,
+{
+    type Length = <<H as LengthOf>::Length as NatAdd<<T as LengthOfEach>::Length>>::Output;
+}
+
+/// Lists the first actions of role `Me` in every protocol of a type-level
+/// list, in order.
+///
+/// - Used by n-ary combinators to gather the first actions of their branches.
+pub trait FirstActionOfEach<Me> {
+    type Actions;
+}
+impl<Me> FirstActionOfEach<Me> for protocol::Nil {
+    type Actions = protocol::Nil;
+}
+impl<H, T, Me> FirstActionOfEach<Me> for protocol::Cons<H, T>
+where
+    H: FirstActionOf<Me>,
+    T: FirstActionOfEach<Me>,
+    <H as FirstActionOf<Me>>::Actions: protocol::Concat<<T as FirstActionOfEach<Me>>::Actions>,
+{
+    type Actions = <<H as FirstActionOf<Me>>::Actions as protocol::Concat<
+        <T as FirstActionOfEach<Me>>::Actions,
+    >>::Output;
+}
+
+/// Lists the last actions of role `Me` in every protocol of a type-level
+/// list, each followed by more protocol as given by `Then`.
+///
+/// - `AnySkips` is `True` if some protocol of the list has a run without an
+///   action of `Me`, as for the branches of a choice; `AllSkip` if every
+///   protocol has one, as for parallel branches.
+pub trait LastActionsWithEach<Me, Then> {
+    type Actions;
+    type AnySkips: types::Bool;
+    type AllSkip: types::Bool;
+}
+impl<Me, Then> LastActionsWithEach<Me, Then> for protocol::Nil {
+    type Actions = protocol::Nil;
+    type AnySkips = types::False;
+    type AllSkip = types::True;
+}
+impl<H, T, Me, Then> LastActionsWithEach<Me, Then> for protocol::Cons<H, T>
+where
+    H: LastActionsWith<Me, Then>,
+    T: LastActionsWithEach<Me, Then>,
+    <H as LastActionsWith<Me, Then>>::Actions:
+        protocol::Concat<<T as LastActionsWithEach<Me, Then>>::Actions>,
+    <H as LastActionsWith<Me, Then>>::Skips: types::BoolOr<<T as LastActionsWithEach<Me, Then>>::AnySkips>
+        + types::BoolAnd<<T as LastActionsWithEach<Me, Then>>::AllSkip>,
+{
+    type Actions = <<H as LastActionsWith<Me, Then>>::Actions as protocol::Concat<
+        <T as LastActionsWithEach<Me, Then>>::Actions,
+    >>::Output;
+    type AnySkips = types::Or<
+        <H as LastActionsWith<Me, Then>>::Skips,
+        <T as LastActionsWithEach<Me, Then>>::AnySkips,
+    >;
+    type AllSkip = types::And<
+        <H as LastActionsWith<Me, Then>>::Skips,
+        <T as LastActionsWithEach<Me, Then>>::AllSkip,
+    >;
+}
+
+/// Adds up the messages role `Me` sends and receives in every protocol of a
+/// type-level list.
+///
+/// - Used by n-ary combinators to count the messages of their branches.
+pub trait MessageCountOfEach<Me> {
+    type Sent;
+    type Received;
+}
+impl<Me> MessageCountOfEach<Me> for protocol::Nil {
+    type Sent = protocol::Zero;
+    type Received = protocol::Zero;
+}
+impl<H, T, Me> MessageCountOfEach<Me> for protocol::Cons<H, T>
+where
+    H: MessageCountOf<Me>,
+    T: MessageCountOfEach<Me>,
+    <H as MessageCountOf<Me>>::Sent: NatAdd<<T as MessageCountOfEach<Me>>::Sent>,
+    <H as MessageCountOf<Me>>::Received: NatAdd<<T as MessageCountOfEach<Me>>::Received>,
+{
+    type Sent =
+        <<H as MessageCountOf<Me>>::Sent as NatAdd<<T as MessageCountOfEach<Me>>::Sent>>::Output;
+    type Received = <<H as MessageCountOf<Me>>::Received as NatAdd<
+        <T as MessageCountOfEach<Me>>::Received,
+    >>::Output;
+}
+
+/// The longest causal chains of role `Me` in every protocol of a type-level
+/// list.
+///
+/// - `Longest` is the longest of them, as for the branches of a choice;
+///   `Total` is their sum, as for parallel branches.
+pub trait CriticalPathOfEach<Me> {
+    type Longest;
+    type Total;
+}
+impl<Me> CriticalPathOfEach<Me> for protocol::Nil {
+    type Longest = protocol::Zero;
+    type Total = protocol::Zero;
+}
+impl<H, T, Me> CriticalPathOfEach<Me> for protocol::Cons<H, T>
+where
+    H: CriticalPathOf<Me>,
+    T: CriticalPathOfEach<Me>,
+    <H as CriticalPathOf<Me>>::Length: NatMax<<T as CriticalPathOfEach<Me>>::Longest>
+        + NatAdd<<T as CriticalPathOfEach<Me>>::Total>,
+{
+    type Longest = <<H as CriticalPathOf<Me>>::Length as NatMax<
+        <T as CriticalPathOfEach<Me>>::Longest,
+    >>::Output;
+    type Total =
+        <<H as CriticalPathOf<Me>>::Length as NatAdd<<T as CriticalPathOfEach<Me>>::Total>>::Output;
+}
+
+/// Checks that the protocols in a type-level list are pairwise role-disjoint.
+///
+/// - Each protocol's roles must be disjoint from the roles of every protocol after it.
+/// - Used by `TParN` to check its branches. See also: [`Disjoint`].
+pub trait PairwiseDisjoint {}
+impl PairwiseDisjoint for protocol::Nil {}
+impl<H, T> PairwiseDisjoint for protocol::Cons<H, T>
+where
+    H: UniqueRoles,
+    T: RolesOfEach + PairwiseDisjoint,
+    <T as RolesOfEach>::Roles: protocol::Dedup<protocol::ByRole>,
+    (): protocol::Disjoint<
+        <H as UniqueRoles>::Roles,
+        <<T as RolesOfEach>::Roles as protocol::Dedup<protocol::ByRole>>::Output,
+    >,
+{
+}
+
+/// Marker for a role `R` that a protocol uses but that is not declared.
+pub struct UndeclaredRole<R>(PhantomData<R>);
+
+/// Marker for a declared role `R` that a protocol never uses.
+pub struct MissingRole<R>(PhantomData<R>);
+
+/// Never implemented: a bound `Marker: RoleCoverageFailure` fails and names
+/// the role found by [`CoversRoles`].
+#[diagnostic::on_unimplemented(
+    message = "the protocol does not use exactly the declared roles: `{Self}`",
+    label = "role set mismatch",
+    note = "check the role for a typo, or add it to or remove it from the declared roles"
+)]
+pub trait RoleCoverageFailure {}
+
+/// Checks that every role of a type-level list is in the list `Declared`.
+///
+/// - Fails naming the first role that is not, as an [`UndeclaredRole`].
+pub trait RolesDeclared<Declared> {}
+impl<Declared> RolesDeclared<Declared> for protocol::Nil {}
+impl<H, T, Declared> RolesDeclared<Declared> for protocol::Cons<H, T>
+where
+    H: protocol::InRoleSet<Declared>,
+    H: RoleDeclaredCase<<H as protocol::InRoleSet<Declared>>::Output>,
+    T: RolesDeclared<Declared>,
+{
+}
+
+/// Helper trait for `RolesDeclared`, dispatching on whether the role is declared.
+pub trait RoleDeclaredCase<IsDeclared> {}
+impl<R> RoleDeclaredCase<types::True> for R {}
+impl<R> RoleDeclaredCase<types::False> for R where UndeclaredRole<R>: RoleCoverageFailure {}
+
+/// Checks that every role of a declared type-level list is in the list `Used`.
+///
+/// - Fails naming the first role that is not, as a [`MissingRole`].
+pub trait RolesUsed<Used> {}
+impl<Used> RolesUsed<Used> for protocol::Nil {}
+impl<H, T, Used> RolesUsed<Used> for protocol::Cons<H, T>
+where
+    H: protocol::InRoleSet<Used>,
+    H: RoleUsedCase<<H as protocol::InRoleSet<Used>>::Output>,
+    T: RolesUsed<Used>,
+{
+}
+
+/// Helper trait for `RolesUsed`, dispatching on whether the role is used.
+pub trait RoleUsedCase<IsUsed> {}
+impl<R> RoleUsedCase<types::True> for R {}
+impl<R> RoleUsedCase<types::False> for R where MissingRole<R>: RoleCoverageFailure {}
+
+/// Checks that a protocol uses exactly the roles of the type-level list `Declared`.
+///
+/// - The role-by-role form of [`SameRoles`] on [`UniqueRoles`], naming the offending role.
+/// - Order and repetitions do not matter.
+/// - See also: [`assert_roles_covered!`] macro.
+pub trait CoversRoles<Declared> {}
+impl<G, Declared> CoversRoles<Declared> for G
+where
+    G: UniqueRoles,
+    <G as UniqueRoles>::Roles: RolesDeclared<Declared>,
+    Declared: RolesUsed<<G as UniqueRoles>::Roles>,
+{
+}
+
+/// Extracts the timing constraint of a timed protocol construct.
+///
+/// - Implemented for the timed combinators `TTimeout`, `TDelay` and `TDeadline`.
+/// - `Dur` is the duration marker; `DURATION` its value, for runtimes and exporters.
+pub trait TimingOf {
+    type Dur: types::DurationMarker;
+    const DURATION: Duration = <Self::Dur as types::DurationMarker>::DURATION;
+}
+impl<IO, Lbl, Dur, Body, OnTimeout> TimingOf for protocol::TTimeout<IO, Lbl, Dur, Body, OnTimeout>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    Body: protocol::TSession<IO>,
+    OnTimeout: protocol::TSession<IO>,
+{
+    type Dur = Dur;
+}
+
+impl<IO, Lbl, R, Dur, T> TimingOf for protocol::TDelay<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: protocol::TSession<IO>,
+{
+    type Dur = Dur;
+}
+impl<IO, Lbl, R, Dur, T> TimingOf for protocol::TDeadline<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: protocol::TSession<IO>,
+{
+    type Dur = Dur;
+}
+
+/// Extracts the branch weights of a probabilistic choice.
+///
+/// - Implemented for `TProb`.
+/// - `Left` and `Right` are the weight markers; `LEFT` and `RIGHT` their values, for
+///   simulators and exporters.
+pub trait WeightsOf {
+    type Left: types::WeightMarker;
+    type Right: types::WeightMarker;
+    const LEFT: u32 = <Self::Left as types::WeightMarker>::WEIGHT;
+    const RIGHT: u32 = <Self::Right as types::WeightMarker>::WEIGHT;
+}
+impl<IO, Lbl, WeightL, L, WeightR, R> WeightsOf for protocol::TProb<IO, Lbl, WeightL, L, WeightR, R>
+where
+    Lbl: types::ProtocolLabel,
+    WeightL: types::WeightMarker,
+    WeightR: types::WeightMarker,
+    L: protocol::TSession<IO>,
+    R: protocol::TSession<IO>,
+{
+    type Left = WeightL;
+    type Right = WeightR;
+}
+
+/// Extracts the predicate of a refined protocol fragment.
+///
+/// - Implemented for the global `TRefine` and its projection `EpRefine`.
+/// - `Pred` is the predicate; `DESCRIPTION` its text, for runtimes and exporters.
+pub trait RefinementOf {
+    type Pred: types::Predicate;
+    const DESCRIPTION: &'static str = <Self::Pred as types::Predicate>::DESCRIPTION;
+}
+impl<Pred: types::Predicate, Inner> RefinementOf for protocol::TRefine<Pred, Inner> {
+    type Pred = Pred;
+}
+impl<Pred: types::Predicate, Inner> RefinementOf for protocol::EpRefine<Pred, Inner> {
+    type Pred = Pred;
+}
+
+/// Collects the metadata of every `TAnnotate` in a protocol as a type-level list.
+///
+/// - Implemented for all protocol combinators; metadata is listed in
+///   protocol order, outer annotations before inner ones.
+/// - Annotations do not affect projection, so only global protocols carry them.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Audited; struct HighPriority;
+/// type Audit = TAnnotate<
+///     Audited,
+///     TInteract<Http, EmptyLabel, TClient, Message, TAnnotate<HighPriority, TEnd<Http>>>,
+/// >;
+/// assert_type_eq!(<Audit as AnnotationsOf>::Annotations, tlist!(Audited, HighPriority));
+/// ```
+pub trait AnnotationsOf {
+    type Annotations;
+}
+impl<IO, Lbl> AnnotationsOf for protocol::TEnd<IO, Lbl> {
+    type Annotations = protocol::Nil;
+}
+impl<IO, Lbl: types::ProtocolLabel, R, H, T> AnnotationsOf for protocol::TInteract<IO, Lbl, R, H, T>
+where
+    T: protocol::TSession<IO> + AnnotationsOf,
+{
+    type Annotations = <T as AnnotationsOf>::Annotations;
+}
+impl<IO, Lbl: types::ProtocolLabel, From, To, H, T> AnnotationsOf
+    for protocol::TMsg<IO, Lbl, From, To, H, T>
+where
+    T: protocol::TSession<IO> + AnnotationsOf,
+{
+    type Annotations = <T as AnnotationsOf>::Annotations;
+}
+impl<IO, Lbl: types::ProtocolLabel, From, ToSet, H, T> AnnotationsOf
+    for protocol::TBroadcast<IO, Lbl, From, ToSet, H, T>
+where
+    T: protocol::TSession<IO> + AnnotationsOf,
+{
+    type Annotations = <T as AnnotationsOf>::Annotations;
+}
+impl<IO, Lbl: types::ProtocolLabel, From, WorkerSet, H, T> AnnotationsOf
+    for protocol::TAnycast<IO, Lbl, From, WorkerSet, H, T>
+where
+    T: protocol::TSession<IO> + AnnotationsOf,
+{
+    type Annotations = <T as AnnotationsOf>::Annotations;
+}
+impl<IO, Lbl: types::ProtocolLabel, From, To, DelegatedEp, T> AnnotationsOf
+    for protocol::TDelegate<IO, Lbl, From, To, DelegatedEp, T>
+where
+    T: protocol::TSession<IO> + AnnotationsOf,
+{
+    type Annotations = <T as AnnotationsOf>::Annotations;
+}
+impl<IO, Lbl: types::ProtocolLabel, A, B, Cont> AnnotationsOf
+    for protocol::TDisconnect<IO, Lbl, A, B, Cont>
+where
+    Cont: protocol::TSession<IO> + AnnotationsOf,
+{
+    type Annotations = <Cont as AnnotationsOf>::Annotations;
+}
+impl<IO, Lbl: types::ProtocolLabel, A, B, Cont> AnnotationsOf
+    for protocol::TConnect<IO, Lbl, A, B, Cont>
+where
+    Cont: protocol::TSession<IO> + AnnotationsOf,
+{
+    type Annotations = <Cont as AnnotationsOf>::Annotations;
+}
+impl<IO, Lbl: types::ProtocolLabel, R, Cont> AnnotationsOf for protocol::TEndFor<IO, Lbl, R, Cont>
+where
+    Cont: protocol::TSession<IO> + AnnotationsOf,
+{
+    type Annotations = <Cont as AnnotationsOf>::Annotations;
+}
+impl<IO, Lbl: types::ProtocolLabel, L, R> AnnotationsOf for protocol::TChoice<IO, Lbl, L, R>
+where
+    L: protocol::TSession<IO> + AnnotationsOf,
+    R: protocol::TSession<IO> + AnnotationsOf,
+    <L as AnnotationsOf>::Annotations: protocol::Concat<<R as AnnotationsOf>::Annotations>,
+{
+    type Annotations = <<L as AnnotationsOf>::Annotations as protocol::Concat<
+        <R as AnnotationsOf>::Annotations,
+    >>::Output;
+}
+impl<IO, Lbl: types::ProtocolLabel, Branches> AnnotationsOf
+    for protocol::TChoiceN<IO, Lbl, Branches>
+where
+    Branches: protocol::SessionList<IO> + AnnotationsOfEach,
+{
+    type Annotations = <Branches as AnnotationsOfEach>::Annotations;
+}
+impl<IO, Lbl: types::ProtocolLabel, Chooser, Branches> AnnotationsOf
+    for protocol::TSelect<IO, Lbl, Chooser, Branches>
+where
+    Branches: protocol::SessionList<IO> + AnnotationsOfEach,
+{
+    type Annotations = <Branches as AnnotationsOfEach>::Annotations;
+}
+impl<IO, Lbl: types::ProtocolLabel, Offeree, Branches> AnnotationsOf
+    for protocol::TOffer<IO, Lbl, Offeree, Branches>
+where
+    Branches: protocol::SessionList<IO> + AnnotationsOfEach,
+{
+    type Annotations = <Branches as AnnotationsOfEach>::Annotations;
+}
+impl<IO, Lbl: types::ProtocolLabel, High, Low> AnnotationsOf
+    for protocol::TPriorityChoice<IO, Lbl, High, Low>
+where
+    High: protocol::TSession<IO> + AnnotationsOf,
+    Low: protocol::TSession<IO> + AnnotationsOf,
+    <High as AnnotationsOf>::Annotations: protocol::Concat<<Low as AnnotationsOf>::Annotations>,
+{
+    type Annotations = <<High as AnnotationsOf>::Annotations as protocol::Concat<
+        <Low as AnnotationsOf>::Annotations,
+    >>::Output;
+}
+impl<IO, Lbl, WeightL, L, WeightR, R> AnnotationsOf
+    for protocol::TProb<IO, Lbl, WeightL, L, WeightR, R>
+where
+    Lbl: types::ProtocolLabel,
+    WeightL: types::WeightMarker,
+    WeightR: types::WeightMarker,
+    L: protocol::TSession<IO> + AnnotationsOf,
+    R: protocol::TSession<IO> + AnnotationsOf,
+    <L as AnnotationsOf>::Annotations: protocol::Concat<<R as AnnotationsOf>::Annotations>,
+{
+    type Annotations = <<L as AnnotationsOf>::Annotations as protocol::Concat<
+        <R as AnnotationsOf>::Annotations,
+    >>::Output;
+}
+impl<IO, Lbl: types::ProtocolLabel, L, R, IsDisjoint> AnnotationsOf
+    for protocol::TPar<IO, Lbl, L, R, IsDisjoint>
+where
+    L: protocol::TSession<IO> + AnnotationsOf,
+    R: protocol::TSession<IO> + AnnotationsOf,
+    <L as AnnotationsOf>::Annotations: protocol::Concat<<R as AnnotationsOf>::Annotations>,
+{
+    type Annotations = <<L as AnnotationsOf>::Annotations as protocol::Concat<
+        <R as AnnotationsOf>::Annotations,
+    >>::Output;
+}
+impl<IO, Lbl: types::ProtocolLabel, Branches> AnnotationsOf for protocol::TParN<IO, Lbl, Branches>
+where
+    Branches: protocol::SessionList<IO> + AnnotationsOfEach,
+{
+    type Annotations = <Branches as AnnotationsOfEach>::Annotations;
+}
+impl<IO, Lbl: types::ProtocolLabel, Steps> AnnotationsOf for protocol::TShuffle<IO, Lbl, Steps>
+where
+    Steps: protocol::SessionList<IO> + AnnotationsOfEach,
+{
+    type Annotations = <Steps as AnnotationsOfEach>::Annotations;
+}
+impl<IO, Lbl: types::ProtocolLabel, S> AnnotationsOf for protocol::TRec<IO, Lbl, S>
+where
+    S: protocol::TSession<IO> + AnnotationsOf,
+{
+    type Annotations = <S as AnnotationsOf>::Annotations;
+}
+impl<IO, Lbl: types::ProtocolLabel, Var, Body> AnnotationsOf for protocol::TRecX<IO, Lbl, Var, Body>
+where
+    Body: protocol::TSession<IO> + AnnotationsOf,
+{
+    type Annotations = <Body as AnnotationsOf>::Annotations;
+}
+impl<Var> AnnotationsOf for protocol::TVar<Var> {
+    type Annotations = protocol::Nil;
+}
+impl<IO, Lbl, Dur, Body, OnTimeout> AnnotationsOf
+    for protocol::TTimeout<IO, Lbl, Dur, Body, OnTimeout>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    Body: protocol::TSession<IO> + AnnotationsOf,
+    OnTimeout: protocol::TSession<IO> + AnnotationsOf,
+    <Body as AnnotationsOf>::Annotations:
+        protocol::Concat<<OnTimeout as AnnotationsOf>::Annotations>,
+{
+    type Annotations = <<Body as AnnotationsOf>::Annotations as protocol::Concat<
+        <OnTimeout as AnnotationsOf>::Annotations,
+    >>::Output;
+}
+impl<IO, Lbl, R, Dur, T> AnnotationsOf for protocol::TDelay<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: protocol::TSession<IO> + AnnotationsOf,
+{
+    type Annotations = <T as AnnotationsOf>::Annotations;
+}
+impl<IO, Lbl, R, Dur, T> AnnotationsOf for protocol::TDeadline<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: protocol::TSession<IO> + AnnotationsOf,
+{
+    type Annotations = <T as AnnotationsOf>::Annotations;
+}
+impl<IO, Lbl, Scope, Interruptor, Handler> AnnotationsOf
+    for protocol::TInterrupt<IO, Lbl, Scope, Interruptor, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Scope: protocol::TSession<IO> + AnnotationsOf,
+    Handler: protocol::TSession<IO> + AnnotationsOf,
+    <Scope as AnnotationsOf>::Annotations:
+        protocol::Concat<<Handler as AnnotationsOf>::Annotations>,
+{
+    type Annotations = <<Scope as AnnotationsOf>::Annotations as protocol::Concat<
+        <Handler as AnnotationsOf>::Annotations,
+    >>::Output;
+}
+impl<IO, Lbl, Decider, Body, Cont> AnnotationsOf
+    for protocol::TOptional<IO, Lbl, Decider, Body, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Body: protocol::TSession<IO> + AnnotationsOf,
+    Cont: protocol::TSession<IO> + AnnotationsOf,
+    <Body as AnnotationsOf>::Annotations: protocol::Concat<<Cont as AnnotationsOf>::Annotations>,
+{
+    type Annotations = <<Body as AnnotationsOf>::Annotations as protocol::Concat<
+        <Cont as AnnotationsOf>::Annotations,
+    >>::Output;
+}
+impl<Pred, Inner> AnnotationsOf for protocol::TRefine<Pred, Inner>
+where
+    Inner: AnnotationsOf,
+{
+    type Annotations = <Inner as AnnotationsOf>::Annotations;
+}
+impl<Meta, Inner> AnnotationsOf for protocol::TAnnotate<Meta, Inner>
+where
+    Inner: AnnotationsOf,
+{
+    type Annotations = protocol::Cons<Meta, <Inner as AnnotationsOf>::Annotations>;
+}
+impl<IO, Lbl: types::ProtocolLabel, R, Err> AnnotationsOf for protocol::TThrow<IO, Lbl, R, Err> {
+    type Annotations = protocol::Nil;
+}
+impl<IO, Lbl, Body, Handler> AnnotationsOf for protocol::TCatch<IO, Lbl, Body, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Body: protocol::TSession<IO> + AnnotationsOf,
+    Handler: protocol::TSession<IO> + AnnotationsOf,
+    <Body as AnnotationsOf>::Annotations: protocol::Concat<<Handler as AnnotationsOf>::Annotations>,
+{
+    type Annotations = <<Body as AnnotationsOf>::Annotations as protocol::Concat<
+        <Handler as AnnotationsOf>::Annotations,
+    >>::Output;
+}
+
+/// Collects the annotations of every protocol in a type-level list, in order.
+///
+/// - Used by n-ary combinators to gather annotations from all of their branches.
+pub trait AnnotationsOfEach {
+    type Annotations;
+}
+impl AnnotationsOfEach for protocol::Nil {
+    type Annotations = protocol::Nil;
+}
+impl<H, T> AnnotationsOfEach for protocol::Cons<H, T>
+where
+    H: AnnotationsOf,
+    T: AnnotationsOfEach,
+    <H as AnnotationsOf>::Annotations: protocol::Concat<<T as AnnotationsOfEach>::Annotations>,
+{
+    type Annotations = <<H as AnnotationsOf>::Annotations as protocol::Concat<
+        <T as AnnotationsOfEach>::Annotations,
+    >>::Output;
 }
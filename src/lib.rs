@@ -18,6 +18,13 @@
 //! - Parallel branches must be disjoint (no overlapping roles).
 //! - Macros and traits prevent invalid protocol construction.
 //!
+//! ## Scope of the Checks
+//! The checks on global protocols follow the protocol as written. Unless a
+//! check says otherwise, a recursion variable is not unfolded: a loop body is
+//! checked once, and a `TVar` ends the path it closes. `TInteract` names no
+//! receiver, so the checks built on channels and role pairs
+//! ([`ConnectionsRespected`], [`DeadlockFree`], [`Linear`]) find none in it.
+//!
 //! ## See Also
 //! - Protocol examples: `tests/protocols/`
 //! - Negative/compile-fail tests: `tests/trybuild/`
@@ -29,7 +36,7 @@
 //!
 //! ## How it works
 //! - The [`ProjectRole`] trait recursively traverses a global protocol (a type implementing [`TSession`]) and produces the local protocol for a specific role.
-//! - Each global combinator (`TInteract`, `TChoice`, `TPar`, etc.) has a corresponding endpoint type (`EpSend`, `EpRecv`, `EpChoice`, `EpPar`, etc.).
+//! - Each global combinator (`TInteract`, `TChoice`, `TPar`, etc.) has a corresponding endpoint type (`EpSend`, `EpRecv`, `EpSelect`/`EpOffer`, `EpPar`, etc.).
 //! - Helper traits (e.g., `ProjectInteract`, `ProjectChoice`, `ProjectPar`) are used to avoid overlapping trait impls and to dispatch on type-level booleans.
 //!
 //! ## Example
@@ -77,6 +84,8 @@ macro_rules! tchoice {
 
 /// Macro for building n-ary protocol parallel compositions.
 ///
+/// Expands to a flat `TParN` with an `EmptyLabel`.
+///
 /// # Example
 /// ```rust
 /// use besedarium::*;
@@ -90,7 +99,7 @@ macro_rules! tchoice {
 #[macro_export]
 macro_rules! tpar {
     ($io:ty; $($branch:ty),* $(,)?) => {
-        <tlist!($($branch),*) as ToTPar<$io>>::Output
+        TParN<$io, EmptyLabel, tlist!($($branch),*)>
     };
 }
 
@@ -98,6 +107,7 @@ macro_rules! tpar {
 macro_rules! assert_type_eq {
     ($A:ty, $B:ty) => {
         const _: fn() = || {
+            #[allow(dead_code)]
             fn _assert_type_eq()
             where
                 $A: $crate::TypeEq<$B>,
@@ -107,22 +117,67 @@ macro_rules! assert_type_eq {
     };
 }
 
+// Backs `assert_disjoint!(par ...)`: role disjointness by default, or
+// `InterleavingSafe` with the `interleaving` feature.
+#[cfg(not(feature = "interleaving"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __assert_disjoint_par {
+    ($TPar:ty) => {
+        const _: fn() = || {
+            #[allow(dead_code)]
+            fn _assert_disjoint_par()
+            where
+                $TPar: $crate::AssertDisjoint,
+            {
+            }
+        };
+    };
+}
+
+#[cfg(feature = "interleaving")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __assert_disjoint_par {
+    ($TPar:ty) => {
+        const _: fn() = || {
+            #[allow(dead_code)]
+            fn _assert_interleaving_safe()
+            where
+                $TPar: $crate::InterleavingSafe,
+            {
+            }
+        };
+    };
+}
+
 #[macro_export]
 macro_rules! assert_disjoint {
     ($A:ty, $B:ty) => {
         const _: fn() = || {
+            #[allow(dead_code)]
             fn _assert_disjoint()
             where
                 (): $crate::Disjoint<
-                    <$A as $crate::RolesOf>::Roles,
-                    <$B as $crate::RolesOf>::Roles,
+                    <$A as $crate::UniqueRoles>::Roles,
+                    <$B as $crate::UniqueRoles>::Roles,
                 >,
             {
             }
         };
     };
     (par $TPar:ty) => {
-        type _Checked = <$TPar as $crate::AssertDisjoint>::Output;
+        $crate::__assert_disjoint_par!($TPar);
+    };
+    (messages $G:ty) => {
+        const _: fn() = || {
+            #[allow(dead_code)]
+            fn _assert_disjoint_messages()
+            where
+                $G: $crate::DisjointMessages,
+            {
+            }
+        };
     };
 }
 
@@ -145,26 +200,493 @@ macro_rules! extract_roles {
 macro_rules! assert_unique_labels {
     ($T:ty) => {
         const _: fn() = || {
+            #[allow(dead_code)]
             fn _assert_unique_labels()
             where
-                <$T as $crate::LabelsOf>::Labels: $crate::UniqueList,
+                <$T as $crate::StepLabelsOf>::Labels: $crate::UniqueList,
+            {
+            }
+        };
+    };
+}
+
+/// Assert at compile time that a protocol uses exactly the declared roles.
+///
+/// The second argument lists the roles, e.g. `tlist!(Alice, Bob)`; order does
+/// not matter. A role that is used but not declared, such as a typo'd role
+/// type, and a declared role that is never used are both rejected. See
+/// [`CoversRoles`].
+#[macro_export]
+macro_rules! assert_roles_covered {
+    ($G:ty, $Declared:ty) => {
+        const _: fn() = || {
+            #[allow(dead_code)]
+            fn _assert_roles_covered()
+            where
+                $G: $crate::CoversRoles<$Declared>,
+            {
+            }
+        };
+    };
+}
+
+/// Assert at compile time that a protocol's payloads evolved compatibly from a
+/// previous version of the same protocol.
+///
+/// The first argument is the new protocol, the second the old one. See
+/// [`PayloadCompatible`] for the compatibility rule.
+#[macro_export]
+macro_rules! assert_payload_compatible {
+    ($New:ty, $Old:ty) => {
+        const _: fn() = || {
+            #[allow(dead_code)]
+            fn _assert_payload_compatible()
+            where
+                $New: $crate::PayloadCompatible<$Old>,
+            {
+            }
+        };
+    };
+}
+
+/// Assert at compile time that a local session type is a session subtype of
+/// another.
+///
+/// The first argument is the subtype, the second the expected supertype. See
+/// [`SubtypeOf`] for the subtyping rule.
+#[macro_export]
+macro_rules! assert_subtype {
+    ($Sub:ty, $Super:ty) => {
+        const _: fn() = || {
+            #[allow(dead_code)]
+            fn _assert_subtype()
+            where
+                $Sub: $crate::SubtypeOf<$Super>,
+            {
+            }
+        };
+    };
+}
+
+/// Assert at compile time that a local session type is an asynchronous
+/// subtype of another.
+///
+/// `assert_async_subtype!(Ep, Expected)` compares two local types;
+/// `assert_async_subtype!(Ep, IO; G => Me)` compares an endpoint against the
+/// projection of a global protocol onto `Me`. See [`AsyncSubtype`].
+#[macro_export]
+macro_rules! assert_async_subtype {
+    ($Sub:ty, $io:ty; $G:ty => $Me:ty $(,)?) => {
+        $crate::assert_async_subtype!($Sub, <() as $crate::ProjectRole<$Me, $io, $G>>::Out);
+    };
+    ($Sub:ty, $Super:ty $(,)?) => {
+        const _: fn() = || {
+            #[allow(dead_code)]
+            fn _assert_async_subtype()
+            where
+                $Sub: $crate::AsyncSubtype<$Super>,
+            {
+            }
+        };
+    };
+}
+
+/// Assert at compile time that a new version of a global protocol can be
+/// deployed where the previous one is expected.
+///
+/// The first argument is the new protocol, the second the old one.
+/// `assert_global_subtype!(New, Old, EvolvedPayloads)` also lets payload
+/// schemas evolve. See [`GlobalSubtype`] for the subtyping rule.
+#[macro_export]
+macro_rules! assert_global_subtype {
+    ($New:ty, $Old:ty $(,)?) => {
+        $crate::assert_global_subtype!($New, $Old, $crate::SamePayloads);
+    };
+    ($New:ty, $Old:ty, $Payloads:ty $(,)?) => {
+        const _: fn() = || {
+            #[allow(dead_code)]
+            fn _assert_global_subtype()
+            where
+                $New: $crate::GlobalSubtype<$Old, $Payloads>,
+            {
+            }
+        };
+    };
+}
+
+/// Assert at compile time that a local session type refines another.
+///
+/// The first argument is the new local type, the second the one it must agree
+/// with. See [`Refines`] for the refinement rule.
+#[macro_export]
+macro_rules! assert_refines {
+    ($New:ty, $Old:ty) => {
+        const _: fn() = || {
+            #[allow(dead_code)]
+            fn _assert_refines()
+            where
+                $New: $crate::Refines<$Old>,
+            {
+            }
+        };
+    };
+}
+
+/// Assert at compile time that every choice of a global protocol is decided
+/// by one role and learned by the others before they act on it.
+///
+/// See [`ChoiceAware`] for what is checked.
+#[macro_export]
+macro_rules! assert_choice_aware {
+    ($G:ty) => {
+        const _: fn() = || {
+            #[allow(dead_code)]
+            fn _assert_choice_aware()
+            where
+                $G: $crate::ChoiceAware,
+            {
+            }
+        };
+    };
+}
+
+/// Assert at compile time that the steps of a global protocol are connected.
+///
+/// `assert_connected!(G)` checks [`Connected`]: the sender of every step took
+/// part in the previous one. `assert_connected!(async G)` checks the relaxed
+/// [`AsyncConnected`] for asynchronous communication.
+#[macro_export]
+macro_rules! assert_connected {
+    (async $G:ty) => {
+        const _: fn() = || {
+            #[allow(dead_code)]
+            fn _assert_async_connected()
+            where
+                $G: $crate::AsyncConnected,
+            {
+            }
+        };
+    };
+    ($G:ty) => {
+        const _: fn() = || {
+            #[allow(dead_code)]
+            fn _assert_connected()
+            where
+                $G: $crate::Connected,
+            {
+            }
+        };
+    };
+}
+
+/// Assert at compile time that a global protocol cannot deadlock.
+///
+/// See [`DeadlockFree`] for what is checked.
+#[macro_export]
+macro_rules! assert_deadlock_free {
+    ($G:ty) => {
+        const _: fn() = || {
+            #[allow(dead_code)]
+            fn _assert_deadlock_free()
+            where
+                $G: $crate::DeadlockFree,
+            {
+            }
+        };
+    };
+}
+
+/// Assert at compile time that the branches of every offer can be told apart
+/// by the label of the frame received.
+///
+/// `assert_deterministic!(Ep)` checks a local session type;
+/// `assert_deterministic!(IO; G => Alice, Bob)` checks the projections of a
+/// global protocol onto the given roles. See [`Deterministic`].
+#[macro_export]
+macro_rules! assert_deterministic {
+    ($io:ty; $G:ty => $($Me:ty),+ $(,)?) => {
+        const _: fn() = || {
+            #[allow(dead_code)]
+            fn _assert_deterministic()
+            where
+                $(<() as $crate::ProjectRole<$Me, $io, $G>>::Out: $crate::Deterministic,)+
+            {
+            }
+        };
+    };
+    ($Ep:ty) => {
+        const _: fn() = || {
+            #[allow(dead_code)]
+            fn _assert_deterministic()
+            where
+                $Ep: $crate::Deterministic,
+            {
+            }
+        };
+    };
+}
+
+/// Assert at compile time that the parallel compositions of a global protocol
+/// never share a channel between branches.
+///
+/// See [`Linear`] for what is checked.
+#[macro_export]
+macro_rules! assert_linear {
+    ($G:ty) => {
+        const _: fn() = || {
+            #[allow(dead_code)]
+            fn _assert_linear()
+            where
+                $G: $crate::Linear,
+            {
+            }
+        };
+    };
+}
+
+/// Assert at compile time that no choice of a global protocol leaves a
+/// message unreceived.
+///
+/// See [`OrphanFree`] for what is checked.
+#[macro_export]
+macro_rules! assert_orphan_free {
+    ($G:ty) => {
+        const _: fn() = || {
+            #[allow(dead_code)]
+            fn _assert_orphan_free()
+            where
+                $G: $crate::OrphanFree,
+            {
+            }
+        };
+    };
+}
+
+/// Assert at compile time that a global protocol never gets stuck.
+///
+/// See [`HasProgress`] for what is checked.
+#[macro_export]
+macro_rules! assert_progress {
+    ($G:ty) => {
+        const _: fn() = || {
+            #[allow(dead_code)]
+            fn _assert_progress()
+            where
+                $G: $crate::HasProgress,
+            {
+            }
+        };
+    };
+}
+
+/// Assert at compile time that every loop of a global protocol has an exit
+/// branch.
+///
+/// See [`Terminating`] for what is checked.
+#[macro_export]
+macro_rules! assert_terminating {
+    ($G:ty) => {
+        const _: fn() = || {
+            #[allow(dead_code)]
+            fn _assert_terminating()
+            where
+                $G: $crate::Terminating,
+            {
+            }
+        };
+    };
+}
+
+/// Assert at compile time that a global protocol can produce a trace of
+/// `Step`s and then end.
+///
+/// See [`Admits`] for how steps are matched.
+#[macro_export]
+macro_rules! assert_admits {
+    ($G:ty, $Trace:ty $(,)?) => {
+        const _: fn() = || {
+            #[allow(dead_code)]
+            fn _assert_admits()
+            where
+                $G: $crate::Admits<$Trace>,
+            {
+            }
+        };
+    };
+}
+
+/// Assert at compile time that the projections of a two-party global protocol
+/// are duals of each other.
+///
+/// The first argument is the protocol marker, then the protocol and its two
+/// roles. See [`DualityHolds`] for what is checked.
+#[macro_export]
+macro_rules! assert_dual {
+    ($io:ty; $G:ty, $A:ty, $B:ty $(,)?) => {
+        const _: fn() = || {
+            #[allow(dead_code)]
+            fn _assert_dual()
+            where
+                $G: $crate::DualityHolds<$io, $A, $B>,
+            {
+            }
+        };
+    };
+}
+
+/// Assert at compile time that two global protocols have the same structure,
+/// ignoring their labels.
+///
+/// `assert_equiv!(IO; A, B)` still compares the labels of `TEnd`;
+/// `assert_equiv!(IO; A, B, IgnoreEnds)` ignores them too. See
+/// [`EquivalentTo`].
+#[macro_export]
+macro_rules! assert_equiv {
+    ($io:ty; $A:ty, $B:ty $(,)?) => {
+        $crate::assert_equiv!($io; $A, $B, $crate::CompareEnds);
+    };
+    ($io:ty; $A:ty, $B:ty, $Ends:ty $(,)?) => {
+        const _: fn() = || {
+            #[allow(dead_code)]
+            fn _assert_equiv()
+            where
+                $A: $crate::EquivalentTo<$io, $B, $Ends>,
+            {
+            }
+        };
+    };
+}
+
+/// Assert at compile time that a global protocol can be projected onto each of
+/// the given roles.
+///
+/// The first argument is the protocol marker, then the protocol and the roles.
+/// See [`IsProjectable`] for what is checked.
+///
+/// ```rust
+/// use besedarium::*;
+/// struct Alice; struct Bob; struct Ping;
+/// impl Role for Alice {} impl Role for Bob {}
+/// impl ProtocolLabel for Ping {}
+/// impl RoleEq<Alice> for Alice { type Output = True; }
+/// impl RoleEq<Bob> for Alice   { type Output = False; }
+/// impl RoleEq<Alice> for Bob   { type Output = False; }
+/// impl RoleEq<Bob> for Bob     { type Output = True; }
+///
+/// type G = TMsg<Http, Ping, Alice, Bob, Message, TEnd<Http, EmptyLabel>>;
+/// assert_projectable!(Http; G => Alice, Bob);
+/// ```
+#[macro_export]
+macro_rules! assert_projectable {
+    ($io:ty; $G:ty => $($Me:ty),+ $(,)?) => {
+        const _: fn() = || {
+            #[allow(dead_code)]
+            fn _assert_projectable()
+            where
+                $($G: $crate::IsProjectable<$Me, $io>,)+
+            {
+            }
+        };
+    };
+}
+
+/// Assert at compile time that projecting the composition of two global
+/// protocols onto a role equals composing their projections.
+///
+/// `assert_projection_composes!(IO; G1, G2, Role)` checks
+/// [`ProjectionComposes`].
+#[macro_export]
+macro_rules! assert_projection_composes {
+    ($io:ty; $G1:ty, $G2:ty, $Me:ty $(,)?) => {
+        const _: fn() = || {
+            #[allow(dead_code)]
+            fn _assert_projection_composes()
+            where
+                $G1: $crate::ProjectionComposes<$io, $G2, $Me>,
             {
             }
         };
     };
 }
 
+/// Assert at compile time that a global protocol is well formed.
+///
+/// `assert_well_formed!(IO; G)` checks [`WellFormed`] at the active
+/// strictness level; `assert_well_formed!(IO; G, StrictLevel)` names the level.
+#[macro_export]
+macro_rules! assert_well_formed {
+    ($io:ty; $G:ty $(,)?) => {
+        const _: fn() = || {
+            #[allow(dead_code)]
+            fn _assert_well_formed()
+            where
+                $G: $crate::WellFormed<$io>,
+            {
+            }
+        };
+    };
+    ($io:ty; $G:ty, $Level:ty $(,)?) => {
+        const _: fn() = || {
+            #[allow(dead_code)]
+            fn _assert_well_formed()
+            where
+                $G: $crate::WellFormedAt<$io, $Level>,
+            {
+            }
+        };
+    };
+}
+
+/// Declare a generic type `Name<N>` as a [`RoleFamily`] indexed by `N`.
+///
+/// Implements `Role` and `RoleFamily` for every member, and the family-level
+/// `RoleEq` that compares two members through [`IndexEq`]. `RoleEq` impls
+/// against roles outside the family are still written by hand.
+///
+/// ```rust
+/// use besedarium::*;
+/// use core::marker::PhantomData;
+/// struct Worker<N>(PhantomData<N>);
+/// role_family!(Worker);
+///
+/// assert_type_eq!(<Worker<Zero> as RoleEq<Worker<Zero>>>::Output, True);
+/// assert_type_eq!(<Worker<Zero> as RoleEq<Worker<Succ<Zero>>>>::Output, False);
+/// ```
+#[macro_export]
+macro_rules! role_family {
+    ($family:ident) => {
+        impl<N> $crate::Role for $family<N> {}
+        impl<N> $crate::RoleFamily for $family<N> {
+            type Index = N;
+        }
+        impl<N: $crate::IndexEq<M>, M> $crate::RoleEq<$family<M>> for $family<N> {
+            type Output = <N as $crate::IndexEq<M>>::Output;
+        }
+    };
+}
+
 /// ## Compile-time Label Uniqueness Assertion
 ///
-/// To ensure that all protocol labels are unique (no duplicates), use the [`assert_unique_labels!`] macro:
+/// To ensure that all protocol labels are unique (no duplicates), use the [`assert_unique_labels!`] macro.
+/// Labels are compared with `LabelEq`; the labels of ends are not checked:
 ///
 /// ```rust
 /// use besedarium::*;
+/// struct MyChoice; impl ProtocolLabel for MyChoice {}
 /// struct MyLabel1; impl ProtocolLabel for MyLabel1 {}
 /// struct MyLabel2; impl ProtocolLabel for MyLabel2 {}
+/// impl LabelEq<MyChoice> for MyChoice { type Output = True; }
+/// impl LabelEq<MyLabel1> for MyChoice { type Output = False; }
+/// impl LabelEq<MyLabel2> for MyChoice { type Output = False; }
+/// impl LabelEq<MyChoice> for MyLabel1 { type Output = False; }
+/// impl LabelEq<MyLabel1> for MyLabel1 { type Output = True; }
+/// impl LabelEq<MyLabel2> for MyLabel1 { type Output = False; }
+/// impl LabelEq<MyChoice> for MyLabel2 { type Output = False; }
+/// impl LabelEq<MyLabel1> for MyLabel2 { type Output = False; }
+/// impl LabelEq<MyLabel2> for MyLabel2 { type Output = True; }
 /// type MyProtocol = TChoice<
 ///     Http,
-///     MyLabel1,
+///     MyChoice,
 ///     TInteract<Http, MyLabel1, TClient, Message, TEnd<Http, MyLabel1>>,
 ///     TInteract<Http, MyLabel2, TServer, Response, TEnd<Http, MyLabel2>>
 /// >;
@@ -177,15 +699,32 @@ pub(crate) mod sealed {
 // Update protocol module reference to use the directory module
 mod protocol;
 pub use protocol::*;
+mod clock;
 mod introspection;
 mod types;
 pub use types::*;
 
 // Re-export key introspection traits
-pub use introspection::{LabelsOf, RolesOf};
+pub use introspection::{
+    AddIo, AddIoCase, AddIos, AnnotationsOf, AnnotationsOfEach, CommunicationGraph, CoversRoles,
+    CriticalPathOf, CriticalPathOfEach, DepthOf, DepthOfEach, FirstActionCase, FirstActionOf,
+    FirstActionOfEach, IOsOf, IOsOfEach, IoEq, LabelsOf, LabelsOfEach, LastActionCase,
+    LastActionOf, LastActionsWith, LastActionsWithEach, LengthOf, LengthOfEach, ListLength,
+    MessageCountOf, MessageCountOfEach, MessagesOf, MessagesOfEach, MissingRole, Nat, NatAdd,
+    NatIf, NatMax, PairwiseDisjoint, PrependRoles, RefinementOf, RoleCoverageFailure,
+    RoleDeclaredCase, RoleUsedCase, RolesDeclared, RolesOf, RolesOfEach, RolesUsed, StepLabelsOf,
+    StepLabelsOfEach, TakesPart, TimingOf, UndeclaredRole, UniqueRoles, WeightsOf,
+};
+
+// Re-export the time sources used by timing features
+pub use clock::{Clock, MockClock, MonotonicClock};
 
 // Note: Most protocol types are now re-exported via protocol/mod.rs
 // so we don't need to repeat those here.
 
 // Re-export canonical type-level booleans from types
 pub use types::{Bool, False, True};
+
+// Re-export the derives that set `ProtocolLabel::NAME` and `Role::NAME`
+#[cfg(feature = "derive")]
+pub use besedarium_derive::{ProtocolLabel, Role};
@@ -0,0 +1,399 @@
+//! # Choice Awareness Checks
+//!
+//! This module checks knowledge of choice: every choice of a global protocol
+//! is made by one role, and every other role learns the outcome before it
+//! acts differently across the branches.
+//!
+//! Key components:
+//!
+//! - `ChoiceAware`: Every `TChoice` and `TSelect` of a protocol is realizable
+//! - `AgreeOnChoice` / `AgreeOnSelect`: Every role of a choice either decides it or can merge its branches
+//! - `StartedBy`: Every branch of a list starts with an action of a given role
+//! - `MergeAll`: The merge of a non-empty list of local types
+//! - `SplitChoice` / `NotStartedByChooser` / `ChoiceAwarenessFailure`: Failure markers naming the role and label
+//!
+//! The deciding role of a `TChoice` is the role acting first in its
+//! branches, so it must act first in both of them. The chooser of a
+//! `TSelect` is named, and must act first in every branch. Every other role
+//! taking part in the branches must have projections of the branches that
+//! `Merge`: it behaves identically until it receives a label telling the
+//! branches apart.
+//!
+//! Other choices are checked recursively only: `TChoiceN` and `TOffer` are
+//! projected branch by branch, and `TPriorityChoice` and `TProb` are decided
+//! by the runtime.
+
+use super::base::{Cons, Nil};
+use super::global::*;
+use super::local::RoleEq;
+use super::transforms::{ActsFirst, MergeBranches, ProjectEach, ProjectRole};
+use super::utils::Concat;
+use crate::introspection::{RolesOf, RolesOfEach};
+use crate::types;
+use core::marker::PhantomData;
+
+/// Failure marker: `R` acts first in only some branches of the choice
+/// labelled `Lbl`, so no single role decides it.
+pub struct SplitChoice<R, Lbl>(PhantomData<(R, Lbl)>);
+
+/// Failure marker: a branch of the selection labelled `Lbl` does not start
+/// with an action of its chooser `Chooser`.
+pub struct NotStartedByChooser<Chooser, Lbl>(PhantomData<(Chooser, Lbl)>);
+
+/// Never implemented: a bound `Marker: ChoiceAwarenessFailure` fails and
+/// names the unrealizable choice found by [`ChoiceAware`].
+#[diagnostic::on_unimplemented(
+    message = "the choice cannot be realized: `{Self}`",
+    label = "no single role decides this choice",
+    note = "start every branch of a choice with an action of the role that decides it"
+)]
+pub trait ChoiceAwarenessFailure {}
+
+/// The merge of a non-empty type-level list of local types of role `Me`.
+pub trait MergeAll<Me> {
+    type Out;
+}
+
+impl<Me, H> MergeAll<Me> for Cons<H, Nil> {
+    type Out = H;
+}
+
+impl<Me, H, H2, T> MergeAll<Me> for Cons<H, Cons<H2, T>>
+where
+    Cons<H2, T>: MergeAll<Me>,
+    H: MergeBranches<Me, <Cons<H2, T> as MergeAll<Me>>::Out>,
+{
+    type Out = <H as MergeBranches<Me, <Cons<H2, T> as MergeAll<Me>>::Out>>::Out;
+}
+
+/// Every branch of a type-level list starts with an action of `Chooser`, the
+/// chooser of the selection labelled `Lbl`.
+pub trait StartedBy<Chooser, Lbl> {}
+
+impl<Chooser, Lbl> StartedBy<Chooser, Lbl> for Nil {}
+
+impl<H, T, Chooser, Lbl> StartedBy<Chooser, Lbl> for Cons<H, T>
+where
+    H: ActsFirst<Chooser>,
+    (): StartedByCase<Chooser, Lbl, <H as ActsFirst<Chooser>>::Output>,
+    T: StartedBy<Chooser, Lbl>,
+{
+}
+
+/// Helper trait for `StartedBy`, dispatching on whether the chooser acts
+/// first in a branch.
+pub trait StartedByCase<Chooser, Lbl, ActsFirst> {}
+
+impl<Chooser, Lbl> StartedByCase<Chooser, Lbl, types::True> for () {}
+
+impl<Chooser, Lbl> StartedByCase<Chooser, Lbl, types::False> for () where
+    NotStartedByChooser<Chooser, Lbl>: ChoiceAwarenessFailure
+{
+}
+
+/// Every role of a type-level list either decides the choice labelled `Lbl`
+/// between `L` and `R`, acting first in both, or can merge its projections
+/// of the two branches.
+pub trait AgreeOnChoice<IO, Lbl, L, R> {}
+
+impl<IO, Lbl, L, R> AgreeOnChoice<IO, Lbl, L, R> for Nil {}
+
+impl<IO, Lbl, L, R, X, T> AgreeOnChoice<IO, Lbl, L, R> for Cons<X, T>
+where
+    L: ActsFirst<X>,
+    R: ActsFirst<X>,
+    X: AgreeOnChoiceCase<IO, Lbl, L, R, <L as ActsFirst<X>>::Output, <R as ActsFirst<X>>::Output>,
+    T: AgreeOnChoice<IO, Lbl, L, R>,
+{
+}
+
+/// Helper trait for `AgreeOnChoice`, dispatching on whether the role acts
+/// first in each branch.
+pub trait AgreeOnChoiceCase<IO, Lbl, L, R, FirstInL, FirstInR> {}
+
+// The deciding role
+impl<X, IO, Lbl, L, R> AgreeOnChoiceCase<IO, Lbl, L, R, types::True, types::True> for X {}
+
+// Every other role must learn the outcome before acting on it
+impl<X, IO, Lbl, L, R> AgreeOnChoiceCase<IO, Lbl, L, R, types::False, types::False> for X
+where
+    L: TSession<IO>,
+    R: TSession<IO>,
+    (): ProjectRole<X, IO, L> + ProjectRole<X, IO, R>,
+    <() as ProjectRole<X, IO, L>>::Out: MergeBranches<X, <() as ProjectRole<X, IO, R>>::Out>,
+{
+}
+
+impl<X, IO, Lbl, L, R> AgreeOnChoiceCase<IO, Lbl, L, R, types::True, types::False> for X where
+    SplitChoice<X, Lbl>: ChoiceAwarenessFailure
+{
+}
+
+impl<X, IO, Lbl, L, R> AgreeOnChoiceCase<IO, Lbl, L, R, types::False, types::True> for X where
+    SplitChoice<X, Lbl>: ChoiceAwarenessFailure
+{
+}
+
+/// Every role of a type-level list other than `Chooser` can merge its
+/// projections of all the branches of a selection.
+pub trait AgreeOnSelect<IO, Chooser, Branches> {}
+
+impl<IO, Chooser, Branches> AgreeOnSelect<IO, Chooser, Branches> for Nil {}
+
+impl<IO, Chooser, Branches, X, T> AgreeOnSelect<IO, Chooser, Branches> for Cons<X, T>
+where
+    X: RoleEq<Chooser>,
+    X: AgreeOnSelectCase<IO, Branches, <X as RoleEq<Chooser>>::Output>,
+    T: AgreeOnSelect<IO, Chooser, Branches>,
+{
+}
+
+/// Helper trait for `AgreeOnSelect`, dispatching on whether the role is the
+/// chooser.
+pub trait AgreeOnSelectCase<IO, Branches, IsChooser> {}
+
+impl<X, IO, Branches> AgreeOnSelectCase<IO, Branches, types::True> for X {}
+
+impl<X, IO, Branches> AgreeOnSelectCase<IO, Branches, types::False> for X
+where
+    (): ProjectEach<X, IO, Branches>,
+    <() as ProjectEach<X, IO, Branches>>::Out: MergeAll<X>,
+{
+}
+
+/// A global protocol whose every `TChoice` and `TSelect` is decided by a
+/// single role that all other roles learn about before acting differently.
+///
+/// Projection already merges the branches of a `TChoice` for the roles that
+/// do not own it and rejects one whose branches start with different roles,
+/// but accepts a `TChoice` with a branch its owner does not act in, and gives
+/// every non-chooser of a `TSelect` an offer whether or not it can tell the
+/// branches apart. Check a protocol with
+/// [`assert_choice_aware!`](crate::assert_choice_aware).
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Alice; struct Bob; struct Carol; struct Buy; struct Quit; struct Pick;
+/// impl Role for Alice {} impl Role for Bob {} impl Role for Carol {}
+/// impl ProtocolLabel for Buy {} impl ProtocolLabel for Quit {} impl ProtocolLabel for Pick {}
+/// impl LabelEq<Buy> for Buy   { type Output = True; }
+/// impl LabelEq<Quit> for Buy  { type Output = False; }
+/// impl LabelEq<Buy> for Quit  { type Output = False; }
+/// impl LabelEq<Quit> for Quit { type Output = True; }
+/// impl RoleEq<Alice> for Alice { type Output = True; }
+/// impl RoleEq<Bob> for Alice   { type Output = False; }
+/// impl RoleEq<Carol> for Alice { type Output = False; }
+/// impl RoleEq<Alice> for Bob   { type Output = False; }
+/// impl RoleEq<Bob> for Bob     { type Output = True; }
+/// impl RoleEq<Carol> for Bob   { type Output = False; }
+/// impl RoleEq<Alice> for Carol { type Output = False; }
+/// impl RoleEq<Bob> for Carol   { type Output = False; }
+/// impl RoleEq<Carol> for Carol { type Output = True; }
+///
+/// // Alice decides and tells Bob, who then informs Carol with a distinct label
+/// type G = TSelect<
+///     Http,
+///     Pick,
+///     Alice,
+///     tlist!(
+///         TMsg<Http, Buy, Alice, Bob, Message, TMsg<Http, Buy, Bob, Carol, Message, TEnd<Http>>>,
+///         TMsg<Http, Quit, Alice, Bob, Message, TMsg<Http, Quit, Bob, Carol, Message, TEnd<Http>>>,
+///     ),
+/// >;
+/// assert_choice_aware!(G);
+/// ```
+pub trait ChoiceAware {}
+
+impl<IO, Lbl> ChoiceAware for TEnd<IO, Lbl> {}
+
+impl<IO, Lbl: types::ProtocolLabel, R, H, T> ChoiceAware for TInteract<IO, Lbl, R, H, T> where
+    T: TSession<IO> + ChoiceAware
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, To, H, T> ChoiceAware for TMsg<IO, Lbl, From, To, H, T> where
+    T: TSession<IO> + ChoiceAware
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, ToSet, H, T> ChoiceAware
+    for TBroadcast<IO, Lbl, From, ToSet, H, T>
+where
+    T: TSession<IO> + ChoiceAware,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, WorkerSet, H, T> ChoiceAware
+    for TAnycast<IO, Lbl, From, WorkerSet, H, T>
+where
+    T: TSession<IO> + ChoiceAware,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, To, DelegatedEp, T> ChoiceAware
+    for TDelegate<IO, Lbl, From, To, DelegatedEp, T>
+where
+    T: TSession<IO> + ChoiceAware,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, A, B, Cont> ChoiceAware for TDisconnect<IO, Lbl, A, B, Cont> where
+    Cont: TSession<IO> + ChoiceAware
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, A, B, Cont> ChoiceAware for TConnect<IO, Lbl, A, B, Cont> where
+    Cont: TSession<IO> + ChoiceAware
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R, Cont> ChoiceAware for TEndFor<IO, Lbl, R, Cont> where
+    Cont: TSession<IO> + ChoiceAware
+{
+}
+
+// The role acting first in one branch must act first in the other, and every
+// other role of the branches must be able to merge them
+impl<IO, Lbl: types::ProtocolLabel, L, R> ChoiceAware for TChoice<IO, Lbl, L, R>
+where
+    L: TSession<IO> + ChoiceAware + RolesOf,
+    R: TSession<IO> + ChoiceAware + RolesOf,
+    <L as RolesOf>::Roles: Concat<<R as RolesOf>::Roles>,
+    <<L as RolesOf>::Roles as Concat<<R as RolesOf>::Roles>>::Output: AgreeOnChoice<IO, Lbl, L, R>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Branches> ChoiceAware for TChoiceN<IO, Lbl, Branches> where
+    Branches: SessionList<IO> + ChoiceAware
+{
+}
+
+// Every branch starts with the chooser, and every other role of the branches
+// must be able to merge them
+impl<IO, Lbl: types::ProtocolLabel, Chooser, Branches> ChoiceAware
+    for TSelect<IO, Lbl, Chooser, Branches>
+where
+    Branches: SessionList<IO> + ChoiceAware + RolesOfEach + StartedBy<Chooser, Lbl>,
+    <Branches as RolesOfEach>::Roles: AgreeOnSelect<IO, Chooser, Branches>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Offeree, Branches> ChoiceAware
+    for TOffer<IO, Lbl, Offeree, Branches>
+where
+    Branches: SessionList<IO> + ChoiceAware,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, High, Low> ChoiceAware for TPriorityChoice<IO, Lbl, High, Low>
+where
+    High: TSession<IO> + ChoiceAware,
+    Low: TSession<IO> + ChoiceAware,
+{
+}
+
+impl<IO, Lbl, WeightL, L, WeightR, R> ChoiceAware for TProb<IO, Lbl, WeightL, L, WeightR, R>
+where
+    Lbl: types::ProtocolLabel,
+    WeightL: types::WeightMarker,
+    WeightR: types::WeightMarker,
+    L: TSession<IO> + ChoiceAware,
+    R: TSession<IO> + ChoiceAware,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, L, R, IsDisjoint> ChoiceAware
+    for TPar<IO, Lbl, L, R, IsDisjoint>
+where
+    L: TSession<IO> + ChoiceAware,
+    R: TSession<IO> + ChoiceAware,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Branches> ChoiceAware for TParN<IO, Lbl, Branches> where
+    Branches: SessionList<IO> + ChoiceAware
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Steps> ChoiceAware for TShuffle<IO, Lbl, Steps> where
+    Steps: SessionList<IO> + ChoiceAware
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, S> ChoiceAware for TRec<IO, Lbl, S> where
+    S: TSession<IO> + ChoiceAware
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Var, Body> ChoiceAware for TRecX<IO, Lbl, Var, Body> where
+    Body: TSession<IO> + ChoiceAware
+{
+}
+
+impl<Var> ChoiceAware for TVar<Var> {}
+
+impl<IO, Lbl, Dur, Body, OnTimeout> ChoiceAware for TTimeout<IO, Lbl, Dur, Body, OnTimeout>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    Body: TSession<IO> + ChoiceAware,
+    OnTimeout: TSession<IO> + ChoiceAware,
+{
+}
+
+impl<IO, Lbl, R, Dur, T> ChoiceAware for TDelay<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: TSession<IO> + ChoiceAware,
+{
+}
+
+impl<IO, Lbl, R, Dur, T> ChoiceAware for TDeadline<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: TSession<IO> + ChoiceAware,
+{
+}
+
+impl<IO, Lbl, Scope, Interruptor, Handler> ChoiceAware
+    for TInterrupt<IO, Lbl, Scope, Interruptor, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Scope: TSession<IO> + ChoiceAware,
+    Handler: TSession<IO> + ChoiceAware,
+{
+}
+
+impl<IO, Lbl, Decider, Body, Cont> ChoiceAware for TOptional<IO, Lbl, Decider, Body, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Body: TSession<IO> + ChoiceAware,
+    Cont: TSession<IO> + ChoiceAware,
+{
+}
+
+impl<Pred, Inner: ChoiceAware> ChoiceAware for TRefine<Pred, Inner> {}
+
+impl<Meta, Inner: ChoiceAware> ChoiceAware for TAnnotate<Meta, Inner> {}
+
+impl<IO, Lbl: types::ProtocolLabel, R, Err> ChoiceAware for TThrow<IO, Lbl, R, Err> {}
+
+impl<IO, Lbl, Body, Handler> ChoiceAware for TCatch<IO, Lbl, Body, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Body: TSession<IO> + ChoiceAware,
+    Handler: TSession<IO> + ChoiceAware,
+{
+}
+
+impl ChoiceAware for Nil {}
+
+impl<H, T> ChoiceAware for Cons<H, T>
+where
+    H: ChoiceAware,
+    T: ChoiceAware,
+{
+}
@@ -40,37 +40,6 @@ pub struct Nil;
 /// - `T`: The tail type-level list
 pub struct Cons<H, T>(PhantomData<(H, T)>);
 
-/// Trait to check that all elements in a type-level list are unique.
-///
-/// Used for compile-time uniqueness assertions (e.g., for protocol labels).
-pub trait UniqueList {}
-
-impl UniqueList for Nil {}
-impl<H, T> UniqueList for Cons<H, T> where T: NotInList<H> + UniqueList {}
-
-/// Helper trait to check if a type is not in a type-level list.
-pub trait NotInList<X> {}
-
-impl<X> NotInList<X> for Nil {}
-impl<X, H, T> NotInList<X> for Cons<H, T>
-where
-    X: NotSame<H>,
-    T: NotInList<X>,
-{
-}
-
-/// Helper trait to check if two types are not the same.
-pub trait NotSame<T> {}
-
-impl<A, B> NotSame<B> for A where A: NotTypeEq<B> {}
-
-/// Base trait for type inequality.
-/// The default implementation works for all non-identical types.
-pub trait NotTypeEq<B> {}
-
-impl<A, B> NotTypeEq<B> for A {}
-// Overlap: no impl for A == A (this is intentional)
-
 /// Type-level trait to check if two types are the same.
 /// Implemented via a marker type Output that is True or False.
 pub trait TypeEq<B> {
@@ -0,0 +1,629 @@
+//! # Connectedness Checks
+//!
+//! This module checks that the steps of a global protocol are causally
+//! connected: the role acting in a step has taken part in the step before,
+//! so it can tell when its turn has come.
+//!
+//! Key components:
+//!
+//! - `Connected`: The sender of every step took part in the previous step
+//! - `AsyncConnected`: The sender of every step took part in some earlier step
+//! - `ConnectedAfter`: The check itself, parameterized by `SyncOrder` or `AsyncOrder`
+//! - `Advance`: The roles that may act after a step, in either order
+//! - `StartsAfter`: The first step of a loop body may follow the step before a `TVar`
+//! - `MayAct` / `MayActCase`: Helper traits checking a single step
+//! - `Unconnected` / `ConnectednessFailure`: Failure markers naming the role and label
+//!
+//! With synchronous communication a role can only know that the previous
+//! step happened if it took part in it. With asynchronous, buffered
+//! communication a role that has already acted may keep sending without
+//! waiting, so `AsyncConnected` only rejects roles acting out of nowhere.
+//!
+//! `TInteract` involves every role, so any role may act after it. The first
+//! step of every branch of a choice or parallel composition is checked
+//! against the step before the combinator, and handlers of interrupts and
+//! failures may start with any role, since every role of the scope is told.
+//! A `TVar` jumps back to the innermost enclosing `TRec` or `TRecX`, so the
+//! first step of that loop body is checked against the step before the
+//! `TVar`.
+
+use super::base::{Cons, Nil};
+use super::global::*;
+use super::local::InRoleSet;
+use super::utils::Concat;
+use crate::types;
+use core::marker::PhantomData;
+
+/// Check order: the sender of a step took part in the previous step.
+pub struct SyncOrder;
+
+/// Check order: the sender of a step took part in some earlier step.
+pub struct AsyncOrder;
+
+/// The roles that may act before any step, or after a step involving every
+/// role.
+pub struct AnyRole;
+
+/// Failure marker: `R` acts in the step labelled `Lbl` without having taken
+/// part in the steps it depends on.
+pub struct Unconnected<R, Lbl>(PhantomData<(R, Lbl)>);
+
+/// Never implemented: a bound `Marker: ConnectednessFailure` fails and names
+/// the unconnected step found by [`Connected`] or [`AsyncConnected`].
+#[diagnostic::on_unimplemented(
+    message = "the protocol is not connected: `{Self}`",
+    label = "this role cannot know that its turn has come",
+    note = "let the role receive a message in the step before it acts"
+)]
+pub trait ConnectednessFailure {}
+
+/// The roles that may act after a step with the given participants, given
+/// the roles `Active` that could act before it.
+pub trait Advance<Active, Participants> {
+    type Active;
+}
+
+impl<Active, Participants> Advance<Active, Participants> for SyncOrder {
+    type Active = Participants;
+}
+
+impl<Participants> Advance<AnyRole, Participants> for AsyncOrder {
+    type Active = AnyRole;
+}
+
+impl<H, T, Participants> Advance<Cons<H, T>, Participants> for AsyncOrder
+where
+    Participants: Concat<Cons<H, T>>,
+{
+    type Active = <Participants as Concat<Cons<H, T>>>::Output;
+}
+
+/// Role `R` may act in the step labelled `Lbl`, given the roles that could
+/// act before it.
+pub trait MayAct<R, Lbl> {}
+
+impl<R, Lbl> MayAct<R, Lbl> for AnyRole {}
+
+impl<R, Lbl, H, T> MayAct<R, Lbl> for Cons<H, T>
+where
+    R: InRoleSet<Cons<H, T>>,
+    R: MayActCase<Lbl, <R as InRoleSet<Cons<H, T>>>::Output>,
+{
+}
+
+/// Helper trait for `MayAct`, dispatching on whether the role could act.
+pub trait MayActCase<Lbl, CouldAct> {}
+
+impl<R, Lbl> MayActCase<Lbl, types::True> for R {}
+
+impl<R, Lbl> MayActCase<Lbl, types::False> for R where Unconnected<R, Lbl>: ConnectednessFailure {}
+
+/// A global protocol whose every step is connected to the steps before it in
+/// the check order `Order`, given the roles `Active` that may act first.
+///
+/// `Loop` is the body of the innermost enclosing recursion, or `Nil` outside
+/// of one: a `TVar` jumps back to it, so its first step must be connected to
+/// the step before the `TVar`.
+pub trait ConnectedAfter<Order, Active, Loop> {}
+
+/// A global protocol where the sender of every step took part in the
+/// previous step.
+///
+/// Check a protocol with [`assert_connected!`](crate::assert_connected).
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Alice; struct Bob; struct Carol;
+/// impl Role for Alice {} impl Role for Bob {} impl Role for Carol {}
+/// impl RoleEq<Alice> for Alice { type Output = True; }
+/// impl RoleEq<Bob> for Alice   { type Output = False; }
+/// impl RoleEq<Carol> for Alice { type Output = False; }
+/// impl RoleEq<Alice> for Bob   { type Output = False; }
+/// impl RoleEq<Bob> for Bob     { type Output = True; }
+/// impl RoleEq<Carol> for Bob   { type Output = False; }
+/// impl RoleEq<Alice> for Carol { type Output = False; }
+/// impl RoleEq<Bob> for Carol   { type Output = False; }
+/// impl RoleEq<Carol> for Carol { type Output = True; }
+///
+/// // Each sender has just received a message
+/// type G = TMsg<Http, EmptyLabel, Alice, Bob, Message,
+///          TMsg<Http, EmptyLabel, Bob, Carol, Message,
+///          TMsg<Http, EmptyLabel, Carol, Alice, Message, TEnd<Http>>>>;
+/// assert_connected!(G);
+/// ```
+pub trait Connected {}
+
+impl<G: ConnectedAfter<SyncOrder, AnyRole, Nil>> Connected for G {}
+
+/// A global protocol where the sender of every step took part in some
+/// earlier step.
+///
+/// This is the relaxed form of [`Connected`] for asynchronous communication.
+/// Check a protocol with [`assert_connected!`](crate::assert_connected)
+/// using the `async` form.
+pub trait AsyncConnected {}
+
+impl<G: ConnectedAfter<AsyncOrder, AnyRole, Nil>> AsyncConnected for G {}
+
+impl<IO, Lbl, Order, Active, Loop> ConnectedAfter<Order, Active, Loop> for TEnd<IO, Lbl> {}
+
+// Every role takes part in a TInteract
+impl<IO, Lbl: types::ProtocolLabel, R, H, T, Order, Active, Loop>
+    ConnectedAfter<Order, Active, Loop> for TInteract<IO, Lbl, R, H, T>
+where
+    Active: MayAct<R, Lbl>,
+    T: TSession<IO> + ConnectedAfter<Order, AnyRole, Loop>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, To, H, T, Order, Active, Loop>
+    ConnectedAfter<Order, Active, Loop> for TMsg<IO, Lbl, From, To, H, T>
+where
+    Active: MayAct<From, Lbl>,
+    Order: Advance<Active, Cons<From, Cons<To, Nil>>>,
+    T: TSession<IO>
+        + ConnectedAfter<Order, <Order as Advance<Active, Cons<From, Cons<To, Nil>>>>::Active, Loop>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, ToSet, H, T, Order, Active, Loop>
+    ConnectedAfter<Order, Active, Loop> for TBroadcast<IO, Lbl, From, ToSet, H, T>
+where
+    Active: MayAct<From, Lbl>,
+    Order: Advance<Active, Cons<From, ToSet>>,
+    T: TSession<IO>
+        + ConnectedAfter<Order, <Order as Advance<Active, Cons<From, ToSet>>>::Active, Loop>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, WorkerSet, H, T, Order, Active, Loop>
+    ConnectedAfter<Order, Active, Loop> for TAnycast<IO, Lbl, From, WorkerSet, H, T>
+where
+    Active: MayAct<From, Lbl>,
+    Order: Advance<Active, Cons<From, WorkerSet>>,
+    T: TSession<IO>
+        + ConnectedAfter<Order, <Order as Advance<Active, Cons<From, WorkerSet>>>::Active, Loop>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, To, DelegatedEp, T, Order, Active, Loop>
+    ConnectedAfter<Order, Active, Loop> for TDelegate<IO, Lbl, From, To, DelegatedEp, T>
+where
+    Active: MayAct<From, Lbl>,
+    Order: Advance<Active, Cons<From, Cons<To, Nil>>>,
+    T: TSession<IO>
+        + ConnectedAfter<Order, <Order as Advance<Active, Cons<From, Cons<To, Nil>>>>::Active, Loop>,
+{
+}
+
+// The first role of the pair closes and opens the connection
+impl<IO, Lbl: types::ProtocolLabel, A, B, Cont, Order, Active, Loop>
+    ConnectedAfter<Order, Active, Loop> for TDisconnect<IO, Lbl, A, B, Cont>
+where
+    Active: MayAct<A, Lbl>,
+    Order: Advance<Active, Cons<A, Cons<B, Nil>>>,
+    Cont: TSession<IO>
+        + ConnectedAfter<Order, <Order as Advance<Active, Cons<A, Cons<B, Nil>>>>::Active, Loop>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, A, B, Cont, Order, Active, Loop>
+    ConnectedAfter<Order, Active, Loop> for TConnect<IO, Lbl, A, B, Cont>
+where
+    Active: MayAct<A, Lbl>,
+    Order: Advance<Active, Cons<A, Cons<B, Nil>>>,
+    Cont: TSession<IO>
+        + ConnectedAfter<Order, <Order as Advance<Active, Cons<A, Cons<B, Nil>>>>::Active, Loop>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R, Cont, Order, Active, Loop>
+    ConnectedAfter<Order, Active, Loop> for TEndFor<IO, Lbl, R, Cont>
+where
+    Cont: TSession<IO> + ConnectedAfter<Order, Active, Loop>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, L, R, Order, Active, Loop> ConnectedAfter<Order, Active, Loop>
+    for TChoice<IO, Lbl, L, R>
+where
+    L: TSession<IO> + ConnectedAfter<Order, Active, Loop>,
+    R: TSession<IO> + ConnectedAfter<Order, Active, Loop>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Branches, Order, Active, Loop>
+    ConnectedAfter<Order, Active, Loop> for TChoiceN<IO, Lbl, Branches>
+where
+    Branches: SessionList<IO> + ConnectedAfter<Order, Active, Loop>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Chooser, Branches, Order, Active, Loop>
+    ConnectedAfter<Order, Active, Loop> for TSelect<IO, Lbl, Chooser, Branches>
+where
+    Branches: SessionList<IO> + ConnectedAfter<Order, Active, Loop>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Offeree, Branches, Order, Active, Loop>
+    ConnectedAfter<Order, Active, Loop> for TOffer<IO, Lbl, Offeree, Branches>
+where
+    Branches: SessionList<IO> + ConnectedAfter<Order, Active, Loop>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, High, Low, Order, Active, Loop>
+    ConnectedAfter<Order, Active, Loop> for TPriorityChoice<IO, Lbl, High, Low>
+where
+    High: TSession<IO> + ConnectedAfter<Order, Active, Loop>,
+    Low: TSession<IO> + ConnectedAfter<Order, Active, Loop>,
+{
+}
+
+impl<IO, Lbl, WeightL, L, WeightR, R, Order, Active, Loop> ConnectedAfter<Order, Active, Loop>
+    for TProb<IO, Lbl, WeightL, L, WeightR, R>
+where
+    Lbl: types::ProtocolLabel,
+    WeightL: types::WeightMarker,
+    WeightR: types::WeightMarker,
+    L: TSession<IO> + ConnectedAfter<Order, Active, Loop>,
+    R: TSession<IO> + ConnectedAfter<Order, Active, Loop>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, L, R, IsDisjoint, Order, Active, Loop>
+    ConnectedAfter<Order, Active, Loop> for TPar<IO, Lbl, L, R, IsDisjoint>
+where
+    L: TSession<IO> + ConnectedAfter<Order, Active, Loop>,
+    R: TSession<IO> + ConnectedAfter<Order, Active, Loop>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Branches, Order, Active, Loop>
+    ConnectedAfter<Order, Active, Loop> for TParN<IO, Lbl, Branches>
+where
+    Branches: SessionList<IO> + ConnectedAfter<Order, Active, Loop>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Steps, Order, Active, Loop> ConnectedAfter<Order, Active, Loop>
+    for TShuffle<IO, Lbl, Steps>
+where
+    Steps: SessionList<IO> + ConnectedAfter<Order, Active, Loop>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, S, Order, Active, Loop> ConnectedAfter<Order, Active, Loop>
+    for TRec<IO, Lbl, S>
+where
+    S: TSession<IO> + ConnectedAfter<Order, Active, S>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Var, Body, Order, Active, Loop>
+    ConnectedAfter<Order, Active, Loop> for TRecX<IO, Lbl, Var, Body>
+where
+    Body: TSession<IO> + ConnectedAfter<Order, Active, Body>,
+{
+}
+
+// The loop starts again after the step before the TVar
+impl<Var, Order, Active, Loop> ConnectedAfter<Order, Active, Loop> for TVar<Var> where
+    Loop: StartsAfter<Active>
+{
+}
+
+impl<IO, Lbl, Dur, Body, OnTimeout, Order, Active, Loop> ConnectedAfter<Order, Active, Loop>
+    for TTimeout<IO, Lbl, Dur, Body, OnTimeout>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    Body: TSession<IO> + ConnectedAfter<Order, Active, Loop>,
+    OnTimeout: TSession<IO> + ConnectedAfter<Order, Active, Loop>,
+{
+}
+
+impl<IO, Lbl, R, Dur, T, Order, Active, Loop> ConnectedAfter<Order, Active, Loop>
+    for TDelay<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: TSession<IO> + ConnectedAfter<Order, Active, Loop>,
+{
+}
+
+impl<IO, Lbl, R, Dur, T, Order, Active, Loop> ConnectedAfter<Order, Active, Loop>
+    for TDeadline<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: TSession<IO> + ConnectedAfter<Order, Active, Loop>,
+{
+}
+
+// Every role of the scope learns about the interrupt
+impl<IO, Lbl, Scope, Interruptor, Handler, Order, Active, Loop> ConnectedAfter<Order, Active, Loop>
+    for TInterrupt<IO, Lbl, Scope, Interruptor, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Scope: TSession<IO> + ConnectedAfter<Order, Active, Loop>,
+    Handler: TSession<IO> + ConnectedAfter<Order, AnyRole, Loop>,
+{
+}
+
+impl<IO, Lbl, Decider, Body, Cont, Order, Active, Loop> ConnectedAfter<Order, Active, Loop>
+    for TOptional<IO, Lbl, Decider, Body, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Body: TSession<IO> + ConnectedAfter<Order, Active, Loop>,
+    Cont: TSession<IO> + ConnectedAfter<Order, Active, Loop>,
+{
+}
+
+impl<Pred, Inner, Order, Active, Loop> ConnectedAfter<Order, Active, Loop> for TRefine<Pred, Inner> where
+    Inner: ConnectedAfter<Order, Active, Loop>
+{
+}
+
+impl<Meta, Inner, Order, Active, Loop> ConnectedAfter<Order, Active, Loop>
+    for TAnnotate<Meta, Inner>
+where
+    Inner: ConnectedAfter<Order, Active, Loop>,
+{
+}
+
+// Raising a failure hands control to the enclosing handler
+impl<IO, Lbl: types::ProtocolLabel, R, Err, Order, Active, Loop> ConnectedAfter<Order, Active, Loop>
+    for TThrow<IO, Lbl, R, Err>
+{
+}
+
+// Every role of the body learns about the failure
+impl<IO, Lbl, Body, Handler, Order, Active, Loop> ConnectedAfter<Order, Active, Loop>
+    for TCatch<IO, Lbl, Body, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Body: TSession<IO> + ConnectedAfter<Order, Active, Loop>,
+    Handler: TSession<IO> + ConnectedAfter<Order, AnyRole, Loop>,
+{
+}
+
+impl<Order, Active, Loop> ConnectedAfter<Order, Active, Loop> for Nil {}
+
+impl<H, T, Order, Active, Loop> ConnectedAfter<Order, Active, Loop> for Cons<H, T>
+where
+    H: ConnectedAfter<Order, Active, Loop>,
+    T: ConnectedAfter<Order, Active, Loop>,
+{
+}
+
+/// The first step of a global protocol may follow a step after which the
+/// roles `Active` may act.
+///
+/// Checks the body of a loop at each `TVar` that jumps back to it.
+pub trait StartsAfter<Active> {}
+
+impl<IO, Lbl, Active> StartsAfter<Active> for TEnd<IO, Lbl> {}
+
+impl<IO, Lbl: types::ProtocolLabel, R, H, T, Active> StartsAfter<Active>
+    for TInteract<IO, Lbl, R, H, T>
+where
+    Active: MayAct<R, Lbl>,
+    T: TSession<IO>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, To, H, T, Active> StartsAfter<Active>
+    for TMsg<IO, Lbl, From, To, H, T>
+where
+    Active: MayAct<From, Lbl>,
+    T: TSession<IO>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, ToSet, H, T, Active> StartsAfter<Active>
+    for TBroadcast<IO, Lbl, From, ToSet, H, T>
+where
+    Active: MayAct<From, Lbl>,
+    T: TSession<IO>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, WorkerSet, H, T, Active> StartsAfter<Active>
+    for TAnycast<IO, Lbl, From, WorkerSet, H, T>
+where
+    Active: MayAct<From, Lbl>,
+    T: TSession<IO>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, To, DelegatedEp, T, Active> StartsAfter<Active>
+    for TDelegate<IO, Lbl, From, To, DelegatedEp, T>
+where
+    Active: MayAct<From, Lbl>,
+    T: TSession<IO>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, A, B, Cont, Active> StartsAfter<Active>
+    for TDisconnect<IO, Lbl, A, B, Cont>
+where
+    Active: MayAct<A, Lbl>,
+    Cont: TSession<IO>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, A, B, Cont, Active> StartsAfter<Active>
+    for TConnect<IO, Lbl, A, B, Cont>
+where
+    Active: MayAct<A, Lbl>,
+    Cont: TSession<IO>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R, Cont, Active> StartsAfter<Active>
+    for TEndFor<IO, Lbl, R, Cont>
+where
+    Cont: TSession<IO> + StartsAfter<Active>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, L, R, Active> StartsAfter<Active> for TChoice<IO, Lbl, L, R>
+where
+    L: TSession<IO> + StartsAfter<Active>,
+    R: TSession<IO> + StartsAfter<Active>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Branches, Active> StartsAfter<Active>
+    for TChoiceN<IO, Lbl, Branches>
+where
+    Branches: SessionList<IO> + StartsAfter<Active>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Chooser, Branches, Active> StartsAfter<Active>
+    for TSelect<IO, Lbl, Chooser, Branches>
+where
+    Branches: SessionList<IO> + StartsAfter<Active>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Offeree, Branches, Active> StartsAfter<Active>
+    for TOffer<IO, Lbl, Offeree, Branches>
+where
+    Branches: SessionList<IO> + StartsAfter<Active>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, High, Low, Active> StartsAfter<Active>
+    for TPriorityChoice<IO, Lbl, High, Low>
+where
+    High: TSession<IO> + StartsAfter<Active>,
+    Low: TSession<IO> + StartsAfter<Active>,
+{
+}
+
+impl<IO, Lbl, WeightL, L, WeightR, R, Active> StartsAfter<Active>
+    for TProb<IO, Lbl, WeightL, L, WeightR, R>
+where
+    Lbl: types::ProtocolLabel,
+    WeightL: types::WeightMarker,
+    WeightR: types::WeightMarker,
+    L: TSession<IO> + StartsAfter<Active>,
+    R: TSession<IO> + StartsAfter<Active>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, L, R, IsDisjoint, Active> StartsAfter<Active>
+    for TPar<IO, Lbl, L, R, IsDisjoint>
+where
+    L: TSession<IO> + StartsAfter<Active>,
+    R: TSession<IO> + StartsAfter<Active>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Branches, Active> StartsAfter<Active>
+    for TParN<IO, Lbl, Branches>
+where
+    Branches: SessionList<IO> + StartsAfter<Active>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Steps, Active> StartsAfter<Active> for TShuffle<IO, Lbl, Steps> where
+    Steps: SessionList<IO> + StartsAfter<Active>
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, S, Active> StartsAfter<Active> for TRec<IO, Lbl, S> where
+    S: TSession<IO> + StartsAfter<Active>
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Var, Body, Active> StartsAfter<Active>
+    for TRecX<IO, Lbl, Var, Body>
+where
+    Body: TSession<IO> + StartsAfter<Active>,
+{
+}
+
+// An unguarded loop body is rejected by `Guarded`
+impl<Var, Active> StartsAfter<Active> for TVar<Var> {}
+
+impl<IO, Lbl, Dur, Body, OnTimeout, Active> StartsAfter<Active>
+    for TTimeout<IO, Lbl, Dur, Body, OnTimeout>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    Body: TSession<IO> + StartsAfter<Active>,
+    OnTimeout: TSession<IO> + StartsAfter<Active>,
+{
+}
+
+impl<IO, Lbl, R, Dur, T, Active> StartsAfter<Active> for TDelay<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: TSession<IO> + StartsAfter<Active>,
+{
+}
+
+impl<IO, Lbl, R, Dur, T, Active> StartsAfter<Active> for TDeadline<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: TSession<IO> + StartsAfter<Active>,
+{
+}
+
+impl<IO, Lbl, Scope, Interruptor, Handler, Active> StartsAfter<Active>
+    for TInterrupt<IO, Lbl, Scope, Interruptor, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Scope: TSession<IO> + StartsAfter<Active>,
+    Handler: TSession<IO>,
+{
+}
+
+impl<IO, Lbl, Decider, Body, Cont, Active> StartsAfter<Active>
+    for TOptional<IO, Lbl, Decider, Body, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Body: TSession<IO> + StartsAfter<Active>,
+    Cont: TSession<IO> + StartsAfter<Active>,
+{
+}
+
+impl<Pred, Inner, Active> StartsAfter<Active> for TRefine<Pred, Inner> where
+    Inner: StartsAfter<Active>
+{
+}
+
+impl<Meta, Inner, Active> StartsAfter<Active> for TAnnotate<Meta, Inner> where
+    Inner: StartsAfter<Active>
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R, Err, Active> StartsAfter<Active>
+    for TThrow<IO, Lbl, R, Err>
+{
+}
+
+impl<IO, Lbl, Body, Handler, Active> StartsAfter<Active> for TCatch<IO, Lbl, Body, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Body: TSession<IO> + StartsAfter<Active>,
+    Handler: TSession<IO>,
+{
+}
+
+impl<Active> StartsAfter<Active> for Nil {}
+
+impl<H, T, Active> StartsAfter<Active> for Cons<H, T>
+where
+    H: StartsAfter<Active>,
+    T: StartsAfter<Active>,
+{
+}
@@ -0,0 +1,740 @@
+//! # Connection Checks
+//!
+//! This module checks that explicit connection management in global protocols
+//! is respected: once `TDisconnect` closes the channel between two roles, no
+//! later interaction may use that pair, and no interaction between two roles
+//! may come before the `TConnect` that opens their channel.
+//!
+//! Key components:
+//!
+//! - `ConnectionsRespected`: Role pairs interact only while connected
+//! - `PairUnused`: A protocol has no interaction between two given roles
+//! - `ConnectFree`: A protocol never connects a sender to any of its receivers
+//! - `LinksPair`: Type-level check whether a sender and its receivers connect two given roles
+//!
+//! A `TVar` is not followed back to its loop (see [the scope of the
+//! checks](crate#scope-of-the-checks)), so a loop body that closes a pair and
+//! starts over with a step on that pair is accepted: reopen the pair with
+//! `TConnect` before the `TVar`.
+
+use super::base::{Cons, Nil};
+use super::global::*;
+use super::local::{InRoleSet, RoleEq};
+use crate::types;
+
+/// Type-level check whether a sender and its receivers connect `A` and `B`.
+///
+/// Implemented for a `(From, ToSet)` pair, where `ToSet` is a type-level list
+/// of receiving roles. `Output` is `True` if `From` is `A` and `B` is in
+/// `ToSet`, or `From` is `B` and `A` is in `ToSet`.
+pub trait LinksPair<A, B> {
+    type Output: types::Bool;
+}
+
+impl<From, ToSet, A, B> LinksPair<A, B> for (From, ToSet)
+where
+    From: RoleEq<A> + RoleEq<B>,
+    A: InRoleSet<ToSet>,
+    B: InRoleSet<ToSet>,
+    <From as RoleEq<A>>::Output: types::BoolAnd<<B as InRoleSet<ToSet>>::Output>,
+    <From as RoleEq<B>>::Output: types::BoolAnd<<A as InRoleSet<ToSet>>::Output>,
+    types::And<<From as RoleEq<A>>::Output, <B as InRoleSet<ToSet>>::Output>:
+        types::BoolOr<types::And<<From as RoleEq<B>>::Output, <A as InRoleSet<ToSet>>::Output>>,
+{
+    type Output = types::Or<
+        types::And<<From as RoleEq<A>>::Output, <B as InRoleSet<ToSet>>::Output>,
+        types::And<<From as RoleEq<B>>::Output, <A as InRoleSet<ToSet>>::Output>,
+    >;
+}
+
+/// A protocol with no interaction between roles `A` and `B`, in either direction.
+///
+/// A `TConnect` of the pair reopens it and ends the check.
+pub trait PairUnused<A, B> {}
+
+/// `PairUnused<A, B>` unless the pair has just been reopened (`Reopened = True`).
+pub trait PairUnusedUnless<Reopened, A, B> {}
+
+impl<S, A, B> PairUnusedUnless<types::True, A, B> for S {}
+
+impl<S: PairUnused<A, B>, A, B> PairUnusedUnless<types::False, A, B> for S {}
+
+impl<IO, Lbl, A, B> PairUnused<A, B> for TEnd<IO, Lbl> {}
+
+impl<IO, Lbl: types::ProtocolLabel, R, H, T, A, B> PairUnused<A, B> for TInteract<IO, Lbl, R, H, T> where
+    T: TSession<IO> + PairUnused<A, B>
+{
+}
+
+// A message uses the pair if it travels between `A` and `B` in either direction
+impl<IO, Lbl: types::ProtocolLabel, From, To, H, T, A, B> PairUnused<A, B>
+    for TMsg<IO, Lbl, From, To, H, T>
+where
+    (From, Cons<To, Nil>): LinksPair<A, B, Output = types::False>,
+    T: TSession<IO> + PairUnused<A, B>,
+{
+}
+
+// A broadcast uses the pair if one of them sends and the other is among the receivers
+impl<IO, Lbl: types::ProtocolLabel, From, ToSet, H, T, A, B> PairUnused<A, B>
+    for TBroadcast<IO, Lbl, From, ToSet, H, T>
+where
+    (From, ToSet): LinksPair<A, B, Output = types::False>,
+    T: TSession<IO> + PairUnused<A, B>,
+{
+}
+
+// Any worker may receive an anycast, so every worker counts as a receiver
+impl<IO, Lbl: types::ProtocolLabel, From, WorkerSet, H, T, A, B> PairUnused<A, B>
+    for TAnycast<IO, Lbl, From, WorkerSet, H, T>
+where
+    (From, WorkerSet): LinksPair<A, B, Output = types::False>,
+    T: TSession<IO> + PairUnused<A, B>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, To, DelegatedEp, T, A, B> PairUnused<A, B>
+    for TDelegate<IO, Lbl, From, To, DelegatedEp, T>
+where
+    (From, Cons<To, Nil>): LinksPair<A, B, Output = types::False>,
+    T: TSession<IO> + PairUnused<A, B>,
+{
+}
+
+// Closing an already closed pair again counts as a use
+impl<IO, Lbl: types::ProtocolLabel, X, Y, Cont, A, B> PairUnused<A, B>
+    for TDisconnect<IO, Lbl, X, Y, Cont>
+where
+    (X, Cons<Y, Nil>): LinksPair<A, B, Output = types::False>,
+    Cont: TSession<IO> + PairUnused<A, B>,
+{
+}
+
+// Connecting the pair reopens it, so the rest of the protocol may use it again
+impl<IO, Lbl: types::ProtocolLabel, X, Y, Cont, A, B> PairUnused<A, B>
+    for TConnect<IO, Lbl, X, Y, Cont>
+where
+    (X, Cons<Y, Nil>): LinksPair<A, B>,
+    Cont: TSession<IO> + PairUnusedUnless<<(X, Cons<Y, Nil>) as LinksPair<A, B>>::Output, A, B>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R, Cont, A, B> PairUnused<A, B> for TEndFor<IO, Lbl, R, Cont> where
+    Cont: TSession<IO> + PairUnused<A, B>
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, L, R, A, B> PairUnused<A, B> for TChoice<IO, Lbl, L, R>
+where
+    L: TSession<IO> + PairUnused<A, B>,
+    R: TSession<IO> + PairUnused<A, B>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Branches, A, B> PairUnused<A, B> for TChoiceN<IO, Lbl, Branches> where
+    Branches: SessionList<IO> + PairUnused<A, B>
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Chooser, Branches, A, B> PairUnused<A, B>
+    for TSelect<IO, Lbl, Chooser, Branches>
+where
+    Branches: SessionList<IO> + PairUnused<A, B>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Offeree, Branches, A, B> PairUnused<A, B>
+    for TOffer<IO, Lbl, Offeree, Branches>
+where
+    Branches: SessionList<IO> + PairUnused<A, B>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, High, Low, A, B> PairUnused<A, B>
+    for TPriorityChoice<IO, Lbl, High, Low>
+where
+    High: TSession<IO> + PairUnused<A, B>,
+    Low: TSession<IO> + PairUnused<A, B>,
+{
+}
+
+impl<IO, Lbl, WeightL, L, WeightR, R, A, B> PairUnused<A, B>
+    for TProb<IO, Lbl, WeightL, L, WeightR, R>
+where
+    Lbl: types::ProtocolLabel,
+    WeightL: types::WeightMarker,
+    WeightR: types::WeightMarker,
+    L: TSession<IO> + PairUnused<A, B>,
+    R: TSession<IO> + PairUnused<A, B>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, L, R, IsDisjoint, A, B> PairUnused<A, B>
+    for TPar<IO, Lbl, L, R, IsDisjoint>
+where
+    L: TSession<IO> + PairUnused<A, B>,
+    R: TSession<IO> + PairUnused<A, B>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Branches, A, B> PairUnused<A, B> for TParN<IO, Lbl, Branches> where
+    Branches: SessionList<IO> + PairUnused<A, B>
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Steps, A, B> PairUnused<A, B> for TShuffle<IO, Lbl, Steps> where
+    Steps: SessionList<IO> + PairUnused<A, B>
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, S, A, B> PairUnused<A, B> for TRec<IO, Lbl, S> where
+    S: TSession<IO> + PairUnused<A, B>
+{
+}
+
+impl<IO, Lbl, Dur, Body, OnTimeout, A, B> PairUnused<A, B>
+    for TTimeout<IO, Lbl, Dur, Body, OnTimeout>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    Body: TSession<IO> + PairUnused<A, B>,
+    OnTimeout: TSession<IO> + PairUnused<A, B>,
+{
+}
+
+impl<IO, Lbl, R, Dur, T, A, B> PairUnused<A, B> for TDelay<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: TSession<IO> + PairUnused<A, B>,
+{
+}
+
+impl<IO, Lbl, R, Dur, T, A, B> PairUnused<A, B> for TDeadline<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: TSession<IO> + PairUnused<A, B>,
+{
+}
+
+impl<IO, Lbl, Scope, Interruptor, Handler, A, B> PairUnused<A, B>
+    for TInterrupt<IO, Lbl, Scope, Interruptor, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Scope: TSession<IO> + PairUnused<A, B>,
+    Handler: TSession<IO> + PairUnused<A, B>,
+{
+}
+
+impl<IO, Lbl, Decider, Body, Cont, A, B> PairUnused<A, B>
+    for TOptional<IO, Lbl, Decider, Body, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Body: TSession<IO> + PairUnused<A, B>,
+    Cont: TSession<IO> + PairUnused<A, B>,
+{
+}
+
+impl<Pred, Inner, A, B> PairUnused<A, B> for TRefine<Pred, Inner> where Inner: PairUnused<A, B> {}
+
+impl<Meta, Inner, A, B> PairUnused<A, B> for TAnnotate<Meta, Inner> where Inner: PairUnused<A, B> {}
+
+impl<IO, Lbl: types::ProtocolLabel, R, Err, A, B> PairUnused<A, B> for TThrow<IO, Lbl, R, Err> {}
+
+impl<IO, Lbl, Body, Handler, A, B> PairUnused<A, B> for TCatch<IO, Lbl, Body, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Body: TSession<IO> + PairUnused<A, B>,
+    Handler: TSession<IO> + PairUnused<A, B>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Var, Body, A, B> PairUnused<A, B> for TRecX<IO, Lbl, Var, Body> where
+    Body: TSession<IO> + PairUnused<A, B>
+{
+}
+
+impl<Var, A, B> PairUnused<A, B> for TVar<Var> {}
+
+impl<A, B> PairUnused<A, B> for Nil {}
+
+impl<H, T, A, B> PairUnused<A, B> for Cons<H, T>
+where
+    H: PairUnused<A, B>,
+    T: PairUnused<A, B>,
+{
+}
+
+/// A protocol with no `TConnect` between `Sender` and any role in `Receivers`.
+///
+/// A `TDisconnect` of such a pair closes the channel that was in use, so a
+/// later `TConnect` may open it again and the check ends there.
+pub trait ConnectFree<Sender, Receivers> {}
+
+/// `ConnectFree<Sender, Receivers>` unless the pair has just been closed (`Closed = True`).
+pub trait ConnectFreeUnless<Closed, Sender, Receivers> {}
+
+impl<S, Sender, Receivers> ConnectFreeUnless<types::True, Sender, Receivers> for S {}
+
+impl<S: ConnectFree<Sender, Receivers>, Sender, Receivers>
+    ConnectFreeUnless<types::False, Sender, Receivers> for S
+{
+}
+
+impl<IO, Lbl, Sender, Receivers> ConnectFree<Sender, Receivers> for TEnd<IO, Lbl> {}
+
+impl<IO, Lbl: types::ProtocolLabel, R, H, T, Sender, Receivers> ConnectFree<Sender, Receivers>
+    for TInteract<IO, Lbl, R, H, T>
+where
+    T: TSession<IO> + ConnectFree<Sender, Receivers>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, To, H, T, Sender, Receivers>
+    ConnectFree<Sender, Receivers> for TMsg<IO, Lbl, From, To, H, T>
+where
+    T: TSession<IO> + ConnectFree<Sender, Receivers>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, ToSet, H, T, Sender, Receivers>
+    ConnectFree<Sender, Receivers> for TBroadcast<IO, Lbl, From, ToSet, H, T>
+where
+    T: TSession<IO> + ConnectFree<Sender, Receivers>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, WorkerSet, H, T, Sender, Receivers>
+    ConnectFree<Sender, Receivers> for TAnycast<IO, Lbl, From, WorkerSet, H, T>
+where
+    T: TSession<IO> + ConnectFree<Sender, Receivers>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, To, DelegatedEp, T, Sender, Receivers>
+    ConnectFree<Sender, Receivers> for TDelegate<IO, Lbl, From, To, DelegatedEp, T>
+where
+    T: TSession<IO> + ConnectFree<Sender, Receivers>,
+{
+}
+
+// Once the channel is closed, a later `TConnect` may open it again
+impl<IO, Lbl: types::ProtocolLabel, X, Y, Cont, Sender, Receivers> ConnectFree<Sender, Receivers>
+    for TDisconnect<IO, Lbl, X, Y, Cont>
+where
+    (Sender, Receivers): LinksPair<X, Y>,
+    Cont: TSession<IO>
+        + ConnectFreeUnless<<(Sender, Receivers) as LinksPair<X, Y>>::Output, Sender, Receivers>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, X, Y, Cont, Sender, Receivers> ConnectFree<Sender, Receivers>
+    for TConnect<IO, Lbl, X, Y, Cont>
+where
+    (Sender, Receivers): LinksPair<X, Y, Output = types::False>,
+    Cont: TSession<IO> + ConnectFree<Sender, Receivers>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R, Cont, Sender, Receivers> ConnectFree<Sender, Receivers>
+    for TEndFor<IO, Lbl, R, Cont>
+where
+    Cont: TSession<IO> + ConnectFree<Sender, Receivers>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, L, R, Sender, Receivers> ConnectFree<Sender, Receivers>
+    for TChoice<IO, Lbl, L, R>
+where
+    L: TSession<IO> + ConnectFree<Sender, Receivers>,
+    R: TSession<IO> + ConnectFree<Sender, Receivers>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Branches, Sender, Receivers> ConnectFree<Sender, Receivers>
+    for TChoiceN<IO, Lbl, Branches>
+where
+    Branches: SessionList<IO> + ConnectFree<Sender, Receivers>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Chooser, Branches, Sender, Receivers>
+    ConnectFree<Sender, Receivers> for TSelect<IO, Lbl, Chooser, Branches>
+where
+    Branches: SessionList<IO> + ConnectFree<Sender, Receivers>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Offeree, Branches, Sender, Receivers>
+    ConnectFree<Sender, Receivers> for TOffer<IO, Lbl, Offeree, Branches>
+where
+    Branches: SessionList<IO> + ConnectFree<Sender, Receivers>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, High, Low, Sender, Receivers> ConnectFree<Sender, Receivers>
+    for TPriorityChoice<IO, Lbl, High, Low>
+where
+    High: TSession<IO> + ConnectFree<Sender, Receivers>,
+    Low: TSession<IO> + ConnectFree<Sender, Receivers>,
+{
+}
+
+impl<IO, Lbl, WeightL, L, WeightR, R, Sender, Receivers> ConnectFree<Sender, Receivers>
+    for TProb<IO, Lbl, WeightL, L, WeightR, R>
+where
+    Lbl: types::ProtocolLabel,
+    WeightL: types::WeightMarker,
+    WeightR: types::WeightMarker,
+    L: TSession<IO> + ConnectFree<Sender, Receivers>,
+    R: TSession<IO> + ConnectFree<Sender, Receivers>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, L, R, IsDisjoint, Sender, Receivers>
+    ConnectFree<Sender, Receivers> for TPar<IO, Lbl, L, R, IsDisjoint>
+where
+    L: TSession<IO> + ConnectFree<Sender, Receivers>,
+    R: TSession<IO> + ConnectFree<Sender, Receivers>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Branches, Sender, Receivers> ConnectFree<Sender, Receivers>
+    for TParN<IO, Lbl, Branches>
+where
+    Branches: SessionList<IO> + ConnectFree<Sender, Receivers>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Steps, Sender, Receivers> ConnectFree<Sender, Receivers>
+    for TShuffle<IO, Lbl, Steps>
+where
+    Steps: SessionList<IO> + ConnectFree<Sender, Receivers>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, S, Sender, Receivers> ConnectFree<Sender, Receivers>
+    for TRec<IO, Lbl, S>
+where
+    S: TSession<IO> + ConnectFree<Sender, Receivers>,
+{
+}
+
+impl<IO, Lbl, Dur, Body, OnTimeout, Sender, Receivers> ConnectFree<Sender, Receivers>
+    for TTimeout<IO, Lbl, Dur, Body, OnTimeout>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    Body: TSession<IO> + ConnectFree<Sender, Receivers>,
+    OnTimeout: TSession<IO> + ConnectFree<Sender, Receivers>,
+{
+}
+
+impl<IO, Lbl, R, Dur, T, Sender, Receivers> ConnectFree<Sender, Receivers>
+    for TDelay<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: TSession<IO> + ConnectFree<Sender, Receivers>,
+{
+}
+
+impl<IO, Lbl, R, Dur, T, Sender, Receivers> ConnectFree<Sender, Receivers>
+    for TDeadline<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: TSession<IO> + ConnectFree<Sender, Receivers>,
+{
+}
+
+impl<IO, Lbl, Scope, Interruptor, Handler, Sender, Receivers> ConnectFree<Sender, Receivers>
+    for TInterrupt<IO, Lbl, Scope, Interruptor, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Scope: TSession<IO> + ConnectFree<Sender, Receivers>,
+    Handler: TSession<IO> + ConnectFree<Sender, Receivers>,
+{
+}
+
+impl<IO, Lbl, Decider, Body, Cont, Sender, Receivers> ConnectFree<Sender, Receivers>
+    for TOptional<IO, Lbl, Decider, Body, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Body: TSession<IO> + ConnectFree<Sender, Receivers>,
+    Cont: TSession<IO> + ConnectFree<Sender, Receivers>,
+{
+}
+
+impl<Pred, Inner, Sender, Receivers> ConnectFree<Sender, Receivers> for TRefine<Pred, Inner> where
+    Inner: ConnectFree<Sender, Receivers>
+{
+}
+
+impl<Meta, Inner, Sender, Receivers> ConnectFree<Sender, Receivers> for TAnnotate<Meta, Inner> where
+    Inner: ConnectFree<Sender, Receivers>
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R, Err, Sender, Receivers> ConnectFree<Sender, Receivers>
+    for TThrow<IO, Lbl, R, Err>
+{
+}
+
+impl<IO, Lbl, Body, Handler, Sender, Receivers> ConnectFree<Sender, Receivers>
+    for TCatch<IO, Lbl, Body, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Body: TSession<IO> + ConnectFree<Sender, Receivers>,
+    Handler: TSession<IO> + ConnectFree<Sender, Receivers>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Var, Body, Sender, Receivers> ConnectFree<Sender, Receivers>
+    for TRecX<IO, Lbl, Var, Body>
+where
+    Body: TSession<IO> + ConnectFree<Sender, Receivers>,
+{
+}
+
+impl<Var, Sender, Receivers> ConnectFree<Sender, Receivers> for TVar<Var> {}
+
+impl<Sender, Receivers> ConnectFree<Sender, Receivers> for Nil {}
+
+impl<H, T, Sender, Receivers> ConnectFree<Sender, Receivers> for Cons<H, T>
+where
+    H: ConnectFree<Sender, Receivers>,
+    T: ConnectFree<Sender, Receivers>,
+{
+}
+
+/// A protocol in which role pairs interact only while connected: never after a
+/// `TDisconnect` closes their channel, and never before a `TConnect` opens it.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Alice; struct Bob;
+/// impl Role for Alice {} impl Role for Bob {}
+/// impl RoleEq<Alice> for Alice { type Output = True; }
+/// impl RoleEq<Bob> for Alice   { type Output = False; }
+/// impl RoleEq<Alice> for Bob   { type Output = False; }
+/// impl RoleEq<Bob> for Bob     { type Output = True; }
+///
+/// // Alice and Bob connect, exchange one message, and hang up
+/// type Session = TConnect<
+///     Http,
+///     EmptyLabel,
+///     Alice,
+///     Bob,
+///     TMsg<
+///         Http,
+///         EmptyLabel,
+///         Alice,
+///         Bob,
+///         Message,
+///         TDisconnect<Http, EmptyLabel, Alice, Bob, TEnd<Http>>,
+///     >,
+/// >;
+/// fn assert_respected<G: ConnectionsRespected>() {}
+/// assert_respected::<Session>();
+/// ```
+pub trait ConnectionsRespected {}
+
+impl<IO, Lbl> ConnectionsRespected for TEnd<IO, Lbl> {}
+
+impl<IO, Lbl: types::ProtocolLabel, R, H, T> ConnectionsRespected for TInteract<IO, Lbl, R, H, T> where
+    T: TSession<IO> + ConnectionsRespected
+{
+}
+
+// A message needs the pair connected already: no later `TConnect` may open it
+impl<IO, Lbl: types::ProtocolLabel, From, To, H, T> ConnectionsRespected
+    for TMsg<IO, Lbl, From, To, H, T>
+where
+    T: TSession<IO> + ConnectFree<From, Cons<To, Nil>> + ConnectionsRespected,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, ToSet, H, T> ConnectionsRespected
+    for TBroadcast<IO, Lbl, From, ToSet, H, T>
+where
+    T: TSession<IO> + ConnectFree<From, ToSet> + ConnectionsRespected,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, WorkerSet, H, T> ConnectionsRespected
+    for TAnycast<IO, Lbl, From, WorkerSet, H, T>
+where
+    T: TSession<IO> + ConnectFree<From, WorkerSet> + ConnectionsRespected,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, To, DelegatedEp, T> ConnectionsRespected
+    for TDelegate<IO, Lbl, From, To, DelegatedEp, T>
+where
+    T: TSession<IO> + ConnectFree<From, Cons<To, Nil>> + ConnectionsRespected,
+{
+}
+
+// Once `X` and `Y` disconnect, the rest of the protocol must leave the pair alone
+impl<IO, Lbl: types::ProtocolLabel, X, Y, Cont> ConnectionsRespected
+    for TDisconnect<IO, Lbl, X, Y, Cont>
+where
+    Cont: TSession<IO> + PairUnused<X, Y> + ConnectionsRespected,
+{
+}
+
+// Connecting an already connected pair again counts as a use
+impl<IO, Lbl: types::ProtocolLabel, X, Y, Cont> ConnectionsRespected
+    for TConnect<IO, Lbl, X, Y, Cont>
+where
+    Cont: TSession<IO> + ConnectFree<X, Cons<Y, Nil>> + ConnectionsRespected,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R, Cont> ConnectionsRespected for TEndFor<IO, Lbl, R, Cont> where
+    Cont: TSession<IO> + ConnectionsRespected
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, L, R> ConnectionsRespected for TChoice<IO, Lbl, L, R>
+where
+    L: TSession<IO> + ConnectionsRespected,
+    R: TSession<IO> + ConnectionsRespected,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Branches> ConnectionsRespected for TChoiceN<IO, Lbl, Branches> where
+    Branches: SessionList<IO> + ConnectionsRespected
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Chooser, Branches> ConnectionsRespected
+    for TSelect<IO, Lbl, Chooser, Branches>
+where
+    Branches: SessionList<IO> + ConnectionsRespected,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Offeree, Branches> ConnectionsRespected
+    for TOffer<IO, Lbl, Offeree, Branches>
+where
+    Branches: SessionList<IO> + ConnectionsRespected,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, High, Low> ConnectionsRespected
+    for TPriorityChoice<IO, Lbl, High, Low>
+where
+    High: TSession<IO> + ConnectionsRespected,
+    Low: TSession<IO> + ConnectionsRespected,
+{
+}
+
+impl<IO, Lbl, WeightL, L, WeightR, R> ConnectionsRespected
+    for TProb<IO, Lbl, WeightL, L, WeightR, R>
+where
+    Lbl: types::ProtocolLabel,
+    WeightL: types::WeightMarker,
+    WeightR: types::WeightMarker,
+    L: TSession<IO> + ConnectionsRespected,
+    R: TSession<IO> + ConnectionsRespected,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, L, R, IsDisjoint> ConnectionsRespected
+    for TPar<IO, Lbl, L, R, IsDisjoint>
+where
+    L: TSession<IO> + ConnectionsRespected,
+    R: TSession<IO> + ConnectionsRespected,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Branches> ConnectionsRespected for TParN<IO, Lbl, Branches> where
+    Branches: SessionList<IO> + ConnectionsRespected
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Steps> ConnectionsRespected for TShuffle<IO, Lbl, Steps> where
+    Steps: SessionList<IO> + ConnectionsRespected
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, S> ConnectionsRespected for TRec<IO, Lbl, S> where
+    S: TSession<IO> + ConnectionsRespected
+{
+}
+
+impl<IO, Lbl, Dur, Body, OnTimeout> ConnectionsRespected for TTimeout<IO, Lbl, Dur, Body, OnTimeout>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    Body: TSession<IO> + ConnectionsRespected,
+    OnTimeout: TSession<IO> + ConnectionsRespected,
+{
+}
+
+impl<IO, Lbl, R, Dur, T> ConnectionsRespected for TDelay<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: TSession<IO> + ConnectionsRespected,
+{
+}
+
+impl<IO, Lbl, R, Dur, T> ConnectionsRespected for TDeadline<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: TSession<IO> + ConnectionsRespected,
+{
+}
+
+impl<IO, Lbl, Scope, Interruptor, Handler> ConnectionsRespected
+    for TInterrupt<IO, Lbl, Scope, Interruptor, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Scope: TSession<IO> + ConnectionsRespected,
+    Handler: TSession<IO> + ConnectionsRespected,
+{
+}
+
+impl<IO, Lbl, Decider, Body, Cont> ConnectionsRespected for TOptional<IO, Lbl, Decider, Body, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Body: TSession<IO> + ConnectionsRespected,
+    Cont: TSession<IO> + ConnectionsRespected,
+{
+}
+
+impl<Pred, Inner> ConnectionsRespected for TRefine<Pred, Inner> where Inner: ConnectionsRespected {}
+
+impl<Meta, Inner> ConnectionsRespected for TAnnotate<Meta, Inner> where Inner: ConnectionsRespected {}
+
+impl<IO, Lbl: types::ProtocolLabel, R, Err> ConnectionsRespected for TThrow<IO, Lbl, R, Err> {}
+
+impl<IO, Lbl, Body, Handler> ConnectionsRespected for TCatch<IO, Lbl, Body, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Body: TSession<IO> + ConnectionsRespected,
+    Handler: TSession<IO> + ConnectionsRespected,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Var, Body> ConnectionsRespected for TRecX<IO, Lbl, Var, Body> where
+    Body: TSession<IO> + ConnectionsRespected
+{
+}
+
+impl<Var> ConnectionsRespected for TVar<Var> {}
+
+impl ConnectionsRespected for Nil {}
+
+impl<H, T> ConnectionsRespected for Cons<H, T>
+where
+    H: ConnectionsRespected,
+    T: ConnectionsRespected,
+{
+}
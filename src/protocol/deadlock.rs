@@ -0,0 +1,635 @@
+//! # Deadlock Checks
+//!
+//! This module checks that a global protocol cannot deadlock under
+//! asynchronous semantics, where sends never block and receives wait for
+//! their message.
+//!
+//! Key components:
+//!
+//! - `Channel`: A directed channel from a sending role to a receiving role
+//! - `ChannelsOf` / `ChannelsOfEach`: The channels a protocol sends messages on
+//! - `ChannelIn`: Type-level membership of a channel in a list of channels
+//! - `ByChannel`: Compares channels as list elements, such as for `Dedup`
+//! - `NoneReversedIn`: No channel of a list runs backwards in another list
+//! - `DeadlockFree`: A protocol without circular waits between concurrent branches
+//! - `CircularWait` / `DeadlockHazard`: The failure marker naming the pair that can deadlock
+//!
+//! A sequential protocol is deadlock-free by construction: every receive is
+//! matched by a send that comes before it. Concurrent branches (`TPar`,
+//! `TParN`, `TShuffle`) give up that order, and a role running several of
+//! them may serve them in any order. When one branch sends from `A` to `B`
+//! and another sends from `B` to `A`, `A` can wait on the first branch while
+//! `B` waits on the second, each for a message the other has not sent yet.
+//! `DeadlockFree` rejects every pair of roles that communicates in opposite
+//! directions across concurrent branches.
+//!
+//! Channels are collected as sets, so a loop body contributes the same
+//! channels however many times it runs, and checking it once is enough. A
+//! `TInteract` gives no role a message to wait for and adds no channel.
+
+use super::base::{Cons, Nil};
+use super::global::*;
+use super::local::RoleEq;
+use super::transforms::SingleChooser;
+use super::utils::{Concat, ElemEq};
+use crate::types;
+use core::marker::PhantomData;
+
+/// A directed channel carrying messages from role `From` to role `To`.
+pub struct Channel<From, To>(PhantomData<(From, To)>);
+
+/// Collects the channels a protocol sends messages on, as a type-level list of
+/// [`Channel`]s in protocol order.
+///
+/// Broadcasts and anycasts use one channel per receiver. Channels may repeat.
+pub trait ChannelsOf {
+    type Channels;
+}
+
+/// Collects the channels of every protocol in a type-level list, in order.
+pub trait ChannelsOfEach {
+    type Channels;
+}
+
+impl ChannelsOfEach for Nil {
+    type Channels = Nil;
+}
+
+impl<H, T> ChannelsOfEach for Cons<H, T>
+where
+    H: ChannelsOf,
+    T: ChannelsOfEach,
+    <H as ChannelsOf>::Channels: Concat<<T as ChannelsOfEach>::Channels>,
+{
+    type Channels =
+        <<H as ChannelsOf>::Channels as Concat<<T as ChannelsOfEach>::Channels>>::Output;
+}
+
+/// The channels from `From` to each role of the type-level list `ToSet`.
+pub trait FanOut<From> {
+    type Channels;
+}
+
+impl<From> FanOut<From> for Nil {
+    type Channels = Nil;
+}
+
+impl<From, H, T: FanOut<From>> FanOut<From> for Cons<H, T> {
+    type Channels = Cons<Channel<From, H>, <T as FanOut<From>>::Channels>;
+}
+
+/// The channels of two protocols run one after the other, or as alternatives.
+type Both<L, R> = <<L as ChannelsOf>::Channels as Concat<<R as ChannelsOf>::Channels>>::Output;
+
+impl<IO, Lbl> ChannelsOf for TEnd<IO, Lbl> {
+    type Channels = Nil;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R, H, T> ChannelsOf for TInteract<IO, Lbl, R, H, T>
+where
+    T: TSession<IO> + ChannelsOf,
+{
+    type Channels = <T as ChannelsOf>::Channels;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, To, H, T> ChannelsOf for TMsg<IO, Lbl, From, To, H, T>
+where
+    T: TSession<IO> + ChannelsOf,
+{
+    type Channels = Cons<Channel<From, To>, <T as ChannelsOf>::Channels>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, ToSet, H, T> ChannelsOf
+    for TBroadcast<IO, Lbl, From, ToSet, H, T>
+where
+    ToSet: FanOut<From>,
+    T: TSession<IO> + ChannelsOf,
+    <ToSet as FanOut<From>>::Channels: Concat<<T as ChannelsOf>::Channels>,
+{
+    type Channels =
+        <<ToSet as FanOut<From>>::Channels as Concat<<T as ChannelsOf>::Channels>>::Output;
+}
+
+// Any worker may receive an anycast, so every worker counts as a receiver
+impl<IO, Lbl: types::ProtocolLabel, From, WorkerSet, H, T> ChannelsOf
+    for TAnycast<IO, Lbl, From, WorkerSet, H, T>
+where
+    WorkerSet: FanOut<From>,
+    T: TSession<IO> + ChannelsOf,
+    <WorkerSet as FanOut<From>>::Channels: Concat<<T as ChannelsOf>::Channels>,
+{
+    type Channels =
+        <<WorkerSet as FanOut<From>>::Channels as Concat<<T as ChannelsOf>::Channels>>::Output;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, To, DelegatedEp, T> ChannelsOf
+    for TDelegate<IO, Lbl, From, To, DelegatedEp, T>
+where
+    T: TSession<IO> + ChannelsOf,
+{
+    type Channels = Cons<Channel<From, To>, <T as ChannelsOf>::Channels>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, A, B, Cont> ChannelsOf for TDisconnect<IO, Lbl, A, B, Cont>
+where
+    Cont: TSession<IO> + ChannelsOf,
+{
+    type Channels = <Cont as ChannelsOf>::Channels;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, A, B, Cont> ChannelsOf for TConnect<IO, Lbl, A, B, Cont>
+where
+    Cont: TSession<IO> + ChannelsOf,
+{
+    type Channels = <Cont as ChannelsOf>::Channels;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R, Cont> ChannelsOf for TEndFor<IO, Lbl, R, Cont>
+where
+    Cont: TSession<IO> + ChannelsOf,
+{
+    type Channels = <Cont as ChannelsOf>::Channels;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, L, R> ChannelsOf for TChoice<IO, Lbl, L, R>
+where
+    L: TSession<IO> + ChannelsOf,
+    R: TSession<IO> + ChannelsOf,
+    <L as ChannelsOf>::Channels: Concat<<R as ChannelsOf>::Channels>,
+{
+    type Channels = Both<L, R>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Branches> ChannelsOf for TChoiceN<IO, Lbl, Branches>
+where
+    Branches: SessionList<IO> + ChannelsOfEach,
+{
+    type Channels = <Branches as ChannelsOfEach>::Channels;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Chooser, Branches> ChannelsOf
+    for TSelect<IO, Lbl, Chooser, Branches>
+where
+    Branches: SessionList<IO> + ChannelsOfEach,
+{
+    type Channels = <Branches as ChannelsOfEach>::Channels;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Offeree, Branches> ChannelsOf
+    for TOffer<IO, Lbl, Offeree, Branches>
+where
+    Branches: SessionList<IO> + ChannelsOfEach,
+{
+    type Channels = <Branches as ChannelsOfEach>::Channels;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, High, Low> ChannelsOf for TPriorityChoice<IO, Lbl, High, Low>
+where
+    High: TSession<IO> + ChannelsOf,
+    Low: TSession<IO> + ChannelsOf,
+    <High as ChannelsOf>::Channels: Concat<<Low as ChannelsOf>::Channels>,
+{
+    type Channels = Both<High, Low>;
+}
+
+impl<IO, Lbl, WeightL, L, WeightR, R> ChannelsOf for TProb<IO, Lbl, WeightL, L, WeightR, R>
+where
+    Lbl: types::ProtocolLabel,
+    WeightL: types::WeightMarker,
+    WeightR: types::WeightMarker,
+    L: TSession<IO> + ChannelsOf,
+    R: TSession<IO> + ChannelsOf,
+    <L as ChannelsOf>::Channels: Concat<<R as ChannelsOf>::Channels>,
+{
+    type Channels = Both<L, R>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, L, R, IsDisjoint> ChannelsOf for TPar<IO, Lbl, L, R, IsDisjoint>
+where
+    L: TSession<IO> + ChannelsOf,
+    R: TSession<IO> + ChannelsOf,
+    <L as ChannelsOf>::Channels: Concat<<R as ChannelsOf>::Channels>,
+{
+    type Channels = Both<L, R>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Branches> ChannelsOf for TParN<IO, Lbl, Branches>
+where
+    Branches: SessionList<IO> + ChannelsOfEach,
+{
+    type Channels = <Branches as ChannelsOfEach>::Channels;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Steps> ChannelsOf for TShuffle<IO, Lbl, Steps>
+where
+    Steps: SessionList<IO> + ChannelsOfEach,
+{
+    type Channels = <Steps as ChannelsOfEach>::Channels;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, S> ChannelsOf for TRec<IO, Lbl, S>
+where
+    S: TSession<IO> + ChannelsOf,
+{
+    type Channels = <S as ChannelsOf>::Channels;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Var, Body> ChannelsOf for TRecX<IO, Lbl, Var, Body>
+where
+    Body: TSession<IO> + ChannelsOf,
+{
+    type Channels = <Body as ChannelsOf>::Channels;
+}
+
+impl<Var> ChannelsOf for TVar<Var> {
+    type Channels = Nil;
+}
+
+impl<IO, Lbl, Dur, Body, OnTimeout> ChannelsOf for TTimeout<IO, Lbl, Dur, Body, OnTimeout>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    Body: TSession<IO> + ChannelsOf,
+    OnTimeout: TSession<IO> + ChannelsOf,
+    <Body as ChannelsOf>::Channels: Concat<<OnTimeout as ChannelsOf>::Channels>,
+{
+    type Channels = Both<Body, OnTimeout>;
+}
+
+impl<IO, Lbl, R, Dur, T> ChannelsOf for TDelay<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: TSession<IO> + ChannelsOf,
+{
+    type Channels = <T as ChannelsOf>::Channels;
+}
+
+impl<IO, Lbl, R, Dur, T> ChannelsOf for TDeadline<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: TSession<IO> + ChannelsOf,
+{
+    type Channels = <T as ChannelsOf>::Channels;
+}
+
+impl<IO, Lbl, Scope, Interruptor, Handler> ChannelsOf
+    for TInterrupt<IO, Lbl, Scope, Interruptor, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Scope: TSession<IO> + ChannelsOf,
+    Handler: TSession<IO> + ChannelsOf,
+    <Scope as ChannelsOf>::Channels: Concat<<Handler as ChannelsOf>::Channels>,
+{
+    type Channels = Both<Scope, Handler>;
+}
+
+impl<IO, Lbl, Decider, Body, Cont> ChannelsOf for TOptional<IO, Lbl, Decider, Body, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Body: TSession<IO> + ChannelsOf,
+    Cont: TSession<IO> + ChannelsOf,
+    <Body as ChannelsOf>::Channels: Concat<<Cont as ChannelsOf>::Channels>,
+{
+    type Channels = Both<Body, Cont>;
+}
+
+impl<Pred, Inner: ChannelsOf> ChannelsOf for TRefine<Pred, Inner> {
+    type Channels = <Inner as ChannelsOf>::Channels;
+}
+
+impl<Meta, Inner: ChannelsOf> ChannelsOf for TAnnotate<Meta, Inner> {
+    type Channels = <Inner as ChannelsOf>::Channels;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R, Err> ChannelsOf for TThrow<IO, Lbl, R, Err> {
+    type Channels = Nil;
+}
+
+impl<IO, Lbl, Body, Handler> ChannelsOf for TCatch<IO, Lbl, Body, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Body: TSession<IO> + ChannelsOf,
+    Handler: TSession<IO> + ChannelsOf,
+    <Body as ChannelsOf>::Channels: Concat<<Handler as ChannelsOf>::Channels>,
+{
+    type Channels = Both<Body, Handler>;
+}
+
+/// Type-level membership of a channel in a list of channels.
+///
+/// Roles are compared with `RoleEq`; `Output` is `True` if some channel of
+/// `Set` has the same sender and the same receiver.
+pub trait ChannelIn<Set> {
+    type Output: types::Bool;
+}
+
+impl<From, To> ChannelIn<Nil> for Channel<From, To> {
+    type Output = types::False;
+}
+
+impl<From, To, F, T, Rest> ChannelIn<Cons<Channel<F, T>, Rest>> for Channel<From, To>
+where
+    From: RoleEq<F>,
+    To: RoleEq<T>,
+    <From as RoleEq<F>>::Output: types::BoolAnd<<To as RoleEq<T>>::Output>,
+    Channel<From, To>: ChannelIn<Rest>,
+    types::And<<From as RoleEq<F>>::Output, <To as RoleEq<T>>::Output>:
+        types::BoolOr<<Channel<From, To> as ChannelIn<Rest>>::Output>,
+{
+    type Output = types::Or<
+        types::And<<From as RoleEq<F>>::Output, <To as RoleEq<T>>::Output>,
+        <Channel<From, To> as ChannelIn<Rest>>::Output,
+    >;
+}
+
+/// Compares list elements as [`Channel`]s: two channels are equal if their
+/// senders and their receivers are, compared with `RoleEq`.
+pub struct ByChannel;
+
+impl<From, To, F, T> ElemEq<ByChannel, Channel<F, T>> for Channel<From, To>
+where
+    From: RoleEq<F>,
+    To: RoleEq<T>,
+    <From as RoleEq<F>>::Output: types::BoolAnd<<To as RoleEq<T>>::Output>,
+{
+    type Output = types::And<<From as RoleEq<F>>::Output, <To as RoleEq<T>>::Output>;
+}
+
+/// Marker for roles `A` and `B` that communicate in opposite directions in two
+/// concurrent branches, so each may wait for the other.
+pub struct CircularWait<A, B>(PhantomData<(A, B)>);
+
+/// Never implemented: a bound `Marker: DeadlockHazard` fails and names the
+/// hazard found by [`DeadlockFree`].
+#[diagnostic::on_unimplemented(
+    message = "the protocol can deadlock: `{Self}`",
+    label = "concurrent branches wait on each other",
+    note = "the two roles send to each other in different concurrent branches; \
+            keep each pair of roles talking in one direction across branches, \
+            or sequence the branches"
+)]
+pub trait DeadlockHazard {}
+
+/// No channel of a type-level list of channels runs backwards in `Other`: for
+/// every `Channel<A, B>` of the list, `Channel<B, A>` is not in `Other`.
+pub trait NoneReversedIn<Other> {}
+
+impl<Other> NoneReversedIn<Other> for Nil {}
+
+impl<A, B, Rest, Other> NoneReversedIn<Other> for Cons<Channel<A, B>, Rest>
+where
+    Channel<B, A>: ChannelIn<Other>,
+    (): NoneReversedInCase<A, B, <Channel<B, A> as ChannelIn<Other>>::Output>,
+    Rest: NoneReversedIn<Other>,
+{
+}
+
+/// Helper trait for `NoneReversedIn`, dispatching on whether the reversed
+/// channel was found.
+pub trait NoneReversedInCase<A, B, Found> {}
+
+impl<A, B> NoneReversedInCase<A, B, types::False> for () {}
+
+impl<A, B> NoneReversedInCase<A, B, types::True> for () where CircularWait<A, B>: DeadlockHazard {}
+
+/// The protocols of a type-level list run concurrently without circular waits:
+/// no pair of roles sends in opposite directions in two different branches.
+pub trait ConcurrentlyDeadlockFree {}
+
+impl ConcurrentlyDeadlockFree for Nil {}
+
+impl<H, T> ConcurrentlyDeadlockFree for Cons<H, T>
+where
+    H: ChannelsOf,
+    T: ChannelsOfEach + ConcurrentlyDeadlockFree,
+    <H as ChannelsOf>::Channels: NoneReversedIn<<T as ChannelsOfEach>::Channels>,
+{
+}
+
+/// A global protocol that cannot deadlock under asynchronous semantics.
+///
+/// Every concurrent composition, and every protocol nested in it, must be
+/// free of circular waits: see the module documentation. A violation names
+/// the pair of roles through [`CircularWait`]. Check a protocol with
+/// [`assert_deadlock_free!`](crate::assert_deadlock_free).
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Alice; struct Bob; struct Carol; struct Ping;
+/// impl Role for Alice {} impl Role for Bob {} impl Role for Carol {}
+/// impl ProtocolLabel for Ping {}
+/// impl RoleEq<Alice> for Alice { type Output = True; }
+/// impl RoleEq<Bob> for Alice   { type Output = False; }
+/// impl RoleEq<Carol> for Alice { type Output = False; }
+/// impl RoleEq<Alice> for Bob   { type Output = False; }
+/// impl RoleEq<Bob> for Bob     { type Output = True; }
+/// impl RoleEq<Carol> for Bob   { type Output = False; }
+/// impl RoleEq<Alice> for Carol { type Output = False; }
+/// impl RoleEq<Bob> for Carol   { type Output = False; }
+/// impl RoleEq<Carol> for Carol { type Output = True; }
+///
+/// // Alice talks to Bob and Carol at the same time
+/// type Fanout = TParN<
+///     Http,
+///     EmptyLabel,
+///     tlist!(
+///         TMsg<Http, Ping, Alice, Bob, Message, TEnd<Http>>,
+///         TMsg<Http, Ping, Alice, Carol, Message, TEnd<Http>>,
+///     ),
+/// >;
+/// assert_deadlock_free!(Fanout);
+/// ```
+pub trait DeadlockFree {}
+
+impl<IO, Lbl> DeadlockFree for TEnd<IO, Lbl> {}
+
+impl<IO, Lbl: types::ProtocolLabel, R, H, T> DeadlockFree for TInteract<IO, Lbl, R, H, T> where
+    T: TSession<IO> + DeadlockFree
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, To, H, T> DeadlockFree for TMsg<IO, Lbl, From, To, H, T> where
+    T: TSession<IO> + DeadlockFree
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, ToSet, H, T> DeadlockFree
+    for TBroadcast<IO, Lbl, From, ToSet, H, T>
+where
+    T: TSession<IO> + DeadlockFree,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, WorkerSet, H, T> DeadlockFree
+    for TAnycast<IO, Lbl, From, WorkerSet, H, T>
+where
+    T: TSession<IO> + DeadlockFree,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, To, DelegatedEp, T> DeadlockFree
+    for TDelegate<IO, Lbl, From, To, DelegatedEp, T>
+where
+    T: TSession<IO> + DeadlockFree,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, A, B, Cont> DeadlockFree for TDisconnect<IO, Lbl, A, B, Cont> where
+    Cont: TSession<IO> + DeadlockFree
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, A, B, Cont> DeadlockFree for TConnect<IO, Lbl, A, B, Cont> where
+    Cont: TSession<IO> + DeadlockFree
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R, Cont> DeadlockFree for TEndFor<IO, Lbl, R, Cont> where
+    Cont: TSession<IO> + DeadlockFree
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, L, R> DeadlockFree for TChoice<IO, Lbl, L, R>
+where
+    L: TSession<IO> + DeadlockFree,
+    R: TSession<IO> + DeadlockFree,
+    Cons<L, Cons<R, Nil>>: SingleChooser<Lbl>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Branches> DeadlockFree for TChoiceN<IO, Lbl, Branches> where
+    Branches: SessionList<IO> + DeadlockFree
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Chooser, Branches> DeadlockFree
+    for TSelect<IO, Lbl, Chooser, Branches>
+where
+    Branches: SessionList<IO> + DeadlockFree,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Offeree, Branches> DeadlockFree
+    for TOffer<IO, Lbl, Offeree, Branches>
+where
+    Branches: SessionList<IO> + DeadlockFree,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, High, Low> DeadlockFree for TPriorityChoice<IO, Lbl, High, Low>
+where
+    High: TSession<IO> + DeadlockFree,
+    Low: TSession<IO> + DeadlockFree,
+{
+}
+
+impl<IO, Lbl, WeightL, L, WeightR, R> DeadlockFree for TProb<IO, Lbl, WeightL, L, WeightR, R>
+where
+    Lbl: types::ProtocolLabel,
+    WeightL: types::WeightMarker,
+    WeightR: types::WeightMarker,
+    L: TSession<IO> + DeadlockFree,
+    R: TSession<IO> + DeadlockFree,
+{
+}
+
+// The two branches run concurrently
+impl<IO, Lbl: types::ProtocolLabel, L, R, IsDisjoint> DeadlockFree
+    for TPar<IO, Lbl, L, R, IsDisjoint>
+where
+    L: TSession<IO> + DeadlockFree,
+    R: TSession<IO> + DeadlockFree,
+    Cons<L, Cons<R, Nil>>: ConcurrentlyDeadlockFree,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Branches> DeadlockFree for TParN<IO, Lbl, Branches> where
+    Branches: SessionList<IO> + DeadlockFree + ConcurrentlyDeadlockFree
+{
+}
+
+// The steps of a shuffle may be served in any order
+impl<IO, Lbl: types::ProtocolLabel, Steps> DeadlockFree for TShuffle<IO, Lbl, Steps> where
+    Steps: SessionList<IO> + DeadlockFree + ConcurrentlyDeadlockFree
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, S> DeadlockFree for TRec<IO, Lbl, S> where
+    S: TSession<IO> + DeadlockFree
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Var, Body> DeadlockFree for TRecX<IO, Lbl, Var, Body> where
+    Body: TSession<IO> + DeadlockFree
+{
+}
+
+impl<Var> DeadlockFree for TVar<Var> {}
+
+impl<IO, Lbl, Dur, Body, OnTimeout> DeadlockFree for TTimeout<IO, Lbl, Dur, Body, OnTimeout>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    Body: TSession<IO> + DeadlockFree,
+    OnTimeout: TSession<IO> + DeadlockFree,
+{
+}
+
+impl<IO, Lbl, R, Dur, T> DeadlockFree for TDelay<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: TSession<IO> + DeadlockFree,
+{
+}
+
+impl<IO, Lbl, R, Dur, T> DeadlockFree for TDeadline<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: TSession<IO> + DeadlockFree,
+{
+}
+
+impl<IO, Lbl, Scope, Interruptor, Handler> DeadlockFree
+    for TInterrupt<IO, Lbl, Scope, Interruptor, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Scope: TSession<IO> + DeadlockFree,
+    Handler: TSession<IO> + DeadlockFree,
+{
+}
+
+impl<IO, Lbl, Decider, Body, Cont> DeadlockFree for TOptional<IO, Lbl, Decider, Body, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Body: TSession<IO> + DeadlockFree,
+    Cont: TSession<IO> + DeadlockFree,
+{
+}
+
+impl<Pred, Inner: DeadlockFree> DeadlockFree for TRefine<Pred, Inner> {}
+
+impl<Meta, Inner: DeadlockFree> DeadlockFree for TAnnotate<Meta, Inner> {}
+
+impl<IO, Lbl: types::ProtocolLabel, R, Err> DeadlockFree for TThrow<IO, Lbl, R, Err> {}
+
+impl<IO, Lbl, Body, Handler> DeadlockFree for TCatch<IO, Lbl, Body, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Body: TSession<IO> + DeadlockFree,
+    Handler: TSession<IO> + DeadlockFree,
+{
+}
+
+impl DeadlockFree for Nil {}
+
+impl<H, T> DeadlockFree for Cons<H, T>
+where
+    H: DeadlockFree,
+    T: DeadlockFree,
+{
+}
@@ -0,0 +1,1201 @@
+//! # Runtime Description
+//!
+//! This module turns a global protocol type into a value mirroring its tree,
+//! so that command-line tools, exporters and monitors can work from data
+//! instead of each walking the protocol types themselves.
+//!
+//! Key components:
+//!
+//! - `Describe`: The runtime description of a global protocol
+//! - `DescribeEach`: The same for every protocol of a type-level list
+//! - `ProtocolDesc`: A node of the description, with its children
+//! - `CombinatorKind`: The combinator a node stands for
+//! - `ChannelDesc`: A channel of the communication graph, from `ProtocolDesc::channels`
+//! - `Steps` / `StepDesc`: The nodes of a description in execution order, from `ProtocolDesc::steps`
+//! - `RoleNames`: The names of the roles of a type-level role set
+//! - `RoleNamesOf`: The names of the roles of a protocol, as a constant array
+//! - `RoleNamesSlice`: The same names as a constant slice, for any number of roles
+//! - `ConstRoleName` / `ConstLabelName`: The `NAME` of a role or label, as a constant
+//! - `ProtocolDisplay`: Indented, Scribble-like text of a global protocol
+//!
+//! Labels and roles are identified by their names (see `ProtocolLabel::NAME`
+//! and `Role::NAME`), messages and other type parameters by their type names.
+//! A node's children are its continuation and branches, in the order of the
+//! combinator's type parameters. A delegated endpoint is listed
+//! as the message of its `TDelegate`.
+
+use super::base::{Cons, Nil};
+use super::global::*;
+use super::local::Role;
+use crate::introspection::UniqueRoles;
+use crate::types;
+use core::any::type_name;
+use core::fmt;
+use core::marker::PhantomData;
+
+/// The combinator a [`ProtocolDesc`] node stands for.
+///
+/// The order of the variants feeds [`FingerprintOf`](super::fingerprint::FingerprintOf):
+/// new variants go at the end.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CombinatorKind {
+    /// `TEnd`
+    End,
+    /// `TInteract`
+    Interact,
+    /// `TMsg`
+    Msg,
+    /// `TBroadcast`
+    Broadcast,
+    /// `TAnycast`
+    Anycast,
+    /// `TDelegate`
+    Delegate,
+    /// `TDisconnect`
+    Disconnect,
+    /// `TConnect`
+    Connect,
+    /// `TEndFor`
+    EndFor,
+    /// `TChoice`
+    Choice,
+    /// `TChoiceN`
+    ChoiceN,
+    /// `TSelect`
+    Select,
+    /// `TOffer`
+    Offer,
+    /// `TPriorityChoice`
+    PriorityChoice,
+    /// `TProb`
+    Prob,
+    /// `TPar`
+    Par,
+    /// `TParN`
+    ParN,
+    /// `TShuffle`
+    Shuffle,
+    /// `TRec`
+    Rec,
+    /// `TRecX`
+    RecX,
+    /// `TVar`
+    Var,
+    /// `TTimeout`
+    Timeout,
+    /// `TDelay`
+    Delay,
+    /// `TDeadline`
+    Deadline,
+    /// `TInterrupt`
+    Interrupt,
+    /// `TOptional`
+    Optional,
+    /// `TRefine`
+    Refine,
+    /// `TAnnotate`
+    Annotate,
+    /// `TThrow`
+    Throw,
+    /// `TCatch`
+    Catch,
+}
+
+impl CombinatorKind {
+    /// Whether the protocol splits at this combinator into alternative or
+    /// concurrent branches: the choices and parallel compositions.
+    pub fn is_branch_point(self) -> bool {
+        use CombinatorKind::*;
+        matches!(
+            self,
+            Choice | ChoiceN | Select | Offer | PriorityChoice | Prob | Par | ParN | Shuffle
+        )
+    }
+
+    // Whether the last child of the node follows it in sequence, rather than
+    // being nested in it
+    fn continues(self) -> bool {
+        use CombinatorKind::*;
+        matches!(
+            self,
+            Interact
+                | Msg
+                | Broadcast
+                | Anycast
+                | Delegate
+                | Disconnect
+                | Connect
+                | EndFor
+                | Delay
+                | Optional
+                | Annotate
+                | Throw
+        )
+    }
+}
+
+/// Runtime description of a global protocol, one node per combinator.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ProtocolDesc {
+    /// The combinator of this node.
+    pub kind: CombinatorKind,
+    /// The label of the node; `None` for `TVar`, `TRefine` and `TAnnotate`.
+    pub label: Option<&'static str>,
+    /// The roles acting at this node: senders, receivers and role sets,
+    /// deciders of choices, interruptors and timed or ending roles.
+    pub roles: Vec<&'static str>,
+    /// The payloads sent at this node, the error of a `TThrow` or the
+    /// endpoint handed over by a `TDelegate`.
+    pub messages: Vec<&'static str>,
+    /// Further type parameters: recursion variables, duration and weight
+    /// markers, predicates and annotation metadata.
+    pub params: Vec<&'static str>,
+    /// The continuation and branches of the node, in type parameter order.
+    pub children: Vec<ProtocolDesc>,
+}
+
+impl ProtocolDesc {
+    fn new(kind: CombinatorKind, label: Option<&'static str>) -> Self {
+        ProtocolDesc {
+            kind,
+            label,
+            roles: Vec::new(),
+            messages: Vec::new(),
+            params: Vec::new(),
+            children: Vec::new(),
+        }
+    }
+
+    /// The communication graph of the protocol: every channel a message,
+    /// broadcast, anycast or delegation is sent on, each once, in order of
+    /// first use.
+    ///
+    /// The runtime counterpart of
+    /// [`CommunicationGraph`](crate::CommunicationGraph), with roles by name.
+    pub fn channels(&self) -> Vec<ChannelDesc> {
+        let mut out = Vec::new();
+        self.append_channels(&mut out);
+        out
+    }
+
+    fn append_channels(&self, out: &mut Vec<ChannelDesc>) {
+        if let CombinatorKind::Msg
+        | CombinatorKind::Broadcast
+        | CombinatorKind::Anycast
+        | CombinatorKind::Delegate = self.kind
+        {
+            if let Some((from, receivers)) = self.roles.split_first() {
+                for to in receivers {
+                    let channel = ChannelDesc { from, to };
+                    if !out.contains(&channel) {
+                        out.push(channel);
+                    }
+                }
+            }
+        }
+        for child in &self.children {
+            child.append_channels(out);
+        }
+    }
+
+    /// Walks the protocol in execution order, one [`StepDesc`] per node.
+    ///
+    /// Each node comes before its continuation and branches. The branches of
+    /// a choice or parallel composition follow their branch point one after
+    /// the other, in type parameter order, and a loop body is walked once.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use besedarium::*;
+    /// type Ask = TMsg<Http, EmptyLabel, TClient, TServer, Message, TChoice<Http, EmptyLabel,
+    ///     TMsg<Http, EmptyLabel, TServer, TClient, Response, TEnd<Http>>,
+    ///     TEnd<Http>>>;
+    /// let desc = Ask::describe();
+    /// let steps: Vec<_> = desc.steps().map(|step| (step.desc.kind, step.depth)).collect();
+    /// assert_eq!(steps, vec![
+    ///     (CombinatorKind::Msg, 0),
+    ///     (CombinatorKind::Choice, 0),
+    ///     (CombinatorKind::Msg, 1),
+    ///     (CombinatorKind::End, 1),
+    ///     (CombinatorKind::End, 1),
+    /// ]);
+    /// assert!(desc.steps().nth(1).unwrap().is_branch_point());
+    /// ```
+    pub fn steps(&self) -> Steps<'_> {
+        Steps {
+            stack: vec![StepDesc {
+                desc: self,
+                depth: 0,
+                path: Vec::new(),
+            }],
+        }
+    }
+}
+
+/// A node of a protocol description, as visited by [`ProtocolDesc::steps`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StepDesc<'a> {
+    /// The node visited.
+    pub desc: &'a ProtocolDesc,
+    /// The nesting depth of the node: the number of branches and scopes it is
+    /// in, as indented by [`ProtocolDisplay`].
+    pub depth: usize,
+    /// The position of the node among the children of each of its
+    /// ancestors, from the root.
+    pub path: Vec<usize>,
+}
+
+impl StepDesc<'_> {
+    /// Whether the protocol splits at this step into branches.
+    pub fn is_branch_point(&self) -> bool {
+        self.desc.kind.is_branch_point()
+    }
+}
+
+/// Iterator over the nodes of a protocol description in execution order.
+///
+/// Returned by [`ProtocolDesc::steps`].
+#[derive(Debug, Clone)]
+pub struct Steps<'a> {
+    stack: Vec<StepDesc<'a>>,
+}
+
+impl<'a> Iterator for Steps<'a> {
+    type Item = StepDesc<'a>;
+
+    fn next(&mut self) -> Option<StepDesc<'a>> {
+        let step = self.stack.pop()?;
+        let last = step.desc.children.len().saturating_sub(1);
+        for (i, child) in step.desc.children.iter().enumerate().rev() {
+            let mut path = step.path.clone();
+            path.push(i);
+            let nested = !(i == last && step.desc.kind.continues());
+            self.stack.push(StepDesc {
+                desc: child,
+                depth: step.depth + usize::from(nested),
+                path,
+            });
+        }
+        Some(step)
+    }
+}
+
+/// A directed channel of a protocol description: `from` sends messages to
+/// `to`. Both roles are given by name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ChannelDesc {
+    pub from: &'static str,
+    pub to: &'static str,
+}
+
+/// Describes a global protocol as a [`ProtocolDesc`] value.
+///
+/// Trait methods cannot be `const fn`, so the description is built when
+/// `describe` is called.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// type PingPong = TMsg<Http, EmptyLabel, TClient, TServer, Message,
+///                 TMsg<Http, EmptyLabel, TServer, TClient, Response, TEnd<Http>>>;
+/// let desc = PingPong::describe();
+/// assert_eq!(desc.kind, CombinatorKind::Msg);
+/// assert_eq!(desc.roles, vec!["TClient", "TServer"]);
+/// assert_eq!(desc.children[0].messages, vec![std::any::type_name::<Response>()]);
+/// assert_eq!(desc.children[0].children[0].kind, CombinatorKind::End);
+/// ```
+pub trait Describe {
+    /// Runtime description of the protocol.
+    fn describe() -> ProtocolDesc;
+}
+
+/// Describes every protocol of a type-level list, in order.
+///
+/// - Used by n-ary combinators to describe their branches.
+pub trait DescribeEach {
+    /// Appends the description of every protocol in the list to `out`.
+    fn describe_each(out: &mut Vec<ProtocolDesc>);
+}
+
+impl DescribeEach for Nil {
+    fn describe_each(_out: &mut Vec<ProtocolDesc>) {}
+}
+
+impl<H: Describe, T: DescribeEach> DescribeEach for Cons<H, T> {
+    fn describe_each(out: &mut Vec<ProtocolDesc>) {
+        out.push(H::describe());
+        T::describe_each(out);
+    }
+}
+
+/// Runtime names of the roles of a type-level role set, such as the
+/// receivers of a `TBroadcast`.
+pub trait RoleNames {
+    /// Appends the name of every role of the list to `out`.
+    fn append_names(out: &mut Vec<&'static str>);
+}
+
+impl RoleNames for Nil {
+    fn append_names(_out: &mut Vec<&'static str>) {}
+}
+
+impl<H: Role, T: RoleNames> RoleNames for Cons<H, T> {
+    fn append_names(out: &mut Vec<&'static str>) {
+        out.push(H::name());
+        T::append_names(out);
+    }
+}
+
+/// The [`Role::NAME`] of a role, as a constant.
+///
+/// The type name is not available in constants, so a role without a `NAME`,
+/// such as the members of a [`role_family!`](crate::role_family), fails to
+/// compile where its constant name is used; the error names
+/// `ConstRoleName::<R>::NAME`.
+pub struct ConstRoleName<R>(PhantomData<R>);
+
+impl<R: Role> ConstRoleName<R> {
+    /// `R::NAME`.
+    pub const NAME: &'static str = match R::NAME {
+        Some(name) => name,
+        None => panic!("a constant role name needs `Role::NAME`, which this role does not set"),
+    };
+}
+
+/// The [`ProtocolLabel::NAME`](types::ProtocolLabel::NAME) of a label, as a
+/// constant.
+///
+/// As for [`ConstRoleName`], a label without a `NAME` fails to compile where
+/// its constant name is used; the error names `ConstLabelName::<Lbl>::NAME`.
+pub struct ConstLabelName<Lbl>(PhantomData<Lbl>);
+
+impl<Lbl: types::ProtocolLabel> ConstLabelName<Lbl> {
+    /// `Lbl::NAME`.
+    pub const NAME: &'static str = match Lbl::NAME {
+        Some(name) => name,
+        None => panic!(
+            "a constant label name needs `ProtocolLabel::NAME`, which this label does not set"
+        ),
+    };
+}
+
+/// Constant names of the roles of a type-level role list, in order, in an
+/// array of `N` entries.
+///
+/// - `NAMES` holds the names in its first `LEN` entries; the rest are empty.
+/// - Every role must set [`Role::NAME`], which derived roles do; see
+///   [`ConstRoleName`].
+pub trait ConstRoleNames<const N: usize> {
+    /// The number of roles in the list.
+    const LEN: usize;
+    /// The names of the roles, padded with empty names.
+    const NAMES: [&'static str; N];
+}
+
+impl<const N: usize> ConstRoleNames<N> for Nil {
+    const LEN: usize = 0;
+    const NAMES: [&'static str; N] = [""; N];
+}
+
+impl<H: Role, T: ConstRoleNames<N>, const N: usize> ConstRoleNames<N> for Cons<H, T> {
+    const LEN: usize = T::LEN + 1;
+    const NAMES: [&'static str; N] = {
+        let mut names = [ConstRoleName::<H>::NAME; N];
+        let mut i = 1;
+        while i < N {
+            names[i] = T::NAMES[i - 1];
+            i += 1;
+        }
+        names
+    };
+}
+
+/// The names of the roles of a global protocol, as a constant array of `N`
+/// names.
+///
+/// - `N` must be the number of roles; it can be left for the compiler to
+///   infer from the type of the constant, as `RoleNamesOf<_>`. Any other `N`
+///   fails to compile.
+/// - Each role is named once, at its first occurrence, as by
+///   [`UniqueRoles`]; the roles must have `RoleEq` impls against each other.
+/// - Roles are named by [`Role::NAME`], which every role must set; see
+///   [`ConstRoleName`].
+/// - The names are a constant, so they can be printed or checked at startup
+///   without allocating, also without `std`.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Alice; struct Bob;
+/// impl Role for Alice { const NAME: Option<&'static str> = Some("alice"); }
+/// impl Role for Bob   { const NAME: Option<&'static str> = Some("bob"); }
+/// impl RoleEq<Alice> for Alice { type Output = True; }
+/// impl RoleEq<Bob> for Alice   { type Output = False; }
+/// impl RoleEq<Alice> for Bob   { type Output = False; }
+/// impl RoleEq<Bob> for Bob     { type Output = True; }
+///
+/// type PingPong = TMsg<Http, EmptyLabel, Alice, Bob, Message,
+///                 TMsg<Http, EmptyLabel, Bob, Alice, Response, TEnd<Http>>>;
+/// const ROLES: [&str; 2] = <PingPong as RoleNamesOf<_>>::ROLE_NAMES;
+/// assert_eq!(ROLES, ["alice", "bob"]);
+/// ```
+pub trait RoleNamesOf<const N: usize> {
+    /// The names of the roles, in order of first occurrence.
+    const ROLE_NAMES: [&'static str; N];
+}
+
+impl<G, const N: usize> RoleNamesOf<N> for G
+where
+    G: UniqueRoles,
+    <G as UniqueRoles>::Roles: ConstRoleNames<N>,
+{
+    const ROLE_NAMES: [&'static str; N] = {
+        assert!(
+            <<G as UniqueRoles>::Roles as ConstRoleNames<N>>::LEN == N,
+            "`RoleNamesOf<N>` needs `N` to be the number of roles of the protocol"
+        );
+        <<G as UniqueRoles>::Roles as ConstRoleNames<N>>::NAMES
+    };
+}
+
+/// The most roles [`RoleNamesSlice`] can name.
+const ROLE_NAMES_CAPACITY: usize = 64;
+
+/// The names of the roles of a global protocol, as a constant slice.
+///
+/// Like [`RoleNamesOf`], without having to know the number of roles: binaries
+/// can list the roles of any protocol. Protocols of more than 64 roles fail to
+/// compile.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Alice; struct Bob;
+/// impl Role for Alice { const NAME: Option<&'static str> = Some("alice"); }
+/// impl Role for Bob   { const NAME: Option<&'static str> = Some("bob"); }
+/// impl RoleEq<Alice> for Alice { type Output = True; }
+/// impl RoleEq<Bob> for Alice   { type Output = False; }
+/// impl RoleEq<Alice> for Bob   { type Output = False; }
+/// impl RoleEq<Bob> for Bob     { type Output = True; }
+///
+/// type PingPong = TMsg<Http, EmptyLabel, Alice, Bob, Message,
+///                 TMsg<Http, EmptyLabel, Bob, Alice, Response, TEnd<Http>>>;
+/// assert_eq!(<PingPong as RoleNamesSlice>::ROLE_NAMES_SLICE, ["alice", "bob"]);
+/// ```
+pub trait RoleNamesSlice {
+    /// The names of the roles, in order of first occurrence.
+    const ROLE_NAMES_SLICE: &'static [&'static str];
+}
+
+impl<G> RoleNamesSlice for G
+where
+    G: UniqueRoles,
+    <G as UniqueRoles>::Roles: ConstRoleNames<ROLE_NAMES_CAPACITY>,
+{
+    const ROLE_NAMES_SLICE: &'static [&'static str] = {
+        let len = <<G as UniqueRoles>::Roles as ConstRoleNames<ROLE_NAMES_CAPACITY>>::LEN;
+        assert!(
+            len <= ROLE_NAMES_CAPACITY,
+            "`RoleNamesSlice` names at most 64 roles"
+        );
+        let names: &'static [&'static str] =
+            &<<G as UniqueRoles>::Roles as ConstRoleNames<ROLE_NAMES_CAPACITY>>::NAMES;
+        names.split_at(len).0
+    };
+}
+
+impl<IO, Lbl: types::ProtocolLabel> Describe for TEnd<IO, Lbl> {
+    fn describe() -> ProtocolDesc {
+        ProtocolDesc::new(CombinatorKind::End, Some(Lbl::name()))
+    }
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R: Role, H, T: TSession<IO> + Describe> Describe
+    for TInteract<IO, Lbl, R, H, T>
+{
+    fn describe() -> ProtocolDesc {
+        ProtocolDesc {
+            roles: vec![R::name()],
+            messages: vec![type_name::<H>()],
+            children: vec![<T as Describe>::describe()],
+            ..ProtocolDesc::new(CombinatorKind::Interact, Some(Lbl::name()))
+        }
+    }
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From: Role, To: Role, H, T: TSession<IO> + Describe> Describe
+    for TMsg<IO, Lbl, From, To, H, T>
+{
+    fn describe() -> ProtocolDesc {
+        ProtocolDesc {
+            roles: vec![From::name(), To::name()],
+            messages: vec![type_name::<H>()],
+            children: vec![<T as Describe>::describe()],
+            ..ProtocolDesc::new(CombinatorKind::Msg, Some(Lbl::name()))
+        }
+    }
+}
+
+impl<
+        IO,
+        Lbl: types::ProtocolLabel,
+        From: Role,
+        ToSet: RoleNames,
+        H,
+        T: TSession<IO> + Describe,
+    > Describe for TBroadcast<IO, Lbl, From, ToSet, H, T>
+{
+    fn describe() -> ProtocolDesc {
+        ProtocolDesc {
+            roles: {
+                let mut roles = vec![From::name()];
+                <ToSet as RoleNames>::append_names(&mut roles);
+                roles
+            },
+            messages: vec![type_name::<H>()],
+            children: vec![<T as Describe>::describe()],
+            ..ProtocolDesc::new(CombinatorKind::Broadcast, Some(Lbl::name()))
+        }
+    }
+}
+
+impl<
+        IO,
+        Lbl: types::ProtocolLabel,
+        From: Role,
+        WorkerSet: RoleNames,
+        H,
+        T: TSession<IO> + Describe,
+    > Describe for TAnycast<IO, Lbl, From, WorkerSet, H, T>
+{
+    fn describe() -> ProtocolDesc {
+        ProtocolDesc {
+            roles: {
+                let mut roles = vec![From::name()];
+                <WorkerSet as RoleNames>::append_names(&mut roles);
+                roles
+            },
+            messages: vec![type_name::<H>()],
+            children: vec![<T as Describe>::describe()],
+            ..ProtocolDesc::new(CombinatorKind::Anycast, Some(Lbl::name()))
+        }
+    }
+}
+
+impl<
+        IO,
+        Lbl: types::ProtocolLabel,
+        From: Role,
+        To: Role,
+        DelegatedEp,
+        T: TSession<IO> + Describe,
+    > Describe for TDelegate<IO, Lbl, From, To, DelegatedEp, T>
+{
+    fn describe() -> ProtocolDesc {
+        ProtocolDesc {
+            roles: vec![From::name(), To::name()],
+            messages: vec![type_name::<DelegatedEp>()],
+            children: vec![<T as Describe>::describe()],
+            ..ProtocolDesc::new(CombinatorKind::Delegate, Some(Lbl::name()))
+        }
+    }
+}
+
+impl<IO, Lbl: types::ProtocolLabel, A: Role, B: Role, Cont: TSession<IO> + Describe> Describe
+    for TDisconnect<IO, Lbl, A, B, Cont>
+{
+    fn describe() -> ProtocolDesc {
+        ProtocolDesc {
+            roles: vec![A::name(), B::name()],
+            children: vec![<Cont as Describe>::describe()],
+            ..ProtocolDesc::new(CombinatorKind::Disconnect, Some(Lbl::name()))
+        }
+    }
+}
+
+impl<IO, Lbl: types::ProtocolLabel, A: Role, B: Role, Cont: TSession<IO> + Describe> Describe
+    for TConnect<IO, Lbl, A, B, Cont>
+{
+    fn describe() -> ProtocolDesc {
+        ProtocolDesc {
+            roles: vec![A::name(), B::name()],
+            children: vec![<Cont as Describe>::describe()],
+            ..ProtocolDesc::new(CombinatorKind::Connect, Some(Lbl::name()))
+        }
+    }
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R: Role, Cont: TSession<IO> + Describe> Describe
+    for TEndFor<IO, Lbl, R, Cont>
+{
+    fn describe() -> ProtocolDesc {
+        ProtocolDesc {
+            roles: vec![R::name()],
+            children: vec![<Cont as Describe>::describe()],
+            ..ProtocolDesc::new(CombinatorKind::EndFor, Some(Lbl::name()))
+        }
+    }
+}
+
+impl<IO, Lbl: types::ProtocolLabel, L: TSession<IO> + Describe, R: TSession<IO> + Describe> Describe
+    for TChoice<IO, Lbl, L, R>
+{
+    fn describe() -> ProtocolDesc {
+        ProtocolDesc {
+            children: vec![<L as Describe>::describe(), <R as Describe>::describe()],
+            ..ProtocolDesc::new(CombinatorKind::Choice, Some(Lbl::name()))
+        }
+    }
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Branches: SessionList<IO> + DescribeEach> Describe
+    for TChoiceN<IO, Lbl, Branches>
+{
+    fn describe() -> ProtocolDesc {
+        ProtocolDesc {
+            children: {
+                let mut children = Vec::new();
+                <Branches as DescribeEach>::describe_each(&mut children);
+                children
+            },
+            ..ProtocolDesc::new(CombinatorKind::ChoiceN, Some(Lbl::name()))
+        }
+    }
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Chooser: Role, Branches: SessionList<IO> + DescribeEach>
+    Describe for TSelect<IO, Lbl, Chooser, Branches>
+{
+    fn describe() -> ProtocolDesc {
+        ProtocolDesc {
+            roles: vec![Chooser::name()],
+            children: {
+                let mut children = Vec::new();
+                <Branches as DescribeEach>::describe_each(&mut children);
+                children
+            },
+            ..ProtocolDesc::new(CombinatorKind::Select, Some(Lbl::name()))
+        }
+    }
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Offeree: Role, Branches: SessionList<IO> + DescribeEach>
+    Describe for TOffer<IO, Lbl, Offeree, Branches>
+{
+    fn describe() -> ProtocolDesc {
+        ProtocolDesc {
+            roles: vec![Offeree::name()],
+            children: {
+                let mut children = Vec::new();
+                <Branches as DescribeEach>::describe_each(&mut children);
+                children
+            },
+            ..ProtocolDesc::new(CombinatorKind::Offer, Some(Lbl::name()))
+        }
+    }
+}
+
+impl<
+        IO,
+        Lbl: types::ProtocolLabel,
+        High: TSession<IO> + Describe,
+        Low: TSession<IO> + Describe,
+    > Describe for TPriorityChoice<IO, Lbl, High, Low>
+{
+    fn describe() -> ProtocolDesc {
+        ProtocolDesc {
+            children: vec![
+                <High as Describe>::describe(),
+                <Low as Describe>::describe(),
+            ],
+            ..ProtocolDesc::new(CombinatorKind::PriorityChoice, Some(Lbl::name()))
+        }
+    }
+}
+
+impl<IO, Lbl, WeightL, L, WeightR, R> Describe for TProb<IO, Lbl, WeightL, L, WeightR, R>
+where
+    Lbl: types::ProtocolLabel,
+    WeightL: types::WeightMarker,
+    L: TSession<IO> + Describe,
+    WeightR: types::WeightMarker,
+    R: TSession<IO> + Describe,
+{
+    fn describe() -> ProtocolDesc {
+        ProtocolDesc {
+            params: vec![type_name::<WeightL>(), type_name::<WeightR>()],
+            children: vec![<L as Describe>::describe(), <R as Describe>::describe()],
+            ..ProtocolDesc::new(CombinatorKind::Prob, Some(Lbl::name()))
+        }
+    }
+}
+
+impl<
+        IO,
+        Lbl: types::ProtocolLabel,
+        L: TSession<IO> + Describe,
+        R: TSession<IO> + Describe,
+        IsDisjoint,
+    > Describe for TPar<IO, Lbl, L, R, IsDisjoint>
+{
+    fn describe() -> ProtocolDesc {
+        ProtocolDesc {
+            children: vec![<L as Describe>::describe(), <R as Describe>::describe()],
+            ..ProtocolDesc::new(CombinatorKind::Par, Some(Lbl::name()))
+        }
+    }
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Branches: SessionList<IO> + DescribeEach> Describe
+    for TParN<IO, Lbl, Branches>
+{
+    fn describe() -> ProtocolDesc {
+        ProtocolDesc {
+            children: {
+                let mut children = Vec::new();
+                <Branches as DescribeEach>::describe_each(&mut children);
+                children
+            },
+            ..ProtocolDesc::new(CombinatorKind::ParN, Some(Lbl::name()))
+        }
+    }
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Steps: SessionList<IO> + DescribeEach> Describe
+    for TShuffle<IO, Lbl, Steps>
+{
+    fn describe() -> ProtocolDesc {
+        ProtocolDesc {
+            children: {
+                let mut children = Vec::new();
+                <Steps as DescribeEach>::describe_each(&mut children);
+                children
+            },
+            ..ProtocolDesc::new(CombinatorKind::Shuffle, Some(Lbl::name()))
+        }
+    }
+}
+
+impl<IO, Lbl: types::ProtocolLabel, S: TSession<IO> + Describe> Describe for TRec<IO, Lbl, S> {
+    fn describe() -> ProtocolDesc {
+        ProtocolDesc {
+            children: vec![<S as Describe>::describe()],
+            ..ProtocolDesc::new(CombinatorKind::Rec, Some(Lbl::name()))
+        }
+    }
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Var, Body: TSession<IO> + Describe> Describe
+    for TRecX<IO, Lbl, Var, Body>
+{
+    fn describe() -> ProtocolDesc {
+        ProtocolDesc {
+            params: vec![type_name::<Var>()],
+            children: vec![<Body as Describe>::describe()],
+            ..ProtocolDesc::new(CombinatorKind::RecX, Some(Lbl::name()))
+        }
+    }
+}
+
+impl<Var> Describe for TVar<Var> {
+    fn describe() -> ProtocolDesc {
+        ProtocolDesc {
+            params: vec![type_name::<Var>()],
+            ..ProtocolDesc::new(CombinatorKind::Var, None)
+        }
+    }
+}
+
+impl<
+        IO,
+        Lbl: types::ProtocolLabel,
+        Dur: types::DurationMarker,
+        Body: TSession<IO> + Describe,
+        OnTimeout: TSession<IO> + Describe,
+    > Describe for TTimeout<IO, Lbl, Dur, Body, OnTimeout>
+{
+    fn describe() -> ProtocolDesc {
+        ProtocolDesc {
+            params: vec![type_name::<Dur>()],
+            children: vec![
+                <Body as Describe>::describe(),
+                <OnTimeout as Describe>::describe(),
+            ],
+            ..ProtocolDesc::new(CombinatorKind::Timeout, Some(Lbl::name()))
+        }
+    }
+}
+
+impl<
+        IO,
+        Lbl: types::ProtocolLabel,
+        R: Role,
+        Dur: types::DurationMarker,
+        T: TSession<IO> + Describe,
+    > Describe for TDelay<IO, Lbl, R, Dur, T>
+{
+    fn describe() -> ProtocolDesc {
+        ProtocolDesc {
+            roles: vec![R::name()],
+            params: vec![type_name::<Dur>()],
+            children: vec![<T as Describe>::describe()],
+            ..ProtocolDesc::new(CombinatorKind::Delay, Some(Lbl::name()))
+        }
+    }
+}
+
+impl<
+        IO,
+        Lbl: types::ProtocolLabel,
+        R: Role,
+        Dur: types::DurationMarker,
+        T: TSession<IO> + Describe,
+    > Describe for TDeadline<IO, Lbl, R, Dur, T>
+{
+    fn describe() -> ProtocolDesc {
+        ProtocolDesc {
+            roles: vec![R::name()],
+            params: vec![type_name::<Dur>()],
+            children: vec![<T as Describe>::describe()],
+            ..ProtocolDesc::new(CombinatorKind::Deadline, Some(Lbl::name()))
+        }
+    }
+}
+
+impl<
+        IO,
+        Lbl: types::ProtocolLabel,
+        Scope: TSession<IO> + Describe,
+        Interruptor: Role,
+        Handler: TSession<IO> + Describe,
+    > Describe for TInterrupt<IO, Lbl, Scope, Interruptor, Handler>
+{
+    fn describe() -> ProtocolDesc {
+        ProtocolDesc {
+            roles: vec![Interruptor::name()],
+            children: vec![
+                <Scope as Describe>::describe(),
+                <Handler as Describe>::describe(),
+            ],
+            ..ProtocolDesc::new(CombinatorKind::Interrupt, Some(Lbl::name()))
+        }
+    }
+}
+
+impl<
+        IO,
+        Lbl: types::ProtocolLabel,
+        Decider: Role,
+        Body: TSession<IO> + Describe,
+        Cont: TSession<IO> + Describe,
+    > Describe for TOptional<IO, Lbl, Decider, Body, Cont>
+{
+    fn describe() -> ProtocolDesc {
+        ProtocolDesc {
+            roles: vec![Decider::name()],
+            children: vec![
+                <Body as Describe>::describe(),
+                <Cont as Describe>::describe(),
+            ],
+            ..ProtocolDesc::new(CombinatorKind::Optional, Some(Lbl::name()))
+        }
+    }
+}
+
+impl<Pred, Inner: Describe> Describe for TRefine<Pred, Inner> {
+    fn describe() -> ProtocolDesc {
+        ProtocolDesc {
+            params: vec![type_name::<Pred>()],
+            children: vec![<Inner as Describe>::describe()],
+            ..ProtocolDesc::new(CombinatorKind::Refine, None)
+        }
+    }
+}
+
+impl<Meta, Inner: Describe> Describe for TAnnotate<Meta, Inner> {
+    fn describe() -> ProtocolDesc {
+        ProtocolDesc {
+            params: vec![type_name::<Meta>()],
+            children: vec![<Inner as Describe>::describe()],
+            ..ProtocolDesc::new(CombinatorKind::Annotate, None)
+        }
+    }
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R: Role, Err> Describe for TThrow<IO, Lbl, R, Err> {
+    fn describe() -> ProtocolDesc {
+        ProtocolDesc {
+            roles: vec![R::name()],
+            messages: vec![type_name::<Err>()],
+            ..ProtocolDesc::new(CombinatorKind::Throw, Some(Lbl::name()))
+        }
+    }
+}
+
+impl<
+        IO,
+        Lbl: types::ProtocolLabel,
+        Body: TSession<IO> + Describe,
+        Handler: TSession<IO> + Describe,
+    > Describe for TCatch<IO, Lbl, Body, Handler>
+{
+    fn describe() -> ProtocolDesc {
+        ProtocolDesc {
+            children: vec![
+                <Body as Describe>::describe(),
+                <Handler as Describe>::describe(),
+            ],
+            ..ProtocolDesc::new(CombinatorKind::Catch, Some(Lbl::name()))
+        }
+    }
+}
+
+/// Displays a global protocol as indented, Scribble-like text.
+///
+/// Built on [`Describe`]; names are shown without their module paths, empty
+/// labels are left out and `TEnd` prints nothing.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Login; struct Register;
+/// impl ProtocolLabel for Login {} impl ProtocolLabel for Register {}
+/// type Entry = TSelect<Http, EmptyLabel, TClient, tlist!(
+///     TMsg<Http, Login, TClient, TServer, Message, TEnd<Http>>,
+///     TMsg<Http, Register, TClient, TServer, Message, TEnd<Http>>
+/// )>;
+/// assert_eq!(
+///     ProtocolDisplay::<Entry>::new().to_string(),
+///     "choice at TClient {\n    Login(Message) from TClient to TServer;\n} or {\n    Register(Message) from TClient to TServer;\n}"
+/// );
+/// ```
+pub struct ProtocolDisplay<G>(PhantomData<G>);
+
+impl<G> ProtocolDisplay<G> {
+    /// The display of `G`.
+    pub fn new() -> Self {
+        ProtocolDisplay(PhantomData)
+    }
+}
+
+impl<G> Default for ProtocolDisplay<G> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<G: Describe> fmt::Display for ProtocolDisplay<G> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        G::describe().fmt(f)
+    }
+}
+
+impl fmt::Display for ProtocolDesc {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut lines = Vec::new();
+        self.push_lines(0, &mut lines);
+        f.write_str(&lines.join("\n"))
+    }
+}
+
+// Strips the module paths from every name in a type name
+fn short_name(name: &str) -> String {
+    let mut out = String::new();
+    let mut path = String::new();
+    for c in name.chars() {
+        if c.is_alphanumeric() || c == '_' || c == ':' {
+            path.push(c);
+        } else {
+            out.push_str(path.rsplit("::").next().unwrap_or_default());
+            path.clear();
+            out.push(c);
+        }
+    }
+    out.push_str(path.rsplit("::").next().unwrap_or_default());
+    out
+}
+
+fn short_names(names: &[&'static str]) -> String {
+    names
+        .iter()
+        .map(|n| short_name(n))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+impl ProtocolDesc {
+    fn short_label(&self) -> String {
+        match self.label.map(short_name) {
+            Some(label) if label != "EmptyLabel" => label,
+            _ => String::new(),
+        }
+    }
+
+    fn role(&self, i: usize) -> String {
+        short_name(self.roles[i])
+    }
+
+    fn param(&self, i: usize) -> String {
+        short_name(self.params[i])
+    }
+
+    fn message(&self) -> String {
+        format!("{}({})", self.short_label(), short_names(&self.messages))
+    }
+
+    // One `{ ... }` block per child, opened by `headers[0]` and separated by
+    // the other headers
+    fn push_blocks(&self, depth: usize, out: &mut Vec<String>, headers: &[String]) {
+        let pad = "    ".repeat(depth);
+        for (i, (header, child)) in headers.iter().zip(&self.children).enumerate() {
+            if i == 0 {
+                out.push(format!("{pad}{header} {{"));
+            } else {
+                out.push(format!("{pad}}} {header} {{"));
+            }
+            child.push_lines(depth + 1, out);
+        }
+        out.push(format!("{pad}}}"));
+    }
+
+    fn push_branches(&self, depth: usize, out: &mut Vec<String>, header: String, sep: &str) {
+        let mut headers = vec![header];
+        headers.resize(self.children.len().max(1), sep.to_string());
+        self.push_blocks(depth, out, &headers);
+    }
+
+    fn push_step(&self, depth: usize, out: &mut Vec<String>, step: String) {
+        out.push(format!("{}{step}", "    ".repeat(depth)));
+        if let Some(cont) = self.children.last() {
+            cont.push_lines(depth, out);
+        }
+    }
+
+    fn push_lines(&self, depth: usize, out: &mut Vec<String>) {
+        use CombinatorKind::*;
+        match self.kind {
+            End => {}
+            Interact => self.push_step(
+                depth,
+                out,
+                format!("{} at {};", self.message(), self.role(0)),
+            ),
+            Msg => self.push_step(
+                depth,
+                out,
+                format!(
+                    "{} from {} to {};",
+                    self.message(),
+                    self.role(0),
+                    self.role(1)
+                ),
+            ),
+            Broadcast => self.push_step(
+                depth,
+                out,
+                format!(
+                    "{} from {} to {};",
+                    self.message(),
+                    self.role(0),
+                    short_names(&self.roles[1..])
+                ),
+            ),
+            Anycast => self.push_step(
+                depth,
+                out,
+                format!(
+                    "{} from {} to one of {};",
+                    self.message(),
+                    self.role(0),
+                    short_names(&self.roles[1..])
+                ),
+            ),
+            Delegate => self.push_step(
+                depth,
+                out,
+                format!(
+                    "{}(delegate {}) from {} to {};",
+                    self.short_label(),
+                    short_names(&self.messages),
+                    self.role(0),
+                    self.role(1)
+                ),
+            ),
+            Disconnect => self.push_step(
+                depth,
+                out,
+                format!("disconnect {} and {};", self.role(0), self.role(1)),
+            ),
+            Connect => self.push_step(
+                depth,
+                out,
+                format!("connect {} to {};", self.role(0), self.role(1)),
+            ),
+            EndFor => self.push_step(depth, out, format!("end for {};", self.role(0))),
+            Choice | ChoiceN => self.push_branches(depth, out, "choice".to_string(), "or"),
+            Select => self.push_branches(depth, out, format!("choice at {}", self.role(0)), "or"),
+            Offer => self.push_branches(depth, out, format!("offer to {}", self.role(0)), "or"),
+            PriorityChoice => {
+                self.push_branches(depth, out, "choice by priority".to_string(), "or")
+            }
+            Prob => self.push_blocks(
+                depth,
+                out,
+                &[
+                    format!("choice weighted {}", self.param(0)),
+                    format!("or weighted {}", self.param(1)),
+                ],
+            ),
+            Par | ParN => self.push_branches(depth, out, "par".to_string(), "and"),
+            Shuffle => self.push_branches(depth, out, "shuffle".to_string(), "and"),
+            Rec => {
+                let header = format!("rec {}", self.short_label());
+                self.push_blocks(depth, out, &[header.trim_end().to_string()])
+            }
+            RecX => self.push_blocks(depth, out, &[format!("rec {}", self.param(0))]),
+            Var => out.push(format!(
+                "{}continue {};",
+                "    ".repeat(depth),
+                self.param(0)
+            )),
+            Timeout => self.push_blocks(
+                depth,
+                out,
+                &[
+                    format!("timeout {}", self.param(0)),
+                    "on timeout".to_string(),
+                ],
+            ),
+            Delay => self.push_step(
+                depth,
+                out,
+                format!("delay {} at {};", self.param(0), self.role(0)),
+            ),
+            Deadline => self.push_blocks(
+                depth,
+                out,
+                &[format!("within {} at {}", self.param(0), self.role(0))],
+            ),
+            Interrupt => self.push_blocks(
+                depth,
+                out,
+                &[
+                    format!("interruptible by {}", self.role(0)),
+                    "on interrupt".to_string(),
+                ],
+            ),
+            Optional => {
+                self.push_blocks(depth, out, &[format!("optional at {}", self.role(0))]);
+                self.children[1].push_lines(depth, out);
+            }
+            Refine => self.push_blocks(depth, out, &[format!("refine {}", self.param(0))]),
+            Annotate => self.push_step(depth, out, format!("@{}", self.param(0))),
+            Throw => self.push_step(
+                depth,
+                out,
+                format!("throw {} at {};", self.message(), self.role(0)),
+            ),
+            Catch => self.push_blocks(depth, out, &["try".to_string(), "catch".to_string()]),
+        }
+    }
+}
@@ -0,0 +1,240 @@
+//! # Determinism Checks
+//!
+//! This module checks that a runtime receiving a frame can always tell which
+//! branch of an offer it belongs to: the sibling branches of every offer
+//! start with pairwise distinct labels.
+//!
+//! Key components:
+//!
+//! - `Deterministic`: Every offer of a local session type dispatches on distinct labels
+//! - `DistinctFirstLabels`: The branches of an offer start with pairwise distinct labels
+//! - `NoneStartsWith` / `NoneStartsWithCase`: No branch of a list starts with a given label
+//! - `AmbiguousOffer` / `DeterminismFailure`: Failure markers naming the offer and the label
+//!
+//! Offers are `EpOffer`, `EpPriorityOffer` and `EpOptionalOffer`; the first
+//! label of a branch is its `GetLocalLabel`. Global protocols are checked
+//! through their projections, with
+//! [`assert_deterministic!`](crate::assert_deterministic). A delegated
+//! endpoint belongs to another session and is not checked.
+
+use super::base::{Cons, Nil};
+use super::local::*;
+use super::transforms::GetLocalLabel;
+use crate::types;
+use core::marker::PhantomData;
+
+/// Failure marker: two branches of the offer labelled `Offer` both start
+/// with the label `Lbl`.
+pub struct AmbiguousOffer<Offer, Lbl>(PhantomData<(Offer, Lbl)>);
+
+/// Never implemented: a bound `Marker: DeterminismFailure` fails and names
+/// the ambiguous offer found by [`Deterministic`].
+#[diagnostic::on_unimplemented(
+    message = "the branches of an offer cannot be told apart: `{Self}`",
+    label = "two branches start with the same label",
+    note = "start every branch of an offer with a message of its own label"
+)]
+pub trait DeterminismFailure {}
+
+/// No local session type of a type-level list starts with the label `Lbl`,
+/// already used by a sibling branch of the offer labelled `Offer`.
+pub trait NoneStartsWith<Offer, Lbl> {}
+
+impl<Offer, Lbl> NoneStartsWith<Offer, Lbl> for Nil {}
+
+impl<H, T, Offer, Lbl> NoneStartsWith<Offer, Lbl> for Cons<H, T>
+where
+    H: GetLocalLabel,
+    <H as GetLocalLabel>::Label: LabelEq<Lbl>,
+    (): NoneStartsWithCase<Offer, Lbl, <<H as GetLocalLabel>::Label as LabelEq<Lbl>>::Output>,
+    T: NoneStartsWith<Offer, Lbl>,
+{
+}
+
+/// Helper trait for `NoneStartsWith`, dispatching on whether a branch starts
+/// with the label.
+pub trait NoneStartsWithCase<Offer, Lbl, Same> {}
+
+impl<Offer, Lbl> NoneStartsWithCase<Offer, Lbl, types::False> for () {}
+
+impl<Offer, Lbl> NoneStartsWithCase<Offer, Lbl, types::True> for () where
+    AmbiguousOffer<Offer, Lbl>: DeterminismFailure
+{
+}
+
+/// The branches of the offer labelled `Offer` start with pairwise distinct
+/// labels.
+pub trait DistinctFirstLabels<Offer> {}
+
+impl<Offer> DistinctFirstLabels<Offer> for Nil {}
+
+impl<H, T, Offer> DistinctFirstLabels<Offer> for Cons<H, T>
+where
+    H: GetLocalLabel,
+    T: NoneStartsWith<Offer, <H as GetLocalLabel>::Label> + DistinctFirstLabels<Offer>,
+{
+}
+
+/// A local session type whose every offer can be dispatched on the label of
+/// the frame received.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Get; struct Put; struct Api;
+/// impl ProtocolLabel for Get {} impl ProtocolLabel for Put {} impl ProtocolLabel for Api {}
+/// impl LabelEq<Get> for Get { type Output = True; }
+/// impl LabelEq<Put> for Get { type Output = False; }
+/// impl LabelEq<Get> for Put { type Output = False; }
+/// impl LabelEq<Put> for Put { type Output = True; }
+///
+/// type Server = EpOffer<Http, Api, TServer, tlist!(
+///     EpRecv<Http, Get, TServer, Message, EpEnd<Http, EmptyLabel, TServer>>,
+///     EpRecv<Http, Put, TServer, Message, EpEnd<Http, EmptyLabel, TServer>>,
+/// )>;
+/// assert_deterministic!(Server);
+/// ```
+pub trait Deterministic {}
+
+impl<IO, Lbl: types::ProtocolLabel, R, H, T: Deterministic> Deterministic
+    for EpSend<IO, Lbl, R, H, T>
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R, H, T: Deterministic> Deterministic
+    for EpRecv<IO, Lbl, R, H, T>
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, ToSet, H, T: Deterministic> Deterministic
+    for EpBroadcast<IO, Lbl, Me, ToSet, H, T>
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, FromSet, H, T: Deterministic> Deterministic
+    for EpGather<IO, Lbl, Me, FromSet, H, T>
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, DelegatedEp, Cont: Deterministic> Deterministic
+    for EpDelegate<IO, Lbl, Me, DelegatedEp, Cont>
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R> Deterministic for EpEnd<IO, Lbl, R> {}
+
+impl<IO, Lbl: types::ProtocolLabel, R> Deterministic for EpSkip<IO, Lbl, R> {}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches: Deterministic> Deterministic
+    for EpChoiceN<IO, Lbl, Me, Branches>
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches: Deterministic> Deterministic
+    for EpSelect<IO, Lbl, Me, Branches>
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches> Deterministic for EpOffer<IO, Lbl, Me, Branches> where
+    Branches: Deterministic + DistinctFirstLabels<Lbl>
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, High, Low> Deterministic
+    for EpPriorityOffer<IO, Lbl, Me, High, Low>
+where
+    High: Deterministic,
+    Low: Deterministic,
+    Cons<High, Cons<Low, Nil>>: DistinctFirstLabels<Lbl>,
+{
+}
+
+// The offeree receives either the first message of the body or that of the
+// continuation
+impl<IO, Lbl: types::ProtocolLabel, Me, Body, Cont> Deterministic
+    for EpOptionalOffer<IO, Lbl, Me, Body, Cont>
+where
+    Body: Deterministic,
+    Cont: Deterministic,
+    Cons<Body, Cons<Cont, Nil>>: DistinctFirstLabels<Lbl>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Body: Deterministic, Cont: Deterministic> Deterministic
+    for EpOptionalSelect<IO, Lbl, Me, Body, Cont>
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, L: Deterministic, R: Deterministic> Deterministic
+    for EpPar<IO, Lbl, Me, L, R>
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches: Deterministic> Deterministic
+    for EpParN<IO, Lbl, Me, Branches>
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Steps: Deterministic> Deterministic
+    for EpShuffle<IO, Lbl, Me, Steps>
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Var, Body: Deterministic> Deterministic
+    for EpRec<IO, Lbl, Var, Body>
+{
+}
+
+impl<Var> Deterministic for EpVar<Var> {}
+
+impl<IO, Lbl, Me, Dur, Body, OnTimeout> Deterministic
+    for EpTimeout<IO, Lbl, Me, Dur, Body, OnTimeout>
+where
+    Lbl: types::ProtocolLabel,
+    Body: Deterministic,
+    OnTimeout: Deterministic,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Dur, T: Deterministic> Deterministic
+    for EpDelay<IO, Lbl, Me, Dur, T>
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Dur, T: Deterministic> Deterministic
+    for EpDeadline<IO, Lbl, Me, Dur, T>
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Scope: Deterministic, Handler: Deterministic> Deterministic
+    for EpInterrupt<IO, Lbl, Me, Scope, Handler>
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Scope: Deterministic, Handler: Deterministic> Deterministic
+    for EpInterruptible<IO, Lbl, Me, Scope, Handler>
+{
+}
+
+impl<Pred, Inner: Deterministic> Deterministic for EpRefine<Pred, Inner> {}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Err> Deterministic for EpThrow<IO, Lbl, Me, Err> {}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Body: Deterministic, Handler: Deterministic> Deterministic
+    for EpCatch<IO, Lbl, Me, Body, Handler>
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Peer, Cont: Deterministic> Deterministic
+    for EpClose<IO, Lbl, Me, Peer, Cont>
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Peer, Cont: Deterministic> Deterministic
+    for EpOpen<IO, Lbl, Me, Peer, Cont>
+{
+}
+
+impl Deterministic for Nil {}
+
+impl<H: Deterministic, T: Deterministic> Deterministic for Cons<H, T> {}
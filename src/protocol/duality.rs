@@ -0,0 +1,276 @@
+//! # Duality
+//!
+//! This module computes the dual of a local session type: the endpoint type
+//! of the peer in a two-party session. Every send becomes a receive and vice
+//! versa, every choice this role makes becomes one the peer offers, and all
+//! labels are preserved.
+//!
+//! Key components:
+//!
+//! - `Dual`: The dual of a local session type, as seen by a peer role
+//! - `DualEach`: The duals of every local session type in a type-level list
+//! - `DualityHolds`: The projections of a two-party protocol are duals of each other
+//! - `MatchesDual`: Helper trait comparing a projection with the expected dual
+//!
+//! Timing constraints (`EpDelay`, `EpDeadline`) and failures (`EpThrow`,
+//! `EpCatch`) belong to a single role and have no dual.
+
+use super::base::{Cons, Nil};
+use super::global::TSession;
+use super::local::*;
+use super::normalize::Normalize;
+use super::transforms::ProjectRole;
+use crate::introspection::CoversRoles;
+use crate::types;
+
+/// The dual of a local session type, as seen by `Peer`.
+///
+/// `Output` is the endpoint type `Peer` must follow to talk to the role this
+/// endpoint type belongs to: `EpSend` and `EpRecv` are swapped, `EpBroadcast`
+/// and `EpGather` become a single `EpRecv` and `EpSend` with that peer,
+/// `EpDelegate` becomes an `EpRecv` of the delegated endpoint, `EpChoiceN`,
+/// `EpSelect` and `EpOptionalSelect` become offers, `EpOffer` and
+/// `EpOptionalOffer` become selections, and `EpEnd` stays `EpEnd`. Labels and
+/// payloads are kept unchanged.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Ping; impl ProtocolLabel for Ping {}
+/// type Client = EpSend<Http, Ping, TClient, Message, EpEnd<Http, EmptyLabel, TClient>>;
+/// assert_type_eq!(
+///     <Client as Dual<TServer>>::Output,
+///     EpRecv<Http, Ping, TServer, Message, EpEnd<Http, EmptyLabel, TServer>>
+/// );
+/// ```
+pub trait Dual<Peer> {
+    type Output;
+}
+
+/// The duals of every local session type in a type-level list, as seen by `Peer`.
+pub trait DualEach<Peer> {
+    type Output;
+}
+
+impl<Peer> DualEach<Peer> for Nil {
+    type Output = Nil;
+}
+
+impl<Peer, H: Dual<Peer>, T: DualEach<Peer>> DualEach<Peer> for Cons<H, T> {
+    type Output = Cons<<H as Dual<Peer>>::Output, <T as DualEach<Peer>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R, H, T: Dual<Peer>, Peer> Dual<Peer>
+    for EpSend<IO, Lbl, R, H, T>
+{
+    type Output = EpRecv<IO, Lbl, Peer, H, <T as Dual<Peer>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R, H, T: Dual<Peer>, Peer> Dual<Peer>
+    for EpRecv<IO, Lbl, R, H, T>
+{
+    type Output = EpSend<IO, Lbl, Peer, H, <T as Dual<Peer>>::Output>;
+}
+
+// From a single peer's point of view a fan-out is one receive, and a fan-in one send
+impl<IO, Lbl: types::ProtocolLabel, Me, ToSet, H, T: Dual<Peer>, Peer> Dual<Peer>
+    for EpBroadcast<IO, Lbl, Me, ToSet, H, T>
+{
+    type Output = EpRecv<IO, Lbl, Peer, H, <T as Dual<Peer>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, FromSet, H, T: Dual<Peer>, Peer> Dual<Peer>
+    for EpGather<IO, Lbl, Me, FromSet, H, T>
+{
+    type Output = EpSend<IO, Lbl, Peer, H, <T as Dual<Peer>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, DelegatedEp, Cont: Dual<Peer>, Peer> Dual<Peer>
+    for EpDelegate<IO, Lbl, Me, DelegatedEp, Cont>
+{
+    type Output = EpRecv<IO, Lbl, Peer, DelegatedEp, <Cont as Dual<Peer>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R, Peer> Dual<Peer> for EpEnd<IO, Lbl, R> {
+    type Output = EpEnd<IO, Lbl, Peer>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R, Peer> Dual<Peer> for EpSkip<IO, Lbl, R> {
+    type Output = EpSkip<IO, Lbl, Peer>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches: DualEach<Peer>, Peer> Dual<Peer>
+    for EpChoiceN<IO, Lbl, Me, Branches>
+{
+    type Output = EpOffer<IO, Lbl, Peer, <Branches as DualEach<Peer>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches: DualEach<Peer>, Peer> Dual<Peer>
+    for EpSelect<IO, Lbl, Me, Branches>
+{
+    type Output = EpOffer<IO, Lbl, Peer, <Branches as DualEach<Peer>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches: DualEach<Peer>, Peer> Dual<Peer>
+    for EpOffer<IO, Lbl, Me, Branches>
+{
+    type Output = EpSelect<IO, Lbl, Peer, <Branches as DualEach<Peer>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Body: Dual<Peer>, Cont: Dual<Peer>, Peer> Dual<Peer>
+    for EpOptionalSelect<IO, Lbl, Me, Body, Cont>
+{
+    type Output =
+        EpOptionalOffer<IO, Lbl, Peer, <Body as Dual<Peer>>::Output, <Cont as Dual<Peer>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Body: Dual<Peer>, Cont: Dual<Peer>, Peer> Dual<Peer>
+    for EpOptionalOffer<IO, Lbl, Me, Body, Cont>
+{
+    type Output =
+        EpOptionalSelect<IO, Lbl, Peer, <Body as Dual<Peer>>::Output, <Cont as Dual<Peer>>::Output>;
+}
+
+// Both ends of a prioritized choice see the same preference
+impl<IO, Lbl: types::ProtocolLabel, Me, High: Dual<Peer>, Low: Dual<Peer>, Peer> Dual<Peer>
+    for EpPriorityOffer<IO, Lbl, Me, High, Low>
+{
+    type Output =
+        EpPriorityOffer<IO, Lbl, Peer, <High as Dual<Peer>>::Output, <Low as Dual<Peer>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, L: Dual<Peer>, R: Dual<Peer>, Peer> Dual<Peer>
+    for EpPar<IO, Lbl, Me, L, R>
+{
+    type Output = EpPar<IO, Lbl, Peer, <L as Dual<Peer>>::Output, <R as Dual<Peer>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches: DualEach<Peer>, Peer> Dual<Peer>
+    for EpParN<IO, Lbl, Me, Branches>
+{
+    type Output = EpParN<IO, Lbl, Peer, <Branches as DualEach<Peer>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Steps: DualEach<Peer>, Peer> Dual<Peer>
+    for EpShuffle<IO, Lbl, Me, Steps>
+{
+    type Output = EpShuffle<IO, Lbl, Peer, <Steps as DualEach<Peer>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Var, Body: Dual<Peer>, Peer> Dual<Peer>
+    for EpRec<IO, Lbl, Var, Body>
+{
+    type Output = EpRec<IO, Lbl, Var, <Body as Dual<Peer>>::Output>;
+}
+
+impl<Var, Peer> Dual<Peer> for EpVar<Var> {
+    type Output = EpVar<Var>;
+}
+
+// Both ends arm the same timer
+impl<IO, Lbl, Me, Dur, Body, OnTimeout, Peer> Dual<Peer>
+    for EpTimeout<IO, Lbl, Me, Dur, Body, OnTimeout>
+where
+    Lbl: types::ProtocolLabel,
+    Body: Dual<Peer>,
+    OnTimeout: Dual<Peer>,
+{
+    type Output = EpTimeout<
+        IO,
+        Lbl,
+        Peer,
+        Dur,
+        <Body as Dual<Peer>>::Output,
+        <OnTimeout as Dual<Peer>>::Output,
+    >;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Scope: Dual<Peer>, Handler: Dual<Peer>, Peer> Dual<Peer>
+    for EpInterrupt<IO, Lbl, Me, Scope, Handler>
+{
+    type Output = EpInterruptible<
+        IO,
+        Lbl,
+        Peer,
+        <Scope as Dual<Peer>>::Output,
+        <Handler as Dual<Peer>>::Output,
+    >;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Scope: Dual<Peer>, Handler: Dual<Peer>, Peer> Dual<Peer>
+    for EpInterruptible<IO, Lbl, Me, Scope, Handler>
+{
+    type Output =
+        EpInterrupt<IO, Lbl, Peer, <Scope as Dual<Peer>>::Output, <Handler as Dual<Peer>>::Output>;
+}
+
+impl<Pred, Inner: Dual<Peer>, Peer> Dual<Peer> for EpRefine<Pred, Inner> {
+    type Output = EpRefine<Pred, <Inner as Dual<Peer>>::Output>;
+}
+
+// Closing and opening a channel are only dual from the other end of that channel
+impl<IO, Lbl: types::ProtocolLabel, Me, Peer, Cont: Dual<Peer>> Dual<Peer>
+    for EpClose<IO, Lbl, Me, Peer, Cont>
+{
+    type Output = EpClose<IO, Lbl, Peer, Me, <Cont as Dual<Peer>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Peer, Cont: Dual<Peer>> Dual<Peer>
+    for EpOpen<IO, Lbl, Me, Peer, Cont>
+{
+    type Output = EpOpen<IO, Lbl, Peer, Me, <Cont as Dual<Peer>>::Output>;
+}
+
+/// A local session type that is exactly `Expected`, the dual of the other
+/// role's projection.
+#[diagnostic::on_unimplemented(
+    message = "the projections are not dual: found `{Self}`, expected `{Expected}`",
+    label = "projection of the second role",
+    note = "the dual of the first role's projection differs from the second role's projection"
+)]
+pub trait MatchesDual<Expected> {}
+
+impl<T> MatchesDual<T> for T {}
+
+/// A two-party global protocol between `A` and `B` whose projections are
+/// duals of each other.
+///
+/// The protocol must use exactly the roles `A` and `B`, and the [`Dual`] of
+/// `A`'s projection, seen by `B`, must be `B`'s projection once both are
+/// normalized. This is a sanity check of both the protocol and the
+/// projection machinery. Check a protocol with
+/// [`assert_dual!`](crate::assert_dual).
+///
+/// The comparison is exact, labels included. The role that does not own a
+/// `TChoice` merges its branches into an offer labelled by the first branch,
+/// while the owner selects under the choice's label; write two-party choices
+/// as `TSelect`, which labels both sides alike.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Alice; struct Bob; struct Ping; struct Pong;
+/// impl Role for Alice {} impl Role for Bob {}
+/// impl ProtocolLabel for Ping {} impl ProtocolLabel for Pong {}
+/// impl RoleEq<Alice> for Alice { type Output = True; }
+/// impl RoleEq<Bob> for Alice   { type Output = False; }
+/// impl RoleEq<Alice> for Bob   { type Output = False; }
+/// impl RoleEq<Bob> for Bob     { type Output = True; }
+///
+/// type G = TMsg<Http, Ping, Alice, Bob, Message,
+///          TMsg<Http, Pong, Bob, Alice, Response, TEnd<Http>>>;
+/// assert_dual!(Http; G, Alice, Bob);
+/// ```
+pub trait DualityHolds<IO, A, B>: TSession<IO> {}
+
+impl<IO, A, B, G> DualityHolds<IO, A, B> for G
+where
+    G: TSession<IO> + CoversRoles<Cons<A, Cons<B, Nil>>>,
+    (): ProjectRole<A, IO, G> + ProjectRole<B, IO, G>,
+    <() as ProjectRole<A, IO, G>>::Out: Normalize,
+    <() as ProjectRole<B, IO, G>>::Out: Normalize,
+    <<() as ProjectRole<A, IO, G>>::Out as Normalize>::Output: Dual<B>,
+    <<() as ProjectRole<B, IO, G>>::Out as Normalize>::Output:
+        MatchesDual<<<<() as ProjectRole<A, IO, G>>::Out as Normalize>::Output as Dual<B>>::Output>,
+{
+}
@@ -0,0 +1,408 @@
+//! # Structural Equivalence
+//!
+//! This module compares global protocols structurally, ignoring their
+//! labels, so that renaming labels does not break equality assertions
+//! between protocols.
+//!
+//! Key components:
+//!
+//! - `EraseLabels`: A global protocol with every label replaced by `EmptyLabel`
+//! - `EraseLabelsEach`: The same for every protocol of a type-level list
+//! - `CompareEnds` / `IgnoreEnds`: Whether the labels of `TEnd` still count
+//! - `EndLabel`: The label an end keeps under either option
+//! - `EquivalentTo` / `SameStructure`: Two protocols agree once their labels are erased
+//!
+//! Only labels are erased: roles, payloads, durations, weights, predicates,
+//! annotations and recursion variables are compared as written.
+
+use super::base::{Cons, Nil};
+use super::global::*;
+use crate::types;
+
+/// Equivalence option: the labels of `TEnd` are compared.
+pub struct CompareEnds;
+
+/// Equivalence option: the labels of `TEnd` are ignored as well.
+pub struct IgnoreEnds;
+
+/// The label a `TEnd` labelled `Lbl` keeps when its labels are erased.
+pub trait EndLabel<Lbl> {
+    type Label;
+}
+
+impl<Lbl> EndLabel<Lbl> for CompareEnds {
+    type Label = Lbl;
+}
+
+impl<Lbl> EndLabel<Lbl> for IgnoreEnds {
+    type Label = types::EmptyLabel;
+}
+
+/// A global protocol with every label replaced by `EmptyLabel`, and the
+/// labels of `TEnd` kept or erased as `Ends` says.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Hello; impl ProtocolLabel for Hello {}
+/// type G = TMsg<Http, Hello, TClient, TServer, Message, TEnd<Http>>;
+/// assert_type_eq!(
+///     <G as EraseLabels<Http, CompareEnds>>::Output,
+///     TMsg<Http, EmptyLabel, TClient, TServer, Message, TEnd<Http>>
+/// );
+/// ```
+pub trait EraseLabels<IO, Ends> {
+    type Output: TSession<IO>;
+}
+
+/// The protocols of a type-level list, each with its labels erased.
+pub trait EraseLabelsEach<IO, Ends> {
+    type Output: SessionList<IO>;
+}
+
+impl<IO, Ends> EraseLabelsEach<IO, Ends> for Nil {
+    type Output = Nil;
+}
+
+impl<IO, Ends, H: EraseLabels<IO, Ends>, T: EraseLabelsEach<IO, Ends>> EraseLabelsEach<IO, Ends>
+    for Cons<H, T>
+{
+    type Output =
+        Cons<<H as EraseLabels<IO, Ends>>::Output, <T as EraseLabelsEach<IO, Ends>>::Output>;
+}
+
+impl<IO, Lbl, Ends: EndLabel<Lbl>> EraseLabels<IO, Ends> for TEnd<IO, Lbl> {
+    type Output = TEnd<IO, <Ends as EndLabel<Lbl>>::Label>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R, H, T, Ends> EraseLabels<IO, Ends>
+    for TInteract<IO, Lbl, R, H, T>
+where
+    T: TSession<IO> + EraseLabels<IO, Ends>,
+{
+    type Output = TInteract<IO, types::EmptyLabel, R, H, <T as EraseLabels<IO, Ends>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, To, H, T, Ends> EraseLabels<IO, Ends>
+    for TMsg<IO, Lbl, From, To, H, T>
+where
+    T: TSession<IO> + EraseLabels<IO, Ends>,
+{
+    type Output = TMsg<IO, types::EmptyLabel, From, To, H, <T as EraseLabels<IO, Ends>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, ToSet, H, T, Ends> EraseLabels<IO, Ends>
+    for TBroadcast<IO, Lbl, From, ToSet, H, T>
+where
+    T: TSession<IO> + EraseLabels<IO, Ends>,
+{
+    type Output =
+        TBroadcast<IO, types::EmptyLabel, From, ToSet, H, <T as EraseLabels<IO, Ends>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, WorkerSet, H, T, Ends> EraseLabels<IO, Ends>
+    for TAnycast<IO, Lbl, From, WorkerSet, H, T>
+where
+    T: TSession<IO> + EraseLabels<IO, Ends>,
+{
+    type Output =
+        TAnycast<IO, types::EmptyLabel, From, WorkerSet, H, <T as EraseLabels<IO, Ends>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, To, DelegatedEp, T, Ends> EraseLabels<IO, Ends>
+    for TDelegate<IO, Lbl, From, To, DelegatedEp, T>
+where
+    T: TSession<IO> + EraseLabels<IO, Ends>,
+{
+    type Output = TDelegate<
+        IO,
+        types::EmptyLabel,
+        From,
+        To,
+        DelegatedEp,
+        <T as EraseLabels<IO, Ends>>::Output,
+    >;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, A, B, Cont, Ends> EraseLabels<IO, Ends>
+    for TDisconnect<IO, Lbl, A, B, Cont>
+where
+    Cont: TSession<IO> + EraseLabels<IO, Ends>,
+{
+    type Output = TDisconnect<IO, types::EmptyLabel, A, B, <Cont as EraseLabels<IO, Ends>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, A, B, Cont, Ends> EraseLabels<IO, Ends>
+    for TConnect<IO, Lbl, A, B, Cont>
+where
+    Cont: TSession<IO> + EraseLabels<IO, Ends>,
+{
+    type Output = TConnect<IO, types::EmptyLabel, A, B, <Cont as EraseLabels<IO, Ends>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R, Cont, Ends> EraseLabels<IO, Ends>
+    for TEndFor<IO, Lbl, R, Cont>
+where
+    Cont: TSession<IO> + EraseLabels<IO, Ends>,
+{
+    type Output = TEndFor<IO, types::EmptyLabel, R, <Cont as EraseLabels<IO, Ends>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, L, R, Ends> EraseLabels<IO, Ends> for TChoice<IO, Lbl, L, R>
+where
+    L: TSession<IO> + EraseLabels<IO, Ends>,
+    R: TSession<IO> + EraseLabels<IO, Ends>,
+{
+    type Output = TChoice<
+        IO,
+        types::EmptyLabel,
+        <L as EraseLabels<IO, Ends>>::Output,
+        <R as EraseLabels<IO, Ends>>::Output,
+    >;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Branches, Ends> EraseLabels<IO, Ends>
+    for TChoiceN<IO, Lbl, Branches>
+where
+    Branches: SessionList<IO> + EraseLabelsEach<IO, Ends>,
+{
+    type Output = TChoiceN<IO, types::EmptyLabel, <Branches as EraseLabelsEach<IO, Ends>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Chooser, Branches, Ends> EraseLabels<IO, Ends>
+    for TSelect<IO, Lbl, Chooser, Branches>
+where
+    Branches: SessionList<IO> + EraseLabelsEach<IO, Ends>,
+{
+    type Output =
+        TSelect<IO, types::EmptyLabel, Chooser, <Branches as EraseLabelsEach<IO, Ends>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Offeree, Branches, Ends> EraseLabels<IO, Ends>
+    for TOffer<IO, Lbl, Offeree, Branches>
+where
+    Branches: SessionList<IO> + EraseLabelsEach<IO, Ends>,
+{
+    type Output =
+        TOffer<IO, types::EmptyLabel, Offeree, <Branches as EraseLabelsEach<IO, Ends>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, High, Low, Ends> EraseLabels<IO, Ends>
+    for TPriorityChoice<IO, Lbl, High, Low>
+where
+    High: TSession<IO> + EraseLabels<IO, Ends>,
+    Low: TSession<IO> + EraseLabels<IO, Ends>,
+{
+    type Output = TPriorityChoice<
+        IO,
+        types::EmptyLabel,
+        <High as EraseLabels<IO, Ends>>::Output,
+        <Low as EraseLabels<IO, Ends>>::Output,
+    >;
+}
+
+impl<IO, Lbl, WeightL, L, WeightR, R, Ends> EraseLabels<IO, Ends>
+    for TProb<IO, Lbl, WeightL, L, WeightR, R>
+where
+    Lbl: types::ProtocolLabel,
+    WeightL: types::WeightMarker,
+    WeightR: types::WeightMarker,
+    L: TSession<IO> + EraseLabels<IO, Ends>,
+    R: TSession<IO> + EraseLabels<IO, Ends>,
+{
+    type Output = TProb<
+        IO,
+        types::EmptyLabel,
+        WeightL,
+        <L as EraseLabels<IO, Ends>>::Output,
+        WeightR,
+        <R as EraseLabels<IO, Ends>>::Output,
+    >;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, L, R, IsDisjoint, Ends> EraseLabels<IO, Ends>
+    for TPar<IO, Lbl, L, R, IsDisjoint>
+where
+    L: TSession<IO> + EraseLabels<IO, Ends>,
+    R: TSession<IO> + EraseLabels<IO, Ends>,
+{
+    type Output = TPar<
+        IO,
+        types::EmptyLabel,
+        <L as EraseLabels<IO, Ends>>::Output,
+        <R as EraseLabels<IO, Ends>>::Output,
+        IsDisjoint,
+    >;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Branches, Ends> EraseLabels<IO, Ends>
+    for TParN<IO, Lbl, Branches>
+where
+    Branches: SessionList<IO> + EraseLabelsEach<IO, Ends>,
+{
+    type Output = TParN<IO, types::EmptyLabel, <Branches as EraseLabelsEach<IO, Ends>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Steps, Ends> EraseLabels<IO, Ends> for TShuffle<IO, Lbl, Steps>
+where
+    Steps: SessionList<IO> + EraseLabelsEach<IO, Ends>,
+{
+    type Output = TShuffle<IO, types::EmptyLabel, <Steps as EraseLabelsEach<IO, Ends>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, S, Ends> EraseLabels<IO, Ends> for TRec<IO, Lbl, S>
+where
+    S: TSession<IO> + EraseLabels<IO, Ends>,
+{
+    type Output = TRec<IO, types::EmptyLabel, <S as EraseLabels<IO, Ends>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Var, Body, Ends> EraseLabels<IO, Ends>
+    for TRecX<IO, Lbl, Var, Body>
+where
+    Body: TSession<IO> + EraseLabels<IO, Ends>,
+{
+    type Output = TRecX<IO, types::EmptyLabel, Var, <Body as EraseLabels<IO, Ends>>::Output>;
+}
+
+impl<IO, Var, Ends> EraseLabels<IO, Ends> for TVar<Var> {
+    type Output = TVar<Var>;
+}
+
+impl<IO, Lbl, Dur, Body, OnTimeout, Ends> EraseLabels<IO, Ends>
+    for TTimeout<IO, Lbl, Dur, Body, OnTimeout>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    Body: TSession<IO> + EraseLabels<IO, Ends>,
+    OnTimeout: TSession<IO> + EraseLabels<IO, Ends>,
+{
+    type Output = TTimeout<
+        IO,
+        types::EmptyLabel,
+        Dur,
+        <Body as EraseLabels<IO, Ends>>::Output,
+        <OnTimeout as EraseLabels<IO, Ends>>::Output,
+    >;
+}
+
+impl<IO, Lbl, R, Dur, T, Ends> EraseLabels<IO, Ends> for TDelay<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: TSession<IO> + EraseLabels<IO, Ends>,
+{
+    type Output = TDelay<IO, types::EmptyLabel, R, Dur, <T as EraseLabels<IO, Ends>>::Output>;
+}
+
+impl<IO, Lbl, R, Dur, T, Ends> EraseLabels<IO, Ends> for TDeadline<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: TSession<IO> + EraseLabels<IO, Ends>,
+{
+    type Output = TDeadline<IO, types::EmptyLabel, R, Dur, <T as EraseLabels<IO, Ends>>::Output>;
+}
+
+impl<IO, Lbl, Scope, Interruptor, Handler, Ends> EraseLabels<IO, Ends>
+    for TInterrupt<IO, Lbl, Scope, Interruptor, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Scope: TSession<IO> + EraseLabels<IO, Ends>,
+    Handler: TSession<IO> + EraseLabels<IO, Ends>,
+{
+    type Output = TInterrupt<
+        IO,
+        types::EmptyLabel,
+        <Scope as EraseLabels<IO, Ends>>::Output,
+        Interruptor,
+        <Handler as EraseLabels<IO, Ends>>::Output,
+    >;
+}
+
+impl<IO, Lbl, Decider, Body, Cont, Ends> EraseLabels<IO, Ends>
+    for TOptional<IO, Lbl, Decider, Body, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Body: TSession<IO> + EraseLabels<IO, Ends>,
+    Cont: TSession<IO> + EraseLabels<IO, Ends>,
+{
+    type Output = TOptional<
+        IO,
+        types::EmptyLabel,
+        Decider,
+        <Body as EraseLabels<IO, Ends>>::Output,
+        <Cont as EraseLabels<IO, Ends>>::Output,
+    >;
+}
+
+impl<IO, Pred: types::Predicate, Inner, Ends> EraseLabels<IO, Ends> for TRefine<Pred, Inner>
+where
+    Inner: TSession<IO> + EraseLabels<IO, Ends>,
+{
+    type Output = TRefine<Pred, <Inner as EraseLabels<IO, Ends>>::Output>;
+}
+
+impl<IO, Meta, Inner, Ends> EraseLabels<IO, Ends> for TAnnotate<Meta, Inner>
+where
+    Inner: TSession<IO> + EraseLabels<IO, Ends>,
+{
+    type Output = TAnnotate<Meta, <Inner as EraseLabels<IO, Ends>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R, Err, Ends> EraseLabels<IO, Ends>
+    for TThrow<IO, Lbl, R, Err>
+{
+    type Output = TThrow<IO, types::EmptyLabel, R, Err>;
+}
+
+impl<IO, Lbl, Body, Handler, Ends> EraseLabels<IO, Ends> for TCatch<IO, Lbl, Body, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Body: TSession<IO> + EraseLabels<IO, Ends>,
+    Handler: TSession<IO> + EraseLabels<IO, Ends>,
+{
+    type Output = TCatch<
+        IO,
+        types::EmptyLabel,
+        <Body as EraseLabels<IO, Ends>>::Output,
+        <Handler as EraseLabels<IO, Ends>>::Output,
+    >;
+}
+
+/// A global protocol that is exactly `Expected`, once the labels of both are
+/// erased.
+#[diagnostic::on_unimplemented(
+    message = "the protocols differ in structure: found `{Self}`, expected `{Expected}`",
+    label = "not equivalent up to labels",
+    note = "both protocols are shown with their labels erased"
+)]
+pub trait SameStructure<Expected> {}
+
+impl<T> SameStructure<T> for T {}
+
+/// A global protocol with the same structure as `Other`, ignoring labels.
+///
+/// With the default `CompareEnds` the labels of `TEnd` must still match;
+/// with `IgnoreEnds` they are ignored too. Check two protocols with
+/// [`assert_equiv!`](crate::assert_equiv).
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Hello; struct Hi; struct Done;
+/// impl ProtocolLabel for Hello {} impl ProtocolLabel for Hi {} impl ProtocolLabel for Done {}
+///
+/// type Before = TMsg<Http, Hello, TClient, TServer, Message, TEnd<Http>>;
+/// type After = TMsg<Http, Hi, TClient, TServer, Message, TEnd<Http, Done>>;
+/// assert_equiv!(Http; Before, After, IgnoreEnds);
+/// ```
+pub trait EquivalentTo<IO, Other, Ends = CompareEnds>: TSession<IO> {}
+
+impl<IO, G, Other, Ends> EquivalentTo<IO, Other, Ends> for G
+where
+    G: TSession<IO> + EraseLabels<IO, Ends>,
+    Other: TSession<IO> + EraseLabels<IO, Ends>,
+    <G as EraseLabels<IO, Ends>>::Output: SameStructure<<Other as EraseLabels<IO, Ends>>::Output>,
+{
+}
@@ -0,0 +1,446 @@
+//! # Protocol Evolution
+//!
+//! This module implements session subtyping for global protocols, so that a
+//! new version of a protocol can be checked against the version it replaces.
+//! A global protocol `G` is a subtype of `H` when `G` can be deployed where
+//! `H` is expected:
+//!
+//! - a `TSelect` or `TChoiceN` may offer its chooser fewer branches,
+//! - a `TOffer` may let its offeree handle more branches,
+//! - every other construct must match exactly, with subtyping applied to its
+//!   continuations and branches.
+//!
+//! This mirrors [`SubtypeOf`](super::subtyping::SubtypeOf) from the point of
+//! view of the role owning each choice. Branches are matched by label, using
+//! `LabelEq` on `GetProtocolLabel`; their order does not matter.
+//!
+//! Key components:
+//!
+//! - `GlobalSubtype`: Session subtyping between two global protocols
+//! - `SamePayloads` / `EvolvedPayloads`: Whether payloads must stay the same or
+//!   may evolve as `PayloadCompatible` allows
+//! - `PayloadRule`: The payload check applied under either option
+//! - `EachGlobalSubtype`: Pointwise subtyping between two type-level lists
+//! - `FindProtocolBranch`: The first protocol of a type-level list with a given label
+//! - `BranchesWithin` / `BranchesCover`: Branch matching for selections and offers
+
+use super::base::{Cons, Nil};
+use super::global::*;
+use super::local::LabelEq;
+use super::schema::{EvolvesFrom, SchemaOf};
+use super::transforms::GetProtocolLabel;
+use crate::types;
+
+/// Evolution option: payloads must be the same in both versions.
+pub struct SamePayloads;
+
+/// Evolution option: payloads may evolve their schemas, as checked by
+/// [`EvolvesFrom`].
+pub struct EvolvedPayloads;
+
+/// The payload `New`, sent under `Lbl`, may replace the payload `Old`.
+pub trait PayloadRule<New, Old, Lbl> {}
+
+impl<H, Lbl> PayloadRule<H, H, Lbl> for SamePayloads {}
+
+impl<H, OldH, Lbl: types::ProtocolLabel> PayloadRule<H, OldH, Lbl> for EvolvedPayloads
+where
+    H: SchemaOf<Lbl>,
+    OldH: SchemaOf<Lbl>,
+    <H as SchemaOf<Lbl>>::Fields: EvolvesFrom<<OldH as SchemaOf<Lbl>>::Fields>,
+{
+}
+
+/// Global session subtyping: `Self` can be deployed where `Super` is expected.
+///
+/// With the default `SamePayloads` every payload must match exactly; with
+/// `EvolvedPayloads` payloads may also evolve their schemas. Check two
+/// versions with [`assert_global_subtype!`](crate::assert_global_subtype).
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Pick; impl ProtocolLabel for Pick {}
+/// struct Get; impl ProtocolLabel for Get {}
+/// struct Put; impl ProtocolLabel for Put {}
+/// impl LabelEq<Get> for Get { type Output = True; }
+/// impl LabelEq<Put> for Get { type Output = False; }
+/// impl LabelEq<Get> for Put { type Output = False; }
+/// impl LabelEq<Put> for Put { type Output = True; }
+///
+/// type GetReq = TMsg<Http, Get, TClient, TServer, Message, TEnd<Http>>;
+/// type PutReq = TMsg<Http, Put, TClient, TServer, Message, TEnd<Http>>;
+/// type V1 = TSelect<Http, Pick, TClient, tlist!(GetReq, PutReq)>;
+/// type V2 = TSelect<Http, Pick, TClient, tlist!(GetReq)>;
+/// // A client that no longer sends `Put` still follows the first version
+/// assert_global_subtype!(V2, V1);
+/// ```
+pub trait GlobalSubtype<Super, Payloads = SamePayloads> {}
+
+/// Pointwise global subtyping between two type-level lists of the same length.
+pub trait EachGlobalSubtype<Super, Payloads> {}
+
+impl<Payloads> EachGlobalSubtype<Nil, Payloads> for Nil {}
+
+impl<H, T, SH, ST, Payloads> EachGlobalSubtype<Cons<SH, ST>, Payloads> for Cons<H, T>
+where
+    H: GlobalSubtype<SH, Payloads>,
+    T: EachGlobalSubtype<ST, Payloads>,
+{
+}
+
+/// The first protocol of a type-level list whose label is `L`.
+pub trait FindProtocolBranch<L> {
+    type Output;
+}
+
+/// Helper trait for dispatching `FindProtocolBranch` on whether the head matches.
+pub trait FindProtocolBranchCase<IsMatch, L> {
+    type Output;
+}
+
+impl<H, T, L> FindProtocolBranch<L> for Cons<H, T>
+where
+    H: GetProtocolLabel,
+    <H as GetProtocolLabel>::Label: LabelEq<L>,
+    Cons<H, T>: FindProtocolBranchCase<<<H as GetProtocolLabel>::Label as LabelEq<L>>::Output, L>,
+{
+    type Output = <Cons<H, T> as FindProtocolBranchCase<
+        <<H as GetProtocolLabel>::Label as LabelEq<L>>::Output,
+        L,
+    >>::Output;
+}
+
+impl<H, T, L> FindProtocolBranchCase<types::True, L> for Cons<H, T> {
+    type Output = H;
+}
+
+impl<H, T: FindProtocolBranch<L>, L> FindProtocolBranchCase<types::False, L> for Cons<H, T> {
+    type Output = <T as FindProtocolBranch<L>>::Output;
+}
+
+/// Every branch of `Self` is a subtype of the branch of `Super` with the same
+/// label.
+///
+/// Used for selections: the subtype may choose from fewer branches.
+pub trait BranchesWithin<Super, Payloads> {}
+
+impl<Super, Payloads> BranchesWithin<Super, Payloads> for Nil {}
+
+impl<H, T, Super, Payloads> BranchesWithin<Super, Payloads> for Cons<H, T>
+where
+    H: GetProtocolLabel,
+    Super: FindProtocolBranch<<H as GetProtocolLabel>::Label>,
+    H: GlobalSubtype<
+        <Super as FindProtocolBranch<<H as GetProtocolLabel>::Label>>::Output,
+        Payloads,
+    >,
+    T: BranchesWithin<Super, Payloads>,
+{
+}
+
+/// Every branch of `Super` has a branch of `Self` with the same label that is
+/// a subtype of it.
+///
+/// Used for offers: the subtype may handle more branches.
+pub trait BranchesCover<Super, Payloads> {}
+
+impl<Sub, Payloads> BranchesCover<Nil, Payloads> for Sub {}
+
+impl<Sub, H, T, Payloads> BranchesCover<Cons<H, T>, Payloads> for Sub
+where
+    H: GetProtocolLabel,
+    Sub: FindProtocolBranch<<H as GetProtocolLabel>::Label>,
+    <Sub as FindProtocolBranch<<H as GetProtocolLabel>::Label>>::Output: GlobalSubtype<H, Payloads>,
+    Sub: BranchesCover<T, Payloads>,
+{
+}
+
+// Leaves
+
+impl<IO, Lbl, Payloads> GlobalSubtype<TEnd<IO, Lbl>, Payloads> for TEnd<IO, Lbl> {}
+
+impl<Var, Payloads> GlobalSubtype<TVar<Var>, Payloads> for TVar<Var> {}
+
+// Error payloads evolve like message payloads
+impl<IO, Lbl: types::ProtocolLabel, R, Err, OldErr, Payloads>
+    GlobalSubtype<TThrow<IO, Lbl, R, OldErr>, Payloads> for TThrow<IO, Lbl, R, Err>
+where
+    Payloads: PayloadRule<Err, OldErr, Lbl>,
+{
+}
+
+// Communication
+
+impl<IO, Lbl: types::ProtocolLabel, R, H, T, OldH, OldT, Payloads>
+    GlobalSubtype<TInteract<IO, Lbl, R, OldH, OldT>, Payloads> for TInteract<IO, Lbl, R, H, T>
+where
+    T: TSession<IO> + GlobalSubtype<OldT, Payloads>,
+    OldT: TSession<IO>,
+    Payloads: PayloadRule<H, OldH, Lbl>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, To, H, T, OldH, OldT, Payloads>
+    GlobalSubtype<TMsg<IO, Lbl, From, To, OldH, OldT>, Payloads> for TMsg<IO, Lbl, From, To, H, T>
+where
+    T: TSession<IO> + GlobalSubtype<OldT, Payloads>,
+    OldT: TSession<IO>,
+    Payloads: PayloadRule<H, OldH, Lbl>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, ToSet, H, T, OldH, OldT, Payloads>
+    GlobalSubtype<TBroadcast<IO, Lbl, From, ToSet, OldH, OldT>, Payloads>
+    for TBroadcast<IO, Lbl, From, ToSet, H, T>
+where
+    T: TSession<IO> + GlobalSubtype<OldT, Payloads>,
+    OldT: TSession<IO>,
+    Payloads: PayloadRule<H, OldH, Lbl>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, WorkerSet, H, T, OldH, OldT, Payloads>
+    GlobalSubtype<TAnycast<IO, Lbl, From, WorkerSet, OldH, OldT>, Payloads>
+    for TAnycast<IO, Lbl, From, WorkerSet, H, T>
+where
+    T: TSession<IO> + GlobalSubtype<OldT, Payloads>,
+    OldT: TSession<IO>,
+    Payloads: PayloadRule<H, OldH, Lbl>,
+{
+}
+
+// A delegated endpoint is a session type, not a serialized payload, so it must
+// stay the same between versions
+impl<IO, Lbl: types::ProtocolLabel, From, To, DelegatedEp, T, OldT, Payloads>
+    GlobalSubtype<TDelegate<IO, Lbl, From, To, DelegatedEp, OldT>, Payloads>
+    for TDelegate<IO, Lbl, From, To, DelegatedEp, T>
+where
+    T: TSession<IO> + GlobalSubtype<OldT, Payloads>,
+    OldT: TSession<IO>,
+{
+}
+
+// Connections
+
+impl<IO, Lbl: types::ProtocolLabel, A, B, Cont, OldCont, Payloads>
+    GlobalSubtype<TDisconnect<IO, Lbl, A, B, OldCont>, Payloads>
+    for TDisconnect<IO, Lbl, A, B, Cont>
+where
+    Cont: TSession<IO> + GlobalSubtype<OldCont, Payloads>,
+    OldCont: TSession<IO>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, A, B, Cont, OldCont, Payloads>
+    GlobalSubtype<TConnect<IO, Lbl, A, B, OldCont>, Payloads> for TConnect<IO, Lbl, A, B, Cont>
+where
+    Cont: TSession<IO> + GlobalSubtype<OldCont, Payloads>,
+    OldCont: TSession<IO>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R, Cont, OldCont, Payloads>
+    GlobalSubtype<TEndFor<IO, Lbl, R, OldCont>, Payloads> for TEndFor<IO, Lbl, R, Cont>
+where
+    Cont: TSession<IO> + GlobalSubtype<OldCont, Payloads>,
+    OldCont: TSession<IO>,
+{
+}
+
+// Branching
+
+impl<IO, Lbl: types::ProtocolLabel, L, R, OldL, OldR, Payloads>
+    GlobalSubtype<TChoice<IO, Lbl, OldL, OldR>, Payloads> for TChoice<IO, Lbl, L, R>
+where
+    L: TSession<IO> + GlobalSubtype<OldL, Payloads>,
+    R: TSession<IO> + GlobalSubtype<OldR, Payloads>,
+    OldL: TSession<IO>,
+    OldR: TSession<IO>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Branches, OldBranches, Payloads>
+    GlobalSubtype<TChoiceN<IO, Lbl, OldBranches>, Payloads> for TChoiceN<IO, Lbl, Branches>
+where
+    Branches: SessionList<IO> + BranchesWithin<OldBranches, Payloads>,
+    OldBranches: SessionList<IO>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Chooser, Branches, OldBranches, Payloads>
+    GlobalSubtype<TSelect<IO, Lbl, Chooser, OldBranches>, Payloads>
+    for TSelect<IO, Lbl, Chooser, Branches>
+where
+    Branches: SessionList<IO> + BranchesWithin<OldBranches, Payloads>,
+    OldBranches: SessionList<IO>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Offeree, Branches, OldBranches, Payloads>
+    GlobalSubtype<TOffer<IO, Lbl, Offeree, OldBranches>, Payloads>
+    for TOffer<IO, Lbl, Offeree, Branches>
+where
+    Branches: SessionList<IO> + BranchesCover<OldBranches, Payloads>,
+    OldBranches: SessionList<IO>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, High, Low, OldHigh, OldLow, Payloads>
+    GlobalSubtype<TPriorityChoice<IO, Lbl, OldHigh, OldLow>, Payloads>
+    for TPriorityChoice<IO, Lbl, High, Low>
+where
+    High: TSession<IO> + GlobalSubtype<OldHigh, Payloads>,
+    Low: TSession<IO> + GlobalSubtype<OldLow, Payloads>,
+    OldHigh: TSession<IO>,
+    OldLow: TSession<IO>,
+{
+}
+
+// Weights may be retuned freely; they do not change which branches exist
+impl<IO, Lbl, WeightL, L, WeightR, R, OldWeightL, OldL, OldWeightR, OldR, Payloads>
+    GlobalSubtype<TProb<IO, Lbl, OldWeightL, OldL, OldWeightR, OldR>, Payloads>
+    for TProb<IO, Lbl, WeightL, L, WeightR, R>
+where
+    Lbl: types::ProtocolLabel,
+    WeightL: types::WeightMarker,
+    WeightR: types::WeightMarker,
+    L: TSession<IO> + GlobalSubtype<OldL, Payloads>,
+    R: TSession<IO> + GlobalSubtype<OldR, Payloads>,
+    OldWeightL: types::WeightMarker,
+    OldWeightR: types::WeightMarker,
+    OldL: TSession<IO>,
+    OldR: TSession<IO>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Decider, Body, Cont, OldBody, OldCont, Payloads>
+    GlobalSubtype<TOptional<IO, Lbl, Decider, OldBody, OldCont>, Payloads>
+    for TOptional<IO, Lbl, Decider, Body, Cont>
+where
+    Body: TSession<IO> + GlobalSubtype<OldBody, Payloads>,
+    Cont: TSession<IO> + GlobalSubtype<OldCont, Payloads>,
+    OldBody: TSession<IO>,
+    OldCont: TSession<IO>,
+{
+}
+
+// Concurrency
+
+impl<IO, Lbl: types::ProtocolLabel, L, R, IsDisjoint, OldL, OldR, OldDisjoint, Payloads>
+    GlobalSubtype<TPar<IO, Lbl, OldL, OldR, OldDisjoint>, Payloads>
+    for TPar<IO, Lbl, L, R, IsDisjoint>
+where
+    L: TSession<IO> + GlobalSubtype<OldL, Payloads>,
+    R: TSession<IO> + GlobalSubtype<OldR, Payloads>,
+    OldL: TSession<IO>,
+    OldR: TSession<IO>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Branches, OldBranches, Payloads>
+    GlobalSubtype<TParN<IO, Lbl, OldBranches>, Payloads> for TParN<IO, Lbl, Branches>
+where
+    Branches: SessionList<IO> + EachGlobalSubtype<OldBranches, Payloads>,
+    OldBranches: SessionList<IO>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Steps, OldSteps, Payloads>
+    GlobalSubtype<TShuffle<IO, Lbl, OldSteps>, Payloads> for TShuffle<IO, Lbl, Steps>
+where
+    Steps: SessionList<IO> + EachGlobalSubtype<OldSteps, Payloads>,
+    OldSteps: SessionList<IO>,
+{
+}
+
+// Recursion
+
+impl<IO, Lbl: types::ProtocolLabel, S, OldS, Payloads> GlobalSubtype<TRec<IO, Lbl, OldS>, Payloads>
+    for TRec<IO, Lbl, S>
+where
+    S: TSession<IO> + GlobalSubtype<OldS, Payloads>,
+    OldS: TSession<IO>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Var, Body, OldBody, Payloads>
+    GlobalSubtype<TRecX<IO, Lbl, Var, OldBody>, Payloads> for TRecX<IO, Lbl, Var, Body>
+where
+    Body: TSession<IO> + GlobalSubtype<OldBody, Payloads>,
+    OldBody: TSession<IO>,
+{
+}
+
+// Timing, interrupts and failures
+
+impl<IO, Lbl, Dur, Body, OnTimeout, OldBody, OldOnTimeout, Payloads>
+    GlobalSubtype<TTimeout<IO, Lbl, Dur, OldBody, OldOnTimeout>, Payloads>
+    for TTimeout<IO, Lbl, Dur, Body, OnTimeout>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    Body: TSession<IO> + GlobalSubtype<OldBody, Payloads>,
+    OnTimeout: TSession<IO> + GlobalSubtype<OldOnTimeout, Payloads>,
+    OldBody: TSession<IO>,
+    OldOnTimeout: TSession<IO>,
+{
+}
+
+impl<IO, Lbl, R, Dur, T, OldT, Payloads> GlobalSubtype<TDelay<IO, Lbl, R, Dur, OldT>, Payloads>
+    for TDelay<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: TSession<IO> + GlobalSubtype<OldT, Payloads>,
+    OldT: TSession<IO>,
+{
+}
+
+impl<IO, Lbl, R, Dur, T, OldT, Payloads> GlobalSubtype<TDeadline<IO, Lbl, R, Dur, OldT>, Payloads>
+    for TDeadline<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: TSession<IO> + GlobalSubtype<OldT, Payloads>,
+    OldT: TSession<IO>,
+{
+}
+
+impl<IO, Lbl, Scope, Interruptor, Handler, OldScope, OldHandler, Payloads>
+    GlobalSubtype<TInterrupt<IO, Lbl, OldScope, Interruptor, OldHandler>, Payloads>
+    for TInterrupt<IO, Lbl, Scope, Interruptor, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Scope: TSession<IO> + GlobalSubtype<OldScope, Payloads>,
+    Handler: TSession<IO> + GlobalSubtype<OldHandler, Payloads>,
+    OldScope: TSession<IO>,
+    OldHandler: TSession<IO>,
+{
+}
+
+impl<IO, Lbl, Body, Handler, OldBody, OldHandler, Payloads>
+    GlobalSubtype<TCatch<IO, Lbl, OldBody, OldHandler>, Payloads> for TCatch<IO, Lbl, Body, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Body: TSession<IO> + GlobalSubtype<OldBody, Payloads>,
+    Handler: TSession<IO> + GlobalSubtype<OldHandler, Payloads>,
+    OldBody: TSession<IO>,
+    OldHandler: TSession<IO>,
+{
+}
+
+impl<Pred, Inner, OldInner, Payloads> GlobalSubtype<TRefine<Pred, OldInner>, Payloads>
+    for TRefine<Pred, Inner>
+where
+    Inner: GlobalSubtype<OldInner, Payloads>,
+{
+}
+
+// Annotations do not change behaviour, so they may change freely between versions
+impl<Meta, Inner, OldMeta, OldInner, Payloads> GlobalSubtype<TAnnotate<OldMeta, OldInner>, Payloads>
+    for TAnnotate<Meta, Inner>
+where
+    Inner: GlobalSubtype<OldInner, Payloads>,
+{
+}
@@ -0,0 +1,250 @@
+//! # Failure Handling Checks
+//!
+//! This module checks that failure branches in global protocols are handled:
+//! every `TThrow` must sit inside the body of an enclosing `TCatch`, whose
+//! handler then takes over for all participants.
+//!
+//! Key components:
+//!
+//! - `CatchScoped`: Every `TThrow` is enclosed by a `TCatch`
+//!
+//! An escaping `TThrow` would abort the protocol with no agreed continuation,
+//! so it is rejected at compile time.
+
+use super::base::{Cons, Nil};
+use super::global::*;
+use crate::types;
+
+/// A protocol whose every `TThrow` is enclosed by a `TCatch`.
+///
+/// - `InCatch`: Type-level boolean, are we inside the body of a `TCatch`?
+///   Check a whole protocol with `CatchScoped<False>`.
+///
+/// The handler of a `TCatch` is not itself protected by that catch: a throw
+/// there escalates to the next enclosing `TCatch`.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Failed;
+///
+/// type Fallible = TCatch<
+///     Http,
+///     EmptyLabel,
+///     TInteract<Http, EmptyLabel, TServer, Response, TThrow<Http, EmptyLabel, TServer, Failed>>,
+///     TInteract<Http, EmptyLabel, TClient, Notify, TEnd<Http>>,
+/// >;
+/// fn assert_caught<G: CatchScoped<False>>() {}
+/// assert_caught::<Fallible>();
+/// ```
+pub trait CatchScoped<InCatch> {}
+
+impl<IO, Lbl, InCatch> CatchScoped<InCatch> for TEnd<IO, Lbl> {}
+
+impl<IO, Lbl: types::ProtocolLabel, R, H, T, InCatch> CatchScoped<InCatch>
+    for TInteract<IO, Lbl, R, H, T>
+where
+    T: TSession<IO> + CatchScoped<InCatch>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, To, H, T, InCatch> CatchScoped<InCatch>
+    for TMsg<IO, Lbl, From, To, H, T>
+where
+    T: TSession<IO> + CatchScoped<InCatch>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, ToSet, H, T, InCatch> CatchScoped<InCatch>
+    for TBroadcast<IO, Lbl, From, ToSet, H, T>
+where
+    T: TSession<IO> + CatchScoped<InCatch>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, WorkerSet, H, T, InCatch> CatchScoped<InCatch>
+    for TAnycast<IO, Lbl, From, WorkerSet, H, T>
+where
+    T: TSession<IO> + CatchScoped<InCatch>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, To, DelegatedEp, T, InCatch> CatchScoped<InCatch>
+    for TDelegate<IO, Lbl, From, To, DelegatedEp, T>
+where
+    T: TSession<IO> + CatchScoped<InCatch>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, A, B, Cont, InCatch> CatchScoped<InCatch>
+    for TDisconnect<IO, Lbl, A, B, Cont>
+where
+    Cont: TSession<IO> + CatchScoped<InCatch>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, A, B, Cont, InCatch> CatchScoped<InCatch>
+    for TConnect<IO, Lbl, A, B, Cont>
+where
+    Cont: TSession<IO> + CatchScoped<InCatch>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R, Cont, InCatch> CatchScoped<InCatch>
+    for TEndFor<IO, Lbl, R, Cont>
+where
+    Cont: TSession<IO> + CatchScoped<InCatch>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, L, R, InCatch> CatchScoped<InCatch> for TChoice<IO, Lbl, L, R>
+where
+    L: TSession<IO> + CatchScoped<InCatch>,
+    R: TSession<IO> + CatchScoped<InCatch>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Branches, InCatch> CatchScoped<InCatch>
+    for TChoiceN<IO, Lbl, Branches>
+where
+    Branches: SessionList<IO> + CatchScoped<InCatch>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Chooser, Branches, InCatch> CatchScoped<InCatch>
+    for TSelect<IO, Lbl, Chooser, Branches>
+where
+    Branches: SessionList<IO> + CatchScoped<InCatch>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Offeree, Branches, InCatch> CatchScoped<InCatch>
+    for TOffer<IO, Lbl, Offeree, Branches>
+where
+    Branches: SessionList<IO> + CatchScoped<InCatch>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, High, Low, InCatch> CatchScoped<InCatch>
+    for TPriorityChoice<IO, Lbl, High, Low>
+where
+    High: TSession<IO> + CatchScoped<InCatch>,
+    Low: TSession<IO> + CatchScoped<InCatch>,
+{
+}
+
+impl<IO, Lbl, WeightL, L, WeightR, R, InCatch> CatchScoped<InCatch>
+    for TProb<IO, Lbl, WeightL, L, WeightR, R>
+where
+    Lbl: types::ProtocolLabel,
+    WeightL: types::WeightMarker,
+    WeightR: types::WeightMarker,
+    L: TSession<IO> + CatchScoped<InCatch>,
+    R: TSession<IO> + CatchScoped<InCatch>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, L, R, IsDisjoint, InCatch> CatchScoped<InCatch>
+    for TPar<IO, Lbl, L, R, IsDisjoint>
+where
+    L: TSession<IO> + CatchScoped<InCatch>,
+    R: TSession<IO> + CatchScoped<InCatch>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Branches, InCatch> CatchScoped<InCatch>
+    for TParN<IO, Lbl, Branches>
+where
+    Branches: SessionList<IO> + CatchScoped<InCatch>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Steps, InCatch> CatchScoped<InCatch>
+    for TShuffle<IO, Lbl, Steps>
+where
+    Steps: SessionList<IO> + CatchScoped<InCatch>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, S, InCatch> CatchScoped<InCatch> for TRec<IO, Lbl, S> where
+    S: TSession<IO> + CatchScoped<InCatch>
+{
+}
+
+impl<IO, Lbl, Dur, Body, OnTimeout, InCatch> CatchScoped<InCatch>
+    for TTimeout<IO, Lbl, Dur, Body, OnTimeout>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    Body: TSession<IO> + CatchScoped<InCatch>,
+    OnTimeout: TSession<IO> + CatchScoped<InCatch>,
+{
+}
+
+impl<IO, Lbl, R, Dur, T, InCatch> CatchScoped<InCatch> for TDelay<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: TSession<IO> + CatchScoped<InCatch>,
+{
+}
+
+impl<IO, Lbl, R, Dur, T, InCatch> CatchScoped<InCatch> for TDeadline<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: TSession<IO> + CatchScoped<InCatch>,
+{
+}
+
+impl<IO, Lbl, Scope, Interruptor, Handler, InCatch> CatchScoped<InCatch>
+    for TInterrupt<IO, Lbl, Scope, Interruptor, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Scope: TSession<IO> + CatchScoped<InCatch>,
+    Handler: TSession<IO> + CatchScoped<InCatch>,
+{
+}
+
+impl<IO, Lbl, Decider, Body, Cont, InCatch> CatchScoped<InCatch>
+    for TOptional<IO, Lbl, Decider, Body, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Body: TSession<IO> + CatchScoped<InCatch>,
+    Cont: TSession<IO> + CatchScoped<InCatch>,
+{
+}
+
+impl<Pred, Inner: CatchScoped<InCatch>, InCatch> CatchScoped<InCatch> for TRefine<Pred, Inner> {}
+
+impl<Meta, Inner: CatchScoped<InCatch>, InCatch> CatchScoped<InCatch> for TAnnotate<Meta, Inner> {}
+
+// A throw is only allowed inside the body of a catch
+impl<IO, Lbl: types::ProtocolLabel, R, Err> CatchScoped<types::True> for TThrow<IO, Lbl, R, Err> {}
+
+// The body is protected by this catch; the handler escalates outward
+impl<IO, Lbl, Body, Handler, InCatch> CatchScoped<InCatch> for TCatch<IO, Lbl, Body, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Body: TSession<IO> + CatchScoped<types::True>,
+    Handler: TSession<IO> + CatchScoped<InCatch>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Var, Body, InCatch> CatchScoped<InCatch>
+    for TRecX<IO, Lbl, Var, Body>
+where
+    Body: TSession<IO> + CatchScoped<InCatch>,
+{
+}
+
+impl<Var, InCatch> CatchScoped<InCatch> for TVar<Var> {}
+
+impl<InCatch> CatchScoped<InCatch> for Nil {}
+
+impl<H, T, InCatch> CatchScoped<InCatch> for Cons<H, T>
+where
+    H: CatchScoped<InCatch>,
+    T: CatchScoped<InCatch>,
+{
+}
@@ -0,0 +1,570 @@
+//! # Protocol Fingerprints
+//!
+//! This module computes a stable 64-bit hash of a global protocol, as a
+//! constant. Peers can exchange it at session setup, or tag messages with it,
+//! and reject a peer running a different protocol version.
+//!
+//! Key components:
+//!
+//! - `FingerprintOf`: The fingerprint of a global protocol, as a `u64` constant
+//! - `FingerprintOfEach`: The same for a type-level list of protocols
+//! - `FingerprintOfRoles`: The same for a type-level list of roles
+//! - `fingerprint_mix`: The hash step the fingerprints are built from
+//!
+//! The fingerprint covers the combinator of every node, in the shape of the
+//! [`Describe`](super::describe::Describe) tree, the names of its labels and
+//! roles, and the durations and weights of timed and probabilistic
+//! combinators. Labels and roles are hashed by their `NAME`, which every label
+//! and role of a fingerprinted protocol must set, as derived labels and roles
+//! do; see [`ConstLabelName`] and [`ConstRoleName`]. The hash is FNV-1a over
+//! these names only, so it does not depend on the platform, the compiler
+//! version or the module a type lives in.
+//!
+//! Payloads are not hashed: message types, delegated endpoints and thrown
+//! errors have no constant name, so two protocols that differ only in their
+//! payloads share a fingerprint. Give such steps distinct labels to tell the
+//! versions apart. Recursion variables, predicates and annotations are not
+//! hashed either: renaming a variable or annotating a step does not change
+//! the protocol.
+
+use super::base::{Cons, Nil};
+use super::describe::{CombinatorKind, ConstLabelName, ConstRoleName};
+use super::global::*;
+use super::local::Role;
+use crate::types;
+use core::time::Duration;
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Mixes `word` into the fingerprint `hash`, one byte at a time.
+pub const fn fingerprint_mix(hash: u64, word: u64) -> u64 {
+    let bytes = word.to_le_bytes();
+    let mut hash = hash;
+    let mut i = 0;
+    while i < bytes.len() {
+        hash ^= bytes[i] as u64;
+        hash = hash.wrapping_mul(FNV_PRIME);
+        i += 1;
+    }
+    hash
+}
+
+// The fingerprint of a node of kind `kind` with the given parameters and
+// child fingerprints
+const fn node(kind: CombinatorKind, words: &[u64]) -> u64 {
+    let mut hash = fingerprint_mix(FNV_OFFSET, kind as u64);
+    let mut i = 0;
+    while i < words.len() {
+        hash = fingerprint_mix(hash, words[i]);
+        i += 1;
+    }
+    hash
+}
+
+const fn duration_word(duration: Duration) -> u64 {
+    duration.as_nanos() as u64
+}
+
+// The hash of a name, one byte at a time
+const fn name_word(name: &str) -> u64 {
+    let bytes = name.as_bytes();
+    let mut hash = FNV_OFFSET;
+    let mut i = 0;
+    while i < bytes.len() {
+        hash ^= bytes[i] as u64;
+        hash = hash.wrapping_mul(FNV_PRIME);
+        i += 1;
+    }
+    hash
+}
+
+const fn label_word<Lbl: types::ProtocolLabel>() -> u64 {
+    name_word(ConstLabelName::<Lbl>::NAME)
+}
+
+const fn role_word<R: Role>() -> u64 {
+    name_word(ConstRoleName::<R>::NAME)
+}
+
+/// A stable hash of a global protocol's structure and names, as a constant.
+///
+/// Protocols that differ in a combinator, in the nesting or order of their
+/// parts, in a label or role, or in a duration or weight get different
+/// fingerprints. See the [module documentation](self) for what is not hashed.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// type V1 = TMsg<Http, EmptyLabel, TClient, TServer, Message, TEnd<Http>>;
+/// type V2 = TMsg<Http, EmptyLabel, TClient, TServer, Message,
+///           TMsg<Http, EmptyLabel, TServer, TClient, Response, TEnd<Http>>>;
+/// const HELLO: u64 = <V2 as FingerprintOf>::FINGERPRINT;
+/// assert_ne!(HELLO, <V1 as FingerprintOf>::FINGERPRINT);
+/// ```
+pub trait FingerprintOf {
+    /// The fingerprint of the protocol.
+    const FINGERPRINT: u64;
+}
+
+/// The fingerprint of a type-level list of protocols, in order.
+///
+/// - Used by n-ary combinators to hash their branches.
+pub trait FingerprintOfEach {
+    /// The fingerprint of the list.
+    const FINGERPRINT: u64;
+}
+
+/// The fingerprint of a type-level list of roles, in order.
+///
+/// - Used by `TBroadcast` and `TAnycast` to hash their role sets.
+pub trait FingerprintOfRoles {
+    /// The fingerprint of the list.
+    const FINGERPRINT: u64;
+}
+
+impl FingerprintOfRoles for Nil {
+    const FINGERPRINT: u64 = FNV_OFFSET;
+}
+
+impl<H: Role, T: FingerprintOfRoles> FingerprintOfRoles for Cons<H, T> {
+    const FINGERPRINT: u64 = fingerprint_mix(
+        fingerprint_mix(FNV_OFFSET, role_word::<H>()),
+        T::FINGERPRINT,
+    );
+}
+
+impl FingerprintOfEach for Nil {
+    const FINGERPRINT: u64 = FNV_OFFSET;
+}
+
+impl<H: FingerprintOf, T: FingerprintOfEach> FingerprintOfEach for Cons<H, T> {
+    const FINGERPRINT: u64 =
+        fingerprint_mix(fingerprint_mix(FNV_OFFSET, H::FINGERPRINT), T::FINGERPRINT);
+}
+
+impl<IO, Lbl: types::ProtocolLabel> FingerprintOf for TEnd<IO, Lbl> {
+    const FINGERPRINT: u64 = node(CombinatorKind::End, &[label_word::<Lbl>()]);
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R: Role, H, T: TSession<IO> + FingerprintOf> FingerprintOf
+    for TInteract<IO, Lbl, R, H, T>
+{
+    const FINGERPRINT: u64 = node(
+        CombinatorKind::Interact,
+        &[
+            label_word::<Lbl>(),
+            role_word::<R>(),
+            <T as FingerprintOf>::FINGERPRINT,
+        ],
+    );
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From: Role, To: Role, H, T: TSession<IO> + FingerprintOf>
+    FingerprintOf for TMsg<IO, Lbl, From, To, H, T>
+{
+    const FINGERPRINT: u64 = node(
+        CombinatorKind::Msg,
+        &[
+            label_word::<Lbl>(),
+            role_word::<From>(),
+            role_word::<To>(),
+            <T as FingerprintOf>::FINGERPRINT,
+        ],
+    );
+}
+
+impl<
+        IO,
+        Lbl: types::ProtocolLabel,
+        From: Role,
+        ToSet: FingerprintOfRoles,
+        H,
+        T: TSession<IO> + FingerprintOf,
+    > FingerprintOf for TBroadcast<IO, Lbl, From, ToSet, H, T>
+{
+    const FINGERPRINT: u64 = node(
+        CombinatorKind::Broadcast,
+        &[
+            label_word::<Lbl>(),
+            role_word::<From>(),
+            <ToSet as FingerprintOfRoles>::FINGERPRINT,
+            <T as FingerprintOf>::FINGERPRINT,
+        ],
+    );
+}
+
+impl<
+        IO,
+        Lbl: types::ProtocolLabel,
+        From: Role,
+        WorkerSet: FingerprintOfRoles,
+        H,
+        T: TSession<IO> + FingerprintOf,
+    > FingerprintOf for TAnycast<IO, Lbl, From, WorkerSet, H, T>
+{
+    const FINGERPRINT: u64 = node(
+        CombinatorKind::Anycast,
+        &[
+            label_word::<Lbl>(),
+            role_word::<From>(),
+            <WorkerSet as FingerprintOfRoles>::FINGERPRINT,
+            <T as FingerprintOf>::FINGERPRINT,
+        ],
+    );
+}
+
+impl<
+        IO,
+        Lbl: types::ProtocolLabel,
+        From: Role,
+        To: Role,
+        DelegatedEp,
+        T: TSession<IO> + FingerprintOf,
+    > FingerprintOf for TDelegate<IO, Lbl, From, To, DelegatedEp, T>
+{
+    const FINGERPRINT: u64 = node(
+        CombinatorKind::Delegate,
+        &[
+            label_word::<Lbl>(),
+            role_word::<From>(),
+            role_word::<To>(),
+            <T as FingerprintOf>::FINGERPRINT,
+        ],
+    );
+}
+
+impl<IO, Lbl: types::ProtocolLabel, A: Role, B: Role, Cont: TSession<IO> + FingerprintOf>
+    FingerprintOf for TDisconnect<IO, Lbl, A, B, Cont>
+{
+    const FINGERPRINT: u64 = node(
+        CombinatorKind::Disconnect,
+        &[
+            label_word::<Lbl>(),
+            role_word::<A>(),
+            role_word::<B>(),
+            <Cont as FingerprintOf>::FINGERPRINT,
+        ],
+    );
+}
+
+impl<IO, Lbl: types::ProtocolLabel, A: Role, B: Role, Cont: TSession<IO> + FingerprintOf>
+    FingerprintOf for TConnect<IO, Lbl, A, B, Cont>
+{
+    const FINGERPRINT: u64 = node(
+        CombinatorKind::Connect,
+        &[
+            label_word::<Lbl>(),
+            role_word::<A>(),
+            role_word::<B>(),
+            <Cont as FingerprintOf>::FINGERPRINT,
+        ],
+    );
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R: Role, Cont: TSession<IO> + FingerprintOf> FingerprintOf
+    for TEndFor<IO, Lbl, R, Cont>
+{
+    const FINGERPRINT: u64 = node(
+        CombinatorKind::EndFor,
+        &[
+            label_word::<Lbl>(),
+            role_word::<R>(),
+            <Cont as FingerprintOf>::FINGERPRINT,
+        ],
+    );
+}
+
+impl<
+        IO,
+        Lbl: types::ProtocolLabel,
+        L: TSession<IO> + FingerprintOf,
+        R: TSession<IO> + FingerprintOf,
+    > FingerprintOf for TChoice<IO, Lbl, L, R>
+{
+    const FINGERPRINT: u64 = node(
+        CombinatorKind::Choice,
+        &[
+            label_word::<Lbl>(),
+            <L as FingerprintOf>::FINGERPRINT,
+            <R as FingerprintOf>::FINGERPRINT,
+        ],
+    );
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Branches: SessionList<IO> + FingerprintOfEach> FingerprintOf
+    for TChoiceN<IO, Lbl, Branches>
+{
+    const FINGERPRINT: u64 = node(
+        CombinatorKind::ChoiceN,
+        &[
+            label_word::<Lbl>(),
+            <Branches as FingerprintOfEach>::FINGERPRINT,
+        ],
+    );
+}
+
+impl<
+        IO,
+        Lbl: types::ProtocolLabel,
+        Chooser: Role,
+        Branches: SessionList<IO> + FingerprintOfEach,
+    > FingerprintOf for TSelect<IO, Lbl, Chooser, Branches>
+{
+    const FINGERPRINT: u64 = node(
+        CombinatorKind::Select,
+        &[
+            label_word::<Lbl>(),
+            role_word::<Chooser>(),
+            <Branches as FingerprintOfEach>::FINGERPRINT,
+        ],
+    );
+}
+
+impl<
+        IO,
+        Lbl: types::ProtocolLabel,
+        Offeree: Role,
+        Branches: SessionList<IO> + FingerprintOfEach,
+    > FingerprintOf for TOffer<IO, Lbl, Offeree, Branches>
+{
+    const FINGERPRINT: u64 = node(
+        CombinatorKind::Offer,
+        &[
+            label_word::<Lbl>(),
+            role_word::<Offeree>(),
+            <Branches as FingerprintOfEach>::FINGERPRINT,
+        ],
+    );
+}
+
+impl<
+        IO,
+        Lbl: types::ProtocolLabel,
+        High: TSession<IO> + FingerprintOf,
+        Low: TSession<IO> + FingerprintOf,
+    > FingerprintOf for TPriorityChoice<IO, Lbl, High, Low>
+{
+    const FINGERPRINT: u64 = node(
+        CombinatorKind::PriorityChoice,
+        &[
+            label_word::<Lbl>(),
+            <High as FingerprintOf>::FINGERPRINT,
+            <Low as FingerprintOf>::FINGERPRINT,
+        ],
+    );
+}
+
+impl<IO, Lbl, WeightL, L, WeightR, R> FingerprintOf for TProb<IO, Lbl, WeightL, L, WeightR, R>
+where
+    Lbl: types::ProtocolLabel,
+    WeightL: types::WeightMarker,
+    L: TSession<IO> + FingerprintOf,
+    WeightR: types::WeightMarker,
+    R: TSession<IO> + FingerprintOf,
+{
+    const FINGERPRINT: u64 = node(
+        CombinatorKind::Prob,
+        &[
+            label_word::<Lbl>(),
+            WeightL::WEIGHT as u64,
+            WeightR::WEIGHT as u64,
+            <L as FingerprintOf>::FINGERPRINT,
+            <R as FingerprintOf>::FINGERPRINT,
+        ],
+    );
+}
+
+impl<
+        IO,
+        Lbl: types::ProtocolLabel,
+        L: TSession<IO> + FingerprintOf,
+        R: TSession<IO> + FingerprintOf,
+        IsDisjoint,
+    > FingerprintOf for TPar<IO, Lbl, L, R, IsDisjoint>
+{
+    const FINGERPRINT: u64 = node(
+        CombinatorKind::Par,
+        &[
+            label_word::<Lbl>(),
+            <L as FingerprintOf>::FINGERPRINT,
+            <R as FingerprintOf>::FINGERPRINT,
+        ],
+    );
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Branches: SessionList<IO> + FingerprintOfEach> FingerprintOf
+    for TParN<IO, Lbl, Branches>
+{
+    const FINGERPRINT: u64 = node(
+        CombinatorKind::ParN,
+        &[
+            label_word::<Lbl>(),
+            <Branches as FingerprintOfEach>::FINGERPRINT,
+        ],
+    );
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Steps: SessionList<IO> + FingerprintOfEach> FingerprintOf
+    for TShuffle<IO, Lbl, Steps>
+{
+    const FINGERPRINT: u64 = node(
+        CombinatorKind::Shuffle,
+        &[
+            label_word::<Lbl>(),
+            <Steps as FingerprintOfEach>::FINGERPRINT,
+        ],
+    );
+}
+
+impl<IO, Lbl: types::ProtocolLabel, S: TSession<IO> + FingerprintOf> FingerprintOf
+    for TRec<IO, Lbl, S>
+{
+    const FINGERPRINT: u64 = node(
+        CombinatorKind::Rec,
+        &[label_word::<Lbl>(), <S as FingerprintOf>::FINGERPRINT],
+    );
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Var, Body: TSession<IO> + FingerprintOf> FingerprintOf
+    for TRecX<IO, Lbl, Var, Body>
+{
+    const FINGERPRINT: u64 = node(
+        CombinatorKind::RecX,
+        &[label_word::<Lbl>(), <Body as FingerprintOf>::FINGERPRINT],
+    );
+}
+
+impl<Var> FingerprintOf for TVar<Var> {
+    const FINGERPRINT: u64 = node(CombinatorKind::Var, &[]);
+}
+
+impl<
+        IO,
+        Lbl: types::ProtocolLabel,
+        Dur: types::DurationMarker,
+        Body: TSession<IO> + FingerprintOf,
+        OnTimeout: TSession<IO> + FingerprintOf,
+    > FingerprintOf for TTimeout<IO, Lbl, Dur, Body, OnTimeout>
+{
+    const FINGERPRINT: u64 = node(
+        CombinatorKind::Timeout,
+        &[
+            label_word::<Lbl>(),
+            duration_word(Dur::DURATION),
+            <Body as FingerprintOf>::FINGERPRINT,
+            <OnTimeout as FingerprintOf>::FINGERPRINT,
+        ],
+    );
+}
+
+impl<
+        IO,
+        Lbl: types::ProtocolLabel,
+        R: Role,
+        Dur: types::DurationMarker,
+        T: TSession<IO> + FingerprintOf,
+    > FingerprintOf for TDelay<IO, Lbl, R, Dur, T>
+{
+    const FINGERPRINT: u64 = node(
+        CombinatorKind::Delay,
+        &[
+            label_word::<Lbl>(),
+            role_word::<R>(),
+            duration_word(Dur::DURATION),
+            <T as FingerprintOf>::FINGERPRINT,
+        ],
+    );
+}
+
+impl<
+        IO,
+        Lbl: types::ProtocolLabel,
+        R: Role,
+        Dur: types::DurationMarker,
+        T: TSession<IO> + FingerprintOf,
+    > FingerprintOf for TDeadline<IO, Lbl, R, Dur, T>
+{
+    const FINGERPRINT: u64 = node(
+        CombinatorKind::Deadline,
+        &[
+            label_word::<Lbl>(),
+            role_word::<R>(),
+            duration_word(Dur::DURATION),
+            <T as FingerprintOf>::FINGERPRINT,
+        ],
+    );
+}
+
+impl<
+        IO,
+        Lbl: types::ProtocolLabel,
+        Scope: TSession<IO> + FingerprintOf,
+        Interruptor: Role,
+        Handler: TSession<IO> + FingerprintOf,
+    > FingerprintOf for TInterrupt<IO, Lbl, Scope, Interruptor, Handler>
+{
+    const FINGERPRINT: u64 = node(
+        CombinatorKind::Interrupt,
+        &[
+            label_word::<Lbl>(),
+            role_word::<Interruptor>(),
+            <Scope as FingerprintOf>::FINGERPRINT,
+            <Handler as FingerprintOf>::FINGERPRINT,
+        ],
+    );
+}
+
+impl<
+        IO,
+        Lbl: types::ProtocolLabel,
+        Decider: Role,
+        Body: TSession<IO> + FingerprintOf,
+        Cont: TSession<IO> + FingerprintOf,
+    > FingerprintOf for TOptional<IO, Lbl, Decider, Body, Cont>
+{
+    const FINGERPRINT: u64 = node(
+        CombinatorKind::Optional,
+        &[
+            label_word::<Lbl>(),
+            role_word::<Decider>(),
+            <Body as FingerprintOf>::FINGERPRINT,
+            <Cont as FingerprintOf>::FINGERPRINT,
+        ],
+    );
+}
+
+impl<Pred, Inner: FingerprintOf> FingerprintOf for TRefine<Pred, Inner> {
+    const FINGERPRINT: u64 = node(
+        CombinatorKind::Refine,
+        &[<Inner as FingerprintOf>::FINGERPRINT],
+    );
+}
+
+impl<Meta, Inner: FingerprintOf> FingerprintOf for TAnnotate<Meta, Inner> {
+    const FINGERPRINT: u64 = <Inner as FingerprintOf>::FINGERPRINT;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R: Role, Err> FingerprintOf for TThrow<IO, Lbl, R, Err> {
+    const FINGERPRINT: u64 = node(
+        CombinatorKind::Throw,
+        &[label_word::<Lbl>(), role_word::<R>()],
+    );
+}
+
+impl<
+        IO,
+        Lbl: types::ProtocolLabel,
+        Body: TSession<IO> + FingerprintOf,
+        Handler: TSession<IO> + FingerprintOf,
+    > FingerprintOf for TCatch<IO, Lbl, Body, Handler>
+{
+    const FINGERPRINT: u64 = node(
+        CombinatorKind::Catch,
+        &[
+            label_word::<Lbl>(),
+            <Body as FingerprintOf>::FINGERPRINT,
+            <Handler as FingerprintOf>::FINGERPRINT,
+        ],
+    );
+}
@@ -0,0 +1,719 @@
+//! # Payload Footprints
+//!
+//! This module computes, at compile time, how much memory the payloads of a
+//! global protocol need, so that embedded peers can size their buffers
+//! statically.
+//!
+//! Key components:
+//!
+//! - `SizeOf`: The size in bytes of a message type, implemented by the user
+//! - `MaxPayloadSize`: The largest payload of any message of a protocol
+//! - `PayloadBufferOf`: The bytes of the messages a role receives on the heaviest run
+//! - `MaxPayloadSizeEach` / `PayloadBufferOfEach`: The same for a type-level list of protocols
+//!
+//! Messages, broadcasts and anycasts carry a payload. Local actions,
+//! delegations, connects, disconnects and throws carry none. As elsewhere,
+//! the footprint follows the protocol as written: a loop body is counted
+//! once, so a buffer sized by [`PayloadBufferOf`] holds the messages of one
+//! iteration.
+
+use super::base::{Cons, Nil};
+use super::global::*;
+use super::local::{InRoleSet, RoleEq};
+use crate::types;
+
+/// The size in bytes a message type takes in a buffer.
+///
+/// Implement it for every message type of a protocol, usually as
+/// `core::mem::size_of::<Self>()` or as the size of its wire encoding. The
+/// marker messages of the crate carry no data and have size zero; a
+/// [`Tagged`](types::Tagged) message has the size of its payload.
+pub trait SizeOf {
+    /// The size of the message, in bytes.
+    const SIZE: usize;
+}
+
+impl SizeOf for types::Message {
+    const SIZE: usize = 0;
+}
+
+impl SizeOf for types::Response {
+    const SIZE: usize = 0;
+}
+
+impl SizeOf for types::Publish {
+    const SIZE: usize = 0;
+}
+
+impl SizeOf for types::Notify {
+    const SIZE: usize = 0;
+}
+
+impl SizeOf for types::Subscribe {
+    const SIZE: usize = 0;
+}
+
+impl<const TAG: u32, H: SizeOf> SizeOf for types::Tagged<TAG, H> {
+    const SIZE: usize = H::SIZE;
+}
+
+/// Helper trait for `PayloadBufferOf`: the size of `H` if the role receives
+/// it, zero otherwise.
+pub trait SizeCase<H> {
+    const SIZE: usize;
+}
+
+impl<H: SizeOf> SizeCase<H> for types::True {
+    const SIZE: usize = H::SIZE;
+}
+
+impl<H> SizeCase<H> for types::False {
+    const SIZE: usize = 0;
+}
+
+const fn max(a: usize, b: usize) -> usize {
+    if a > b {
+        a
+    } else {
+        b
+    }
+}
+
+/// The size of the largest payload of a global protocol, in bytes.
+///
+/// A buffer of this size holds any single message of the protocol.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Reading([u8; 12]);
+/// impl SizeOf for Reading { const SIZE: usize = core::mem::size_of::<Self>(); }
+///
+/// type Poll = TMsg<Http, EmptyLabel, TClient, TServer, Message,
+///     TMsg<Http, EmptyLabel, TServer, TClient, Reading, TEnd<Http>>>;
+/// const FRAME: usize = <Poll as MaxPayloadSize>::MAX_PAYLOAD;
+/// assert_eq!(FRAME, 12);
+/// ```
+pub trait MaxPayloadSize {
+    /// The size of the largest payload, in bytes.
+    const MAX_PAYLOAD: usize;
+}
+
+/// The size of the largest payload of a type-level list of protocols.
+///
+/// - Used by n-ary combinators to measure their branches.
+pub trait MaxPayloadSizeEach {
+    /// The size of the largest payload, in bytes.
+    const MAX_PAYLOAD: usize;
+}
+
+impl MaxPayloadSizeEach for Nil {
+    const MAX_PAYLOAD: usize = 0;
+}
+
+impl<H: MaxPayloadSize, T: MaxPayloadSizeEach> MaxPayloadSizeEach for Cons<H, T> {
+    const MAX_PAYLOAD: usize = max(H::MAX_PAYLOAD, T::MAX_PAYLOAD);
+}
+
+/// The bytes of the messages role `Me` receives on the heaviest run of a
+/// global protocol.
+///
+/// - A message counts for its receiver, a broadcast for each of its
+///   receivers and an anycast for each worker that may take it.
+/// - The heaviest branch of a choice is taken. The branches of a parallel
+///   composition are added up, as their messages can arrive together.
+/// - A loop body is counted once. A scoped body and its handler, and an
+///   optional body and what follows it, are added up.
+/// - Roles are compared with `RoleEq`, and with `InRoleSet` for role sets.
+///
+/// A role that keeps every message it receives until the end of the protocol,
+/// or of a loop iteration, needs a buffer of this size.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Sensor; struct Hub;
+/// impl Role for Sensor {} impl Role for Hub {}
+/// impl RoleEq<Hub> for Sensor { type Output = False; }
+/// impl RoleEq<Hub> for Hub    { type Output = True; }
+/// struct Reading([u8; 12]);
+/// impl SizeOf for Reading { const SIZE: usize = core::mem::size_of::<Self>(); }
+///
+/// type Report = TMsg<Http, EmptyLabel, Sensor, Hub, Reading,
+///     TMsg<Http, EmptyLabel, Sensor, Hub, Reading,
+///     TMsg<Http, EmptyLabel, Hub, Sensor, Response, TEnd<Http>>>>;
+/// const INBOX: usize = <Report as PayloadBufferOf<Hub>>::BUFFER;
+/// assert_eq!(INBOX, 24);
+/// ```
+pub trait PayloadBufferOf<Me> {
+    /// The size of the messages received, in bytes.
+    const BUFFER: usize;
+}
+
+/// The bytes of the messages role `Me` receives in every protocol of a
+/// type-level list.
+///
+/// - `LONGEST` is the heaviest of them, as for the branches of a choice;
+///   `TOTAL` is their sum, as for parallel branches.
+pub trait PayloadBufferOfEach<Me> {
+    /// The bytes of the heaviest protocol of the list.
+    const LONGEST: usize;
+    /// The bytes of all protocols of the list.
+    const TOTAL: usize;
+}
+
+impl<Me> PayloadBufferOfEach<Me> for Nil {
+    const LONGEST: usize = 0;
+    const TOTAL: usize = 0;
+}
+
+impl<H: PayloadBufferOf<Me>, T: PayloadBufferOfEach<Me>, Me> PayloadBufferOfEach<Me>
+    for Cons<H, T>
+{
+    const LONGEST: usize = max(H::BUFFER, T::LONGEST);
+    const TOTAL: usize = H::BUFFER + T::TOTAL;
+}
+
+impl<IO, Lbl> MaxPayloadSize for TEnd<IO, Lbl> {
+    const MAX_PAYLOAD: usize = 0;
+}
+
+impl<IO, Lbl, R, H, T> MaxPayloadSize for TInteract<IO, Lbl, R, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: TSession<IO> + MaxPayloadSize,
+{
+    const MAX_PAYLOAD: usize = <T as MaxPayloadSize>::MAX_PAYLOAD;
+}
+
+impl<IO, Lbl, From, To, H, T> MaxPayloadSize for TMsg<IO, Lbl, From, To, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    H: SizeOf,
+    T: TSession<IO> + MaxPayloadSize,
+{
+    const MAX_PAYLOAD: usize = max(H::SIZE, <T as MaxPayloadSize>::MAX_PAYLOAD);
+}
+
+impl<IO, Lbl, From, ToSet, H, T> MaxPayloadSize for TBroadcast<IO, Lbl, From, ToSet, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    H: SizeOf,
+    T: TSession<IO> + MaxPayloadSize,
+{
+    const MAX_PAYLOAD: usize = max(H::SIZE, <T as MaxPayloadSize>::MAX_PAYLOAD);
+}
+
+impl<IO, Lbl, From, WorkerSet, H, T> MaxPayloadSize for TAnycast<IO, Lbl, From, WorkerSet, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    H: SizeOf,
+    T: TSession<IO> + MaxPayloadSize,
+{
+    const MAX_PAYLOAD: usize = max(H::SIZE, <T as MaxPayloadSize>::MAX_PAYLOAD);
+}
+
+impl<IO, Lbl, From, To, DelegatedEp, T> MaxPayloadSize
+    for TDelegate<IO, Lbl, From, To, DelegatedEp, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: TSession<IO> + MaxPayloadSize,
+{
+    const MAX_PAYLOAD: usize = <T as MaxPayloadSize>::MAX_PAYLOAD;
+}
+
+impl<IO, Lbl, A, B, Cont> MaxPayloadSize for TDisconnect<IO, Lbl, A, B, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Cont: TSession<IO> + MaxPayloadSize,
+{
+    const MAX_PAYLOAD: usize = <Cont as MaxPayloadSize>::MAX_PAYLOAD;
+}
+
+impl<IO, Lbl, A, B, Cont> MaxPayloadSize for TConnect<IO, Lbl, A, B, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Cont: TSession<IO> + MaxPayloadSize,
+{
+    const MAX_PAYLOAD: usize = <Cont as MaxPayloadSize>::MAX_PAYLOAD;
+}
+
+impl<IO, Lbl, R, Cont> MaxPayloadSize for TEndFor<IO, Lbl, R, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Cont: TSession<IO> + MaxPayloadSize,
+{
+    const MAX_PAYLOAD: usize = <Cont as MaxPayloadSize>::MAX_PAYLOAD;
+}
+
+impl<IO, Lbl, L, R> MaxPayloadSize for TChoice<IO, Lbl, L, R>
+where
+    Lbl: types::ProtocolLabel,
+    L: TSession<IO> + MaxPayloadSize,
+    R: TSession<IO> + MaxPayloadSize,
+{
+    const MAX_PAYLOAD: usize = max(
+        <L as MaxPayloadSize>::MAX_PAYLOAD,
+        <R as MaxPayloadSize>::MAX_PAYLOAD,
+    );
+}
+
+impl<IO, Lbl, Branches> MaxPayloadSize for TChoiceN<IO, Lbl, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: SessionList<IO> + MaxPayloadSizeEach,
+{
+    const MAX_PAYLOAD: usize = <Branches as MaxPayloadSizeEach>::MAX_PAYLOAD;
+}
+
+impl<IO, Lbl, Chooser, Branches> MaxPayloadSize for TSelect<IO, Lbl, Chooser, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: SessionList<IO> + MaxPayloadSizeEach,
+{
+    const MAX_PAYLOAD: usize = <Branches as MaxPayloadSizeEach>::MAX_PAYLOAD;
+}
+
+impl<IO, Lbl, Offeree, Branches> MaxPayloadSize for TOffer<IO, Lbl, Offeree, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: SessionList<IO> + MaxPayloadSizeEach,
+{
+    const MAX_PAYLOAD: usize = <Branches as MaxPayloadSizeEach>::MAX_PAYLOAD;
+}
+
+impl<IO, Lbl, High, Low> MaxPayloadSize for TPriorityChoice<IO, Lbl, High, Low>
+where
+    Lbl: types::ProtocolLabel,
+    High: TSession<IO> + MaxPayloadSize,
+    Low: TSession<IO> + MaxPayloadSize,
+{
+    const MAX_PAYLOAD: usize = max(
+        <High as MaxPayloadSize>::MAX_PAYLOAD,
+        <Low as MaxPayloadSize>::MAX_PAYLOAD,
+    );
+}
+
+impl<IO, Lbl, WeightL, L, WeightR, R> MaxPayloadSize for TProb<IO, Lbl, WeightL, L, WeightR, R>
+where
+    Lbl: types::ProtocolLabel,
+    WeightL: types::WeightMarker,
+    WeightR: types::WeightMarker,
+    L: TSession<IO> + MaxPayloadSize,
+    R: TSession<IO> + MaxPayloadSize,
+{
+    const MAX_PAYLOAD: usize = max(
+        <L as MaxPayloadSize>::MAX_PAYLOAD,
+        <R as MaxPayloadSize>::MAX_PAYLOAD,
+    );
+}
+
+impl<IO, Lbl, L, R, IsDisjoint> MaxPayloadSize for TPar<IO, Lbl, L, R, IsDisjoint>
+where
+    Lbl: types::ProtocolLabel,
+    L: TSession<IO> + MaxPayloadSize,
+    R: TSession<IO> + MaxPayloadSize,
+{
+    const MAX_PAYLOAD: usize = max(
+        <L as MaxPayloadSize>::MAX_PAYLOAD,
+        <R as MaxPayloadSize>::MAX_PAYLOAD,
+    );
+}
+
+impl<IO, Lbl, Branches> MaxPayloadSize for TParN<IO, Lbl, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: SessionList<IO> + MaxPayloadSizeEach,
+{
+    const MAX_PAYLOAD: usize = <Branches as MaxPayloadSizeEach>::MAX_PAYLOAD;
+}
+
+impl<IO, Lbl, Steps> MaxPayloadSize for TShuffle<IO, Lbl, Steps>
+where
+    Lbl: types::ProtocolLabel,
+    Steps: SessionList<IO> + MaxPayloadSizeEach,
+{
+    const MAX_PAYLOAD: usize = <Steps as MaxPayloadSizeEach>::MAX_PAYLOAD;
+}
+
+impl<IO, Lbl, S> MaxPayloadSize for TRec<IO, Lbl, S>
+where
+    Lbl: types::ProtocolLabel,
+    S: TSession<IO> + MaxPayloadSize,
+{
+    const MAX_PAYLOAD: usize = <S as MaxPayloadSize>::MAX_PAYLOAD;
+}
+
+impl<IO, Lbl, Var, Body> MaxPayloadSize for TRecX<IO, Lbl, Var, Body>
+where
+    Lbl: types::ProtocolLabel,
+    Body: TSession<IO> + MaxPayloadSize,
+{
+    const MAX_PAYLOAD: usize = <Body as MaxPayloadSize>::MAX_PAYLOAD;
+}
+
+impl<Var> MaxPayloadSize for TVar<Var> {
+    const MAX_PAYLOAD: usize = 0;
+}
+
+impl<IO, Lbl, Dur, Body, OnTimeout> MaxPayloadSize for TTimeout<IO, Lbl, Dur, Body, OnTimeout>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    Body: TSession<IO> + MaxPayloadSize,
+    OnTimeout: TSession<IO> + MaxPayloadSize,
+{
+    const MAX_PAYLOAD: usize = max(
+        <Body as MaxPayloadSize>::MAX_PAYLOAD,
+        <OnTimeout as MaxPayloadSize>::MAX_PAYLOAD,
+    );
+}
+
+impl<IO, Lbl, R, Dur, T> MaxPayloadSize for TDelay<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: TSession<IO> + MaxPayloadSize,
+{
+    const MAX_PAYLOAD: usize = <T as MaxPayloadSize>::MAX_PAYLOAD;
+}
+
+impl<IO, Lbl, R, Dur, T> MaxPayloadSize for TDeadline<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: TSession<IO> + MaxPayloadSize,
+{
+    const MAX_PAYLOAD: usize = <T as MaxPayloadSize>::MAX_PAYLOAD;
+}
+
+impl<IO, Lbl, Scope, Interruptor, Handler> MaxPayloadSize
+    for TInterrupt<IO, Lbl, Scope, Interruptor, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Scope: TSession<IO> + MaxPayloadSize,
+    Handler: TSession<IO> + MaxPayloadSize,
+{
+    const MAX_PAYLOAD: usize = max(
+        <Scope as MaxPayloadSize>::MAX_PAYLOAD,
+        <Handler as MaxPayloadSize>::MAX_PAYLOAD,
+    );
+}
+
+impl<IO, Lbl, Decider, Body, Cont> MaxPayloadSize for TOptional<IO, Lbl, Decider, Body, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Body: TSession<IO> + MaxPayloadSize,
+    Cont: TSession<IO> + MaxPayloadSize,
+{
+    const MAX_PAYLOAD: usize = max(
+        <Body as MaxPayloadSize>::MAX_PAYLOAD,
+        <Cont as MaxPayloadSize>::MAX_PAYLOAD,
+    );
+}
+
+impl<Pred, Inner> MaxPayloadSize for TRefine<Pred, Inner>
+where
+    Inner: MaxPayloadSize,
+{
+    const MAX_PAYLOAD: usize = <Inner as MaxPayloadSize>::MAX_PAYLOAD;
+}
+
+impl<Meta, Inner> MaxPayloadSize for TAnnotate<Meta, Inner>
+where
+    Inner: MaxPayloadSize,
+{
+    const MAX_PAYLOAD: usize = <Inner as MaxPayloadSize>::MAX_PAYLOAD;
+}
+
+impl<IO, Lbl, R, Err> MaxPayloadSize for TThrow<IO, Lbl, R, Err>
+where
+    Lbl: types::ProtocolLabel,
+{
+    const MAX_PAYLOAD: usize = 0;
+}
+
+impl<IO, Lbl, Body, Handler> MaxPayloadSize for TCatch<IO, Lbl, Body, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Body: TSession<IO> + MaxPayloadSize,
+    Handler: TSession<IO> + MaxPayloadSize,
+{
+    const MAX_PAYLOAD: usize = max(
+        <Body as MaxPayloadSize>::MAX_PAYLOAD,
+        <Handler as MaxPayloadSize>::MAX_PAYLOAD,
+    );
+}
+
+impl<IO, Lbl, Me> PayloadBufferOf<Me> for TEnd<IO, Lbl> {
+    const BUFFER: usize = 0;
+}
+
+impl<IO, Lbl, R, H, T, Me> PayloadBufferOf<Me> for TInteract<IO, Lbl, R, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: TSession<IO> + PayloadBufferOf<Me>,
+{
+    const BUFFER: usize = <T as PayloadBufferOf<Me>>::BUFFER;
+}
+
+impl<IO, Lbl, From, To, H, T, Me> PayloadBufferOf<Me> for TMsg<IO, Lbl, From, To, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    H: SizeOf,
+    To: RoleEq<Me>,
+    <To as RoleEq<Me>>::Output: SizeCase<H>,
+    T: TSession<IO> + PayloadBufferOf<Me>,
+{
+    const BUFFER: usize =
+        <<To as RoleEq<Me>>::Output as SizeCase<H>>::SIZE + <T as PayloadBufferOf<Me>>::BUFFER;
+}
+
+impl<IO, Lbl, From, ToSet, H, T, Me> PayloadBufferOf<Me> for TBroadcast<IO, Lbl, From, ToSet, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    H: SizeOf,
+    Me: InRoleSet<ToSet>,
+    <Me as InRoleSet<ToSet>>::Output: SizeCase<H>,
+    T: TSession<IO> + PayloadBufferOf<Me>,
+{
+    const BUFFER: usize = <<Me as InRoleSet<ToSet>>::Output as SizeCase<H>>::SIZE
+        + <T as PayloadBufferOf<Me>>::BUFFER;
+}
+
+impl<IO, Lbl, From, WorkerSet, H, T, Me> PayloadBufferOf<Me>
+    for TAnycast<IO, Lbl, From, WorkerSet, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    H: SizeOf,
+    Me: InRoleSet<WorkerSet>,
+    <Me as InRoleSet<WorkerSet>>::Output: SizeCase<H>,
+    T: TSession<IO> + PayloadBufferOf<Me>,
+{
+    const BUFFER: usize = <<Me as InRoleSet<WorkerSet>>::Output as SizeCase<H>>::SIZE
+        + <T as PayloadBufferOf<Me>>::BUFFER;
+}
+
+impl<IO, Lbl, From, To, DelegatedEp, T, Me> PayloadBufferOf<Me>
+    for TDelegate<IO, Lbl, From, To, DelegatedEp, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: TSession<IO> + PayloadBufferOf<Me>,
+{
+    const BUFFER: usize = <T as PayloadBufferOf<Me>>::BUFFER;
+}
+
+impl<IO, Lbl, A, B, Cont, Me> PayloadBufferOf<Me> for TDisconnect<IO, Lbl, A, B, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Cont: TSession<IO> + PayloadBufferOf<Me>,
+{
+    const BUFFER: usize = <Cont as PayloadBufferOf<Me>>::BUFFER;
+}
+
+impl<IO, Lbl, A, B, Cont, Me> PayloadBufferOf<Me> for TConnect<IO, Lbl, A, B, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Cont: TSession<IO> + PayloadBufferOf<Me>,
+{
+    const BUFFER: usize = <Cont as PayloadBufferOf<Me>>::BUFFER;
+}
+
+impl<IO, Lbl, R, Cont, Me> PayloadBufferOf<Me> for TEndFor<IO, Lbl, R, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Cont: TSession<IO> + PayloadBufferOf<Me>,
+{
+    const BUFFER: usize = <Cont as PayloadBufferOf<Me>>::BUFFER;
+}
+
+impl<IO, Lbl, L, R, Me> PayloadBufferOf<Me> for TChoice<IO, Lbl, L, R>
+where
+    Lbl: types::ProtocolLabel,
+    L: TSession<IO> + PayloadBufferOf<Me>,
+    R: TSession<IO> + PayloadBufferOf<Me>,
+{
+    const BUFFER: usize = max(
+        <L as PayloadBufferOf<Me>>::BUFFER,
+        <R as PayloadBufferOf<Me>>::BUFFER,
+    );
+}
+
+impl<IO, Lbl, Branches, Me> PayloadBufferOf<Me> for TChoiceN<IO, Lbl, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: SessionList<IO> + PayloadBufferOfEach<Me>,
+{
+    const BUFFER: usize = <Branches as PayloadBufferOfEach<Me>>::LONGEST;
+}
+
+impl<IO, Lbl, Chooser, Branches, Me> PayloadBufferOf<Me> for TSelect<IO, Lbl, Chooser, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: SessionList<IO> + PayloadBufferOfEach<Me>,
+{
+    const BUFFER: usize = <Branches as PayloadBufferOfEach<Me>>::LONGEST;
+}
+
+impl<IO, Lbl, Offeree, Branches, Me> PayloadBufferOf<Me> for TOffer<IO, Lbl, Offeree, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: SessionList<IO> + PayloadBufferOfEach<Me>,
+{
+    const BUFFER: usize = <Branches as PayloadBufferOfEach<Me>>::LONGEST;
+}
+
+impl<IO, Lbl, High, Low, Me> PayloadBufferOf<Me> for TPriorityChoice<IO, Lbl, High, Low>
+where
+    Lbl: types::ProtocolLabel,
+    High: TSession<IO> + PayloadBufferOf<Me>,
+    Low: TSession<IO> + PayloadBufferOf<Me>,
+{
+    const BUFFER: usize = max(
+        <High as PayloadBufferOf<Me>>::BUFFER,
+        <Low as PayloadBufferOf<Me>>::BUFFER,
+    );
+}
+
+impl<IO, Lbl, WeightL, L, WeightR, R, Me> PayloadBufferOf<Me>
+    for TProb<IO, Lbl, WeightL, L, WeightR, R>
+where
+    Lbl: types::ProtocolLabel,
+    WeightL: types::WeightMarker,
+    WeightR: types::WeightMarker,
+    L: TSession<IO> + PayloadBufferOf<Me>,
+    R: TSession<IO> + PayloadBufferOf<Me>,
+{
+    const BUFFER: usize = max(
+        <L as PayloadBufferOf<Me>>::BUFFER,
+        <R as PayloadBufferOf<Me>>::BUFFER,
+    );
+}
+
+impl<IO, Lbl, L, R, IsDisjoint, Me> PayloadBufferOf<Me> for TPar<IO, Lbl, L, R, IsDisjoint>
+where
+    Lbl: types::ProtocolLabel,
+    L: TSession<IO> + PayloadBufferOf<Me>,
+    R: TSession<IO> + PayloadBufferOf<Me>,
+{
+    const BUFFER: usize = <L as PayloadBufferOf<Me>>::BUFFER + <R as PayloadBufferOf<Me>>::BUFFER;
+}
+
+impl<IO, Lbl, Branches, Me> PayloadBufferOf<Me> for TParN<IO, Lbl, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: SessionList<IO> + PayloadBufferOfEach<Me>,
+{
+    const BUFFER: usize = <Branches as PayloadBufferOfEach<Me>>::TOTAL;
+}
+
+impl<IO, Lbl, Steps, Me> PayloadBufferOf<Me> for TShuffle<IO, Lbl, Steps>
+where
+    Lbl: types::ProtocolLabel,
+    Steps: SessionList<IO> + PayloadBufferOfEach<Me>,
+{
+    const BUFFER: usize = <Steps as PayloadBufferOfEach<Me>>::TOTAL;
+}
+
+impl<IO, Lbl, S, Me> PayloadBufferOf<Me> for TRec<IO, Lbl, S>
+where
+    Lbl: types::ProtocolLabel,
+    S: TSession<IO> + PayloadBufferOf<Me>,
+{
+    const BUFFER: usize = <S as PayloadBufferOf<Me>>::BUFFER;
+}
+
+impl<IO, Lbl, Var, Body, Me> PayloadBufferOf<Me> for TRecX<IO, Lbl, Var, Body>
+where
+    Lbl: types::ProtocolLabel,
+    Body: TSession<IO> + PayloadBufferOf<Me>,
+{
+    const BUFFER: usize = <Body as PayloadBufferOf<Me>>::BUFFER;
+}
+
+impl<Var, Me> PayloadBufferOf<Me> for TVar<Var> {
+    const BUFFER: usize = 0;
+}
+
+impl<IO, Lbl, Dur, Body, OnTimeout, Me> PayloadBufferOf<Me>
+    for TTimeout<IO, Lbl, Dur, Body, OnTimeout>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    Body: TSession<IO> + PayloadBufferOf<Me>,
+    OnTimeout: TSession<IO> + PayloadBufferOf<Me>,
+{
+    const BUFFER: usize =
+        <Body as PayloadBufferOf<Me>>::BUFFER + <OnTimeout as PayloadBufferOf<Me>>::BUFFER;
+}
+
+impl<IO, Lbl, R, Dur, T, Me> PayloadBufferOf<Me> for TDelay<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: TSession<IO> + PayloadBufferOf<Me>,
+{
+    const BUFFER: usize = <T as PayloadBufferOf<Me>>::BUFFER;
+}
+
+impl<IO, Lbl, R, Dur, T, Me> PayloadBufferOf<Me> for TDeadline<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: TSession<IO> + PayloadBufferOf<Me>,
+{
+    const BUFFER: usize = <T as PayloadBufferOf<Me>>::BUFFER;
+}
+
+impl<IO, Lbl, Scope, Interruptor, Handler, Me> PayloadBufferOf<Me>
+    for TInterrupt<IO, Lbl, Scope, Interruptor, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Scope: TSession<IO> + PayloadBufferOf<Me>,
+    Handler: TSession<IO> + PayloadBufferOf<Me>,
+{
+    const BUFFER: usize =
+        <Scope as PayloadBufferOf<Me>>::BUFFER + <Handler as PayloadBufferOf<Me>>::BUFFER;
+}
+
+impl<IO, Lbl, Decider, Body, Cont, Me> PayloadBufferOf<Me>
+    for TOptional<IO, Lbl, Decider, Body, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Body: TSession<IO> + PayloadBufferOf<Me>,
+    Cont: TSession<IO> + PayloadBufferOf<Me>,
+{
+    const BUFFER: usize =
+        <Body as PayloadBufferOf<Me>>::BUFFER + <Cont as PayloadBufferOf<Me>>::BUFFER;
+}
+
+impl<Pred, Inner, Me> PayloadBufferOf<Me> for TRefine<Pred, Inner>
+where
+    Inner: PayloadBufferOf<Me>,
+{
+    const BUFFER: usize = <Inner as PayloadBufferOf<Me>>::BUFFER;
+}
+
+impl<Meta, Inner, Me> PayloadBufferOf<Me> for TAnnotate<Meta, Inner>
+where
+    Inner: PayloadBufferOf<Me>,
+{
+    const BUFFER: usize = <Inner as PayloadBufferOf<Me>>::BUFFER;
+}
+
+impl<IO, Lbl, R, Err, Me> PayloadBufferOf<Me> for TThrow<IO, Lbl, R, Err>
+where
+    Lbl: types::ProtocolLabel,
+{
+    const BUFFER: usize = 0;
+}
+
+impl<IO, Lbl, Body, Handler, Me> PayloadBufferOf<Me> for TCatch<IO, Lbl, Body, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Body: TSession<IO> + PayloadBufferOf<Me>,
+    Handler: TSession<IO> + PayloadBufferOf<Me>,
+{
+    const BUFFER: usize =
+        <Body as PayloadBufferOf<Me>>::BUFFER + <Handler as PayloadBufferOf<Me>>::BUFFER;
+}
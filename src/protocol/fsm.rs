@@ -0,0 +1,466 @@
+//! # Finite-State-Machine View
+//!
+//! This module converts local session types into state machines, the shape
+//! code generators, diagram exporters and runtime monitors work with.
+//!
+//! Key components:
+//!
+//! - `ToFsm`: The transitions of a local session type, starting in the type itself
+//! - `TransitionsFrom`: The transitions of a local session type, starting in a given state
+//! - `TransitionsFromEach` / `ForkEach`: The same for every branch of a type-level list
+//! - `Transition`: A type-level transition between two states
+//! - `Out` / `In` / `Internal` / `Fork`: Type-level transition directions
+//! - `FsmTransition` / `TransitionData`: Runtime description of a transition list
+//!
+//! A state is identified by the local session type left to run, so the start
+//! state of `T` is `T` itself and every transition leads to the continuation
+//! of the step it performs. Choices do not get states of their own: the first
+//! transitions of each branch leave the state of the choice.
+//!
+//! A few constructs are approximated:
+//!
+//! - `EpVar<Var>` as a target stands for the state of the enclosing `EpRec`
+//!   bound to `Var`.
+//! - Parallel compositions and shuffles `Fork` into one sub-machine per branch.
+//! - Timer, interrupt and failure edges leave the entry state of their scope.
+//! - A state reached along several paths lists its transitions once per path,
+//!   so the transition list should be read as a set.
+
+use super::base::{Cons, Nil};
+use super::local::*;
+use super::utils::Concat;
+use crate::types;
+use core::any::type_name;
+use core::marker::PhantomData;
+
+/// Direction of a transition, as runtime data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    /// The role sends a message.
+    Out,
+    /// The role receives a message.
+    In,
+    /// The role changes state without communicating (timers, channel setup and teardown).
+    Internal,
+    /// The role starts one of several concurrent sub-machines.
+    Fork,
+}
+
+/// Type-level marker for a send.
+pub struct Out;
+/// Type-level marker for a receive.
+pub struct In;
+/// Type-level marker for a step without communication.
+pub struct Internal;
+/// Type-level marker for entering a concurrent branch.
+pub struct Fork;
+
+/// Trait for type-level transition directions.
+pub trait DirectionMarker {
+    const DIRECTION: Direction;
+}
+
+impl DirectionMarker for Out {
+    const DIRECTION: Direction = Direction::Out;
+}
+
+impl DirectionMarker for In {
+    const DIRECTION: Direction = Direction::In;
+}
+
+impl DirectionMarker for Internal {
+    const DIRECTION: Direction = Direction::Internal;
+}
+
+impl DirectionMarker for Fork {
+    const DIRECTION: Direction = Direction::Fork;
+}
+
+/// A transition from state `From` to state `To`.
+///
+/// - `Dir`: Direction marker (`Out`, `In`, `Internal` or `Fork`).
+/// - `Lbl`: Label of the step taking the transition.
+/// - `Msg`: Message type, or the duration, peer or error involved; `Void` when there is none.
+pub struct Transition<From, Dir, Lbl, Msg, To>(PhantomData<(From, Dir, Lbl, Msg, To)>);
+
+/// The transitions of a local session type, as a type-level list of
+/// `Transition`s starting in `State`.
+pub trait TransitionsFrom<State> {
+    type Output;
+}
+
+/// The transitions of every local session type in a type-level list, all
+/// starting in `State`.
+pub trait TransitionsFromEach<State> {
+    type Output;
+}
+
+impl<State> TransitionsFromEach<State> for Nil {
+    type Output = Nil;
+}
+
+impl<State, H, T> TransitionsFromEach<State> for Cons<H, T>
+where
+    H: TransitionsFrom<State>,
+    T: TransitionsFromEach<State>,
+    <H as TransitionsFrom<State>>::Output: Concat<<T as TransitionsFromEach<State>>::Output>,
+{
+    type Output = <<H as TransitionsFrom<State>>::Output as Concat<
+        <T as TransitionsFromEach<State>>::Output,
+    >>::Output;
+}
+
+/// A `Fork` transition from `State` into every branch of a type-level list,
+/// followed by the transitions of each branch.
+pub trait ForkEach<State, Lbl> {
+    type Output;
+}
+
+impl<State, Lbl> ForkEach<State, Lbl> for Nil {
+    type Output = Nil;
+}
+
+impl<State, Lbl, H, T> ForkEach<State, Lbl> for Cons<H, T>
+where
+    H: TransitionsFrom<H>,
+    T: ForkEach<State, Lbl>,
+    <H as TransitionsFrom<H>>::Output: Concat<<T as ForkEach<State, Lbl>>::Output>,
+{
+    type Output = Cons<
+        Transition<State, Fork, Lbl, Void, H>,
+        <<H as TransitionsFrom<H>>::Output as Concat<<T as ForkEach<State, Lbl>>::Output>>::Output,
+    >;
+}
+
+/// The state machine of a local session type.
+///
+/// `Transitions` is a type-level list of `Transition`s whose start state is
+/// `Self`.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// type Done = EpEnd<Http, EmptyLabel, TClient>;
+/// type Answer = EpRecv<Http, EmptyLabel, TClient, Response, Done>;
+/// type Ask = EpSend<Http, EmptyLabel, TClient, Message, Answer>;
+/// assert_type_eq!(
+///     <Ask as ToFsm>::Transitions,
+///     tlist!(
+///         Transition<Ask, Out, EmptyLabel, Message, Answer>,
+///         Transition<Answer, In, EmptyLabel, Response, Done>
+///     )
+/// );
+/// assert_eq!(<Ask as ToFsm>::transitions()[1].direction, Direction::In);
+/// ```
+pub trait ToFsm {
+    type Transitions;
+
+    /// Runtime description of `Transitions`.
+    fn transitions() -> Vec<FsmTransition>
+    where
+        Self::Transitions: TransitionData,
+    {
+        let mut out = Vec::new();
+        <Self::Transitions as TransitionData>::append_to(&mut out);
+        out
+    }
+}
+
+impl<T: TransitionsFrom<T>> ToFsm for T {
+    type Transitions = <T as TransitionsFrom<T>>::Output;
+}
+
+/// Runtime description of a transition; labels are identified by their names
+/// (see `ProtocolLabel::NAME`), states and messages by their type names.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FsmTransition {
+    pub from: &'static str,
+    pub direction: Direction,
+    pub label: &'static str,
+    pub message: &'static str,
+    pub to: &'static str,
+}
+
+/// Converts a type-level list of `Transition`s into runtime data.
+pub trait TransitionData {
+    /// Appends a description of every transition in the list to `out`.
+    fn append_to(out: &mut Vec<FsmTransition>);
+}
+
+impl TransitionData for Nil {
+    fn append_to(_out: &mut Vec<FsmTransition>) {}
+}
+
+impl<From, Dir: DirectionMarker, Lbl: types::ProtocolLabel, Msg, To, T: TransitionData>
+    TransitionData for Cons<Transition<From, Dir, Lbl, Msg, To>, T>
+{
+    fn append_to(out: &mut Vec<FsmTransition>) {
+        out.push(FsmTransition {
+            from: type_name::<From>(),
+            direction: Dir::DIRECTION,
+            label: Lbl::name(),
+            message: type_name::<Msg>(),
+            to: type_name::<To>(),
+        });
+        T::append_to(out);
+    }
+}
+
+// Final states
+
+impl<State, IO, Lbl: types::ProtocolLabel, R> TransitionsFrom<State> for EpEnd<IO, Lbl, R> {
+    type Output = Nil;
+}
+
+impl<State, IO, Lbl: types::ProtocolLabel, R> TransitionsFrom<State> for EpSkip<IO, Lbl, R> {
+    type Output = Nil;
+}
+
+impl<State, Var> TransitionsFrom<State> for EpVar<Var> {
+    type Output = Nil;
+}
+
+impl<State, IO, Lbl: types::ProtocolLabel, Me, Err> TransitionsFrom<State>
+    for EpThrow<IO, Lbl, Me, Err>
+{
+    type Output = Nil;
+}
+
+// Communication
+
+impl<State, IO, Lbl: types::ProtocolLabel, R, H, T: TransitionsFrom<T>> TransitionsFrom<State>
+    for EpSend<IO, Lbl, R, H, T>
+{
+    type Output = Cons<Transition<State, Out, Lbl, H, T>, <T as TransitionsFrom<T>>::Output>;
+}
+
+impl<State, IO, Lbl: types::ProtocolLabel, R, H, T: TransitionsFrom<T>> TransitionsFrom<State>
+    for EpRecv<IO, Lbl, R, H, T>
+{
+    type Output = Cons<Transition<State, In, Lbl, H, T>, <T as TransitionsFrom<T>>::Output>;
+}
+
+impl<State, IO, Lbl: types::ProtocolLabel, Me, ToSet, H, T: TransitionsFrom<T>>
+    TransitionsFrom<State> for EpBroadcast<IO, Lbl, Me, ToSet, H, T>
+{
+    type Output = Cons<Transition<State, Out, Lbl, H, T>, <T as TransitionsFrom<T>>::Output>;
+}
+
+impl<State, IO, Lbl: types::ProtocolLabel, Me, FromSet, H, T: TransitionsFrom<T>>
+    TransitionsFrom<State> for EpGather<IO, Lbl, Me, FromSet, H, T>
+{
+    type Output = Cons<Transition<State, In, Lbl, H, T>, <T as TransitionsFrom<T>>::Output>;
+}
+
+impl<State, IO, Lbl: types::ProtocolLabel, Me, DelegatedEp, Cont: TransitionsFrom<Cont>>
+    TransitionsFrom<State> for EpDelegate<IO, Lbl, Me, DelegatedEp, Cont>
+{
+    type Output = Cons<
+        Transition<State, Out, Lbl, DelegatedEp, Cont>,
+        <Cont as TransitionsFrom<Cont>>::Output,
+    >;
+}
+
+// Choices: branches leave the state of the choice
+
+impl<State, IO, Lbl: types::ProtocolLabel, Me, Branches: TransitionsFromEach<State>>
+    TransitionsFrom<State> for EpChoiceN<IO, Lbl, Me, Branches>
+{
+    type Output = <Branches as TransitionsFromEach<State>>::Output;
+}
+
+impl<State, IO, Lbl: types::ProtocolLabel, Me, Branches: TransitionsFromEach<State>>
+    TransitionsFrom<State> for EpSelect<IO, Lbl, Me, Branches>
+{
+    type Output = <Branches as TransitionsFromEach<State>>::Output;
+}
+
+impl<State, IO, Lbl: types::ProtocolLabel, Me, Branches: TransitionsFromEach<State>>
+    TransitionsFrom<State> for EpOffer<IO, Lbl, Me, Branches>
+{
+    type Output = <Branches as TransitionsFromEach<State>>::Output;
+}
+
+impl<State, IO, Lbl, Me, High, Low> TransitionsFrom<State>
+    for EpPriorityOffer<IO, Lbl, Me, High, Low>
+where
+    Lbl: types::ProtocolLabel,
+    High: TransitionsFrom<State>,
+    Low: TransitionsFrom<State>,
+    <High as TransitionsFrom<State>>::Output: Concat<<Low as TransitionsFrom<State>>::Output>,
+{
+    type Output = <<High as TransitionsFrom<State>>::Output as Concat<
+        <Low as TransitionsFrom<State>>::Output,
+    >>::Output;
+}
+
+// Optional steps: run the body and then the continuation, or skip to the continuation
+
+impl<State, IO, Lbl, Me, Body, Cont> TransitionsFrom<State>
+    for EpOptionalSelect<IO, Lbl, Me, Body, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Body: ComposeEp<Cont>,
+    Cont: TransitionsFrom<Cont>,
+    <Body as ComposeEp<Cont>>::Output: TransitionsFrom<State>,
+    <<Body as ComposeEp<Cont>>::Output as TransitionsFrom<State>>::Output:
+        Concat<<Cont as TransitionsFrom<Cont>>::Output>,
+{
+    type Output = Cons<
+        Transition<State, Out, Lbl, Void, Cont>,
+        <<<Body as ComposeEp<Cont>>::Output as TransitionsFrom<State>>::Output as Concat<
+            <Cont as TransitionsFrom<Cont>>::Output,
+        >>::Output,
+    >;
+}
+
+impl<State, IO, Lbl, Me, Body, Cont> TransitionsFrom<State>
+    for EpOptionalOffer<IO, Lbl, Me, Body, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Body: ComposeEp<Cont>,
+    Cont: TransitionsFrom<Cont>,
+    <Body as ComposeEp<Cont>>::Output: TransitionsFrom<State>,
+    <<Body as ComposeEp<Cont>>::Output as TransitionsFrom<State>>::Output:
+        Concat<<Cont as TransitionsFrom<Cont>>::Output>,
+{
+    type Output = Cons<
+        Transition<State, In, Lbl, Void, Cont>,
+        <<<Body as ComposeEp<Cont>>::Output as TransitionsFrom<State>>::Output as Concat<
+            <Cont as TransitionsFrom<Cont>>::Output,
+        >>::Output,
+    >;
+}
+
+// Concurrency: one sub-machine per branch
+
+impl<State, IO, Lbl, Me, L, R> TransitionsFrom<State> for EpPar<IO, Lbl, Me, L, R>
+where
+    Lbl: types::ProtocolLabel,
+    Cons<L, Cons<R, Nil>>: ForkEach<State, Lbl>,
+{
+    type Output = <Cons<L, Cons<R, Nil>> as ForkEach<State, Lbl>>::Output;
+}
+
+impl<State, IO, Lbl: types::ProtocolLabel, Me, Branches: ForkEach<State, Lbl>>
+    TransitionsFrom<State> for EpParN<IO, Lbl, Me, Branches>
+{
+    type Output = <Branches as ForkEach<State, Lbl>>::Output;
+}
+
+impl<State, IO, Lbl: types::ProtocolLabel, Me, Steps: ForkEach<State, Lbl>> TransitionsFrom<State>
+    for EpShuffle<IO, Lbl, Me, Steps>
+{
+    type Output = <Steps as ForkEach<State, Lbl>>::Output;
+}
+
+// Recursion: the loop starts in the state of the binder
+
+impl<State, IO, Lbl: types::ProtocolLabel, Var, Body: TransitionsFrom<State>> TransitionsFrom<State>
+    for EpRec<IO, Lbl, Var, Body>
+{
+    type Output = <Body as TransitionsFrom<State>>::Output;
+}
+
+impl<State, Pred, Inner: TransitionsFrom<State>> TransitionsFrom<State> for EpRefine<Pred, Inner> {
+    type Output = <Inner as TransitionsFrom<State>>::Output;
+}
+
+// Timing
+
+impl<State, IO, Lbl: types::ProtocolLabel, Me, Dur, T: TransitionsFrom<T>> TransitionsFrom<State>
+    for EpDelay<IO, Lbl, Me, Dur, T>
+{
+    type Output = Cons<Transition<State, Internal, Lbl, Dur, T>, <T as TransitionsFrom<T>>::Output>;
+}
+
+impl<State, IO, Lbl: types::ProtocolLabel, Me, Dur, T: TransitionsFrom<T>> TransitionsFrom<State>
+    for EpDeadline<IO, Lbl, Me, Dur, T>
+{
+    type Output = Cons<Transition<State, Internal, Lbl, Dur, T>, <T as TransitionsFrom<T>>::Output>;
+}
+
+impl<State, IO, Lbl, Me, Dur, Body, OnTimeout> TransitionsFrom<State>
+    for EpTimeout<IO, Lbl, Me, Dur, Body, OnTimeout>
+where
+    Lbl: types::ProtocolLabel,
+    Body: TransitionsFrom<State>,
+    OnTimeout: TransitionsFrom<OnTimeout>,
+    <Body as TransitionsFrom<State>>::Output:
+        Concat<<OnTimeout as TransitionsFrom<OnTimeout>>::Output>,
+{
+    type Output = Cons<
+        Transition<State, Internal, Lbl, Dur, OnTimeout>,
+        <<Body as TransitionsFrom<State>>::Output as Concat<
+            <OnTimeout as TransitionsFrom<OnTimeout>>::Output,
+        >>::Output,
+    >;
+}
+
+// Interrupts and failures: the handler is entered from the scope's entry state
+
+impl<State, IO, Lbl, Me, Scope, Handler> TransitionsFrom<State>
+    for EpInterrupt<IO, Lbl, Me, Scope, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Scope: TransitionsFrom<State>,
+    Handler: TransitionsFrom<Handler>,
+    <Scope as TransitionsFrom<State>>::Output:
+        Concat<<Handler as TransitionsFrom<Handler>>::Output>,
+{
+    type Output = Cons<
+        Transition<State, Out, Lbl, Void, Handler>,
+        <<Scope as TransitionsFrom<State>>::Output as Concat<
+            <Handler as TransitionsFrom<Handler>>::Output,
+        >>::Output,
+    >;
+}
+
+impl<State, IO, Lbl, Me, Scope, Handler> TransitionsFrom<State>
+    for EpInterruptible<IO, Lbl, Me, Scope, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Scope: TransitionsFrom<State>,
+    Handler: TransitionsFrom<Handler>,
+    <Scope as TransitionsFrom<State>>::Output:
+        Concat<<Handler as TransitionsFrom<Handler>>::Output>,
+{
+    type Output = Cons<
+        Transition<State, In, Lbl, Void, Handler>,
+        <<Scope as TransitionsFrom<State>>::Output as Concat<
+            <Handler as TransitionsFrom<Handler>>::Output,
+        >>::Output,
+    >;
+}
+
+impl<State, IO, Lbl, Me, Body, Handler> TransitionsFrom<State>
+    for EpCatch<IO, Lbl, Me, Body, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Body: TransitionsFrom<State>,
+    Handler: TransitionsFrom<Handler>,
+    <Body as TransitionsFrom<State>>::Output: Concat<<Handler as TransitionsFrom<Handler>>::Output>,
+{
+    type Output = Cons<
+        Transition<State, Internal, Lbl, Void, Handler>,
+        <<Body as TransitionsFrom<State>>::Output as Concat<
+            <Handler as TransitionsFrom<Handler>>::Output,
+        >>::Output,
+    >;
+}
+
+// Connections
+
+impl<State, IO, Lbl: types::ProtocolLabel, Me, Peer, Cont: TransitionsFrom<Cont>>
+    TransitionsFrom<State> for EpClose<IO, Lbl, Me, Peer, Cont>
+{
+    type Output =
+        Cons<Transition<State, Internal, Lbl, Peer, Cont>, <Cont as TransitionsFrom<Cont>>::Output>;
+}
+
+impl<State, IO, Lbl: types::ProtocolLabel, Me, Peer, Cont: TransitionsFrom<Cont>>
+    TransitionsFrom<State> for EpOpen<IO, Lbl, Me, Peer, Cont>
+{
+    type Output =
+        Cons<Transition<State, Internal, Lbl, Peer, Cont>, <Cont as TransitionsFrom<Cont>>::Output>;
+}
@@ -9,15 +9,38 @@
 //! - `TSession`: Core trait for all global session type combinators
 //! - `TEnd`: Protocol termination
 //! - `TInteract`: Individual interaction between roles
+//! - `TMsg`: Interaction with explicit sender and receiver roles
+//! - `TBroadcast`: One sender delivering the same message to a set of roles
+//! - `TAnycast`: One sender delivering a message to any one role of a set
+//! - `TDelegate`: One role handing the rest of another session to a peer
+//! - `TDisconnect`: Two roles closing their channel while the protocol continues
+//! - `TConnect`: Two roles opening a channel mid-protocol
+//! - `TEndFor`: One role leaving the session while the others continue
 //! - `TChoice`: Binary protocol choice
+//! - `TChoiceN`: N-ary protocol choice over a type-level list of branches
+//! - `TSelect` / `TOffer`: Directed choice naming the deciding or reacting role
+//! - `TPriorityChoice`: Binary choice preferring one branch when both are enabled
+//! - `TProb`: Binary choice with type-level branch weights
+//! - `TOptional`: Step that a deciding role may skip
 //! - `TPar`: Parallel protocol composition
+//! - `TParN`: N-ary parallel composition over a type-level list of branches
+//! - `TShuffle`: Steps that each happen exactly once, in any order
 //! - `TRec`: Recursive protocol definition
+//! - `TRecX` / `TVar`: Recursion binder and the variable that jumps back to it
+//! - `TTimeout`: Protocol fragment that must complete within a duration
+//! - `TDelay` / `TDeadline`: Minimum waits and absolute deadlines for a role
+//! - `TInterrupt`: Interruptible scope with a handler
+//! - `TRefine`: Predicate attached to a protocol fragment
+//! - `TAnnotate`: Marker metadata attached to a protocol fragment
+//! - `TThrow` / `TCatch`: Raising a failure and the scope that handles it
 //!
 //! Global protocols are designed to be projected onto specific roles to
 //! produce local (endpoint) protocols that describe the behavior of
 //! individual participants.
 
 use super::base::{Cons, Nil};
+use super::utils::Disjoint;
+use crate::introspection::{PairwiseDisjoint, UniqueRoles};
 use crate::sealed;
 use crate::types;
 use core::marker::PhantomData;
@@ -25,7 +48,10 @@ use core::marker::PhantomData;
 /// Core trait for all global session type combinators.
 ///
 /// - `IO`: Protocol marker type (e.g., Http, Mqtt).
-/// - Implemented by all protocol combinators (TEnd, TInteract, TChoice, TPar, TRec).
+/// - Implemented by all protocol combinators (TEnd, TInteract, TMsg, TBroadcast, TAnycast,
+///   TDelegate, TDisconnect, TConnect, TEndFor, TChoice, TChoiceN, TPriorityChoice, TProb,
+///   TSelect, TOffer, TOptional, TPar, TParN, TShuffle, TRec, TRecX, TVar, TTimeout, TDelay,
+///   TDeadline, TInterrupt, TRefine, TAnnotate, TThrow, TCatch).
 /// - Used for type-level composition and compile-time protocol checks.
 pub trait TSession<IO>: sealed::Sealed {
     /// Compose this session with another session of the same IO type.
@@ -57,7 +83,9 @@ impl<IO, Lbl> TSession<IO> for TEnd<IO, Lbl> {
 /// - `H`: Message type being sent or received.
 /// - `T`: Continuation protocol after this interaction.
 ///
-/// Used to model a single send/receive step in a protocol.
+/// Used to model a single send/receive step in a protocol. Every role other
+/// than `R` projects the step as a receive; use `TMsg` to name the receiver so
+/// that the remaining roles skip it.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
 pub struct TInteract<IO, Lbl: types::ProtocolLabel, R, H, T: TSession<IO>>(
     PhantomData<(IO, Lbl, R, H, T)>,
@@ -74,6 +102,267 @@ impl<IO, Lbl: types::ProtocolLabel, R, H, T: TSession<IO>> TSession<IO>
     const IS_EMPTY: bool = false;
 }
 
+/// A single message with both endpoints named explicitly.
+///
+/// - `IO`: Protocol marker type (e.g., Http, Mqtt).
+/// - `Lbl`: Label for this interaction (for projection and debugging).
+/// - `From`: Role sending the message.
+/// - `To`: Role receiving the message.
+/// - `H`: Message type being sent.
+/// - `T`: Continuation protocol after this interaction.
+///
+/// Unlike `TInteract`, which names only one role and treats every other role
+/// as a receiver, `TMsg` makes multiparty protocols unambiguous: roles other
+/// than `From` and `To` do not take part in this step.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct TMsg<IO, Lbl: types::ProtocolLabel, From, To, H, T: TSession<IO>>(
+    PhantomData<(IO, Lbl, From, To, H, T)>,
+);
+
+impl<IO, Lbl: types::ProtocolLabel, From, To, H, T: TSession<IO>> sealed::Sealed
+    for TMsg<IO, Lbl, From, To, H, T>
+{
+}
+impl<IO, Lbl: types::ProtocolLabel, From, To, H, T: TSession<IO>> TSession<IO>
+    for TMsg<IO, Lbl, From, To, H, T>
+{
+    type Compose<Rhs: TSession<IO>> = TMsg<IO, Lbl, From, To, H, T::Compose<Rhs>>;
+    const IS_EMPTY: bool = false;
+}
+
+/// A single message delivered from one sender to every role in a set.
+///
+/// - `IO`: Protocol marker type (e.g., Http, Mqtt).
+/// - `Lbl`: Label for this interaction (for projection and debugging).
+/// - `From`: Role sending the message.
+/// - `ToSet`: Type-level list of roles receiving the message.
+/// - `H`: Message type being sent.
+/// - `T`: Continuation protocol after this interaction.
+///
+/// The sender performs one send; each member of `ToSet` receives. Roles
+/// outside the set do not take part in this step. The sender must not be a
+/// member of its own `ToSet`.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// // The broker publishes one notification to both workers
+/// type Publish = TBroadcast<Mqtt, EmptyLabel, TBroker, tlist!(TClient, TWorker), Notify, TEnd<Mqtt>>;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct TBroadcast<IO, Lbl: types::ProtocolLabel, From, ToSet, H, T: TSession<IO>>(
+    PhantomData<(IO, Lbl, From, ToSet, H, T)>,
+);
+
+impl<IO, Lbl: types::ProtocolLabel, From, ToSet, H, T: TSession<IO>> sealed::Sealed
+    for TBroadcast<IO, Lbl, From, ToSet, H, T>
+{
+}
+impl<IO, Lbl: types::ProtocolLabel, From, ToSet, H, T: TSession<IO>> TSession<IO>
+    for TBroadcast<IO, Lbl, From, ToSet, H, T>
+{
+    type Compose<Rhs: TSession<IO>> = TBroadcast<IO, Lbl, From, ToSet, H, T::Compose<Rhs>>;
+    const IS_EMPTY: bool = false;
+}
+
+/// A single message delivered from one sender to any one role in a set.
+///
+/// - `IO`: Protocol marker type (e.g., Http, Mqtt).
+/// - `Lbl`: Label for this interaction (for projection and debugging).
+/// - `From`: Role sending the message.
+/// - `WorkerSet`: Type-level list of roles, one of which receives the message.
+/// - `H`: Message type being sent.
+/// - `T`: Continuation protocol after this interaction.
+///
+/// The sender performs one send and the runtime picks which member of
+/// `WorkerSet` receives it, as in a load-balanced worker pool. Each member
+/// therefore offers a choice between receiving the message and skipping this
+/// step. Roles outside the set do not take part. The sender must not be a
+/// member of its own `WorkerSet`.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct WorkerA; struct WorkerB;
+/// // The client hands a job to whichever worker is free
+/// type Dispatch = TAnycast<Http, EmptyLabel, TClient, tlist!(WorkerA, WorkerB), Message, TEnd<Http>>;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct TAnycast<IO, Lbl: types::ProtocolLabel, From, WorkerSet, H, T: TSession<IO>>(
+    PhantomData<(IO, Lbl, From, WorkerSet, H, T)>,
+);
+
+impl<IO, Lbl: types::ProtocolLabel, From, WorkerSet, H, T: TSession<IO>> sealed::Sealed
+    for TAnycast<IO, Lbl, From, WorkerSet, H, T>
+{
+}
+impl<IO, Lbl: types::ProtocolLabel, From, WorkerSet, H, T: TSession<IO>> TSession<IO>
+    for TAnycast<IO, Lbl, From, WorkerSet, H, T>
+{
+    type Compose<Rhs: TSession<IO>> = TAnycast<IO, Lbl, From, WorkerSet, H, T::Compose<Rhs>>;
+    const IS_EMPTY: bool = false;
+}
+
+/// Session delegation: `From` hands the remainder of another session to `To`.
+///
+/// - `IO`: Protocol marker type (e.g., Http, Mqtt).
+/// - `Lbl`: Label for this interaction (for projection and debugging).
+/// - `From`: Role giving up the delegated endpoint.
+/// - `To`: Role taking over the delegated endpoint.
+/// - `DelegatedEp`: Local session type of the endpoint being handed over.
+/// - `T`: Continuation protocol after the hand-over.
+///
+/// The message carried is the endpoint itself: projection yields an `EpSend`
+/// for `From` and an `EpRecv` for `To` whose payload is `DelegatedEp`. After
+/// the hand-over `To` plays `DelegatedEp` in the other session on `From`'s
+/// behalf.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// // The broker hands its pending reply to a worker
+/// type Reply = EpSend<Http, EmptyLabel, TServer, Response, EpEnd<Http, EmptyLabel, TServer>>;
+/// type HandOff = TDelegate<Http, EmptyLabel, TBroker, TWorker, Reply, TEnd<Http>>;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct TDelegate<IO, Lbl: types::ProtocolLabel, From, To, DelegatedEp, T: TSession<IO>>(
+    PhantomData<(IO, Lbl, From, To, DelegatedEp, T)>,
+);
+
+impl<IO, Lbl: types::ProtocolLabel, From, To, DelegatedEp, T: TSession<IO>> sealed::Sealed
+    for TDelegate<IO, Lbl, From, To, DelegatedEp, T>
+{
+}
+impl<IO, Lbl: types::ProtocolLabel, From, To, DelegatedEp, T: TSession<IO>> TSession<IO>
+    for TDelegate<IO, Lbl, From, To, DelegatedEp, T>
+{
+    type Compose<Rhs: TSession<IO>> = TDelegate<IO, Lbl, From, To, DelegatedEp, T::Compose<Rhs>>;
+    const IS_EMPTY: bool = false;
+}
+
+/// Roles `A` and `B` close the channel between them.
+///
+/// - `IO`: Protocol marker type.
+/// - `Lbl`: Label for this step (for projection and debugging).
+/// - `A`, `B`: The two roles closing their channel.
+/// - `Cont`: Continuation protocol, which may still involve `A` and `B` with other roles.
+///
+/// No interaction between `A` and `B` may follow the disconnect; check this
+/// with `ConnectionsRespected`.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// // The client hangs up on the server, then the server notifies the broker
+/// type HangUp = TDisconnect<
+///     Http,
+///     EmptyLabel,
+///     TClient,
+///     TServer,
+///     TMsg<Http, EmptyLabel, TServer, TBroker, Notify, TEnd<Http>>,
+/// >;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct TDisconnect<IO, Lbl: types::ProtocolLabel, A, B, Cont: TSession<IO>>(
+    PhantomData<(IO, Lbl, A, B, Cont)>,
+);
+
+impl<IO, Lbl: types::ProtocolLabel, A, B, Cont: TSession<IO>> sealed::Sealed
+    for TDisconnect<IO, Lbl, A, B, Cont>
+{
+}
+impl<IO, Lbl: types::ProtocolLabel, A, B, Cont: TSession<IO>> TSession<IO>
+    for TDisconnect<IO, Lbl, A, B, Cont>
+{
+    type Compose<Rhs: TSession<IO>> = TDisconnect<IO, Lbl, A, B, Cont::Compose<Rhs>>;
+    const IS_EMPTY: bool = false;
+}
+
+/// Roles `A` and `B` open a channel between them.
+///
+/// - `IO`: Protocol marker type.
+/// - `Lbl`: Label for this step (for projection and debugging).
+/// - `A`, `B`: The two roles opening a channel.
+/// - `Cont`: Continuation protocol, in which `A` and `B` may now interact.
+///
+/// Models participants joining mid-session. No interaction between `A` and
+/// `B` may precede the connect; check this with `ConnectionsRespected`.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// // The broker introduces a worker, which then answers the client directly
+/// type Introduce = TMsg<
+///     Http,
+///     EmptyLabel,
+///     TBroker,
+///     TWorker,
+///     Message,
+///     TConnect<
+///         Http,
+///         EmptyLabel,
+///         TClient,
+///         TWorker,
+///         TMsg<Http, EmptyLabel, TWorker, TClient, Response, TEnd<Http>>,
+///     >,
+/// >;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct TConnect<IO, Lbl: types::ProtocolLabel, A, B, Cont: TSession<IO>>(
+    PhantomData<(IO, Lbl, A, B, Cont)>,
+);
+
+impl<IO, Lbl: types::ProtocolLabel, A, B, Cont: TSession<IO>> sealed::Sealed
+    for TConnect<IO, Lbl, A, B, Cont>
+{
+}
+impl<IO, Lbl: types::ProtocolLabel, A, B, Cont: TSession<IO>> TSession<IO>
+    for TConnect<IO, Lbl, A, B, Cont>
+{
+    type Compose<Rhs: TSession<IO>> = TConnect<IO, Lbl, A, B, Cont::Compose<Rhs>>;
+    const IS_EMPTY: bool = false;
+}
+
+/// Partial termination: role `R` leaves the session while the others continue.
+///
+/// - `IO`: Protocol marker type.
+/// - `Lbl`: Label for this step (for projection and debugging).
+/// - `R`: The role leaving the session.
+/// - `Cont`: Continuation protocol for the remaining roles.
+///
+/// `R` projects to `EpEnd` at this point. Projection requires that `R` never
+/// reappears in `Cont`.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// // The client leaves once served; the server keeps reporting to the broker
+/// type Report = TMsg<Http, EmptyLabel, TServer, TBroker, Notify, TEnd<Http>>;
+/// type Served = TMsg<
+///     Http,
+///     EmptyLabel,
+///     TServer,
+///     TClient,
+///     Response,
+///     TEndFor<Http, EmptyLabel, TClient, Report>,
+/// >;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct TEndFor<IO, Lbl: types::ProtocolLabel, R, Cont: TSession<IO>>(
+    PhantomData<(IO, Lbl, R, Cont)>,
+);
+
+impl<IO, Lbl: types::ProtocolLabel, R, Cont: TSession<IO>> sealed::Sealed
+    for TEndFor<IO, Lbl, R, Cont>
+{
+}
+impl<IO, Lbl: types::ProtocolLabel, R, Cont: TSession<IO>> TSession<IO>
+    for TEndFor<IO, Lbl, R, Cont>
+{
+    type Compose<Rhs: TSession<IO>> = TEndFor<IO, Lbl, R, Cont::Compose<Rhs>>;
+    const IS_EMPTY: bool = false;
+}
+
 /// Binary protocol choice between two branches.
 ///
 /// - `IO`: Protocol marker type.
@@ -103,7 +392,9 @@ impl<IO, Lbl: types::ProtocolLabel, L: TSession<IO>, R: TSession<IO>> TSession<I
 /// - `Lbl`: Label for this recursion (for projection and debugging).
 /// - `S`: The protocol fragment to repeat (may refer to itself).
 ///
-/// Used to model loops or streaming protocols.
+/// Used to model loops or streaming protocols. Projection requires the body
+/// to be `Guarded` and yields an `EpRec` that keeps `Lbl` and uses it as the
+/// recursion variable.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
 pub struct TRec<IO, Lbl: types::ProtocolLabel, S: TSession<IO>>(PhantomData<(IO, Lbl, S)>);
 
@@ -113,6 +404,67 @@ impl<IO, Lbl: types::ProtocolLabel, S: TSession<IO>> TSession<IO> for TRec<IO, L
     const IS_EMPTY: bool = false;
 }
 
+/// Recursion binder: names the start of a loop that `TVar<Var>` can jump back to.
+///
+/// - `IO`: Protocol marker type.
+/// - `Lbl`: Label for this recursion (for projection and debugging).
+/// - `Var`: Recursion variable naming this binder (see `VarEq`).
+/// - `Body`: The loop body; `TVar<Var>` inside it restarts the loop.
+///
+/// Bodies should be `Guarded` and protocols `WellScoped`; projection requires
+/// the body to be guarded.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Loop;
+/// impl VarEq<Loop> for Loop { type Output = True; }
+/// struct Stream; impl ProtocolLabel for Stream {}
+///
+/// // Client keeps sending messages until it decides to stop
+/// type Streaming = TRecX<
+///     Http,
+///     Stream,
+///     Loop,
+///     TChoice<
+///         Http,
+///         EmptyLabel,
+///         TInteract<Http, EmptyLabel, TClient, Message, TVar<Loop>>,
+///         TEnd<Http>,
+///     >,
+/// >;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct TRecX<IO, Lbl: types::ProtocolLabel, Var, Body: TSession<IO>>(
+    PhantomData<(IO, Lbl, Var, Body)>,
+);
+
+impl<IO, Lbl: types::ProtocolLabel, Var, Body: TSession<IO>> sealed::Sealed
+    for TRecX<IO, Lbl, Var, Body>
+{
+}
+impl<IO, Lbl: types::ProtocolLabel, Var, Body: TSession<IO>> TSession<IO>
+    for TRecX<IO, Lbl, Var, Body>
+{
+    type Compose<Rhs: TSession<IO>> = TRecX<IO, Lbl, Var, Body::Compose<Rhs>>;
+    const IS_EMPTY: bool = false;
+}
+
+/// Recursion variable: jumps back to the enclosing `TRecX` bound to `Var`.
+///
+/// - `Var`: The recursion variable to jump to.
+///
+/// A jump never falls through, so composing a `TVar` with a continuation
+/// leaves it unchanged.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct TVar<Var>(PhantomData<Var>);
+
+impl<Var> sealed::Sealed for TVar<Var> {}
+impl<IO, Var> TSession<IO> for TVar<Var> {
+    type Compose<Rhs: TSession<IO>> = TVar<Var>;
+    const IS_EMPTY: bool = false;
+}
+
 /// Branded parallel composition of two protocol branches.
 ///
 /// - `IO`: Protocol marker type.
@@ -137,6 +489,642 @@ impl<IO, Lbl: types::ProtocolLabel, L: TSession<IO>, R: TSession<IO>, IsDisjoint
     const IS_EMPTY: bool = false;
 }
 
+/// A type-level list of global sessions, used as the branch list of n-ary
+/// combinators.
+///
+/// Provides list-level composition so that n-ary combinators can implement
+/// `TSession::Compose` by composing every branch with the same continuation.
+pub trait SessionList<IO> {
+    /// Compose every session in the list with `Rhs`.
+    type Compose<Rhs: TSession<IO>>: SessionList<IO>;
+}
+
+impl<IO> SessionList<IO> for Nil {
+    type Compose<Rhs: TSession<IO>> = Nil;
+}
+
+impl<IO, H: TSession<IO>, T: SessionList<IO>> SessionList<IO> for Cons<H, T> {
+    type Compose<Rhs: TSession<IO>> = Cons<H::Compose<Rhs>, T::Compose<Rhs>>;
+}
+
+/// N-ary protocol choice over a flat list of branches.
+///
+/// - `IO`: Protocol marker type.
+/// - `Lbl`: Label for this choice (for projection and debugging).
+/// - `Branches`: Type-level list of protocol branches, usually built with `tlist!`.
+///
+/// Unlike nesting binary `TChoice`, the branch list stays flat, so the shape of
+/// the type matches the shape of the protocol and can be compared directly
+/// with `assert_type_eq!`.
+///
+/// When the branches start with pairwise distinct labels (compared with
+/// `LabelEq`), the labels serve as wire tags: the choice projects to
+/// `EpSelect` for the roles acting first in some branch and to `EpOffer` for
+/// the others, and a skipped branch keeps its own label. Otherwise it
+/// projects to `EpChoiceN`.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Login; impl ProtocolLabel for Login {}
+/// type Choice = TChoiceN<
+///     Http,
+///     Login,
+///     tlist!(
+///         TInteract<Http, EmptyLabel, TClient, Message, TEnd<Http>>,
+///         TInteract<Http, EmptyLabel, TClient, Publish, TEnd<Http>>,
+///         TInteract<Http, EmptyLabel, TServer, Response, TEnd<Http>>,
+///     ),
+/// >;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct TChoiceN<IO, Lbl: types::ProtocolLabel, Branches: SessionList<IO>>(
+    PhantomData<(IO, Lbl, Branches)>,
+);
+
+impl<IO, Lbl: types::ProtocolLabel, Branches: SessionList<IO>> sealed::Sealed
+    for TChoiceN<IO, Lbl, Branches>
+{
+}
+impl<IO, Lbl: types::ProtocolLabel, Branches: SessionList<IO>> TSession<IO>
+    for TChoiceN<IO, Lbl, Branches>
+{
+    type Compose<Rhs: TSession<IO>> = TChoiceN<IO, Lbl, Branches::Compose<Rhs>>;
+    const IS_EMPTY: bool = false;
+}
+
+/// Prioritized choice: `High` is taken whenever it is enabled, `Low` otherwise.
+///
+/// - `IO`: Protocol marker type.
+/// - `Lbl`: Label for this choice (for projection and debugging).
+/// - `High`: The preferred branch, e.g. control-plane traffic.
+/// - `Low`: The fallback branch, e.g. data-plane traffic.
+///
+/// Projects to `EpPriorityOffer`, so the preference survives into local
+/// types and can be honoured by runtimes and shown by exporters.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Control; impl ProtocolLabel for Control {}
+/// type Plane = TPriorityChoice<
+///     Mqtt,
+///     Control,
+///     TInteract<Mqtt, EmptyLabel, TBroker, Notify, TEnd<Mqtt>>,
+///     TInteract<Mqtt, EmptyLabel, TBroker, Publish, TEnd<Mqtt>>,
+/// >;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct TPriorityChoice<IO, Lbl: types::ProtocolLabel, High: TSession<IO>, Low: TSession<IO>>(
+    PhantomData<(IO, Lbl, High, Low)>,
+);
+
+impl<IO, Lbl: types::ProtocolLabel, High: TSession<IO>, Low: TSession<IO>> sealed::Sealed
+    for TPriorityChoice<IO, Lbl, High, Low>
+{
+}
+impl<IO, Lbl: types::ProtocolLabel, High: TSession<IO>, Low: TSession<IO>> TSession<IO>
+    for TPriorityChoice<IO, Lbl, High, Low>
+{
+    type Compose<Rhs: TSession<IO>> =
+        TPriorityChoice<IO, Lbl, High::Compose<Rhs>, Low::Compose<Rhs>>;
+    const IS_EMPTY: bool = false;
+}
+
+/// Weighted choice: `L` is taken with relative weight `WeightL`, `R` with `WeightR`.
+///
+/// - `IO`: Protocol marker type.
+/// - `Lbl`: Label for this choice (for projection and debugging).
+/// - `WeightL`, `WeightR`: Weight markers such as `Weight<3>`.
+/// - `L`, `R`: The two protocol branches.
+///
+/// Projection ignores the weights and treats `TProb` as a plain `TChoice`;
+/// simulators and exporters read them back through [`WeightsOf`](crate::WeightsOf).
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Load; impl ProtocolLabel for Load {}
+/// // Nine requests out of ten are reads
+/// type Traffic = TProb<
+///     Http,
+///     Load,
+///     Weight<9>,
+///     TInteract<Http, EmptyLabel, TClient, Message, TEnd<Http>>,
+///     Weight<1>,
+///     TInteract<Http, EmptyLabel, TClient, Publish, TEnd<Http>>,
+/// >;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct TProb<IO, Lbl, WeightL, L, WeightR, R>(PhantomData<(IO, Lbl, WeightL, L, WeightR, R)>)
+where
+    Lbl: types::ProtocolLabel,
+    WeightL: types::WeightMarker,
+    L: TSession<IO>,
+    WeightR: types::WeightMarker,
+    R: TSession<IO>;
+
+impl<IO, Lbl, WeightL, L, WeightR, R> sealed::Sealed for TProb<IO, Lbl, WeightL, L, WeightR, R>
+where
+    Lbl: types::ProtocolLabel,
+    WeightL: types::WeightMarker,
+    L: TSession<IO>,
+    WeightR: types::WeightMarker,
+    R: TSession<IO>,
+{
+}
+impl<IO, Lbl, WeightL, L, WeightR, R> TSession<IO> for TProb<IO, Lbl, WeightL, L, WeightR, R>
+where
+    Lbl: types::ProtocolLabel,
+    WeightL: types::WeightMarker,
+    L: TSession<IO>,
+    WeightR: types::WeightMarker,
+    R: TSession<IO>,
+{
+    type Compose<Rhs: TSession<IO>> =
+        TProb<IO, Lbl, WeightL, L::Compose<Rhs>, WeightR, R::Compose<Rhs>>;
+    const IS_EMPTY: bool = false;
+}
+
+/// Internal choice: `Chooser` decides which branch the protocol takes.
+///
+/// - `IO`: Protocol marker type.
+/// - `Lbl`: Label for this choice (for projection and debugging).
+/// - `Chooser`: The role that picks the branch.
+/// - `Branches`: Type-level list of protocol branches.
+///
+/// Projects to `EpSelect` for `Chooser` and to `EpOffer` for every other role
+/// taking part in the branches.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Pay; impl ProtocolLabel for Pay {}
+/// type Checkout = TSelect<
+///     Http,
+///     Pay,
+///     TClient,
+///     tlist!(
+///         TInteract<Http, EmptyLabel, TClient, Message, TEnd<Http>>,
+///         TInteract<Http, EmptyLabel, TClient, Publish, TEnd<Http>>,
+///     ),
+/// >;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct TSelect<IO, Lbl: types::ProtocolLabel, Chooser, Branches: SessionList<IO>>(
+    PhantomData<(IO, Lbl, Chooser, Branches)>,
+);
+
+impl<IO, Lbl: types::ProtocolLabel, Chooser, Branches: SessionList<IO>> sealed::Sealed
+    for TSelect<IO, Lbl, Chooser, Branches>
+{
+}
+impl<IO, Lbl: types::ProtocolLabel, Chooser, Branches: SessionList<IO>> TSession<IO>
+    for TSelect<IO, Lbl, Chooser, Branches>
+{
+    type Compose<Rhs: TSession<IO>> = TSelect<IO, Lbl, Chooser, Branches::Compose<Rhs>>;
+    const IS_EMPTY: bool = false;
+}
+
+/// External choice: `Offeree` reacts to a branch picked by its peers.
+///
+/// - `IO`: Protocol marker type.
+/// - `Lbl`: Label for this choice (for projection and debugging).
+/// - `Offeree`: The role that is offered the branches.
+/// - `Branches`: Type-level list of protocol branches.
+///
+/// Projects to `EpOffer` for `Offeree` and to `EpSelect` for every other role
+/// taking part in the branches.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct TOffer<IO, Lbl: types::ProtocolLabel, Offeree, Branches: SessionList<IO>>(
+    PhantomData<(IO, Lbl, Offeree, Branches)>,
+);
+
+impl<IO, Lbl: types::ProtocolLabel, Offeree, Branches: SessionList<IO>> sealed::Sealed
+    for TOffer<IO, Lbl, Offeree, Branches>
+{
+}
+impl<IO, Lbl: types::ProtocolLabel, Offeree, Branches: SessionList<IO>> TSession<IO>
+    for TOffer<IO, Lbl, Offeree, Branches>
+{
+    type Compose<Rhs: TSession<IO>> = TOffer<IO, Lbl, Offeree, Branches::Compose<Rhs>>;
+    const IS_EMPTY: bool = false;
+}
+
+/// Optional step: `Decider` chooses whether `Body` runs before `Cont`.
+///
+/// - `IO`: Protocol marker type.
+/// - `Lbl`: Label for this choice (for projection and debugging).
+/// - `Decider`: The role that decides whether the optional step happens.
+/// - `Body`: The optional protocol fragment.
+/// - `Cont`: The protocol that follows in either case.
+///
+/// Semantically this is `TSelect<IO, Lbl, Decider, tlist!(Body;Cont, Cont)>`,
+/// but projection keeps the shared continuation in one place instead of
+/// duplicating `Cont` in both branches.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Warning; impl ProtocolLabel for Warning {}
+/// // The server MAY send a warning before the response
+/// type MaybeWarn = TOptional<
+///     Http,
+///     Warning,
+///     TServer,
+///     TInteract<Http, EmptyLabel, TServer, Notify, TEnd<Http>>,
+///     TInteract<Http, EmptyLabel, TServer, Response, TEnd<Http>>,
+/// >;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct TOptional<IO, Lbl: types::ProtocolLabel, Decider, Body: TSession<IO>, Cont: TSession<IO>>(
+    PhantomData<(IO, Lbl, Decider, Body, Cont)>,
+);
+
+impl<IO, Lbl: types::ProtocolLabel, Decider, Body: TSession<IO>, Cont: TSession<IO>> sealed::Sealed
+    for TOptional<IO, Lbl, Decider, Body, Cont>
+{
+}
+impl<IO, Lbl: types::ProtocolLabel, Decider, Body: TSession<IO>, Cont: TSession<IO>> TSession<IO>
+    for TOptional<IO, Lbl, Decider, Body, Cont>
+{
+    type Compose<Rhs: TSession<IO>> = TOptional<IO, Lbl, Decider, Body, Cont::Compose<Rhs>>;
+    const IS_EMPTY: bool = false;
+}
+
+/// Timed protocol fragment: `Body` must complete within `Dur`, otherwise
+/// control transfers to `OnTimeout`.
+///
+/// - `IO`: Protocol marker type.
+/// - `Lbl`: Label for this timeout (for projection and debugging).
+/// - `Dur`: Duration marker, e.g. `Millis<500>` or `Secs<30>`.
+/// - `Body`: The protocol fragment under the deadline.
+/// - `OnTimeout`: The protocol followed when the deadline passes.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Poll; impl ProtocolLabel for Poll {}
+/// type Polling = TTimeout<
+///     Http,
+///     Poll,
+///     Millis<500>,
+///     TInteract<Http, EmptyLabel, TServer, Response, TEnd<Http>>,
+///     TInteract<Http, EmptyLabel, TClient, Notify, TEnd<Http>>,
+/// >;
+/// assert_eq!(<Polling as TimingOf>::DURATION.as_millis(), 500);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct TTimeout<
+    IO,
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    Body: TSession<IO>,
+    OnTimeout: TSession<IO>,
+>(PhantomData<(IO, Lbl, Dur, Body, OnTimeout)>);
+
+impl<
+        IO,
+        Lbl: types::ProtocolLabel,
+        Dur: types::DurationMarker,
+        Body: TSession<IO>,
+        OnTimeout: TSession<IO>,
+    > sealed::Sealed for TTimeout<IO, Lbl, Dur, Body, OnTimeout>
+{
+}
+impl<
+        IO,
+        Lbl: types::ProtocolLabel,
+        Dur: types::DurationMarker,
+        Body: TSession<IO>,
+        OnTimeout: TSession<IO>,
+    > TSession<IO> for TTimeout<IO, Lbl, Dur, Body, OnTimeout>
+{
+    type Compose<Rhs: TSession<IO>> =
+        TTimeout<IO, Lbl, Dur, Body::Compose<Rhs>, OnTimeout::Compose<Rhs>>;
+    const IS_EMPTY: bool = false;
+}
+
+/// Minimum wait: `R` must wait at least `Dur` before continuing with `T`.
+///
+/// - `IO`: Protocol marker type.
+/// - `Lbl`: Label for this constraint (for projection and debugging).
+/// - `R`: The role the constraint applies to.
+/// - `Dur`: Duration marker, e.g. `Millis<500>` or `Secs<30>`.
+/// - `T`: Continuation protocol.
+///
+/// Only `R` carries the constraint after projection; other roles continue with `T`.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Poll; impl ProtocolLabel for Poll {}
+/// // Rate-limited polling: the client waits a second between requests
+/// type Polling = TDelay<
+///     Http,
+///     Poll,
+///     TClient,
+///     Secs<1>,
+///     TInteract<Http, EmptyLabel, TClient, Message, TEnd<Http>>,
+/// >;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct TDelay<IO, Lbl: types::ProtocolLabel, R, Dur: types::DurationMarker, T: TSession<IO>>(
+    PhantomData<(IO, Lbl, R, Dur, T)>,
+);
+
+impl<IO, Lbl: types::ProtocolLabel, R, Dur: types::DurationMarker, T: TSession<IO>> sealed::Sealed
+    for TDelay<IO, Lbl, R, Dur, T>
+{
+}
+impl<IO, Lbl: types::ProtocolLabel, R, Dur: types::DurationMarker, T: TSession<IO>> TSession<IO>
+    for TDelay<IO, Lbl, R, Dur, T>
+{
+    type Compose<Rhs: TSession<IO>> = TDelay<IO, Lbl, R, Dur, T::Compose<Rhs>>;
+    const IS_EMPTY: bool = false;
+}
+
+/// Absolute deadline: `R` must complete `T` before `Dur` has elapsed since the
+/// session started.
+///
+/// - `IO`: Protocol marker type.
+/// - `Lbl`: Label for this constraint (for projection and debugging).
+/// - `R`: The role the constraint applies to.
+/// - `Dur`: Duration marker, e.g. `Millis<500>` or `Secs<30>`.
+/// - `T`: Continuation protocol.
+///
+/// Only `R` carries the constraint after projection; other roles continue with `T`.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Submit; impl ProtocolLabel for Submit {}
+/// type Exam = TDeadline<
+///     Http,
+///     Submit,
+///     TClient,
+///     Secs<3600>,
+///     TInteract<Http, EmptyLabel, TClient, Message, TEnd<Http>>,
+/// >;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct TDeadline<IO, Lbl: types::ProtocolLabel, R, Dur: types::DurationMarker, T: TSession<IO>>(
+    PhantomData<(IO, Lbl, R, Dur, T)>,
+);
+
+impl<IO, Lbl: types::ProtocolLabel, R, Dur: types::DurationMarker, T: TSession<IO>> sealed::Sealed
+    for TDeadline<IO, Lbl, R, Dur, T>
+{
+}
+impl<IO, Lbl: types::ProtocolLabel, R, Dur: types::DurationMarker, T: TSession<IO>> TSession<IO>
+    for TDeadline<IO, Lbl, R, Dur, T>
+{
+    type Compose<Rhs: TSession<IO>> = TDeadline<IO, Lbl, R, Dur, T::Compose<Rhs>>;
+    const IS_EMPTY: bool = false;
+}
+
+/// Interruptible scope: `Interruptor` may interrupt `Scope` at any point with a
+/// message, diverting every participant into `Handler`.
+///
+/// - `IO`: Protocol marker type.
+/// - `Lbl`: Label for this scope (for projection and debugging).
+/// - `Scope`: The protocol fragment that can be interrupted.
+/// - `Interruptor`: The role allowed to raise the interrupt.
+/// - `Handler`: The protocol all participants continue with after an interrupt.
+///
+/// If `Scope` completes without an interrupt, the protocol continues after the
+/// `TInterrupt`; composition appends to both `Scope` and `Handler`.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Download; impl ProtocolLabel for Download {}
+/// // The client may cancel a running download
+/// type Cancellable = TInterrupt<
+///     Http,
+///     Download,
+///     TInteract<Http, EmptyLabel, TServer, Response, TEnd<Http>>,
+///     TClient,
+///     TInteract<Http, EmptyLabel, TServer, Notify, TEnd<Http>>,
+/// >;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct TInterrupt<
+    IO,
+    Lbl: types::ProtocolLabel,
+    Scope: TSession<IO>,
+    Interruptor,
+    Handler: TSession<IO>,
+>(PhantomData<(IO, Lbl, Scope, Interruptor, Handler)>);
+
+impl<IO, Lbl: types::ProtocolLabel, Scope: TSession<IO>, Interruptor, Handler: TSession<IO>>
+    sealed::Sealed for TInterrupt<IO, Lbl, Scope, Interruptor, Handler>
+{
+}
+impl<IO, Lbl: types::ProtocolLabel, Scope: TSession<IO>, Interruptor, Handler: TSession<IO>>
+    TSession<IO> for TInterrupt<IO, Lbl, Scope, Interruptor, Handler>
+{
+    type Compose<Rhs: TSession<IO>> =
+        TInterrupt<IO, Lbl, Scope::Compose<Rhs>, Interruptor, Handler::Compose<Rhs>>;
+    const IS_EMPTY: bool = false;
+}
+
+/// Refinement: a type-level predicate attached to a protocol fragment.
+///
+/// - `Pred`: The predicate, implementing `Predicate`.
+/// - `Inner`: The refined protocol; the predicate constrains its first
+///   interaction, e.g. the payload of a `TMsg`.
+///
+/// Refinements are carried through projection as `EpRefine`, so runtimes can
+/// enforce them and exporters can display them.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Positive;
+/// impl Predicate for Positive {
+///     const DESCRIPTION: &'static str = "value > 0";
+/// }
+/// type Deposit = TRefine<Positive, TInteract<Http, EmptyLabel, TClient, i64, TEnd<Http>>>;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct TRefine<Pred, Inner>(PhantomData<(Pred, Inner)>);
+
+impl<Pred, Inner> sealed::Sealed for TRefine<Pred, Inner> {}
+impl<IO, Pred: types::Predicate, Inner: TSession<IO>> TSession<IO> for TRefine<Pred, Inner> {
+    type Compose<Rhs: TSession<IO>> = TRefine<Pred, Inner::Compose<Rhs>>;
+    const IS_EMPTY: bool = Inner::IS_EMPTY;
+}
+
+/// Annotation: marker metadata attached to a protocol fragment.
+///
+/// - `Meta`: Any marker type, e.g. a documentation key, QoS level or security
+///   classification.
+/// - `Inner`: The annotated protocol.
+///
+/// Annotations are transparent: projection ignores them, so an annotated
+/// fragment projects exactly like `Inner`. Collect them with `AnnotationsOf`.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Confidential;
+/// type Login = TAnnotate<Confidential, TInteract<Http, EmptyLabel, TClient, Message, TEnd<Http>>>;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct TAnnotate<Meta, Inner>(PhantomData<(Meta, Inner)>);
+
+impl<Meta, Inner> sealed::Sealed for TAnnotate<Meta, Inner> {}
+impl<IO, Meta, Inner: TSession<IO>> TSession<IO> for TAnnotate<Meta, Inner> {
+    type Compose<Rhs: TSession<IO>> = TAnnotate<Meta, Inner::Compose<Rhs>>;
+    const IS_EMPTY: bool = Inner::IS_EMPTY;
+}
+
+/// Failure: `R` raises the error `Err`, abandoning the enclosing `TCatch` body.
+///
+/// - `IO`: Protocol marker type.
+/// - `Lbl`: Label for this failure point (for projection and debugging).
+/// - `R`: The role that raises the error.
+/// - `Err`: The error payload.
+///
+/// Control transfers to the handler of the nearest enclosing `TCatch`, so
+/// nothing composed after a `TThrow` is reachable. Use `CatchScoped` to check
+/// that every throw has an enclosing catch.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct TThrow<IO, Lbl: types::ProtocolLabel, R, Err>(PhantomData<(IO, Lbl, R, Err)>);
+
+impl<IO, Lbl: types::ProtocolLabel, R, Err> sealed::Sealed for TThrow<IO, Lbl, R, Err> {}
+impl<IO, Lbl: types::ProtocolLabel, R, Err> TSession<IO> for TThrow<IO, Lbl, R, Err> {
+    // Control never falls through a throw
+    type Compose<Rhs: TSession<IO>> = TThrow<IO, Lbl, R, Err>;
+    const IS_EMPTY: bool = false;
+}
+
+/// Failure scope: a `TThrow` inside `Body` diverts every participant into `Handler`.
+///
+/// - `IO`: Protocol marker type.
+/// - `Lbl`: Label for this scope (for projection and debugging).
+/// - `Body`: The protocol fragment that may fail.
+/// - `Handler`: The protocol all participants continue with after a failure.
+///
+/// If `Body` completes normally, the protocol continues after the `TCatch`;
+/// composition appends to both `Body` and `Handler`.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Failed;
+/// // The server may fail to respond; the client is then notified
+/// type Fallible = TCatch<
+///     Http,
+///     EmptyLabel,
+///     TChoice<
+///         Http,
+///         EmptyLabel,
+///         TInteract<Http, EmptyLabel, TServer, Response, TEnd<Http>>,
+///         TThrow<Http, EmptyLabel, TServer, Failed>,
+///     >,
+///     TInteract<Http, EmptyLabel, TServer, Notify, TEnd<Http>>,
+/// >;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct TCatch<IO, Lbl: types::ProtocolLabel, Body: TSession<IO>, Handler: TSession<IO>>(
+    PhantomData<(IO, Lbl, Body, Handler)>,
+);
+
+impl<IO, Lbl: types::ProtocolLabel, Body: TSession<IO>, Handler: TSession<IO>> sealed::Sealed
+    for TCatch<IO, Lbl, Body, Handler>
+{
+}
+impl<IO, Lbl: types::ProtocolLabel, Body: TSession<IO>, Handler: TSession<IO>> TSession<IO>
+    for TCatch<IO, Lbl, Body, Handler>
+{
+    type Compose<Rhs: TSession<IO>> = TCatch<IO, Lbl, Body::Compose<Rhs>, Handler::Compose<Rhs>>;
+    const IS_EMPTY: bool = false;
+}
+
+/// N-ary parallel composition over a flat list of branches.
+///
+/// - `IO`: Protocol marker type.
+/// - `Lbl`: Label for this parallel composition.
+/// - `Branches`: Type-level list of protocol branches, usually built with `tlist!`.
+///
+/// Branches must be pairwise role-disjoint. This is checked when the
+/// composition is projected or passed to `assert_disjoint!(par ...)`, so an
+/// overlapping `TParN` cannot be turned into endpoint types.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Downloads; impl ProtocolLabel for Downloads {}
+/// type Par = TParN<
+///     Http,
+///     Downloads,
+///     tlist!(
+///         TInteract<Http, EmptyLabel, TClient, Message, TEnd<Http>>,
+///         TInteract<Http, EmptyLabel, TBroker, Publish, TEnd<Http>>,
+///         TInteract<Http, EmptyLabel, TWorker, Notify, TEnd<Http>>,
+///     ),
+/// >;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct TParN<IO, Lbl: types::ProtocolLabel, Branches: SessionList<IO>>(
+    PhantomData<(IO, Lbl, Branches)>,
+);
+
+impl<IO, Lbl: types::ProtocolLabel, Branches: SessionList<IO>> sealed::Sealed
+    for TParN<IO, Lbl, Branches>
+{
+}
+impl<IO, Lbl: types::ProtocolLabel, Branches: SessionList<IO>> TSession<IO>
+    for TParN<IO, Lbl, Branches>
+{
+    type Compose<Rhs: TSession<IO>> = TParN<IO, Lbl, Branches::Compose<Rhs>>;
+    const IS_EMPTY: bool = false;
+}
+
+/// Unordered steps: every step happens exactly once, in any order.
+///
+/// - `IO`: Protocol marker type.
+/// - `Lbl`: Label for this shuffle.
+/// - `Steps`: Type-level list of protocol steps, usually built with `tlist!`.
+///
+/// Unlike `TParN`, steps may share roles; a runtime driving the shuffle tells
+/// them apart by the label each step starts with. Steps must therefore be
+/// pairwise independent (see [`PairwiseIndependent`](crate::PairwiseIndependent)),
+/// which is checked when the shuffle is projected.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Headers; impl ProtocolLabel for Headers {}
+/// struct ContentType; impl ProtocolLabel for ContentType {}
+/// struct Accept; impl ProtocolLabel for Accept {}
+/// type HeaderExchange = TShuffle<
+///     Http,
+///     Headers,
+///     tlist!(
+///         TMsg<Http, ContentType, TClient, TServer, Message, TEnd<Http>>,
+///         TMsg<Http, Accept, TClient, TServer, Message, TEnd<Http>>,
+///     ),
+/// >;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct TShuffle<IO, Lbl: types::ProtocolLabel, Steps: SessionList<IO>>(
+    PhantomData<(IO, Lbl, Steps)>,
+);
+
+impl<IO, Lbl: types::ProtocolLabel, Steps: SessionList<IO>> sealed::Sealed
+    for TShuffle<IO, Lbl, Steps>
+{
+}
+impl<IO, Lbl: types::ProtocolLabel, Steps: SessionList<IO>> TSession<IO>
+    for TShuffle<IO, Lbl, Steps>
+{
+    type Compose<Rhs: TSession<IO>> = TShuffle<IO, Lbl, Steps::Compose<Rhs>>;
+    const IS_EMPTY: bool = false;
+}
+
 /// Trait for mapping a type-level list to a nested `TChoice`.
 ///
 /// # Examples
@@ -213,6 +1201,26 @@ impl<IO, H: TSession<IO>, T: ToTPar<IO>> ToTPar<IO> for Cons<H, T> {
 }
 
 /// Compile-time Disjointness Assertion Machinery
+///
+/// Implemented for a `TPar` whose branches share no role, and for a `TParN`
+/// whose branches are pairwise role-disjoint; see [`Disjoint`].
 pub trait AssertDisjoint {
     type Output;
 }
+
+impl<IO, Lbl, L, R, IsDisjoint> AssertDisjoint for TPar<IO, Lbl, L, R, IsDisjoint>
+where
+    Lbl: types::ProtocolLabel,
+    L: TSession<IO> + UniqueRoles,
+    R: TSession<IO> + UniqueRoles,
+    (): Disjoint<<L as UniqueRoles>::Roles, <R as UniqueRoles>::Roles>,
+{
+    type Output = types::True;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Branches> AssertDisjoint for TParN<IO, Lbl, Branches>
+where
+    Branches: SessionList<IO> + PairwiseDisjoint,
+{
+    type Output = types::True;
+}
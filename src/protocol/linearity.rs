@@ -0,0 +1,354 @@
+//! # Linearity Checks
+//!
+//! This module checks that the branches of every parallel composition use
+//! their channels linearly: each ordered pair of roles communicates in at
+//! most one branch of a `TPar` or `TParN`.
+//!
+//! Key components:
+//!
+//! - `Linear`: Every parallel composition of a protocol uses its channels linearly
+//! - `BranchesLinear`: The branches of a type-level list share no channel
+//! - `NoneSharedWith` / `NoneSharedWithCase`: No channel of a list appears in another list
+//! - `SharedChannel` / `LinearityViolation`: The failure marker naming the shared pair
+//! - `InterleavingSafe`: The branches of one `TPar` or `TParN` interleave safely
+//!
+//! Role-disjoint branches are always linear. Branches that share a role stay
+//! safe as long as no channel is used by two of them: messages sent from `A`
+//! to `B` by two concurrent branches could overtake each other, and `B`
+//! could not tell which branch a message belongs to. Channels are collected
+//! with `ChannelsOf`; see [the scope of the checks](crate#scope-of-the-checks)
+//! for `TInteract`.
+//!
+//! `InterleavingSafe` applies the same rule to a single composition. It is
+//! the relaxed counterpart of `AssertDisjoint`: a role may act in several
+//! branches, since its actions on distinct channels are causally independent
+//! and commute. With the `interleaving` feature enabled,
+//! `assert_disjoint!(par ...)` checks `InterleavingSafe` instead of role
+//! disjointness.
+
+use super::base::{Cons, Nil};
+use super::deadlock::{Channel, ChannelIn, ChannelsOf, ChannelsOfEach};
+use super::global::*;
+use crate::types;
+use core::marker::PhantomData;
+
+/// Marker for the channel from `A` to `B` used by two concurrent branches.
+pub struct SharedChannel<A, B>(PhantomData<(A, B)>);
+
+/// Never implemented: a bound `Marker: LinearityViolation` fails and names the
+/// channel found by [`Linear`].
+#[diagnostic::on_unimplemented(
+    message = "the channel is used by two concurrent branches: `{Self}`",
+    label = "parallel branches share a channel",
+    note = "let each ordered pair of roles communicate in one branch of a parallel composition"
+)]
+pub trait LinearityViolation {}
+
+/// No channel of a type-level list of channels appears in `Other`.
+pub trait NoneSharedWith<Other> {}
+
+impl<Other> NoneSharedWith<Other> for Nil {}
+
+impl<A, B, Rest, Other> NoneSharedWith<Other> for Cons<Channel<A, B>, Rest>
+where
+    Channel<A, B>: ChannelIn<Other>,
+    (): NoneSharedWithCase<A, B, <Channel<A, B> as ChannelIn<Other>>::Output>,
+    Rest: NoneSharedWith<Other>,
+{
+}
+
+/// Helper trait for `NoneSharedWith`, dispatching on whether the channel was
+/// found.
+pub trait NoneSharedWithCase<A, B, Found> {}
+
+impl<A, B> NoneSharedWithCase<A, B, types::False> for () {}
+
+impl<A, B> NoneSharedWithCase<A, B, types::True> for () where SharedChannel<A, B>: LinearityViolation
+{}
+
+/// The protocols of a type-level list, run concurrently, share no channel.
+pub trait BranchesLinear {}
+
+impl BranchesLinear for Nil {}
+
+impl<H, T> BranchesLinear for Cons<H, T>
+where
+    H: ChannelsOf,
+    T: ChannelsOfEach + BranchesLinear,
+    <H as ChannelsOf>::Channels: NoneSharedWith<<T as ChannelsOfEach>::Channels>,
+{
+}
+
+/// A global protocol whose parallel compositions use their channels linearly.
+///
+/// Within every `TPar` and `TParN`, each ordered pair of roles communicates
+/// in at most one branch; this holds for role-disjoint branches and extends
+/// to branches sharing a role. A violation names the pair through
+/// [`SharedChannel`]. Check a protocol with
+/// [`assert_linear!`](crate::assert_linear).
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Alice; struct Bob; struct Carol; struct Ping;
+/// impl Role for Alice {} impl Role for Bob {} impl Role for Carol {}
+/// impl ProtocolLabel for Ping {}
+/// impl RoleEq<Alice> for Alice { type Output = True; }
+/// impl RoleEq<Bob> for Alice   { type Output = False; }
+/// impl RoleEq<Carol> for Alice { type Output = False; }
+/// impl RoleEq<Alice> for Bob   { type Output = False; }
+/// impl RoleEq<Bob> for Bob     { type Output = True; }
+/// impl RoleEq<Carol> for Bob   { type Output = False; }
+/// impl RoleEq<Alice> for Carol { type Output = False; }
+/// impl RoleEq<Bob> for Carol   { type Output = False; }
+/// impl RoleEq<Carol> for Carol { type Output = True; }
+///
+/// // Bob takes part in both branches, on different channels
+/// type G = TPar<
+///     Http,
+///     EmptyLabel,
+///     TMsg<Http, Ping, Alice, Bob, Message, TEnd<Http>>,
+///     TMsg<Http, Ping, Bob, Carol, Message, TEnd<Http>>,
+///     False,
+/// >;
+/// assert_linear!(G);
+/// ```
+pub trait Linear {}
+
+impl<IO, Lbl> Linear for TEnd<IO, Lbl> {}
+
+impl<IO, Lbl: types::ProtocolLabel, R, H, T> Linear for TInteract<IO, Lbl, R, H, T> where
+    T: TSession<IO> + Linear
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, To, H, T> Linear for TMsg<IO, Lbl, From, To, H, T> where
+    T: TSession<IO> + Linear
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, ToSet, H, T> Linear
+    for TBroadcast<IO, Lbl, From, ToSet, H, T>
+where
+    T: TSession<IO> + Linear,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, WorkerSet, H, T> Linear
+    for TAnycast<IO, Lbl, From, WorkerSet, H, T>
+where
+    T: TSession<IO> + Linear,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, To, DelegatedEp, T> Linear
+    for TDelegate<IO, Lbl, From, To, DelegatedEp, T>
+where
+    T: TSession<IO> + Linear,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, A, B, Cont> Linear for TDisconnect<IO, Lbl, A, B, Cont> where
+    Cont: TSession<IO> + Linear
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, A, B, Cont> Linear for TConnect<IO, Lbl, A, B, Cont> where
+    Cont: TSession<IO> + Linear
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R, Cont> Linear for TEndFor<IO, Lbl, R, Cont> where
+    Cont: TSession<IO> + Linear
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, L, R> Linear for TChoice<IO, Lbl, L, R>
+where
+    L: TSession<IO> + Linear,
+    R: TSession<IO> + Linear,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Branches> Linear for TChoiceN<IO, Lbl, Branches> where
+    Branches: SessionList<IO> + Linear
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Chooser, Branches> Linear
+    for TSelect<IO, Lbl, Chooser, Branches>
+where
+    Branches: SessionList<IO> + Linear,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Offeree, Branches> Linear for TOffer<IO, Lbl, Offeree, Branches> where
+    Branches: SessionList<IO> + Linear
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, High, Low> Linear for TPriorityChoice<IO, Lbl, High, Low>
+where
+    High: TSession<IO> + Linear,
+    Low: TSession<IO> + Linear,
+{
+}
+
+impl<IO, Lbl, WeightL, L, WeightR, R> Linear for TProb<IO, Lbl, WeightL, L, WeightR, R>
+where
+    Lbl: types::ProtocolLabel,
+    WeightL: types::WeightMarker,
+    WeightR: types::WeightMarker,
+    L: TSession<IO> + Linear,
+    R: TSession<IO> + Linear,
+{
+}
+
+// The two branches run concurrently
+impl<IO, Lbl: types::ProtocolLabel, L, R, IsDisjoint> Linear for TPar<IO, Lbl, L, R, IsDisjoint>
+where
+    L: TSession<IO> + Linear,
+    R: TSession<IO> + Linear,
+    Cons<L, Cons<R, Nil>>: BranchesLinear,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Branches> Linear for TParN<IO, Lbl, Branches> where
+    Branches: SessionList<IO> + Linear + BranchesLinear
+{
+}
+
+// The steps of a shuffle run one at a time, each on its own label
+impl<IO, Lbl: types::ProtocolLabel, Steps> Linear for TShuffle<IO, Lbl, Steps> where
+    Steps: SessionList<IO> + Linear
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, S> Linear for TRec<IO, Lbl, S> where S: TSession<IO> + Linear {}
+
+impl<IO, Lbl: types::ProtocolLabel, Var, Body> Linear for TRecX<IO, Lbl, Var, Body> where
+    Body: TSession<IO> + Linear
+{
+}
+
+impl<Var> Linear for TVar<Var> {}
+
+impl<IO, Lbl, Dur, Body, OnTimeout> Linear for TTimeout<IO, Lbl, Dur, Body, OnTimeout>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    Body: TSession<IO> + Linear,
+    OnTimeout: TSession<IO> + Linear,
+{
+}
+
+impl<IO, Lbl, R, Dur, T> Linear for TDelay<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: TSession<IO> + Linear,
+{
+}
+
+impl<IO, Lbl, R, Dur, T> Linear for TDeadline<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: TSession<IO> + Linear,
+{
+}
+
+impl<IO, Lbl, Scope, Interruptor, Handler> Linear
+    for TInterrupt<IO, Lbl, Scope, Interruptor, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Scope: TSession<IO> + Linear,
+    Handler: TSession<IO> + Linear,
+{
+}
+
+impl<IO, Lbl, Decider, Body, Cont> Linear for TOptional<IO, Lbl, Decider, Body, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Body: TSession<IO> + Linear,
+    Cont: TSession<IO> + Linear,
+{
+}
+
+impl<Pred, Inner: Linear> Linear for TRefine<Pred, Inner> {}
+
+impl<Meta, Inner: Linear> Linear for TAnnotate<Meta, Inner> {}
+
+impl<IO, Lbl: types::ProtocolLabel, R, Err> Linear for TThrow<IO, Lbl, R, Err> {}
+
+impl<IO, Lbl, Body, Handler> Linear for TCatch<IO, Lbl, Body, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Body: TSession<IO> + Linear,
+    Handler: TSession<IO> + Linear,
+{
+}
+
+impl Linear for Nil {}
+
+impl<H, T> Linear for Cons<H, T>
+where
+    H: Linear,
+    T: Linear,
+{
+}
+
+/// A parallel composition whose branches interleave safely.
+///
+/// The branches may share roles, but no channel: the actions of a role in
+/// two branches then happen on distinct channels and commute, so every
+/// interleaving of the branches is observed the same way. Unlike [`Linear`],
+/// only the branches of the composition itself are compared. A violation
+/// names the pair through [`SharedChannel`].
+///
+/// Branches with no role in common share no channel, so every composition
+/// that [`AssertDisjoint`] accepts is accepted here too, whatever its IO
+/// marker.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Alice; struct Bob; struct Carol; struct Ping;
+/// impl Role for Alice {} impl Role for Bob {} impl Role for Carol {}
+/// impl ProtocolLabel for Ping {}
+/// impl RoleEq<Alice> for Alice { type Output = True; }
+/// impl RoleEq<Bob> for Alice   { type Output = False; }
+/// impl RoleEq<Carol> for Alice { type Output = False; }
+/// impl RoleEq<Alice> for Bob   { type Output = False; }
+/// impl RoleEq<Bob> for Bob     { type Output = True; }
+/// impl RoleEq<Carol> for Bob   { type Output = False; }
+/// impl RoleEq<Alice> for Carol { type Output = False; }
+/// impl RoleEq<Bob> for Carol   { type Output = False; }
+/// impl RoleEq<Carol> for Carol { type Output = True; }
+///
+/// fn interleaving_safe<G: InterleavingSafe>() {}
+///
+/// // Bob receives from Alice in one branch and sends to Carol in the other
+/// interleaving_safe::<TPar<
+///     Http,
+///     EmptyLabel,
+///     TMsg<Http, Ping, Alice, Bob, Message, TEnd<Http>>,
+///     TMsg<Http, Ping, Bob, Carol, Message, TEnd<Http>>,
+///     False,
+/// >>();
+/// ```
+pub trait InterleavingSafe {}
+
+impl<IO, Lbl: types::ProtocolLabel, L, R, IsDisjoint> InterleavingSafe
+    for TPar<IO, Lbl, L, R, IsDisjoint>
+where
+    L: TSession<IO>,
+    R: TSession<IO>,
+    Cons<L, Cons<R, Nil>>: BranchesLinear,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Branches> InterleavingSafe for TParN<IO, Lbl, Branches> where
+    Branches: SessionList<IO> + BranchesLinear
+{
+}
@@ -9,15 +9,32 @@
 //! - `EpSession`: Core trait for all local session types
 //! - `EpSend`: Endpoint sending operation
 //! - `EpRecv`: Endpoint receiving operation
-//! - `EpChoice`: Endpoint protocol choice
+//! - `EpBroadcast` / `EpGather`: Endpoint fan-out send and fan-in receive over a role set
+//! - `EpDelegate`: Endpoint handing a session endpoint over to a peer
+//! - `EpChoiceN`: Endpoint n-ary protocol choice
+//! - `EpSelect` / `EpOffer`: Endpoint choice made by this role / by a peer
+//! - `EpOptionalSelect` / `EpOptionalOffer`: Endpoint optional step decided here / by a peer
+//! - `EpPriorityOffer`: Endpoint choice with a preferred branch
+//! - `EpClose`: Endpoint closing its channel to a peer
+//! - `EpOpen`: Endpoint opening a channel to a peer
 //! - `EpPar`: Endpoint parallel composition
+//! - `EpParN`: Endpoint n-ary parallel composition
+//! - `EpShuffle`: Endpoint steps driven in whatever order they arrive
+//! - `EpRec` / `EpVar`: Endpoint recursion binder and loop-back
+//! - `EpTimeout`: Endpoint fragment under a timer
+//! - `EpDelay` / `EpDeadline`: Endpoint minimum wait and absolute deadline
+//! - `EpInterrupt` / `EpInterruptible`: Endpoint interruptible scope, raising or reacting
+//! - `EpRefine`: Endpoint fragment carrying a predicate
+//! - `EpThrow` / `EpCatch`: Endpoint failure and failure scope
 //! - `EpEnd`: Endpoint protocol termination
 //! - `EpSkip`: No-op type for roles not involved in a branch
+//! - `ComposeEp` / `EpSeq`: Sequential composition of local session types
 //!
 //! Local protocols are derived from global protocols through projection
 //! onto specific roles. They describe the sequence of operations that
 //! an individual participant must perform.
 
+use super::base::{Cons, Nil};
 use crate::sealed;
 use crate::types;
 use core::marker::PhantomData;
@@ -35,20 +52,125 @@ pub struct Void;
 /// Marker trait for protocol participants (roles).
 ///
 /// Implement this trait for each participant in your protocol.
-pub trait Role {}
-impl Role for TClient {}
-impl Role for TServer {}
-impl Role for TBroker {}
-impl Role for TWorker {}
-impl Role for Void {}
+///
+/// Runtime reflection, diagrams and monitor errors show a role by its `NAME`,
+/// or by its type name if `NAME` is not set. With the `derive` feature,
+/// `#[derive(Role)]` sets `NAME` to the name of the type, or to the one given
+/// by `#[besedarium(name = "...")]`.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Alice;
+/// impl Role for Alice {
+///     const NAME: Option<&'static str> = Some("alice");
+/// }
+/// assert_eq!(Alice::name(), "alice");
+/// assert_eq!(TClient::name(), "TClient");
+/// ```
+pub trait Role {
+    /// The name shown for this role; `None` shows the type name.
+    const NAME: Option<&'static str> = None;
+
+    /// `NAME`, or the type name if `NAME` is not set.
+    fn name() -> &'static str {
+        match Self::NAME {
+            Some(name) => name,
+            None => core::any::type_name::<Self>(),
+        }
+    }
+}
+impl Role for TClient {
+    const NAME: Option<&'static str> = Some("TClient");
+}
+impl Role for TServer {
+    const NAME: Option<&'static str> = Some("TServer");
+}
+impl Role for TBroker {
+    const NAME: Option<&'static str> = Some("TBroker");
+}
+impl Role for TWorker {
+    const NAME: Option<&'static str> = Some("TWorker");
+}
+impl Role for Void {
+    const NAME: Option<&'static str> = Some("Void");
+}
 
 /// Type-level equality for roles.
 ///
 /// Used to determine if two roles are the same at compile time (for projection).
+#[diagnostic::on_unimplemented(
+    message = "no `RoleEq` impl compares the roles `{Self}` and `{R}`",
+    label = "projection cannot tell `{Self}` and `{R}` apart",
+    note = "implement `RoleEq` for every ordered pair of roles in the protocol"
+)]
 pub trait RoleEq<R> {
     type Output;
 }
 
+// A built-in role equals itself and differs from the other built-in roles
+macro_rules! role_eq {
+    ($R:ident: $($Other:ident),*) => {
+        impl RoleEq<$R> for $R {
+            type Output = types::True;
+        }
+        $(
+            impl RoleEq<$Other> for $R {
+                type Output = types::False;
+            }
+        )*
+    };
+}
+
+role_eq!(TClient: TServer, TBroker, TWorker);
+role_eq!(TServer: TClient, TBroker, TWorker);
+role_eq!(TBroker: TClient, TServer, TWorker);
+role_eq!(TWorker: TClient, TServer, TBroker);
+
+/// Type-level equality between protocol labels.
+///
+/// Implemented by the user for every pair of labels the analyses need to
+/// tell apart, like `RoleEq` for roles.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct ContentType; impl ProtocolLabel for ContentType {}
+/// struct Accept; impl ProtocolLabel for Accept {}
+/// impl LabelEq<ContentType> for ContentType { type Output = True; }
+/// impl LabelEq<Accept> for ContentType { type Output = False; }
+/// impl LabelEq<ContentType> for Accept { type Output = False; }
+/// impl LabelEq<Accept> for Accept { type Output = True; }
+/// ```
+pub trait LabelEq<L> {
+    type Output: types::Bool;
+}
+
+// The label of unlabelled steps equals itself
+impl LabelEq<types::EmptyLabel> for types::EmptyLabel {
+    type Output = types::True;
+}
+
+/// Type-level membership of a role in a list of roles.
+///
+/// Implemented for every role that implements `RoleEq` against each member of
+/// `Set`; `Output` is `True` if any member equals the role.
+pub trait InRoleSet<Set> {
+    type Output: types::Bool;
+}
+
+impl<R> InRoleSet<Nil> for R {
+    type Output = types::False;
+}
+
+impl<R, H, T> InRoleSet<Cons<H, T>> for R
+where
+    R: RoleEq<H> + InRoleSet<T>,
+    <R as RoleEq<H>>::Output: types::BoolOr<<R as InRoleSet<T>>::Output>,
+{
+    type Output = types::Or<<R as RoleEq<H>>::Output, <R as InRoleSet<T>>::Output>;
+}
+
 /// Trait for all local (endpoint) session types.
 ///
 /// - `IO`: Protocol marker type.
@@ -77,6 +199,92 @@ pub struct EpRecv<IO, Lbl: types::ProtocolLabel, R, H, T>(PhantomData<(IO, Lbl,
 impl<IO, Lbl: types::ProtocolLabel, R, H, T> EpSession<IO, R> for EpRecv<IO, Lbl, R, H, T> {}
 impl<IO, Lbl: types::ProtocolLabel, R, H, T> sealed::Sealed for EpRecv<IO, Lbl, R, H, T> {}
 
+/// Endpoint type for sending one message to every role in a set, projected
+/// from `TBroadcast`.
+///
+/// - `IO`: Protocol marker type.
+/// - `Lbl`: Label for this interaction (for traceability and debugging).
+/// - `Me`: Role performing the send.
+/// - `ToSet`: Type-level list of roles receiving the message.
+/// - `H`: Message type being sent.
+/// - `T`: Continuation after sending.
+///
+/// Runtimes may map this to a native fan-out primitive instead of one send
+/// per receiver. Each receiver sees an ordinary `EpRecv`.
+pub struct EpBroadcast<IO, Lbl: types::ProtocolLabel, Me, ToSet, H, T>(
+    PhantomData<(IO, Lbl, Me, ToSet, H, T)>,
+);
+impl<IO, Lbl: types::ProtocolLabel, Me, ToSet, H, T> EpSession<IO, Me>
+    for EpBroadcast<IO, Lbl, Me, ToSet, H, T>
+{
+}
+impl<IO, Lbl: types::ProtocolLabel, Me, ToSet, H, T> sealed::Sealed
+    for EpBroadcast<IO, Lbl, Me, ToSet, H, T>
+{
+}
+
+/// Endpoint type for receiving one message from every role in a set.
+///
+/// - `IO`: Protocol marker type.
+/// - `Lbl`: Label for this interaction (for traceability and debugging).
+/// - `Me`: Role performing the receive.
+/// - `FromSet`: Type-level list of roles each sending one message.
+/// - `H`: Message type being received.
+/// - `T`: Continuation once every message has arrived.
+///
+/// The fan-in counterpart of `EpBroadcast`, for runtimes with a native gather
+/// primitive. No global combinator projects to it yet; it is written by hand.
+pub struct EpGather<IO, Lbl: types::ProtocolLabel, Me, FromSet, H, T>(
+    PhantomData<(IO, Lbl, Me, FromSet, H, T)>,
+);
+impl<IO, Lbl: types::ProtocolLabel, Me, FromSet, H, T> EpSession<IO, Me>
+    for EpGather<IO, Lbl, Me, FromSet, H, T>
+{
+}
+impl<IO, Lbl: types::ProtocolLabel, Me, FromSet, H, T> sealed::Sealed
+    for EpGather<IO, Lbl, Me, FromSet, H, T>
+{
+}
+
+/// Endpoint type for handing a session endpoint over to a peer, projected from
+/// `TDelegate`.
+///
+/// - `IO`: Protocol marker type.
+/// - `Lbl`: Label for this hand-over (for traceability and debugging).
+/// - `Me`: The role giving up the endpoint.
+/// - `DelegatedEp`: Local session type of the endpoint handed over.
+/// - `Cont`: Continuation after the hand-over.
+///
+/// After the hand-over `Me` no longer owns the delegated endpoint. The peer
+/// receives it as the payload of an ordinary `EpRecv`.
+pub struct EpDelegate<IO, Lbl: types::ProtocolLabel, Me, DelegatedEp, Cont>(
+    PhantomData<(IO, Lbl, Me, DelegatedEp, Cont)>,
+);
+impl<IO, Lbl: types::ProtocolLabel, Me, DelegatedEp, Cont> EpSession<IO, Me>
+    for EpDelegate<IO, Lbl, Me, DelegatedEp, Cont>
+{
+}
+impl<IO, Lbl: types::ProtocolLabel, Me, DelegatedEp, Cont> sealed::Sealed
+    for EpDelegate<IO, Lbl, Me, DelegatedEp, Cont>
+{
+}
+
+/// Delegation metadata a runtime needs to perform an `EpDelegate` step.
+///
+/// - `Endpoint`: Local session type of the endpoint handed over.
+/// - `Cont`: Local session type to continue with afterwards.
+pub trait DelegationOf {
+    type Endpoint;
+    type Cont;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, DelegatedEp, Cont> DelegationOf
+    for EpDelegate<IO, Lbl, Me, DelegatedEp, Cont>
+{
+    type Endpoint = DelegatedEp;
+    type Cont = Cont;
+}
+
 /// Endpoint type for protocol termination in a local protocol.
 ///
 /// - `IO`: Protocol marker type.
@@ -86,15 +294,62 @@ pub struct EpEnd<IO, Lbl: types::ProtocolLabel, R>(PhantomData<(IO, Lbl, R)>);
 impl<IO, Lbl: types::ProtocolLabel, R> EpSession<IO, R> for EpEnd<IO, Lbl, R> {}
 impl<IO, Lbl: types::ProtocolLabel, R> sealed::Sealed for EpEnd<IO, Lbl, R> {}
 
-/// Endpoint type for local protocol branching (choice/offer).
+/// Endpoint type for local n-ary branching over a flat list of branches.
 ///
 /// - `IO`: Protocol marker type.
 /// - `Lbl`: Label for this choice (for traceability and debugging).
 /// - `Me`: The role being projected.
-/// - `L`, `R`: The two local protocol branches.
-pub struct EpChoice<IO, Lbl: types::ProtocolLabel, Me, L, R>(PhantomData<(IO, Lbl, Me, L, R)>);
-impl<IO, Lbl: types::ProtocolLabel, Me, L, R> EpSession<IO, Me> for EpChoice<IO, Lbl, Me, L, R> {}
-impl<IO, Lbl: types::ProtocolLabel, Me, L, R> sealed::Sealed for EpChoice<IO, Lbl, Me, L, R> {}
+/// - `Branches`: Type-level list of local protocol branches.
+///
+/// Produced by projecting a `TChoiceN` whose branches are not tagged by
+/// distinct labels; branches the role does not take part in are `EpSkip`.
+pub struct EpChoiceN<IO, Lbl: types::ProtocolLabel, Me, Branches>(
+    PhantomData<(IO, Lbl, Me, Branches)>,
+);
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches> EpSession<IO, Me>
+    for EpChoiceN<IO, Lbl, Me, Branches>
+{
+}
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches> sealed::Sealed
+    for EpChoiceN<IO, Lbl, Me, Branches>
+{
+}
+
+/// Endpoint type for a choice this role makes (internal choice).
+///
+/// - `IO`: Protocol marker type.
+/// - `Lbl`: Label for this choice (for traceability and debugging).
+/// - `Me`: The role being projected, which picks the branch.
+/// - `Branches`: Type-level list of local protocol branches.
+pub struct EpSelect<IO, Lbl: types::ProtocolLabel, Me, Branches>(
+    PhantomData<(IO, Lbl, Me, Branches)>,
+);
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches> EpSession<IO, Me>
+    for EpSelect<IO, Lbl, Me, Branches>
+{
+}
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches> sealed::Sealed
+    for EpSelect<IO, Lbl, Me, Branches>
+{
+}
+
+/// Endpoint type for a choice a peer makes (external choice).
+///
+/// - `IO`: Protocol marker type.
+/// - `Lbl`: Label for this choice (for traceability and debugging).
+/// - `Me`: The role being projected, which reacts to the picked branch.
+/// - `Branches`: Type-level list of local protocol branches.
+pub struct EpOffer<IO, Lbl: types::ProtocolLabel, Me, Branches>(
+    PhantomData<(IO, Lbl, Me, Branches)>,
+);
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches> EpSession<IO, Me>
+    for EpOffer<IO, Lbl, Me, Branches>
+{
+}
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches> sealed::Sealed
+    for EpOffer<IO, Lbl, Me, Branches>
+{
+}
 
 /// Endpoint type for local protocol parallel composition.
 ///
@@ -106,46 +361,514 @@ pub struct EpPar<IO, Lbl: types::ProtocolLabel, Me, L, R>(PhantomData<(IO, Lbl,
 impl<IO, Lbl: types::ProtocolLabel, Me, L, R> EpSession<IO, Me> for EpPar<IO, Lbl, Me, L, R> {}
 impl<IO, Lbl: types::ProtocolLabel, Me, L, R> sealed::Sealed for EpPar<IO, Lbl, Me, L, R> {}
 
+/// Endpoint type for local n-ary parallel composition.
+///
+/// - `IO`: Protocol marker type.
+/// - `Lbl`: Label for this parallel composition (for traceability and debugging).
+/// - `Me`: The role being projected.
+/// - `Branches`: Type-level list of local protocol branches.
+///
+/// Produced by projecting `TParN`; only the branches the role takes part in
+/// are kept.
+pub struct EpParN<IO, Lbl: types::ProtocolLabel, Me, Branches>(
+    PhantomData<(IO, Lbl, Me, Branches)>,
+);
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches> EpSession<IO, Me>
+    for EpParN<IO, Lbl, Me, Branches>
+{
+}
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches> sealed::Sealed
+    for EpParN<IO, Lbl, Me, Branches>
+{
+}
+
+/// Endpoint type for a local shuffle.
+///
+/// - `IO`: Protocol marker type.
+/// - `Lbl`: Label for this shuffle (for traceability and debugging).
+/// - `Me`: The role being projected.
+/// - `Steps`: Type-level list of local steps.
+///
+/// Produced by projecting `TShuffle`; only the steps the role takes part in
+/// are kept. The runtime drives each step exactly once, dispatching incoming
+/// messages to the step whose label they carry, so steps may complete out of
+/// order.
+pub struct EpShuffle<IO, Lbl: types::ProtocolLabel, Me, Steps>(PhantomData<(IO, Lbl, Me, Steps)>);
+impl<IO, Lbl: types::ProtocolLabel, Me, Steps> EpSession<IO, Me> for EpShuffle<IO, Lbl, Me, Steps> {}
+impl<IO, Lbl: types::ProtocolLabel, Me, Steps> sealed::Sealed for EpShuffle<IO, Lbl, Me, Steps> {}
+
+/// Endpoint type for a local loop, projected from `TRecX`.
+///
+/// - `IO`: Protocol marker type.
+/// - `Lbl`: Label for this recursion (for traceability and debugging).
+/// - `Var`: Recursion variable bound by this loop.
+/// - `Body`: The local loop body; `EpVar<Var>` inside it restarts the loop.
+pub struct EpRec<IO, Lbl: types::ProtocolLabel, Var, Body>(PhantomData<(IO, Lbl, Var, Body)>);
+impl<IO, Lbl: types::ProtocolLabel, Me, Var, Body> EpSession<IO, Me> for EpRec<IO, Lbl, Var, Body> {}
+impl<IO, Lbl: types::ProtocolLabel, Var, Body> sealed::Sealed for EpRec<IO, Lbl, Var, Body> {}
+
+/// Endpoint type for jumping back to the enclosing `EpRec` bound to `Var`.
+pub struct EpVar<Var>(PhantomData<Var>);
+impl<IO, Me, Var> EpSession<IO, Me> for EpVar<Var> {}
+impl<Var> sealed::Sealed for EpVar<Var> {}
+
+/// Endpoint type for a local fragment under a timer, projected from `TTimeout`.
+///
+/// - `IO`: Protocol marker type.
+/// - `Lbl`: Label for this timeout (for traceability and debugging).
+/// - `Me`: The role being projected.
+/// - `Dur`: Duration marker the runtime should arm the timer with.
+/// - `Body`: Local protocol to run before the timer fires.
+/// - `OnTimeout`: Local protocol to switch to when the timer fires.
+pub struct EpTimeout<IO, Lbl: types::ProtocolLabel, Me, Dur, Body, OnTimeout>(
+    PhantomData<(IO, Lbl, Me, Dur, Body, OnTimeout)>,
+);
+impl<IO, Lbl: types::ProtocolLabel, Me, Dur, Body, OnTimeout> EpSession<IO, Me>
+    for EpTimeout<IO, Lbl, Me, Dur, Body, OnTimeout>
+{
+}
+impl<IO, Lbl: types::ProtocolLabel, Me, Dur, Body, OnTimeout> sealed::Sealed
+    for EpTimeout<IO, Lbl, Me, Dur, Body, OnTimeout>
+{
+}
+
+/// Endpoint type for a minimum wait before continuing, projected from `TDelay`.
+///
+/// - `IO`: Protocol marker type.
+/// - `Lbl`: Label for this constraint (for traceability and debugging).
+/// - `Me`: The role being projected.
+/// - `Dur`: Duration marker the runtime must wait out.
+/// - `T`: Continuation local protocol.
+pub struct EpDelay<IO, Lbl: types::ProtocolLabel, Me, Dur, T>(PhantomData<(IO, Lbl, Me, Dur, T)>);
+impl<IO, Lbl: types::ProtocolLabel, Me, Dur, T> EpSession<IO, Me> for EpDelay<IO, Lbl, Me, Dur, T> {}
+impl<IO, Lbl: types::ProtocolLabel, Me, Dur, T> sealed::Sealed for EpDelay<IO, Lbl, Me, Dur, T> {}
+
+/// Endpoint type for an absolute deadline, projected from `TDeadline`.
+///
+/// - `IO`: Protocol marker type.
+/// - `Lbl`: Label for this constraint (for traceability and debugging).
+/// - `Me`: The role being projected.
+/// - `Dur`: Duration marker measured from the session start.
+/// - `T`: Continuation local protocol.
+pub struct EpDeadline<IO, Lbl: types::ProtocolLabel, Me, Dur, T>(PhantomData<(IO, Lbl, Me, Dur, T)>);
+impl<IO, Lbl: types::ProtocolLabel, Me, Dur, T> EpSession<IO, Me> for EpDeadline<IO, Lbl, Me, Dur, T> {}
+impl<IO, Lbl: types::ProtocolLabel, Me, Dur, T> sealed::Sealed for EpDeadline<IO, Lbl, Me, Dur, T> {}
+
+/// Endpoint type for an interruptible scope this role may interrupt.
+///
+/// - `IO`: Protocol marker type.
+/// - `Lbl`: Label for this scope (for traceability and debugging).
+/// - `Me`: The role being projected, which may raise the interrupt.
+/// - `Scope`: Local protocol that runs until interrupted.
+/// - `Handler`: Local protocol to continue with after raising the interrupt.
+pub struct EpInterrupt<IO, Lbl: types::ProtocolLabel, Me, Scope, Handler>(
+    PhantomData<(IO, Lbl, Me, Scope, Handler)>,
+);
+impl<IO, Lbl: types::ProtocolLabel, Me, Scope, Handler> EpSession<IO, Me>
+    for EpInterrupt<IO, Lbl, Me, Scope, Handler>
+{
+}
+impl<IO, Lbl: types::ProtocolLabel, Me, Scope, Handler> sealed::Sealed
+    for EpInterrupt<IO, Lbl, Me, Scope, Handler>
+{
+}
+
+/// Endpoint type for an interruptible scope a peer may interrupt.
+///
+/// - `IO`: Protocol marker type.
+/// - `Lbl`: Label for this scope (for traceability and debugging).
+/// - `Me`: The role being projected, which must be ready to receive the interrupt.
+/// - `Scope`: Local protocol that runs until interrupted.
+/// - `Handler`: Local protocol to continue with once interrupted.
+pub struct EpInterruptible<IO, Lbl: types::ProtocolLabel, Me, Scope, Handler>(
+    PhantomData<(IO, Lbl, Me, Scope, Handler)>,
+);
+impl<IO, Lbl: types::ProtocolLabel, Me, Scope, Handler> EpSession<IO, Me>
+    for EpInterruptible<IO, Lbl, Me, Scope, Handler>
+{
+}
+impl<IO, Lbl: types::ProtocolLabel, Me, Scope, Handler> sealed::Sealed
+    for EpInterruptible<IO, Lbl, Me, Scope, Handler>
+{
+}
+
+/// Endpoint type for a refined fragment.
+///
+/// - `Pred`: The predicate carried over from the global `TRefine`.
+/// - `Inner`: The refined local protocol.
+pub struct EpRefine<Pred, Inner>(PhantomData<(Pred, Inner)>);
+impl<IO, Me, Pred: types::Predicate, Inner: EpSession<IO, Me>> EpSession<IO, Me>
+    for EpRefine<Pred, Inner>
+{
+}
+impl<Pred, Inner> sealed::Sealed for EpRefine<Pred, Inner> {}
+
+/// Endpoint type for an optional step this role decides on.
+///
+/// - `IO`: Protocol marker type.
+/// - `Lbl`: Label for this choice (for traceability and debugging).
+/// - `Me`: The role being projected (the decider).
+/// - `Body`: Local protocol of the optional step.
+/// - `Cont`: Local protocol that follows whether or not `Body` ran.
+pub struct EpOptionalSelect<IO, Lbl: types::ProtocolLabel, Me, Body, Cont>(
+    PhantomData<(IO, Lbl, Me, Body, Cont)>,
+);
+impl<IO, Lbl: types::ProtocolLabel, Me, Body, Cont> EpSession<IO, Me>
+    for EpOptionalSelect<IO, Lbl, Me, Body, Cont>
+{
+}
+impl<IO, Lbl: types::ProtocolLabel, Me, Body, Cont> sealed::Sealed
+    for EpOptionalSelect<IO, Lbl, Me, Body, Cont>
+{
+}
+
+/// Endpoint type for an optional step a peer decides on.
+///
+/// - `IO`: Protocol marker type.
+/// - `Lbl`: Label for this choice (for traceability and debugging).
+/// - `Me`: The role being projected, which takes part in the optional step.
+/// - `Body`: Local protocol of the optional step.
+/// - `Cont`: Local protocol that follows whether or not `Body` ran.
+pub struct EpOptionalOffer<IO, Lbl: types::ProtocolLabel, Me, Body, Cont>(
+    PhantomData<(IO, Lbl, Me, Body, Cont)>,
+);
+impl<IO, Lbl: types::ProtocolLabel, Me, Body, Cont> EpSession<IO, Me>
+    for EpOptionalOffer<IO, Lbl, Me, Body, Cont>
+{
+}
+impl<IO, Lbl: types::ProtocolLabel, Me, Body, Cont> sealed::Sealed
+    for EpOptionalOffer<IO, Lbl, Me, Body, Cont>
+{
+}
+
+/// Endpoint type for raising a failure.
+///
+/// - `IO`: Protocol marker type.
+/// - `Lbl`: Label for this failure point (for traceability and debugging).
+/// - `Me`: The role raising the error.
+/// - `Err`: The error payload.
+pub struct EpThrow<IO, Lbl: types::ProtocolLabel, Me, Err>(PhantomData<(IO, Lbl, Me, Err)>);
+impl<IO, Lbl: types::ProtocolLabel, Me, Err> EpSession<IO, Me> for EpThrow<IO, Lbl, Me, Err> {}
+impl<IO, Lbl: types::ProtocolLabel, Me, Err> sealed::Sealed for EpThrow<IO, Lbl, Me, Err> {}
+
+/// Endpoint type for a failure scope.
+///
+/// - `IO`: Protocol marker type.
+/// - `Lbl`: Label for this scope (for traceability and debugging).
+/// - `Me`: The role being projected.
+/// - `Body`: Local protocol that may fail.
+/// - `Handler`: Local protocol to continue with after a failure.
+pub struct EpCatch<IO, Lbl: types::ProtocolLabel, Me, Body, Handler>(
+    PhantomData<(IO, Lbl, Me, Body, Handler)>,
+);
+impl<IO, Lbl: types::ProtocolLabel, Me, Body, Handler> EpSession<IO, Me>
+    for EpCatch<IO, Lbl, Me, Body, Handler>
+{
+}
+impl<IO, Lbl: types::ProtocolLabel, Me, Body, Handler> sealed::Sealed
+    for EpCatch<IO, Lbl, Me, Body, Handler>
+{
+}
+
+/// Endpoint type for a prioritized choice.
+///
+/// - `IO`: Protocol marker type.
+/// - `Lbl`: Label for this choice (for traceability and debugging).
+/// - `Me`: The role being projected.
+/// - `High`: Local protocol of the preferred branch.
+/// - `Low`: Local protocol of the fallback branch.
+pub struct EpPriorityOffer<IO, Lbl: types::ProtocolLabel, Me, High, Low>(
+    PhantomData<(IO, Lbl, Me, High, Low)>,
+);
+impl<IO, Lbl: types::ProtocolLabel, Me, High, Low> EpSession<IO, Me>
+    for EpPriorityOffer<IO, Lbl, Me, High, Low>
+{
+}
+impl<IO, Lbl: types::ProtocolLabel, Me, High, Low> sealed::Sealed
+    for EpPriorityOffer<IO, Lbl, Me, High, Low>
+{
+}
+
+/// Endpoint type for closing the channel to a peer.
+///
+/// - `IO`: Protocol marker type.
+/// - `Lbl`: Label for this step (for traceability and debugging).
+/// - `Me`: The role being projected.
+/// - `Peer`: The role at the other end of the closed channel.
+/// - `Cont`: Local protocol after closing.
+pub struct EpClose<IO, Lbl: types::ProtocolLabel, Me, Peer, Cont>(
+    PhantomData<(IO, Lbl, Me, Peer, Cont)>,
+);
+impl<IO, Lbl: types::ProtocolLabel, Me, Peer, Cont> EpSession<IO, Me>
+    for EpClose<IO, Lbl, Me, Peer, Cont>
+{
+}
+impl<IO, Lbl: types::ProtocolLabel, Me, Peer, Cont> sealed::Sealed
+    for EpClose<IO, Lbl, Me, Peer, Cont>
+{
+}
+
+/// Endpoint type for opening a channel to a peer.
+///
+/// - `IO`: Protocol marker type.
+/// - `Lbl`: Label for this step (for traceability and debugging).
+/// - `Me`: The role being projected.
+/// - `Peer`: The role at the other end of the new channel.
+/// - `Cont`: Local protocol after opening.
+pub struct EpOpen<IO, Lbl: types::ProtocolLabel, Me, Peer, Cont>(
+    PhantomData<(IO, Lbl, Me, Peer, Cont)>,
+);
+impl<IO, Lbl: types::ProtocolLabel, Me, Peer, Cont> EpSession<IO, Me>
+    for EpOpen<IO, Lbl, Me, Peer, Cont>
+{
+}
+impl<IO, Lbl: types::ProtocolLabel, Me, Peer, Cont> sealed::Sealed
+    for EpOpen<IO, Lbl, Me, Peer, Cont>
+{
+}
+
 /// No-op endpoint type for roles uninvolved in a protocol branch.
 ///
 /// - `IO`: Protocol marker type.
 /// - `Lbl`: Label for this skip operation (for traceability and debugging).
 /// - `R`: Role that is skipping this branch.
 ///
-/// Used to improve type-level precision for projections.
+/// This is the single "no behaviour" endpoint: composition and skip filtering
+/// classify it through [`IsSkip`]. The deprecated `EpSilent` is an alias
+/// for the unlabelled form.
 pub struct EpSkip<IO, Lbl: types::ProtocolLabel, R>(PhantomData<(IO, Lbl, R)>);
 impl<IO, Lbl: types::ProtocolLabel, R> EpSession<IO, R> for EpSkip<IO, Lbl, R> {}
 impl<IO, Lbl: types::ProtocolLabel, R> sealed::Sealed for EpSkip<IO, Lbl, R> {}
 
-/// Type-level marker types for dispatch
-pub struct IsEpSkipType;
-pub struct IsNotEpSkipType;
+/// Sequential composition of local session types.
+///
+/// `<A as ComposeEp<B>>::Output` runs `A` and then `B`: every `EpEnd` (or
+/// `EpSkip`) leaf of `A` is replaced by `B`, mirroring `TSession::Compose`
+/// for global types. This lets handlers be assembled from separately
+/// projected fragments.
+///
+/// Loop-backs (`EpVar`) and failures (`EpThrow`) never reach the end and are
+/// left alone; optional steps only sequence their continuation.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// type Ask = EpSend<Http, EmptyLabel, TClient, Message, EpEnd<Http, EmptyLabel, TClient>>;
+/// type Answer = EpRecv<Http, EmptyLabel, TClient, Response, EpEnd<Http, EmptyLabel, TClient>>;
+/// assert_type_eq!(
+///     <Ask as ComposeEp<Answer>>::Output,
+///     EpSend<Http, EmptyLabel, TClient, Message, Answer>
+/// );
+/// ```
+pub trait ComposeEp<Rhs> {
+    type Output;
+}
+
+/// EpSeq: `A` followed by `B`, see `ComposeEp`.
+pub type EpSeq<A, B> = <A as ComposeEp<B>>::Output;
 
-/// Implementation marker trait for EpSkip dispatch
-pub trait IsEpSkipTypeImpl<IO, Me: Role> {
-    type TypeMarker;
+/// Composes every local session type in a type-level list with `Rhs`.
+pub trait ComposeEachEp<Rhs> {
+    type Output;
 }
 
-// EpSkip maps to IsEpSkipType
-impl<IO, Lbl: types::ProtocolLabel, Me: Role> IsEpSkipTypeImpl<IO, Me> for EpSkip<IO, Lbl, Me> {
-    type TypeMarker = IsEpSkipType;
+impl<Rhs> ComposeEachEp<Rhs> for Nil {
+    type Output = Nil;
 }
 
-// All other EpSession<IO, Me> types map to IsNotEpSkipType
-impl<IO, Lbl: types::ProtocolLabel, Me: Role, H, T> IsEpSkipTypeImpl<IO, Me> for EpSend<IO, Lbl, Me, H, T> {
-    type TypeMarker = IsNotEpSkipType;
+impl<H: ComposeEp<Rhs>, T: ComposeEachEp<Rhs>, Rhs> ComposeEachEp<Rhs> for Cons<H, T> {
+    type Output = Cons<<H as ComposeEp<Rhs>>::Output, <T as ComposeEachEp<Rhs>>::Output>;
 }
-impl<IO, Lbl: types::ProtocolLabel, Me: Role, H, T> IsEpSkipTypeImpl<IO, Me> for EpRecv<IO, Lbl, Me, H, T> {
-    type TypeMarker = IsNotEpSkipType;
+
+impl<IO, Lbl: types::ProtocolLabel, R, Rhs> ComposeEp<Rhs> for EpEnd<IO, Lbl, R> {
+    type Output = Rhs;
 }
-impl<IO, Lbl: types::ProtocolLabel, MeChoice: Role, L, R> IsEpSkipTypeImpl<IO, MeChoice> for EpChoice<IO, Lbl, MeChoice, L, R> {
-    type TypeMarker = IsNotEpSkipType;
+
+impl<IO, Lbl: types::ProtocolLabel, R, Rhs> ComposeEp<Rhs> for EpSkip<IO, Lbl, R> {
+    type Output = Rhs;
 }
-impl<IO, Lbl: types::ProtocolLabel, MePar: Role, L, R> IsEpSkipTypeImpl<IO, MePar> for EpPar<IO, Lbl, MePar, L, R> {
-    type TypeMarker = IsNotEpSkipType;
+
+impl<IO, Lbl: types::ProtocolLabel, R, H, T: ComposeEp<Rhs>, Rhs> ComposeEp<Rhs>
+    for EpSend<IO, Lbl, R, H, T>
+{
+    type Output = EpSend<IO, Lbl, R, H, <T as ComposeEp<Rhs>>::Output>;
 }
-impl<IO, Lbl: types::ProtocolLabel, Me: Role> IsEpSkipTypeImpl<IO, Me> for EpEnd<IO, Lbl, Me> {
-    type TypeMarker = IsNotEpSkipType;
+
+impl<IO, Lbl: types::ProtocolLabel, R, H, T: ComposeEp<Rhs>, Rhs> ComposeEp<Rhs>
+    for EpRecv<IO, Lbl, R, H, T>
+{
+    type Output = EpRecv<IO, Lbl, R, H, <T as ComposeEp<Rhs>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, ToSet, H, T: ComposeEp<Rhs>, Rhs> ComposeEp<Rhs>
+    for EpBroadcast<IO, Lbl, Me, ToSet, H, T>
+{
+    type Output = EpBroadcast<IO, Lbl, Me, ToSet, H, <T as ComposeEp<Rhs>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, FromSet, H, T: ComposeEp<Rhs>, Rhs> ComposeEp<Rhs>
+    for EpGather<IO, Lbl, Me, FromSet, H, T>
+{
+    type Output = EpGather<IO, Lbl, Me, FromSet, H, <T as ComposeEp<Rhs>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, DelegatedEp, Cont: ComposeEp<Rhs>, Rhs> ComposeEp<Rhs>
+    for EpDelegate<IO, Lbl, Me, DelegatedEp, Cont>
+{
+    type Output = EpDelegate<IO, Lbl, Me, DelegatedEp, <Cont as ComposeEp<Rhs>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches: ComposeEachEp<Rhs>, Rhs> ComposeEp<Rhs>
+    for EpChoiceN<IO, Lbl, Me, Branches>
+{
+    type Output = EpChoiceN<IO, Lbl, Me, <Branches as ComposeEachEp<Rhs>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches: ComposeEachEp<Rhs>, Rhs> ComposeEp<Rhs>
+    for EpSelect<IO, Lbl, Me, Branches>
+{
+    type Output = EpSelect<IO, Lbl, Me, <Branches as ComposeEachEp<Rhs>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches: ComposeEachEp<Rhs>, Rhs> ComposeEp<Rhs>
+    for EpOffer<IO, Lbl, Me, Branches>
+{
+    type Output = EpOffer<IO, Lbl, Me, <Branches as ComposeEachEp<Rhs>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Body, Cont: ComposeEp<Rhs>, Rhs> ComposeEp<Rhs>
+    for EpOptionalSelect<IO, Lbl, Me, Body, Cont>
+{
+    type Output = EpOptionalSelect<IO, Lbl, Me, Body, <Cont as ComposeEp<Rhs>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Body, Cont: ComposeEp<Rhs>, Rhs> ComposeEp<Rhs>
+    for EpOptionalOffer<IO, Lbl, Me, Body, Cont>
+{
+    type Output = EpOptionalOffer<IO, Lbl, Me, Body, <Cont as ComposeEp<Rhs>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, High: ComposeEp<Rhs>, Low: ComposeEp<Rhs>, Rhs>
+    ComposeEp<Rhs> for EpPriorityOffer<IO, Lbl, Me, High, Low>
+{
+    type Output = EpPriorityOffer<
+        IO,
+        Lbl,
+        Me,
+        <High as ComposeEp<Rhs>>::Output,
+        <Low as ComposeEp<Rhs>>::Output,
+    >;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, L: ComposeEp<Rhs>, R: ComposeEp<Rhs>, Rhs> ComposeEp<Rhs>
+    for EpPar<IO, Lbl, Me, L, R>
+{
+    type Output = EpPar<IO, Lbl, Me, <L as ComposeEp<Rhs>>::Output, <R as ComposeEp<Rhs>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches: ComposeEachEp<Rhs>, Rhs> ComposeEp<Rhs>
+    for EpParN<IO, Lbl, Me, Branches>
+{
+    type Output = EpParN<IO, Lbl, Me, <Branches as ComposeEachEp<Rhs>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Steps: ComposeEachEp<Rhs>, Rhs> ComposeEp<Rhs>
+    for EpShuffle<IO, Lbl, Me, Steps>
+{
+    type Output = EpShuffle<IO, Lbl, Me, <Steps as ComposeEachEp<Rhs>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Var, Body: ComposeEp<Rhs>, Rhs> ComposeEp<Rhs>
+    for EpRec<IO, Lbl, Var, Body>
+{
+    type Output = EpRec<IO, Lbl, Var, <Body as ComposeEp<Rhs>>::Output>;
+}
+
+impl<Var, Rhs> ComposeEp<Rhs> for EpVar<Var> {
+    type Output = EpVar<Var>;
+}
+
+impl<
+        IO,
+        Lbl: types::ProtocolLabel,
+        Me,
+        Dur,
+        Body: ComposeEp<Rhs>,
+        OnTimeout: ComposeEp<Rhs>,
+        Rhs,
+    > ComposeEp<Rhs> for EpTimeout<IO, Lbl, Me, Dur, Body, OnTimeout>
+{
+    type Output = EpTimeout<
+        IO,
+        Lbl,
+        Me,
+        Dur,
+        <Body as ComposeEp<Rhs>>::Output,
+        <OnTimeout as ComposeEp<Rhs>>::Output,
+    >;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Dur, T: ComposeEp<Rhs>, Rhs> ComposeEp<Rhs>
+    for EpDelay<IO, Lbl, Me, Dur, T>
+{
+    type Output = EpDelay<IO, Lbl, Me, Dur, <T as ComposeEp<Rhs>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Dur, T: ComposeEp<Rhs>, Rhs> ComposeEp<Rhs>
+    for EpDeadline<IO, Lbl, Me, Dur, T>
+{
+    type Output = EpDeadline<IO, Lbl, Me, Dur, <T as ComposeEp<Rhs>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Scope: ComposeEp<Rhs>, Handler: ComposeEp<Rhs>, Rhs>
+    ComposeEp<Rhs> for EpInterrupt<IO, Lbl, Me, Scope, Handler>
+{
+    type Output = EpInterrupt<
+        IO,
+        Lbl,
+        Me,
+        <Scope as ComposeEp<Rhs>>::Output,
+        <Handler as ComposeEp<Rhs>>::Output,
+    >;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Scope: ComposeEp<Rhs>, Handler: ComposeEp<Rhs>, Rhs>
+    ComposeEp<Rhs> for EpInterruptible<IO, Lbl, Me, Scope, Handler>
+{
+    type Output = EpInterruptible<
+        IO,
+        Lbl,
+        Me,
+        <Scope as ComposeEp<Rhs>>::Output,
+        <Handler as ComposeEp<Rhs>>::Output,
+    >;
+}
+
+impl<Pred, Inner: ComposeEp<Rhs>, Rhs> ComposeEp<Rhs> for EpRefine<Pred, Inner> {
+    type Output = EpRefine<Pred, <Inner as ComposeEp<Rhs>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Err, Rhs> ComposeEp<Rhs> for EpThrow<IO, Lbl, Me, Err> {
+    type Output = EpThrow<IO, Lbl, Me, Err>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Body: ComposeEp<Rhs>, Handler: ComposeEp<Rhs>, Rhs>
+    ComposeEp<Rhs> for EpCatch<IO, Lbl, Me, Body, Handler>
+{
+    type Output =
+        EpCatch<IO, Lbl, Me, <Body as ComposeEp<Rhs>>::Output, <Handler as ComposeEp<Rhs>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Peer, Cont: ComposeEp<Rhs>, Rhs> ComposeEp<Rhs>
+    for EpClose<IO, Lbl, Me, Peer, Cont>
+{
+    type Output = EpClose<IO, Lbl, Me, Peer, <Cont as ComposeEp<Rhs>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Peer, Cont: ComposeEp<Rhs>, Rhs> ComposeEp<Rhs>
+    for EpOpen<IO, Lbl, Me, Peer, Cont>
+{
+    type Output = EpOpen<IO, Lbl, Me, Peer, <Cont as ComposeEp<Rhs>>::Output>;
 }
 
 /// Traits for checking if an endpoint type is a specific variant
@@ -170,8 +893,18 @@ impl<IO, Lbl: types::ProtocolLabel, R, H, T, Me: Role> IsEpSkipVariant<IO, Me> f
 impl<IO, Lbl: types::ProtocolLabel, R, H, T, Me: Role> IsEpSkipVariant<IO, Me> for EpRecv<IO, Lbl, R, H, T> {
     type Output = types::False;
 }
-impl<IO, Lbl: types::ProtocolLabel, MeChoice: Role, L, R, MeFilter: Role> IsEpSkipVariant<IO, MeFilter>
-    for EpChoice<IO, Lbl, MeChoice, L, R>
+impl<IO, Lbl: types::ProtocolLabel, MeChoice: Role, Branches, MeFilter: Role>
+    IsEpSkipVariant<IO, MeFilter> for EpChoiceN<IO, Lbl, MeChoice, Branches>
+{
+    type Output = types::False;
+}
+impl<IO, Lbl: types::ProtocolLabel, MeChoice: Role, Branches, MeFilter: Role>
+    IsEpSkipVariant<IO, MeFilter> for EpSelect<IO, Lbl, MeChoice, Branches>
+{
+    type Output = types::False;
+}
+impl<IO, Lbl: types::ProtocolLabel, MeChoice: Role, Branches, MeFilter: Role>
+    IsEpSkipVariant<IO, MeFilter> for EpOffer<IO, Lbl, MeChoice, Branches>
 {
     type Output = types::False;
 }
@@ -180,6 +913,102 @@ impl<IO, Lbl: types::ProtocolLabel, MePar: Role, L, R, MeFilter: Role> IsEpSkipV
 {
     type Output = types::False;
 }
+impl<IO, Lbl: types::ProtocolLabel, MePar: Role, Branches, MeFilter: Role>
+    IsEpSkipVariant<IO, MeFilter> for EpParN<IO, Lbl, MePar, Branches>
+{
+    type Output = types::False;
+}
+impl<IO, Lbl: types::ProtocolLabel, MeX: Role, Scope, Handler, MeFilter: Role>
+    IsEpSkipVariant<IO, MeFilter> for EpInterrupt<IO, Lbl, MeX, Scope, Handler>
+{
+    type Output = types::False;
+}
+impl<IO, Lbl: types::ProtocolLabel, MeX: Role, Scope, Handler, MeFilter: Role>
+    IsEpSkipVariant<IO, MeFilter> for EpInterruptible<IO, Lbl, MeX, Scope, Handler>
+{
+    type Output = types::False;
+}
+impl<IO, MeFilter: Role, Pred, Inner> IsEpSkipVariant<IO, MeFilter> for EpRefine<Pred, Inner> {
+    type Output = types::False;
+}
+impl<IO, Lbl: types::ProtocolLabel, MeX: Role, Body, Cont, MeFilter: Role>
+    IsEpSkipVariant<IO, MeFilter> for EpOptionalSelect<IO, Lbl, MeX, Body, Cont>
+{
+    type Output = types::False;
+}
+impl<IO, Lbl: types::ProtocolLabel, MeX: Role, Body, Cont, MeFilter: Role>
+    IsEpSkipVariant<IO, MeFilter> for EpOptionalOffer<IO, Lbl, MeX, Body, Cont>
+{
+    type Output = types::False;
+}
+impl<IO, Lbl: types::ProtocolLabel, MeX: Role, High, Low, MeFilter: Role>
+    IsEpSkipVariant<IO, MeFilter> for EpPriorityOffer<IO, Lbl, MeX, High, Low>
+{
+    type Output = types::False;
+}
+impl<IO, Lbl: types::ProtocolLabel, MeX: Role, Peer, Cont, MeFilter: Role>
+    IsEpSkipVariant<IO, MeFilter> for EpClose<IO, Lbl, MeX, Peer, Cont>
+{
+    type Output = types::False;
+}
+impl<IO, Lbl: types::ProtocolLabel, MeX: Role, Peer, Cont, MeFilter: Role>
+    IsEpSkipVariant<IO, MeFilter> for EpOpen<IO, Lbl, MeX, Peer, Cont>
+{
+    type Output = types::False;
+}
+impl<IO, Lbl: types::ProtocolLabel, MeX: Role, Steps, MeFilter: Role> IsEpSkipVariant<IO, MeFilter>
+    for EpShuffle<IO, Lbl, MeX, Steps>
+{
+    type Output = types::False;
+}
+impl<IO, Lbl: types::ProtocolLabel, Var, Body, MeFilter: Role> IsEpSkipVariant<IO, MeFilter>
+    for EpRec<IO, Lbl, Var, Body>
+{
+    type Output = types::False;
+}
+impl<IO, Var, MeFilter: Role> IsEpSkipVariant<IO, MeFilter> for EpVar<Var> {
+    type Output = types::False;
+}
+impl<IO, Lbl: types::ProtocolLabel, MeX: Role, Err, MeFilter: Role> IsEpSkipVariant<IO, MeFilter>
+    for EpThrow<IO, Lbl, MeX, Err>
+{
+    type Output = types::False;
+}
+impl<IO, Lbl: types::ProtocolLabel, MeX: Role, Body, Handler, MeFilter: Role>
+    IsEpSkipVariant<IO, MeFilter> for EpCatch<IO, Lbl, MeX, Body, Handler>
+{
+    type Output = types::False;
+}
+impl<IO, Lbl: types::ProtocolLabel, MeX: Role, Dur, Body, OnTimeout, MeFilter: Role>
+    IsEpSkipVariant<IO, MeFilter> for EpTimeout<IO, Lbl, MeX, Dur, Body, OnTimeout>
+{
+    type Output = types::False;
+}
+impl<IO, Lbl: types::ProtocolLabel, MeX: Role, Dur, T, MeFilter: Role> IsEpSkipVariant<IO, MeFilter>
+    for EpDelay<IO, Lbl, MeX, Dur, T>
+{
+    type Output = types::False;
+}
+impl<IO, Lbl: types::ProtocolLabel, MeX: Role, Dur, T, MeFilter: Role> IsEpSkipVariant<IO, MeFilter>
+    for EpDeadline<IO, Lbl, MeX, Dur, T>
+{
+    type Output = types::False;
+}
+impl<IO, Lbl: types::ProtocolLabel, MeX: Role, DelegatedEp, Cont, MeFilter: Role>
+    IsEpSkipVariant<IO, MeFilter> for EpDelegate<IO, Lbl, MeX, DelegatedEp, Cont>
+{
+    type Output = types::False;
+}
+impl<IO, Lbl: types::ProtocolLabel, MeX: Role, ToSet, H, T, MeFilter: Role>
+    IsEpSkipVariant<IO, MeFilter> for EpBroadcast<IO, Lbl, MeX, ToSet, H, T>
+{
+    type Output = types::False;
+}
+impl<IO, Lbl: types::ProtocolLabel, MeX: Role, FromSet, H, T, MeFilter: Role>
+    IsEpSkipVariant<IO, MeFilter> for EpGather<IO, Lbl, MeX, FromSet, H, T>
+{
+    type Output = types::False;
+}
 impl<IO, Lbl: types::ProtocolLabel, MeEnd: Role, MeFilter: Role> IsEpSkipVariant<IO, MeFilter> for EpEnd<IO, Lbl, MeEnd> {
     type Output = types::False;
 }
@@ -194,8 +1023,18 @@ impl<IO, Lbl: types::ProtocolLabel, R, H, T, Me: Role> IsEpEndVariant<IO, Me> fo
 impl<IO, Lbl: types::ProtocolLabel, R, H, T, Me: Role> IsEpEndVariant<IO, Me> for EpRecv<IO, Lbl, R, H, T> {
     type Output = types::False;
 }
-impl<IO, Lbl: types::ProtocolLabel, MeChoice: Role, L, R, MeFilter: Role> IsEpEndVariant<IO, MeFilter>
-    for EpChoice<IO, Lbl, MeChoice, L, R>
+impl<IO, Lbl: types::ProtocolLabel, MeChoice: Role, Branches, MeFilter: Role>
+    IsEpEndVariant<IO, MeFilter> for EpChoiceN<IO, Lbl, MeChoice, Branches>
+{
+    type Output = types::False;
+}
+impl<IO, Lbl: types::ProtocolLabel, MeChoice: Role, Branches, MeFilter: Role>
+    IsEpEndVariant<IO, MeFilter> for EpSelect<IO, Lbl, MeChoice, Branches>
+{
+    type Output = types::False;
+}
+impl<IO, Lbl: types::ProtocolLabel, MeChoice: Role, Branches, MeFilter: Role>
+    IsEpEndVariant<IO, MeFilter> for EpOffer<IO, Lbl, MeChoice, Branches>
 {
     type Output = types::False;
 }
@@ -204,6 +1043,102 @@ impl<IO, Lbl: types::ProtocolLabel, MePar: Role, L, R, MeFilter: Role> IsEpEndVa
 {
     type Output = types::False;
 }
+impl<IO, Lbl: types::ProtocolLabel, MePar: Role, Branches, MeFilter: Role>
+    IsEpEndVariant<IO, MeFilter> for EpParN<IO, Lbl, MePar, Branches>
+{
+    type Output = types::False;
+}
+impl<IO, Lbl: types::ProtocolLabel, MeX: Role, Scope, Handler, MeFilter: Role>
+    IsEpEndVariant<IO, MeFilter> for EpInterrupt<IO, Lbl, MeX, Scope, Handler>
+{
+    type Output = types::False;
+}
+impl<IO, Lbl: types::ProtocolLabel, MeX: Role, Scope, Handler, MeFilter: Role>
+    IsEpEndVariant<IO, MeFilter> for EpInterruptible<IO, Lbl, MeX, Scope, Handler>
+{
+    type Output = types::False;
+}
+impl<IO, MeFilter: Role, Pred, Inner> IsEpEndVariant<IO, MeFilter> for EpRefine<Pred, Inner> {
+    type Output = types::False;
+}
+impl<IO, Lbl: types::ProtocolLabel, MeX: Role, Body, Cont, MeFilter: Role>
+    IsEpEndVariant<IO, MeFilter> for EpOptionalSelect<IO, Lbl, MeX, Body, Cont>
+{
+    type Output = types::False;
+}
+impl<IO, Lbl: types::ProtocolLabel, MeX: Role, Body, Cont, MeFilter: Role>
+    IsEpEndVariant<IO, MeFilter> for EpOptionalOffer<IO, Lbl, MeX, Body, Cont>
+{
+    type Output = types::False;
+}
+impl<IO, Lbl: types::ProtocolLabel, MeX: Role, High, Low, MeFilter: Role>
+    IsEpEndVariant<IO, MeFilter> for EpPriorityOffer<IO, Lbl, MeX, High, Low>
+{
+    type Output = types::False;
+}
+impl<IO, Lbl: types::ProtocolLabel, MeX: Role, Peer, Cont, MeFilter: Role>
+    IsEpEndVariant<IO, MeFilter> for EpClose<IO, Lbl, MeX, Peer, Cont>
+{
+    type Output = types::False;
+}
+impl<IO, Lbl: types::ProtocolLabel, MeX: Role, Peer, Cont, MeFilter: Role>
+    IsEpEndVariant<IO, MeFilter> for EpOpen<IO, Lbl, MeX, Peer, Cont>
+{
+    type Output = types::False;
+}
+impl<IO, Lbl: types::ProtocolLabel, MeX: Role, Steps, MeFilter: Role> IsEpEndVariant<IO, MeFilter>
+    for EpShuffle<IO, Lbl, MeX, Steps>
+{
+    type Output = types::False;
+}
+impl<IO, Lbl: types::ProtocolLabel, Var, Body, MeFilter: Role> IsEpEndVariant<IO, MeFilter>
+    for EpRec<IO, Lbl, Var, Body>
+{
+    type Output = types::False;
+}
+impl<IO, Var, MeFilter: Role> IsEpEndVariant<IO, MeFilter> for EpVar<Var> {
+    type Output = types::False;
+}
+impl<IO, Lbl: types::ProtocolLabel, MeX: Role, Err, MeFilter: Role> IsEpEndVariant<IO, MeFilter>
+    for EpThrow<IO, Lbl, MeX, Err>
+{
+    type Output = types::False;
+}
+impl<IO, Lbl: types::ProtocolLabel, MeX: Role, Body, Handler, MeFilter: Role>
+    IsEpEndVariant<IO, MeFilter> for EpCatch<IO, Lbl, MeX, Body, Handler>
+{
+    type Output = types::False;
+}
+impl<IO, Lbl: types::ProtocolLabel, MeX: Role, Dur, Body, OnTimeout, MeFilter: Role>
+    IsEpEndVariant<IO, MeFilter> for EpTimeout<IO, Lbl, MeX, Dur, Body, OnTimeout>
+{
+    type Output = types::False;
+}
+impl<IO, Lbl: types::ProtocolLabel, MeX: Role, Dur, T, MeFilter: Role> IsEpEndVariant<IO, MeFilter>
+    for EpDelay<IO, Lbl, MeX, Dur, T>
+{
+    type Output = types::False;
+}
+impl<IO, Lbl: types::ProtocolLabel, MeX: Role, Dur, T, MeFilter: Role> IsEpEndVariant<IO, MeFilter>
+    for EpDeadline<IO, Lbl, MeX, Dur, T>
+{
+    type Output = types::False;
+}
+impl<IO, Lbl: types::ProtocolLabel, MeX: Role, DelegatedEp, Cont, MeFilter: Role>
+    IsEpEndVariant<IO, MeFilter> for EpDelegate<IO, Lbl, MeX, DelegatedEp, Cont>
+{
+    type Output = types::False;
+}
+impl<IO, Lbl: types::ProtocolLabel, MeX: Role, ToSet, H, T, MeFilter: Role>
+    IsEpEndVariant<IO, MeFilter> for EpBroadcast<IO, Lbl, MeX, ToSet, H, T>
+{
+    type Output = types::False;
+}
+impl<IO, Lbl: types::ProtocolLabel, MeX: Role, FromSet, H, T, MeFilter: Role>
+    IsEpEndVariant<IO, MeFilter> for EpGather<IO, Lbl, MeX, FromSet, H, T>
+{
+    type Output = types::False;
+}
 impl<IO, Lbl: types::ProtocolLabel, MeSkip: Role, MeFilter: Role> IsEpEndVariant<IO, MeFilter> for EpSkip<IO, Lbl, MeSkip> {
     type Output = types::False;
 }
@@ -213,15 +1148,3 @@ pub type IsSkip<T, IO, Me> = <T as IsEpSkipVariant<IO, Me>>::Output;
 
 /// IsEnd: True if T is EpEnd<IO, Me>, else False.
 pub type IsEnd<T, IO, Me> = <T as IsEpEndVariant<IO, Me>>::Output;
-
-/// Public facade trait that routes to the implementation trait
-pub trait GetEpSkipTypeMarker<IO, Me: Role> {
-    type TypeMarker;
-}
-
-impl<IO, Me: Role, T> GetEpSkipTypeMarker<IO, Me> for T
-where
-    T: IsEpSkipTypeImpl<IO, Me>,
-{
-    type TypeMarker = <T as IsEpSkipTypeImpl<IO, Me>>::TypeMarker;
-}
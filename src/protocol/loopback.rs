@@ -0,0 +1,280 @@
+//! # Loopback Checks
+//!
+//! This module checks that no role of a global protocol interacts with
+//! itself: every message, delegation and connection joins two different
+//! roles, and no role broadcasts or anycasts to a set it belongs to.
+//!
+//! Key components:
+//!
+//! - `NoSelfMessaging`: No step of a protocol loops back to the role taking it
+//! - `DistinctRoles` / `OutsideRoles`: A role differs from another role, or from every role of a set
+//! - `SelfMessageCase`: Helper trait dispatching on whether a step loops back
+//! - `SelfMessagingFailure`: Never-implemented trait reporting the `SelfMessage` found
+//!
+//! Projection already rejects a `TMsg` from a role to itself, but only when
+//! that role is projected. `NoSelfMessaging` checks the whole protocol at
+//! once, and can be used as a bound wherever a protocol is accepted.
+
+use super::base::{Cons, Nil};
+use super::global::*;
+use super::local::{InRoleSet, RoleEq};
+use super::transforms::SelfMessage;
+use crate::types;
+
+/// Never implemented: a bound `SelfMessage<R, Lbl>: SelfMessagingFailure`
+/// fails and names the step found by [`NoSelfMessaging`].
+#[diagnostic::on_unimplemented(
+    message = "a role interacts with itself: `{Self}`",
+    label = "this step loops back to the role taking it",
+    note = "address the message, delegation or connection to another role"
+)]
+pub trait SelfMessagingFailure {}
+
+/// Helper trait for `DistinctRoles` and `OutsideRoles`, dispatching on
+/// whether the step labelled `Lbl` loops back to the role taking it.
+pub trait SelfMessageCase<Lbl, IsSelf> {}
+
+impl<R, Lbl> SelfMessageCase<Lbl, types::False> for R {}
+
+impl<R, Lbl> SelfMessageCase<Lbl, types::True> for R where SelfMessage<R, Lbl>: SelfMessagingFailure {}
+
+/// The role `Self` is not `Other`, so the step labelled `Lbl` between them
+/// joins two roles.
+pub trait DistinctRoles<Other, Lbl> {}
+
+impl<R, Other, Lbl> DistinctRoles<Other, Lbl> for R
+where
+    R: RoleEq<Other>,
+    R: SelfMessageCase<Lbl, <R as RoleEq<Other>>::Output>,
+{
+}
+
+/// The role `Self` is not in the role list `Set` it sends to in the step
+/// labelled `Lbl`.
+pub trait OutsideRoles<Set, Lbl> {}
+
+impl<R, Set, Lbl> OutsideRoles<Set, Lbl> for R
+where
+    R: InRoleSet<Set>,
+    R: SelfMessageCase<Lbl, <R as InRoleSet<Set>>::Output>,
+{
+}
+
+/// A global protocol in which no role sends to, delegates to or connects
+/// with itself.
+///
+/// Use it as a bound wherever a protocol is accepted, or check a protocol
+/// once with a function bound.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Alice; struct Bob; struct Ping;
+/// impl Role for Alice {} impl Role for Bob {}
+/// impl ProtocolLabel for Ping {}
+/// impl RoleEq<Alice> for Alice { type Output = True; }
+/// impl RoleEq<Bob> for Alice   { type Output = False; }
+/// impl RoleEq<Alice> for Bob   { type Output = False; }
+/// impl RoleEq<Bob> for Bob     { type Output = True; }
+///
+/// fn run<G: NoSelfMessaging>() {}
+/// run::<TMsg<Http, Ping, Alice, Bob, Message, TEnd<Http>>>();
+/// ```
+pub trait NoSelfMessaging {}
+
+impl<IO, Lbl> NoSelfMessaging for TEnd<IO, Lbl> {}
+
+impl<IO, Lbl: types::ProtocolLabel, R, H, T> NoSelfMessaging for TInteract<IO, Lbl, R, H, T> where
+    T: TSession<IO> + NoSelfMessaging
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, To, H, T> NoSelfMessaging
+    for TMsg<IO, Lbl, From, To, H, T>
+where
+    From: DistinctRoles<To, Lbl>,
+    T: TSession<IO> + NoSelfMessaging,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, ToSet, H, T> NoSelfMessaging
+    for TBroadcast<IO, Lbl, From, ToSet, H, T>
+where
+    From: OutsideRoles<ToSet, Lbl>,
+    T: TSession<IO> + NoSelfMessaging,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, WorkerSet, H, T> NoSelfMessaging
+    for TAnycast<IO, Lbl, From, WorkerSet, H, T>
+where
+    From: OutsideRoles<WorkerSet, Lbl>,
+    T: TSession<IO> + NoSelfMessaging,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, To, DelegatedEp, T> NoSelfMessaging
+    for TDelegate<IO, Lbl, From, To, DelegatedEp, T>
+where
+    From: DistinctRoles<To, Lbl>,
+    T: TSession<IO> + NoSelfMessaging,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, A, B, Cont> NoSelfMessaging for TDisconnect<IO, Lbl, A, B, Cont>
+where
+    A: DistinctRoles<B, Lbl>,
+    Cont: TSession<IO> + NoSelfMessaging,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, A, B, Cont> NoSelfMessaging for TConnect<IO, Lbl, A, B, Cont>
+where
+    A: DistinctRoles<B, Lbl>,
+    Cont: TSession<IO> + NoSelfMessaging,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R, Cont> NoSelfMessaging for TEndFor<IO, Lbl, R, Cont> where
+    Cont: TSession<IO> + NoSelfMessaging
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, L, R> NoSelfMessaging for TChoice<IO, Lbl, L, R>
+where
+    L: TSession<IO> + NoSelfMessaging,
+    R: TSession<IO> + NoSelfMessaging,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Branches> NoSelfMessaging for TChoiceN<IO, Lbl, Branches> where
+    Branches: SessionList<IO> + NoSelfMessaging
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Chooser, Branches> NoSelfMessaging
+    for TSelect<IO, Lbl, Chooser, Branches>
+where
+    Branches: SessionList<IO> + NoSelfMessaging,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Offeree, Branches> NoSelfMessaging
+    for TOffer<IO, Lbl, Offeree, Branches>
+where
+    Branches: SessionList<IO> + NoSelfMessaging,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, High, Low> NoSelfMessaging
+    for TPriorityChoice<IO, Lbl, High, Low>
+where
+    High: TSession<IO> + NoSelfMessaging,
+    Low: TSession<IO> + NoSelfMessaging,
+{
+}
+
+impl<IO, Lbl, WeightL, L, WeightR, R> NoSelfMessaging for TProb<IO, Lbl, WeightL, L, WeightR, R>
+where
+    Lbl: types::ProtocolLabel,
+    WeightL: types::WeightMarker,
+    WeightR: types::WeightMarker,
+    L: TSession<IO> + NoSelfMessaging,
+    R: TSession<IO> + NoSelfMessaging,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, L, R, IsDisjoint> NoSelfMessaging
+    for TPar<IO, Lbl, L, R, IsDisjoint>
+where
+    L: TSession<IO> + NoSelfMessaging,
+    R: TSession<IO> + NoSelfMessaging,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Branches> NoSelfMessaging for TParN<IO, Lbl, Branches> where
+    Branches: SessionList<IO> + NoSelfMessaging
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Steps> NoSelfMessaging for TShuffle<IO, Lbl, Steps> where
+    Steps: SessionList<IO> + NoSelfMessaging
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, S> NoSelfMessaging for TRec<IO, Lbl, S> where
+    S: TSession<IO> + NoSelfMessaging
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Var, Body> NoSelfMessaging for TRecX<IO, Lbl, Var, Body> where
+    Body: TSession<IO> + NoSelfMessaging
+{
+}
+
+impl<Var> NoSelfMessaging for TVar<Var> {}
+
+impl<IO, Lbl, Dur, Body, OnTimeout> NoSelfMessaging for TTimeout<IO, Lbl, Dur, Body, OnTimeout>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    Body: TSession<IO> + NoSelfMessaging,
+    OnTimeout: TSession<IO> + NoSelfMessaging,
+{
+}
+
+impl<IO, Lbl, R, Dur, T> NoSelfMessaging for TDelay<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: TSession<IO> + NoSelfMessaging,
+{
+}
+
+impl<IO, Lbl, R, Dur, T> NoSelfMessaging for TDeadline<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: TSession<IO> + NoSelfMessaging,
+{
+}
+
+impl<IO, Lbl, Scope, Interruptor, Handler> NoSelfMessaging
+    for TInterrupt<IO, Lbl, Scope, Interruptor, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Scope: TSession<IO> + NoSelfMessaging,
+    Handler: TSession<IO> + NoSelfMessaging,
+{
+}
+
+impl<IO, Lbl, Decider, Body, Cont> NoSelfMessaging for TOptional<IO, Lbl, Decider, Body, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Body: TSession<IO> + NoSelfMessaging,
+    Cont: TSession<IO> + NoSelfMessaging,
+{
+}
+
+impl<Pred, Inner: NoSelfMessaging> NoSelfMessaging for TRefine<Pred, Inner> {}
+
+impl<Meta, Inner: NoSelfMessaging> NoSelfMessaging for TAnnotate<Meta, Inner> {}
+
+impl<IO, Lbl: types::ProtocolLabel, R, Err> NoSelfMessaging for TThrow<IO, Lbl, R, Err> {}
+
+impl<IO, Lbl, Body, Handler> NoSelfMessaging for TCatch<IO, Lbl, Body, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Body: TSession<IO> + NoSelfMessaging,
+    Handler: TSession<IO> + NoSelfMessaging,
+{
+}
+
+impl NoSelfMessaging for Nil {}
+
+impl<H, T> NoSelfMessaging for Cons<H, T>
+where
+    H: NoSelfMessaging,
+    T: NoSelfMessaging,
+{
+}
@@ -0,0 +1,477 @@
+//! # Merging
+//!
+//! This module implements full merging of local session types, the operation
+//! multiparty projection uses for a role that does not decide a choice: the
+//! role's projections of every branch are merged into a single local type.
+//!
+//! Key components:
+//!
+//! - `Merge`: The merge of two local session types
+//! - `MergeEach`: Pointwise merge of two type-level lists
+//! - `MergeRecvCase`: Helper trait merging two receives on whether their labels match
+//! - `InsertBranch`: Adds a branch to an offer, merging it with a branch of the same label
+//! - `InsertBranches`: Adds every branch of a list to an offer
+//! - `WithChoiceLabel`: Gives a merged offer the label of the choice it tells apart
+//!
+//! Receives and offers are external choices and merge by label: branches with
+//! distinct labels are collected into one `EpOffer`, branches with the same
+//! label must have the same payload and are merged recursively. Every other
+//! construct only merges with the same construct, with its continuations and
+//! branches merged pointwise; in particular two different sends or selections
+//! cannot be merged, because a role that does not own a choice cannot act
+//! differently on its outcome before learning it.
+//!
+//! Labels are compared with `LabelEq`. An offer formed by merging two receives
+//! takes the label of the left one; projection then relabels an offer at the
+//! top of a merged choice with the label of the choice, the label the
+//! deciding role selects under.
+
+use super::base::{Cons, Nil};
+use super::local::*;
+use super::normalize::{
+    ChoiceNShape, OfferShape, ParShape, SelectShape, ShapeOf, SkipShape, StepShape,
+};
+use super::transforms::GetLocalLabel;
+use crate::types;
+
+/// The merge of two local session types.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Get; impl ProtocolLabel for Get {}
+/// struct Put; impl ProtocolLabel for Put {}
+/// impl LabelEq<Get> for Get { type Output = True; }
+/// impl LabelEq<Put> for Get { type Output = False; }
+/// impl LabelEq<Get> for Put { type Output = False; }
+/// impl LabelEq<Put> for Put { type Output = True; }
+///
+/// type OnGet = EpRecv<Http, Get, TServer, Message, EpEnd<Http, EmptyLabel, TServer>>;
+/// type OnPut = EpRecv<Http, Put, TServer, Message, EpEnd<Http, EmptyLabel, TServer>>;
+/// assert_type_eq!(
+///     <OnGet as Merge<OnPut>>::Output,
+///     EpOffer<Http, Get, TServer, tlist!(OnGet, OnPut)>
+/// );
+/// assert_type_eq!(<OnGet as Merge<OnGet>>::Output, OnGet);
+/// ```
+#[diagnostic::on_unimplemented(
+    message = "the local types `{Self}` and `{Rhs}` cannot be merged",
+    label = "branches of a choice disagree for a role that does not own it",
+    note = "only receives and offers may differ between branches; everything else must match"
+)]
+pub trait Merge<Rhs> {
+    type Output;
+}
+
+/// Pointwise merge of two type-level lists of the same length.
+pub trait MergeEach<Rhs> {
+    type Output;
+}
+
+impl MergeEach<Nil> for Nil {
+    type Output = Nil;
+}
+
+impl<H: Merge<RH>, T: MergeEach<RT>, RH, RT> MergeEach<Cons<RH, RT>> for Cons<H, T> {
+    type Output = Cons<<H as Merge<RH>>::Output, <T as MergeEach<RT>>::Output>;
+}
+
+/// Adds branch `X` to a list of offer branches.
+///
+/// `X` is merged with the first branch carrying the same label, or appended
+/// if there is none.
+pub trait InsertBranch<X> {
+    type Output;
+}
+
+/// Helper trait for dispatching `InsertBranch` on whether the head matches.
+pub trait InsertBranchCase<IsMatch, X> {
+    type Output;
+}
+
+impl<X> InsertBranch<X> for Nil {
+    type Output = Cons<X, Nil>;
+}
+
+impl<H, T, X> InsertBranch<X> for Cons<H, T>
+where
+    H: GetLocalLabel,
+    X: GetLocalLabel,
+    <H as GetLocalLabel>::Label: LabelEq<<X as GetLocalLabel>::Label>,
+    Cons<H, T>: InsertBranchCase<
+        <<H as GetLocalLabel>::Label as LabelEq<<X as GetLocalLabel>::Label>>::Output,
+        X,
+    >,
+{
+    type Output = <Cons<H, T> as InsertBranchCase<
+        <<H as GetLocalLabel>::Label as LabelEq<<X as GetLocalLabel>::Label>>::Output,
+        X,
+    >>::Output;
+}
+
+impl<H: Merge<X>, T, X> InsertBranchCase<types::True, X> for Cons<H, T> {
+    type Output = Cons<<H as Merge<X>>::Output, T>;
+}
+
+impl<H, T: InsertBranch<X>, X> InsertBranchCase<types::False, X> for Cons<H, T> {
+    type Output = Cons<H, <T as InsertBranch<X>>::Output>;
+}
+
+/// Adds every branch of the list `Xs` to a list of offer branches.
+pub trait InsertBranches<Xs> {
+    type Output;
+}
+
+impl<Branches> InsertBranches<Nil> for Branches {
+    type Output = Branches;
+}
+
+impl<Branches, X, Xs> InsertBranches<Cons<X, Xs>> for Branches
+where
+    Branches: InsertBranch<X>,
+    <Branches as InsertBranch<X>>::Output: InsertBranches<Xs>,
+{
+    type Output = <<Branches as InsertBranch<X>>::Output as InsertBranches<Xs>>::Output;
+}
+
+/// Helper trait for merging two receives on whether their labels match.
+///
+/// - `SameLabel`: Type-level boolean, do both receives carry the same label?
+/// - `Rhs`: The other receive.
+///
+/// Matching receives must carry the same payload and merge their
+/// continuations; distinct ones become the two branches of an `EpOffer`.
+pub trait MergeRecvCase<SameLabel, Rhs> {
+    type Output;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, H, T: Merge<RT>, RLbl: types::ProtocolLabel, RT>
+    MergeRecvCase<types::True, EpRecv<IO, RLbl, Me, H, RT>> for EpRecv<IO, Lbl, Me, H, T>
+{
+    type Output = EpRecv<IO, Lbl, Me, H, <T as Merge<RT>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, H, T, Rhs> MergeRecvCase<types::False, Rhs>
+    for EpRecv<IO, Lbl, Me, H, T>
+{
+    type Output = EpOffer<IO, Lbl, Me, Cons<EpRecv<IO, Lbl, Me, H, T>, Cons<Rhs, Nil>>>;
+}
+
+// External choices
+
+impl<IO, Lbl, Me, H, T, RLbl, RH, RT> Merge<EpRecv<IO, RLbl, Me, RH, RT>>
+    for EpRecv<IO, Lbl, Me, H, T>
+where
+    Lbl: types::ProtocolLabel + LabelEq<RLbl>,
+    RLbl: types::ProtocolLabel,
+    Self: MergeRecvCase<<Lbl as LabelEq<RLbl>>::Output, EpRecv<IO, RLbl, Me, RH, RT>>,
+{
+    type Output = <Self as MergeRecvCase<
+        <Lbl as LabelEq<RLbl>>::Output,
+        EpRecv<IO, RLbl, Me, RH, RT>,
+    >>::Output;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches, RLbl: types::ProtocolLabel, RBranches>
+    Merge<EpOffer<IO, RLbl, Me, RBranches>> for EpOffer<IO, Lbl, Me, Branches>
+where
+    Branches: InsertBranches<RBranches>,
+{
+    type Output = EpOffer<IO, Lbl, Me, <Branches as InsertBranches<RBranches>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches, RLbl: types::ProtocolLabel, RH, RT>
+    Merge<EpRecv<IO, RLbl, Me, RH, RT>> for EpOffer<IO, Lbl, Me, Branches>
+where
+    Branches: InsertBranch<EpRecv<IO, RLbl, Me, RH, RT>>,
+{
+    type Output =
+        EpOffer<IO, Lbl, Me, <Branches as InsertBranch<EpRecv<IO, RLbl, Me, RH, RT>>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, H, T, RLbl: types::ProtocolLabel, RBranches>
+    Merge<EpOffer<IO, RLbl, Me, RBranches>> for EpRecv<IO, Lbl, Me, H, T>
+where
+    Cons<EpRecv<IO, Lbl, Me, H, T>, Nil>: InsertBranches<RBranches>,
+{
+    type Output = EpOffer<
+        IO,
+        RLbl,
+        Me,
+        <Cons<EpRecv<IO, Lbl, Me, H, T>, Nil> as InsertBranches<RBranches>>::Output,
+    >;
+}
+
+// Leaves: labels of terminal steps are only for traceability, keep the left one
+
+impl<IO, Lbl: types::ProtocolLabel, R, RLbl: types::ProtocolLabel> Merge<EpEnd<IO, RLbl, R>>
+    for EpEnd<IO, Lbl, R>
+{
+    type Output = EpEnd<IO, Lbl, R>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R, RLbl: types::ProtocolLabel> Merge<EpSkip<IO, RLbl, R>>
+    for EpSkip<IO, Lbl, R>
+{
+    type Output = EpSkip<IO, Lbl, R>;
+}
+
+impl<Var> Merge<EpVar<Var>> for EpVar<Var> {
+    type Output = EpVar<Var>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Err> Merge<EpThrow<IO, Lbl, Me, Err>>
+    for EpThrow<IO, Lbl, Me, Err>
+{
+    type Output = EpThrow<IO, Lbl, Me, Err>;
+}
+
+// Internal actions: identical up to their continuations
+
+impl<IO, Lbl: types::ProtocolLabel, R, H, T: Merge<RT>, RT> Merge<EpSend<IO, Lbl, R, H, RT>>
+    for EpSend<IO, Lbl, R, H, T>
+{
+    type Output = EpSend<IO, Lbl, R, H, <T as Merge<RT>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, ToSet, H, T: Merge<RT>, RT>
+    Merge<EpBroadcast<IO, Lbl, Me, ToSet, H, RT>> for EpBroadcast<IO, Lbl, Me, ToSet, H, T>
+{
+    type Output = EpBroadcast<IO, Lbl, Me, ToSet, H, <T as Merge<RT>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, FromSet, H, T: Merge<RT>, RT>
+    Merge<EpGather<IO, Lbl, Me, FromSet, H, RT>> for EpGather<IO, Lbl, Me, FromSet, H, T>
+{
+    type Output = EpGather<IO, Lbl, Me, FromSet, H, <T as Merge<RT>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, DelegatedEp, Cont: Merge<RCont>, RCont>
+    Merge<EpDelegate<IO, Lbl, Me, DelegatedEp, RCont>>
+    for EpDelegate<IO, Lbl, Me, DelegatedEp, Cont>
+{
+    type Output = EpDelegate<IO, Lbl, Me, DelegatedEp, <Cont as Merge<RCont>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches: MergeEach<RBranches>, RBranches>
+    Merge<EpSelect<IO, Lbl, Me, RBranches>> for EpSelect<IO, Lbl, Me, Branches>
+{
+    type Output = EpSelect<IO, Lbl, Me, <Branches as MergeEach<RBranches>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches: MergeEach<RBranches>, RBranches>
+    Merge<EpChoiceN<IO, Lbl, Me, RBranches>> for EpChoiceN<IO, Lbl, Me, Branches>
+{
+    type Output = EpChoiceN<IO, Lbl, Me, <Branches as MergeEach<RBranches>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Body: Merge<RBody>, Cont: Merge<RCont>, RBody, RCont>
+    Merge<EpOptionalSelect<IO, Lbl, Me, RBody, RCont>>
+    for EpOptionalSelect<IO, Lbl, Me, Body, Cont>
+{
+    type Output = EpOptionalSelect<
+        IO,
+        Lbl,
+        Me,
+        <Body as Merge<RBody>>::Output,
+        <Cont as Merge<RCont>>::Output,
+    >;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Body: Merge<RBody>, Cont: Merge<RCont>, RBody, RCont>
+    Merge<EpOptionalOffer<IO, Lbl, Me, RBody, RCont>> for EpOptionalOffer<IO, Lbl, Me, Body, Cont>
+{
+    type Output = EpOptionalOffer<
+        IO,
+        Lbl,
+        Me,
+        <Body as Merge<RBody>>::Output,
+        <Cont as Merge<RCont>>::Output,
+    >;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, High: Merge<RHigh>, Low: Merge<RLow>, RHigh, RLow>
+    Merge<EpPriorityOffer<IO, Lbl, Me, RHigh, RLow>> for EpPriorityOffer<IO, Lbl, Me, High, Low>
+{
+    type Output =
+        EpPriorityOffer<IO, Lbl, Me, <High as Merge<RHigh>>::Output, <Low as Merge<RLow>>::Output>;
+}
+
+// Concurrency and recursion: pointwise
+
+impl<IO, Lbl: types::ProtocolLabel, Me, L: Merge<RL>, R: Merge<RR>, RL, RR>
+    Merge<EpPar<IO, Lbl, Me, RL, RR>> for EpPar<IO, Lbl, Me, L, R>
+{
+    type Output = EpPar<IO, Lbl, Me, <L as Merge<RL>>::Output, <R as Merge<RR>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches: MergeEach<RBranches>, RBranches>
+    Merge<EpParN<IO, Lbl, Me, RBranches>> for EpParN<IO, Lbl, Me, Branches>
+{
+    type Output = EpParN<IO, Lbl, Me, <Branches as MergeEach<RBranches>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Steps: MergeEach<RSteps>, RSteps>
+    Merge<EpShuffle<IO, Lbl, Me, RSteps>> for EpShuffle<IO, Lbl, Me, Steps>
+{
+    type Output = EpShuffle<IO, Lbl, Me, <Steps as MergeEach<RSteps>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Var, Body: Merge<RBody>, RBody>
+    Merge<EpRec<IO, Lbl, Var, RBody>> for EpRec<IO, Lbl, Var, Body>
+{
+    type Output = EpRec<IO, Lbl, Var, <Body as Merge<RBody>>::Output>;
+}
+
+// Timing, interrupts, failures and refinements: pointwise
+
+impl<IO, Lbl, Me, Dur, Body, OnTimeout, RBody, ROnTimeout>
+    Merge<EpTimeout<IO, Lbl, Me, Dur, RBody, ROnTimeout>>
+    for EpTimeout<IO, Lbl, Me, Dur, Body, OnTimeout>
+where
+    Lbl: types::ProtocolLabel,
+    Body: Merge<RBody>,
+    OnTimeout: Merge<ROnTimeout>,
+{
+    type Output = EpTimeout<
+        IO,
+        Lbl,
+        Me,
+        Dur,
+        <Body as Merge<RBody>>::Output,
+        <OnTimeout as Merge<ROnTimeout>>::Output,
+    >;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Dur, T: Merge<RT>, RT> Merge<EpDelay<IO, Lbl, Me, Dur, RT>>
+    for EpDelay<IO, Lbl, Me, Dur, T>
+{
+    type Output = EpDelay<IO, Lbl, Me, Dur, <T as Merge<RT>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Dur, T: Merge<RT>, RT>
+    Merge<EpDeadline<IO, Lbl, Me, Dur, RT>> for EpDeadline<IO, Lbl, Me, Dur, T>
+{
+    type Output = EpDeadline<IO, Lbl, Me, Dur, <T as Merge<RT>>::Output>;
+}
+
+impl<IO, Lbl, Me, Scope, Handler, RScope, RHandler>
+    Merge<EpInterrupt<IO, Lbl, Me, RScope, RHandler>> for EpInterrupt<IO, Lbl, Me, Scope, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Scope: Merge<RScope>,
+    Handler: Merge<RHandler>,
+{
+    type Output = EpInterrupt<
+        IO,
+        Lbl,
+        Me,
+        <Scope as Merge<RScope>>::Output,
+        <Handler as Merge<RHandler>>::Output,
+    >;
+}
+
+impl<IO, Lbl, Me, Scope, Handler, RScope, RHandler>
+    Merge<EpInterruptible<IO, Lbl, Me, RScope, RHandler>>
+    for EpInterruptible<IO, Lbl, Me, Scope, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Scope: Merge<RScope>,
+    Handler: Merge<RHandler>,
+{
+    type Output = EpInterruptible<
+        IO,
+        Lbl,
+        Me,
+        <Scope as Merge<RScope>>::Output,
+        <Handler as Merge<RHandler>>::Output,
+    >;
+}
+
+impl<IO, Lbl, Me, Body, Handler, RBody, RHandler> Merge<EpCatch<IO, Lbl, Me, RBody, RHandler>>
+    for EpCatch<IO, Lbl, Me, Body, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Body: Merge<RBody>,
+    Handler: Merge<RHandler>,
+{
+    type Output =
+        EpCatch<IO, Lbl, Me, <Body as Merge<RBody>>::Output, <Handler as Merge<RHandler>>::Output>;
+}
+
+impl<Pred, Inner: Merge<RInner>, RInner> Merge<EpRefine<Pred, RInner>> for EpRefine<Pred, Inner> {
+    type Output = EpRefine<Pred, <Inner as Merge<RInner>>::Output>;
+}
+
+// Connections: pointwise
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Peer, Cont: Merge<RCont>, RCont>
+    Merge<EpClose<IO, Lbl, Me, Peer, RCont>> for EpClose<IO, Lbl, Me, Peer, Cont>
+{
+    type Output = EpClose<IO, Lbl, Me, Peer, <Cont as Merge<RCont>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Peer, Cont: Merge<RCont>, RCont>
+    Merge<EpOpen<IO, Lbl, Me, Peer, RCont>> for EpOpen<IO, Lbl, Me, Peer, Cont>
+{
+    type Output = EpOpen<IO, Lbl, Me, Peer, <Cont as Merge<RCont>>::Output>;
+}
+
+/// The merged branches `Self` of the choice labelled `Lbl`, with an `EpOffer`
+/// at the top relabelled to `Lbl`.
+///
+/// Every other local type is left unchanged.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Pick; impl ProtocolLabel for Pick {}
+/// struct Get; impl ProtocolLabel for Get {}
+/// type OnGet = EpRecv<Http, Get, TServer, Message, EpEnd<Http, EmptyLabel, TServer>>;
+/// assert_type_eq!(
+///     <EpOffer<Http, Get, TServer, tlist!(OnGet)> as WithChoiceLabel<Pick>>::Output,
+///     EpOffer<Http, Pick, TServer, tlist!(OnGet)>
+/// );
+/// assert_type_eq!(<OnGet as WithChoiceLabel<Pick>>::Output, OnGet);
+/// ```
+pub trait WithChoiceLabel<Lbl> {
+    type Output;
+}
+
+/// Helper trait for dispatching `WithChoiceLabel` on the `ShapeOf` a local type.
+pub trait WithChoiceLabelCase<Lbl, Shape> {
+    type Output;
+}
+
+impl<Lbl, T: ShapeOf> WithChoiceLabel<Lbl> for T
+where
+    T: WithChoiceLabelCase<Lbl, <T as ShapeOf>::Shape>,
+{
+    type Output = <T as WithChoiceLabelCase<Lbl, <T as ShapeOf>::Shape>>::Output;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches, OLbl: types::ProtocolLabel>
+    WithChoiceLabelCase<Lbl, OfferShape> for EpOffer<IO, OLbl, Me, Branches>
+{
+    type Output = EpOffer<IO, Lbl, Me, Branches>;
+}
+
+impl<Lbl, T> WithChoiceLabelCase<Lbl, SkipShape> for T {
+    type Output = T;
+}
+
+impl<Lbl, T> WithChoiceLabelCase<Lbl, ParShape> for T {
+    type Output = T;
+}
+
+impl<Lbl, T> WithChoiceLabelCase<Lbl, SelectShape> for T {
+    type Output = T;
+}
+
+impl<Lbl, T> WithChoiceLabelCase<Lbl, ChoiceNShape> for T {
+    type Output = T;
+}
+
+impl<Lbl, T> WithChoiceLabelCase<Lbl, StepShape> for T {
+    type Output = T;
+}
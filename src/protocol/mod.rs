@@ -5,11 +5,42 @@
 //!
 //! ## Module Structure
 //!
+//! - `awareness`: Checks that every role learns the outcome of the choices it depends on
 //! - `base`: Foundational types and traits for type-level programming
 //! - `global`: Global protocol types representing multi-party choreography
 //! - `local`: Local protocol types representing endpoint behavior
 //! - `transforms`: Projection and other transformations between protocol representations
 //! - `utils`: Utility traits for protocol manipulation and checking
+//! - `schema`: Payload schemas per label and schema evolution checks
+//! - `recursion`: Recursion variables with guardedness and scoping checks
+//! - `failure`: Checks that every thrown failure is caught
+//! - `connection`: Checks that role pairs interact only while connected
+//! - `connectedness`: Checks that every step is taken by a role that took part in the steps before it
+//! - `deadlock`: Checks that concurrent branches cannot wait on each other
+//! - `describe`: Runtime description of a global protocol as a tree of values
+//! - `determinism`: Checks that the branches of every offer start with distinct labels
+//! - `separability`: Checks that parallel branches send distinct message types
+//! - `shuffle`: Checks that the steps of a shuffle are pairwise independent
+//! - `duality`: Dual local session types for two-party sessions
+//! - `equivalence`: Structural equality of global protocols up to their labels
+//! - `evolution`: Session subtyping between versions of a global protocol
+//! - `linearity`: Checks that parallel branches never share a channel
+//! - `loopback`: Checks that no role sends to, delegates to or connects with itself
+//! - `merge`: Full merging of local session types for projection
+//! - `fingerprint`: Hashes of protocol structure and names for version checks
+//! - `footprint`: Compile-time payload sizes for statically sized buffers
+//! - `fsm`: State-machine view of local session types
+//! - `normalize`: Normal forms of local session types
+//! - `orphan`: Checks that no message sent before a choice is known goes unreceived
+//! - `progress`: Checks that no reachable state of a protocol is stuck
+//! - `simplify`: Flattening and nesting of endpoint choices
+//! - `slice`: Sub-protocols extracted or replaced by the label of their root
+//! - `subtyping`: Session subtyping between local session types, synchronous or asynchronous
+//! - `synthesize`: Global protocols reconstructed from per-role local types
+//! - `termination`: Checks that every loop of a protocol has an exit branch
+//! - `trace`: Checks that a global protocol can produce a given trace of steps
+//! - `validity`: Well-formedness checks for local session types
+//! - `well_formed`: A single well-formedness check bundling the checks on global protocols
 //!
 //! ## Key Concepts
 //!
@@ -19,33 +50,187 @@
 //! - **Type-Level Operations**: Compile-time reasoning about protocol properties
 
 // Re-export everything from the submodules
+pub mod awareness;
 pub mod base;
+pub mod connectedness;
+pub mod connection;
+pub mod deadlock;
+pub mod describe;
+pub mod determinism;
+pub mod duality;
+pub mod equivalence;
+pub mod evolution;
+pub mod failure;
+pub mod fingerprint;
+pub mod footprint;
+pub mod fsm;
 pub mod global;
+pub mod linearity;
 pub mod local;
+pub mod loopback;
+pub mod merge;
+pub mod normalize;
+pub mod orphan;
+pub mod progress;
+pub mod recursion;
+pub mod schema;
+pub mod separability;
+pub mod shuffle;
+pub mod simplify;
+pub mod slice;
+pub mod subtyping;
+pub mod synthesize;
+pub mod termination;
+pub mod trace;
 pub mod transforms;
 pub mod utils;
-
-// Additional test-specific helpers
-#[cfg(test)]
-pub mod test_helpers;
-#[cfg(test)]
-pub mod test_overrides;
+pub mod validity;
+pub mod well_formed;
 
 // Re-export commonly used items at the protocol module level
-pub use self::base::{Cons, Nil, NotInList, NotSame, NotTypeEq, UniqueList};
+pub use self::awareness::{
+    AgreeOnChoice, AgreeOnChoiceCase, AgreeOnSelect, AgreeOnSelectCase, ChoiceAware,
+    ChoiceAwarenessFailure, MergeAll, NotStartedByChooser, SplitChoice, StartedBy, StartedByCase,
+};
+pub use self::base::{Cons, Nil};
+pub use self::connectedness::{
+    Advance, AnyRole, AsyncConnected, AsyncOrder, Connected, ConnectedAfter, ConnectednessFailure,
+    MayAct, MayActCase, StartsAfter, SyncOrder, Unconnected,
+};
+pub use self::connection::{
+    ConnectFree, ConnectFreeUnless, ConnectionsRespected, LinksPair, PairUnused, PairUnusedUnless,
+};
+pub use self::deadlock::{
+    ByChannel, Channel, ChannelIn, ChannelsOf, ChannelsOfEach, CircularWait,
+    ConcurrentlyDeadlockFree, DeadlockFree, DeadlockHazard, FanOut, NoneReversedIn,
+    NoneReversedInCase,
+};
+pub use self::describe::{
+    ChannelDesc, CombinatorKind, ConstLabelName, ConstRoleName, ConstRoleNames, Describe,
+    DescribeEach, ProtocolDesc, ProtocolDisplay, RoleNames, RoleNamesOf, RoleNamesSlice, StepDesc,
+    Steps,
+};
+pub use self::determinism::{
+    AmbiguousOffer, DeterminismFailure, Deterministic, DistinctFirstLabels, NoneStartsWith,
+    NoneStartsWithCase,
+};
+pub use self::duality::{Dual, DualEach, DualityHolds, MatchesDual};
+pub use self::equivalence::{
+    CompareEnds, EndLabel, EquivalentTo, EraseLabels, EraseLabelsEach, IgnoreEnds, SameStructure,
+};
+pub use self::evolution::{
+    BranchesCover, BranchesWithin, EachGlobalSubtype, EvolvedPayloads, FindProtocolBranch,
+    FindProtocolBranchCase, GlobalSubtype, PayloadRule, SamePayloads,
+};
+pub use self::failure::CatchScoped;
+pub use self::fingerprint::{
+    fingerprint_mix, FingerprintOf, FingerprintOfEach, FingerprintOfRoles,
+};
+pub use self::footprint::{
+    MaxPayloadSize, MaxPayloadSizeEach, PayloadBufferOf, PayloadBufferOfEach, SizeCase, SizeOf,
+};
+pub use self::fsm::{
+    Direction, DirectionMarker, Fork, ForkEach, FsmTransition, In, Internal, Out, ToFsm, Transition,
+    TransitionData, TransitionsFrom, TransitionsFromEach,
+};
 pub use self::global::{
-    AssertDisjoint, TChoice, TEnd, TInteract, TPar, TRec, TSession, ToTChoice, ToTPar,
+    AssertDisjoint, SessionList, TAnnotate, TAnycast, TBroadcast, TCatch, TChoice, TChoiceN,
+    TConnect, TDeadline, TDelay, TDelegate, TDisconnect, TEnd, TEndFor, TInteract, TInterrupt, TMsg,
+    TOffer, TOptional, TPar, TParN, TPriorityChoice, TProb, TRec, TRecX, TRefine, TSelect, TSession,
+    TShuffle, TThrow, TTimeout, TVar, ToTChoice, ToTPar,
+};
+pub use self::linearity::{
+    BranchesLinear, InterleavingSafe, Linear, LinearityViolation, NoneSharedWith, NoneSharedWithCase,
+    SharedChannel,
 };
 pub use self::local::{
-    EpChoice, EpEnd, EpPar, EpRecv, EpSend, EpSession, EpSkip, GetEpSkipTypeMarker, IsEnd,
-    IsEpEndVariant, IsEpSkipTypeImpl, IsEpSkipVariant, IsSkip, Role, RoleEq, TBroker, TClient,
+    ComposeEachEp, ComposeEp, DelegationOf, EpBroadcast, EpCatch, EpChoiceN, EpClose, EpDeadline,
+    EpDelay, EpDelegate, EpEnd, EpGather, EpInterrupt, EpInterruptible, EpOffer, EpOpen,
+    EpOptionalOffer, EpOptionalSelect, EpPar, EpParN, EpPriorityOffer, EpRec, EpRecv, EpRefine,
+    EpSelect, EpSend, EpSeq, EpSession, EpShuffle, EpSkip, EpThrow, EpTimeout, EpVar, InRoleSet,
+    IsEnd, IsEpEndVariant, IsEpSkipVariant, IsSkip, LabelEq, Role, RoleEq, TBroker, TClient,
     TServer, TWorker, Void,
 };
+pub use self::loopback::{
+    DistinctRoles, NoSelfMessaging, OutsideRoles, SelfMessageCase, SelfMessagingFailure,
+};
+pub use self::merge::{
+    InsertBranch, InsertBranchCase, InsertBranches, Merge, MergeEach, MergeRecvCase,
+    WithChoiceLabel, WithChoiceLabelCase,
+};
+pub use self::normalize::{
+    ChoiceNShape, Collapse, DropSkips, DropSkipsCase, FlattenPar, FlattenParCase, Normalize,
+    NormalizeEach, OfferShape, ParShape, Refines, SelectShape, ShapeOf, SkipShape, StepShape,
+};
+pub use self::orphan::{
+    BranchesSend, EarlySendCase, EarlySends, EarlySendsEach, HasSend, OrphanFailure, OrphanFree,
+    OrphanMessage, SendsCovered, SendsCoveredCase, SendsOf, SendsOfEach, Sent,
+};
+pub use self::progress::{
+    EmptyChoice, HasProgress, ProgressChoiceCase, ProgressEndForCase, ProgressFailure,
+    StuckAfterLeaving,
+};
+pub use self::recursion::{BindsVar, Guarded, VarEq, WellScoped};
+pub use self::schema::{AllOptional, EvolvesFrom, Optional, PayloadCompatible, Required, SchemaOf};
+pub use self::separability::{
+    BranchMessagesDisjoint, DisjointMessages, LacksMessage, LacksMessageCase, MessageEq,
+    MessagesDisjoint, SeparabilityFailure, SharedMessage,
+};
+pub use self::shuffle::{DistinctFrom, PairwiseIndependent};
+pub use self::simplify::{
+    Flatten, FlattenChoices, MapChoices, MapChoicesEach, Nest, NestChoices, RebuildChoice,
+    SpliceChoices, SpliceChoicesCase,
+};
+pub use self::slice::{
+    FindLabel, FindLabelCase, FindLabelEach, LabelNotFound, ReplaceAfter, ReplaceAfterEach,
+    ReplaceByLabel, ReplaceCase, ReplaceLabelled, ReplaceLabelledEach, SliceByLabel, SliceCase,
+    SliceFailure,
+};
+pub use self::subtyping::{
+    AnticipateSend, AsyncSubtype, Asynchronous, EachSubtypeOf, FindBranch, FindBranchCase,
+    OffersAtLeast, SelectsWithin, SubtypeOf, SubtypingFailure, Synchronous, UnmatchedBranch,
+};
+pub use self::synthesize::{
+    AllEnded, ChoiceShape, NoBranchLeft, NoBranchesLeft, ReplaceLocal, ReplaceLocalCase,
+    Synthesize, SynthesizeBranches, SynthesizeCandidate, SynthesizeChoiceCase, SynthesizeChoiceFrom,
+    SynthesizeFrom, SynthesizeSendCase, TakeBranch, TakeBranches, TakeReceive, TakeReceiveCase,
+    TakeReceiveLabelCase,
+};
+pub use self::termination::{
+    AllCanExit, AnyCanExit, CanExit, Diverges, LoopsForever, MayDiverge, Terminating,
+    TerminatingCase, TerminationFailure,
+};
+pub use self::trace::{
+    Admits, AdmitsEndCase, AdmitsFrom, AdmitsStepCase, AllNullable, AnyNullable, CatchEach,
+    ConsEach, Derive, DeriveAt, DeriveEach, InterruptEach, MatchCase, Nullable, ParLeftEach,
+    ParNEach, ParRightEach, PrependEach, ShuffleEach, Step, StepMatches, StepSentBy, TimeoutEach,
+    TraceFailure, UnexpectedStep, Unfinished, Unfold, UnfoldEach, UnfoldVarCase,
+};
 pub use self::transforms::{
-    ComposeProjectedParBranches, ComposeProjectedParBranchesCase, ContainsRole, FilterSkips,
-    FilterSkipsCase, GetLocalLabel, GetProtocolLabel, NotContainsRole, ProjectChoice, ProjectChoiceCase, 
-    ProjectInteract, ProjectPar, ProjectRoleOrSkip, ProjectRole, TParContainsRoleImpl,
+    ActsFirst, AnyActsFirst, BranchLabels, BranchTags, BranchesTagged, BranchesTaggedCase,
+    ChooserFailure, ChoosersAgree, ChoosersAgreeCase, ComposeProjectedParBranches,
+    ComposeProjectedParBranchesCase, ContainsRole, EndForRejoinCase, FilterByIO, FilterByIOCase,
+    FilterSkips, FilterSkipsCase, GetLocalLabel, GetLocalRole, GetProtocolLabel, IndexEq, IoLocal,
+    IoSession, IsProjectable, LabelInBranches, LocalOf, LocalOfCase, MergeBranches, MixedChoosers,
+    NotContainsRole, NotParticipating, Permissive, ProjectAll, ProjectAnycast, ProjectBranches,
+    ProjectBroadcast, ProjectByIO, ProjectCatchCase, ProjectChoice, ProjectChoiceCase,
+    ProjectChoiceNCase, ProjectConnect, ProjectDeadline, ProjectDelay, ProjectDelegate,
+    ProjectDisconnect, ProjectEach, ProjectEndForCase, ProjectInteract, ProjectInterruptCase,
+    ProjectMixed, ProjectMsg, ProjectOfferCase, ProjectOptionalCase, ProjectPar, ProjectParNCase,
+    ProjectPriorityCase, ProjectRecXCase, ProjectRefineCase, ProjectRole, ProjectRoleOrSkip,
+    ProjectRoles, ProjectRolesCase, ProjectSelectCase, ProjectShuffleCase, ProjectStrictCase,
+    ProjectTaggedBranches, ProjectTaggedChoiceCase, ProjectTaggedOwnerCase, ProjectThrow,
+    ProjectTimeoutCase, ProjectionComposes, ProjectionFailure, ProjectsAlike, ReassignRole,
+    RoleAfterEnd, RoleFamily, RoleLocal, SelfMessage, SessionIOs, SingleChooser, SingleChooserCase,
+    StartedByUnlessSilent, Strict, Succ, TParContainsRoleImpl, UninformedOfChoice, Zero,
 };
 pub use self::utils::{
-    CheckNil, Concat, ConcatCons, Disjoint, DisjointCons, IsEmpty, IsNil, IsNotNil,
+    ByLabel, ByRole, CheckNil, Concat, ConcatCons, Contains, Dedup, Disjoint, DisjointCase,
+    DisjointnessFailure, DuplicateLabel, ElemEq, IsEmpty, IsNil, IsNotNil, LabelUniquenessFailure,
+    RemoveAll, RemoveAllCase, SameRoles, SharedRole, SubsetOf, UniqueLabelCase, UniqueList,
+};
+pub use self::validity::{
+    ActionShape, ActionsDisjoint, DisjointFromAll, EachLocallyWellFormed, LocallyWellFormed,
+    OtherAction, PairwiseDisjointActions, RecvAction, SendAction,
 };
+pub use self::well_formed::{ActiveLevel, BasicLevel, StrictLevel, WellFormed, WellFormedAt};
@@ -0,0 +1,636 @@
+//! # Normalization
+//!
+//! This module rewrites local session types into a normal form, so that two
+//! projections describing the same endpoint behaviour can be compared with
+//! `assert_type_eq!`. Normalization removes `EpSkip` branches, collapses
+//! choices and parallel compositions left with a single branch, and flattens
+//! nested parallel compositions into one `EpParN`.
+//!
+//! Key components:
+//!
+//! - `Normalize`: The normal form of a local session type
+//! - `NormalizeEach`: The normal forms of every local session type in a type-level list
+//! - `ShapeOf`: Classifies a local session type as a skip, a parallel composition, a choice or a step
+//! - `DropSkips`: Removes `EpSkip` entries from a type-level list
+//! - `FlattenPar`: Removes `EpSkip` entries and splices in the branches of nested `EpParN`
+//! - `Collapse`: Rebuilds a choice or parallel composition from its remaining branches
+//! - `Refines`: Two local session types agree once normalized
+
+use super::base::{Cons, Nil};
+use super::local::*;
+use super::utils::Concat;
+use crate::types;
+
+/// Shape of an `EpSkip`.
+pub struct SkipShape;
+/// Shape of an `EpParN`.
+pub struct ParShape;
+/// Shape of an `EpSelect`.
+pub struct SelectShape;
+/// Shape of an `EpOffer`.
+pub struct OfferShape;
+/// Shape of an `EpChoiceN`.
+pub struct ChoiceNShape;
+/// Shape of every other local session type.
+pub struct StepShape;
+
+/// Classifies a local session type for normalization and choice flattening.
+pub trait ShapeOf {
+    type Shape;
+}
+
+/// The normal form of a local session type.
+///
+/// - Every continuation and branch is normalized.
+/// - `EpSkip` branches are removed from choices, parallel compositions and shuffles.
+/// - A choice, parallel composition or shuffle with one branch left becomes that
+///   branch; one with no branch left becomes `EpSkip`.
+/// - `EpPar` becomes `EpParN`, and the branches of nested `EpParN` are spliced
+///   into the enclosing one.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Ping; impl ProtocolLabel for Ping {}
+/// struct Pick; impl ProtocolLabel for Pick {}
+/// type Send = EpSend<Http, Ping, TClient, Message, EpEnd<Http, EmptyLabel, TClient>>;
+/// type Choice = EpSelect<Http, Pick, TClient, tlist!(Send, EpSkip<Http, Pick, TClient>)>;
+/// assert_type_eq!(<Choice as Normalize>::Output, Send);
+/// ```
+pub trait Normalize {
+    type Output;
+}
+
+/// Refinement: `Self` behaves like `Old` up to `EpSkip` steps and the shape
+/// of its choices and parallel compositions.
+///
+/// Holds when both types have the same normal form. Useful to check that a
+/// refactored protocol still projects to what its previous version did.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Ping; impl ProtocolLabel for Ping {}
+/// struct Pick; impl ProtocolLabel for Pick {}
+/// type Old = EpSend<Http, Ping, TClient, Message, EpEnd<Http, EmptyLabel, TClient>>;
+/// type New = EpSelect<Http, Pick, TClient, tlist!(EpSkip<Http, Pick, TClient>, Old)>;
+/// assert_refines!(New, Old);
+/// ```
+pub trait Refines<Old> {}
+
+impl<New, Old> Refines<Old> for New
+where
+    New: Normalize,
+    Old: Normalize,
+    <New as Normalize>::Output: types::TypeEq<<Old as Normalize>::Output>,
+{
+}
+
+/// The normal forms of every local session type in a type-level list.
+pub trait NormalizeEach {
+    type Output;
+}
+
+impl NormalizeEach for Nil {
+    type Output = Nil;
+}
+
+impl<H: Normalize, T: NormalizeEach> NormalizeEach for Cons<H, T> {
+    type Output = Cons<<H as Normalize>::Output, <T as NormalizeEach>::Output>;
+}
+
+/// Removes `EpSkip` entries from a type-level list.
+pub trait DropSkips {
+    type Output;
+}
+
+/// Helper trait for dispatching `DropSkips` on the shape of the head.
+pub trait DropSkipsCase<Shape> {
+    type Output;
+}
+
+impl DropSkips for Nil {
+    type Output = Nil;
+}
+
+impl<H: ShapeOf, T> DropSkips for Cons<H, T>
+where
+    Cons<H, T>: DropSkipsCase<<H as ShapeOf>::Shape>,
+{
+    type Output = <Cons<H, T> as DropSkipsCase<<H as ShapeOf>::Shape>>::Output;
+}
+
+impl<H, T: DropSkips> DropSkipsCase<SkipShape> for Cons<H, T> {
+    type Output = <T as DropSkips>::Output;
+}
+
+impl<H, T: DropSkips> DropSkipsCase<ParShape> for Cons<H, T> {
+    type Output = Cons<H, <T as DropSkips>::Output>;
+}
+
+impl<H, T: DropSkips> DropSkipsCase<StepShape> for Cons<H, T> {
+    type Output = Cons<H, <T as DropSkips>::Output>;
+}
+
+impl<H, T: DropSkips> DropSkipsCase<SelectShape> for Cons<H, T> {
+    type Output = Cons<H, <T as DropSkips>::Output>;
+}
+
+impl<H, T: DropSkips> DropSkipsCase<OfferShape> for Cons<H, T> {
+    type Output = Cons<H, <T as DropSkips>::Output>;
+}
+
+impl<H, T: DropSkips> DropSkipsCase<ChoiceNShape> for Cons<H, T> {
+    type Output = Cons<H, <T as DropSkips>::Output>;
+}
+
+/// Removes `EpSkip` entries from a type-level list and replaces every `EpParN`
+/// entry with its branches.
+pub trait FlattenPar {
+    type Output;
+}
+
+/// Helper trait for dispatching `FlattenPar` on the shape of the head.
+pub trait FlattenParCase<Shape> {
+    type Output;
+}
+
+impl FlattenPar for Nil {
+    type Output = Nil;
+}
+
+impl<H: ShapeOf, T> FlattenPar for Cons<H, T>
+where
+    Cons<H, T>: FlattenParCase<<H as ShapeOf>::Shape>,
+{
+    type Output = <Cons<H, T> as FlattenParCase<<H as ShapeOf>::Shape>>::Output;
+}
+
+impl<H, T: FlattenPar> FlattenParCase<SkipShape> for Cons<H, T> {
+    type Output = <T as FlattenPar>::Output;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches, T> FlattenParCase<ParShape>
+    for Cons<EpParN<IO, Lbl, Me, Branches>, T>
+where
+    T: FlattenPar,
+    Branches: Concat<<T as FlattenPar>::Output>,
+{
+    type Output = <Branches as Concat<<T as FlattenPar>::Output>>::Output;
+}
+
+impl<H, T: FlattenPar> FlattenParCase<StepShape> for Cons<H, T> {
+    type Output = Cons<H, <T as FlattenPar>::Output>;
+}
+
+impl<H, T: FlattenPar> FlattenParCase<SelectShape> for Cons<H, T> {
+    type Output = Cons<H, <T as FlattenPar>::Output>;
+}
+
+impl<H, T: FlattenPar> FlattenParCase<OfferShape> for Cons<H, T> {
+    type Output = Cons<H, <T as FlattenPar>::Output>;
+}
+
+impl<H, T: FlattenPar> FlattenParCase<ChoiceNShape> for Cons<H, T> {
+    type Output = Cons<H, <T as FlattenPar>::Output>;
+}
+
+/// Rebuilds a choice, parallel composition or shuffle from the branches left
+/// after normalization.
+///
+/// Implemented on the original type: no branch left gives `EpSkip`, a single
+/// branch gives that branch, and two or more give the original constructor
+/// over the new list (`EpParN` for `EpPar`).
+pub trait Collapse<List> {
+    type Output;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches> Collapse<Nil>
+    for EpChoiceN<IO, Lbl, Me, Branches>
+{
+    type Output = EpSkip<IO, Lbl, Me>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches, X> Collapse<Cons<X, Nil>>
+    for EpChoiceN<IO, Lbl, Me, Branches>
+{
+    type Output = X;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches, A, B, T> Collapse<Cons<A, Cons<B, T>>>
+    for EpChoiceN<IO, Lbl, Me, Branches>
+{
+    type Output = EpChoiceN<IO, Lbl, Me, Cons<A, Cons<B, T>>>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches> Collapse<Nil>
+    for EpSelect<IO, Lbl, Me, Branches>
+{
+    type Output = EpSkip<IO, Lbl, Me>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches, X> Collapse<Cons<X, Nil>>
+    for EpSelect<IO, Lbl, Me, Branches>
+{
+    type Output = X;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches, A, B, T> Collapse<Cons<A, Cons<B, T>>>
+    for EpSelect<IO, Lbl, Me, Branches>
+{
+    type Output = EpSelect<IO, Lbl, Me, Cons<A, Cons<B, T>>>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches> Collapse<Nil> for EpOffer<IO, Lbl, Me, Branches> {
+    type Output = EpSkip<IO, Lbl, Me>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches, X> Collapse<Cons<X, Nil>>
+    for EpOffer<IO, Lbl, Me, Branches>
+{
+    type Output = X;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches, A, B, T> Collapse<Cons<A, Cons<B, T>>>
+    for EpOffer<IO, Lbl, Me, Branches>
+{
+    type Output = EpOffer<IO, Lbl, Me, Cons<A, Cons<B, T>>>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches> Collapse<Nil> for EpParN<IO, Lbl, Me, Branches> {
+    type Output = EpSkip<IO, Lbl, Me>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches, X> Collapse<Cons<X, Nil>>
+    for EpParN<IO, Lbl, Me, Branches>
+{
+    type Output = X;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches, A, B, T> Collapse<Cons<A, Cons<B, T>>>
+    for EpParN<IO, Lbl, Me, Branches>
+{
+    type Output = EpParN<IO, Lbl, Me, Cons<A, Cons<B, T>>>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches> Collapse<Nil>
+    for EpShuffle<IO, Lbl, Me, Branches>
+{
+    type Output = EpSkip<IO, Lbl, Me>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches, X> Collapse<Cons<X, Nil>>
+    for EpShuffle<IO, Lbl, Me, Branches>
+{
+    type Output = X;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches, A, B, T> Collapse<Cons<A, Cons<B, T>>>
+    for EpShuffle<IO, Lbl, Me, Branches>
+{
+    type Output = EpShuffle<IO, Lbl, Me, Cons<A, Cons<B, T>>>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, L, R> Collapse<Nil> for EpPar<IO, Lbl, Me, L, R> {
+    type Output = EpSkip<IO, Lbl, Me>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, L, R, X> Collapse<Cons<X, Nil>>
+    for EpPar<IO, Lbl, Me, L, R>
+{
+    type Output = X;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, L, R, A, B, T> Collapse<Cons<A, Cons<B, T>>>
+    for EpPar<IO, Lbl, Me, L, R>
+{
+    type Output = EpParN<IO, Lbl, Me, Cons<A, Cons<B, T>>>;
+}
+
+// Shapes
+
+impl<IO, Lbl: types::ProtocolLabel, R> ShapeOf for EpSkip<IO, Lbl, R> {
+    type Shape = SkipShape;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches> ShapeOf for EpParN<IO, Lbl, Me, Branches> {
+    type Shape = ParShape;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R, H, T> ShapeOf for EpSend<IO, Lbl, R, H, T> {
+    type Shape = StepShape;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R, H, T> ShapeOf for EpRecv<IO, Lbl, R, H, T> {
+    type Shape = StepShape;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, ToSet, H, T> ShapeOf
+    for EpBroadcast<IO, Lbl, Me, ToSet, H, T>
+{
+    type Shape = StepShape;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, FromSet, H, T> ShapeOf
+    for EpGather<IO, Lbl, Me, FromSet, H, T>
+{
+    type Shape = StepShape;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, DelegatedEp, Cont> ShapeOf
+    for EpDelegate<IO, Lbl, Me, DelegatedEp, Cont>
+{
+    type Shape = StepShape;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R> ShapeOf for EpEnd<IO, Lbl, R> {
+    type Shape = StepShape;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches> ShapeOf for EpChoiceN<IO, Lbl, Me, Branches> {
+    type Shape = ChoiceNShape;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches> ShapeOf for EpSelect<IO, Lbl, Me, Branches> {
+    type Shape = SelectShape;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches> ShapeOf for EpOffer<IO, Lbl, Me, Branches> {
+    type Shape = OfferShape;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, L, R> ShapeOf for EpPar<IO, Lbl, Me, L, R> {
+    type Shape = StepShape;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Steps> ShapeOf for EpShuffle<IO, Lbl, Me, Steps> {
+    type Shape = StepShape;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Var, Body> ShapeOf for EpRec<IO, Lbl, Var, Body> {
+    type Shape = StepShape;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Dur, Body, OnTimeout> ShapeOf
+    for EpTimeout<IO, Lbl, Me, Dur, Body, OnTimeout>
+{
+    type Shape = StepShape;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Dur, T> ShapeOf for EpDelay<IO, Lbl, Me, Dur, T> {
+    type Shape = StepShape;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Dur, T> ShapeOf for EpDeadline<IO, Lbl, Me, Dur, T> {
+    type Shape = StepShape;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Scope, Handler> ShapeOf
+    for EpInterrupt<IO, Lbl, Me, Scope, Handler>
+{
+    type Shape = StepShape;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Scope, Handler> ShapeOf
+    for EpInterruptible<IO, Lbl, Me, Scope, Handler>
+{
+    type Shape = StepShape;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Body, Cont> ShapeOf
+    for EpOptionalSelect<IO, Lbl, Me, Body, Cont>
+{
+    type Shape = StepShape;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Body, Cont> ShapeOf
+    for EpOptionalOffer<IO, Lbl, Me, Body, Cont>
+{
+    type Shape = StepShape;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Err> ShapeOf for EpThrow<IO, Lbl, Me, Err> {
+    type Shape = StepShape;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Body, Handler> ShapeOf
+    for EpCatch<IO, Lbl, Me, Body, Handler>
+{
+    type Shape = StepShape;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, High, Low> ShapeOf
+    for EpPriorityOffer<IO, Lbl, Me, High, Low>
+{
+    type Shape = StepShape;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Peer, Cont> ShapeOf for EpClose<IO, Lbl, Me, Peer, Cont> {
+    type Shape = StepShape;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Peer, Cont> ShapeOf for EpOpen<IO, Lbl, Me, Peer, Cont> {
+    type Shape = StepShape;
+}
+
+impl<Var> ShapeOf for EpVar<Var> {
+    type Shape = StepShape;
+}
+
+impl<Pred, Inner> ShapeOf for EpRefine<Pred, Inner> {
+    type Shape = StepShape;
+}
+
+// Leaves
+
+impl<IO, Lbl: types::ProtocolLabel, R> Normalize for EpEnd<IO, Lbl, R> {
+    type Output = EpEnd<IO, Lbl, R>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R> Normalize for EpSkip<IO, Lbl, R> {
+    type Output = EpSkip<IO, Lbl, R>;
+}
+
+impl<Var> Normalize for EpVar<Var> {
+    type Output = EpVar<Var>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Err> Normalize for EpThrow<IO, Lbl, Me, Err> {
+    type Output = EpThrow<IO, Lbl, Me, Err>;
+}
+
+// Sequencing
+
+impl<IO, Lbl: types::ProtocolLabel, R, H, T: Normalize> Normalize for EpSend<IO, Lbl, R, H, T> {
+    type Output = EpSend<IO, Lbl, R, H, <T as Normalize>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R, H, T: Normalize> Normalize for EpRecv<IO, Lbl, R, H, T> {
+    type Output = EpRecv<IO, Lbl, R, H, <T as Normalize>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, ToSet, H, T: Normalize> Normalize
+    for EpBroadcast<IO, Lbl, Me, ToSet, H, T>
+{
+    type Output = EpBroadcast<IO, Lbl, Me, ToSet, H, <T as Normalize>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, FromSet, H, T: Normalize> Normalize
+    for EpGather<IO, Lbl, Me, FromSet, H, T>
+{
+    type Output = EpGather<IO, Lbl, Me, FromSet, H, <T as Normalize>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, DelegatedEp, Cont: Normalize> Normalize
+    for EpDelegate<IO, Lbl, Me, DelegatedEp, Cont>
+{
+    type Output = EpDelegate<IO, Lbl, Me, DelegatedEp, <Cont as Normalize>::Output>;
+}
+
+// Branching
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches> Normalize for EpChoiceN<IO, Lbl, Me, Branches>
+where
+    Branches: NormalizeEach,
+    <Branches as NormalizeEach>::Output: DropSkips,
+    Self: Collapse<<<Branches as NormalizeEach>::Output as DropSkips>::Output>,
+{
+    type Output =
+        <Self as Collapse<<<Branches as NormalizeEach>::Output as DropSkips>::Output>>::Output;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches> Normalize for EpSelect<IO, Lbl, Me, Branches>
+where
+    Branches: NormalizeEach,
+    <Branches as NormalizeEach>::Output: DropSkips,
+    Self: Collapse<<<Branches as NormalizeEach>::Output as DropSkips>::Output>,
+{
+    type Output =
+        <Self as Collapse<<<Branches as NormalizeEach>::Output as DropSkips>::Output>>::Output;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches> Normalize for EpOffer<IO, Lbl, Me, Branches>
+where
+    Branches: NormalizeEach,
+    <Branches as NormalizeEach>::Output: DropSkips,
+    Self: Collapse<<<Branches as NormalizeEach>::Output as DropSkips>::Output>,
+{
+    type Output =
+        <Self as Collapse<<<Branches as NormalizeEach>::Output as DropSkips>::Output>>::Output;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches> Normalize for EpShuffle<IO, Lbl, Me, Branches>
+where
+    Branches: NormalizeEach,
+    <Branches as NormalizeEach>::Output: DropSkips,
+    Self: Collapse<<<Branches as NormalizeEach>::Output as DropSkips>::Output>,
+{
+    type Output =
+        <Self as Collapse<<<Branches as NormalizeEach>::Output as DropSkips>::Output>>::Output;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches> Normalize for EpParN<IO, Lbl, Me, Branches>
+where
+    Branches: NormalizeEach,
+    <Branches as NormalizeEach>::Output: FlattenPar,
+    Self: Collapse<<<Branches as NormalizeEach>::Output as FlattenPar>::Output>,
+{
+    type Output =
+        <Self as Collapse<<<Branches as NormalizeEach>::Output as FlattenPar>::Output>>::Output;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, L, R> Normalize for EpPar<IO, Lbl, Me, L, R>
+where
+    Cons<L, Cons<R, Nil>>: NormalizeEach,
+    <Cons<L, Cons<R, Nil>> as NormalizeEach>::Output: FlattenPar,
+    Self: Collapse<<<Cons<L, Cons<R, Nil>> as NormalizeEach>::Output as FlattenPar>::Output>,
+{
+    type Output = <Self as Collapse<
+        <<Cons<L, Cons<R, Nil>> as NormalizeEach>::Output as FlattenPar>::Output,
+    >>::Output;
+}
+
+// Structural cases: every child is normalized in place
+
+impl<IO, Lbl: types::ProtocolLabel, Var, Body: Normalize> Normalize for EpRec<IO, Lbl, Var, Body> {
+    type Output = EpRec<IO, Lbl, Var, <Body as Normalize>::Output>;
+}
+
+impl<IO, Lbl, Me, Dur, Body, OnTimeout> Normalize for EpTimeout<IO, Lbl, Me, Dur, Body, OnTimeout>
+where
+    Lbl: types::ProtocolLabel,
+    Body: Normalize,
+    OnTimeout: Normalize,
+{
+    type Output =
+        EpTimeout<IO, Lbl, Me, Dur, <Body as Normalize>::Output, <OnTimeout as Normalize>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Dur, T: Normalize> Normalize
+    for EpDelay<IO, Lbl, Me, Dur, T>
+{
+    type Output = EpDelay<IO, Lbl, Me, Dur, <T as Normalize>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Dur, T: Normalize> Normalize
+    for EpDeadline<IO, Lbl, Me, Dur, T>
+{
+    type Output = EpDeadline<IO, Lbl, Me, Dur, <T as Normalize>::Output>;
+}
+
+impl<Pred, Inner: Normalize> Normalize for EpRefine<Pred, Inner> {
+    type Output = EpRefine<Pred, <Inner as Normalize>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Scope: Normalize, Handler: Normalize> Normalize
+    for EpInterrupt<IO, Lbl, Me, Scope, Handler>
+{
+    type Output =
+        EpInterrupt<IO, Lbl, Me, <Scope as Normalize>::Output, <Handler as Normalize>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Scope: Normalize, Handler: Normalize> Normalize
+    for EpInterruptible<IO, Lbl, Me, Scope, Handler>
+{
+    type Output =
+        EpInterruptible<IO, Lbl, Me, <Scope as Normalize>::Output, <Handler as Normalize>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Body: Normalize, Cont: Normalize> Normalize
+    for EpOptionalSelect<IO, Lbl, Me, Body, Cont>
+{
+    type Output =
+        EpOptionalSelect<IO, Lbl, Me, <Body as Normalize>::Output, <Cont as Normalize>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Body: Normalize, Cont: Normalize> Normalize
+    for EpOptionalOffer<IO, Lbl, Me, Body, Cont>
+{
+    type Output =
+        EpOptionalOffer<IO, Lbl, Me, <Body as Normalize>::Output, <Cont as Normalize>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Body: Normalize, Handler: Normalize> Normalize
+    for EpCatch<IO, Lbl, Me, Body, Handler>
+{
+    type Output = EpCatch<IO, Lbl, Me, <Body as Normalize>::Output, <Handler as Normalize>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, High: Normalize, Low: Normalize> Normalize
+    for EpPriorityOffer<IO, Lbl, Me, High, Low>
+{
+    type Output =
+        EpPriorityOffer<IO, Lbl, Me, <High as Normalize>::Output, <Low as Normalize>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Peer, Cont: Normalize> Normalize
+    for EpClose<IO, Lbl, Me, Peer, Cont>
+{
+    type Output = EpClose<IO, Lbl, Me, Peer, <Cont as Normalize>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Peer, Cont: Normalize> Normalize
+    for EpOpen<IO, Lbl, Me, Peer, Cont>
+{
+    type Output = EpOpen<IO, Lbl, Me, Peer, <Cont as Normalize>::Output>;
+}
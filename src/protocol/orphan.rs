@@ -0,0 +1,922 @@
+//! # Orphan Message Checks
+//!
+//! This module detects orphan messages: under asynchronous semantics a role
+//! that has not yet learnt which branch of a choice was taken may send a
+//! message that no role receives in the branch actually taken.
+//!
+//! Key components:
+//!
+//! - `OrphanFree`: No choice of a protocol leaves a message unreceived in one of its branches
+//! - `Sent`: A directed message, named by its sender and label
+//! - `SendsOf` / `SendsOfEach`: Every directed message of a protocol
+//! - `EarlySends` / `EarlySendsEach`: The messages sent by roles that have not yet heard of the choice
+//! - `EarlySendCase`: Helper trait dispatching on whether the sender already knows the choice
+//! - `HasSend` / `SendsCovered` / `SendsCoveredCase` / `BranchesSend`: Membership of messages in a branch
+//! - `OrphanMessage` / `OrphanFailure`: Failure markers naming the choice, the sender and the label
+//!
+//! A role knows the outcome of a choice once it decides it or receives a
+//! message in the chosen branch. A message sent by a role before that, in
+//! any branch, must be sent by the same role with the same label in every
+//! branch, or it may be left unreceived. Directed messages are `TMsg`,
+//! `TBroadcast`, `TAnycast` and `TDelegate`; `TInteract` names no receiver
+//! and is not checked.
+//!
+//! Choices with a named decider are checked: `TSelect` and `TOptional`.
+//! Other choices are checked recursively only; `ChoiceAware` covers the
+//! deciding role of a `TChoice`.
+
+use super::base::{Cons, Nil};
+use super::global::*;
+use super::local::{InRoleSet, LabelEq, RoleEq};
+use super::transforms::SingleChooser;
+use super::utils::Concat;
+use crate::types;
+use core::marker::PhantomData;
+
+/// A directed message: `R` sends a message labelled `Lbl`.
+pub struct Sent<R, Lbl>(PhantomData<(R, Lbl)>);
+
+/// Failure marker: in some branch of the choice labelled `Choice`, `R` may
+/// send a message labelled `Lbl` that no role receives.
+pub struct OrphanMessage<Choice, R, Lbl>(PhantomData<(Choice, R, Lbl)>);
+
+/// Never implemented: a bound `Marker: OrphanFailure` fails and names the
+/// orphan message found by [`OrphanFree`].
+#[diagnostic::on_unimplemented(
+    message = "a message may be sent but never received: `{Self}`",
+    label = "the sender cannot know which branch was chosen",
+    note = "tell the sender the outcome of the choice first, or send the message in every branch"
+)]
+pub trait OrphanFailure {}
+
+/// Every directed message of a protocol, in every branch, as a type-level
+/// list of [`Sent`].
+pub trait SendsOf {
+    type Sends;
+}
+
+/// `SendsOf` for every protocol of a type-level list, concatenated.
+pub trait SendsOfEach {
+    type Sends;
+}
+
+/// The directed messages of a protocol sent by roles outside `Informed`,
+/// before they receive anything.
+pub trait EarlySends<Informed> {
+    type Sends;
+}
+
+/// `EarlySends` for every protocol of a type-level list, concatenated.
+pub trait EarlySendsEach<Informed> {
+    type Sends;
+}
+
+/// Helper trait for `EarlySends`, dispatching on whether the sender `R`
+/// already knows the outcome of the choice.
+pub trait EarlySendCase<R, Lbl, Known> {
+    type Sends;
+}
+
+impl<R, Lbl> EarlySendCase<R, Lbl, types::True> for () {
+    type Sends = Nil;
+}
+
+impl<R, Lbl> EarlySendCase<R, Lbl, types::False> for () {
+    type Sends = Cons<Sent<R, Lbl>, Nil>;
+}
+
+/// Whether a type-level list of [`Sent`] contains a message from `R`
+/// labelled `Lbl`.
+pub trait HasSend<R, Lbl> {
+    type Output: types::Bool;
+}
+
+impl<R, Lbl> HasSend<R, Lbl> for Nil {
+    type Output = types::False;
+}
+
+impl<HR, HL, T, R, Lbl> HasSend<R, Lbl> for Cons<Sent<HR, HL>, T>
+where
+    HR: RoleEq<R>,
+    HL: LabelEq<Lbl>,
+    T: HasSend<R, Lbl>,
+    <HR as RoleEq<R>>::Output: types::BoolAnd<<HL as LabelEq<Lbl>>::Output>,
+    types::And<<HR as RoleEq<R>>::Output, <HL as LabelEq<Lbl>>::Output>:
+        types::BoolOr<<T as HasSend<R, Lbl>>::Output>,
+{
+    type Output = types::Or<
+        types::And<<HR as RoleEq<R>>::Output, <HL as LabelEq<Lbl>>::Output>,
+        <T as HasSend<R, Lbl>>::Output,
+    >;
+}
+
+/// Every message of a type-level list of [`Sent`] is also in `Sends`, the
+/// messages of a branch of the choice labelled `Choice`.
+pub trait SendsCovered<Choice, Sends> {}
+
+impl<Choice, Sends> SendsCovered<Choice, Sends> for Nil {}
+
+impl<R, Lbl, T, Choice, Sends> SendsCovered<Choice, Sends> for Cons<Sent<R, Lbl>, T>
+where
+    Sends: HasSend<R, Lbl>,
+    (): SendsCoveredCase<Choice, R, Lbl, <Sends as HasSend<R, Lbl>>::Output>,
+    T: SendsCovered<Choice, Sends>,
+{
+}
+
+/// Helper trait for `SendsCovered`, dispatching on whether a branch sends the
+/// message.
+pub trait SendsCoveredCase<Choice, R, Lbl, Found> {}
+
+impl<Choice, R, Lbl> SendsCoveredCase<Choice, R, Lbl, types::True> for () {}
+
+impl<Choice, R, Lbl> SendsCoveredCase<Choice, R, Lbl, types::False> for () where
+    OrphanMessage<Choice, R, Lbl>: OrphanFailure
+{
+}
+
+/// Every branch of a type-level list sends every message of `Early`, the
+/// early messages of the choice labelled `Choice`.
+pub trait BranchesSend<Choice, Early> {}
+
+impl<Choice, Early> BranchesSend<Choice, Early> for Nil {}
+
+impl<H, T, Choice, Early> BranchesSend<Choice, Early> for Cons<H, T>
+where
+    H: SendsOf,
+    Early: SendsCovered<Choice, <H as SendsOf>::Sends>,
+    T: BranchesSend<Choice, Early>,
+{
+}
+
+/// A global protocol whose choices leave no orphan message.
+///
+/// See the module documentation for what is checked. Check a protocol with
+/// [`assert_orphan_free!`](crate::assert_orphan_free).
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Alice; struct Bob; struct Carol;
+/// struct Pick; struct Go; struct Stop; struct Log;
+/// impl Role for Alice {} impl Role for Bob {} impl Role for Carol {}
+/// impl ProtocolLabel for Pick {} impl ProtocolLabel for Go {}
+/// impl ProtocolLabel for Stop {} impl ProtocolLabel for Log {}
+/// impl RoleEq<Alice> for Alice { type Output = True; }
+/// impl RoleEq<Carol> for Alice { type Output = False; }
+/// impl RoleEq<Alice> for Carol { type Output = False; }
+/// impl RoleEq<Bob> for Carol   { type Output = False; }
+/// impl RoleEq<Carol> for Carol { type Output = True; }
+/// impl LabelEq<Log> for Go     { type Output = False; }
+/// impl LabelEq<Log> for Stop   { type Output = False; }
+/// impl LabelEq<Log> for Log    { type Output = True; }
+///
+/// // Carol logs to Bob in both branches, so Bob receives her message either way
+/// type G = TSelect<
+///     Http,
+///     Pick,
+///     Alice,
+///     tlist!(
+///         TMsg<Http, Go, Alice, Bob, Message, TMsg<Http, Log, Carol, Bob, Notify, TEnd<Http>>>,
+///         TMsg<Http, Stop, Alice, Bob, Message, TMsg<Http, Log, Carol, Bob, Notify, TEnd<Http>>>,
+///     ),
+/// >;
+/// assert_orphan_free!(G);
+/// ```
+pub trait OrphanFree {}
+
+impl<IO, Lbl> SendsOf for TEnd<IO, Lbl> {
+    type Sends = Nil;
+}
+
+impl<IO, Lbl, Informed> EarlySends<Informed> for TEnd<IO, Lbl> {
+    type Sends = Nil;
+}
+
+impl<IO, Lbl> OrphanFree for TEnd<IO, Lbl> {}
+
+impl<IO, Lbl: types::ProtocolLabel, R, H, T> SendsOf for TInteract<IO, Lbl, R, H, T>
+where
+    T: TSession<IO> + SendsOf,
+{
+    type Sends = <T as SendsOf>::Sends;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R, H, T, Informed> EarlySends<Informed>
+    for TInteract<IO, Lbl, R, H, T>
+where
+    T: TSession<IO> + EarlySends<Informed>,
+{
+    type Sends = <T as EarlySends<Informed>>::Sends;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R, H, T> OrphanFree for TInteract<IO, Lbl, R, H, T> where
+    T: TSession<IO> + OrphanFree
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, To, H, T> SendsOf for TMsg<IO, Lbl, From, To, H, T>
+where
+    T: TSession<IO> + SendsOf,
+{
+    type Sends = Cons<Sent<From, Lbl>, <T as SendsOf>::Sends>;
+}
+
+// The receiver knows the outcome from here on
+impl<IO, Lbl: types::ProtocolLabel, From, To, H, T, Informed> EarlySends<Informed>
+    for TMsg<IO, Lbl, From, To, H, T>
+where
+    T: TSession<IO> + EarlySends<Cons<To, Informed>>,
+    From: InRoleSet<Informed>,
+    (): EarlySendCase<From, Lbl, <From as InRoleSet<Informed>>::Output>,
+    <() as EarlySendCase<From, Lbl, <From as InRoleSet<Informed>>::Output>>::Sends:
+        Concat<<T as EarlySends<Cons<To, Informed>>>::Sends>,
+{
+    type Sends = <<() as EarlySendCase<From, Lbl, <From as InRoleSet<Informed>>::Output>>::Sends as Concat<
+        <T as EarlySends<Cons<To, Informed>>>::Sends,
+    >>::Output;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, To, H, T> OrphanFree for TMsg<IO, Lbl, From, To, H, T> where
+    T: TSession<IO> + OrphanFree
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, ToSet, H, T> SendsOf
+    for TBroadcast<IO, Lbl, From, ToSet, H, T>
+where
+    T: TSession<IO> + SendsOf,
+{
+    type Sends = Cons<Sent<From, Lbl>, <T as SendsOf>::Sends>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, ToSet, H, T, Informed> EarlySends<Informed>
+    for TBroadcast<IO, Lbl, From, ToSet, H, T>
+where
+    ToSet: Concat<Informed>,
+    T: TSession<IO> + EarlySends<<ToSet as Concat<Informed>>::Output>,
+    From: InRoleSet<Informed>,
+    (): EarlySendCase<From, Lbl, <From as InRoleSet<Informed>>::Output>,
+    <() as EarlySendCase<From, Lbl, <From as InRoleSet<Informed>>::Output>>::Sends:
+        Concat<<T as EarlySends<<ToSet as Concat<Informed>>::Output>>::Sends>,
+{
+    type Sends = <<() as EarlySendCase<From, Lbl, <From as InRoleSet<Informed>>::Output>>::Sends as Concat<
+        <T as EarlySends<<ToSet as Concat<Informed>>::Output>>::Sends,
+    >>::Output;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, ToSet, H, T> OrphanFree
+    for TBroadcast<IO, Lbl, From, ToSet, H, T>
+where
+    T: TSession<IO> + OrphanFree,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, WorkerSet, H, T> SendsOf
+    for TAnycast<IO, Lbl, From, WorkerSet, H, T>
+where
+    T: TSession<IO> + SendsOf,
+{
+    type Sends = Cons<Sent<From, Lbl>, <T as SendsOf>::Sends>;
+}
+
+// Any worker may take the message, so none of them counts as informed
+impl<IO, Lbl: types::ProtocolLabel, From, WorkerSet, H, T, Informed> EarlySends<Informed>
+    for TAnycast<IO, Lbl, From, WorkerSet, H, T>
+where
+    T: TSession<IO> + EarlySends<Informed>,
+    From: InRoleSet<Informed>,
+    (): EarlySendCase<From, Lbl, <From as InRoleSet<Informed>>::Output>,
+    <() as EarlySendCase<From, Lbl, <From as InRoleSet<Informed>>::Output>>::Sends:
+        Concat<<T as EarlySends<Informed>>::Sends>,
+{
+    type Sends = <<() as EarlySendCase<From, Lbl, <From as InRoleSet<Informed>>::Output>>::Sends as Concat<
+        <T as EarlySends<Informed>>::Sends,
+    >>::Output;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, WorkerSet, H, T> OrphanFree
+    for TAnycast<IO, Lbl, From, WorkerSet, H, T>
+where
+    T: TSession<IO> + OrphanFree,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, To, DelegatedEp, T> SendsOf
+    for TDelegate<IO, Lbl, From, To, DelegatedEp, T>
+where
+    T: TSession<IO> + SendsOf,
+{
+    type Sends = Cons<Sent<From, Lbl>, <T as SendsOf>::Sends>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, To, DelegatedEp, T, Informed> EarlySends<Informed>
+    for TDelegate<IO, Lbl, From, To, DelegatedEp, T>
+where
+    T: TSession<IO> + EarlySends<Cons<To, Informed>>,
+    From: InRoleSet<Informed>,
+    (): EarlySendCase<From, Lbl, <From as InRoleSet<Informed>>::Output>,
+    <() as EarlySendCase<From, Lbl, <From as InRoleSet<Informed>>::Output>>::Sends:
+        Concat<<T as EarlySends<Cons<To, Informed>>>::Sends>,
+{
+    type Sends = <<() as EarlySendCase<From, Lbl, <From as InRoleSet<Informed>>::Output>>::Sends as Concat<
+        <T as EarlySends<Cons<To, Informed>>>::Sends,
+    >>::Output;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, To, DelegatedEp, T> OrphanFree
+    for TDelegate<IO, Lbl, From, To, DelegatedEp, T>
+where
+    T: TSession<IO> + OrphanFree,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, A, B, Cont> SendsOf for TDisconnect<IO, Lbl, A, B, Cont>
+where
+    Cont: TSession<IO> + SendsOf,
+{
+    type Sends = <Cont as SendsOf>::Sends;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, A, B, Cont, Informed> EarlySends<Informed>
+    for TDisconnect<IO, Lbl, A, B, Cont>
+where
+    Cont: TSession<IO> + EarlySends<Informed>,
+{
+    type Sends = <Cont as EarlySends<Informed>>::Sends;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, A, B, Cont> OrphanFree for TDisconnect<IO, Lbl, A, B, Cont> where
+    Cont: TSession<IO> + OrphanFree
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, A, B, Cont> SendsOf for TConnect<IO, Lbl, A, B, Cont>
+where
+    Cont: TSession<IO> + SendsOf,
+{
+    type Sends = <Cont as SendsOf>::Sends;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, A, B, Cont, Informed> EarlySends<Informed>
+    for TConnect<IO, Lbl, A, B, Cont>
+where
+    Cont: TSession<IO> + EarlySends<Informed>,
+{
+    type Sends = <Cont as EarlySends<Informed>>::Sends;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, A, B, Cont> OrphanFree for TConnect<IO, Lbl, A, B, Cont> where
+    Cont: TSession<IO> + OrphanFree
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R, Cont> SendsOf for TEndFor<IO, Lbl, R, Cont>
+where
+    Cont: TSession<IO> + SendsOf,
+{
+    type Sends = <Cont as SendsOf>::Sends;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R, Cont, Informed> EarlySends<Informed>
+    for TEndFor<IO, Lbl, R, Cont>
+where
+    Cont: TSession<IO> + EarlySends<Informed>,
+{
+    type Sends = <Cont as EarlySends<Informed>>::Sends;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R, Cont> OrphanFree for TEndFor<IO, Lbl, R, Cont> where
+    Cont: TSession<IO> + OrphanFree
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, L, R> SendsOf for TChoice<IO, Lbl, L, R>
+where
+    L: TSession<IO> + SendsOf,
+    R: TSession<IO> + SendsOf,
+    <L as SendsOf>::Sends: Concat<<R as SendsOf>::Sends>,
+{
+    type Sends = <<L as SendsOf>::Sends as Concat<<R as SendsOf>::Sends>>::Output;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, L, R, Informed> EarlySends<Informed> for TChoice<IO, Lbl, L, R>
+where
+    L: TSession<IO> + EarlySends<Informed>,
+    R: TSession<IO> + EarlySends<Informed>,
+    <L as EarlySends<Informed>>::Sends: Concat<<R as EarlySends<Informed>>::Sends>,
+{
+    type Sends =
+        <<L as EarlySends<Informed>>::Sends as Concat<<R as EarlySends<Informed>>::Sends>>::Output;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, L, R> OrphanFree for TChoice<IO, Lbl, L, R>
+where
+    L: TSession<IO> + OrphanFree,
+    R: TSession<IO> + OrphanFree,
+    Cons<L, Cons<R, Nil>>: SingleChooser<Lbl>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Branches> SendsOf for TChoiceN<IO, Lbl, Branches>
+where
+    Branches: SessionList<IO> + SendsOfEach,
+{
+    type Sends = <Branches as SendsOfEach>::Sends;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Branches, Informed> EarlySends<Informed>
+    for TChoiceN<IO, Lbl, Branches>
+where
+    Branches: SessionList<IO> + EarlySendsEach<Informed>,
+{
+    type Sends = <Branches as EarlySendsEach<Informed>>::Sends;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Branches> OrphanFree for TChoiceN<IO, Lbl, Branches> where
+    Branches: SessionList<IO> + OrphanFree
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Chooser, Branches> SendsOf
+    for TSelect<IO, Lbl, Chooser, Branches>
+where
+    Branches: SessionList<IO> + SendsOfEach,
+{
+    type Sends = <Branches as SendsOfEach>::Sends;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Chooser, Branches, Informed> EarlySends<Informed>
+    for TSelect<IO, Lbl, Chooser, Branches>
+where
+    Branches: SessionList<IO> + EarlySendsEach<Informed>,
+{
+    type Sends = <Branches as EarlySendsEach<Informed>>::Sends;
+}
+
+// Only the chooser knows the branch before any message of it is received
+impl<IO, Lbl: types::ProtocolLabel, Chooser, Branches> OrphanFree
+    for TSelect<IO, Lbl, Chooser, Branches>
+where
+    Branches: SessionList<IO> + OrphanFree + EarlySendsEach<Cons<Chooser, Nil>>,
+    Branches: BranchesSend<Lbl, <Branches as EarlySendsEach<Cons<Chooser, Nil>>>::Sends>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Offeree, Branches> SendsOf
+    for TOffer<IO, Lbl, Offeree, Branches>
+where
+    Branches: SessionList<IO> + SendsOfEach,
+{
+    type Sends = <Branches as SendsOfEach>::Sends;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Offeree, Branches, Informed> EarlySends<Informed>
+    for TOffer<IO, Lbl, Offeree, Branches>
+where
+    Branches: SessionList<IO> + EarlySendsEach<Informed>,
+{
+    type Sends = <Branches as EarlySendsEach<Informed>>::Sends;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Offeree, Branches> OrphanFree
+    for TOffer<IO, Lbl, Offeree, Branches>
+where
+    Branches: SessionList<IO> + OrphanFree,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, High, Low> SendsOf for TPriorityChoice<IO, Lbl, High, Low>
+where
+    High: TSession<IO> + SendsOf,
+    Low: TSession<IO> + SendsOf,
+    <High as SendsOf>::Sends: Concat<<Low as SendsOf>::Sends>,
+{
+    type Sends = <<High as SendsOf>::Sends as Concat<<Low as SendsOf>::Sends>>::Output;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, High, Low, Informed> EarlySends<Informed>
+    for TPriorityChoice<IO, Lbl, High, Low>
+where
+    High: TSession<IO> + EarlySends<Informed>,
+    Low: TSession<IO> + EarlySends<Informed>,
+    <High as EarlySends<Informed>>::Sends: Concat<<Low as EarlySends<Informed>>::Sends>,
+{
+    type Sends = <<High as EarlySends<Informed>>::Sends as Concat<
+        <Low as EarlySends<Informed>>::Sends,
+    >>::Output;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, High, Low> OrphanFree for TPriorityChoice<IO, Lbl, High, Low>
+where
+    High: TSession<IO> + OrphanFree,
+    Low: TSession<IO> + OrphanFree,
+{
+}
+
+impl<IO, Lbl, WeightL, L, WeightR, R> SendsOf for TProb<IO, Lbl, WeightL, L, WeightR, R>
+where
+    Lbl: types::ProtocolLabel,
+    WeightL: types::WeightMarker,
+    WeightR: types::WeightMarker,
+    L: TSession<IO> + SendsOf,
+    R: TSession<IO> + SendsOf,
+    <L as SendsOf>::Sends: Concat<<R as SendsOf>::Sends>,
+{
+    type Sends = <<L as SendsOf>::Sends as Concat<<R as SendsOf>::Sends>>::Output;
+}
+
+impl<IO, Lbl, WeightL, L, WeightR, R, Informed> EarlySends<Informed>
+    for TProb<IO, Lbl, WeightL, L, WeightR, R>
+where
+    Lbl: types::ProtocolLabel,
+    WeightL: types::WeightMarker,
+    WeightR: types::WeightMarker,
+    L: TSession<IO> + EarlySends<Informed>,
+    R: TSession<IO> + EarlySends<Informed>,
+    <L as EarlySends<Informed>>::Sends: Concat<<R as EarlySends<Informed>>::Sends>,
+{
+    type Sends =
+        <<L as EarlySends<Informed>>::Sends as Concat<<R as EarlySends<Informed>>::Sends>>::Output;
+}
+
+impl<IO, Lbl, WeightL, L, WeightR, R> OrphanFree for TProb<IO, Lbl, WeightL, L, WeightR, R>
+where
+    Lbl: types::ProtocolLabel,
+    WeightL: types::WeightMarker,
+    WeightR: types::WeightMarker,
+    L: TSession<IO> + OrphanFree,
+    R: TSession<IO> + OrphanFree,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Decider, Body, Cont> SendsOf
+    for TOptional<IO, Lbl, Decider, Body, Cont>
+where
+    Body: TSession<IO> + SendsOf,
+    Cont: TSession<IO> + SendsOf,
+    <Body as SendsOf>::Sends: Concat<<Cont as SendsOf>::Sends>,
+{
+    type Sends = <<Body as SendsOf>::Sends as Concat<<Cont as SendsOf>::Sends>>::Output;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Decider, Body, Cont, Informed> EarlySends<Informed>
+    for TOptional<IO, Lbl, Decider, Body, Cont>
+where
+    Body: TSession<IO> + EarlySends<Informed>,
+    Cont: TSession<IO> + EarlySends<Informed>,
+    <Body as EarlySends<Informed>>::Sends: Concat<<Cont as EarlySends<Informed>>::Sends>,
+{
+    type Sends = <<Body as EarlySends<Informed>>::Sends as Concat<
+        <Cont as EarlySends<Informed>>::Sends,
+    >>::Output;
+}
+
+// The branches are the body followed by the continuation, and the
+// continuation alone
+impl<IO, Lbl: types::ProtocolLabel, Decider, Body, Cont> OrphanFree
+    for TOptional<IO, Lbl, Decider, Body, Cont>
+where
+    Body: TSession<IO> + OrphanFree,
+    Cont: TSession<IO> + OrphanFree,
+    Cons<Body::Compose<Cont>, Cons<Cont, Nil>>: EarlySendsEach<Cons<Decider, Nil>>,
+    Cons<Body::Compose<Cont>, Cons<Cont, Nil>>: BranchesSend<
+        Lbl,
+        <Cons<Body::Compose<Cont>, Cons<Cont, Nil>> as EarlySendsEach<Cons<Decider, Nil>>>::Sends,
+    >,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, L, R, IsDisjoint> SendsOf for TPar<IO, Lbl, L, R, IsDisjoint>
+where
+    L: TSession<IO> + SendsOf,
+    R: TSession<IO> + SendsOf,
+    <L as SendsOf>::Sends: Concat<<R as SendsOf>::Sends>,
+{
+    type Sends = <<L as SendsOf>::Sends as Concat<<R as SendsOf>::Sends>>::Output;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, L, R, IsDisjoint, Informed> EarlySends<Informed>
+    for TPar<IO, Lbl, L, R, IsDisjoint>
+where
+    L: TSession<IO> + EarlySends<Informed>,
+    R: TSession<IO> + EarlySends<Informed>,
+    <L as EarlySends<Informed>>::Sends: Concat<<R as EarlySends<Informed>>::Sends>,
+{
+    type Sends =
+        <<L as EarlySends<Informed>>::Sends as Concat<<R as EarlySends<Informed>>::Sends>>::Output;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, L, R, IsDisjoint> OrphanFree for TPar<IO, Lbl, L, R, IsDisjoint>
+where
+    L: TSession<IO> + OrphanFree,
+    R: TSession<IO> + OrphanFree,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Branches> SendsOf for TParN<IO, Lbl, Branches>
+where
+    Branches: SessionList<IO> + SendsOfEach,
+{
+    type Sends = <Branches as SendsOfEach>::Sends;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Branches, Informed> EarlySends<Informed>
+    for TParN<IO, Lbl, Branches>
+where
+    Branches: SessionList<IO> + EarlySendsEach<Informed>,
+{
+    type Sends = <Branches as EarlySendsEach<Informed>>::Sends;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Branches> OrphanFree for TParN<IO, Lbl, Branches> where
+    Branches: SessionList<IO> + OrphanFree
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Steps> SendsOf for TShuffle<IO, Lbl, Steps>
+where
+    Steps: SessionList<IO> + SendsOfEach,
+{
+    type Sends = <Steps as SendsOfEach>::Sends;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Steps, Informed> EarlySends<Informed>
+    for TShuffle<IO, Lbl, Steps>
+where
+    Steps: SessionList<IO> + EarlySendsEach<Informed>,
+{
+    type Sends = <Steps as EarlySendsEach<Informed>>::Sends;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Steps> OrphanFree for TShuffle<IO, Lbl, Steps> where
+    Steps: SessionList<IO> + OrphanFree
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, S> SendsOf for TRec<IO, Lbl, S>
+where
+    S: TSession<IO> + SendsOf,
+{
+    type Sends = <S as SendsOf>::Sends;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, S, Informed> EarlySends<Informed> for TRec<IO, Lbl, S>
+where
+    S: TSession<IO> + EarlySends<Informed>,
+{
+    type Sends = <S as EarlySends<Informed>>::Sends;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, S> OrphanFree for TRec<IO, Lbl, S> where
+    S: TSession<IO> + OrphanFree
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Var, Body> SendsOf for TRecX<IO, Lbl, Var, Body>
+where
+    Body: TSession<IO> + SendsOf,
+{
+    type Sends = <Body as SendsOf>::Sends;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Var, Body, Informed> EarlySends<Informed>
+    for TRecX<IO, Lbl, Var, Body>
+where
+    Body: TSession<IO> + EarlySends<Informed>,
+{
+    type Sends = <Body as EarlySends<Informed>>::Sends;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Var, Body> OrphanFree for TRecX<IO, Lbl, Var, Body> where
+    Body: TSession<IO> + OrphanFree
+{
+}
+
+impl<Var> SendsOf for TVar<Var> {
+    type Sends = Nil;
+}
+
+impl<Var, Informed> EarlySends<Informed> for TVar<Var> {
+    type Sends = Nil;
+}
+
+impl<Var> OrphanFree for TVar<Var> {}
+
+impl<IO, Lbl, Dur, Body, OnTimeout> SendsOf for TTimeout<IO, Lbl, Dur, Body, OnTimeout>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    Body: TSession<IO> + SendsOf,
+    OnTimeout: TSession<IO> + SendsOf,
+    <Body as SendsOf>::Sends: Concat<<OnTimeout as SendsOf>::Sends>,
+{
+    type Sends = <<Body as SendsOf>::Sends as Concat<<OnTimeout as SendsOf>::Sends>>::Output;
+}
+
+impl<IO, Lbl, Dur, Body, OnTimeout, Informed> EarlySends<Informed>
+    for TTimeout<IO, Lbl, Dur, Body, OnTimeout>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    Body: TSession<IO> + EarlySends<Informed>,
+    OnTimeout: TSession<IO> + EarlySends<Informed>,
+    <Body as EarlySends<Informed>>::Sends: Concat<<OnTimeout as EarlySends<Informed>>::Sends>,
+{
+    type Sends = <<Body as EarlySends<Informed>>::Sends as Concat<
+        <OnTimeout as EarlySends<Informed>>::Sends,
+    >>::Output;
+}
+
+impl<IO, Lbl, Dur, Body, OnTimeout> OrphanFree for TTimeout<IO, Lbl, Dur, Body, OnTimeout>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    Body: TSession<IO> + OrphanFree,
+    OnTimeout: TSession<IO> + OrphanFree,
+{
+}
+
+impl<IO, Lbl, R, Dur, T> SendsOf for TDelay<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: TSession<IO> + SendsOf,
+{
+    type Sends = <T as SendsOf>::Sends;
+}
+
+impl<IO, Lbl, R, Dur, T, Informed> EarlySends<Informed> for TDelay<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: TSession<IO> + EarlySends<Informed>,
+{
+    type Sends = <T as EarlySends<Informed>>::Sends;
+}
+
+impl<IO, Lbl, R, Dur, T> OrphanFree for TDelay<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: TSession<IO> + OrphanFree,
+{
+}
+
+impl<IO, Lbl, R, Dur, T> SendsOf for TDeadline<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: TSession<IO> + SendsOf,
+{
+    type Sends = <T as SendsOf>::Sends;
+}
+
+impl<IO, Lbl, R, Dur, T, Informed> EarlySends<Informed> for TDeadline<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: TSession<IO> + EarlySends<Informed>,
+{
+    type Sends = <T as EarlySends<Informed>>::Sends;
+}
+
+impl<IO, Lbl, R, Dur, T> OrphanFree for TDeadline<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: TSession<IO> + OrphanFree,
+{
+}
+
+impl<IO, Lbl, Scope, Interruptor, Handler> SendsOf
+    for TInterrupt<IO, Lbl, Scope, Interruptor, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Scope: TSession<IO> + SendsOf,
+    Handler: TSession<IO> + SendsOf,
+    <Scope as SendsOf>::Sends: Concat<<Handler as SendsOf>::Sends>,
+{
+    type Sends = <<Scope as SendsOf>::Sends as Concat<<Handler as SendsOf>::Sends>>::Output;
+}
+
+impl<IO, Lbl, Scope, Interruptor, Handler, Informed> EarlySends<Informed>
+    for TInterrupt<IO, Lbl, Scope, Interruptor, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Scope: TSession<IO> + EarlySends<Informed>,
+    Handler: TSession<IO> + EarlySends<Informed>,
+    <Scope as EarlySends<Informed>>::Sends: Concat<<Handler as EarlySends<Informed>>::Sends>,
+{
+    type Sends = <<Scope as EarlySends<Informed>>::Sends as Concat<
+        <Handler as EarlySends<Informed>>::Sends,
+    >>::Output;
+}
+
+impl<IO, Lbl, Scope, Interruptor, Handler> OrphanFree
+    for TInterrupt<IO, Lbl, Scope, Interruptor, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Scope: TSession<IO> + OrphanFree,
+    Handler: TSession<IO> + OrphanFree,
+{
+}
+
+impl<Pred, Inner: SendsOf> SendsOf for TRefine<Pred, Inner> {
+    type Sends = <Inner as SendsOf>::Sends;
+}
+
+impl<Pred, Inner: EarlySends<Informed>, Informed> EarlySends<Informed> for TRefine<Pred, Inner> {
+    type Sends = <Inner as EarlySends<Informed>>::Sends;
+}
+
+impl<Pred, Inner: OrphanFree> OrphanFree for TRefine<Pred, Inner> {}
+
+impl<Meta, Inner: SendsOf> SendsOf for TAnnotate<Meta, Inner> {
+    type Sends = <Inner as SendsOf>::Sends;
+}
+
+impl<Meta, Inner: EarlySends<Informed>, Informed> EarlySends<Informed> for TAnnotate<Meta, Inner> {
+    type Sends = <Inner as EarlySends<Informed>>::Sends;
+}
+
+impl<Meta, Inner: OrphanFree> OrphanFree for TAnnotate<Meta, Inner> {}
+
+impl<IO, Lbl: types::ProtocolLabel, R, Err> SendsOf for TThrow<IO, Lbl, R, Err> {
+    type Sends = Nil;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R, Err, Informed> EarlySends<Informed>
+    for TThrow<IO, Lbl, R, Err>
+{
+    type Sends = Nil;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R, Err> OrphanFree for TThrow<IO, Lbl, R, Err> {}
+
+impl<IO, Lbl, Body, Handler> SendsOf for TCatch<IO, Lbl, Body, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Body: TSession<IO> + SendsOf,
+    Handler: TSession<IO> + SendsOf,
+    <Body as SendsOf>::Sends: Concat<<Handler as SendsOf>::Sends>,
+{
+    type Sends = <<Body as SendsOf>::Sends as Concat<<Handler as SendsOf>::Sends>>::Output;
+}
+
+impl<IO, Lbl, Body, Handler, Informed> EarlySends<Informed> for TCatch<IO, Lbl, Body, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Body: TSession<IO> + EarlySends<Informed>,
+    Handler: TSession<IO> + EarlySends<Informed>,
+    <Body as EarlySends<Informed>>::Sends: Concat<<Handler as EarlySends<Informed>>::Sends>,
+{
+    type Sends = <<Body as EarlySends<Informed>>::Sends as Concat<
+        <Handler as EarlySends<Informed>>::Sends,
+    >>::Output;
+}
+
+impl<IO, Lbl, Body, Handler> OrphanFree for TCatch<IO, Lbl, Body, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Body: TSession<IO> + OrphanFree,
+    Handler: TSession<IO> + OrphanFree,
+{
+}
+
+impl SendsOfEach for Nil {
+    type Sends = Nil;
+}
+
+impl<H, T> SendsOfEach for Cons<H, T>
+where
+    H: SendsOf,
+    T: SendsOfEach,
+    <H as SendsOf>::Sends: Concat<<T as SendsOfEach>::Sends>,
+{
+    type Sends = <<H as SendsOf>::Sends as Concat<<T as SendsOfEach>::Sends>>::Output;
+}
+
+impl<Informed> EarlySendsEach<Informed> for Nil {
+    type Sends = Nil;
+}
+
+impl<H, T, Informed> EarlySendsEach<Informed> for Cons<H, T>
+where
+    H: EarlySends<Informed>,
+    T: EarlySendsEach<Informed>,
+    <H as EarlySends<Informed>>::Sends: Concat<<T as EarlySendsEach<Informed>>::Sends>,
+{
+    type Sends = <<H as EarlySends<Informed>>::Sends as Concat<
+        <T as EarlySendsEach<Informed>>::Sends,
+    >>::Output;
+}
+
+impl OrphanFree for Nil {}
+
+impl<H, T> OrphanFree for Cons<H, T>
+where
+    H: OrphanFree,
+    T: OrphanFree,
+{
+}
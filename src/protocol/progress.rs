@@ -0,0 +1,285 @@
+//! # Progress Checks
+//!
+//! This module checks that a global protocol never gets stuck: every state
+//! other than `TEnd` leaves some role an interaction it can perform.
+//!
+//! Key components:
+//!
+//! - `HasProgress`: No reachable state of a protocol is stuck
+//! - `ProgressChoiceCase` / `ProgressEndForCase`: Helper traits dispatching on the stuck cases
+//! - `EmptyChoice` / `StuckAfterLeaving` / `ProgressFailure`: Failure markers naming the stuck state
+//!
+//! A protocol gets stuck when:
+//!
+//! - a choice (`TChoiceN`, `TSelect`, `TOffer`) has no branch to take;
+//! - a role left with `TEndFor` is still needed by the rest of the protocol;
+//! - a pair closed with `TDisconnect` still interacts before it is reconnected.
+//!
+//! A loop body is checked once, and a `TVar` is never stuck: `Guarded` makes
+//! every loop body act before it jumps back. The next iteration is not
+//! checked, so a role that leaves with `TEndFor`, or a pair closed with
+//! `TDisconnect`, before a `TVar` is not caught when the body needs it again.
+//! A `TThrow` hands the rest of the protocol to its handler, whose presence
+//! `CatchScoped` checks.
+
+use super::base::{Cons, Nil};
+use super::connection::PairUnused;
+use super::global::*;
+use super::transforms::{ContainsRole, SingleChooser};
+use super::utils::IsEmpty;
+use crate::types;
+use core::marker::PhantomData;
+
+/// Marker for a choice labelled `Lbl` with no branches, where no role can act.
+pub struct EmptyChoice<Lbl>(PhantomData<Lbl>);
+
+/// Marker for a role `R` that is still needed after it left the session.
+pub struct StuckAfterLeaving<R>(PhantomData<R>);
+
+/// Never implemented: a bound `Marker: ProgressFailure` fails and names the
+/// stuck state found by [`HasProgress`].
+#[diagnostic::on_unimplemented(
+    message = "the protocol can get stuck: `{Self}`",
+    label = "no role can act in this state",
+    note = "give every choice a branch, and do not use a role after its `TEndFor`"
+)]
+pub trait ProgressFailure {}
+
+/// A global protocol whose every reachable state, other than `TEnd`, enables
+/// an interaction for some role.
+///
+/// See the module documentation for the stuck states that are rejected.
+/// Check a protocol with [`assert_progress!`](crate::assert_progress).
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Alice; struct Bob; struct Bye;
+/// impl Role for Alice {} impl Role for Bob {}
+/// impl ProtocolLabel for Bye {}
+/// impl RoleEq<Alice> for Alice { type Output = True; }
+/// impl RoleEq<Bob> for Alice   { type Output = False; }
+/// impl RoleEq<Alice> for Bob   { type Output = False; }
+/// impl RoleEq<Bob> for Bob     { type Output = True; }
+///
+/// // Bob says goodbye and leaves; Alice does not need him afterwards
+/// type G = TMsg<Http, Bye, Bob, Alice, Message, TEndFor<Http, Bye, Bob, TEnd<Http>>>;
+/// assert_progress!(G);
+/// ```
+pub trait HasProgress {}
+
+/// Helper trait for choices, dispatching on whether the branch list is empty.
+pub trait ProgressChoiceCase<Lbl, IsEmpty> {}
+
+impl<Branches: HasProgress, Lbl> ProgressChoiceCase<Lbl, types::False> for Branches {}
+
+impl<Branches, Lbl> ProgressChoiceCase<Lbl, types::True> for Branches where
+    EmptyChoice<Lbl>: ProgressFailure
+{
+}
+
+/// Helper trait for `TEndFor`, dispatching on whether the continuation still
+/// involves the role that left.
+pub trait ProgressEndForCase<R, StillNeeded> {}
+
+impl<Cont: HasProgress, R> ProgressEndForCase<R, types::False> for Cont {}
+
+impl<Cont, R> ProgressEndForCase<R, types::True> for Cont where StuckAfterLeaving<R>: ProgressFailure
+{}
+
+impl<IO, Lbl> HasProgress for TEnd<IO, Lbl> {}
+
+impl<IO, Lbl: types::ProtocolLabel, R, H, T> HasProgress for TInteract<IO, Lbl, R, H, T> where
+    T: TSession<IO> + HasProgress
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, To, H, T> HasProgress for TMsg<IO, Lbl, From, To, H, T> where
+    T: TSession<IO> + HasProgress
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, ToSet, H, T> HasProgress
+    for TBroadcast<IO, Lbl, From, ToSet, H, T>
+where
+    T: TSession<IO> + HasProgress,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, WorkerSet, H, T> HasProgress
+    for TAnycast<IO, Lbl, From, WorkerSet, H, T>
+where
+    T: TSession<IO> + HasProgress,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, To, DelegatedEp, T> HasProgress
+    for TDelegate<IO, Lbl, From, To, DelegatedEp, T>
+where
+    T: TSession<IO> + HasProgress,
+{
+}
+
+// The closed pair must not interact again until a `TConnect` reopens it
+impl<IO, Lbl: types::ProtocolLabel, A, B, Cont> HasProgress for TDisconnect<IO, Lbl, A, B, Cont> where
+    Cont: TSession<IO> + HasProgress + PairUnused<A, B>
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, A, B, Cont> HasProgress for TConnect<IO, Lbl, A, B, Cont> where
+    Cont: TSession<IO> + HasProgress
+{
+}
+
+// The role that left must not appear in the rest of the protocol
+impl<IO, Lbl: types::ProtocolLabel, R, Cont> HasProgress for TEndFor<IO, Lbl, R, Cont>
+where
+    Cont: TSession<IO> + ContainsRole<R>,
+    Cont: ProgressEndForCase<R, <Cont as ContainsRole<R>>::Output>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, L, R> HasProgress for TChoice<IO, Lbl, L, R>
+where
+    L: TSession<IO> + HasProgress,
+    R: TSession<IO> + HasProgress,
+    Cons<L, Cons<R, Nil>>: SingleChooser<Lbl>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Branches> HasProgress for TChoiceN<IO, Lbl, Branches>
+where
+    Branches: SessionList<IO> + IsEmpty,
+    Branches: ProgressChoiceCase<Lbl, <Branches as IsEmpty>::Output>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Chooser, Branches> HasProgress
+    for TSelect<IO, Lbl, Chooser, Branches>
+where
+    Branches: SessionList<IO> + IsEmpty,
+    Branches: ProgressChoiceCase<Lbl, <Branches as IsEmpty>::Output>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Offeree, Branches> HasProgress
+    for TOffer<IO, Lbl, Offeree, Branches>
+where
+    Branches: SessionList<IO> + IsEmpty,
+    Branches: ProgressChoiceCase<Lbl, <Branches as IsEmpty>::Output>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, High, Low> HasProgress for TPriorityChoice<IO, Lbl, High, Low>
+where
+    High: TSession<IO> + HasProgress,
+    Low: TSession<IO> + HasProgress,
+{
+}
+
+impl<IO, Lbl, WeightL, L, WeightR, R> HasProgress for TProb<IO, Lbl, WeightL, L, WeightR, R>
+where
+    Lbl: types::ProtocolLabel,
+    WeightL: types::WeightMarker,
+    WeightR: types::WeightMarker,
+    L: TSession<IO> + HasProgress,
+    R: TSession<IO> + HasProgress,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, L, R, IsDisjoint> HasProgress
+    for TPar<IO, Lbl, L, R, IsDisjoint>
+where
+    L: TSession<IO> + HasProgress,
+    R: TSession<IO> + HasProgress,
+{
+}
+
+// A parallel composition without branches is finished, not stuck
+impl<IO, Lbl: types::ProtocolLabel, Branches> HasProgress for TParN<IO, Lbl, Branches> where
+    Branches: SessionList<IO> + HasProgress
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Steps> HasProgress for TShuffle<IO, Lbl, Steps> where
+    Steps: SessionList<IO> + HasProgress
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, S> HasProgress for TRec<IO, Lbl, S> where
+    S: TSession<IO> + HasProgress
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Var, Body> HasProgress for TRecX<IO, Lbl, Var, Body> where
+    Body: TSession<IO> + HasProgress
+{
+}
+
+impl<Var> HasProgress for TVar<Var> {}
+
+impl<IO, Lbl, Dur, Body, OnTimeout> HasProgress for TTimeout<IO, Lbl, Dur, Body, OnTimeout>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    Body: TSession<IO> + HasProgress,
+    OnTimeout: TSession<IO> + HasProgress,
+{
+}
+
+impl<IO, Lbl, R, Dur, T> HasProgress for TDelay<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: TSession<IO> + HasProgress,
+{
+}
+
+impl<IO, Lbl, R, Dur, T> HasProgress for TDeadline<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: TSession<IO> + HasProgress,
+{
+}
+
+impl<IO, Lbl, Scope, Interruptor, Handler> HasProgress
+    for TInterrupt<IO, Lbl, Scope, Interruptor, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Scope: TSession<IO> + HasProgress,
+    Handler: TSession<IO> + HasProgress,
+{
+}
+
+impl<IO, Lbl, Decider, Body, Cont> HasProgress for TOptional<IO, Lbl, Decider, Body, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Body: TSession<IO> + HasProgress,
+    Cont: TSession<IO> + HasProgress,
+{
+}
+
+impl<Pred, Inner: HasProgress> HasProgress for TRefine<Pred, Inner> {}
+
+impl<Meta, Inner: HasProgress> HasProgress for TAnnotate<Meta, Inner> {}
+
+// Raising a failure hands control to the enclosing handler
+impl<IO, Lbl: types::ProtocolLabel, R, Err> HasProgress for TThrow<IO, Lbl, R, Err> {}
+
+impl<IO, Lbl, Body, Handler> HasProgress for TCatch<IO, Lbl, Body, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Body: TSession<IO> + HasProgress,
+    Handler: TSession<IO> + HasProgress,
+{
+}
+
+impl HasProgress for Nil {}
+
+impl<H, T> HasProgress for Cons<H, T>
+where
+    H: HasProgress,
+    T: HasProgress,
+{
+}
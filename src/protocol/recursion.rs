@@ -0,0 +1,458 @@
+//! # Recursion Variables and Checks
+//!
+//! This module supports μ-recursion in global protocols: `TRecX` binds a
+//! recursion variable and `TVar` jumps back to it.
+//!
+//! Key components:
+//!
+//! - `VarEq`: Type-level equality between recursion variables
+//! - `BindsVar`: Checks whether a list of bound variables contains a variable
+//! - `Guarded`: A loop body performs an action before it can jump back
+//! - `WellScoped`: Every `TVar` refers to an enclosing `TRecX`
+//!
+//! Unguarded bodies such as `TRecX<_, _, V, TVar<V>>` would loop without ever
+//! communicating, and unbound variables have nowhere to jump to; both are
+//! rejected at compile time.
+
+use super::base::{Cons, Nil};
+use super::global::*;
+use crate::types;
+
+/// Type-level equality between recursion variables.
+///
+/// Implemented by the user for every pair of variables in use, like `RoleEq`
+/// for roles.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Outer;
+/// struct Inner;
+/// impl VarEq<Outer> for Outer { type Output = True; }
+/// impl VarEq<Inner> for Outer { type Output = False; }
+/// impl VarEq<Outer> for Inner { type Output = False; }
+/// impl VarEq<Inner> for Inner { type Output = True; }
+/// ```
+pub trait VarEq<V> {
+    type Output: types::Bool;
+}
+
+/// Checks whether a type-level list of bound variables contains `V`.
+pub trait BindsVar<V> {
+    type Output: types::Bool;
+}
+
+impl<V> BindsVar<V> for Nil {
+    type Output = types::False;
+}
+
+impl<V, H, T> BindsVar<V> for Cons<H, T>
+where
+    H: VarEq<V>,
+    T: BindsVar<V>,
+    <H as VarEq<V>>::Output: types::BoolOr<<T as BindsVar<V>>::Output>,
+{
+    type Output = types::Or<<H as VarEq<V>>::Output, <T as BindsVar<V>>::Output>;
+}
+
+/// A protocol that cannot reach a `TVar` without performing an action first.
+///
+/// Required of every `TRecX` body. `TVar` itself is not guarded; every other
+/// combinator is guarded when all of its immediate continuations are.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` can jump back to its loop before any role acts",
+    label = "unguarded recursion",
+    note = "start every path of a loop body with an interaction before its `TVar`"
+)]
+pub trait Guarded {}
+
+impl<IO, Lbl> Guarded for TEnd<IO, Lbl> {}
+
+// A throw leaves the loop, so it can never reach a variable
+impl<IO, Lbl: types::ProtocolLabel, R, Err> Guarded for TThrow<IO, Lbl, R, Err> {}
+
+// Interactions are actions, so whatever follows them is guarded
+impl<IO, Lbl: types::ProtocolLabel, R, H, T: TSession<IO>> Guarded for TInteract<IO, Lbl, R, H, T> {}
+
+impl<IO, Lbl: types::ProtocolLabel, From, To, H, T: TSession<IO>> Guarded
+    for TMsg<IO, Lbl, From, To, H, T>
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, ToSet, H, T: TSession<IO>> Guarded
+    for TBroadcast<IO, Lbl, From, ToSet, H, T>
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, WorkerSet, H, T: TSession<IO>> Guarded
+    for TAnycast<IO, Lbl, From, WorkerSet, H, T>
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, To, DelegatedEp, T: TSession<IO>> Guarded
+    for TDelegate<IO, Lbl, From, To, DelegatedEp, T>
+{
+}
+
+// Closing a channel is not itself a communication
+impl<IO, Lbl: types::ProtocolLabel, A, B, Cont> Guarded for TDisconnect<IO, Lbl, A, B, Cont> where
+    Cont: TSession<IO> + Guarded
+{
+}
+
+// Opening a channel is not itself a communication
+impl<IO, Lbl: types::ProtocolLabel, A, B, Cont> Guarded for TConnect<IO, Lbl, A, B, Cont> where
+    Cont: TSession<IO> + Guarded
+{
+}
+
+// A role leaving is not itself a communication
+impl<IO, Lbl: types::ProtocolLabel, R, Cont> Guarded for TEndFor<IO, Lbl, R, Cont> where
+    Cont: TSession<IO> + Guarded
+{
+}
+
+// Directed choices communicate the picked branch, which is itself an action
+impl<IO, Lbl: types::ProtocolLabel, Chooser, Branches: SessionList<IO>> Guarded
+    for TSelect<IO, Lbl, Chooser, Branches>
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Offeree, Branches: SessionList<IO>> Guarded
+    for TOffer<IO, Lbl, Offeree, Branches>
+{
+}
+
+// Deciding whether to run the optional step is communicated as well
+impl<IO, Lbl: types::ProtocolLabel, Decider, Body: TSession<IO>, Cont: TSession<IO>> Guarded
+    for TOptional<IO, Lbl, Decider, Body, Cont>
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, L, R> Guarded for TChoice<IO, Lbl, L, R>
+where
+    L: TSession<IO> + Guarded,
+    R: TSession<IO> + Guarded,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Branches> Guarded for TChoiceN<IO, Lbl, Branches> where
+    Branches: SessionList<IO> + Guarded
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, High, Low> Guarded for TPriorityChoice<IO, Lbl, High, Low>
+where
+    High: TSession<IO> + Guarded,
+    Low: TSession<IO> + Guarded,
+{
+}
+
+impl<IO, Lbl, WeightL, L, WeightR, R> Guarded for TProb<IO, Lbl, WeightL, L, WeightR, R>
+where
+    Lbl: types::ProtocolLabel,
+    WeightL: types::WeightMarker,
+    WeightR: types::WeightMarker,
+    L: TSession<IO> + Guarded,
+    R: TSession<IO> + Guarded,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, L, R, IsDisjoint> Guarded for TPar<IO, Lbl, L, R, IsDisjoint>
+where
+    L: TSession<IO> + Guarded,
+    R: TSession<IO> + Guarded,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Branches> Guarded for TParN<IO, Lbl, Branches> where
+    Branches: SessionList<IO> + Guarded
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Steps> Guarded for TShuffle<IO, Lbl, Steps> where
+    Steps: SessionList<IO> + Guarded
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, S> Guarded for TRec<IO, Lbl, S> where S: TSession<IO> + Guarded {}
+
+// A timer firing is not a communication, so both sides must be guarded
+impl<IO, Lbl, Dur, Body, OnTimeout> Guarded for TTimeout<IO, Lbl, Dur, Body, OnTimeout>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    Body: TSession<IO> + Guarded,
+    OnTimeout: TSession<IO> + Guarded,
+{
+}
+
+// Waiting is not a communication either
+impl<IO, Lbl, R, Dur, T> Guarded for TDelay<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: TSession<IO> + Guarded,
+{
+}
+
+impl<IO, Lbl, R, Dur, T> Guarded for TDeadline<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: TSession<IO> + Guarded,
+{
+}
+
+// The handler only runs after the interrupt message, which guards it
+impl<IO, Lbl, Scope, Interruptor, Handler> Guarded
+    for TInterrupt<IO, Lbl, Scope, Interruptor, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Scope: TSession<IO> + Guarded,
+    Handler: TSession<IO>,
+{
+}
+
+impl<Pred, Inner: Guarded> Guarded for TRefine<Pred, Inner> {}
+
+impl<Meta, Inner: Guarded> Guarded for TAnnotate<Meta, Inner> {}
+
+impl<IO, Lbl, Body, Handler> Guarded for TCatch<IO, Lbl, Body, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Body: TSession<IO> + Guarded,
+    Handler: TSession<IO> + Guarded,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Var, Body> Guarded for TRecX<IO, Lbl, Var, Body> where
+    Body: TSession<IO> + Guarded
+{
+}
+
+// Branch lists are guarded when every branch is
+impl Guarded for Nil {}
+
+impl<H: Guarded, T: Guarded> Guarded for Cons<H, T> {}
+
+/// A protocol whose every `TVar` refers to an enclosing `TRecX`.
+///
+/// - `Bound`: Type-level list of the variables bound so far. Check a whole
+///   protocol with `WellScoped<Nil>`.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Loop;
+/// impl VarEq<Loop> for Loop { type Output = True; }
+///
+/// type Ping = TRecX<Http, EmptyLabel, Loop, TInteract<Http, EmptyLabel, TClient, Message, TVar<Loop>>>;
+/// fn assert_closed<G: WellScoped<Nil>>() {}
+/// assert_closed::<Ping>();
+/// ```
+pub trait WellScoped<Bound> {}
+
+impl<IO, Lbl, Bound> WellScoped<Bound> for TEnd<IO, Lbl> {}
+
+impl<IO, Lbl: types::ProtocolLabel, R, Err, Bound> WellScoped<Bound> for TThrow<IO, Lbl, R, Err> {}
+
+impl<IO, Lbl: types::ProtocolLabel, R, H, T, Bound> WellScoped<Bound>
+    for TInteract<IO, Lbl, R, H, T>
+where
+    T: TSession<IO> + WellScoped<Bound>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, To, H, T, Bound> WellScoped<Bound>
+    for TMsg<IO, Lbl, From, To, H, T>
+where
+    T: TSession<IO> + WellScoped<Bound>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, ToSet, H, T, Bound> WellScoped<Bound>
+    for TBroadcast<IO, Lbl, From, ToSet, H, T>
+where
+    T: TSession<IO> + WellScoped<Bound>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, WorkerSet, H, T, Bound> WellScoped<Bound>
+    for TAnycast<IO, Lbl, From, WorkerSet, H, T>
+where
+    T: TSession<IO> + WellScoped<Bound>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, To, DelegatedEp, T, Bound> WellScoped<Bound>
+    for TDelegate<IO, Lbl, From, To, DelegatedEp, T>
+where
+    T: TSession<IO> + WellScoped<Bound>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, A, B, Cont, Bound> WellScoped<Bound>
+    for TDisconnect<IO, Lbl, A, B, Cont>
+where
+    Cont: TSession<IO> + WellScoped<Bound>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, A, B, Cont, Bound> WellScoped<Bound>
+    for TConnect<IO, Lbl, A, B, Cont>
+where
+    Cont: TSession<IO> + WellScoped<Bound>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R, Cont, Bound> WellScoped<Bound> for TEndFor<IO, Lbl, R, Cont> where
+    Cont: TSession<IO> + WellScoped<Bound>
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, L, R, Bound> WellScoped<Bound> for TChoice<IO, Lbl, L, R>
+where
+    L: TSession<IO> + WellScoped<Bound>,
+    R: TSession<IO> + WellScoped<Bound>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Branches, Bound> WellScoped<Bound>
+    for TChoiceN<IO, Lbl, Branches>
+where
+    Branches: SessionList<IO> + WellScoped<Bound>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Chooser, Branches, Bound> WellScoped<Bound>
+    for TSelect<IO, Lbl, Chooser, Branches>
+where
+    Branches: SessionList<IO> + WellScoped<Bound>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Offeree, Branches, Bound> WellScoped<Bound>
+    for TOffer<IO, Lbl, Offeree, Branches>
+where
+    Branches: SessionList<IO> + WellScoped<Bound>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, High, Low, Bound> WellScoped<Bound>
+    for TPriorityChoice<IO, Lbl, High, Low>
+where
+    High: TSession<IO> + WellScoped<Bound>,
+    Low: TSession<IO> + WellScoped<Bound>,
+{
+}
+
+impl<IO, Lbl, WeightL, L, WeightR, R, Bound> WellScoped<Bound>
+    for TProb<IO, Lbl, WeightL, L, WeightR, R>
+where
+    Lbl: types::ProtocolLabel,
+    WeightL: types::WeightMarker,
+    WeightR: types::WeightMarker,
+    L: TSession<IO> + WellScoped<Bound>,
+    R: TSession<IO> + WellScoped<Bound>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, L, R, IsDisjoint, Bound> WellScoped<Bound>
+    for TPar<IO, Lbl, L, R, IsDisjoint>
+where
+    L: TSession<IO> + WellScoped<Bound>,
+    R: TSession<IO> + WellScoped<Bound>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Branches, Bound> WellScoped<Bound> for TParN<IO, Lbl, Branches> where
+    Branches: SessionList<IO> + WellScoped<Bound>
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Steps, Bound> WellScoped<Bound> for TShuffle<IO, Lbl, Steps> where
+    Steps: SessionList<IO> + WellScoped<Bound>
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, S, Bound> WellScoped<Bound> for TRec<IO, Lbl, S> where
+    S: TSession<IO> + WellScoped<Bound>
+{
+}
+
+impl<IO, Lbl, Dur, Body, OnTimeout, Bound> WellScoped<Bound>
+    for TTimeout<IO, Lbl, Dur, Body, OnTimeout>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    Body: TSession<IO> + WellScoped<Bound>,
+    OnTimeout: TSession<IO> + WellScoped<Bound>,
+{
+}
+
+impl<IO, Lbl, R, Dur, T, Bound> WellScoped<Bound> for TDelay<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: TSession<IO> + WellScoped<Bound>,
+{
+}
+
+impl<IO, Lbl, R, Dur, T, Bound> WellScoped<Bound> for TDeadline<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: TSession<IO> + WellScoped<Bound>,
+{
+}
+
+impl<IO, Lbl, Scope, Interruptor, Handler, Bound> WellScoped<Bound>
+    for TInterrupt<IO, Lbl, Scope, Interruptor, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Scope: TSession<IO> + WellScoped<Bound>,
+    Handler: TSession<IO> + WellScoped<Bound>,
+{
+}
+
+impl<IO, Lbl, Decider, Body, Cont, Bound> WellScoped<Bound>
+    for TOptional<IO, Lbl, Decider, Body, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Body: TSession<IO> + WellScoped<Bound>,
+    Cont: TSession<IO> + WellScoped<Bound>,
+{
+}
+
+impl<Pred, Inner: WellScoped<Bound>, Bound> WellScoped<Bound> for TRefine<Pred, Inner> {}
+
+impl<Meta, Inner: WellScoped<Bound>, Bound> WellScoped<Bound> for TAnnotate<Meta, Inner> {}
+
+impl<IO, Lbl, Body, Handler, Bound> WellScoped<Bound> for TCatch<IO, Lbl, Body, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Body: TSession<IO> + WellScoped<Bound>,
+    Handler: TSession<IO> + WellScoped<Bound>,
+{
+}
+
+// A binder brings its variable into scope for the body
+impl<IO, Lbl: types::ProtocolLabel, Var, Body, Bound> WellScoped<Bound>
+    for TRecX<IO, Lbl, Var, Body>
+where
+    Body: TSession<IO> + WellScoped<Cons<Var, Bound>>,
+{
+}
+
+// A variable is well-scoped only if some enclosing binder bound it
+impl<Var, Bound> WellScoped<Bound> for TVar<Var> where Bound: BindsVar<Var, Output = types::True> {}
+
+impl<Bound> WellScoped<Bound> for Nil {}
+
+impl<H, T, Bound> WellScoped<Bound> for Cons<H, T>
+where
+    H: WellScoped<Bound>,
+    T: WellScoped<Bound>,
+{
+}
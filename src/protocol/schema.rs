@@ -0,0 +1,410 @@
+//! # Payload Schema Evolution
+//!
+//! This module ties payload types to the labels they travel under and checks
+//! that two versions of a protocol evolved their payloads compatibly.
+//!
+//! Key components:
+//!
+//! - `SchemaOf`: Declares the field list of a payload type at a given label
+//! - `Required` / `Optional`: Field markers used in schema field lists
+//! - `EvolvesFrom`: Field-list compatibility between two schema versions
+//! - `PayloadCompatible`: Walks two protocol versions in lockstep and checks
+//!   every payload at matching labels
+//!
+//! The compatibility rule mirrors what a serde-based wire format tolerates:
+//! the new schema keeps all old fields, in the same order, and may only append
+//! optional fields. Anything else (removing, reordering, or adding a required
+//! field) is rejected at compile time.
+//!
+//! `PayloadCompatible` keeps the structure of the protocol fixed. To also let
+//! choices gain or lose branches between versions, use `GlobalSubtype` with
+//! `EvolvedPayloads`.
+
+use super::base::{Cons, Nil};
+use super::global::*;
+use crate::types;
+use core::marker::PhantomData;
+
+/// Declares the schema of a payload type when it is sent under label `Lbl`.
+///
+/// - `Fields`: Type-level list of `Required<F>` / `Optional<F>` field markers.
+///
+/// The label parameter lets the same Rust type carry different schemas at
+/// different points of a protocol.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Login; impl ProtocolLabel for Login {}
+/// struct User; struct Password; struct Otp;
+///
+/// struct Credentials;
+/// impl SchemaOf<Login> for Credentials {
+///     type Fields = tlist!(Required<User>, Required<Password>, Optional<Otp>);
+/// }
+/// ```
+pub trait SchemaOf<Lbl: types::ProtocolLabel> {
+    type Fields;
+}
+
+/// Field marker for a field that must always be present.
+pub struct Required<F>(PhantomData<F>);
+
+/// Field marker for a field that may be absent on the wire.
+pub struct Optional<F>(PhantomData<F>);
+
+/// Trait to check that every field in a type-level list is `Optional`.
+pub trait AllOptional {}
+
+impl AllOptional for Nil {}
+impl<F, T: AllOptional> AllOptional for Cons<Optional<F>, T> {}
+
+/// Field-list compatibility: `Self` is a compatible evolution of `Old`.
+///
+/// Holds when `Old` is a prefix of `Self` and every field after that prefix is
+/// `Optional`.
+pub trait EvolvesFrom<Old> {}
+
+// Base case: once the old fields are exhausted, only optional fields may remain
+impl<New: AllOptional> EvolvesFrom<Nil> for New {}
+
+// Recursive case: heads must be the same field marker
+impl<H, NT, OT> EvolvesFrom<Cons<H, OT>> for Cons<H, NT> where NT: EvolvesFrom<OT> {}
+
+/// Payload compatibility between two versions of a global protocol.
+///
+/// `Self` is the new protocol, `Old` the previous one. Both must have the same
+/// structure and labels; at every `TInteract` the new payload's schema must
+/// evolve from the old payload's schema at that label.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Login; impl ProtocolLabel for Login {}
+/// struct User; struct Otp;
+///
+/// struct CredentialsV1;
+/// impl SchemaOf<Login> for CredentialsV1 { type Fields = tlist!(Required<User>); }
+/// struct CredentialsV2;
+/// impl SchemaOf<Login> for CredentialsV2 {
+///     type Fields = tlist!(Required<User>, Optional<Otp>);
+/// }
+///
+/// type V1 = TInteract<Http, Login, TClient, CredentialsV1, TEnd<Http>>;
+/// type V2 = TInteract<Http, Login, TClient, CredentialsV2, TEnd<Http>>;
+/// assert_payload_compatible!(V2, V1);
+/// ```
+pub trait PayloadCompatible<Old> {}
+
+impl<IO, Lbl, OldLbl> PayloadCompatible<TEnd<IO, OldLbl>> for TEnd<IO, Lbl> {}
+
+impl<IO, Lbl, R, H, T, OldH, OldT> PayloadCompatible<TInteract<IO, Lbl, R, OldH, OldT>>
+    for TInteract<IO, Lbl, R, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: TSession<IO> + PayloadCompatible<OldT>,
+    OldT: TSession<IO>,
+    H: SchemaOf<Lbl>,
+    OldH: SchemaOf<Lbl>,
+    <H as SchemaOf<Lbl>>::Fields: EvolvesFrom<<OldH as SchemaOf<Lbl>>::Fields>,
+{
+}
+
+impl<IO, Lbl, From, To, H, T, OldH, OldT> PayloadCompatible<TMsg<IO, Lbl, From, To, OldH, OldT>>
+    for TMsg<IO, Lbl, From, To, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: TSession<IO> + PayloadCompatible<OldT>,
+    OldT: TSession<IO>,
+    H: SchemaOf<Lbl>,
+    OldH: SchemaOf<Lbl>,
+    <H as SchemaOf<Lbl>>::Fields: EvolvesFrom<<OldH as SchemaOf<Lbl>>::Fields>,
+{
+}
+
+impl<IO, Lbl, From, ToSet, H, T, OldH, OldT>
+    PayloadCompatible<TBroadcast<IO, Lbl, From, ToSet, OldH, OldT>>
+    for TBroadcast<IO, Lbl, From, ToSet, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: TSession<IO> + PayloadCompatible<OldT>,
+    OldT: TSession<IO>,
+    H: SchemaOf<Lbl>,
+    OldH: SchemaOf<Lbl>,
+    <H as SchemaOf<Lbl>>::Fields: EvolvesFrom<<OldH as SchemaOf<Lbl>>::Fields>,
+{
+}
+
+impl<IO, Lbl, From, WorkerSet, H, T, OldH, OldT>
+    PayloadCompatible<TAnycast<IO, Lbl, From, WorkerSet, OldH, OldT>>
+    for TAnycast<IO, Lbl, From, WorkerSet, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: TSession<IO> + PayloadCompatible<OldT>,
+    OldT: TSession<IO>,
+    H: SchemaOf<Lbl>,
+    OldH: SchemaOf<Lbl>,
+    <H as SchemaOf<Lbl>>::Fields: EvolvesFrom<<OldH as SchemaOf<Lbl>>::Fields>,
+{
+}
+
+// A delegated endpoint is a session type, not a serialized payload, so it must
+// stay the same between versions
+impl<IO, Lbl, From, To, DelegatedEp, T, OldT>
+    PayloadCompatible<TDelegate<IO, Lbl, From, To, DelegatedEp, OldT>>
+    for TDelegate<IO, Lbl, From, To, DelegatedEp, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: TSession<IO> + PayloadCompatible<OldT>,
+    OldT: TSession<IO>,
+{
+}
+
+impl<IO, Lbl, A, B, Cont, OldCont> PayloadCompatible<TDisconnect<IO, Lbl, A, B, OldCont>>
+    for TDisconnect<IO, Lbl, A, B, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Cont: TSession<IO> + PayloadCompatible<OldCont>,
+    OldCont: TSession<IO>,
+{
+}
+
+impl<IO, Lbl, A, B, Cont, OldCont> PayloadCompatible<TConnect<IO, Lbl, A, B, OldCont>>
+    for TConnect<IO, Lbl, A, B, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Cont: TSession<IO> + PayloadCompatible<OldCont>,
+    OldCont: TSession<IO>,
+{
+}
+
+impl<IO, Lbl, R, Cont, OldCont> PayloadCompatible<TEndFor<IO, Lbl, R, OldCont>>
+    for TEndFor<IO, Lbl, R, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Cont: TSession<IO> + PayloadCompatible<OldCont>,
+    OldCont: TSession<IO>,
+{
+}
+
+impl<IO, Lbl, L, R, OldL, OldR> PayloadCompatible<TChoice<IO, Lbl, OldL, OldR>>
+    for TChoice<IO, Lbl, L, R>
+where
+    Lbl: types::ProtocolLabel,
+    L: TSession<IO> + PayloadCompatible<OldL>,
+    R: TSession<IO> + PayloadCompatible<OldR>,
+    OldL: TSession<IO>,
+    OldR: TSession<IO>,
+{
+}
+
+impl<IO, Lbl, Branches, OldBranches> PayloadCompatible<TChoiceN<IO, Lbl, OldBranches>>
+    for TChoiceN<IO, Lbl, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: SessionList<IO> + PayloadCompatible<OldBranches>,
+    OldBranches: SessionList<IO>,
+{
+}
+
+impl<IO, Lbl, Chooser, Branches, OldBranches>
+    PayloadCompatible<TSelect<IO, Lbl, Chooser, OldBranches>>
+    for TSelect<IO, Lbl, Chooser, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: SessionList<IO> + PayloadCompatible<OldBranches>,
+    OldBranches: SessionList<IO>,
+{
+}
+
+impl<IO, Lbl, Offeree, Branches, OldBranches>
+    PayloadCompatible<TOffer<IO, Lbl, Offeree, OldBranches>> for TOffer<IO, Lbl, Offeree, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: SessionList<IO> + PayloadCompatible<OldBranches>,
+    OldBranches: SessionList<IO>,
+{
+}
+
+impl<IO, Lbl, Branches, OldBranches> PayloadCompatible<TParN<IO, Lbl, OldBranches>>
+    for TParN<IO, Lbl, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: SessionList<IO> + PayloadCompatible<OldBranches>,
+    OldBranches: SessionList<IO>,
+{
+}
+
+impl<IO, Lbl, Steps, OldSteps> PayloadCompatible<TShuffle<IO, Lbl, OldSteps>>
+    for TShuffle<IO, Lbl, Steps>
+where
+    Lbl: types::ProtocolLabel,
+    Steps: SessionList<IO> + PayloadCompatible<OldSteps>,
+    OldSteps: SessionList<IO>,
+{
+}
+
+impl<IO, Lbl, High, Low, OldHigh, OldLow>
+    PayloadCompatible<TPriorityChoice<IO, Lbl, OldHigh, OldLow>>
+    for TPriorityChoice<IO, Lbl, High, Low>
+where
+    Lbl: types::ProtocolLabel,
+    High: TSession<IO> + PayloadCompatible<OldHigh>,
+    Low: TSession<IO> + PayloadCompatible<OldLow>,
+    OldHigh: TSession<IO>,
+    OldLow: TSession<IO>,
+{
+}
+
+// Weights may be retuned freely; they carry no payload
+impl<IO, Lbl, WeightL, L, WeightR, R, OldWeightL, OldL, OldWeightR, OldR>
+    PayloadCompatible<TProb<IO, Lbl, OldWeightL, OldL, OldWeightR, OldR>>
+    for TProb<IO, Lbl, WeightL, L, WeightR, R>
+where
+    Lbl: types::ProtocolLabel,
+    WeightL: types::WeightMarker,
+    WeightR: types::WeightMarker,
+    L: TSession<IO> + PayloadCompatible<OldL>,
+    R: TSession<IO> + PayloadCompatible<OldR>,
+    OldWeightL: types::WeightMarker,
+    OldWeightR: types::WeightMarker,
+    OldL: TSession<IO>,
+    OldR: TSession<IO>,
+{
+}
+
+impl<IO, Lbl, L, R, IsDisjoint, OldL, OldR, OldDisjoint>
+    PayloadCompatible<TPar<IO, Lbl, OldL, OldR, OldDisjoint>> for TPar<IO, Lbl, L, R, IsDisjoint>
+where
+    Lbl: types::ProtocolLabel,
+    L: TSession<IO> + PayloadCompatible<OldL>,
+    R: TSession<IO> + PayloadCompatible<OldR>,
+    OldL: TSession<IO>,
+    OldR: TSession<IO>,
+{
+}
+
+impl<IO, Lbl, S, OldS> PayloadCompatible<TRec<IO, Lbl, OldS>> for TRec<IO, Lbl, S>
+where
+    Lbl: types::ProtocolLabel,
+    S: TSession<IO> + PayloadCompatible<OldS>,
+    OldS: TSession<IO>,
+{
+}
+
+impl<IO, Lbl, Dur, Body, OnTimeout, OldDur, OldBody, OldOnTimeout>
+    PayloadCompatible<TTimeout<IO, Lbl, OldDur, OldBody, OldOnTimeout>>
+    for TTimeout<IO, Lbl, Dur, Body, OnTimeout>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    OldDur: types::DurationMarker,
+    Body: TSession<IO> + PayloadCompatible<OldBody>,
+    OnTimeout: TSession<IO> + PayloadCompatible<OldOnTimeout>,
+    OldBody: TSession<IO>,
+    OldOnTimeout: TSession<IO>,
+{
+}
+
+impl<IO, Lbl, R, Dur, T, OldDur, OldT> PayloadCompatible<TDelay<IO, Lbl, R, OldDur, OldT>>
+    for TDelay<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    OldDur: types::DurationMarker,
+    T: TSession<IO> + PayloadCompatible<OldT>,
+    OldT: TSession<IO>,
+{
+}
+
+impl<IO, Lbl, R, Dur, T, OldDur, OldT> PayloadCompatible<TDeadline<IO, Lbl, R, OldDur, OldT>>
+    for TDeadline<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    OldDur: types::DurationMarker,
+    T: TSession<IO> + PayloadCompatible<OldT>,
+    OldT: TSession<IO>,
+{
+}
+
+impl<IO, Lbl, Scope, Interruptor, Handler, OldScope, OldHandler>
+    PayloadCompatible<TInterrupt<IO, Lbl, OldScope, Interruptor, OldHandler>>
+    for TInterrupt<IO, Lbl, Scope, Interruptor, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Scope: TSession<IO> + PayloadCompatible<OldScope>,
+    Handler: TSession<IO> + PayloadCompatible<OldHandler>,
+    OldScope: TSession<IO>,
+    OldHandler: TSession<IO>,
+{
+}
+
+impl<IO, Lbl, Decider, Body, Cont, OldBody, OldCont>
+    PayloadCompatible<TOptional<IO, Lbl, Decider, OldBody, OldCont>>
+    for TOptional<IO, Lbl, Decider, Body, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Body: TSession<IO> + PayloadCompatible<OldBody>,
+    Cont: TSession<IO> + PayloadCompatible<OldCont>,
+    OldBody: TSession<IO>,
+    OldCont: TSession<IO>,
+{
+}
+
+// Error payloads evolve like message payloads
+impl<IO, Lbl, R, Err, OldErr> PayloadCompatible<TThrow<IO, Lbl, R, OldErr>>
+    for TThrow<IO, Lbl, R, Err>
+where
+    Lbl: types::ProtocolLabel,
+    Err: SchemaOf<Lbl>,
+    OldErr: SchemaOf<Lbl>,
+    <Err as SchemaOf<Lbl>>::Fields: EvolvesFrom<<OldErr as SchemaOf<Lbl>>::Fields>,
+{
+}
+
+impl<IO, Lbl, Body, Handler, OldBody, OldHandler>
+    PayloadCompatible<TCatch<IO, Lbl, OldBody, OldHandler>> for TCatch<IO, Lbl, Body, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Body: TSession<IO> + PayloadCompatible<OldBody>,
+    Handler: TSession<IO> + PayloadCompatible<OldHandler>,
+    OldBody: TSession<IO>,
+    OldHandler: TSession<IO>,
+{
+}
+
+// Refinements must be kept as-is; only the refined fragment may evolve
+impl<Pred, Inner, OldInner> PayloadCompatible<TRefine<Pred, OldInner>> for TRefine<Pred, Inner> where
+    Inner: PayloadCompatible<OldInner>
+{
+}
+
+// Annotations carry no payload, so they may change freely between versions
+impl<Meta, Inner, OldMeta, OldInner> PayloadCompatible<TAnnotate<OldMeta, OldInner>>
+    for TAnnotate<Meta, Inner>
+where
+    Inner: PayloadCompatible<OldInner>,
+{
+}
+
+impl<IO, Lbl, Var, Body, OldBody> PayloadCompatible<TRecX<IO, Lbl, Var, OldBody>>
+    for TRecX<IO, Lbl, Var, Body>
+where
+    Lbl: types::ProtocolLabel,
+    Body: TSession<IO> + PayloadCompatible<OldBody>,
+    OldBody: TSession<IO>,
+{
+}
+
+impl<Var> PayloadCompatible<TVar<Var>> for TVar<Var> {}
+
+// Branch lists of n-ary combinators are compared element by element
+impl PayloadCompatible<Nil> for Nil {}
+
+impl<H, T, OldH, OldT> PayloadCompatible<Cons<OldH, OldT>> for Cons<H, T>
+where
+    H: PayloadCompatible<OldH>,
+    T: PayloadCompatible<OldT>,
+{
+}
@@ -0,0 +1,170 @@
+//! # Wire-Level Separability
+//!
+//! This module checks that the branches of a parallel composition exchange
+//! distinct message types. `Disjoint` only compares roles: two branches whose
+//! roles share infrastructure, such as a broker or a connection, can still
+//! send the same message type, and a frame of that type cannot be routed to
+//! its branch on the wire.
+//!
+//! Key components:
+//!
+//! - `MessageEq`: Type-level equality for message types
+//! - `DisjointMessages`: The branches of a `TPar` or `TParN` share no message type
+//! - `MessagesDisjoint`: No message type of a list appears in another list
+//! - `BranchMessagesDisjoint`: The branches of a list pairwise share no message type
+//! - `LacksMessage` / `LacksMessageCase`: A list does not contain a given message type
+//! - `SharedMessage` / `SeparabilityFailure`: Failure markers naming the composition and the message
+//!
+//! Message types are collected with `MessagesOf` and compared with
+//! `MessageEq`, which is implemented for the crate's message markers. Check a
+//! composition with `assert_disjoint!(messages ...)`.
+
+use super::base::{Cons, Nil};
+use super::global::*;
+use crate::introspection::{MessagesOf, MessagesOfEach};
+use crate::types;
+use core::marker::PhantomData;
+
+/// Type-level equality for message types.
+///
+/// Implement it for every ordered pair of payload types that travel in
+/// parallel branches checked by [`DisjointMessages`].
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Order; struct Invoice;
+/// impl MessageEq<Order> for Order { type Output = True; }
+/// impl MessageEq<Invoice> for Order { type Output = False; }
+/// impl MessageEq<Order> for Invoice { type Output = False; }
+/// impl MessageEq<Invoice> for Invoice { type Output = True; }
+/// ```
+#[diagnostic::on_unimplemented(
+    message = "no `MessageEq` impl compares the message types `{Self}` and `{M}`",
+    label = "cannot tell `{Self}` and `{M}` apart",
+    note = "implement `MessageEq` for every ordered pair of message types in the parallel branches"
+)]
+pub trait MessageEq<M> {
+    type Output: types::Bool;
+}
+
+// A message marker equals itself and differs from the other markers listed
+macro_rules! message_eq {
+    ($M:ident: $($Other:ident),*) => {
+        impl MessageEq<types::$M> for types::$M {
+            type Output = types::True;
+        }
+        $(
+            impl MessageEq<types::$Other> for types::$M {
+                type Output = types::False;
+            }
+        )*
+    };
+}
+
+message_eq!(Message: Response, Publish, Notify, Subscribe);
+message_eq!(Response: Message, Publish, Notify, Subscribe);
+message_eq!(Publish: Message, Response, Notify, Subscribe);
+message_eq!(Notify: Message, Response, Publish, Subscribe);
+message_eq!(Subscribe: Message, Response, Publish, Notify);
+
+/// Failure marker: two branches of the parallel composition labelled `Par`
+/// both send the message type `M`.
+pub struct SharedMessage<Par, M>(PhantomData<(Par, M)>);
+
+/// Never implemented: a bound `Marker: SeparabilityFailure` fails and names
+/// the message type found by [`DisjointMessages`].
+#[diagnostic::on_unimplemented(
+    message = "parallel branches cannot be told apart on the wire: `{Self}`",
+    label = "two branches send the same message type",
+    note = "give each branch its own payload types"
+)]
+pub trait SeparabilityFailure {}
+
+/// No message type of a type-level list is `M`, already sent by another
+/// branch of the composition labelled `Par`.
+pub trait LacksMessage<Par, M> {}
+
+impl<Par, M> LacksMessage<Par, M> for Nil {}
+
+impl<H, T, Par, M> LacksMessage<Par, M> for Cons<H, T>
+where
+    H: MessageEq<M>,
+    (): LacksMessageCase<Par, M, <H as MessageEq<M>>::Output>,
+    T: LacksMessage<Par, M>,
+{
+}
+
+/// Helper trait for `LacksMessage`, dispatching on whether a message type
+/// equals `M`.
+pub trait LacksMessageCase<Par, M, Same> {}
+
+impl<Par, M> LacksMessageCase<Par, M, types::False> for () {}
+
+impl<Par, M> LacksMessageCase<Par, M, types::True> for () where
+    SharedMessage<Par, M>: SeparabilityFailure
+{
+}
+
+/// No message type of `Self` appears in `Other`.
+pub trait MessagesDisjoint<Par, Other> {}
+
+impl<Par, Other> MessagesDisjoint<Par, Other> for Nil {}
+
+impl<H, T, Par, Other> MessagesDisjoint<Par, Other> for Cons<H, T>
+where
+    Other: LacksMessage<Par, H>,
+    T: MessagesDisjoint<Par, Other>,
+{
+}
+
+/// The protocols of a type-level list pairwise share no message type.
+pub trait BranchMessagesDisjoint<Par> {}
+
+impl<Par> BranchMessagesDisjoint<Par> for Nil {}
+
+impl<H, T, Par> BranchMessagesDisjoint<Par> for Cons<H, T>
+where
+    H: MessagesOf,
+    T: MessagesOfEach + BranchMessagesDisjoint<Par>,
+    <H as MessagesOf>::Messages: MessagesDisjoint<Par, <T as MessagesOfEach>::Messages>,
+{
+}
+
+/// A parallel composition whose branches send pairwise distinct message
+/// types, so every frame can be routed to its branch by its type.
+///
+/// Message types repeated within one branch are allowed. Only the branches of
+/// the composition itself are compared.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Orders; struct Events; struct Both;
+/// impl ProtocolLabel for Orders {} impl ProtocolLabel for Events {}
+/// impl ProtocolLabel for Both {}
+///
+/// type Par = TPar<
+///     Mqtt,
+///     Both,
+///     TInteract<Mqtt, Orders, TBroker, Publish, TEnd<Mqtt>>,
+///     TInteract<Mqtt, Events, TBroker, Notify, TEnd<Mqtt>>,
+///     False,
+/// >;
+/// assert_disjoint!(messages Par);
+/// ```
+pub trait DisjointMessages {}
+
+impl<IO, Lbl: types::ProtocolLabel, L, R, IsDisjoint> DisjointMessages
+    for TPar<IO, Lbl, L, R, IsDisjoint>
+where
+    L: TSession<IO> + MessagesOf,
+    R: TSession<IO> + MessagesOf,
+    <L as MessagesOf>::Messages: MessagesDisjoint<Lbl, <R as MessagesOf>::Messages>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Branches> DisjointMessages for TParN<IO, Lbl, Branches> where
+    Branches: SessionList<IO> + BranchMessagesDisjoint<Lbl>
+{
+}
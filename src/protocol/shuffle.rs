@@ -0,0 +1,43 @@
+//! # Shuffle Checks
+//!
+//! This module checks that the steps of a `TShuffle` are pairwise independent.
+//! A runtime driving a shuffle accepts its steps in whatever order they
+//! arrive and dispatches each message to the step whose label it carries, so
+//! no two steps may start with the same label.
+//!
+//! Key components:
+//!
+//! - `DistinctFrom`: No step of a list starts with a given label
+//! - `PairwiseIndependent`: The steps of a list start with pairwise distinct labels
+
+use super::base::{Cons, Nil};
+use super::local::LabelEq;
+use super::transforms::GetProtocolLabel;
+use crate::types;
+
+/// No step of a type-level list starts with label `L`.
+pub trait DistinctFrom<L> {}
+
+impl<L> DistinctFrom<L> for Nil {}
+
+impl<H, T, L> DistinctFrom<L> for Cons<H, T>
+where
+    H: GetProtocolLabel,
+    <H as GetProtocolLabel>::Label: LabelEq<L, Output = types::False>,
+    T: DistinctFrom<L>,
+{
+}
+
+/// The steps of a type-level list start with pairwise distinct labels.
+///
+/// Required of the steps of every projected `TShuffle`.
+pub trait PairwiseIndependent {}
+
+impl PairwiseIndependent for Nil {}
+
+impl<H, T> PairwiseIndependent for Cons<H, T>
+where
+    H: GetProtocolLabel,
+    T: DistinctFrom<<H as GetProtocolLabel>::Label> + PairwiseIndependent,
+{
+}
@@ -0,0 +1,569 @@
+//! # Choice Simplification
+//!
+//! Projecting an n-ary global choice built with `tchoice!` yields a chain of
+//! binary local choices, each nested in the last branch of the one before.
+//! This module converts between that nested form and a flat form with one
+//! branch list per choice, which is far easier to read in compiler errors and
+//! to assert against.
+//!
+//! Key components:
+//!
+//! - `FlattenChoices`: Splices nested choices of the same kind into their parent
+//! - `NestChoices`: The inverse, rebuilding binary choices from flat branch lists
+//! - `MapChoices`: Structural traversal rebuilding every choice in a local session type
+//! - `MapChoicesEach`: `MapChoices` for every local session type in a type-level list
+//! - `RebuildChoice`: Rebuilds one choice from its traversed branches
+//! - `SpliceChoices`: Replaces every branch of the same kind as the parent with its branches
+//!
+//! Only choices of the same kind are spliced: an `EpSelect` inside an
+//! `EpSelect`, an `EpOffer` inside an `EpOffer` or an `EpChoiceN` inside an
+//! `EpChoiceN`. Flattening discards the labels of the spliced choices, and
+//! nesting reuses the label of the outermost choice at every level, so the
+//! two transforms are inverse only up to the labels of nested choices.
+
+use super::base::{Cons, Nil};
+use super::local::*;
+use super::normalize::{
+    ChoiceNShape, OfferShape, ParShape, SelectShape, ShapeOf, SkipShape, StepShape,
+};
+use super::utils::Concat;
+use crate::types;
+
+/// Traversal mode splicing nested choices into their parent.
+pub struct Flatten;
+
+/// Traversal mode rebuilding binary choices from flat branch lists.
+pub struct Nest;
+
+/// Rebuilds every choice in a local session type according to `Mode`.
+///
+/// Every construct other than a choice is rebuilt unchanged around its
+/// traversed continuations; choices traverse their branches and are then
+/// rebuilt with `RebuildChoice`.
+pub trait MapChoices<Mode> {
+    type Output;
+}
+
+/// `MapChoices` for every local session type in a type-level list.
+pub trait MapChoicesEach<Mode> {
+    type Output;
+}
+
+impl<Mode> MapChoicesEach<Mode> for Nil {
+    type Output = Nil;
+}
+
+impl<Mode, H: MapChoices<Mode>, T: MapChoicesEach<Mode>> MapChoicesEach<Mode> for Cons<H, T> {
+    type Output = Cons<<H as MapChoices<Mode>>::Output, <T as MapChoicesEach<Mode>>::Output>;
+}
+
+/// Rebuilds a choice from its traversed branches according to `Mode`.
+pub trait RebuildChoice<Mode, Branches> {
+    type Output;
+}
+
+/// Splices nested choices into a flat choice.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct L1; impl ProtocolLabel for L1 {}
+/// struct L2; impl ProtocolLabel for L2 {}
+/// struct L3; impl ProtocolLabel for L3 {}
+///
+/// type Done = EpEnd<Http, EmptyLabel, TClient>;
+/// type A = EpSend<Http, L1, TClient, Message, Done>;
+/// type B = EpSend<Http, L2, TClient, Message, Done>;
+/// type C = EpSend<Http, L3, TClient, Message, Done>;
+/// type Nested = EpSelect<Http, L1, TClient, tlist!(A, EpSelect<Http, L2, TClient, tlist!(B, C)>)>;
+///
+/// assert_type_eq!(
+///     <Nested as FlattenChoices>::Output,
+///     EpSelect<Http, L1, TClient, tlist!(A, B, C)>
+/// );
+/// ```
+pub trait FlattenChoices {
+    type Output;
+}
+
+impl<T: MapChoices<Flatten>> FlattenChoices for T {
+    type Output = <T as MapChoices<Flatten>>::Output;
+}
+
+/// Rebuilds binary choices from flat branch lists.
+///
+/// A choice with more than two branches becomes a binary choice between its
+/// first branch and a choice of the same kind over the remaining ones.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct L1; impl ProtocolLabel for L1 {}
+/// struct L2; impl ProtocolLabel for L2 {}
+/// struct L3; impl ProtocolLabel for L3 {}
+///
+/// type Done = EpEnd<Http, EmptyLabel, TClient>;
+/// type A = EpRecv<Http, L1, TClient, Message, Done>;
+/// type B = EpRecv<Http, L2, TClient, Message, Done>;
+/// type C = EpRecv<Http, L3, TClient, Message, Done>;
+/// type Flat = EpOffer<Http, L1, TClient, tlist!(A, B, C)>;
+///
+/// assert_type_eq!(
+///     <Flat as NestChoices>::Output,
+///     EpOffer<Http, L1, TClient, tlist!(A, EpOffer<Http, L1, TClient, tlist!(B, C)>)>
+/// );
+/// assert_type_eq!(<<Flat as NestChoices>::Output as FlattenChoices>::Output, Flat);
+/// ```
+pub trait NestChoices {
+    type Output;
+}
+
+impl<T: MapChoices<Nest>> NestChoices for T {
+    type Output = <T as MapChoices<Nest>>::Output;
+}
+
+/// Replaces every entry of a type-level list that is a choice of the kind
+/// `Outer` with its branches.
+pub trait SpliceChoices<Outer> {
+    type Output;
+}
+
+/// Helper trait for dispatching `SpliceChoices` on the shape of the head.
+pub trait SpliceChoicesCase<Outer, Shape> {
+    type Output;
+}
+
+impl<Outer> SpliceChoices<Outer> for Nil {
+    type Output = Nil;
+}
+
+impl<Outer, H: ShapeOf, T> SpliceChoices<Outer> for Cons<H, T>
+where
+    Cons<H, T>: SpliceChoicesCase<Outer, <H as ShapeOf>::Shape>,
+{
+    type Output = <Cons<H, T> as SpliceChoicesCase<Outer, <H as ShapeOf>::Shape>>::Output;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches, T> SpliceChoicesCase<SelectShape, SelectShape>
+    for Cons<EpSelect<IO, Lbl, Me, Branches>, T>
+where
+    T: SpliceChoices<SelectShape>,
+    Branches: Concat<<T as SpliceChoices<SelectShape>>::Output>,
+{
+    type Output = <Branches as Concat<<T as SpliceChoices<SelectShape>>::Output>>::Output;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches, T> SpliceChoicesCase<OfferShape, OfferShape>
+    for Cons<EpOffer<IO, Lbl, Me, Branches>, T>
+where
+    T: SpliceChoices<OfferShape>,
+    Branches: Concat<<T as SpliceChoices<OfferShape>>::Output>,
+{
+    type Output = <Branches as Concat<<T as SpliceChoices<OfferShape>>::Output>>::Output;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches, T> SpliceChoicesCase<ChoiceNShape, ChoiceNShape>
+    for Cons<EpChoiceN<IO, Lbl, Me, Branches>, T>
+where
+    T: SpliceChoices<ChoiceNShape>,
+    Branches: Concat<<T as SpliceChoices<ChoiceNShape>>::Output>,
+{
+    type Output = <Branches as Concat<<T as SpliceChoices<ChoiceNShape>>::Output>>::Output;
+}
+
+impl<Outer, H, T: SpliceChoices<Outer>> SpliceChoicesCase<Outer, StepShape> for Cons<H, T> {
+    type Output = Cons<H, <T as SpliceChoices<Outer>>::Output>;
+}
+
+impl<Outer, H, T: SpliceChoices<Outer>> SpliceChoicesCase<Outer, SkipShape> for Cons<H, T> {
+    type Output = Cons<H, <T as SpliceChoices<Outer>>::Output>;
+}
+
+impl<Outer, H, T: SpliceChoices<Outer>> SpliceChoicesCase<Outer, ParShape> for Cons<H, T> {
+    type Output = Cons<H, <T as SpliceChoices<Outer>>::Output>;
+}
+
+impl<H, T: SpliceChoices<SelectShape>> SpliceChoicesCase<SelectShape, OfferShape> for Cons<H, T> {
+    type Output = Cons<H, <T as SpliceChoices<SelectShape>>::Output>;
+}
+
+impl<H, T: SpliceChoices<SelectShape>> SpliceChoicesCase<SelectShape, ChoiceNShape> for Cons<H, T> {
+    type Output = Cons<H, <T as SpliceChoices<SelectShape>>::Output>;
+}
+
+impl<H, T: SpliceChoices<OfferShape>> SpliceChoicesCase<OfferShape, SelectShape> for Cons<H, T> {
+    type Output = Cons<H, <T as SpliceChoices<OfferShape>>::Output>;
+}
+
+impl<H, T: SpliceChoices<OfferShape>> SpliceChoicesCase<OfferShape, ChoiceNShape> for Cons<H, T> {
+    type Output = Cons<H, <T as SpliceChoices<OfferShape>>::Output>;
+}
+
+impl<H, T: SpliceChoices<ChoiceNShape>> SpliceChoicesCase<ChoiceNShape, SelectShape>
+    for Cons<H, T>
+{
+    type Output = Cons<H, <T as SpliceChoices<ChoiceNShape>>::Output>;
+}
+
+impl<H, T: SpliceChoices<ChoiceNShape>> SpliceChoicesCase<ChoiceNShape, OfferShape> for Cons<H, T> {
+    type Output = Cons<H, <T as SpliceChoices<ChoiceNShape>>::Output>;
+}
+
+// --- Flatten: splice nested choices of the same kind ---
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Old, Branches> RebuildChoice<Flatten, Branches>
+    for EpSelect<IO, Lbl, Me, Old>
+where
+    Branches: SpliceChoices<SelectShape>,
+{
+    type Output = EpSelect<IO, Lbl, Me, <Branches as SpliceChoices<SelectShape>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Old, Branches> RebuildChoice<Flatten, Branches>
+    for EpOffer<IO, Lbl, Me, Old>
+where
+    Branches: SpliceChoices<OfferShape>,
+{
+    type Output = EpOffer<IO, Lbl, Me, <Branches as SpliceChoices<OfferShape>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Old, Branches> RebuildChoice<Flatten, Branches>
+    for EpChoiceN<IO, Lbl, Me, Old>
+where
+    Branches: SpliceChoices<ChoiceNShape>,
+{
+    type Output = EpChoiceN<IO, Lbl, Me, <Branches as SpliceChoices<ChoiceNShape>>::Output>;
+}
+
+// --- Nest: at most two branches per choice ---
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Old> RebuildChoice<Nest, Nil>
+    for EpSelect<IO, Lbl, Me, Old>
+{
+    type Output = EpSelect<IO, Lbl, Me, Nil>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Old, A> RebuildChoice<Nest, Cons<A, Nil>>
+    for EpSelect<IO, Lbl, Me, Old>
+{
+    type Output = EpSelect<IO, Lbl, Me, Cons<A, Nil>>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Old, A, B> RebuildChoice<Nest, Cons<A, Cons<B, Nil>>>
+    for EpSelect<IO, Lbl, Me, Old>
+{
+    type Output = EpSelect<IO, Lbl, Me, Cons<A, Cons<B, Nil>>>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Old, A, B, C, T>
+    RebuildChoice<Nest, Cons<A, Cons<B, Cons<C, T>>>> for EpSelect<IO, Lbl, Me, Old>
+where
+    Self: RebuildChoice<Nest, Cons<B, Cons<C, T>>>,
+{
+    type Output = EpSelect<
+        IO,
+        Lbl,
+        Me,
+        Cons<A, Cons<<Self as RebuildChoice<Nest, Cons<B, Cons<C, T>>>>::Output, Nil>>,
+    >;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Old> RebuildChoice<Nest, Nil>
+    for EpOffer<IO, Lbl, Me, Old>
+{
+    type Output = EpOffer<IO, Lbl, Me, Nil>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Old, A> RebuildChoice<Nest, Cons<A, Nil>>
+    for EpOffer<IO, Lbl, Me, Old>
+{
+    type Output = EpOffer<IO, Lbl, Me, Cons<A, Nil>>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Old, A, B> RebuildChoice<Nest, Cons<A, Cons<B, Nil>>>
+    for EpOffer<IO, Lbl, Me, Old>
+{
+    type Output = EpOffer<IO, Lbl, Me, Cons<A, Cons<B, Nil>>>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Old, A, B, C, T>
+    RebuildChoice<Nest, Cons<A, Cons<B, Cons<C, T>>>> for EpOffer<IO, Lbl, Me, Old>
+where
+    Self: RebuildChoice<Nest, Cons<B, Cons<C, T>>>,
+{
+    type Output = EpOffer<
+        IO,
+        Lbl,
+        Me,
+        Cons<A, Cons<<Self as RebuildChoice<Nest, Cons<B, Cons<C, T>>>>::Output, Nil>>,
+    >;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Old> RebuildChoice<Nest, Nil>
+    for EpChoiceN<IO, Lbl, Me, Old>
+{
+    type Output = EpChoiceN<IO, Lbl, Me, Nil>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Old, A> RebuildChoice<Nest, Cons<A, Nil>>
+    for EpChoiceN<IO, Lbl, Me, Old>
+{
+    type Output = EpChoiceN<IO, Lbl, Me, Cons<A, Nil>>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Old, A, B> RebuildChoice<Nest, Cons<A, Cons<B, Nil>>>
+    for EpChoiceN<IO, Lbl, Me, Old>
+{
+    type Output = EpChoiceN<IO, Lbl, Me, Cons<A, Cons<B, Nil>>>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Old, A, B, C, T>
+    RebuildChoice<Nest, Cons<A, Cons<B, Cons<C, T>>>> for EpChoiceN<IO, Lbl, Me, Old>
+where
+    Self: RebuildChoice<Nest, Cons<B, Cons<C, T>>>,
+{
+    type Output = EpChoiceN<
+        IO,
+        Lbl,
+        Me,
+        Cons<A, Cons<<Self as RebuildChoice<Nest, Cons<B, Cons<C, T>>>>::Output, Nil>>,
+    >;
+}
+
+// --- Structural traversal ---
+
+impl<Mode, IO, Lbl: types::ProtocolLabel, R> MapChoices<Mode> for EpEnd<IO, Lbl, R> {
+    type Output = EpEnd<IO, Lbl, R>;
+}
+
+impl<Mode, IO, Lbl: types::ProtocolLabel, R> MapChoices<Mode> for EpSkip<IO, Lbl, R> {
+    type Output = EpSkip<IO, Lbl, R>;
+}
+
+impl<Mode, Var> MapChoices<Mode> for EpVar<Var> {
+    type Output = EpVar<Var>;
+}
+
+impl<Mode, IO, Lbl: types::ProtocolLabel, Me, Err> MapChoices<Mode> for EpThrow<IO, Lbl, Me, Err> {
+    type Output = EpThrow<IO, Lbl, Me, Err>;
+}
+
+impl<Mode, IO, Lbl: types::ProtocolLabel, R, H, T: MapChoices<Mode>> MapChoices<Mode>
+    for EpSend<IO, Lbl, R, H, T>
+{
+    type Output = EpSend<IO, Lbl, R, H, <T as MapChoices<Mode>>::Output>;
+}
+
+impl<Mode, IO, Lbl: types::ProtocolLabel, R, H, T: MapChoices<Mode>> MapChoices<Mode>
+    for EpRecv<IO, Lbl, R, H, T>
+{
+    type Output = EpRecv<IO, Lbl, R, H, <T as MapChoices<Mode>>::Output>;
+}
+
+impl<Mode, IO, Lbl: types::ProtocolLabel, Me, ToSet, H, T: MapChoices<Mode>> MapChoices<Mode>
+    for EpBroadcast<IO, Lbl, Me, ToSet, H, T>
+{
+    type Output = EpBroadcast<IO, Lbl, Me, ToSet, H, <T as MapChoices<Mode>>::Output>;
+}
+
+impl<Mode, IO, Lbl: types::ProtocolLabel, Me, FromSet, H, T: MapChoices<Mode>> MapChoices<Mode>
+    for EpGather<IO, Lbl, Me, FromSet, H, T>
+{
+    type Output = EpGather<IO, Lbl, Me, FromSet, H, <T as MapChoices<Mode>>::Output>;
+}
+
+impl<Mode, IO, Lbl: types::ProtocolLabel, Me, DelegatedEp, Cont: MapChoices<Mode>> MapChoices<Mode>
+    for EpDelegate<IO, Lbl, Me, DelegatedEp, Cont>
+{
+    type Output = EpDelegate<IO, Lbl, Me, DelegatedEp, <Cont as MapChoices<Mode>>::Output>;
+}
+
+impl<Mode, IO, Lbl: types::ProtocolLabel, Me, Body: MapChoices<Mode>, Cont: MapChoices<Mode>>
+    MapChoices<Mode> for EpOptionalSelect<IO, Lbl, Me, Body, Cont>
+{
+    type Output = EpOptionalSelect<
+        IO,
+        Lbl,
+        Me,
+        <Body as MapChoices<Mode>>::Output,
+        <Cont as MapChoices<Mode>>::Output,
+    >;
+}
+
+impl<Mode, IO, Lbl: types::ProtocolLabel, Me, Body: MapChoices<Mode>, Cont: MapChoices<Mode>>
+    MapChoices<Mode> for EpOptionalOffer<IO, Lbl, Me, Body, Cont>
+{
+    type Output = EpOptionalOffer<
+        IO,
+        Lbl,
+        Me,
+        <Body as MapChoices<Mode>>::Output,
+        <Cont as MapChoices<Mode>>::Output,
+    >;
+}
+
+impl<Mode, IO, Lbl: types::ProtocolLabel, Me, High: MapChoices<Mode>, Low: MapChoices<Mode>>
+    MapChoices<Mode> for EpPriorityOffer<IO, Lbl, Me, High, Low>
+{
+    type Output = EpPriorityOffer<
+        IO,
+        Lbl,
+        Me,
+        <High as MapChoices<Mode>>::Output,
+        <Low as MapChoices<Mode>>::Output,
+    >;
+}
+
+impl<Mode, IO, Lbl: types::ProtocolLabel, Me, L: MapChoices<Mode>, R: MapChoices<Mode>>
+    MapChoices<Mode> for EpPar<IO, Lbl, Me, L, R>
+{
+    type Output =
+        EpPar<IO, Lbl, Me, <L as MapChoices<Mode>>::Output, <R as MapChoices<Mode>>::Output>;
+}
+
+impl<Mode, IO, Lbl: types::ProtocolLabel, Me, Branches: MapChoicesEach<Mode>> MapChoices<Mode>
+    for EpParN<IO, Lbl, Me, Branches>
+{
+    type Output = EpParN<IO, Lbl, Me, <Branches as MapChoicesEach<Mode>>::Output>;
+}
+
+impl<Mode, IO, Lbl: types::ProtocolLabel, Me, Steps: MapChoicesEach<Mode>> MapChoices<Mode>
+    for EpShuffle<IO, Lbl, Me, Steps>
+{
+    type Output = EpShuffle<IO, Lbl, Me, <Steps as MapChoicesEach<Mode>>::Output>;
+}
+
+impl<Mode, IO, Lbl: types::ProtocolLabel, Var, Body: MapChoices<Mode>> MapChoices<Mode>
+    for EpRec<IO, Lbl, Var, Body>
+{
+    type Output = EpRec<IO, Lbl, Var, <Body as MapChoices<Mode>>::Output>;
+}
+
+impl<
+        Mode,
+        IO,
+        Lbl: types::ProtocolLabel,
+        Me,
+        Dur,
+        Body: MapChoices<Mode>,
+        OnTimeout: MapChoices<Mode>,
+    > MapChoices<Mode> for EpTimeout<IO, Lbl, Me, Dur, Body, OnTimeout>
+{
+    type Output = EpTimeout<
+        IO,
+        Lbl,
+        Me,
+        Dur,
+        <Body as MapChoices<Mode>>::Output,
+        <OnTimeout as MapChoices<Mode>>::Output,
+    >;
+}
+
+impl<Mode, IO, Lbl: types::ProtocolLabel, Me, Dur, T: MapChoices<Mode>> MapChoices<Mode>
+    for EpDelay<IO, Lbl, Me, Dur, T>
+{
+    type Output = EpDelay<IO, Lbl, Me, Dur, <T as MapChoices<Mode>>::Output>;
+}
+
+impl<Mode, IO, Lbl: types::ProtocolLabel, Me, Dur, T: MapChoices<Mode>> MapChoices<Mode>
+    for EpDeadline<IO, Lbl, Me, Dur, T>
+{
+    type Output = EpDeadline<IO, Lbl, Me, Dur, <T as MapChoices<Mode>>::Output>;
+}
+
+impl<
+        Mode,
+        IO,
+        Lbl: types::ProtocolLabel,
+        Me,
+        Scope: MapChoices<Mode>,
+        Handler: MapChoices<Mode>,
+    > MapChoices<Mode> for EpInterrupt<IO, Lbl, Me, Scope, Handler>
+{
+    type Output = EpInterrupt<
+        IO,
+        Lbl,
+        Me,
+        <Scope as MapChoices<Mode>>::Output,
+        <Handler as MapChoices<Mode>>::Output,
+    >;
+}
+
+impl<
+        Mode,
+        IO,
+        Lbl: types::ProtocolLabel,
+        Me,
+        Scope: MapChoices<Mode>,
+        Handler: MapChoices<Mode>,
+    > MapChoices<Mode> for EpInterruptible<IO, Lbl, Me, Scope, Handler>
+{
+    type Output = EpInterruptible<
+        IO,
+        Lbl,
+        Me,
+        <Scope as MapChoices<Mode>>::Output,
+        <Handler as MapChoices<Mode>>::Output,
+    >;
+}
+
+impl<Mode, Pred, Inner: MapChoices<Mode>> MapChoices<Mode> for EpRefine<Pred, Inner> {
+    type Output = EpRefine<Pred, <Inner as MapChoices<Mode>>::Output>;
+}
+
+impl<
+        Mode,
+        IO,
+        Lbl: types::ProtocolLabel,
+        Me,
+        Body: MapChoices<Mode>,
+        Handler: MapChoices<Mode>,
+    > MapChoices<Mode> for EpCatch<IO, Lbl, Me, Body, Handler>
+{
+    type Output = EpCatch<
+        IO,
+        Lbl,
+        Me,
+        <Body as MapChoices<Mode>>::Output,
+        <Handler as MapChoices<Mode>>::Output,
+    >;
+}
+
+impl<Mode, IO, Lbl: types::ProtocolLabel, Me, Peer, Cont: MapChoices<Mode>> MapChoices<Mode>
+    for EpClose<IO, Lbl, Me, Peer, Cont>
+{
+    type Output = EpClose<IO, Lbl, Me, Peer, <Cont as MapChoices<Mode>>::Output>;
+}
+
+impl<Mode, IO, Lbl: types::ProtocolLabel, Me, Peer, Cont: MapChoices<Mode>> MapChoices<Mode>
+    for EpOpen<IO, Lbl, Me, Peer, Cont>
+{
+    type Output = EpOpen<IO, Lbl, Me, Peer, <Cont as MapChoices<Mode>>::Output>;
+}
+
+impl<Mode, IO, Lbl, Me, Branches> MapChoices<Mode> for EpChoiceN<IO, Lbl, Me, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: MapChoicesEach<Mode>,
+    Self: RebuildChoice<Mode, <Branches as MapChoicesEach<Mode>>::Output>,
+{
+    type Output = <Self as RebuildChoice<Mode, <Branches as MapChoicesEach<Mode>>::Output>>::Output;
+}
+
+impl<Mode, IO, Lbl, Me, Branches> MapChoices<Mode> for EpSelect<IO, Lbl, Me, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: MapChoicesEach<Mode>,
+    Self: RebuildChoice<Mode, <Branches as MapChoicesEach<Mode>>::Output>,
+{
+    type Output = <Self as RebuildChoice<Mode, <Branches as MapChoicesEach<Mode>>::Output>>::Output;
+}
+
+impl<Mode, IO, Lbl, Me, Branches> MapChoices<Mode> for EpOffer<IO, Lbl, Me, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: MapChoicesEach<Mode>,
+    Self: RebuildChoice<Mode, <Branches as MapChoicesEach<Mode>>::Output>,
+{
+    type Output = <Self as RebuildChoice<Mode, <Branches as MapChoicesEach<Mode>>::Output>>::Output;
+}
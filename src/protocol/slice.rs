@@ -0,0 +1,1576 @@
+//! # Slicing by Label
+//!
+//! This module navigates global protocols by label: it extracts the
+//! sub-protocol rooted at a labelled combinator, or swaps that sub-protocol
+//! for another one, so that labels name the parts of a protocol for modular
+//! reasoning.
+//!
+//! Key components:
+//!
+//! - `SliceByLabel`: The sub-protocol rooted at the first combinator with a given label
+//! - `ReplaceByLabel`: A protocol with that sub-protocol replaced
+//! - `FindLabel` / `FindLabelEach` / `FindLabelCase`: The search behind both
+//! - `ReplaceLabelled` / `ReplaceLabelledEach` / `ReplaceAfter` / `ReplaceAfterEach` / `ReplaceCase`: The replacement
+//! - `LabelNotFound` / `SliceFailure`: The failure marker naming the missing label
+//!
+//! The search visits a combinator before its continuation and branches, and
+//! those in the order of the combinator's type parameters; the first
+//! combinator whose label equals the searched one, compared with `LabelEq`,
+//! is the root of the slice. The slice includes its continuation. The labels
+//! of `TEnd` are not searched, and `TRefine` and `TAnnotate`, which have no
+//! label, are looked through. Every label of the protocol other than those
+//! of `TEnd` needs a `LabelEq` impl against the searched label, including
+//! `EmptyLabel` where it labels a combinator.
+
+use super::base::{Cons, Nil};
+use super::global::*;
+use super::local::LabelEq;
+use crate::types;
+use core::marker::PhantomData;
+
+/// Failure marker: no combinator of the protocol is labelled `Lbl`.
+pub struct LabelNotFound<Lbl>(PhantomData<Lbl>);
+
+/// Never implemented: a bound `Marker: SliceFailure` fails and names the
+/// label [`SliceByLabel`] or [`ReplaceByLabel`] did not find.
+#[diagnostic::on_unimplemented(
+    message = "the protocol has no combinator labelled as requested: `{Self}`",
+    label = "no combinator carries this label",
+    note = "slice or replace by a label of a combinator of the protocol; the labels of `TEnd` are not searched"
+)]
+pub trait SliceFailure {}
+
+/// The sub-protocol of a global protocol rooted at the first combinator
+/// labelled `Lbl`, continuation included.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Login; struct Query;
+/// impl ProtocolLabel for Login {} impl ProtocolLabel for Query {}
+/// impl LabelEq<Query> for Login { type Output = False; }
+/// impl LabelEq<Query> for Query { type Output = True; }
+///
+/// type Ask = TMsg<Http, Query, TClient, TServer, Message,
+///            TMsg<Http, Query, TServer, TClient, Response, TEnd<Http>>>;
+/// type Session = TMsg<Http, Login, TClient, TServer, Message, Ask>;
+/// assert_type_eq!(<Session as SliceByLabel<Query>>::Output, Ask);
+/// ```
+pub trait SliceByLabel<Lbl> {
+    type Output;
+}
+
+impl<G, Lbl> SliceByLabel<Lbl> for G
+where
+    G: FindLabel<Lbl>,
+    (): SliceCase<Lbl, <G as FindLabel<Lbl>>::Found, <G as FindLabel<Lbl>>::Slice>,
+{
+    type Output =
+        <() as SliceCase<Lbl, <G as FindLabel<Lbl>>::Found, <G as FindLabel<Lbl>>::Slice>>::Output;
+}
+
+/// Helper trait for `SliceByLabel`, dispatching on whether the label was
+/// found.
+pub trait SliceCase<Lbl, Found, Slice> {
+    type Output;
+}
+
+impl<Lbl, Slice> SliceCase<Lbl, types::True, Slice> for () {
+    type Output = Slice;
+}
+
+impl<Lbl, Slice> SliceCase<Lbl, types::False, Slice> for ()
+where
+    LabelNotFound<Lbl>: SliceFailure,
+{
+    type Output = Nil;
+}
+
+/// A global protocol over `IO` with the sub-protocol rooted at the first
+/// combinator labelled `Lbl` replaced by `New`.
+///
+/// The replaced sub-protocol includes its continuation, as the slice
+/// [`SliceByLabel`] returns does; `New` ends the branch it is put in.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Login; struct Query;
+/// impl ProtocolLabel for Login {} impl ProtocolLabel for Query {}
+/// impl LabelEq<Query> for Login { type Output = False; }
+/// impl LabelEq<Query> for Query { type Output = True; }
+///
+/// type Ask = TMsg<Http, Query, TClient, TServer, Message, TEnd<Http>>;
+/// type Session = TMsg<Http, Login, TClient, TServer, Message, Ask>;
+/// type Cached = TInteract<Http, Query, TClient, Message, TEnd<Http>>;
+/// assert_type_eq!(
+///     <Session as ReplaceByLabel<Http, Query, Cached>>::Output,
+///     TMsg<Http, Login, TClient, TServer, Message, Cached>
+/// );
+/// ```
+pub trait ReplaceByLabel<IO, Lbl, New> {
+    type Output: TSession<IO>;
+}
+
+impl<IO, G, Lbl, New> ReplaceByLabel<IO, Lbl, New> for G
+where
+    G: SliceByLabel<Lbl> + ReplaceLabelled<IO, Lbl, New>,
+{
+    type Output = <G as ReplaceLabelled<IO, Lbl, New>>::Output;
+}
+
+/// Searches a protocol for the first combinator labelled `Target`.
+///
+/// `Found` is `True` if there is one, and `Slice` is then the sub-protocol
+/// rooted at it; otherwise `Slice` is `Nil`.
+pub trait FindLabel<Target> {
+    type Found: types::Bool;
+    type Slice;
+}
+
+/// [`FindLabel`] over the protocols of a type-level list: the first slice
+/// found in any of them, in order.
+pub trait FindLabelEach<Target> {
+    type Found: types::Bool;
+    type Slice;
+}
+
+impl<Target> FindLabelEach<Target> for Nil {
+    type Found = types::False;
+    type Slice = Nil;
+}
+
+impl<H, T, Target> FindLabelEach<Target> for Cons<H, T>
+where
+    H: FindLabel<Target>,
+    T: FindLabelEach<Target>,
+    (): FindLabelCase<
+        <H as FindLabel<Target>>::Found,
+        <H as FindLabel<Target>>::Slice,
+        <T as FindLabelEach<Target>>::Found,
+        <T as FindLabelEach<Target>>::Slice,
+    >,
+{
+    type Found = <() as FindLabelCase<
+        <H as FindLabel<Target>>::Found,
+        <H as FindLabel<Target>>::Slice,
+        <T as FindLabelEach<Target>>::Found,
+        <T as FindLabelEach<Target>>::Slice,
+    >>::Found;
+    type Slice = <() as FindLabelCase<
+        <H as FindLabel<Target>>::Found,
+        <H as FindLabel<Target>>::Slice,
+        <T as FindLabelEach<Target>>::Found,
+        <T as FindLabelEach<Target>>::Slice,
+    >>::Slice;
+}
+
+/// Helper trait for `FindLabel`, dispatching on whether the search already
+/// matched `Here`; otherwise the result is `Found` and `Slice` of the rest.
+pub trait FindLabelCase<Matched, Here, Found, Slice> {
+    type Found: types::Bool;
+    type Slice;
+}
+
+impl<Here, Found, Slice> FindLabelCase<types::True, Here, Found, Slice> for () {
+    type Found = types::True;
+    type Slice = Here;
+}
+
+impl<Here, Found: types::Bool, Slice> FindLabelCase<types::False, Here, Found, Slice> for () {
+    type Found = Found;
+    type Slice = Slice;
+}
+
+/// A protocol with the sub-protocol rooted at the first combinator labelled
+/// `Target` replaced by `New`; unchanged if there is none.
+pub trait ReplaceLabelled<IO, Target, New> {
+    type Output: TSession<IO>;
+}
+
+/// [`ReplaceLabelled`] over a type-level list: replaces in the first
+/// protocol of the list where `Target` is found.
+pub trait ReplaceLabelledEach<IO, Target, New> {
+    type Output: SessionList<IO>;
+}
+
+impl<IO, Target, New> ReplaceLabelledEach<IO, Target, New> for Nil {
+    type Output = Nil;
+}
+
+impl<IO, H, T, Target, New> ReplaceLabelledEach<IO, Target, New> for Cons<H, T>
+where
+    H: TSession<IO> + ReplaceLabelled<IO, Target, New> + FindLabel<Target>,
+    T: ReplaceAfterEach<IO, Target, New, <H as FindLabel<Target>>::Found>,
+{
+    type Output = Cons<
+        <H as ReplaceLabelled<IO, Target, New>>::Output,
+        <T as ReplaceAfterEach<IO, Target, New, <H as FindLabel<Target>>::Found>>::Output,
+    >;
+}
+
+/// [`ReplaceLabelled`] on a protocol after an earlier one, unless `Done`
+/// says the label was found in the earlier one.
+pub trait ReplaceAfter<IO, Target, New, Done> {
+    type Output: TSession<IO>;
+}
+
+impl<IO, G: TSession<IO>, Target, New> ReplaceAfter<IO, Target, New, types::True> for G {
+    type Output = G;
+}
+
+impl<IO, G, Target, New> ReplaceAfter<IO, Target, New, types::False> for G
+where
+    G: ReplaceLabelled<IO, Target, New>,
+{
+    type Output = <G as ReplaceLabelled<IO, Target, New>>::Output;
+}
+
+/// [`ReplaceLabelledEach`] on a type-level list after an earlier protocol,
+/// unless `Done` says the label was found in the earlier one.
+pub trait ReplaceAfterEach<IO, Target, New, Done> {
+    type Output: SessionList<IO>;
+}
+
+impl<IO, L: SessionList<IO>, Target, New> ReplaceAfterEach<IO, Target, New, types::True> for L {
+    type Output = L;
+}
+
+impl<IO, L, Target, New> ReplaceAfterEach<IO, Target, New, types::False> for L
+where
+    L: ReplaceLabelledEach<IO, Target, New>,
+{
+    type Output = <L as ReplaceLabelledEach<IO, Target, New>>::Output;
+}
+
+/// Helper trait for `ReplaceLabelled`, dispatching on whether a combinator
+/// is labelled as searched: it is then replaced by `New`, and otherwise
+/// `Rebuilt` from its replaced children.
+pub trait ReplaceCase<IO, Matched, New, Rebuilt> {
+    type Output: TSession<IO>;
+}
+
+impl<IO, New: TSession<IO>, Rebuilt> ReplaceCase<IO, types::True, New, Rebuilt> for () {
+    type Output = New;
+}
+
+impl<IO, New, Rebuilt: TSession<IO>> ReplaceCase<IO, types::False, New, Rebuilt> for () {
+    type Output = Rebuilt;
+}
+
+// Searching
+
+impl<IO, Lbl, Target> FindLabel<Target> for TEnd<IO, Lbl> {
+    type Found = types::False;
+    type Slice = Nil;
+}
+
+impl<IO, Lbl, R, H, T, Target> FindLabel<Target> for TInteract<IO, Lbl, R, H, T>
+where
+    Lbl: types::ProtocolLabel + LabelEq<Target>,
+    T: TSession<IO>,
+    Cons<T, Nil>: FindLabelEach<Target>,
+    (): FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Cons<T, Nil> as FindLabelEach<Target>>::Found,
+        <Cons<T, Nil> as FindLabelEach<Target>>::Slice,
+    >,
+{
+    type Found = <() as FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Cons<T, Nil> as FindLabelEach<Target>>::Found,
+        <Cons<T, Nil> as FindLabelEach<Target>>::Slice,
+    >>::Found;
+    type Slice = <() as FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Cons<T, Nil> as FindLabelEach<Target>>::Found,
+        <Cons<T, Nil> as FindLabelEach<Target>>::Slice,
+    >>::Slice;
+}
+
+impl<IO, Lbl, From, To, H, T, Target> FindLabel<Target> for TMsg<IO, Lbl, From, To, H, T>
+where
+    Lbl: types::ProtocolLabel + LabelEq<Target>,
+    T: TSession<IO>,
+    Cons<T, Nil>: FindLabelEach<Target>,
+    (): FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Cons<T, Nil> as FindLabelEach<Target>>::Found,
+        <Cons<T, Nil> as FindLabelEach<Target>>::Slice,
+    >,
+{
+    type Found = <() as FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Cons<T, Nil> as FindLabelEach<Target>>::Found,
+        <Cons<T, Nil> as FindLabelEach<Target>>::Slice,
+    >>::Found;
+    type Slice = <() as FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Cons<T, Nil> as FindLabelEach<Target>>::Found,
+        <Cons<T, Nil> as FindLabelEach<Target>>::Slice,
+    >>::Slice;
+}
+
+impl<IO, Lbl, From, ToSet, H, T, Target> FindLabel<Target>
+    for TBroadcast<IO, Lbl, From, ToSet, H, T>
+where
+    Lbl: types::ProtocolLabel + LabelEq<Target>,
+    T: TSession<IO>,
+    Cons<T, Nil>: FindLabelEach<Target>,
+    (): FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Cons<T, Nil> as FindLabelEach<Target>>::Found,
+        <Cons<T, Nil> as FindLabelEach<Target>>::Slice,
+    >,
+{
+    type Found = <() as FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Cons<T, Nil> as FindLabelEach<Target>>::Found,
+        <Cons<T, Nil> as FindLabelEach<Target>>::Slice,
+    >>::Found;
+    type Slice = <() as FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Cons<T, Nil> as FindLabelEach<Target>>::Found,
+        <Cons<T, Nil> as FindLabelEach<Target>>::Slice,
+    >>::Slice;
+}
+
+impl<IO, Lbl, From, WorkerSet, H, T, Target> FindLabel<Target>
+    for TAnycast<IO, Lbl, From, WorkerSet, H, T>
+where
+    Lbl: types::ProtocolLabel + LabelEq<Target>,
+    T: TSession<IO>,
+    Cons<T, Nil>: FindLabelEach<Target>,
+    (): FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Cons<T, Nil> as FindLabelEach<Target>>::Found,
+        <Cons<T, Nil> as FindLabelEach<Target>>::Slice,
+    >,
+{
+    type Found = <() as FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Cons<T, Nil> as FindLabelEach<Target>>::Found,
+        <Cons<T, Nil> as FindLabelEach<Target>>::Slice,
+    >>::Found;
+    type Slice = <() as FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Cons<T, Nil> as FindLabelEach<Target>>::Found,
+        <Cons<T, Nil> as FindLabelEach<Target>>::Slice,
+    >>::Slice;
+}
+
+impl<IO, Lbl, From, To, DelegatedEp, T, Target> FindLabel<Target>
+    for TDelegate<IO, Lbl, From, To, DelegatedEp, T>
+where
+    Lbl: types::ProtocolLabel + LabelEq<Target>,
+    T: TSession<IO>,
+    Cons<T, Nil>: FindLabelEach<Target>,
+    (): FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Cons<T, Nil> as FindLabelEach<Target>>::Found,
+        <Cons<T, Nil> as FindLabelEach<Target>>::Slice,
+    >,
+{
+    type Found = <() as FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Cons<T, Nil> as FindLabelEach<Target>>::Found,
+        <Cons<T, Nil> as FindLabelEach<Target>>::Slice,
+    >>::Found;
+    type Slice = <() as FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Cons<T, Nil> as FindLabelEach<Target>>::Found,
+        <Cons<T, Nil> as FindLabelEach<Target>>::Slice,
+    >>::Slice;
+}
+
+impl<IO, Lbl, A, B, Cont, Target> FindLabel<Target> for TDisconnect<IO, Lbl, A, B, Cont>
+where
+    Lbl: types::ProtocolLabel + LabelEq<Target>,
+    Cont: TSession<IO>,
+    Cons<Cont, Nil>: FindLabelEach<Target>,
+    (): FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Cons<Cont, Nil> as FindLabelEach<Target>>::Found,
+        <Cons<Cont, Nil> as FindLabelEach<Target>>::Slice,
+    >,
+{
+    type Found = <() as FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Cons<Cont, Nil> as FindLabelEach<Target>>::Found,
+        <Cons<Cont, Nil> as FindLabelEach<Target>>::Slice,
+    >>::Found;
+    type Slice = <() as FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Cons<Cont, Nil> as FindLabelEach<Target>>::Found,
+        <Cons<Cont, Nil> as FindLabelEach<Target>>::Slice,
+    >>::Slice;
+}
+
+impl<IO, Lbl, A, B, Cont, Target> FindLabel<Target> for TConnect<IO, Lbl, A, B, Cont>
+where
+    Lbl: types::ProtocolLabel + LabelEq<Target>,
+    Cont: TSession<IO>,
+    Cons<Cont, Nil>: FindLabelEach<Target>,
+    (): FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Cons<Cont, Nil> as FindLabelEach<Target>>::Found,
+        <Cons<Cont, Nil> as FindLabelEach<Target>>::Slice,
+    >,
+{
+    type Found = <() as FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Cons<Cont, Nil> as FindLabelEach<Target>>::Found,
+        <Cons<Cont, Nil> as FindLabelEach<Target>>::Slice,
+    >>::Found;
+    type Slice = <() as FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Cons<Cont, Nil> as FindLabelEach<Target>>::Found,
+        <Cons<Cont, Nil> as FindLabelEach<Target>>::Slice,
+    >>::Slice;
+}
+
+impl<IO, Lbl, R, Cont, Target> FindLabel<Target> for TEndFor<IO, Lbl, R, Cont>
+where
+    Lbl: types::ProtocolLabel + LabelEq<Target>,
+    Cont: TSession<IO>,
+    Cons<Cont, Nil>: FindLabelEach<Target>,
+    (): FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Cons<Cont, Nil> as FindLabelEach<Target>>::Found,
+        <Cons<Cont, Nil> as FindLabelEach<Target>>::Slice,
+    >,
+{
+    type Found = <() as FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Cons<Cont, Nil> as FindLabelEach<Target>>::Found,
+        <Cons<Cont, Nil> as FindLabelEach<Target>>::Slice,
+    >>::Found;
+    type Slice = <() as FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Cons<Cont, Nil> as FindLabelEach<Target>>::Found,
+        <Cons<Cont, Nil> as FindLabelEach<Target>>::Slice,
+    >>::Slice;
+}
+
+impl<IO, Lbl, L, R, Target> FindLabel<Target> for TChoice<IO, Lbl, L, R>
+where
+    Lbl: types::ProtocolLabel + LabelEq<Target>,
+    L: TSession<IO>,
+    R: TSession<IO>,
+    Cons<L, Cons<R, Nil>>: FindLabelEach<Target>,
+    (): FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Cons<L, Cons<R, Nil>> as FindLabelEach<Target>>::Found,
+        <Cons<L, Cons<R, Nil>> as FindLabelEach<Target>>::Slice,
+    >,
+{
+    type Found = <() as FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Cons<L, Cons<R, Nil>> as FindLabelEach<Target>>::Found,
+        <Cons<L, Cons<R, Nil>> as FindLabelEach<Target>>::Slice,
+    >>::Found;
+    type Slice = <() as FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Cons<L, Cons<R, Nil>> as FindLabelEach<Target>>::Found,
+        <Cons<L, Cons<R, Nil>> as FindLabelEach<Target>>::Slice,
+    >>::Slice;
+}
+
+impl<IO, Lbl, Branches, Target> FindLabel<Target> for TChoiceN<IO, Lbl, Branches>
+where
+    Lbl: types::ProtocolLabel + LabelEq<Target>,
+    Branches: SessionList<IO> + FindLabelEach<Target>,
+    (): FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Branches as FindLabelEach<Target>>::Found,
+        <Branches as FindLabelEach<Target>>::Slice,
+    >,
+{
+    type Found = <() as FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Branches as FindLabelEach<Target>>::Found,
+        <Branches as FindLabelEach<Target>>::Slice,
+    >>::Found;
+    type Slice = <() as FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Branches as FindLabelEach<Target>>::Found,
+        <Branches as FindLabelEach<Target>>::Slice,
+    >>::Slice;
+}
+
+impl<IO, Lbl, Chooser, Branches, Target> FindLabel<Target> for TSelect<IO, Lbl, Chooser, Branches>
+where
+    Lbl: types::ProtocolLabel + LabelEq<Target>,
+    Branches: SessionList<IO> + FindLabelEach<Target>,
+    (): FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Branches as FindLabelEach<Target>>::Found,
+        <Branches as FindLabelEach<Target>>::Slice,
+    >,
+{
+    type Found = <() as FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Branches as FindLabelEach<Target>>::Found,
+        <Branches as FindLabelEach<Target>>::Slice,
+    >>::Found;
+    type Slice = <() as FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Branches as FindLabelEach<Target>>::Found,
+        <Branches as FindLabelEach<Target>>::Slice,
+    >>::Slice;
+}
+
+impl<IO, Lbl, Offeree, Branches, Target> FindLabel<Target> for TOffer<IO, Lbl, Offeree, Branches>
+where
+    Lbl: types::ProtocolLabel + LabelEq<Target>,
+    Branches: SessionList<IO> + FindLabelEach<Target>,
+    (): FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Branches as FindLabelEach<Target>>::Found,
+        <Branches as FindLabelEach<Target>>::Slice,
+    >,
+{
+    type Found = <() as FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Branches as FindLabelEach<Target>>::Found,
+        <Branches as FindLabelEach<Target>>::Slice,
+    >>::Found;
+    type Slice = <() as FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Branches as FindLabelEach<Target>>::Found,
+        <Branches as FindLabelEach<Target>>::Slice,
+    >>::Slice;
+}
+
+impl<IO, Lbl, High, Low, Target> FindLabel<Target> for TPriorityChoice<IO, Lbl, High, Low>
+where
+    Lbl: types::ProtocolLabel + LabelEq<Target>,
+    High: TSession<IO>,
+    Low: TSession<IO>,
+    Cons<High, Cons<Low, Nil>>: FindLabelEach<Target>,
+    (): FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Cons<High, Cons<Low, Nil>> as FindLabelEach<Target>>::Found,
+        <Cons<High, Cons<Low, Nil>> as FindLabelEach<Target>>::Slice,
+    >,
+{
+    type Found = <() as FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Cons<High, Cons<Low, Nil>> as FindLabelEach<Target>>::Found,
+        <Cons<High, Cons<Low, Nil>> as FindLabelEach<Target>>::Slice,
+    >>::Found;
+    type Slice = <() as FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Cons<High, Cons<Low, Nil>> as FindLabelEach<Target>>::Found,
+        <Cons<High, Cons<Low, Nil>> as FindLabelEach<Target>>::Slice,
+    >>::Slice;
+}
+
+impl<IO, Lbl, WeightL, L, WeightR, R, Target> FindLabel<Target>
+    for TProb<IO, Lbl, WeightL, L, WeightR, R>
+where
+    Lbl: types::ProtocolLabel + LabelEq<Target>,
+    WeightL: types::WeightMarker,
+    L: TSession<IO>,
+    WeightR: types::WeightMarker,
+    R: TSession<IO>,
+    Cons<L, Cons<R, Nil>>: FindLabelEach<Target>,
+    (): FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Cons<L, Cons<R, Nil>> as FindLabelEach<Target>>::Found,
+        <Cons<L, Cons<R, Nil>> as FindLabelEach<Target>>::Slice,
+    >,
+{
+    type Found = <() as FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Cons<L, Cons<R, Nil>> as FindLabelEach<Target>>::Found,
+        <Cons<L, Cons<R, Nil>> as FindLabelEach<Target>>::Slice,
+    >>::Found;
+    type Slice = <() as FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Cons<L, Cons<R, Nil>> as FindLabelEach<Target>>::Found,
+        <Cons<L, Cons<R, Nil>> as FindLabelEach<Target>>::Slice,
+    >>::Slice;
+}
+
+impl<IO, Lbl, L, R, IsDisjoint, Target> FindLabel<Target> for TPar<IO, Lbl, L, R, IsDisjoint>
+where
+    Lbl: types::ProtocolLabel + LabelEq<Target>,
+    L: TSession<IO>,
+    R: TSession<IO>,
+    Cons<L, Cons<R, Nil>>: FindLabelEach<Target>,
+    (): FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Cons<L, Cons<R, Nil>> as FindLabelEach<Target>>::Found,
+        <Cons<L, Cons<R, Nil>> as FindLabelEach<Target>>::Slice,
+    >,
+{
+    type Found = <() as FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Cons<L, Cons<R, Nil>> as FindLabelEach<Target>>::Found,
+        <Cons<L, Cons<R, Nil>> as FindLabelEach<Target>>::Slice,
+    >>::Found;
+    type Slice = <() as FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Cons<L, Cons<R, Nil>> as FindLabelEach<Target>>::Found,
+        <Cons<L, Cons<R, Nil>> as FindLabelEach<Target>>::Slice,
+    >>::Slice;
+}
+
+impl<IO, Lbl, Branches, Target> FindLabel<Target> for TParN<IO, Lbl, Branches>
+where
+    Lbl: types::ProtocolLabel + LabelEq<Target>,
+    Branches: SessionList<IO> + FindLabelEach<Target>,
+    (): FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Branches as FindLabelEach<Target>>::Found,
+        <Branches as FindLabelEach<Target>>::Slice,
+    >,
+{
+    type Found = <() as FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Branches as FindLabelEach<Target>>::Found,
+        <Branches as FindLabelEach<Target>>::Slice,
+    >>::Found;
+    type Slice = <() as FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Branches as FindLabelEach<Target>>::Found,
+        <Branches as FindLabelEach<Target>>::Slice,
+    >>::Slice;
+}
+
+impl<IO, Lbl, Steps, Target> FindLabel<Target> for TShuffle<IO, Lbl, Steps>
+where
+    Lbl: types::ProtocolLabel + LabelEq<Target>,
+    Steps: SessionList<IO> + FindLabelEach<Target>,
+    (): FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Steps as FindLabelEach<Target>>::Found,
+        <Steps as FindLabelEach<Target>>::Slice,
+    >,
+{
+    type Found = <() as FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Steps as FindLabelEach<Target>>::Found,
+        <Steps as FindLabelEach<Target>>::Slice,
+    >>::Found;
+    type Slice = <() as FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Steps as FindLabelEach<Target>>::Found,
+        <Steps as FindLabelEach<Target>>::Slice,
+    >>::Slice;
+}
+
+impl<IO, Lbl, S, Target> FindLabel<Target> for TRec<IO, Lbl, S>
+where
+    Lbl: types::ProtocolLabel + LabelEq<Target>,
+    S: TSession<IO>,
+    Cons<S, Nil>: FindLabelEach<Target>,
+    (): FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Cons<S, Nil> as FindLabelEach<Target>>::Found,
+        <Cons<S, Nil> as FindLabelEach<Target>>::Slice,
+    >,
+{
+    type Found = <() as FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Cons<S, Nil> as FindLabelEach<Target>>::Found,
+        <Cons<S, Nil> as FindLabelEach<Target>>::Slice,
+    >>::Found;
+    type Slice = <() as FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Cons<S, Nil> as FindLabelEach<Target>>::Found,
+        <Cons<S, Nil> as FindLabelEach<Target>>::Slice,
+    >>::Slice;
+}
+
+impl<IO, Lbl, Var, Body, Target> FindLabel<Target> for TRecX<IO, Lbl, Var, Body>
+where
+    Lbl: types::ProtocolLabel + LabelEq<Target>,
+    Body: TSession<IO>,
+    Cons<Body, Nil>: FindLabelEach<Target>,
+    (): FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Cons<Body, Nil> as FindLabelEach<Target>>::Found,
+        <Cons<Body, Nil> as FindLabelEach<Target>>::Slice,
+    >,
+{
+    type Found = <() as FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Cons<Body, Nil> as FindLabelEach<Target>>::Found,
+        <Cons<Body, Nil> as FindLabelEach<Target>>::Slice,
+    >>::Found;
+    type Slice = <() as FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Cons<Body, Nil> as FindLabelEach<Target>>::Found,
+        <Cons<Body, Nil> as FindLabelEach<Target>>::Slice,
+    >>::Slice;
+}
+
+impl<Var, Target> FindLabel<Target> for TVar<Var> {
+    type Found = types::False;
+    type Slice = Nil;
+}
+
+impl<IO, Lbl, Dur, Body, OnTimeout, Target> FindLabel<Target>
+    for TTimeout<IO, Lbl, Dur, Body, OnTimeout>
+where
+    Lbl: types::ProtocolLabel + LabelEq<Target>,
+    Dur: types::DurationMarker,
+    Body: TSession<IO>,
+    OnTimeout: TSession<IO>,
+    Cons<Body, Cons<OnTimeout, Nil>>: FindLabelEach<Target>,
+    (): FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Cons<Body, Cons<OnTimeout, Nil>> as FindLabelEach<Target>>::Found,
+        <Cons<Body, Cons<OnTimeout, Nil>> as FindLabelEach<Target>>::Slice,
+    >,
+{
+    type Found = <() as FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Cons<Body, Cons<OnTimeout, Nil>> as FindLabelEach<Target>>::Found,
+        <Cons<Body, Cons<OnTimeout, Nil>> as FindLabelEach<Target>>::Slice,
+    >>::Found;
+    type Slice = <() as FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Cons<Body, Cons<OnTimeout, Nil>> as FindLabelEach<Target>>::Found,
+        <Cons<Body, Cons<OnTimeout, Nil>> as FindLabelEach<Target>>::Slice,
+    >>::Slice;
+}
+
+impl<IO, Lbl, R, Dur, T, Target> FindLabel<Target> for TDelay<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel + LabelEq<Target>,
+    Dur: types::DurationMarker,
+    T: TSession<IO>,
+    Cons<T, Nil>: FindLabelEach<Target>,
+    (): FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Cons<T, Nil> as FindLabelEach<Target>>::Found,
+        <Cons<T, Nil> as FindLabelEach<Target>>::Slice,
+    >,
+{
+    type Found = <() as FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Cons<T, Nil> as FindLabelEach<Target>>::Found,
+        <Cons<T, Nil> as FindLabelEach<Target>>::Slice,
+    >>::Found;
+    type Slice = <() as FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Cons<T, Nil> as FindLabelEach<Target>>::Found,
+        <Cons<T, Nil> as FindLabelEach<Target>>::Slice,
+    >>::Slice;
+}
+
+impl<IO, Lbl, R, Dur, T, Target> FindLabel<Target> for TDeadline<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel + LabelEq<Target>,
+    Dur: types::DurationMarker,
+    T: TSession<IO>,
+    Cons<T, Nil>: FindLabelEach<Target>,
+    (): FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Cons<T, Nil> as FindLabelEach<Target>>::Found,
+        <Cons<T, Nil> as FindLabelEach<Target>>::Slice,
+    >,
+{
+    type Found = <() as FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Cons<T, Nil> as FindLabelEach<Target>>::Found,
+        <Cons<T, Nil> as FindLabelEach<Target>>::Slice,
+    >>::Found;
+    type Slice = <() as FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Cons<T, Nil> as FindLabelEach<Target>>::Found,
+        <Cons<T, Nil> as FindLabelEach<Target>>::Slice,
+    >>::Slice;
+}
+
+impl<IO, Lbl, Scope, Interruptor, Handler, Target> FindLabel<Target>
+    for TInterrupt<IO, Lbl, Scope, Interruptor, Handler>
+where
+    Lbl: types::ProtocolLabel + LabelEq<Target>,
+    Scope: TSession<IO>,
+    Handler: TSession<IO>,
+    Cons<Scope, Cons<Handler, Nil>>: FindLabelEach<Target>,
+    (): FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Cons<Scope, Cons<Handler, Nil>> as FindLabelEach<Target>>::Found,
+        <Cons<Scope, Cons<Handler, Nil>> as FindLabelEach<Target>>::Slice,
+    >,
+{
+    type Found = <() as FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Cons<Scope, Cons<Handler, Nil>> as FindLabelEach<Target>>::Found,
+        <Cons<Scope, Cons<Handler, Nil>> as FindLabelEach<Target>>::Slice,
+    >>::Found;
+    type Slice = <() as FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Cons<Scope, Cons<Handler, Nil>> as FindLabelEach<Target>>::Found,
+        <Cons<Scope, Cons<Handler, Nil>> as FindLabelEach<Target>>::Slice,
+    >>::Slice;
+}
+
+impl<IO, Lbl, Decider, Body, Cont, Target> FindLabel<Target>
+    for TOptional<IO, Lbl, Decider, Body, Cont>
+where
+    Lbl: types::ProtocolLabel + LabelEq<Target>,
+    Body: TSession<IO>,
+    Cont: TSession<IO>,
+    Cons<Body, Cons<Cont, Nil>>: FindLabelEach<Target>,
+    (): FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Cons<Body, Cons<Cont, Nil>> as FindLabelEach<Target>>::Found,
+        <Cons<Body, Cons<Cont, Nil>> as FindLabelEach<Target>>::Slice,
+    >,
+{
+    type Found = <() as FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Cons<Body, Cons<Cont, Nil>> as FindLabelEach<Target>>::Found,
+        <Cons<Body, Cons<Cont, Nil>> as FindLabelEach<Target>>::Slice,
+    >>::Found;
+    type Slice = <() as FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Cons<Body, Cons<Cont, Nil>> as FindLabelEach<Target>>::Found,
+        <Cons<Body, Cons<Cont, Nil>> as FindLabelEach<Target>>::Slice,
+    >>::Slice;
+}
+
+impl<Pred, Inner, Target> FindLabel<Target> for TRefine<Pred, Inner>
+where
+    Inner: FindLabel<Target>,
+{
+    type Found = <Inner as FindLabel<Target>>::Found;
+    type Slice = <Inner as FindLabel<Target>>::Slice;
+}
+
+impl<Meta, Inner, Target> FindLabel<Target> for TAnnotate<Meta, Inner>
+where
+    Inner: FindLabel<Target>,
+{
+    type Found = <Inner as FindLabel<Target>>::Found;
+    type Slice = <Inner as FindLabel<Target>>::Slice;
+}
+
+impl<IO, Lbl, R, Err, Target> FindLabel<Target> for TThrow<IO, Lbl, R, Err>
+where
+    Lbl: types::ProtocolLabel + LabelEq<Target>,
+    (): FindLabelCase<<Lbl as LabelEq<Target>>::Output, Self, types::False, Nil>,
+{
+    type Found =
+        <() as FindLabelCase<<Lbl as LabelEq<Target>>::Output, Self, types::False, Nil>>::Found;
+    type Slice =
+        <() as FindLabelCase<<Lbl as LabelEq<Target>>::Output, Self, types::False, Nil>>::Slice;
+}
+
+impl<IO, Lbl, Body, Handler, Target> FindLabel<Target> for TCatch<IO, Lbl, Body, Handler>
+where
+    Lbl: types::ProtocolLabel + LabelEq<Target>,
+    Body: TSession<IO>,
+    Handler: TSession<IO>,
+    Cons<Body, Cons<Handler, Nil>>: FindLabelEach<Target>,
+    (): FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Cons<Body, Cons<Handler, Nil>> as FindLabelEach<Target>>::Found,
+        <Cons<Body, Cons<Handler, Nil>> as FindLabelEach<Target>>::Slice,
+    >,
+{
+    type Found = <() as FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Cons<Body, Cons<Handler, Nil>> as FindLabelEach<Target>>::Found,
+        <Cons<Body, Cons<Handler, Nil>> as FindLabelEach<Target>>::Slice,
+    >>::Found;
+    type Slice = <() as FindLabelCase<
+        <Lbl as LabelEq<Target>>::Output,
+        Self,
+        <Cons<Body, Cons<Handler, Nil>> as FindLabelEach<Target>>::Found,
+        <Cons<Body, Cons<Handler, Nil>> as FindLabelEach<Target>>::Slice,
+    >>::Slice;
+}
+
+// Replacing
+
+impl<IO, Lbl, Target, New> ReplaceLabelled<IO, Target, New> for TEnd<IO, Lbl> {
+    type Output = Self;
+}
+
+impl<IO, Lbl, R, H, T, Target, New> ReplaceLabelled<IO, Target, New> for TInteract<IO, Lbl, R, H, T>
+where
+    Lbl: types::ProtocolLabel + LabelEq<Target>,
+    T: TSession<IO> + ReplaceLabelled<IO, Target, New>,
+    (): ReplaceCase<
+        IO,
+        <Lbl as LabelEq<Target>>::Output,
+        New,
+        TInteract<IO, Lbl, R, H, <T as ReplaceLabelled<IO, Target, New>>::Output>,
+    >,
+{
+    type Output = <() as ReplaceCase<
+        IO,
+        <Lbl as LabelEq<Target>>::Output,
+        New,
+        TInteract<IO, Lbl, R, H, <T as ReplaceLabelled<IO, Target, New>>::Output>,
+    >>::Output;
+}
+
+impl<IO, Lbl, From, To, H, T, Target, New> ReplaceLabelled<IO, Target, New>
+    for TMsg<IO, Lbl, From, To, H, T>
+where
+    Lbl: types::ProtocolLabel + LabelEq<Target>,
+    T: TSession<IO> + ReplaceLabelled<IO, Target, New>,
+    (): ReplaceCase<
+        IO,
+        <Lbl as LabelEq<Target>>::Output,
+        New,
+        TMsg<IO, Lbl, From, To, H, <T as ReplaceLabelled<IO, Target, New>>::Output>,
+    >,
+{
+    type Output = <() as ReplaceCase<
+        IO,
+        <Lbl as LabelEq<Target>>::Output,
+        New,
+        TMsg<IO, Lbl, From, To, H, <T as ReplaceLabelled<IO, Target, New>>::Output>,
+    >>::Output;
+}
+
+impl<IO, Lbl, From, ToSet, H, T, Target, New> ReplaceLabelled<IO, Target, New>
+    for TBroadcast<IO, Lbl, From, ToSet, H, T>
+where
+    Lbl: types::ProtocolLabel + LabelEq<Target>,
+    T: TSession<IO> + ReplaceLabelled<IO, Target, New>,
+    (): ReplaceCase<
+        IO,
+        <Lbl as LabelEq<Target>>::Output,
+        New,
+        TBroadcast<IO, Lbl, From, ToSet, H, <T as ReplaceLabelled<IO, Target, New>>::Output>,
+    >,
+{
+    type Output = <() as ReplaceCase<
+        IO,
+        <Lbl as LabelEq<Target>>::Output,
+        New,
+        TBroadcast<IO, Lbl, From, ToSet, H, <T as ReplaceLabelled<IO, Target, New>>::Output>,
+    >>::Output;
+}
+
+impl<IO, Lbl, From, WorkerSet, H, T, Target, New> ReplaceLabelled<IO, Target, New>
+    for TAnycast<IO, Lbl, From, WorkerSet, H, T>
+where
+    Lbl: types::ProtocolLabel + LabelEq<Target>,
+    T: TSession<IO> + ReplaceLabelled<IO, Target, New>,
+    (): ReplaceCase<
+        IO,
+        <Lbl as LabelEq<Target>>::Output,
+        New,
+        TAnycast<IO, Lbl, From, WorkerSet, H, <T as ReplaceLabelled<IO, Target, New>>::Output>,
+    >,
+{
+    type Output = <() as ReplaceCase<
+        IO,
+        <Lbl as LabelEq<Target>>::Output,
+        New,
+        TAnycast<IO, Lbl, From, WorkerSet, H, <T as ReplaceLabelled<IO, Target, New>>::Output>,
+    >>::Output;
+}
+
+impl<IO, Lbl, From, To, DelegatedEp, T, Target, New> ReplaceLabelled<IO, Target, New>
+    for TDelegate<IO, Lbl, From, To, DelegatedEp, T>
+where
+    Lbl: types::ProtocolLabel + LabelEq<Target>,
+    T: TSession<IO> + ReplaceLabelled<IO, Target, New>,
+    (): ReplaceCase<
+        IO,
+        <Lbl as LabelEq<Target>>::Output,
+        New,
+        TDelegate<IO, Lbl, From, To, DelegatedEp, <T as ReplaceLabelled<IO, Target, New>>::Output>,
+    >,
+{
+    type Output = <() as ReplaceCase<
+        IO,
+        <Lbl as LabelEq<Target>>::Output,
+        New,
+        TDelegate<IO, Lbl, From, To, DelegatedEp, <T as ReplaceLabelled<IO, Target, New>>::Output>,
+    >>::Output;
+}
+
+impl<IO, Lbl, A, B, Cont, Target, New> ReplaceLabelled<IO, Target, New>
+    for TDisconnect<IO, Lbl, A, B, Cont>
+where
+    Lbl: types::ProtocolLabel + LabelEq<Target>,
+    Cont: TSession<IO> + ReplaceLabelled<IO, Target, New>,
+    (): ReplaceCase<
+        IO,
+        <Lbl as LabelEq<Target>>::Output,
+        New,
+        TDisconnect<IO, Lbl, A, B, <Cont as ReplaceLabelled<IO, Target, New>>::Output>,
+    >,
+{
+    type Output = <() as ReplaceCase<
+        IO,
+        <Lbl as LabelEq<Target>>::Output,
+        New,
+        TDisconnect<IO, Lbl, A, B, <Cont as ReplaceLabelled<IO, Target, New>>::Output>,
+    >>::Output;
+}
+
+impl<IO, Lbl, A, B, Cont, Target, New> ReplaceLabelled<IO, Target, New>
+    for TConnect<IO, Lbl, A, B, Cont>
+where
+    Lbl: types::ProtocolLabel + LabelEq<Target>,
+    Cont: TSession<IO> + ReplaceLabelled<IO, Target, New>,
+    (): ReplaceCase<
+        IO,
+        <Lbl as LabelEq<Target>>::Output,
+        New,
+        TConnect<IO, Lbl, A, B, <Cont as ReplaceLabelled<IO, Target, New>>::Output>,
+    >,
+{
+    type Output = <() as ReplaceCase<
+        IO,
+        <Lbl as LabelEq<Target>>::Output,
+        New,
+        TConnect<IO, Lbl, A, B, <Cont as ReplaceLabelled<IO, Target, New>>::Output>,
+    >>::Output;
+}
+
+impl<IO, Lbl, R, Cont, Target, New> ReplaceLabelled<IO, Target, New> for TEndFor<IO, Lbl, R, Cont>
+where
+    Lbl: types::ProtocolLabel + LabelEq<Target>,
+    Cont: TSession<IO> + ReplaceLabelled<IO, Target, New>,
+    (): ReplaceCase<
+        IO,
+        <Lbl as LabelEq<Target>>::Output,
+        New,
+        TEndFor<IO, Lbl, R, <Cont as ReplaceLabelled<IO, Target, New>>::Output>,
+    >,
+{
+    type Output = <() as ReplaceCase<
+        IO,
+        <Lbl as LabelEq<Target>>::Output,
+        New,
+        TEndFor<IO, Lbl, R, <Cont as ReplaceLabelled<IO, Target, New>>::Output>,
+    >>::Output;
+}
+
+impl<IO, Lbl, L, R, Target, New> ReplaceLabelled<IO, Target, New> for TChoice<IO, Lbl, L, R>
+where
+    Lbl: types::ProtocolLabel + LabelEq<Target>,
+    L: TSession<IO> + ReplaceLabelled<IO, Target, New> + FindLabel<Target>,
+    R: TSession<IO> + ReplaceAfter<IO, Target, New, <L as FindLabel<Target>>::Found>,
+    (): ReplaceCase<
+        IO,
+        <Lbl as LabelEq<Target>>::Output,
+        New,
+        TChoice<
+            IO,
+            Lbl,
+            <L as ReplaceLabelled<IO, Target, New>>::Output,
+            <R as ReplaceAfter<IO, Target, New, <L as FindLabel<Target>>::Found>>::Output,
+        >,
+    >,
+{
+    type Output = <() as ReplaceCase<
+        IO,
+        <Lbl as LabelEq<Target>>::Output,
+        New,
+        TChoice<
+            IO,
+            Lbl,
+            <L as ReplaceLabelled<IO, Target, New>>::Output,
+            <R as ReplaceAfter<IO, Target, New, <L as FindLabel<Target>>::Found>>::Output,
+        >,
+    >>::Output;
+}
+
+impl<IO, Lbl, Branches, Target, New> ReplaceLabelled<IO, Target, New>
+    for TChoiceN<IO, Lbl, Branches>
+where
+    Lbl: types::ProtocolLabel + LabelEq<Target>,
+    Branches: SessionList<IO> + ReplaceLabelledEach<IO, Target, New>,
+    (): ReplaceCase<
+        IO,
+        <Lbl as LabelEq<Target>>::Output,
+        New,
+        TChoiceN<IO, Lbl, <Branches as ReplaceLabelledEach<IO, Target, New>>::Output>,
+    >,
+{
+    type Output = <() as ReplaceCase<
+        IO,
+        <Lbl as LabelEq<Target>>::Output,
+        New,
+        TChoiceN<IO, Lbl, <Branches as ReplaceLabelledEach<IO, Target, New>>::Output>,
+    >>::Output;
+}
+
+impl<IO, Lbl, Chooser, Branches, Target, New> ReplaceLabelled<IO, Target, New>
+    for TSelect<IO, Lbl, Chooser, Branches>
+where
+    Lbl: types::ProtocolLabel + LabelEq<Target>,
+    Branches: SessionList<IO> + ReplaceLabelledEach<IO, Target, New>,
+    (): ReplaceCase<
+        IO,
+        <Lbl as LabelEq<Target>>::Output,
+        New,
+        TSelect<IO, Lbl, Chooser, <Branches as ReplaceLabelledEach<IO, Target, New>>::Output>,
+    >,
+{
+    type Output = <() as ReplaceCase<
+        IO,
+        <Lbl as LabelEq<Target>>::Output,
+        New,
+        TSelect<IO, Lbl, Chooser, <Branches as ReplaceLabelledEach<IO, Target, New>>::Output>,
+    >>::Output;
+}
+
+impl<IO, Lbl, Offeree, Branches, Target, New> ReplaceLabelled<IO, Target, New>
+    for TOffer<IO, Lbl, Offeree, Branches>
+where
+    Lbl: types::ProtocolLabel + LabelEq<Target>,
+    Branches: SessionList<IO> + ReplaceLabelledEach<IO, Target, New>,
+    (): ReplaceCase<
+        IO,
+        <Lbl as LabelEq<Target>>::Output,
+        New,
+        TOffer<IO, Lbl, Offeree, <Branches as ReplaceLabelledEach<IO, Target, New>>::Output>,
+    >,
+{
+    type Output = <() as ReplaceCase<
+        IO,
+        <Lbl as LabelEq<Target>>::Output,
+        New,
+        TOffer<IO, Lbl, Offeree, <Branches as ReplaceLabelledEach<IO, Target, New>>::Output>,
+    >>::Output;
+}
+
+impl<IO, Lbl, High, Low, Target, New> ReplaceLabelled<IO, Target, New>
+    for TPriorityChoice<IO, Lbl, High, Low>
+where
+    Lbl: types::ProtocolLabel + LabelEq<Target>,
+    High: TSession<IO> + ReplaceLabelled<IO, Target, New> + FindLabel<Target>,
+    Low: TSession<IO> + ReplaceAfter<IO, Target, New, <High as FindLabel<Target>>::Found>,
+    (): ReplaceCase<
+        IO,
+        <Lbl as LabelEq<Target>>::Output,
+        New,
+        TPriorityChoice<
+            IO,
+            Lbl,
+            <High as ReplaceLabelled<IO, Target, New>>::Output,
+            <Low as ReplaceAfter<IO, Target, New, <High as FindLabel<Target>>::Found>>::Output,
+        >,
+    >,
+{
+    type Output = <() as ReplaceCase<
+        IO,
+        <Lbl as LabelEq<Target>>::Output,
+        New,
+        TPriorityChoice<
+            IO,
+            Lbl,
+            <High as ReplaceLabelled<IO, Target, New>>::Output,
+            <Low as ReplaceAfter<IO, Target, New, <High as FindLabel<Target>>::Found>>::Output,
+        >,
+    >>::Output;
+}
+
+impl<IO, Lbl, WeightL, L, WeightR, R, Target, New> ReplaceLabelled<IO, Target, New>
+    for TProb<IO, Lbl, WeightL, L, WeightR, R>
+where
+    Lbl: types::ProtocolLabel + LabelEq<Target>,
+    WeightL: types::WeightMarker,
+    L: TSession<IO> + ReplaceLabelled<IO, Target, New> + FindLabel<Target>,
+    WeightR: types::WeightMarker,
+    R: TSession<IO> + ReplaceAfter<IO, Target, New, <L as FindLabel<Target>>::Found>,
+    (): ReplaceCase<
+        IO,
+        <Lbl as LabelEq<Target>>::Output,
+        New,
+        TProb<
+            IO,
+            Lbl,
+            WeightL,
+            <L as ReplaceLabelled<IO, Target, New>>::Output,
+            WeightR,
+            <R as ReplaceAfter<IO, Target, New, <L as FindLabel<Target>>::Found>>::Output,
+        >,
+    >,
+{
+    type Output = <() as ReplaceCase<
+        IO,
+        <Lbl as LabelEq<Target>>::Output,
+        New,
+        TProb<
+            IO,
+            Lbl,
+            WeightL,
+            <L as ReplaceLabelled<IO, Target, New>>::Output,
+            WeightR,
+            <R as ReplaceAfter<IO, Target, New, <L as FindLabel<Target>>::Found>>::Output,
+        >,
+    >>::Output;
+}
+
+impl<IO, Lbl, L, R, IsDisjoint, Target, New> ReplaceLabelled<IO, Target, New>
+    for TPar<IO, Lbl, L, R, IsDisjoint>
+where
+    Lbl: types::ProtocolLabel + LabelEq<Target>,
+    L: TSession<IO> + ReplaceLabelled<IO, Target, New> + FindLabel<Target>,
+    R: TSession<IO> + ReplaceAfter<IO, Target, New, <L as FindLabel<Target>>::Found>,
+    (): ReplaceCase<
+        IO,
+        <Lbl as LabelEq<Target>>::Output,
+        New,
+        TPar<
+            IO,
+            Lbl,
+            <L as ReplaceLabelled<IO, Target, New>>::Output,
+            <R as ReplaceAfter<IO, Target, New, <L as FindLabel<Target>>::Found>>::Output,
+            IsDisjoint,
+        >,
+    >,
+{
+    type Output = <() as ReplaceCase<
+        IO,
+        <Lbl as LabelEq<Target>>::Output,
+        New,
+        TPar<
+            IO,
+            Lbl,
+            <L as ReplaceLabelled<IO, Target, New>>::Output,
+            <R as ReplaceAfter<IO, Target, New, <L as FindLabel<Target>>::Found>>::Output,
+            IsDisjoint,
+        >,
+    >>::Output;
+}
+
+impl<IO, Lbl, Branches, Target, New> ReplaceLabelled<IO, Target, New> for TParN<IO, Lbl, Branches>
+where
+    Lbl: types::ProtocolLabel + LabelEq<Target>,
+    Branches: SessionList<IO> + ReplaceLabelledEach<IO, Target, New>,
+    (): ReplaceCase<
+        IO,
+        <Lbl as LabelEq<Target>>::Output,
+        New,
+        TParN<IO, Lbl, <Branches as ReplaceLabelledEach<IO, Target, New>>::Output>,
+    >,
+{
+    type Output = <() as ReplaceCase<
+        IO,
+        <Lbl as LabelEq<Target>>::Output,
+        New,
+        TParN<IO, Lbl, <Branches as ReplaceLabelledEach<IO, Target, New>>::Output>,
+    >>::Output;
+}
+
+impl<IO, Lbl, Steps, Target, New> ReplaceLabelled<IO, Target, New> for TShuffle<IO, Lbl, Steps>
+where
+    Lbl: types::ProtocolLabel + LabelEq<Target>,
+    Steps: SessionList<IO> + ReplaceLabelledEach<IO, Target, New>,
+    (): ReplaceCase<
+        IO,
+        <Lbl as LabelEq<Target>>::Output,
+        New,
+        TShuffle<IO, Lbl, <Steps as ReplaceLabelledEach<IO, Target, New>>::Output>,
+    >,
+{
+    type Output = <() as ReplaceCase<
+        IO,
+        <Lbl as LabelEq<Target>>::Output,
+        New,
+        TShuffle<IO, Lbl, <Steps as ReplaceLabelledEach<IO, Target, New>>::Output>,
+    >>::Output;
+}
+
+impl<IO, Lbl, S, Target, New> ReplaceLabelled<IO, Target, New> for TRec<IO, Lbl, S>
+where
+    Lbl: types::ProtocolLabel + LabelEq<Target>,
+    S: TSession<IO> + ReplaceLabelled<IO, Target, New>,
+    (): ReplaceCase<
+        IO,
+        <Lbl as LabelEq<Target>>::Output,
+        New,
+        TRec<IO, Lbl, <S as ReplaceLabelled<IO, Target, New>>::Output>,
+    >,
+{
+    type Output = <() as ReplaceCase<
+        IO,
+        <Lbl as LabelEq<Target>>::Output,
+        New,
+        TRec<IO, Lbl, <S as ReplaceLabelled<IO, Target, New>>::Output>,
+    >>::Output;
+}
+
+impl<IO, Lbl, Var, Body, Target, New> ReplaceLabelled<IO, Target, New> for TRecX<IO, Lbl, Var, Body>
+where
+    Lbl: types::ProtocolLabel + LabelEq<Target>,
+    Body: TSession<IO> + ReplaceLabelled<IO, Target, New>,
+    (): ReplaceCase<
+        IO,
+        <Lbl as LabelEq<Target>>::Output,
+        New,
+        TRecX<IO, Lbl, Var, <Body as ReplaceLabelled<IO, Target, New>>::Output>,
+    >,
+{
+    type Output = <() as ReplaceCase<
+        IO,
+        <Lbl as LabelEq<Target>>::Output,
+        New,
+        TRecX<IO, Lbl, Var, <Body as ReplaceLabelled<IO, Target, New>>::Output>,
+    >>::Output;
+}
+
+impl<IO, Var, Target, New> ReplaceLabelled<IO, Target, New> for TVar<Var> {
+    type Output = Self;
+}
+
+impl<IO, Lbl, Dur, Body, OnTimeout, Target, New> ReplaceLabelled<IO, Target, New> for TTimeout<IO, Lbl, Dur, Body, OnTimeout>
+where
+Lbl: types::ProtocolLabel + LabelEq<Target>,
+Dur: types::DurationMarker,
+Body: TSession<IO> + ReplaceLabelled<IO, Target, New> + FindLabel<Target>,
+OnTimeout: TSession<IO> + ReplaceAfter<IO, Target, New, <Body as FindLabel<Target>>::Found>,
+(): ReplaceCase<IO, <Lbl as LabelEq<Target>>::Output, New, TTimeout<IO, Lbl, Dur, <Body as ReplaceLabelled<IO, Target, New>>::Output, <OnTimeout as ReplaceAfter<IO, Target, New, <Body as FindLabel<Target>>::Found>>::Output>>,
+{
+type Output = <() as ReplaceCase<IO, <Lbl as LabelEq<Target>>::Output, New, TTimeout<IO, Lbl, Dur, <Body as ReplaceLabelled<IO, Target, New>>::Output, <OnTimeout as ReplaceAfter<IO, Target, New, <Body as FindLabel<Target>>::Found>>::Output>>>::Output;
+}
+
+impl<IO, Lbl, R, Dur, T, Target, New> ReplaceLabelled<IO, Target, New>
+    for TDelay<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel + LabelEq<Target>,
+    Dur: types::DurationMarker,
+    T: TSession<IO> + ReplaceLabelled<IO, Target, New>,
+    (): ReplaceCase<
+        IO,
+        <Lbl as LabelEq<Target>>::Output,
+        New,
+        TDelay<IO, Lbl, R, Dur, <T as ReplaceLabelled<IO, Target, New>>::Output>,
+    >,
+{
+    type Output = <() as ReplaceCase<
+        IO,
+        <Lbl as LabelEq<Target>>::Output,
+        New,
+        TDelay<IO, Lbl, R, Dur, <T as ReplaceLabelled<IO, Target, New>>::Output>,
+    >>::Output;
+}
+
+impl<IO, Lbl, R, Dur, T, Target, New> ReplaceLabelled<IO, Target, New>
+    for TDeadline<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel + LabelEq<Target>,
+    Dur: types::DurationMarker,
+    T: TSession<IO> + ReplaceLabelled<IO, Target, New>,
+    (): ReplaceCase<
+        IO,
+        <Lbl as LabelEq<Target>>::Output,
+        New,
+        TDeadline<IO, Lbl, R, Dur, <T as ReplaceLabelled<IO, Target, New>>::Output>,
+    >,
+{
+    type Output = <() as ReplaceCase<
+        IO,
+        <Lbl as LabelEq<Target>>::Output,
+        New,
+        TDeadline<IO, Lbl, R, Dur, <T as ReplaceLabelled<IO, Target, New>>::Output>,
+    >>::Output;
+}
+
+impl<IO, Lbl, Scope, Interruptor, Handler, Target, New> ReplaceLabelled<IO, Target, New>
+    for TInterrupt<IO, Lbl, Scope, Interruptor, Handler>
+where
+    Lbl: types::ProtocolLabel + LabelEq<Target>,
+    Scope: TSession<IO> + ReplaceLabelled<IO, Target, New> + FindLabel<Target>,
+    Handler: TSession<IO> + ReplaceAfter<IO, Target, New, <Scope as FindLabel<Target>>::Found>,
+    (): ReplaceCase<
+        IO,
+        <Lbl as LabelEq<Target>>::Output,
+        New,
+        TInterrupt<
+            IO,
+            Lbl,
+            <Scope as ReplaceLabelled<IO, Target, New>>::Output,
+            Interruptor,
+            <Handler as ReplaceAfter<IO, Target, New, <Scope as FindLabel<Target>>::Found>>::Output,
+        >,
+    >,
+{
+    type Output = <() as ReplaceCase<
+        IO,
+        <Lbl as LabelEq<Target>>::Output,
+        New,
+        TInterrupt<
+            IO,
+            Lbl,
+            <Scope as ReplaceLabelled<IO, Target, New>>::Output,
+            Interruptor,
+            <Handler as ReplaceAfter<IO, Target, New, <Scope as FindLabel<Target>>::Found>>::Output,
+        >,
+    >>::Output;
+}
+
+impl<IO, Lbl, Decider, Body, Cont, Target, New> ReplaceLabelled<IO, Target, New>
+    for TOptional<IO, Lbl, Decider, Body, Cont>
+where
+    Lbl: types::ProtocolLabel + LabelEq<Target>,
+    Body: TSession<IO> + ReplaceLabelled<IO, Target, New> + FindLabel<Target>,
+    Cont: TSession<IO> + ReplaceAfter<IO, Target, New, <Body as FindLabel<Target>>::Found>,
+    (): ReplaceCase<
+        IO,
+        <Lbl as LabelEq<Target>>::Output,
+        New,
+        TOptional<
+            IO,
+            Lbl,
+            Decider,
+            <Body as ReplaceLabelled<IO, Target, New>>::Output,
+            <Cont as ReplaceAfter<IO, Target, New, <Body as FindLabel<Target>>::Found>>::Output,
+        >,
+    >,
+{
+    type Output = <() as ReplaceCase<
+        IO,
+        <Lbl as LabelEq<Target>>::Output,
+        New,
+        TOptional<
+            IO,
+            Lbl,
+            Decider,
+            <Body as ReplaceLabelled<IO, Target, New>>::Output,
+            <Cont as ReplaceAfter<IO, Target, New, <Body as FindLabel<Target>>::Found>>::Output,
+        >,
+    >>::Output;
+}
+
+impl<IO, Pred, Inner, Target, New> ReplaceLabelled<IO, Target, New> for TRefine<Pred, Inner>
+where
+    Pred: types::Predicate,
+    Inner: ReplaceLabelled<IO, Target, New>,
+{
+    type Output = TRefine<Pred, <Inner as ReplaceLabelled<IO, Target, New>>::Output>;
+}
+
+impl<IO, Meta, Inner, Target, New> ReplaceLabelled<IO, Target, New> for TAnnotate<Meta, Inner>
+where
+    Inner: ReplaceLabelled<IO, Target, New>,
+{
+    type Output = TAnnotate<Meta, <Inner as ReplaceLabelled<IO, Target, New>>::Output>;
+}
+
+impl<IO, Lbl, R, Err, Target, New> ReplaceLabelled<IO, Target, New> for TThrow<IO, Lbl, R, Err>
+where
+    Lbl: types::ProtocolLabel + LabelEq<Target>,
+    (): ReplaceCase<IO, <Lbl as LabelEq<Target>>::Output, New, TThrow<IO, Lbl, R, Err>>,
+{
+    type Output = <() as ReplaceCase<
+        IO,
+        <Lbl as LabelEq<Target>>::Output,
+        New,
+        TThrow<IO, Lbl, R, Err>,
+    >>::Output;
+}
+
+impl<IO, Lbl, Body, Handler, Target, New> ReplaceLabelled<IO, Target, New>
+    for TCatch<IO, Lbl, Body, Handler>
+where
+    Lbl: types::ProtocolLabel + LabelEq<Target>,
+    Body: TSession<IO> + ReplaceLabelled<IO, Target, New> + FindLabel<Target>,
+    Handler: TSession<IO> + ReplaceAfter<IO, Target, New, <Body as FindLabel<Target>>::Found>,
+    (): ReplaceCase<
+        IO,
+        <Lbl as LabelEq<Target>>::Output,
+        New,
+        TCatch<
+            IO,
+            Lbl,
+            <Body as ReplaceLabelled<IO, Target, New>>::Output,
+            <Handler as ReplaceAfter<IO, Target, New, <Body as FindLabel<Target>>::Found>>::Output,
+        >,
+    >,
+{
+    type Output = <() as ReplaceCase<
+        IO,
+        <Lbl as LabelEq<Target>>::Output,
+        New,
+        TCatch<
+            IO,
+            Lbl,
+            <Body as ReplaceLabelled<IO, Target, New>>::Output,
+            <Handler as ReplaceAfter<IO, Target, New, <Body as FindLabel<Target>>::Found>>::Output,
+        >,
+    >>::Output;
+}
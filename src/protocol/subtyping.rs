@@ -0,0 +1,433 @@
+//! # Session Subtyping
+//!
+//! This module implements session subtyping for local session types. A local
+//! type `S` is a subtype of `T` when an endpoint written against `S` can be
+//! used wherever one following `T` is expected:
+//!
+//! - an offer may handle more branches than required,
+//! - a selection may pick from fewer branches than allowed,
+//! - every other construct must match exactly, with subtyping applied to its
+//!   continuations and branches.
+//!
+//! Branches of offers and selections are matched by label, using `LabelEq`;
+//! their order does not matter. Payloads, roles and labels are compared for
+//! equality.
+//!
+//! Over an asynchronous transport a send never waits for its peer, so an
+//! endpoint may also send ahead of receives that the expected type performs
+//! first. This is opt-in: `AsyncSubtype` checks hand-optimized endpoints that
+//! reorder their sends this way.
+//!
+//! Key components:
+//!
+//! - `SubtypeOf`: Session subtyping between two local session types
+//! - `Synchronous` / `Asynchronous`: Whether sends may move ahead of receives
+//! - `AsyncSubtype`: Session subtyping for asynchronous transports
+//! - `AnticipateSend`: A local type with a send pulled ahead of the receives before it
+//! - `EachSubtypeOf`: Pointwise subtyping between two type-level lists
+//! - `FindBranch`: The first branch of a type-level list headed by a given label
+//! - `SelectsWithin`: Every branch of a list has a matching supertype branch
+//! - `OffersAtLeast`: Every branch of a list is matched by a subtype branch
+//! - `UnmatchedBranch` / `SubtypingFailure`: The failure marker naming a branch without a counterpart
+
+use super::base::{Cons, Nil};
+use super::local::*;
+use super::transforms::GetLocalLabel;
+use crate::types;
+use core::marker::PhantomData;
+
+/// Subtyping mode: sends and receives happen in the order written.
+pub struct Synchronous;
+
+/// Subtyping mode: a send may happen before receives that precede it in the
+/// expected type.
+pub struct Asynchronous;
+
+/// Session subtyping: `Self` can be used where `Super` is expected.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Pick; impl ProtocolLabel for Pick {}
+/// struct Get; impl ProtocolLabel for Get {}
+/// struct Put; impl ProtocolLabel for Put {}
+/// impl LabelEq<Get> for Get { type Output = True; }
+/// impl LabelEq<Put> for Get { type Output = False; }
+/// impl LabelEq<Get> for Put { type Output = False; }
+/// impl LabelEq<Put> for Put { type Output = True; }
+///
+/// type GetOnly = EpRecv<Http, Get, TServer, Message, EpEnd<Http, EmptyLabel, TServer>>;
+/// type PutOnly = EpRecv<Http, Put, TServer, Message, EpEnd<Http, EmptyLabel, TServer>>;
+/// type Narrow = EpOffer<Http, Pick, TServer, tlist!(GetOnly)>;
+/// type Broad = EpOffer<Http, Pick, TServer, tlist!(PutOnly, GetOnly)>;
+/// // A server handling both requests can stand in for one handling only `Get`
+/// assert_subtype!(Broad, Narrow);
+/// ```
+pub trait SubtypeOf<Super, Mode = Synchronous> {}
+
+/// Asynchronous session subtyping: `Self` can be used where `Super` is
+/// expected, over a transport where sends do not wait for their peer.
+///
+/// On top of [`SubtypeOf`], a send may be moved ahead of the receives that
+/// precede it in `Super`. Check an optimized endpoint against the projection
+/// it replaces with [`assert_async_subtype!`](crate::assert_async_subtype).
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Ping; impl ProtocolLabel for Ping {}
+/// struct Pong; impl ProtocolLabel for Pong {}
+///
+/// type End = EpEnd<Http, EmptyLabel, TClient>;
+/// type Canonical = EpRecv<Http, Pong, TClient, Message, EpSend<Http, Ping, TClient, Message, End>>;
+/// type Optimized = EpSend<Http, Ping, TClient, Message, EpRecv<Http, Pong, TClient, Message, End>>;
+/// assert_async_subtype!(Optimized, Canonical);
+/// ```
+pub trait AsyncSubtype<Super> {}
+
+impl<Sub: SubtypeOf<Super, Asynchronous>, Super> AsyncSubtype<Super> for Sub {}
+
+/// `Self` with its first send, labelled `Lbl` with payload `H`, pulled ahead
+/// of the receives before it.
+///
+/// `Rest` is what remains once that send has happened: the receives, in
+/// their order, followed by the continuation of the send.
+pub trait AnticipateSend<IO, Lbl, R, H> {
+    type Rest;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R, H, T> AnticipateSend<IO, Lbl, R, H>
+    for EpSend<IO, Lbl, R, H, T>
+{
+    type Rest = T;
+}
+
+impl<IO, Lbl, R, H, RecvLbl, RecvH, T> AnticipateSend<IO, Lbl, R, H>
+    for EpRecv<IO, RecvLbl, R, RecvH, T>
+where
+    RecvLbl: types::ProtocolLabel,
+    T: AnticipateSend<IO, Lbl, R, H>,
+{
+    type Rest = EpRecv<IO, RecvLbl, R, RecvH, <T as AnticipateSend<IO, Lbl, R, H>>::Rest>;
+}
+
+/// Pointwise session subtyping between two type-level lists of the same length.
+pub trait EachSubtypeOf<Super, Mode = Synchronous> {}
+
+impl<Mode> EachSubtypeOf<Nil, Mode> for Nil {}
+
+impl<H: SubtypeOf<SH, Mode>, T: EachSubtypeOf<ST, Mode>, SH, ST, Mode>
+    EachSubtypeOf<Cons<SH, ST>, Mode> for Cons<H, T>
+{
+}
+
+/// Failure marker: a branch labelled `Lbl` has no branch with the same label
+/// to match, so an offer lacks a branch it must handle or a selection picks
+/// a branch it may not.
+pub struct UnmatchedBranch<Lbl>(PhantomData<Lbl>);
+
+/// Never implemented: a bound `Marker: SubtypingFailure` fails and names the
+/// branch [`SubtypeOf`] could not match.
+#[diagnostic::on_unimplemented(
+    message = "not a subtype: a branch has no counterpart with the same label: `{Self}`",
+    label = "no branch with this label to match",
+    note = "an offer must handle every branch of the offer it replaces, and a selection may only pick branches the selection it replaces allows"
+)]
+pub trait SubtypingFailure {
+    /// The matching branch, which does not exist.
+    type Branch;
+}
+
+/// The first branch of a type-level list whose label is `L`.
+///
+/// Fails naming [`UnmatchedBranch`] if no branch has the label `L`.
+pub trait FindBranch<L> {
+    type Output;
+}
+
+/// Helper trait for dispatching `FindBranch` on whether the head matches.
+pub trait FindBranchCase<IsMatch, L> {
+    type Output;
+}
+
+impl<L> FindBranch<L> for Nil
+where
+    UnmatchedBranch<L>: SubtypingFailure,
+{
+    type Output = <UnmatchedBranch<L> as SubtypingFailure>::Branch;
+}
+
+impl<H, T, L> FindBranch<L> for Cons<H, T>
+where
+    H: GetLocalLabel,
+    <H as GetLocalLabel>::Label: LabelEq<L>,
+    Cons<H, T>: FindBranchCase<<<H as GetLocalLabel>::Label as LabelEq<L>>::Output, L>,
+{
+    type Output = <Cons<H, T> as FindBranchCase<
+        <<H as GetLocalLabel>::Label as LabelEq<L>>::Output,
+        L,
+    >>::Output;
+}
+
+impl<H, T, L> FindBranchCase<types::True, L> for Cons<H, T> {
+    type Output = H;
+}
+
+impl<H, T: FindBranch<L>, L> FindBranchCase<types::False, L> for Cons<H, T> {
+    type Output = <T as FindBranch<L>>::Output;
+}
+
+/// Every branch of `Self` is a subtype of the branch of `Super` with the same label.
+///
+/// Used for selections: the subtype may choose from fewer branches.
+pub trait SelectsWithin<Super, Mode = Synchronous> {}
+
+impl<Super, Mode> SelectsWithin<Super, Mode> for Nil {}
+
+impl<H, T, Super, Mode> SelectsWithin<Super, Mode> for Cons<H, T>
+where
+    H: GetLocalLabel,
+    Super: FindBranch<<H as GetLocalLabel>::Label>,
+    H: SubtypeOf<<Super as FindBranch<<H as GetLocalLabel>::Label>>::Output, Mode>,
+    T: SelectsWithin<Super, Mode>,
+{
+}
+
+/// Every branch of `Super` has a branch of `Self` with the same label that is
+/// a subtype of it.
+///
+/// Used for offers: the subtype may handle more branches.
+pub trait OffersAtLeast<Super, Mode = Synchronous> {}
+
+impl<Sub, Mode> OffersAtLeast<Nil, Mode> for Sub {}
+
+impl<Sub, H, T, Mode> OffersAtLeast<Cons<H, T>, Mode> for Sub
+where
+    H: GetLocalLabel,
+    Sub: FindBranch<<H as GetLocalLabel>::Label>,
+    <Sub as FindBranch<<H as GetLocalLabel>::Label>>::Output: SubtypeOf<H, Mode>,
+    Sub: OffersAtLeast<T, Mode>,
+{
+}
+
+// Leaves
+
+impl<IO, Lbl: types::ProtocolLabel, R, Mode> SubtypeOf<EpEnd<IO, Lbl, R>, Mode>
+    for EpEnd<IO, Lbl, R>
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R, Mode> SubtypeOf<EpSkip<IO, Lbl, R>, Mode>
+    for EpSkip<IO, Lbl, R>
+{
+}
+
+impl<Var, Mode> SubtypeOf<EpVar<Var>, Mode> for EpVar<Var> {}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Err, Mode> SubtypeOf<EpThrow<IO, Lbl, Me, Err>, Mode>
+    for EpThrow<IO, Lbl, Me, Err>
+{
+}
+
+// Communication
+
+impl<IO, Lbl: types::ProtocolLabel, R, H, T: SubtypeOf<ST>, ST>
+    SubtypeOf<EpSend<IO, Lbl, R, H, ST>, Synchronous> for EpSend<IO, Lbl, R, H, T>
+{
+}
+
+// The send may be found further down `Super`, behind receives
+impl<IO, Lbl: types::ProtocolLabel, R, H, T, Super> SubtypeOf<Super, Asynchronous>
+    for EpSend<IO, Lbl, R, H, T>
+where
+    Super: AnticipateSend<IO, Lbl, R, H>,
+    T: SubtypeOf<<Super as AnticipateSend<IO, Lbl, R, H>>::Rest, Asynchronous>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R, H, T: SubtypeOf<ST, Mode>, ST, Mode>
+    SubtypeOf<EpRecv<IO, Lbl, R, H, ST>, Mode> for EpRecv<IO, Lbl, R, H, T>
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, ToSet, H, T: SubtypeOf<ST, Mode>, ST, Mode>
+    SubtypeOf<EpBroadcast<IO, Lbl, Me, ToSet, H, ST>, Mode>
+    for EpBroadcast<IO, Lbl, Me, ToSet, H, T>
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, FromSet, H, T: SubtypeOf<ST, Mode>, ST, Mode>
+    SubtypeOf<EpGather<IO, Lbl, Me, FromSet, H, ST>, Mode>
+    for EpGather<IO, Lbl, Me, FromSet, H, T>
+{
+}
+
+impl<IO, Lbl, Me, DelegatedEp, Cont, SuperCont, Mode>
+    SubtypeOf<EpDelegate<IO, Lbl, Me, DelegatedEp, SuperCont>, Mode>
+    for EpDelegate<IO, Lbl, Me, DelegatedEp, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Cont: SubtypeOf<SuperCont, Mode>,
+{
+}
+
+// Branching
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches, SuperBranches, Mode>
+    SubtypeOf<EpSelect<IO, Lbl, Me, SuperBranches>, Mode> for EpSelect<IO, Lbl, Me, Branches>
+where
+    Branches: SelectsWithin<SuperBranches, Mode>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches, SuperBranches, Mode>
+    SubtypeOf<EpChoiceN<IO, Lbl, Me, SuperBranches>, Mode> for EpChoiceN<IO, Lbl, Me, Branches>
+where
+    Branches: SelectsWithin<SuperBranches, Mode>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches, SuperBranches, Mode>
+    SubtypeOf<EpOffer<IO, Lbl, Me, SuperBranches>, Mode> for EpOffer<IO, Lbl, Me, Branches>
+where
+    Branches: OffersAtLeast<SuperBranches, Mode>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Body, Cont, SuperBody, SuperCont, Mode>
+    SubtypeOf<EpOptionalSelect<IO, Lbl, Me, SuperBody, SuperCont>, Mode>
+    for EpOptionalSelect<IO, Lbl, Me, Body, Cont>
+where
+    Body: SubtypeOf<SuperBody, Mode>,
+    Cont: SubtypeOf<SuperCont, Mode>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Body, Cont, SuperBody, SuperCont, Mode>
+    SubtypeOf<EpOptionalOffer<IO, Lbl, Me, SuperBody, SuperCont>, Mode>
+    for EpOptionalOffer<IO, Lbl, Me, Body, Cont>
+where
+    Body: SubtypeOf<SuperBody, Mode>,
+    Cont: SubtypeOf<SuperCont, Mode>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, High, Low, SuperHigh, SuperLow, Mode>
+    SubtypeOf<EpPriorityOffer<IO, Lbl, Me, SuperHigh, SuperLow>, Mode>
+    for EpPriorityOffer<IO, Lbl, Me, High, Low>
+where
+    High: SubtypeOf<SuperHigh, Mode>,
+    Low: SubtypeOf<SuperLow, Mode>,
+{
+}
+
+// Concurrency
+
+impl<IO, Lbl: types::ProtocolLabel, Me, L, R, SuperL, SuperR, Mode>
+    SubtypeOf<EpPar<IO, Lbl, Me, SuperL, SuperR>, Mode> for EpPar<IO, Lbl, Me, L, R>
+where
+    L: SubtypeOf<SuperL, Mode>,
+    R: SubtypeOf<SuperR, Mode>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches, SuperBranches, Mode>
+    SubtypeOf<EpParN<IO, Lbl, Me, SuperBranches>, Mode> for EpParN<IO, Lbl, Me, Branches>
+where
+    Branches: EachSubtypeOf<SuperBranches, Mode>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Steps, SuperSteps, Mode>
+    SubtypeOf<EpShuffle<IO, Lbl, Me, SuperSteps>, Mode> for EpShuffle<IO, Lbl, Me, Steps>
+where
+    Steps: EachSubtypeOf<SuperSteps, Mode>,
+{
+}
+
+// Recursion
+
+impl<IO, Lbl: types::ProtocolLabel, Var, Body: SubtypeOf<SuperBody, Mode>, SuperBody, Mode>
+    SubtypeOf<EpRec<IO, Lbl, Var, SuperBody>, Mode> for EpRec<IO, Lbl, Var, Body>
+{
+}
+
+// Timing, interrupts and failures
+
+impl<IO, Lbl, Me, Dur, Body, OnTimeout, SuperBody, SuperOnTimeout, Mode>
+    SubtypeOf<EpTimeout<IO, Lbl, Me, Dur, SuperBody, SuperOnTimeout>, Mode>
+    for EpTimeout<IO, Lbl, Me, Dur, Body, OnTimeout>
+where
+    Lbl: types::ProtocolLabel,
+    Body: SubtypeOf<SuperBody, Mode>,
+    OnTimeout: SubtypeOf<SuperOnTimeout, Mode>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Dur, T: SubtypeOf<ST, Mode>, ST, Mode>
+    SubtypeOf<EpDelay<IO, Lbl, Me, Dur, ST>, Mode> for EpDelay<IO, Lbl, Me, Dur, T>
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Dur, T: SubtypeOf<ST, Mode>, ST, Mode>
+    SubtypeOf<EpDeadline<IO, Lbl, Me, Dur, ST>, Mode> for EpDeadline<IO, Lbl, Me, Dur, T>
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Scope, Handler, SuperScope, SuperHandler, Mode>
+    SubtypeOf<EpInterrupt<IO, Lbl, Me, SuperScope, SuperHandler>, Mode>
+    for EpInterrupt<IO, Lbl, Me, Scope, Handler>
+where
+    Scope: SubtypeOf<SuperScope, Mode>,
+    Handler: SubtypeOf<SuperHandler, Mode>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Scope, Handler, SuperScope, SuperHandler, Mode>
+    SubtypeOf<EpInterruptible<IO, Lbl, Me, SuperScope, SuperHandler>, Mode>
+    for EpInterruptible<IO, Lbl, Me, Scope, Handler>
+where
+    Scope: SubtypeOf<SuperScope, Mode>,
+    Handler: SubtypeOf<SuperHandler, Mode>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Body, Handler, SuperBody, SuperHandler, Mode>
+    SubtypeOf<EpCatch<IO, Lbl, Me, SuperBody, SuperHandler>, Mode>
+    for EpCatch<IO, Lbl, Me, Body, Handler>
+where
+    Body: SubtypeOf<SuperBody, Mode>,
+    Handler: SubtypeOf<SuperHandler, Mode>,
+{
+}
+
+impl<Pred, Inner: SubtypeOf<SuperInner, Mode>, SuperInner, Mode>
+    SubtypeOf<EpRefine<Pred, SuperInner>, Mode> for EpRefine<Pred, Inner>
+{
+}
+
+// Connections
+
+impl<
+        IO,
+        Lbl: types::ProtocolLabel,
+        Me,
+        Peer,
+        Cont: SubtypeOf<SuperCont, Mode>,
+        SuperCont,
+        Mode,
+    > SubtypeOf<EpClose<IO, Lbl, Me, Peer, SuperCont>, Mode> for EpClose<IO, Lbl, Me, Peer, Cont>
+{
+}
+
+impl<
+        IO,
+        Lbl: types::ProtocolLabel,
+        Me,
+        Peer,
+        Cont: SubtypeOf<SuperCont, Mode>,
+        SuperCont,
+        Mode,
+    > SubtypeOf<EpOpen<IO, Lbl, Me, Peer, SuperCont>, Mode> for EpOpen<IO, Lbl, Me, Peer, Cont>
+{
+}
@@ -0,0 +1,545 @@
+//! # Global Synthesis
+//!
+//! Projection derives local types from a global protocol. This module goes the
+//! other way: given the local type of every role, it reconstructs a global
+//! protocol whose projections they are, or fails to compile if the local types
+//! cannot be paired up. This supports bottom-up workflows where the endpoint
+//! types already exist in services and the choreography view is wanted.
+//!
+//! Key components:
+//!
+//! - `Synthesize`: Reconstructs a global protocol from a list of `RoleLocal` entries
+//! - `SynthesizeFrom` / `SynthesizeCandidate` / `SynthesizeSendCase`: The search for the next message
+//! - `TakeReceive`: Finds and consumes the first receive of a label
+//! - `TakeReceiveCase` / `TakeReceiveLabelCase`: Helper traits for `TakeReceive`
+//! - `ReplaceLocal` / `ReplaceLocalCase`: Replaces the local type of one role
+//! - `ChoiceShape`: Tells selections apart from the other supported local steps
+//! - `SynthesizeChoiceFrom` / `SynthesizeChoiceCase`: The search for the next selection
+//! - `SynthesizeBranches`: Synthesizes the branches of a selection one by one
+//! - `TakeBranch` / `TakeBranches`: Takes the first branch of a choice
+//! - `NoBranchLeft` / `NoBranchesLeft`: Every branch of a choice has been taken
+//! - `AllEnded`: Every role has finished
+//!
+//! The input uses the same `RoleLocal<Role, Local>` entries that `ProjectAll`
+//! produces, so synthesizing the projections of a protocol gives back a
+//! protocol with the same projections. Synthesis covers `EpSend`, `EpRecv`,
+//! `EpSelect`, `EpOffer`, `EpSkip` and `EpEnd`. At each step the first role, in
+//! list order, whose local type starts with a send that some other role is
+//! ready to receive becomes the sender of a `TMsg`; the first role ready to
+//! receive that label is the receiver, and its payload must match.
+//!
+//! Once no message can go, the first role whose local type is an `EpSelect`
+//! becomes the chooser of a `TSelect`. Every other role must then offer the
+//! choice with the same label, skip it or have finished. The `n`-th branch of
+//! the `TSelect` is synthesized from the `n`-th branch of every `EpSelect` and
+//! `EpOffer`, so all of them need the same number of branches. Synthesis ends
+//! with a `TEnd` once every role has reached `EpEnd` or `EpSkip`.
+//!
+//! Labels are compared with `LabelEq` and roles with `RoleEq`.
+
+use super::base::{Cons, Nil};
+use super::global::{SessionList, TEnd, TMsg, TSelect, TSession};
+use super::local::*;
+use super::transforms::RoleLocal;
+use super::validity::{ActionShape, OtherAction, RecvAction, SendAction};
+use crate::types;
+
+/// Reconstructs a global protocol over `IO` from the local type of every role.
+///
+/// Implemented for type-level lists of [`RoleLocal`] entries whose local types
+/// use sends, receives, selections, offers, skips and ends only.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Alice; struct Bob; struct Ping; struct Pong;
+/// impl Role for Alice {} impl Role for Bob {}
+/// impl ProtocolLabel for Ping {} impl ProtocolLabel for Pong {}
+/// impl RoleEq<Alice> for Alice { type Output = True; }
+/// impl RoleEq<Bob> for Alice   { type Output = False; }
+/// impl RoleEq<Alice> for Bob   { type Output = False; }
+/// impl RoleEq<Bob> for Bob     { type Output = True; }
+/// impl LabelEq<Ping> for Ping { type Output = True; }
+/// impl LabelEq<Pong> for Ping { type Output = False; }
+/// impl LabelEq<Ping> for Pong { type Output = False; }
+/// impl LabelEq<Pong> for Pong { type Output = True; }
+///
+/// type AliceEnd = EpEnd<Http, EmptyLabel, Alice>;
+/// type BobEnd = EpEnd<Http, EmptyLabel, Bob>;
+/// type Locals = tlist!(
+///     RoleLocal<Alice, EpSend<Http, Ping, Alice, Message, EpRecv<Http, Pong, Alice, Response, AliceEnd>>>,
+///     RoleLocal<Bob, EpRecv<Http, Ping, Bob, Message, EpSend<Http, Pong, Bob, Response, BobEnd>>>,
+/// );
+/// assert_type_eq!(
+///     <Locals as Synthesize<Http>>::Output,
+///     TMsg<Http, Ping, Alice, Bob, Message, TMsg<Http, Pong, Bob, Alice, Response, TEnd<Http>>>
+/// );
+/// ```
+pub trait Synthesize<IO> {
+    type Output: TSession<IO>;
+}
+
+impl<IO, Locals> Synthesize<IO> for Locals
+where
+    (): SynthesizeFrom<IO, Locals, Locals>,
+{
+    type Output = <() as SynthesizeFrom<IO, Locals, Locals>>::Output;
+}
+
+/// Helper trait for [`Synthesize`]: looks for the next message among the
+/// `Candidates` entries of `All`.
+pub trait SynthesizeFrom<IO, All, Candidates> {
+    type Output: TSession<IO>;
+}
+
+// No role can send: look for a role that selects
+impl<IO, All> SynthesizeFrom<IO, All, Nil> for ()
+where
+    (): SynthesizeChoiceFrom<IO, All, All>,
+{
+    type Output = <() as SynthesizeChoiceFrom<IO, All, All>>::Output;
+}
+
+impl<IO, All, R, L, T> SynthesizeFrom<IO, All, Cons<RoleLocal<R, L>, T>> for ()
+where
+    L: ActionShape,
+    (): SynthesizeCandidate<IO, All, R, L, T, <L as ActionShape>::Shape>,
+{
+    type Output = <() as SynthesizeCandidate<IO, All, R, L, T, <L as ActionShape>::Shape>>::Output;
+}
+
+/// Helper trait for [`SynthesizeFrom`]: dispatches on the leading action of
+/// the candidate role `R`.
+pub trait SynthesizeCandidate<IO, All, R, L, T, Shape> {
+    type Output: TSession<IO>;
+}
+
+// A role that starts by receiving cannot send next
+impl<IO, All, R, L, T, Lbl> SynthesizeCandidate<IO, All, R, L, T, RecvAction<Lbl>> for ()
+where
+    (): SynthesizeFrom<IO, All, T>,
+{
+    type Output = <() as SynthesizeFrom<IO, All, T>>::Output;
+}
+
+// A role that has finished cannot send next
+impl<IO, All, R, L, T> SynthesizeCandidate<IO, All, R, L, T, OtherAction> for ()
+where
+    (): SynthesizeFrom<IO, All, T>,
+{
+    type Output = <() as SynthesizeFrom<IO, All, T>>::Output;
+}
+
+// A role that starts by sending: look for a role ready to receive the label
+impl<IO, All, R, L, T, Lbl> SynthesizeCandidate<IO, All, R, L, T, SendAction<Lbl>> for ()
+where
+    All: TakeReceive<Lbl>,
+    (): SynthesizeSendCase<IO, All, R, L, T, <All as TakeReceive<Lbl>>::Found>,
+{
+    type Output =
+        <() as SynthesizeSendCase<IO, All, R, L, T, <All as TakeReceive<Lbl>>::Found>>::Output;
+}
+
+/// Helper trait for [`SynthesizeCandidate`]: dispatches on whether some role
+/// is ready to receive what `R` sends.
+pub trait SynthesizeSendCase<IO, All, R, L, T, Found> {
+    type Output: TSession<IO>;
+}
+
+// Nobody receives the label yet: try the next candidate
+impl<IO, All, R, L, T> SynthesizeSendCase<IO, All, R, L, T, types::False> for ()
+where
+    (): SynthesizeFrom<IO, All, T>,
+{
+    type Output = <() as SynthesizeFrom<IO, All, T>>::Output;
+}
+
+// Pair the send with the first matching receive and continue with both advanced
+impl<IO, All, R, Lbl, H, Cont, T>
+    SynthesizeSendCase<IO, All, R, EpSend<IO, Lbl, R, H, Cont>, T, types::True> for ()
+where
+    Lbl: types::ProtocolLabel,
+    All: TakeReceive<Lbl>,
+    <All as TakeReceive<Lbl>>::Payload: types::TypeEq<H>,
+    <All as TakeReceive<Lbl>>::Rest: ReplaceLocal<R, Cont>,
+    (): SynthesizeFrom<
+        IO,
+        <<All as TakeReceive<Lbl>>::Rest as ReplaceLocal<R, Cont>>::Output,
+        <<All as TakeReceive<Lbl>>::Rest as ReplaceLocal<R, Cont>>::Output,
+    >,
+{
+    type Output = TMsg<
+        IO,
+        Lbl,
+        R,
+        <All as TakeReceive<Lbl>>::To,
+        H,
+        <() as SynthesizeFrom<
+            IO,
+            <<All as TakeReceive<Lbl>>::Rest as ReplaceLocal<R, Cont>>::Output,
+            <<All as TakeReceive<Lbl>>::Rest as ReplaceLocal<R, Cont>>::Output,
+        >>::Output,
+    >;
+}
+
+/// Finds the first entry of a list of [`RoleLocal`] entries whose local type
+/// starts by receiving `Lbl`.
+///
+/// `Found` tells whether there is one. If so, `To` is its role, `Payload` the
+/// received type and `Rest` the list with that entry advanced past the receive.
+pub trait TakeReceive<Lbl> {
+    type Found: types::Bool;
+    type To;
+    type Payload;
+    type Rest;
+}
+
+impl<Lbl> TakeReceive<Lbl> for Nil {
+    type Found = types::False;
+    type To = ();
+    type Payload = ();
+    type Rest = Nil;
+}
+
+impl<Lbl, R, L, T> TakeReceive<Lbl> for Cons<RoleLocal<R, L>, T>
+where
+    L: ActionShape,
+    (): TakeReceiveCase<Lbl, R, L, T, <L as ActionShape>::Shape>,
+{
+    type Found = <() as TakeReceiveCase<Lbl, R, L, T, <L as ActionShape>::Shape>>::Found;
+    type To = <() as TakeReceiveCase<Lbl, R, L, T, <L as ActionShape>::Shape>>::To;
+    type Payload = <() as TakeReceiveCase<Lbl, R, L, T, <L as ActionShape>::Shape>>::Payload;
+    type Rest = <() as TakeReceiveCase<Lbl, R, L, T, <L as ActionShape>::Shape>>::Rest;
+}
+
+/// Helper trait for [`TakeReceive`]: dispatches on the leading action of the
+/// head entry.
+pub trait TakeReceiveCase<Lbl, R, L, T, Shape> {
+    type Found: types::Bool;
+    type To;
+    type Payload;
+    type Rest;
+}
+
+// The head starts by receiving: compare the labels
+impl<Lbl, R, L, T, Lbl2> TakeReceiveCase<Lbl, R, L, T, RecvAction<Lbl2>> for ()
+where
+    Lbl2: LabelEq<Lbl>,
+    (): TakeReceiveLabelCase<Lbl, R, L, T, <Lbl2 as LabelEq<Lbl>>::Output>,
+{
+    type Found = <() as TakeReceiveLabelCase<Lbl, R, L, T, <Lbl2 as LabelEq<Lbl>>::Output>>::Found;
+    type To = <() as TakeReceiveLabelCase<Lbl, R, L, T, <Lbl2 as LabelEq<Lbl>>::Output>>::To;
+    type Payload =
+        <() as TakeReceiveLabelCase<Lbl, R, L, T, <Lbl2 as LabelEq<Lbl>>::Output>>::Payload;
+    type Rest = <() as TakeReceiveLabelCase<Lbl, R, L, T, <Lbl2 as LabelEq<Lbl>>::Output>>::Rest;
+}
+
+// The head starts by sending: keep it and search the tail
+impl<Lbl, R, L, T, Lbl2> TakeReceiveCase<Lbl, R, L, T, SendAction<Lbl2>> for ()
+where
+    T: TakeReceive<Lbl>,
+{
+    type Found = <T as TakeReceive<Lbl>>::Found;
+    type To = <T as TakeReceive<Lbl>>::To;
+    type Payload = <T as TakeReceive<Lbl>>::Payload;
+    type Rest = Cons<RoleLocal<R, L>, <T as TakeReceive<Lbl>>::Rest>;
+}
+
+// The head has finished: keep it and search the tail
+impl<Lbl, R, L, T> TakeReceiveCase<Lbl, R, L, T, OtherAction> for ()
+where
+    T: TakeReceive<Lbl>,
+{
+    type Found = <T as TakeReceive<Lbl>>::Found;
+    type To = <T as TakeReceive<Lbl>>::To;
+    type Payload = <T as TakeReceive<Lbl>>::Payload;
+    type Rest = Cons<RoleLocal<R, L>, <T as TakeReceive<Lbl>>::Rest>;
+}
+
+/// Helper trait for [`TakeReceiveCase`]: dispatches on whether the head
+/// receives `Lbl`.
+pub trait TakeReceiveLabelCase<Lbl, R, L, T, Matches> {
+    type Found: types::Bool;
+    type To;
+    type Payload;
+    type Rest;
+}
+
+// The head receives `Lbl`: consume the receive
+impl<IO, Lbl, R, H, Cont, T>
+    TakeReceiveLabelCase<Lbl, R, EpRecv<IO, Lbl, R, H, Cont>, T, types::True> for ()
+where
+    Lbl: types::ProtocolLabel,
+{
+    type Found = types::True;
+    type To = R;
+    type Payload = H;
+    type Rest = Cons<RoleLocal<R, Cont>, T>;
+}
+
+// The head receives another label: keep it and search the tail
+impl<Lbl, R, L, T> TakeReceiveLabelCase<Lbl, R, L, T, types::False> for ()
+where
+    T: TakeReceive<Lbl>,
+{
+    type Found = <T as TakeReceive<Lbl>>::Found;
+    type To = <T as TakeReceive<Lbl>>::To;
+    type Payload = <T as TakeReceive<Lbl>>::Payload;
+    type Rest = Cons<RoleLocal<R, L>, <T as TakeReceive<Lbl>>::Rest>;
+}
+
+/// Replaces the local type of role `R` in a list of [`RoleLocal`] entries with
+/// `New`.
+pub trait ReplaceLocal<R, New> {
+    type Output;
+}
+
+impl<R, New, H, L, T> ReplaceLocal<R, New> for Cons<RoleLocal<H, L>, T>
+where
+    R: RoleEq<H>,
+    (): ReplaceLocalCase<R, New, H, L, T, <R as RoleEq<H>>::Output>,
+{
+    type Output = <() as ReplaceLocalCase<R, New, H, L, T, <R as RoleEq<H>>::Output>>::Output;
+}
+
+/// Helper trait for [`ReplaceLocal`]: dispatches on whether the head entry
+/// belongs to `R`.
+pub trait ReplaceLocalCase<R, New, H, L, T, Found> {
+    type Output;
+}
+
+impl<R, New, H, L, T> ReplaceLocalCase<R, New, H, L, T, types::True> for () {
+    type Output = Cons<RoleLocal<H, New>, T>;
+}
+
+impl<R, New, H, L, T> ReplaceLocalCase<R, New, H, L, T, types::False> for ()
+where
+    T: ReplaceLocal<R, New>,
+{
+    type Output = Cons<RoleLocal<H, L>, <T as ReplaceLocal<R, New>>::Output>;
+}
+
+/// Tells whether a local type is a selection, among the local types synthesis
+/// supports.
+#[diagnostic::on_unimplemented(
+    message = "synthesis does not support the local type `{Self}`",
+    label = "not a send, receive, selection, offer, skip or end",
+    note = "`Synthesize` covers `EpSend`, `EpRecv`, `EpSelect`, `EpOffer`, `EpSkip` and `EpEnd` only"
+)]
+pub trait ChoiceShape {
+    type Selects: types::Bool;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches> ChoiceShape for EpSelect<IO, Lbl, Me, Branches> {
+    type Selects = types::True;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches> ChoiceShape for EpOffer<IO, Lbl, Me, Branches> {
+    type Selects = types::False;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R, H, T> ChoiceShape for EpSend<IO, Lbl, R, H, T> {
+    type Selects = types::False;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R, H, T> ChoiceShape for EpRecv<IO, Lbl, R, H, T> {
+    type Selects = types::False;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R> ChoiceShape for EpSkip<IO, Lbl, R> {
+    type Selects = types::False;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R> ChoiceShape for EpEnd<IO, Lbl, R> {
+    type Selects = types::False;
+}
+
+/// Helper trait for [`SynthesizeFrom`]: looks for the next selection among
+/// the `Candidates` entries of `All`, once no message can go.
+pub trait SynthesizeChoiceFrom<IO, All, Candidates> {
+    type Output: TSession<IO>;
+}
+
+// Nobody selects either: every role must have finished
+impl<IO, All> SynthesizeChoiceFrom<IO, All, Nil> for ()
+where
+    All: AllEnded<IO>,
+{
+    type Output = TEnd<IO>;
+}
+
+impl<IO, All, R, L, T> SynthesizeChoiceFrom<IO, All, Cons<RoleLocal<R, L>, T>> for ()
+where
+    L: ChoiceShape,
+    (): SynthesizeChoiceCase<IO, All, R, L, T, <L as ChoiceShape>::Selects>,
+{
+    type Output =
+        <() as SynthesizeChoiceCase<IO, All, R, L, T, <L as ChoiceShape>::Selects>>::Output;
+}
+
+/// Helper trait for [`SynthesizeChoiceFrom`]: dispatches on whether the
+/// candidate role `R` selects.
+pub trait SynthesizeChoiceCase<IO, All, R, L, T, Selects> {
+    type Output: TSession<IO>;
+}
+
+// The candidate does not select: try the next one
+impl<IO, All, R, L, T> SynthesizeChoiceCase<IO, All, R, L, T, types::False> for ()
+where
+    (): SynthesizeChoiceFrom<IO, All, T>,
+{
+    type Output = <() as SynthesizeChoiceFrom<IO, All, T>>::Output;
+}
+
+// The candidate selects: it chooses, and every branch is synthesized in turn
+impl<IO, All, R, Lbl, Branches, T>
+    SynthesizeChoiceCase<IO, All, R, EpSelect<IO, Lbl, R, Branches>, T, types::True> for ()
+where
+    Lbl: types::ProtocolLabel,
+    (): SynthesizeBranches<IO, Lbl, All, Branches>,
+{
+    type Output = TSelect<IO, Lbl, R, <() as SynthesizeBranches<IO, Lbl, All, Branches>>::Output>;
+}
+
+/// Synthesizes the branches of the choice labelled `Lbl` from `All`.
+///
+/// `Remaining` are the branches of the chooser still to be synthesized; each
+/// step takes the first branch of every role in the choice with
+/// [`TakeBranches`].
+pub trait SynthesizeBranches<IO, Lbl, All, Remaining> {
+    type Output: SessionList<IO>;
+}
+
+impl<IO, Lbl, All> SynthesizeBranches<IO, Lbl, All, Nil> for ()
+where
+    All: NoBranchesLeft,
+{
+    type Output = Nil;
+}
+
+impl<IO, Lbl, All, B, Bs> SynthesizeBranches<IO, Lbl, All, Cons<B, Bs>> for ()
+where
+    All: TakeBranches<Lbl>,
+    <All as TakeBranches<Lbl>>::Head: Synthesize<IO>,
+    (): SynthesizeBranches<IO, Lbl, <All as TakeBranches<Lbl>>::Tail, Bs>,
+{
+    type Output = Cons<
+        <<All as TakeBranches<Lbl>>::Head as Synthesize<IO>>::Output,
+        <() as SynthesizeBranches<IO, Lbl, <All as TakeBranches<Lbl>>::Tail, Bs>>::Output,
+    >;
+}
+
+/// Takes the first branch of the choice labelled `Lbl` from a local type.
+///
+/// `Head` is the local type in that branch and `Tail` the local type left with
+/// the other branches. A role that skips the choice or has finished stays as
+/// it is in every branch.
+#[diagnostic::on_unimplemented(
+    message = "the local type `{Self}` cannot take part in the choice labelled `{Lbl}`",
+    label = "no branch of that choice left to take",
+    note = "when a role selects, every other role must offer the same choice, skip it or have finished"
+)]
+pub trait TakeBranch<Lbl> {
+    type Head;
+    type Tail;
+}
+
+impl<Lbl, IO, Lbl2, Me, B, Bs> TakeBranch<Lbl> for EpSelect<IO, Lbl2, Me, Cons<B, Bs>>
+where
+    Lbl2: types::ProtocolLabel + LabelEq<Lbl, Output = types::True>,
+{
+    type Head = B;
+    type Tail = EpSelect<IO, Lbl2, Me, Bs>;
+}
+
+impl<Lbl, IO, Lbl2, Me, B, Bs> TakeBranch<Lbl> for EpOffer<IO, Lbl2, Me, Cons<B, Bs>>
+where
+    Lbl2: types::ProtocolLabel + LabelEq<Lbl, Output = types::True>,
+{
+    type Head = B;
+    type Tail = EpOffer<IO, Lbl2, Me, Bs>;
+}
+
+impl<Lbl, IO, Lbl2: types::ProtocolLabel, R> TakeBranch<Lbl> for EpSkip<IO, Lbl2, R> {
+    type Head = EpSkip<IO, Lbl2, R>;
+    type Tail = EpSkip<IO, Lbl2, R>;
+}
+
+impl<Lbl, IO, Lbl2: types::ProtocolLabel, R> TakeBranch<Lbl> for EpEnd<IO, Lbl2, R> {
+    type Head = EpEnd<IO, Lbl2, R>;
+    type Tail = EpEnd<IO, Lbl2, R>;
+}
+
+/// [`TakeBranch`] over every entry of a list of [`RoleLocal`] entries.
+pub trait TakeBranches<Lbl> {
+    type Head;
+    type Tail;
+}
+
+impl<Lbl> TakeBranches<Lbl> for Nil {
+    type Head = Nil;
+    type Tail = Nil;
+}
+
+impl<Lbl, R, L, T> TakeBranches<Lbl> for Cons<RoleLocal<R, L>, T>
+where
+    L: TakeBranch<Lbl>,
+    T: TakeBranches<Lbl>,
+{
+    type Head = Cons<RoleLocal<R, <L as TakeBranch<Lbl>>::Head>, <T as TakeBranches<Lbl>>::Head>;
+    type Tail = Cons<RoleLocal<R, <L as TakeBranch<Lbl>>::Tail>, <T as TakeBranches<Lbl>>::Tail>;
+}
+
+/// A local type has no branch of a choice left: a selection or offer without
+/// branches, a skip or an end.
+#[diagnostic::on_unimplemented(
+    message = "the local type `{Self}` has more branches than the chooser",
+    label = "branches left once the chooser has none",
+    note = "every `EpSelect` and `EpOffer` of a choice must have as many branches as the chooser"
+)]
+pub trait NoBranchLeft {}
+
+impl<IO, Lbl: types::ProtocolLabel, Me> NoBranchLeft for EpSelect<IO, Lbl, Me, Nil> {}
+
+impl<IO, Lbl: types::ProtocolLabel, Me> NoBranchLeft for EpOffer<IO, Lbl, Me, Nil> {}
+
+impl<IO, Lbl: types::ProtocolLabel, R> NoBranchLeft for EpSkip<IO, Lbl, R> {}
+
+impl<IO, Lbl: types::ProtocolLabel, R> NoBranchLeft for EpEnd<IO, Lbl, R> {}
+
+/// [`NoBranchLeft`] for every entry of a list of [`RoleLocal`] entries.
+pub trait NoBranchesLeft {}
+
+impl NoBranchesLeft for Nil {}
+
+impl<R, L, T> NoBranchesLeft for Cons<RoleLocal<R, L>, T>
+where
+    L: NoBranchLeft,
+    T: NoBranchesLeft,
+{
+}
+
+/// Every entry of a list of [`RoleLocal`] entries has reached `EpEnd` or
+/// `EpSkip`.
+#[diagnostic::on_unimplemented(
+    message = "the local types `{Self}` cannot be synthesized into a global protocol",
+    label = "some role is not finished",
+    note = "a role waits for a message no other role sends"
+)]
+pub trait AllEnded<IO> {}
+
+impl<IO> AllEnded<IO> for Nil {}
+
+impl<IO, Lbl, R, T> AllEnded<IO> for Cons<RoleLocal<R, EpEnd<IO, Lbl, R>>, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: AllEnded<IO>,
+{
+}
+
+impl<IO, Lbl, R, T> AllEnded<IO> for Cons<RoleLocal<R, EpSkip<IO, Lbl, R>>, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: AllEnded<IO>,
+{
+}
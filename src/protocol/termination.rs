@@ -0,0 +1,633 @@
+//! # Termination Checks
+//!
+//! This module checks that a global protocol cannot loop forever: every loop
+//! has an exit branch, so some path through its body leaves the loop instead
+//! of jumping back to its start.
+//!
+//! Key components:
+//!
+//! - `Terminating`: Every loop of a protocol has an exit branch
+//! - `MayDiverge`: Some loop of a protocol has no exit branch
+//! - `CanExit`: Some path through a protocol reaches its end without a jump
+//! - `Diverges`: Whether some loop of a protocol has no exit branch
+//! - `AnyCanExit` / `AllCanExit`: `CanExit` over the branches of a choice or a parallel composition
+//! - `TerminatingCase`: Helper trait dispatching on `Diverges`
+//! - `LoopsForever` / `TerminationFailure`: Failure markers naming the protocol
+//!
+//! Loops are `TRec` and `TRecX`. A path exits a loop when it reaches `TEnd`
+//! or raises a failure with `TThrow`; a path ending in a `TVar` jumps back.
+//! The check is conservative: a jump to an enclosing loop does not count as
+//! an exit of the inner loop. Loops with an exit branch count as terminating
+//! even though a role may keep choosing to repeat them. Stuck states are
+//! covered by `HasProgress`.
+
+use super::base::{Cons, Nil};
+use super::global::*;
+use crate::types;
+use core::marker::PhantomData;
+
+/// Failure marker: the protocol `G` contains a loop without an exit branch.
+pub struct LoopsForever<G>(PhantomData<G>);
+
+/// Never implemented: a bound `Marker: TerminationFailure` fails and names
+/// the protocol rejected by [`Terminating`].
+#[diagnostic::on_unimplemented(
+    message = "the protocol may never terminate: `{Self}`",
+    label = "a loop has no exit branch",
+    note = "give every `TRec` and `TRecX` a branch that ends without jumping back"
+)]
+pub trait TerminationFailure {}
+
+/// Whether some path through a protocol reaches its end without a jump.
+///
+/// Every branch of a parallel composition must exit for the composition to
+/// exit; one branch of a choice is enough.
+pub trait CanExit {
+    type Output: types::Bool;
+}
+
+/// `CanExit` for some protocol of a type-level list.
+pub trait AnyCanExit {
+    type Output: types::Bool;
+}
+
+/// `CanExit` for every protocol of a type-level list.
+pub trait AllCanExit {
+    type Output: types::Bool;
+}
+
+/// Whether some loop of a protocol, or of a type-level list of protocols,
+/// has no exit branch.
+pub trait Diverges {
+    type Output: types::Bool;
+}
+
+/// A global protocol whose every loop has an exit branch.
+///
+/// See the module documentation for what counts as an exit. Check a protocol
+/// with [`assert_terminating!`](crate::assert_terminating).
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Loop; struct Batch;
+/// impl VarEq<Loop> for Loop { type Output = True; }
+/// impl ProtocolLabel for Batch {}
+///
+/// // The client sends items until it stops
+/// type Upload = TRecX<
+///     Http,
+///     Batch,
+///     Loop,
+///     TChoice<
+///         Http,
+///         Batch,
+///         TInteract<Http, Batch, TClient, Message, TVar<Loop>>,
+///         TEnd<Http>,
+///     >,
+/// >;
+/// assert_terminating!(Upload);
+/// ```
+pub trait Terminating {}
+
+impl<G> Terminating for G
+where
+    G: Diverges,
+    G: TerminatingCase<<G as Diverges>::Output>,
+{
+}
+
+/// A global protocol with a loop that has no exit branch.
+///
+/// The inverse of [`Terminating`], for protocols such as servers and streams
+/// that are meant to run forever.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Loop; struct Feed;
+/// impl VarEq<Loop> for Loop { type Output = True; }
+/// impl ProtocolLabel for Feed {}
+///
+/// type Stream = TRecX<Http, Feed, Loop, TInteract<Http, Feed, TServer, Message, TVar<Loop>>>;
+/// fn runs_forever<G: MayDiverge>() {}
+/// runs_forever::<Stream>();
+/// ```
+pub trait MayDiverge {}
+
+impl<G> MayDiverge for G where G: Diverges<Output = types::True> {}
+
+/// Helper trait for `Terminating`, dispatching on whether the protocol
+/// diverges.
+pub trait TerminatingCase<Diverging> {}
+
+impl<G> TerminatingCase<types::False> for G {}
+
+impl<G> TerminatingCase<types::True> for G where LoopsForever<G>: TerminationFailure {}
+
+impl<IO, Lbl> CanExit for TEnd<IO, Lbl> {
+    type Output = types::True;
+}
+
+impl<IO, Lbl> Diverges for TEnd<IO, Lbl> {
+    type Output = types::False;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R, H, T> CanExit for TInteract<IO, Lbl, R, H, T>
+where
+    T: TSession<IO> + CanExit,
+{
+    type Output = <T as CanExit>::Output;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R, H, T> Diverges for TInteract<IO, Lbl, R, H, T>
+where
+    T: TSession<IO> + Diverges,
+{
+    type Output = <T as Diverges>::Output;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, To, H, T> CanExit for TMsg<IO, Lbl, From, To, H, T>
+where
+    T: TSession<IO> + CanExit,
+{
+    type Output = <T as CanExit>::Output;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, To, H, T> Diverges for TMsg<IO, Lbl, From, To, H, T>
+where
+    T: TSession<IO> + Diverges,
+{
+    type Output = <T as Diverges>::Output;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, ToSet, H, T> CanExit
+    for TBroadcast<IO, Lbl, From, ToSet, H, T>
+where
+    T: TSession<IO> + CanExit,
+{
+    type Output = <T as CanExit>::Output;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, ToSet, H, T> Diverges
+    for TBroadcast<IO, Lbl, From, ToSet, H, T>
+where
+    T: TSession<IO> + Diverges,
+{
+    type Output = <T as Diverges>::Output;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, WorkerSet, H, T> CanExit
+    for TAnycast<IO, Lbl, From, WorkerSet, H, T>
+where
+    T: TSession<IO> + CanExit,
+{
+    type Output = <T as CanExit>::Output;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, WorkerSet, H, T> Diverges
+    for TAnycast<IO, Lbl, From, WorkerSet, H, T>
+where
+    T: TSession<IO> + Diverges,
+{
+    type Output = <T as Diverges>::Output;
+}
+
+// The delegated endpoint belongs to another session and is not checked
+impl<IO, Lbl: types::ProtocolLabel, From, To, DelegatedEp, T> CanExit
+    for TDelegate<IO, Lbl, From, To, DelegatedEp, T>
+where
+    T: TSession<IO> + CanExit,
+{
+    type Output = <T as CanExit>::Output;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, To, DelegatedEp, T> Diverges
+    for TDelegate<IO, Lbl, From, To, DelegatedEp, T>
+where
+    T: TSession<IO> + Diverges,
+{
+    type Output = <T as Diverges>::Output;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, A, B, Cont> CanExit for TDisconnect<IO, Lbl, A, B, Cont>
+where
+    Cont: TSession<IO> + CanExit,
+{
+    type Output = <Cont as CanExit>::Output;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, A, B, Cont> Diverges for TDisconnect<IO, Lbl, A, B, Cont>
+where
+    Cont: TSession<IO> + Diverges,
+{
+    type Output = <Cont as Diverges>::Output;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, A, B, Cont> CanExit for TConnect<IO, Lbl, A, B, Cont>
+where
+    Cont: TSession<IO> + CanExit,
+{
+    type Output = <Cont as CanExit>::Output;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, A, B, Cont> Diverges for TConnect<IO, Lbl, A, B, Cont>
+where
+    Cont: TSession<IO> + Diverges,
+{
+    type Output = <Cont as Diverges>::Output;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R, Cont> CanExit for TEndFor<IO, Lbl, R, Cont>
+where
+    Cont: TSession<IO> + CanExit,
+{
+    type Output = <Cont as CanExit>::Output;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R, Cont> Diverges for TEndFor<IO, Lbl, R, Cont>
+where
+    Cont: TSession<IO> + Diverges,
+{
+    type Output = <Cont as Diverges>::Output;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, L, R> CanExit for TChoice<IO, Lbl, L, R>
+where
+    L: TSession<IO> + CanExit,
+    R: TSession<IO> + CanExit,
+    <L as CanExit>::Output: types::BoolOr<<R as CanExit>::Output>,
+{
+    type Output = types::Or<<L as CanExit>::Output, <R as CanExit>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, L, R> Diverges for TChoice<IO, Lbl, L, R>
+where
+    L: TSession<IO> + Diverges,
+    R: TSession<IO> + Diverges,
+    <L as Diverges>::Output: types::BoolOr<<R as Diverges>::Output>,
+{
+    type Output = types::Or<<L as Diverges>::Output, <R as Diverges>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Branches> CanExit for TChoiceN<IO, Lbl, Branches>
+where
+    Branches: SessionList<IO> + AnyCanExit,
+{
+    type Output = <Branches as AnyCanExit>::Output;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Branches> Diverges for TChoiceN<IO, Lbl, Branches>
+where
+    Branches: SessionList<IO> + Diverges,
+{
+    type Output = <Branches as Diverges>::Output;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Chooser, Branches> CanExit
+    for TSelect<IO, Lbl, Chooser, Branches>
+where
+    Branches: SessionList<IO> + AnyCanExit,
+{
+    type Output = <Branches as AnyCanExit>::Output;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Chooser, Branches> Diverges
+    for TSelect<IO, Lbl, Chooser, Branches>
+where
+    Branches: SessionList<IO> + Diverges,
+{
+    type Output = <Branches as Diverges>::Output;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Offeree, Branches> CanExit
+    for TOffer<IO, Lbl, Offeree, Branches>
+where
+    Branches: SessionList<IO> + AnyCanExit,
+{
+    type Output = <Branches as AnyCanExit>::Output;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Offeree, Branches> Diverges
+    for TOffer<IO, Lbl, Offeree, Branches>
+where
+    Branches: SessionList<IO> + Diverges,
+{
+    type Output = <Branches as Diverges>::Output;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, High, Low> CanExit for TPriorityChoice<IO, Lbl, High, Low>
+where
+    High: TSession<IO> + CanExit,
+    Low: TSession<IO> + CanExit,
+    <High as CanExit>::Output: types::BoolOr<<Low as CanExit>::Output>,
+{
+    type Output = types::Or<<High as CanExit>::Output, <Low as CanExit>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, High, Low> Diverges for TPriorityChoice<IO, Lbl, High, Low>
+where
+    High: TSession<IO> + Diverges,
+    Low: TSession<IO> + Diverges,
+    <High as Diverges>::Output: types::BoolOr<<Low as Diverges>::Output>,
+{
+    type Output = types::Or<<High as Diverges>::Output, <Low as Diverges>::Output>;
+}
+
+impl<IO, Lbl, WeightL, L, WeightR, R> CanExit for TProb<IO, Lbl, WeightL, L, WeightR, R>
+where
+    Lbl: types::ProtocolLabel,
+    WeightL: types::WeightMarker,
+    WeightR: types::WeightMarker,
+    L: TSession<IO> + CanExit,
+    R: TSession<IO> + CanExit,
+    <L as CanExit>::Output: types::BoolOr<<R as CanExit>::Output>,
+{
+    type Output = types::Or<<L as CanExit>::Output, <R as CanExit>::Output>;
+}
+
+impl<IO, Lbl, WeightL, L, WeightR, R> Diverges for TProb<IO, Lbl, WeightL, L, WeightR, R>
+where
+    Lbl: types::ProtocolLabel,
+    WeightL: types::WeightMarker,
+    WeightR: types::WeightMarker,
+    L: TSession<IO> + Diverges,
+    R: TSession<IO> + Diverges,
+    <L as Diverges>::Output: types::BoolOr<<R as Diverges>::Output>,
+{
+    type Output = types::Or<<L as Diverges>::Output, <R as Diverges>::Output>;
+}
+
+// Both branches run, so both must reach their end
+impl<IO, Lbl: types::ProtocolLabel, L, R, IsDisjoint> CanExit for TPar<IO, Lbl, L, R, IsDisjoint>
+where
+    L: TSession<IO> + CanExit,
+    R: TSession<IO> + CanExit,
+    <L as CanExit>::Output: types::BoolAnd<<R as CanExit>::Output>,
+{
+    type Output = types::And<<L as CanExit>::Output, <R as CanExit>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, L, R, IsDisjoint> Diverges for TPar<IO, Lbl, L, R, IsDisjoint>
+where
+    L: TSession<IO> + Diverges,
+    R: TSession<IO> + Diverges,
+    <L as Diverges>::Output: types::BoolOr<<R as Diverges>::Output>,
+{
+    type Output = types::Or<<L as Diverges>::Output, <R as Diverges>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Branches> CanExit for TParN<IO, Lbl, Branches>
+where
+    Branches: SessionList<IO> + AllCanExit,
+{
+    type Output = <Branches as AllCanExit>::Output;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Branches> Diverges for TParN<IO, Lbl, Branches>
+where
+    Branches: SessionList<IO> + Diverges,
+{
+    type Output = <Branches as Diverges>::Output;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Steps> CanExit for TShuffle<IO, Lbl, Steps>
+where
+    Steps: SessionList<IO> + AllCanExit,
+{
+    type Output = <Steps as AllCanExit>::Output;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Steps> Diverges for TShuffle<IO, Lbl, Steps>
+where
+    Steps: SessionList<IO> + Diverges,
+{
+    type Output = <Steps as Diverges>::Output;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, S> CanExit for TRec<IO, Lbl, S>
+where
+    S: TSession<IO> + CanExit,
+{
+    type Output = <S as CanExit>::Output;
+}
+
+// A loop diverges when its body cannot exit or contains a loop that cannot
+impl<IO, Lbl: types::ProtocolLabel, S> Diverges for TRec<IO, Lbl, S>
+where
+    S: TSession<IO> + CanExit + Diverges,
+    <S as CanExit>::Output: types::Not,
+    <<S as CanExit>::Output as types::Not>::Output: types::BoolOr<<S as Diverges>::Output>,
+{
+    type Output =
+        types::Or<<<S as CanExit>::Output as types::Not>::Output, <S as Diverges>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Var, Body> CanExit for TRecX<IO, Lbl, Var, Body>
+where
+    Body: TSession<IO> + CanExit,
+{
+    type Output = <Body as CanExit>::Output;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Var, Body> Diverges for TRecX<IO, Lbl, Var, Body>
+where
+    Body: TSession<IO> + CanExit + Diverges,
+    <Body as CanExit>::Output: types::Not,
+    <<Body as CanExit>::Output as types::Not>::Output: types::BoolOr<<Body as Diverges>::Output>,
+{
+    type Output =
+        types::Or<<<Body as CanExit>::Output as types::Not>::Output, <Body as Diverges>::Output>;
+}
+
+impl<Var> CanExit for TVar<Var> {
+    type Output = types::False;
+}
+
+impl<Var> Diverges for TVar<Var> {
+    type Output = types::False;
+}
+
+impl<IO, Lbl, Dur, Body, OnTimeout> CanExit for TTimeout<IO, Lbl, Dur, Body, OnTimeout>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    Body: TSession<IO> + CanExit,
+    OnTimeout: TSession<IO> + CanExit,
+    <Body as CanExit>::Output: types::BoolOr<<OnTimeout as CanExit>::Output>,
+{
+    type Output = types::Or<<Body as CanExit>::Output, <OnTimeout as CanExit>::Output>;
+}
+
+impl<IO, Lbl, Dur, Body, OnTimeout> Diverges for TTimeout<IO, Lbl, Dur, Body, OnTimeout>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    Body: TSession<IO> + Diverges,
+    OnTimeout: TSession<IO> + Diverges,
+    <Body as Diverges>::Output: types::BoolOr<<OnTimeout as Diverges>::Output>,
+{
+    type Output = types::Or<<Body as Diverges>::Output, <OnTimeout as Diverges>::Output>;
+}
+
+impl<IO, Lbl, R, Dur, T> CanExit for TDelay<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: TSession<IO> + CanExit,
+{
+    type Output = <T as CanExit>::Output;
+}
+
+impl<IO, Lbl, R, Dur, T> Diverges for TDelay<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: TSession<IO> + Diverges,
+{
+    type Output = <T as Diverges>::Output;
+}
+
+impl<IO, Lbl, R, Dur, T> CanExit for TDeadline<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: TSession<IO> + CanExit,
+{
+    type Output = <T as CanExit>::Output;
+}
+
+impl<IO, Lbl, R, Dur, T> Diverges for TDeadline<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: TSession<IO> + Diverges,
+{
+    type Output = <T as Diverges>::Output;
+}
+
+impl<IO, Lbl, Scope, Interruptor, Handler> CanExit
+    for TInterrupt<IO, Lbl, Scope, Interruptor, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Scope: TSession<IO> + CanExit,
+    Handler: TSession<IO> + CanExit,
+    <Scope as CanExit>::Output: types::BoolOr<<Handler as CanExit>::Output>,
+{
+    type Output = types::Or<<Scope as CanExit>::Output, <Handler as CanExit>::Output>;
+}
+
+impl<IO, Lbl, Scope, Interruptor, Handler> Diverges
+    for TInterrupt<IO, Lbl, Scope, Interruptor, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Scope: TSession<IO> + Diverges,
+    Handler: TSession<IO> + Diverges,
+    <Scope as Diverges>::Output: types::BoolOr<<Handler as Diverges>::Output>,
+{
+    type Output = types::Or<<Scope as Diverges>::Output, <Handler as Diverges>::Output>;
+}
+
+// Skipping the optional body is always possible
+impl<IO, Lbl, Decider, Body, Cont> CanExit for TOptional<IO, Lbl, Decider, Body, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Body: TSession<IO>,
+    Cont: TSession<IO> + CanExit,
+{
+    type Output = <Cont as CanExit>::Output;
+}
+
+impl<IO, Lbl, Decider, Body, Cont> Diverges for TOptional<IO, Lbl, Decider, Body, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Body: TSession<IO> + Diverges,
+    Cont: TSession<IO> + Diverges,
+    <Body as Diverges>::Output: types::BoolOr<<Cont as Diverges>::Output>,
+{
+    type Output = types::Or<<Body as Diverges>::Output, <Cont as Diverges>::Output>;
+}
+
+impl<Pred, Inner: CanExit> CanExit for TRefine<Pred, Inner> {
+    type Output = <Inner as CanExit>::Output;
+}
+
+impl<Pred, Inner: Diverges> Diverges for TRefine<Pred, Inner> {
+    type Output = <Inner as Diverges>::Output;
+}
+
+impl<Meta, Inner: CanExit> CanExit for TAnnotate<Meta, Inner> {
+    type Output = <Inner as CanExit>::Output;
+}
+
+impl<Meta, Inner: Diverges> Diverges for TAnnotate<Meta, Inner> {
+    type Output = <Inner as Diverges>::Output;
+}
+
+// Raising a failure leaves the loop for the enclosing handler
+impl<IO, Lbl: types::ProtocolLabel, R, Err> CanExit for TThrow<IO, Lbl, R, Err> {
+    type Output = types::True;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R, Err> Diverges for TThrow<IO, Lbl, R, Err> {
+    type Output = types::False;
+}
+
+impl<IO, Lbl, Body, Handler> CanExit for TCatch<IO, Lbl, Body, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Body: TSession<IO> + CanExit,
+    Handler: TSession<IO> + CanExit,
+    <Body as CanExit>::Output: types::BoolOr<<Handler as CanExit>::Output>,
+{
+    type Output = types::Or<<Body as CanExit>::Output, <Handler as CanExit>::Output>;
+}
+
+impl<IO, Lbl, Body, Handler> Diverges for TCatch<IO, Lbl, Body, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Body: TSession<IO> + Diverges,
+    Handler: TSession<IO> + Diverges,
+    <Body as Diverges>::Output: types::BoolOr<<Handler as Diverges>::Output>,
+{
+    type Output = types::Or<<Body as Diverges>::Output, <Handler as Diverges>::Output>;
+}
+
+impl AnyCanExit for Nil {
+    type Output = types::False;
+}
+
+impl<H, T> AnyCanExit for Cons<H, T>
+where
+    H: CanExit,
+    T: AnyCanExit,
+    <H as CanExit>::Output: types::BoolOr<<T as AnyCanExit>::Output>,
+{
+    type Output = types::Or<<H as CanExit>::Output, <T as AnyCanExit>::Output>;
+}
+
+impl AllCanExit for Nil {
+    type Output = types::True;
+}
+
+impl<H, T> AllCanExit for Cons<H, T>
+where
+    H: CanExit,
+    T: AllCanExit,
+    <H as CanExit>::Output: types::BoolAnd<<T as AllCanExit>::Output>,
+{
+    type Output = types::And<<H as CanExit>::Output, <T as AllCanExit>::Output>;
+}
+
+impl Diverges for Nil {
+    type Output = types::False;
+}
+
+impl<H, T> Diverges for Cons<H, T>
+where
+    H: Diverges,
+    T: Diverges,
+    <H as Diverges>::Output: types::BoolOr<<T as Diverges>::Output>,
+{
+    type Output = types::Or<<H as Diverges>::Output, <T as Diverges>::Output>;
+}
@@ -0,0 +1,1336 @@
+//! # Trace Inclusion
+//!
+//! This module checks at compile time that a global protocol can produce a
+//! given run. A trace is a type-level list of `Step`s, each naming the role
+//! that acts, the label and the message; scenario tests such as "login, two
+//! publishes, then end" become compile-time assertions.
+//!
+//! Key components:
+//!
+//! - `Step`: One step of a trace
+//! - `Admits`: A protocol can produce a trace and then end
+//! - `AdmitsFrom`: Some protocol of a list can produce a trace and then end
+//! - `Derive`: The protocols left after a step, and whether the step raises a failure
+//! - `DeriveEach` / `DeriveAt`: `Derive` over a list of alternatives, or of parallel branches
+//! - `Nullable` / `AnyNullable` / `AllNullable`: A protocol can end without another step
+//! - `Unfold` / `UnfoldEach` / `UnfoldVarCase`: Loop bodies with their variable replaced by the loop
+//! - `StepMatches` / `StepSentBy` / `MatchCase`: Matching a step against an interaction
+//! - `ConsEach`, `PrependEach`, `ParLeftEach`, `ParRightEach`, `ParNEach`, `ShuffleEach`,
+//!   `CatchEach`, `TimeoutEach`, `InterruptEach`: Residuals put back into their combinator
+//! - `UnexpectedStep` / `Unfinished` / `TraceFailure`: Failure markers
+//!
+//! A step matches an interaction when its role is the sender (or the role of a
+//! `TInteract` or `TThrow`), compared with `RoleEq`, its label is compared
+//! with `LabelEq` and its message with `MessageEq`. A delegation is matched by
+//! role and label only.
+//!
+//! Choices may take any branch, and the branches of a parallel composition or
+//! the steps of a shuffle interleave. Loops are unfolded on demand: `VarEq`
+//! must compare the recursion variables in use, including the labels of
+//! `TRec` loops, and loop bodies must be `Guarded`. A `TThrow` step continues
+//! with the handler of its `TCatch`; a timeout or an interrupt may fire
+//! before any step of its scope. Connections, delays, deadlines, refinements
+//! and annotations produce no step.
+
+use super::base::{Cons, Nil};
+use super::global::*;
+use super::local::{LabelEq, RoleEq};
+use super::recursion::VarEq;
+use super::separability::MessageEq;
+use super::utils::{Concat, IsEmpty};
+use crate::types;
+use core::marker::PhantomData;
+
+/// One step of a trace: `R` acts on the label `Lbl` with the message `M`.
+///
+/// For a `TMsg`, `TBroadcast`, `TAnycast` or `TDelegate`, `R` is the sender.
+pub struct Step<R, Lbl, M>(PhantomData<(R, Lbl, M)>);
+
+/// Failure marker: no protocol left by the trace so far can take the step `S`.
+pub struct UnexpectedStep<S>(PhantomData<S>);
+
+/// Failure marker: the trace ended while none of the protocols `States` left
+/// by it can end.
+pub struct Unfinished<States>(PhantomData<States>);
+
+/// Never implemented: a bound `Marker: TraceFailure` fails and names the
+/// step rejected by [`Admits`].
+#[diagnostic::on_unimplemented(
+    message = "the protocol does not admit the trace: `{Self}`",
+    label = "the protocol cannot produce this trace",
+    note = "compare the steps of the trace with the interactions of the protocol"
+)]
+pub trait TraceFailure {}
+
+/// Whether a step matches an interaction by the role `From` with the label
+/// `Lbl` and the message `M`.
+pub trait StepMatches<From, Lbl, M> {
+    type Output: types::Bool;
+}
+
+impl<R, L, Msg, From, Lbl, M> StepMatches<From, Lbl, M> for Step<R, L, Msg>
+where
+    R: RoleEq<From>,
+    L: LabelEq<Lbl>,
+    Msg: MessageEq<M>,
+    <R as RoleEq<From>>::Output: types::BoolAnd<<L as LabelEq<Lbl>>::Output>,
+    types::And<<R as RoleEq<From>>::Output, <L as LabelEq<Lbl>>::Output>:
+        types::BoolAnd<<Msg as MessageEq<M>>::Output>,
+{
+    type Output = types::And<
+        types::And<<R as RoleEq<From>>::Output, <L as LabelEq<Lbl>>::Output>,
+        <Msg as MessageEq<M>>::Output,
+    >;
+}
+
+/// Whether a step is taken by the role `From` with the label `Lbl`,
+/// whatever its message.
+pub trait StepSentBy<From, Lbl> {
+    type Output: types::Bool;
+}
+
+impl<R, L, Msg, From, Lbl> StepSentBy<From, Lbl> for Step<R, L, Msg>
+where
+    R: RoleEq<From>,
+    L: LabelEq<Lbl>,
+    <R as RoleEq<From>>::Output: types::BoolAnd<<L as LabelEq<Lbl>>::Output>,
+{
+    type Output = types::And<<R as RoleEq<From>>::Output, <L as LabelEq<Lbl>>::Output>;
+}
+
+/// Helper trait for `Derive`: the continuation `T` if a step matched,
+/// nothing otherwise.
+pub trait MatchCase<Matched, T> {
+    type Output;
+}
+
+impl<T> MatchCase<types::True, T> for () {
+    type Output = Cons<T, Nil>;
+}
+
+impl<T> MatchCase<types::False, T> for () {
+    type Output = Nil;
+}
+
+/// The protocols left after the step `S`, as a type-level list; empty if the
+/// protocol cannot take the step.
+///
+/// `Raised` is `True` if the step is a `TThrow`, which leaves no protocol of
+/// its own but hands control to the enclosing `TCatch`.
+pub trait Derive<S> {
+    type Output;
+    type Raised: types::Bool;
+}
+
+/// `Derive` for every protocol of a type-level list of alternatives, with the
+/// protocols left concatenated.
+pub trait DeriveEach<S> {
+    type Output;
+    type Raised: types::Bool;
+}
+
+/// `Derive` for a type-level list of parallel branches: every list of
+/// branches left after one branch takes the step `S`.
+pub trait DeriveAt<S> {
+    type Output;
+    type Raised: types::Bool;
+}
+
+/// Whether a protocol can end without taking another step.
+pub trait Nullable {
+    type Output: types::Bool;
+}
+
+/// `Nullable` for some protocol of a type-level list.
+pub trait AnyNullable {
+    type Output: types::Bool;
+}
+
+/// `Nullable` for every protocol of a type-level list.
+pub trait AllNullable {
+    type Output: types::Bool;
+}
+
+/// A protocol with every `TVar<Var>` replaced by `Loop`.
+pub trait Unfold<IO, Var, Loop> {
+    type Output: TSession<IO>;
+}
+
+/// `Unfold` for every protocol of a type-level list.
+pub trait UnfoldEach<IO, Var, Loop> {
+    type Output: SessionList<IO>;
+}
+
+/// Helper trait for `Unfold`, dispatching on whether a `TVar` jumps to the
+/// loop being unfolded.
+pub trait UnfoldVarCase<IO, Loop, Same> {
+    type Output: TSession<IO>;
+}
+
+/// A global protocol that can produce the trace `Trace`, a type-level list of
+/// [`Step`]s, and then end.
+///
+/// See the module documentation for how steps are matched. Check a trace
+/// with [`assert_admits!`](crate::assert_admits).
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Client; struct Login; struct Post;
+/// impl Role for Client {}
+/// impl RoleEq<Client> for Client { type Output = True; }
+/// impl ProtocolLabel for Login {} impl ProtocolLabel for Post {}
+/// impl LabelEq<Login> for Login { type Output = True; }
+/// impl LabelEq<Post> for Login  { type Output = False; }
+/// impl LabelEq<Login> for Post  { type Output = False; }
+/// impl LabelEq<Post> for Post   { type Output = True; }
+///
+/// // After logging in, the client publishes until it stops
+/// type Session = TInteract<
+///     Mqtt,
+///     Login,
+///     Client,
+///     Message,
+///     TRec<
+///         Mqtt,
+///         Post,
+///         TChoice<Mqtt, Post, TInteract<Mqtt, Post, Client, Publish, TVar<Post>>, TEnd<Mqtt>>,
+///     >,
+/// >;
+/// impl VarEq<Post> for Post { type Output = True; }
+///
+/// assert_admits!(Session, tlist!(
+///     Step<Client, Login, Message>,
+///     Step<Client, Post, Publish>,
+///     Step<Client, Post, Publish>,
+/// ));
+/// ```
+pub trait Admits<Trace> {}
+
+impl<G, Trace> Admits<Trace> for G where Cons<G, Nil>: AdmitsFrom<Trace> {}
+
+/// Some protocol of a type-level list can produce the trace `Trace` and then
+/// end.
+pub trait AdmitsFrom<Trace> {}
+
+impl<States> AdmitsFrom<Nil> for States
+where
+    States: AnyNullable,
+    States: AdmitsEndCase<<States as AnyNullable>::Output>,
+{
+}
+
+impl<States, S, Rest> AdmitsFrom<Cons<S, Rest>> for States
+where
+    States: DeriveEach<S>,
+    <States as DeriveEach<S>>::Output: IsEmpty,
+    <States as DeriveEach<S>>::Output:
+        AdmitsStepCase<S, Rest, <<States as DeriveEach<S>>::Output as IsEmpty>::Output>,
+{
+}
+
+/// Helper trait for `AdmitsFrom`, dispatching on whether a protocol can end
+/// once the trace is over.
+pub trait AdmitsEndCase<CanEnd> {}
+
+impl<States> AdmitsEndCase<types::True> for States {}
+
+impl<States> AdmitsEndCase<types::False> for States where Unfinished<States>: TraceFailure {}
+
+/// Helper trait for `AdmitsFrom`, dispatching on whether a protocol is left
+/// after the step `S`.
+pub trait AdmitsStepCase<S, Rest, NoneLeft> {}
+
+impl<Next, S, Rest> AdmitsStepCase<S, Rest, types::False> for Next where Next: AdmitsFrom<Rest> {}
+
+impl<Next, S, Rest> AdmitsStepCase<S, Rest, types::True> for Next where
+    UnexpectedStep<S>: TraceFailure
+{
+}
+
+impl<IO, Lbl, S> Derive<S> for TEnd<IO, Lbl> {
+    type Output = Nil;
+    type Raised = types::False;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R, H, T, S> Derive<S> for TInteract<IO, Lbl, R, H, T>
+where
+    T: TSession<IO>,
+    S: StepMatches<R, Lbl, H>,
+    (): MatchCase<<S as StepMatches<R, Lbl, H>>::Output, T>,
+{
+    type Output = <() as MatchCase<<S as StepMatches<R, Lbl, H>>::Output, T>>::Output;
+    type Raised = types::False;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, To, H, T, S> Derive<S> for TMsg<IO, Lbl, From, To, H, T>
+where
+    T: TSession<IO>,
+    S: StepMatches<From, Lbl, H>,
+    (): MatchCase<<S as StepMatches<From, Lbl, H>>::Output, T>,
+{
+    type Output = <() as MatchCase<<S as StepMatches<From, Lbl, H>>::Output, T>>::Output;
+    type Raised = types::False;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, ToSet, H, T, S> Derive<S>
+    for TBroadcast<IO, Lbl, From, ToSet, H, T>
+where
+    T: TSession<IO>,
+    S: StepMatches<From, Lbl, H>,
+    (): MatchCase<<S as StepMatches<From, Lbl, H>>::Output, T>,
+{
+    type Output = <() as MatchCase<<S as StepMatches<From, Lbl, H>>::Output, T>>::Output;
+    type Raised = types::False;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, WorkerSet, H, T, S> Derive<S>
+    for TAnycast<IO, Lbl, From, WorkerSet, H, T>
+where
+    T: TSession<IO>,
+    S: StepMatches<From, Lbl, H>,
+    (): MatchCase<<S as StepMatches<From, Lbl, H>>::Output, T>,
+{
+    type Output = <() as MatchCase<<S as StepMatches<From, Lbl, H>>::Output, T>>::Output;
+    type Raised = types::False;
+}
+
+// The delegated endpoint is not a message type, so only role and label count
+impl<IO, Lbl: types::ProtocolLabel, From, To, DelegatedEp, T, S> Derive<S>
+    for TDelegate<IO, Lbl, From, To, DelegatedEp, T>
+where
+    T: TSession<IO>,
+    S: StepSentBy<From, Lbl>,
+    (): MatchCase<<S as StepSentBy<From, Lbl>>::Output, T>,
+{
+    type Output = <() as MatchCase<<S as StepSentBy<From, Lbl>>::Output, T>>::Output;
+    type Raised = types::False;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, A, B, Cont, S> Derive<S> for TDisconnect<IO, Lbl, A, B, Cont>
+where
+    Cont: TSession<IO> + Derive<S>,
+{
+    type Output = <Cont as Derive<S>>::Output;
+    type Raised = <Cont as Derive<S>>::Raised;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, A, B, Cont, S> Derive<S> for TConnect<IO, Lbl, A, B, Cont>
+where
+    Cont: TSession<IO> + Derive<S>,
+{
+    type Output = <Cont as Derive<S>>::Output;
+    type Raised = <Cont as Derive<S>>::Raised;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R, Cont, S> Derive<S> for TEndFor<IO, Lbl, R, Cont>
+where
+    Cont: TSession<IO> + Derive<S>,
+{
+    type Output = <Cont as Derive<S>>::Output;
+    type Raised = <Cont as Derive<S>>::Raised;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, L, R, S> Derive<S> for TChoice<IO, Lbl, L, R>
+where
+    L: TSession<IO>,
+    R: TSession<IO>,
+    Cons<L, Cons<R, Nil>>: DeriveEach<S>,
+{
+    type Output = <Cons<L, Cons<R, Nil>> as DeriveEach<S>>::Output;
+    type Raised = <Cons<L, Cons<R, Nil>> as DeriveEach<S>>::Raised;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Branches, S> Derive<S> for TChoiceN<IO, Lbl, Branches>
+where
+    Branches: SessionList<IO> + DeriveEach<S>,
+{
+    type Output = <Branches as DeriveEach<S>>::Output;
+    type Raised = <Branches as DeriveEach<S>>::Raised;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Chooser, Branches, S> Derive<S>
+    for TSelect<IO, Lbl, Chooser, Branches>
+where
+    Branches: SessionList<IO> + DeriveEach<S>,
+{
+    type Output = <Branches as DeriveEach<S>>::Output;
+    type Raised = <Branches as DeriveEach<S>>::Raised;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Offeree, Branches, S> Derive<S>
+    for TOffer<IO, Lbl, Offeree, Branches>
+where
+    Branches: SessionList<IO> + DeriveEach<S>,
+{
+    type Output = <Branches as DeriveEach<S>>::Output;
+    type Raised = <Branches as DeriveEach<S>>::Raised;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, High, Low, S> Derive<S> for TPriorityChoice<IO, Lbl, High, Low>
+where
+    High: TSession<IO>,
+    Low: TSession<IO>,
+    Cons<High, Cons<Low, Nil>>: DeriveEach<S>,
+{
+    type Output = <Cons<High, Cons<Low, Nil>> as DeriveEach<S>>::Output;
+    type Raised = <Cons<High, Cons<Low, Nil>> as DeriveEach<S>>::Raised;
+}
+
+impl<IO, Lbl, WeightL, L, WeightR, R, S> Derive<S> for TProb<IO, Lbl, WeightL, L, WeightR, R>
+where
+    Lbl: types::ProtocolLabel,
+    WeightL: types::WeightMarker,
+    WeightR: types::WeightMarker,
+    L: TSession<IO>,
+    R: TSession<IO>,
+    Cons<L, Cons<R, Nil>>: DeriveEach<S>,
+{
+    type Output = <Cons<L, Cons<R, Nil>> as DeriveEach<S>>::Output;
+    type Raised = <Cons<L, Cons<R, Nil>> as DeriveEach<S>>::Raised;
+}
+
+// Either the optional body runs before the continuation, or it is skipped
+impl<IO, Lbl: types::ProtocolLabel, Decider, Body, Cont, S> Derive<S>
+    for TOptional<IO, Lbl, Decider, Body, Cont>
+where
+    Body: TSession<IO>,
+    Cont: TSession<IO>,
+    Cons<Body::Compose<Cont>, Cons<Cont, Nil>>: DeriveEach<S>,
+{
+    type Output = <Cons<Body::Compose<Cont>, Cons<Cont, Nil>> as DeriveEach<S>>::Output;
+    type Raised = <Cons<Body::Compose<Cont>, Cons<Cont, Nil>> as DeriveEach<S>>::Raised;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, L, R, IsDisjoint, S> Derive<S>
+    for TPar<IO, Lbl, L, R, IsDisjoint>
+where
+    L: TSession<IO> + Derive<S>,
+    R: TSession<IO> + Derive<S>,
+    <L as Derive<S>>::Output: ParLeftEach<IO, Lbl, R, IsDisjoint>,
+    <R as Derive<S>>::Output: ParRightEach<IO, Lbl, L, IsDisjoint>,
+    <<L as Derive<S>>::Output as ParLeftEach<IO, Lbl, R, IsDisjoint>>::Output:
+        Concat<<<R as Derive<S>>::Output as ParRightEach<IO, Lbl, L, IsDisjoint>>::Output>,
+    <L as Derive<S>>::Raised: types::BoolOr<<R as Derive<S>>::Raised>,
+{
+    type Output =
+        <<<L as Derive<S>>::Output as ParLeftEach<IO, Lbl, R, IsDisjoint>>::Output as Concat<
+            <<R as Derive<S>>::Output as ParRightEach<IO, Lbl, L, IsDisjoint>>::Output,
+        >>::Output;
+    type Raised = types::Or<<L as Derive<S>>::Raised, <R as Derive<S>>::Raised>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Branches, S> Derive<S> for TParN<IO, Lbl, Branches>
+where
+    Branches: SessionList<IO> + DeriveAt<S>,
+    <Branches as DeriveAt<S>>::Output: ParNEach<IO, Lbl>,
+{
+    type Output = <<Branches as DeriveAt<S>>::Output as ParNEach<IO, Lbl>>::Output;
+    type Raised = <Branches as DeriveAt<S>>::Raised;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Steps, S> Derive<S> for TShuffle<IO, Lbl, Steps>
+where
+    Steps: SessionList<IO> + DeriveAt<S>,
+    <Steps as DeriveAt<S>>::Output: ShuffleEach<IO, Lbl>,
+{
+    type Output = <<Steps as DeriveAt<S>>::Output as ShuffleEach<IO, Lbl>>::Output;
+    type Raised = <Steps as DeriveAt<S>>::Raised;
+}
+
+// `TRec` loops use their label as the recursion variable
+impl<IO, Lbl: types::ProtocolLabel, Body, S> Derive<S> for TRec<IO, Lbl, Body>
+where
+    Body: TSession<IO> + Unfold<IO, Lbl, TRec<IO, Lbl, Body>>,
+    <Body as Unfold<IO, Lbl, TRec<IO, Lbl, Body>>>::Output: Derive<S>,
+{
+    type Output = <<Body as Unfold<IO, Lbl, TRec<IO, Lbl, Body>>>::Output as Derive<S>>::Output;
+    type Raised = <<Body as Unfold<IO, Lbl, TRec<IO, Lbl, Body>>>::Output as Derive<S>>::Raised;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Var, Body, S> Derive<S> for TRecX<IO, Lbl, Var, Body>
+where
+    Body: TSession<IO> + Unfold<IO, Var, TRecX<IO, Lbl, Var, Body>>,
+    <Body as Unfold<IO, Var, TRecX<IO, Lbl, Var, Body>>>::Output: Derive<S>,
+{
+    type Output =
+        <<Body as Unfold<IO, Var, TRecX<IO, Lbl, Var, Body>>>::Output as Derive<S>>::Output;
+    type Raised =
+        <<Body as Unfold<IO, Var, TRecX<IO, Lbl, Var, Body>>>::Output as Derive<S>>::Raised;
+}
+
+// A free variable has nowhere to jump to; `WellScoped` rejects it
+impl<Var, S> Derive<S> for TVar<Var> {
+    type Output = Nil;
+    type Raised = types::False;
+}
+
+impl<IO, Lbl, Dur, Body, OnTimeout, S> Derive<S> for TTimeout<IO, Lbl, Dur, Body, OnTimeout>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    Body: TSession<IO> + Derive<S>,
+    OnTimeout: TSession<IO> + Derive<S>,
+    <Body as Derive<S>>::Output: TimeoutEach<IO, Lbl, Dur, OnTimeout>,
+    <<Body as Derive<S>>::Output as TimeoutEach<IO, Lbl, Dur, OnTimeout>>::Output:
+        Concat<<OnTimeout as Derive<S>>::Output>,
+    <Body as Derive<S>>::Raised: types::BoolOr<<OnTimeout as Derive<S>>::Raised>,
+{
+    type Output =
+        <<<Body as Derive<S>>::Output as TimeoutEach<IO, Lbl, Dur, OnTimeout>>::Output as Concat<
+            <OnTimeout as Derive<S>>::Output,
+        >>::Output;
+    type Raised = types::Or<<Body as Derive<S>>::Raised, <OnTimeout as Derive<S>>::Raised>;
+}
+
+impl<IO, Lbl, R, Dur, T, S> Derive<S> for TDelay<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: TSession<IO> + Derive<S>,
+{
+    type Output = <T as Derive<S>>::Output;
+    type Raised = <T as Derive<S>>::Raised;
+}
+
+impl<IO, Lbl, R, Dur, T, S> Derive<S> for TDeadline<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: TSession<IO> + Derive<S>,
+{
+    type Output = <T as Derive<S>>::Output;
+    type Raised = <T as Derive<S>>::Raised;
+}
+
+impl<IO, Lbl, Scope, Interruptor, Handler, S> Derive<S>
+    for TInterrupt<IO, Lbl, Scope, Interruptor, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Scope: TSession<IO> + Derive<S>,
+    Handler: TSession<IO> + Derive<S>,
+    <Scope as Derive<S>>::Output: InterruptEach<IO, Lbl, Interruptor, Handler>,
+    <<Scope as Derive<S>>::Output as InterruptEach<IO, Lbl, Interruptor, Handler>>::Output:
+        Concat<<Handler as Derive<S>>::Output>,
+    <Scope as Derive<S>>::Raised: types::BoolOr<<Handler as Derive<S>>::Raised>,
+{
+    type Output = <<<Scope as Derive<S>>::Output as InterruptEach<IO, Lbl, Interruptor, Handler>>::Output as Concat<
+        <Handler as Derive<S>>::Output,
+    >>::Output;
+    type Raised = types::Or<<Scope as Derive<S>>::Raised, <Handler as Derive<S>>::Raised>;
+}
+
+// Refinements constrain the first interaction of `Inner` and leave no trace
+impl<Pred, Inner: Derive<S>, S> Derive<S> for TRefine<Pred, Inner> {
+    type Output = <Inner as Derive<S>>::Output;
+    type Raised = <Inner as Derive<S>>::Raised;
+}
+
+impl<Meta, Inner: Derive<S>, S> Derive<S> for TAnnotate<Meta, Inner> {
+    type Output = <Inner as Derive<S>>::Output;
+    type Raised = <Inner as Derive<S>>::Raised;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R, Err, S> Derive<S> for TThrow<IO, Lbl, R, Err>
+where
+    S: StepMatches<R, Lbl, Err>,
+{
+    type Output = Nil;
+    type Raised = <S as StepMatches<R, Lbl, Err>>::Output;
+}
+
+// A throw in the body continues with the handler, outside the scope
+impl<IO, Lbl, Body, Handler, S> Derive<S> for TCatch<IO, Lbl, Body, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Body: TSession<IO> + Derive<S>,
+    Handler: TSession<IO>,
+    <Body as Derive<S>>::Output: CatchEach<IO, Lbl, Handler>,
+    (): MatchCase<<Body as Derive<S>>::Raised, Handler>,
+    <<Body as Derive<S>>::Output as CatchEach<IO, Lbl, Handler>>::Output:
+        Concat<<() as MatchCase<<Body as Derive<S>>::Raised, Handler>>::Output>,
+{
+    type Output =
+        <<<Body as Derive<S>>::Output as CatchEach<IO, Lbl, Handler>>::Output as Concat<
+            <() as MatchCase<<Body as Derive<S>>::Raised, Handler>>::Output,
+        >>::Output;
+    type Raised = types::False;
+}
+
+impl<S> DeriveEach<S> for Nil {
+    type Output = Nil;
+    type Raised = types::False;
+}
+
+impl<H, T, S> DeriveEach<S> for Cons<H, T>
+where
+    H: Derive<S>,
+    T: DeriveEach<S>,
+    <H as Derive<S>>::Output: Concat<<T as DeriveEach<S>>::Output>,
+    <H as Derive<S>>::Raised: types::BoolOr<<T as DeriveEach<S>>::Raised>,
+{
+    type Output = <<H as Derive<S>>::Output as Concat<<T as DeriveEach<S>>::Output>>::Output;
+    type Raised = types::Or<<H as Derive<S>>::Raised, <T as DeriveEach<S>>::Raised>;
+}
+
+impl<S> DeriveAt<S> for Nil {
+    type Output = Nil;
+    type Raised = types::False;
+}
+
+// Either the first branch takes the step, or one of the others does
+impl<H, T, S> DeriveAt<S> for Cons<H, T>
+where
+    H: Derive<S>,
+    T: DeriveAt<S>,
+    <H as Derive<S>>::Output: ConsEach<T>,
+    <T as DeriveAt<S>>::Output: PrependEach<H>,
+    <<H as Derive<S>>::Output as ConsEach<T>>::Output:
+        Concat<<<T as DeriveAt<S>>::Output as PrependEach<H>>::Output>,
+    <H as Derive<S>>::Raised: types::BoolOr<<T as DeriveAt<S>>::Raised>,
+{
+    type Output = <<<H as Derive<S>>::Output as ConsEach<T>>::Output as Concat<
+        <<T as DeriveAt<S>>::Output as PrependEach<H>>::Output,
+    >>::Output;
+    type Raised = types::Or<<H as Derive<S>>::Raised, <T as DeriveAt<S>>::Raised>;
+}
+
+/// Every element `X` of a type-level list turned into `Cons<X, T>`.
+pub trait ConsEach<T> {
+    type Output;
+}
+
+impl<T> ConsEach<T> for Nil {
+    type Output = Nil;
+}
+
+impl<X, Rest: ConsEach<T>, T> ConsEach<T> for Cons<X, Rest> {
+    type Output = Cons<Cons<X, T>, <Rest as ConsEach<T>>::Output>;
+}
+
+/// Every list of a type-level list with `H` put in front.
+pub trait PrependEach<H> {
+    type Output;
+}
+
+impl<H> PrependEach<H> for Nil {
+    type Output = Nil;
+}
+
+impl<X, Rest: PrependEach<H>, H> PrependEach<H> for Cons<X, Rest> {
+    type Output = Cons<Cons<H, X>, <Rest as PrependEach<H>>::Output>;
+}
+
+/// Every protocol `X` of a type-level list turned into `TPar<IO, Lbl, X, R, IsDisjoint>`.
+pub trait ParLeftEach<IO, Lbl, R, IsDisjoint> {
+    type Output;
+}
+
+impl<IO, Lbl, R, IsDisjoint> ParLeftEach<IO, Lbl, R, IsDisjoint> for Nil {
+    type Output = Nil;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R, IsDisjoint, X, Rest> ParLeftEach<IO, Lbl, R, IsDisjoint>
+    for Cons<X, Rest>
+where
+    X: TSession<IO>,
+    R: TSession<IO>,
+    Rest: ParLeftEach<IO, Lbl, R, IsDisjoint>,
+{
+    type Output = Cons<
+        TPar<IO, Lbl, X, R, IsDisjoint>,
+        <Rest as ParLeftEach<IO, Lbl, R, IsDisjoint>>::Output,
+    >;
+}
+
+/// Every protocol `X` of a type-level list turned into `TPar<IO, Lbl, L, X, IsDisjoint>`.
+pub trait ParRightEach<IO, Lbl, L, IsDisjoint> {
+    type Output;
+}
+
+impl<IO, Lbl, L, IsDisjoint> ParRightEach<IO, Lbl, L, IsDisjoint> for Nil {
+    type Output = Nil;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, L, IsDisjoint, X, Rest> ParRightEach<IO, Lbl, L, IsDisjoint>
+    for Cons<X, Rest>
+where
+    L: TSession<IO>,
+    X: TSession<IO>,
+    Rest: ParRightEach<IO, Lbl, L, IsDisjoint>,
+{
+    type Output = Cons<
+        TPar<IO, Lbl, L, X, IsDisjoint>,
+        <Rest as ParRightEach<IO, Lbl, L, IsDisjoint>>::Output,
+    >;
+}
+
+/// Every list of branches `X` of a type-level list turned into `TParN<IO, Lbl, X>`.
+pub trait ParNEach<IO, Lbl> {
+    type Output;
+}
+
+impl<IO, Lbl> ParNEach<IO, Lbl> for Nil {
+    type Output = Nil;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, X, Rest> ParNEach<IO, Lbl> for Cons<X, Rest>
+where
+    X: SessionList<IO>,
+    Rest: ParNEach<IO, Lbl>,
+{
+    type Output = Cons<TParN<IO, Lbl, X>, <Rest as ParNEach<IO, Lbl>>::Output>;
+}
+
+/// Every list of steps `X` of a type-level list turned into `TShuffle<IO, Lbl, X>`.
+pub trait ShuffleEach<IO, Lbl> {
+    type Output;
+}
+
+impl<IO, Lbl> ShuffleEach<IO, Lbl> for Nil {
+    type Output = Nil;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, X, Rest> ShuffleEach<IO, Lbl> for Cons<X, Rest>
+where
+    X: SessionList<IO>,
+    Rest: ShuffleEach<IO, Lbl>,
+{
+    type Output = Cons<TShuffle<IO, Lbl, X>, <Rest as ShuffleEach<IO, Lbl>>::Output>;
+}
+
+/// Every protocol `X` of a type-level list turned into `TCatch<IO, Lbl, X, Handler>`.
+pub trait CatchEach<IO, Lbl, Handler> {
+    type Output;
+}
+
+impl<IO, Lbl, Handler> CatchEach<IO, Lbl, Handler> for Nil {
+    type Output = Nil;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Handler, X, Rest> CatchEach<IO, Lbl, Handler> for Cons<X, Rest>
+where
+    X: TSession<IO>,
+    Handler: TSession<IO>,
+    Rest: CatchEach<IO, Lbl, Handler>,
+{
+    type Output = Cons<TCatch<IO, Lbl, X, Handler>, <Rest as CatchEach<IO, Lbl, Handler>>::Output>;
+}
+
+/// Every protocol `X` of a type-level list turned into
+/// `TTimeout<IO, Lbl, Dur, X, OnTimeout>`.
+pub trait TimeoutEach<IO, Lbl, Dur, OnTimeout> {
+    type Output;
+}
+
+impl<IO, Lbl, Dur, OnTimeout> TimeoutEach<IO, Lbl, Dur, OnTimeout> for Nil {
+    type Output = Nil;
+}
+
+impl<IO, Lbl, Dur, OnTimeout, X, Rest> TimeoutEach<IO, Lbl, Dur, OnTimeout> for Cons<X, Rest>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    X: TSession<IO>,
+    OnTimeout: TSession<IO>,
+    Rest: TimeoutEach<IO, Lbl, Dur, OnTimeout>,
+{
+    type Output = Cons<
+        TTimeout<IO, Lbl, Dur, X, OnTimeout>,
+        <Rest as TimeoutEach<IO, Lbl, Dur, OnTimeout>>::Output,
+    >;
+}
+
+/// Every protocol `X` of a type-level list turned into
+/// `TInterrupt<IO, Lbl, X, Interruptor, Handler>`.
+pub trait InterruptEach<IO, Lbl, Interruptor, Handler> {
+    type Output;
+}
+
+impl<IO, Lbl, Interruptor, Handler> InterruptEach<IO, Lbl, Interruptor, Handler> for Nil {
+    type Output = Nil;
+}
+
+impl<IO, Lbl, Interruptor, Handler, X, Rest> InterruptEach<IO, Lbl, Interruptor, Handler>
+    for Cons<X, Rest>
+where
+    Lbl: types::ProtocolLabel,
+    X: TSession<IO>,
+    Handler: TSession<IO>,
+    Rest: InterruptEach<IO, Lbl, Interruptor, Handler>,
+{
+    type Output = Cons<
+        TInterrupt<IO, Lbl, X, Interruptor, Handler>,
+        <Rest as InterruptEach<IO, Lbl, Interruptor, Handler>>::Output,
+    >;
+}
+
+impl<IO, Lbl> Nullable for TEnd<IO, Lbl> {
+    type Output = types::True;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R, H, T> Nullable for TInteract<IO, Lbl, R, H, T>
+where
+    T: TSession<IO>,
+{
+    type Output = types::False;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, To, H, T> Nullable for TMsg<IO, Lbl, From, To, H, T>
+where
+    T: TSession<IO>,
+{
+    type Output = types::False;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, ToSet, H, T> Nullable
+    for TBroadcast<IO, Lbl, From, ToSet, H, T>
+where
+    T: TSession<IO>,
+{
+    type Output = types::False;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, WorkerSet, H, T> Nullable
+    for TAnycast<IO, Lbl, From, WorkerSet, H, T>
+where
+    T: TSession<IO>,
+{
+    type Output = types::False;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, To, DelegatedEp, T> Nullable
+    for TDelegate<IO, Lbl, From, To, DelegatedEp, T>
+where
+    T: TSession<IO>,
+{
+    type Output = types::False;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, A, B, Cont> Nullable for TDisconnect<IO, Lbl, A, B, Cont>
+where
+    Cont: TSession<IO> + Nullable,
+{
+    type Output = <Cont as Nullable>::Output;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, A, B, Cont> Nullable for TConnect<IO, Lbl, A, B, Cont>
+where
+    Cont: TSession<IO> + Nullable,
+{
+    type Output = <Cont as Nullable>::Output;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R, Cont> Nullable for TEndFor<IO, Lbl, R, Cont>
+where
+    Cont: TSession<IO> + Nullable,
+{
+    type Output = <Cont as Nullable>::Output;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, L, R> Nullable for TChoice<IO, Lbl, L, R>
+where
+    L: TSession<IO> + Nullable,
+    R: TSession<IO> + Nullable,
+    <L as Nullable>::Output: types::BoolOr<<R as Nullable>::Output>,
+{
+    type Output = types::Or<<L as Nullable>::Output, <R as Nullable>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Branches> Nullable for TChoiceN<IO, Lbl, Branches>
+where
+    Branches: SessionList<IO> + AnyNullable,
+{
+    type Output = <Branches as AnyNullable>::Output;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Chooser, Branches> Nullable
+    for TSelect<IO, Lbl, Chooser, Branches>
+where
+    Branches: SessionList<IO> + AnyNullable,
+{
+    type Output = <Branches as AnyNullable>::Output;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Offeree, Branches> Nullable
+    for TOffer<IO, Lbl, Offeree, Branches>
+where
+    Branches: SessionList<IO> + AnyNullable,
+{
+    type Output = <Branches as AnyNullable>::Output;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, High, Low> Nullable for TPriorityChoice<IO, Lbl, High, Low>
+where
+    High: TSession<IO> + Nullable,
+    Low: TSession<IO> + Nullable,
+    <High as Nullable>::Output: types::BoolOr<<Low as Nullable>::Output>,
+{
+    type Output = types::Or<<High as Nullable>::Output, <Low as Nullable>::Output>;
+}
+
+impl<IO, Lbl, WeightL, L, WeightR, R> Nullable for TProb<IO, Lbl, WeightL, L, WeightR, R>
+where
+    Lbl: types::ProtocolLabel,
+    WeightL: types::WeightMarker,
+    WeightR: types::WeightMarker,
+    L: TSession<IO> + Nullable,
+    R: TSession<IO> + Nullable,
+    <L as Nullable>::Output: types::BoolOr<<R as Nullable>::Output>,
+{
+    type Output = types::Or<<L as Nullable>::Output, <R as Nullable>::Output>;
+}
+
+// Skipping the optional body ends the protocol if the continuation can
+impl<IO, Lbl: types::ProtocolLabel, Decider, Body, Cont> Nullable
+    for TOptional<IO, Lbl, Decider, Body, Cont>
+where
+    Body: TSession<IO>,
+    Cont: TSession<IO> + Nullable,
+{
+    type Output = <Cont as Nullable>::Output;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, L, R, IsDisjoint> Nullable for TPar<IO, Lbl, L, R, IsDisjoint>
+where
+    L: TSession<IO> + Nullable,
+    R: TSession<IO> + Nullable,
+    <L as Nullable>::Output: types::BoolAnd<<R as Nullable>::Output>,
+{
+    type Output = types::And<<L as Nullable>::Output, <R as Nullable>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Branches> Nullable for TParN<IO, Lbl, Branches>
+where
+    Branches: SessionList<IO> + AllNullable,
+{
+    type Output = <Branches as AllNullable>::Output;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Steps> Nullable for TShuffle<IO, Lbl, Steps>
+where
+    Steps: SessionList<IO> + AllNullable,
+{
+    type Output = <Steps as AllNullable>::Output;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Body> Nullable for TRec<IO, Lbl, Body>
+where
+    Body: TSession<IO> + Nullable,
+{
+    type Output = <Body as Nullable>::Output;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Var, Body> Nullable for TRecX<IO, Lbl, Var, Body>
+where
+    Body: TSession<IO> + Nullable,
+{
+    type Output = <Body as Nullable>::Output;
+}
+
+// Jumping back restarts the loop, which needs a step before it can end
+impl<Var> Nullable for TVar<Var> {
+    type Output = types::False;
+}
+
+impl<IO, Lbl, Dur, Body, OnTimeout> Nullable for TTimeout<IO, Lbl, Dur, Body, OnTimeout>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    Body: TSession<IO> + Nullable,
+    OnTimeout: TSession<IO> + Nullable,
+    <Body as Nullable>::Output: types::BoolOr<<OnTimeout as Nullable>::Output>,
+{
+    type Output = types::Or<<Body as Nullable>::Output, <OnTimeout as Nullable>::Output>;
+}
+
+impl<IO, Lbl, R, Dur, T> Nullable for TDelay<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: TSession<IO> + Nullable,
+{
+    type Output = <T as Nullable>::Output;
+}
+
+impl<IO, Lbl, R, Dur, T> Nullable for TDeadline<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: TSession<IO> + Nullable,
+{
+    type Output = <T as Nullable>::Output;
+}
+
+impl<IO, Lbl, Scope, Interruptor, Handler> Nullable
+    for TInterrupt<IO, Lbl, Scope, Interruptor, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Scope: TSession<IO> + Nullable,
+    Handler: TSession<IO> + Nullable,
+    <Scope as Nullable>::Output: types::BoolOr<<Handler as Nullable>::Output>,
+{
+    type Output = types::Or<<Scope as Nullable>::Output, <Handler as Nullable>::Output>;
+}
+
+impl<Pred, Inner: Nullable> Nullable for TRefine<Pred, Inner> {
+    type Output = <Inner as Nullable>::Output;
+}
+
+impl<Meta, Inner: Nullable> Nullable for TAnnotate<Meta, Inner> {
+    type Output = <Inner as Nullable>::Output;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R, Err> Nullable for TThrow<IO, Lbl, R, Err> {
+    type Output = types::False;
+}
+
+impl<IO, Lbl, Body, Handler> Nullable for TCatch<IO, Lbl, Body, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Body: TSession<IO> + Nullable,
+    Handler: TSession<IO>,
+{
+    type Output = <Body as Nullable>::Output;
+}
+
+impl AnyNullable for Nil {
+    type Output = types::False;
+}
+
+impl<H, T> AnyNullable for Cons<H, T>
+where
+    H: Nullable,
+    T: AnyNullable,
+    <H as Nullable>::Output: types::BoolOr<<T as AnyNullable>::Output>,
+{
+    type Output = types::Or<<H as Nullable>::Output, <T as AnyNullable>::Output>;
+}
+
+impl AllNullable for Nil {
+    type Output = types::True;
+}
+
+impl<H, T> AllNullable for Cons<H, T>
+where
+    H: Nullable,
+    T: AllNullable,
+    <H as Nullable>::Output: types::BoolAnd<<T as AllNullable>::Output>,
+{
+    type Output = types::And<<H as Nullable>::Output, <T as AllNullable>::Output>;
+}
+
+impl<IO, Lbl, Var, Loop> Unfold<IO, Var, Loop> for TEnd<IO, Lbl> {
+    type Output = TEnd<IO, Lbl>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R, H, T, Var, Loop> Unfold<IO, Var, Loop>
+    for TInteract<IO, Lbl, R, H, T>
+where
+    T: TSession<IO> + Unfold<IO, Var, Loop>,
+{
+    type Output = TInteract<IO, Lbl, R, H, <T as Unfold<IO, Var, Loop>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, To, H, T, Var, Loop> Unfold<IO, Var, Loop>
+    for TMsg<IO, Lbl, From, To, H, T>
+where
+    T: TSession<IO> + Unfold<IO, Var, Loop>,
+{
+    type Output = TMsg<IO, Lbl, From, To, H, <T as Unfold<IO, Var, Loop>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, ToSet, H, T, Var, Loop> Unfold<IO, Var, Loop>
+    for TBroadcast<IO, Lbl, From, ToSet, H, T>
+where
+    T: TSession<IO> + Unfold<IO, Var, Loop>,
+{
+    type Output = TBroadcast<IO, Lbl, From, ToSet, H, <T as Unfold<IO, Var, Loop>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, WorkerSet, H, T, Var, Loop> Unfold<IO, Var, Loop>
+    for TAnycast<IO, Lbl, From, WorkerSet, H, T>
+where
+    T: TSession<IO> + Unfold<IO, Var, Loop>,
+{
+    type Output = TAnycast<IO, Lbl, From, WorkerSet, H, <T as Unfold<IO, Var, Loop>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, From, To, DelegatedEp, T, Var, Loop> Unfold<IO, Var, Loop>
+    for TDelegate<IO, Lbl, From, To, DelegatedEp, T>
+where
+    T: TSession<IO> + Unfold<IO, Var, Loop>,
+{
+    type Output = TDelegate<IO, Lbl, From, To, DelegatedEp, <T as Unfold<IO, Var, Loop>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, A, B, Cont, Var, Loop> Unfold<IO, Var, Loop>
+    for TDisconnect<IO, Lbl, A, B, Cont>
+where
+    Cont: TSession<IO> + Unfold<IO, Var, Loop>,
+{
+    type Output = TDisconnect<IO, Lbl, A, B, <Cont as Unfold<IO, Var, Loop>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, A, B, Cont, Var, Loop> Unfold<IO, Var, Loop>
+    for TConnect<IO, Lbl, A, B, Cont>
+where
+    Cont: TSession<IO> + Unfold<IO, Var, Loop>,
+{
+    type Output = TConnect<IO, Lbl, A, B, <Cont as Unfold<IO, Var, Loop>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R, Cont, Var, Loop> Unfold<IO, Var, Loop>
+    for TEndFor<IO, Lbl, R, Cont>
+where
+    Cont: TSession<IO> + Unfold<IO, Var, Loop>,
+{
+    type Output = TEndFor<IO, Lbl, R, <Cont as Unfold<IO, Var, Loop>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, L, R, Var, Loop> Unfold<IO, Var, Loop>
+    for TChoice<IO, Lbl, L, R>
+where
+    L: TSession<IO> + Unfold<IO, Var, Loop>,
+    R: TSession<IO> + Unfold<IO, Var, Loop>,
+{
+    type Output = TChoice<
+        IO,
+        Lbl,
+        <L as Unfold<IO, Var, Loop>>::Output,
+        <R as Unfold<IO, Var, Loop>>::Output,
+    >;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Branches, Var, Loop> Unfold<IO, Var, Loop>
+    for TChoiceN<IO, Lbl, Branches>
+where
+    Branches: SessionList<IO> + UnfoldEach<IO, Var, Loop>,
+{
+    type Output = TChoiceN<IO, Lbl, <Branches as UnfoldEach<IO, Var, Loop>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Chooser, Branches, Var, Loop> Unfold<IO, Var, Loop>
+    for TSelect<IO, Lbl, Chooser, Branches>
+where
+    Branches: SessionList<IO> + UnfoldEach<IO, Var, Loop>,
+{
+    type Output = TSelect<IO, Lbl, Chooser, <Branches as UnfoldEach<IO, Var, Loop>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Offeree, Branches, Var, Loop> Unfold<IO, Var, Loop>
+    for TOffer<IO, Lbl, Offeree, Branches>
+where
+    Branches: SessionList<IO> + UnfoldEach<IO, Var, Loop>,
+{
+    type Output = TOffer<IO, Lbl, Offeree, <Branches as UnfoldEach<IO, Var, Loop>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, High, Low, Var, Loop> Unfold<IO, Var, Loop>
+    for TPriorityChoice<IO, Lbl, High, Low>
+where
+    High: TSession<IO> + Unfold<IO, Var, Loop>,
+    Low: TSession<IO> + Unfold<IO, Var, Loop>,
+{
+    type Output = TPriorityChoice<
+        IO,
+        Lbl,
+        <High as Unfold<IO, Var, Loop>>::Output,
+        <Low as Unfold<IO, Var, Loop>>::Output,
+    >;
+}
+
+impl<IO, Lbl, WeightL, L, WeightR, R, Var, Loop> Unfold<IO, Var, Loop>
+    for TProb<IO, Lbl, WeightL, L, WeightR, R>
+where
+    Lbl: types::ProtocolLabel,
+    WeightL: types::WeightMarker,
+    WeightR: types::WeightMarker,
+    L: TSession<IO> + Unfold<IO, Var, Loop>,
+    R: TSession<IO> + Unfold<IO, Var, Loop>,
+{
+    type Output = TProb<
+        IO,
+        Lbl,
+        WeightL,
+        <L as Unfold<IO, Var, Loop>>::Output,
+        WeightR,
+        <R as Unfold<IO, Var, Loop>>::Output,
+    >;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Decider, Body, Cont, Var, Loop> Unfold<IO, Var, Loop>
+    for TOptional<IO, Lbl, Decider, Body, Cont>
+where
+    Body: TSession<IO> + Unfold<IO, Var, Loop>,
+    Cont: TSession<IO> + Unfold<IO, Var, Loop>,
+{
+    type Output = TOptional<
+        IO,
+        Lbl,
+        Decider,
+        <Body as Unfold<IO, Var, Loop>>::Output,
+        <Cont as Unfold<IO, Var, Loop>>::Output,
+    >;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, L, R, IsDisjoint, Var, Loop> Unfold<IO, Var, Loop>
+    for TPar<IO, Lbl, L, R, IsDisjoint>
+where
+    L: TSession<IO> + Unfold<IO, Var, Loop>,
+    R: TSession<IO> + Unfold<IO, Var, Loop>,
+{
+    type Output = TPar<
+        IO,
+        Lbl,
+        <L as Unfold<IO, Var, Loop>>::Output,
+        <R as Unfold<IO, Var, Loop>>::Output,
+        IsDisjoint,
+    >;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Branches, Var, Loop> Unfold<IO, Var, Loop>
+    for TParN<IO, Lbl, Branches>
+where
+    Branches: SessionList<IO> + UnfoldEach<IO, Var, Loop>,
+{
+    type Output = TParN<IO, Lbl, <Branches as UnfoldEach<IO, Var, Loop>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Steps, Var, Loop> Unfold<IO, Var, Loop>
+    for TShuffle<IO, Lbl, Steps>
+where
+    Steps: SessionList<IO> + UnfoldEach<IO, Var, Loop>,
+{
+    type Output = TShuffle<IO, Lbl, <Steps as UnfoldEach<IO, Var, Loop>>::Output>;
+}
+
+// Nested loops are unfolded through; they must not rebind `Var`
+impl<IO, Lbl: types::ProtocolLabel, Body, Var, Loop> Unfold<IO, Var, Loop> for TRec<IO, Lbl, Body>
+where
+    Body: TSession<IO> + Unfold<IO, Var, Loop>,
+{
+    type Output = TRec<IO, Lbl, <Body as Unfold<IO, Var, Loop>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, V, Body, Var, Loop> Unfold<IO, Var, Loop>
+    for TRecX<IO, Lbl, V, Body>
+where
+    Body: TSession<IO> + Unfold<IO, Var, Loop>,
+{
+    type Output = TRecX<IO, Lbl, V, <Body as Unfold<IO, Var, Loop>>::Output>;
+}
+
+impl<IO, V, Var, Loop> Unfold<IO, Var, Loop> for TVar<V>
+where
+    V: VarEq<Var>,
+    TVar<V>: UnfoldVarCase<IO, Loop, <V as VarEq<Var>>::Output>,
+{
+    type Output = <TVar<V> as UnfoldVarCase<IO, Loop, <V as VarEq<Var>>::Output>>::Output;
+}
+
+impl<IO, V, Loop: TSession<IO>> UnfoldVarCase<IO, Loop, types::True> for TVar<V> {
+    type Output = Loop;
+}
+
+impl<IO, V, Loop> UnfoldVarCase<IO, Loop, types::False> for TVar<V> {
+    type Output = TVar<V>;
+}
+
+impl<IO, Lbl, Dur, Body, OnTimeout, Var, Loop> Unfold<IO, Var, Loop>
+    for TTimeout<IO, Lbl, Dur, Body, OnTimeout>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    Body: TSession<IO> + Unfold<IO, Var, Loop>,
+    OnTimeout: TSession<IO> + Unfold<IO, Var, Loop>,
+{
+    type Output = TTimeout<
+        IO,
+        Lbl,
+        Dur,
+        <Body as Unfold<IO, Var, Loop>>::Output,
+        <OnTimeout as Unfold<IO, Var, Loop>>::Output,
+    >;
+}
+
+impl<IO, Lbl, R, Dur, T, Var, Loop> Unfold<IO, Var, Loop> for TDelay<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: TSession<IO> + Unfold<IO, Var, Loop>,
+{
+    type Output = TDelay<IO, Lbl, R, Dur, <T as Unfold<IO, Var, Loop>>::Output>;
+}
+
+impl<IO, Lbl, R, Dur, T, Var, Loop> Unfold<IO, Var, Loop> for TDeadline<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: TSession<IO> + Unfold<IO, Var, Loop>,
+{
+    type Output = TDeadline<IO, Lbl, R, Dur, <T as Unfold<IO, Var, Loop>>::Output>;
+}
+
+impl<IO, Lbl, Scope, Interruptor, Handler, Var, Loop> Unfold<IO, Var, Loop>
+    for TInterrupt<IO, Lbl, Scope, Interruptor, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Scope: TSession<IO> + Unfold<IO, Var, Loop>,
+    Handler: TSession<IO> + Unfold<IO, Var, Loop>,
+{
+    type Output = TInterrupt<
+        IO,
+        Lbl,
+        <Scope as Unfold<IO, Var, Loop>>::Output,
+        Interruptor,
+        <Handler as Unfold<IO, Var, Loop>>::Output,
+    >;
+}
+
+impl<IO, Pred: types::Predicate, Inner, Var, Loop> Unfold<IO, Var, Loop> for TRefine<Pred, Inner>
+where
+    Inner: TSession<IO> + Unfold<IO, Var, Loop>,
+{
+    type Output = TRefine<Pred, <Inner as Unfold<IO, Var, Loop>>::Output>;
+}
+
+impl<IO, Meta, Inner, Var, Loop> Unfold<IO, Var, Loop> for TAnnotate<Meta, Inner>
+where
+    Inner: TSession<IO> + Unfold<IO, Var, Loop>,
+{
+    type Output = TAnnotate<Meta, <Inner as Unfold<IO, Var, Loop>>::Output>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R, Err, Var, Loop> Unfold<IO, Var, Loop>
+    for TThrow<IO, Lbl, R, Err>
+{
+    type Output = TThrow<IO, Lbl, R, Err>;
+}
+
+impl<IO, Lbl, Body, Handler, Var, Loop> Unfold<IO, Var, Loop> for TCatch<IO, Lbl, Body, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Body: TSession<IO> + Unfold<IO, Var, Loop>,
+    Handler: TSession<IO> + Unfold<IO, Var, Loop>,
+{
+    type Output = TCatch<
+        IO,
+        Lbl,
+        <Body as Unfold<IO, Var, Loop>>::Output,
+        <Handler as Unfold<IO, Var, Loop>>::Output,
+    >;
+}
+
+impl<IO, Var, Loop> UnfoldEach<IO, Var, Loop> for Nil {
+    type Output = Nil;
+}
+
+impl<IO, H, T, Var, Loop> UnfoldEach<IO, Var, Loop> for Cons<H, T>
+where
+    H: Unfold<IO, Var, Loop>,
+    T: UnfoldEach<IO, Var, Loop>,
+{
+    type Output =
+        Cons<<H as Unfold<IO, Var, Loop>>::Output, <T as UnfoldEach<IO, Var, Loop>>::Output>;
+}
@@ -6,18 +6,59 @@
 //! Key components:
 //!
 //! - `ProjectRole`: Main trait for projecting global protocols onto specific roles
+//! - `Permissive` / `Strict`: Projection strategies selected by the last parameter of `ProjectRole`
+//! - `ProjectionFailure`: Never-implemented trait reporting failure markers such as `SelfMessage`, `UninformedOfChoice`, `NotParticipating` and `RoleAfterEnd`
 //! - `ProjectInteract`: Helper trait for projecting individual interactions
+//! - `ProjectMsg`: Helper trait for projecting interactions with explicit receivers
+//! - `ProjectBroadcast`: Helper trait for projecting fan-out sends
+//! - `ProjectDelegate`: Helper trait for projecting session delegation
 //! - `ProjectChoice`: Helper trait for projecting protocol branches
+//! - `MergeBranches`: The merge of the projected branches of a `TChoice` for a role that does not own it
+//! - `ProjectBranches`: Helper trait for projecting the branch lists of n-ary combinators
+//! - `ProjectTaggedChoiceCase` / `ProjectTaggedBranches`: Helper traits projecting `TChoiceN` with tagged branches
+//! - `BranchesTagged`: Whether the branches of a choice start with pairwise distinct labels
+//! - `BranchTags`: The labels a local choice dispatches on
+//! - `ProjectSelectCase` / `ProjectOfferCase`: Helper traits for projecting directed choices
 //! - `ProjectPar`: Helper trait for projecting parallel compositions
+//! - `ProjectShuffleCase`: Helper trait for projecting shuffles
+//! - `ProjectRecXCase`: Helper trait for projecting recursion binders
+//! - `ProjectTimeoutCase`: Helper trait for projecting timed fragments
+//! - `ProjectDelay` / `ProjectDeadline`: Helper traits for projecting scheduling constraints
+//! - `ProjectInterruptCase`: Helper trait for projecting interruptible scopes
+//! - `ProjectOptionalCase`: Helper trait for projecting optional steps
+//! - `ProjectPriorityCase`: Helper trait for projecting prioritized choices
+//! - `ProjectDisconnect`: Helper trait for projecting channel closes
+//! - `ProjectConnect`: Helper trait for projecting channel opens
+//! - `ProjectEndForCase` / `EndForRejoinCase`: Helper traits for projecting per-role termination
+//! - `ProjectRefineCase`: Helper trait for projecting refinements
+//! - `ProjectThrow` / `ProjectCatchCase`: Helper traits for projecting failure handling
+//! - `GetLocalRole`: Extracts the role a local session type belongs to
 //! - `ContainsRole`: Helper trait to check if a role participates in a protocol
+//! - `ActsFirst`: Helper trait to find the role that owns a choice
+//! - `SingleChooser`: Every branch of a choice is started by its owner, failing with `MixedChoosers`
+//! - `ProjectAll` / `LocalOf`: Projection onto every role at once, and lookup by role
+//! - `ProjectMixed`: Projection of a role taking part in protocols over several IO markers
+//! - `FilterByIO` / `ProjectByIO`: The protocols over one IO marker, and their projections
+//! - `IsProjectable`: Whether projection onto a role is defined
+//! - `ProjectionComposes`: Whether projection commutes with sequential composition
+//! - `RoleFamily` / `IndexEq`: Indexed families of roles, such as `Worker<N>`, compared by index
+//! - `ReassignRole` / `ProjectsAlike`: A local type run by another role, and symmetric projection
 //!
 //! These transformations ensure that global protocols can be correctly
 //! interpreted from the perspective of each participating role.
 
+use super::awareness::ChoiceAware;
 use super::base::*;
 use super::global::*;
 use super::local::*;
+use super::merge::{Merge, WithChoiceLabel};
+use super::normalize::Normalize;
+use super::recursion::Guarded;
+use super::shuffle::PairwiseIndependent;
+use super::validity::LocallyWellFormed;
+use crate::introspection::{AddIos, IoEq, PairwiseDisjoint, RolesOf, RolesOfEach};
 use crate::types;
+use core::marker::PhantomData;
 
 /// Projects a global protocol onto a single role, producing the local protocol for that role.
 ///
@@ -54,10 +95,128 @@ use crate::types;
 ///     >
 /// );
 /// ```
-pub trait ProjectRole<Me, IO, G: TSession<IO>> {
+///
+/// The `Strategy` parameter defaults to [`Permissive`], which inserts `EpSkip`
+/// wherever the role has nothing to do. [`Strict`] rejects roles that do not
+/// take part in the protocol and returns the normal form of the projection.
+#[diagnostic::on_unimplemented(
+    message = "the protocol `{G}` cannot be projected onto the role `{Me}`",
+    label = "no projection onto `{Me}`",
+    note = "every role needs `Role` and a `RoleEq` impl against every role of the protocol"
+)]
+pub trait ProjectRole<Me, IO, G: TSession<IO>, Strategy = Permissive> {
+    type Out: EpSession<IO, Me>;
+}
+
+/// Projection strategy: the lenient projection, which keeps `EpSkip`
+/// placeholders for the steps a role is not involved in. The default.
+pub struct Permissive;
+
+/// Projection strategy: classical projection. The role must take part in the
+/// protocol, every choice of the protocol must be decided by a single role
+/// that the others learn of ([`ChoiceAware`]), and the result is the
+/// [`Normalize`]d permissive projection, which must be [`LocallyWellFormed`].
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Alice; struct Bob; struct Carol; struct Both; struct Ping; struct Pong;
+/// impl Role for Alice {} impl Role for Bob {} impl Role for Carol {}
+/// impl ProtocolLabel for Both {} impl ProtocolLabel for Ping {} impl ProtocolLabel for Pong {}
+/// impl LabelEq<Ping> for Ping { type Output = True; }
+/// impl LabelEq<Pong> for Ping { type Output = False; }
+/// impl LabelEq<Ping> for Pong { type Output = False; }
+/// impl LabelEq<Pong> for Pong { type Output = True; }
+/// impl RoleEq<Alice> for Alice { type Output = True; }
+/// impl RoleEq<Bob> for Alice   { type Output = False; }
+/// impl RoleEq<Carol> for Alice { type Output = False; }
+/// impl RoleEq<Alice> for Bob   { type Output = False; }
+/// impl RoleEq<Bob> for Bob     { type Output = True; }
+/// impl RoleEq<Carol> for Bob   { type Output = False; }
+/// impl RoleEq<Alice> for Carol { type Output = False; }
+/// impl RoleEq<Bob> for Carol   { type Output = False; }
+/// impl RoleEq<Carol> for Carol { type Output = True; }
+///
+/// type ToBob = TMsg<Http, Ping, Alice, Bob, Message, TEnd<Http, EmptyLabel>>;
+/// type ToCarol = TMsg<Http, Pong, Alice, Carol, Response, TEnd<Http, EmptyLabel>>;
+/// type G = TPar<Http, Both, ToBob, ToCarol, False>;
+/// type SendPing = EpSend<Http, Ping, Alice, Message, EpEnd<Http, EmptyLabel, Alice>>;
+/// type SendPong = EpSend<Http, Pong, Alice, Response, EpEnd<Http, EmptyLabel, Alice>>;
+/// // The permissive projection keeps the binary parallel composition...
+/// assert_type_eq!(
+///     <() as ProjectRole<Alice, Http, G>>::Out,
+///     EpPar<Http, Both, Alice, SendPing, SendPong>
+/// );
+/// // ...while the strict projection returns its normal form.
+/// assert_type_eq!(
+///     <() as ProjectRole<Alice, Http, G, Strict>>::Out,
+///     EpParN<Http, Both, Alice, tlist!(SendPing, SendPong)>
+/// );
+/// ```
+pub struct Strict;
+
+impl<Me, IO, G> ProjectRole<Me, IO, G, Strict> for ()
+where
+    G: TSession<IO> + ContainsRole<Me>,
+    (): ProjectStrictCase<Me, IO, G, <G as ContainsRole<Me>>::Output>,
+{
+    type Out = <() as ProjectStrictCase<Me, IO, G, <G as ContainsRole<Me>>::Output>>::Out;
+}
+
+/// Helper trait for [`Strict`] projection: dispatches on whether `Me` takes
+/// part in `G`.
+pub trait ProjectStrictCase<Me, IO, G: TSession<IO>, Participates> {
     type Out: EpSession<IO, Me>;
 }
 
+// The role takes part: normalize the permissive projection of a protocol
+// whose choices every role learns of
+impl<Me, IO, G> ProjectStrictCase<Me, IO, G, types::True> for ()
+where
+    G: TSession<IO> + ChoiceAware,
+    (): ProjectRole<Me, IO, G>,
+    <() as ProjectRole<Me, IO, G>>::Out: Normalize,
+    <<() as ProjectRole<Me, IO, G>>::Out as Normalize>::Output:
+        EpSession<IO, Me> + LocallyWellFormed<IO, Me>,
+{
+    type Out = <<() as ProjectRole<Me, IO, G>>::Out as Normalize>::Output;
+}
+
+// The role never appears in the protocol
+impl<Me, IO, G> ProjectStrictCase<Me, IO, G, types::False> for ()
+where
+    Me: Role,
+    G: TSession<IO>,
+    NotParticipating<Me>: ProjectionFailure,
+{
+    type Out = EpEnd<IO, types::EmptyLabel, Me>;
+}
+
+/// Failure marker: `Me` both sends and receives the message labelled `Lbl`.
+pub struct SelfMessage<Me, Lbl>(PhantomData<(Me, Lbl)>);
+
+/// Failure marker: `Me` takes part in only one branch of the choice labelled
+/// `Lbl` without owning it, so it cannot learn which branch was taken.
+pub struct UninformedOfChoice<Me, Lbl>(PhantomData<(Me, Lbl)>);
+
+/// Failure marker: `Me` does not take part in a protocol projected with the
+/// [`Strict`] strategy.
+pub struct NotParticipating<Me>(PhantomData<Me>);
+
+/// Failure marker: `R` leaves the session at the `TEndFor` labelled `Lbl` and
+/// then takes part in its continuation again.
+pub struct RoleAfterEnd<R, Lbl>(PhantomData<(R, Lbl)>);
+
+/// Never implemented: projection rules that detect an unprojectable protocol
+/// require a failure marker to implement it, so the error names the marker
+/// together with the offending role and label.
+#[diagnostic::on_unimplemented(
+    message = "projection failed: `{Self}`",
+    label = "this protocol cannot be projected",
+    note = "the failure marker names the offending role and the label of the combinator"
+)]
+pub trait ProjectionFailure {}
+
 // Base case: projecting end-of-session yields EpEnd with preserved label
 impl<Me, IO, Lbl> ProjectRole<Me, IO, TEnd<IO, Lbl>> for ()
 where
@@ -119,675 +278,4655 @@ where
     type Out = EpRecv<IO, Lbl, Me, H, <() as ProjectRole<Me, IO, T>>::Out>;
 }
 
-/// Helper trait for projecting a protocol choice.
-///
-/// - `Me`: The role being projected.
-/// - `IO`: Protocol marker type.
-/// - `L`, `R`: The two protocol branches.
-pub trait ProjectChoice<Me, IO, L: TSession<IO>, R: TSession<IO>> {
-    type Out: EpSession<IO, Me>;
+// Projection for an interaction with explicit sender and receiver: dispatch on
+// whether the projected role is the sender and whether it is the receiver
+impl<Me, IO, Lbl, From, To, H, T> ProjectRole<Me, IO, TMsg<IO, Lbl, From, To, H, T>> for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+    From: Role,
+    To: Role,
+    T: TSession<IO>,
+    Me: RoleEq<From> + RoleEq<To>,
+    <Me as RoleEq<From>>::Output: types::Bool,
+    <Me as RoleEq<To>>::Output: types::Bool,
+    (): ProjectMsg<<Me as RoleEq<From>>::Output, <Me as RoleEq<To>>::Output, Me, IO, Lbl, H, T>,
+{
+    type Out = <() as ProjectMsg<
+        <Me as RoleEq<From>>::Output,
+        <Me as RoleEq<To>>::Output,
+        Me,
+        IO,
+        Lbl,
+        H,
+        T,
+    >>::Out;
 }
 
-// Projection implementation for TChoice - delegate to ProjectChoice helper with preserved label
-impl<Me, IO, Lbl, L, R> ProjectRole<Me, IO, TChoice<IO, Lbl, L, R>> for ()
+// Projection for a broadcast: dispatch on whether the projected role is the
+// sender and whether it belongs to the receiver set
+impl<Me, IO, Lbl, From, ToSet, H, T> ProjectRole<Me, IO, TBroadcast<IO, Lbl, From, ToSet, H, T>>
+    for ()
 where
     Me: Role,
     Lbl: types::ProtocolLabel,
-    L: TSession<IO>,
-    R: TSession<IO>,
-    L: ContainsRole<Me>,
-    <L as ContainsRole<Me>>::Output: types::Bool,
-    R: ContainsRole<Me>,
-    <R as ContainsRole<Me>>::Output: types::Bool,
-    (): ProjectChoiceCase<
+    From: Role,
+    T: TSession<IO>,
+    Me: RoleEq<From> + InRoleSet<ToSet>,
+    <Me as RoleEq<From>>::Output: types::Bool,
+    (): ProjectBroadcast<
+        <Me as RoleEq<From>>::Output,
+        <Me as InRoleSet<ToSet>>::Output,
         Me,
         IO,
         Lbl,
-        L,
-        R,
-        <L as ContainsRole<Me>>::Output,
-        <R as ContainsRole<Me>>::Output,
+        ToSet,
+        H,
+        T,
     >,
 {
-    type Out = <() as ProjectChoiceCase<
+    type Out = <() as ProjectBroadcast<
+        <Me as RoleEq<From>>::Output,
+        <Me as InRoleSet<ToSet>>::Output,
         Me,
         IO,
         Lbl,
-        L,
-        R,
-        <L as ContainsRole<Me>>::Output,
-        <R as ContainsRole<Me>>::Output,
+        ToSet,
+        H,
+        T,
     >>::Out;
 }
 
-// Helper trait for handling different cases of ProjectChoice based on role presence
-pub trait ProjectChoiceCase<Me, IO, Lbl: types::ProtocolLabel, L: TSession<IO>, R: TSession<IO>, LContainsMe, RContainsMe> {
+/// Helper trait for projecting a `TBroadcast` interaction.
+///
+/// - `IsFrom`: Type-level boolean, is the projected role the sender?
+/// - `InSet`: Type-level boolean, is the projected role in the receiver set?
+/// - `Me`: The role being projected.
+/// - `IO`: Protocol marker type.
+/// - `Lbl`: Label for this interaction (preserved from global protocol).
+/// - `ToSet`: Type-level list of receiving roles.
+/// - `H`: Message type.
+/// - `T`: Continuation protocol.
+///
+/// As with `ProjectMsg`, a sender that is also in its own receiver set has no
+/// projection.
+pub trait ProjectBroadcast<
+    IsFrom,
+    InSet,
+    Me: Role,
+    IO,
+    Lbl: types::ProtocolLabel,
+    ToSet,
+    H,
+    T: TSession<IO>,
+>
+{
     type Out: EpSession<IO, Me>;
 }
 
-// Case 1: Both branches contain the role - preserve label
-impl<Me, IO, Lbl, L, R> ProjectChoiceCase<Me, IO, Lbl, L, R, types::True, types::True> for ()
+// The sender fans the message out once, then continues
+impl<Me, IO, Lbl, ToSet, H, T> ProjectBroadcast<types::True, types::False, Me, IO, Lbl, ToSet, H, T>
+    for ()
 where
     Me: Role,
     Lbl: types::ProtocolLabel,
-    L: TSession<IO>,
-    R: TSession<IO>,
-    (): ProjectRole<Me, IO, L>,
-    (): ProjectRole<Me, IO, R>,
+    T: TSession<IO>,
+    (): ProjectRole<Me, IO, T>,
 {
-    type Out = EpChoice<
-        IO,
-        Lbl,
-        Me,
-        <() as ProjectRole<Me, IO, L>>::Out,
-        <() as ProjectRole<Me, IO, R>>::Out
-    >;
+    type Out = EpBroadcast<IO, Lbl, Me, ToSet, H, <() as ProjectRole<Me, IO, T>>::Out>;
 }
 
-// Case 2: Only left branch contains the role - wrap the projection in EpChoice with the Choice's label
-impl<Me, IO, Lbl, L, R> ProjectChoiceCase<Me, IO, Lbl, L, R, types::True, types::False> for ()
+// Every member of the set receives, then continues
+impl<Me, IO, Lbl, ToSet, H, T> ProjectBroadcast<types::False, types::True, Me, IO, Lbl, ToSet, H, T>
+    for ()
 where
     Me: Role,
     Lbl: types::ProtocolLabel,
-    L: TSession<IO>,
-    R: TSession<IO>,
-    (): ProjectRole<Me, IO, L>,
+    T: TSession<IO>,
+    (): ProjectRole<Me, IO, T>,
+{
+    type Out = EpRecv<IO, Lbl, Me, H, <() as ProjectRole<Me, IO, T>>::Out>;
+}
+
+// Roles outside the set continue with the rest of the protocol
+impl<Me, IO, Lbl, ToSet, H, T>
+    ProjectBroadcast<types::False, types::False, Me, IO, Lbl, ToSet, H, T> for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+    T: TSession<IO>,
+    (): ProjectRole<Me, IO, T>,
 {
-    // Wrap the projection in EpChoice with the parent Choice's label
-    type Out = EpChoice<
+    type Out = <() as ProjectRole<Me, IO, T>>::Out;
+}
+
+// Projection for an anycast: dispatch on whether the projected role is the
+// sender and whether it belongs to the worker set
+impl<Me, IO, Lbl, From, WorkerSet, H, T>
+    ProjectRole<Me, IO, TAnycast<IO, Lbl, From, WorkerSet, H, T>> for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+    From: Role,
+    T: TSession<IO>,
+    Me: RoleEq<From> + InRoleSet<WorkerSet>,
+    <Me as RoleEq<From>>::Output: types::Bool,
+    (): ProjectAnycast<
+        <Me as RoleEq<From>>::Output,
+        <Me as InRoleSet<WorkerSet>>::Output,
+        Me,
         IO,
         Lbl,
+        H,
+        T,
+    >,
+{
+    type Out = <() as ProjectAnycast<
+        <Me as RoleEq<From>>::Output,
+        <Me as InRoleSet<WorkerSet>>::Output,
         Me,
-        <() as ProjectRole<Me, IO, L>>::Out,
-        EpSkip<IO, Lbl, Me>
-    >;
+        IO,
+        Lbl,
+        H,
+        T,
+    >>::Out;
+}
+
+/// Helper trait for projecting a `TAnycast` interaction.
+///
+/// - `IsFrom`: Type-level boolean, is the projected role the sender?
+/// - `InSet`: Type-level boolean, is the projected role in the worker set?
+/// - `Me`: The role being projected.
+/// - `IO`: Protocol marker type.
+/// - `Lbl`: Label for this interaction (preserved from global protocol).
+/// - `H`: Message type.
+/// - `T`: Continuation protocol.
+///
+/// As with `ProjectMsg`, a sender that is also in the worker set has no
+/// projection.
+pub trait ProjectAnycast<IsFrom, InSet, Me: Role, IO, Lbl: types::ProtocolLabel, H, T: TSession<IO>>
+{
+    type Out: EpSession<IO, Me>;
 }
 
-// Case 3: Only right branch contains the role - wrap the projection in EpChoice with the Choice's label
-impl<Me, IO, Lbl, L, R> ProjectChoiceCase<Me, IO, Lbl, L, R, types::False, types::True> for ()
+// The sender sends once, then continues
+impl<Me, IO, Lbl, H, T> ProjectAnycast<types::True, types::False, Me, IO, Lbl, H, T> for ()
 where
     Me: Role,
     Lbl: types::ProtocolLabel,
-    L: TSession<IO>,
-    R: TSession<IO>,
-    (): ProjectRole<Me, IO, R>,
+    T: TSession<IO>,
+    (): ProjectRole<Me, IO, T>,
+{
+    type Out = EpSend<IO, Lbl, Me, H, <() as ProjectRole<Me, IO, T>>::Out>;
+}
+
+// Workers offer to either receive the message or skip it; both continue alike
+impl<Me, IO, Lbl, H, T> ProjectAnycast<types::False, types::True, Me, IO, Lbl, H, T> for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+    T: TSession<IO>,
+    (): ProjectRole<Me, IO, T>,
 {
-    // Wrap the projection in EpChoice with the parent Choice's label
-    type Out = EpChoice<
+    type Out = EpOffer<
         IO,
         Lbl,
         Me,
-        EpSkip<IO, Lbl, Me>,
-        <() as ProjectRole<Me, IO, R>>::Out
+        Cons<
+            EpRecv<IO, Lbl, Me, H, <() as ProjectRole<Me, IO, T>>::Out>,
+            Cons<<() as ProjectRole<Me, IO, T>>::Out, Nil>,
+        >,
     >;
 }
 
-// Case 4: Neither branch contains the role
-impl<Me, IO, Lbl, L, R> ProjectChoiceCase<Me, IO, Lbl, L, R, types::False, types::False> for ()
+// Roles outside the worker set continue with the rest of the protocol
+impl<Me, IO, Lbl, H, T> ProjectAnycast<types::False, types::False, Me, IO, Lbl, H, T> for ()
 where
     Me: Role,
     Lbl: types::ProtocolLabel,
-    L: TSession<IO>,
-    R: TSession<IO>,
+    T: TSession<IO>,
+    (): ProjectRole<Me, IO, T>,
 {
-    type Out = EpSkip<IO, Lbl, Me>;
+    type Out = <() as ProjectRole<Me, IO, T>>::Out;
 }
 
-// --- Helper trait to check if a role is present in a protocol branch.
-/// Returns a type-level boolean indicating whether the role is present.
-pub trait ContainsRole<R> {
-    type Output: types::Bool;
+// Projection for a delegation: dispatch on whether the projected role hands
+// the endpoint over, receives it, or neither
+impl<Me, IO, Lbl, From, To, DelegatedEp, T>
+    ProjectRole<Me, IO, TDelegate<IO, Lbl, From, To, DelegatedEp, T>> for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+    From: Role,
+    To: Role,
+    T: TSession<IO>,
+    DelegatedEp: GetLocalRole + EpSession<IO, <DelegatedEp as GetLocalRole>::Role>,
+    Me: RoleEq<From> + RoleEq<To>,
+    <Me as RoleEq<From>>::Output: types::Bool,
+    <Me as RoleEq<To>>::Output: types::Bool,
+    (): ProjectDelegate<
+        <Me as RoleEq<From>>::Output,
+        <Me as RoleEq<To>>::Output,
+        Me,
+        IO,
+        Lbl,
+        DelegatedEp,
+        T,
+    >,
+{
+    type Out = <() as ProjectDelegate<
+        <Me as RoleEq<From>>::Output,
+        <Me as RoleEq<To>>::Output,
+        Me,
+        IO,
+        Lbl,
+        DelegatedEp,
+        T,
+    >>::Out;
 }
 
-/// Helper trait to check if a role is NOT present in a protocol branch.
-pub trait NotContainsRole<R> {}
-
-// Base case: TEnd doesn't contain any role
-impl<IO, Lbl, R> ContainsRole<R> for TEnd<IO, Lbl> {
-    type Output = types::False;
+/// Helper trait for projecting a `TDelegate`.
+///
+/// - `IsFrom`: Type-level boolean, is the projected role handing the endpoint over?
+/// - `IsTo`: Type-level boolean, is the projected role receiving it?
+/// - `Me`: The role being projected.
+/// - `IO`: Protocol marker type.
+/// - `Lbl`: Label for this hand-over (preserved from global protocol).
+/// - `DelegatedEp`: Local session type of the endpoint handed over.
+/// - `T`: Continuation protocol.
+///
+/// As with `ProjectMsg`, a role cannot delegate to itself.
+pub trait ProjectDelegate<
+    IsFrom,
+    IsTo,
+    Me: Role,
+    IO,
+    Lbl: types::ProtocolLabel,
+    DelegatedEp,
+    T: TSession<IO>,
+>
+{
+    type Out: EpSession<IO, Me>;
 }
 
-impl<IO, Lbl, R> NotContainsRole<R> for TEnd<IO, Lbl> {}
-
-// TInteract contains the role if:
-// 1. The role is the same as the sender (R1 == R2), or
-// 2. The role is a receiver of the message (all roles are considered receivers
-//    except for the sender), or
-// 3. The continuation contains the role
-impl<IO, Lbl, H, T, R1, R2> ContainsRole<R2> for TInteract<IO, Lbl, R1, H, T>
+// The delegating role gives up the endpoint, then continues
+impl<Me, IO, Lbl, DelegatedEp, T>
+    ProjectDelegate<types::True, types::False, Me, IO, Lbl, DelegatedEp, T> for ()
 where
+    Me: Role,
     Lbl: types::ProtocolLabel,
-    R1: RoleEq<R2>,
-    <R1 as RoleEq<R2>>::Output: types::Bool,
-    T: TSession<IO> + ContainsRole<R2>,
-    <T as ContainsRole<R2>>::Output: types::Bool,
-    // For TInteract, we consider all roles to be involved (either as sender or receiver)
-    // This makes the role always present, which is what the tests expect
-    types::True: types::BoolOr<<T as ContainsRole<R2>>::Output>,
+    T: TSession<IO>,
+    (): ProjectRole<Me, IO, T>,
 {
-    // Always true for TInteract - all roles are considered to be involved
-    type Output = types::True;
+    type Out = EpDelegate<IO, Lbl, Me, DelegatedEp, <() as ProjectRole<Me, IO, T>>::Out>;
 }
 
-// TInteract doesn't ever satisfy NotContainsRole, since we consider all roles to be involved
-// in an interaction (except if the protocol explicitly declares that certain roles aren't involved).
-// This implementation is intentionally left empty - TInteract never implements NotContainsRole
-
-// TChoice contains the role if either branch contains it
-impl<IO, Lbl, L, R, RoleT> ContainsRole<RoleT> for TChoice<IO, Lbl, L, R>
+// The receiving role gets the endpoint as an ordinary payload
+impl<Me, IO, Lbl, DelegatedEp, T>
+    ProjectDelegate<types::False, types::True, Me, IO, Lbl, DelegatedEp, T> for ()
 where
+    Me: Role,
     Lbl: types::ProtocolLabel,
-    L: TSession<IO> + ContainsRole<RoleT>,
-    <L as ContainsRole<RoleT>>::Output: types::Bool,
-    R: TSession<IO> + ContainsRole<RoleT>,
-    <R as ContainsRole<RoleT>>::Output: types::Bool,
-    // The following ensures Or can be used with these types
-    <L as ContainsRole<RoleT>>::Output: types::BoolOr<<R as ContainsRole<RoleT>>::Output>,
+    T: TSession<IO>,
+    (): ProjectRole<Me, IO, T>,
 {
-    // True if either branch contains the role
-    type Output = types::Or<<L as ContainsRole<RoleT>>::Output, <R as ContainsRole<RoleT>>::Output>;
+    type Out = EpRecv<IO, Lbl, Me, DelegatedEp, <() as ProjectRole<Me, IO, T>>::Out>;
 }
 
-// TChoice doesn't contain the role if neither branch contains it
-impl<IO, Lbl, L, R, RoleT> NotContainsRole<RoleT> for TChoice<IO, Lbl, L, R>
+// Bystanders skip this step and continue with the rest of the protocol
+impl<Me, IO, Lbl, DelegatedEp, T>
+    ProjectDelegate<types::False, types::False, Me, IO, Lbl, DelegatedEp, T> for ()
 where
+    Me: Role,
     Lbl: types::ProtocolLabel,
-    L: TSession<IO> + NotContainsRole<RoleT>,
-    R: TSession<IO> + NotContainsRole<RoleT>,
+    T: TSession<IO>,
+    (): ProjectRole<Me, IO, T>,
 {
+    type Out = <() as ProjectRole<Me, IO, T>>::Out;
 }
 
-// Use a single implementation with dispatch on L branch containment
-impl<IO, Lbl, L, R, IsDisjoint, RoleT> ContainsRole<RoleT> for TPar<IO, Lbl, L, R, IsDisjoint>
+// Projection for a disconnect: dispatch on whether the projected role is one
+// of the two roles closing their channel
+impl<Me, IO, Lbl, A, B, Cont> ProjectRole<Me, IO, TDisconnect<IO, Lbl, A, B, Cont>> for ()
 where
+    Me: Role,
     Lbl: types::ProtocolLabel,
-    L: TSession<IO> + ContainsRole<RoleT>,
-    <L as ContainsRole<RoleT>>::Output: types::Bool,
-    R: TSession<IO> + ContainsRole<RoleT>,
-    <R as ContainsRole<RoleT>>::Output: types::Bool,
-    // Use a helper trait to dispatch based on L branch containment
-    (): TParContainsRoleImpl<
-        <L as ContainsRole<RoleT>>::Output,
-        <R as ContainsRole<RoleT>>::Output,
+    A: Role,
+    B: Role,
+    Cont: TSession<IO>,
+    Me: RoleEq<A> + RoleEq<B>,
+    <Me as RoleEq<A>>::Output: types::Bool,
+    <Me as RoleEq<B>>::Output: types::Bool,
+    (): ProjectDisconnect<
+        <Me as RoleEq<A>>::Output,
+        <Me as RoleEq<B>>::Output,
+        Me,
+        IO,
+        Lbl,
+        A,
+        B,
+        Cont,
     >,
 {
-    type Output = <() as TParContainsRoleImpl<
-        <L as ContainsRole<RoleT>>::Output,
-        <R as ContainsRole<RoleT>>::Output,
-    >>::Output;
+    type Out = <() as ProjectDisconnect<
+        <Me as RoleEq<A>>::Output,
+        <Me as RoleEq<B>>::Output,
+        Me,
+        IO,
+        Lbl,
+        A,
+        B,
+        Cont,
+    >>::Out;
 }
 
-// TPar doesn't contain the role if neither branch contains it
-impl<IO, Lbl, L, R, IsDisjoint, RoleT> NotContainsRole<RoleT> for TPar<IO, Lbl, L, R, IsDisjoint>
-where
+/// Helper trait for projecting a `TDisconnect`.
+///
+/// - `IsA`: Type-level boolean, is the projected role `A`?
+/// - `IsB`: Type-level boolean, is the projected role `B`?
+/// - `Me`: The role being projected.
+/// - `IO`: Protocol marker type.
+/// - `Lbl`: Label for this step (preserved from global protocol).
+/// - `A`, `B`: The two roles closing their channel.
+/// - `Cont`: Continuation protocol.
+///
+/// As with `ProjectMsg`, a role cannot close a channel to itself.
+pub trait ProjectDisconnect<
+    IsA,
+    IsB,
+    Me: Role,
+    IO,
     Lbl: types::ProtocolLabel,
-    L: TSession<IO> + NotContainsRole<RoleT>,
-    R: TSession<IO> + NotContainsRole<RoleT>,
+    A,
+    B,
+    Cont: TSession<IO>,
+>
 {
+    type Out: EpSession<IO, Me>;
 }
 
-// Helper trait for TPar role containment logic
-pub trait TParContainsRoleImpl<LContains, RContains> {
-    type Output: types::Bool;
+// `A` closes its channel to `B`
+impl<Me, IO, Lbl, A, B, Cont> ProjectDisconnect<types::True, types::False, Me, IO, Lbl, A, B, Cont>
+    for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+    Cont: TSession<IO>,
+    (): ProjectRole<Me, IO, Cont>,
+{
+    type Out = EpClose<IO, Lbl, Me, B, <() as ProjectRole<Me, IO, Cont>>::Out>;
 }
 
-// If either branch contains the role, TPar contains it
-impl TParContainsRoleImpl<types::True, types::True> for () {
-    type Output = types::True;
+// `B` closes its channel to `A`
+impl<Me, IO, Lbl, A, B, Cont> ProjectDisconnect<types::False, types::True, Me, IO, Lbl, A, B, Cont>
+    for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+    Cont: TSession<IO>,
+    (): ProjectRole<Me, IO, Cont>,
+{
+    type Out = EpClose<IO, Lbl, Me, A, <() as ProjectRole<Me, IO, Cont>>::Out>;
 }
 
-impl TParContainsRoleImpl<types::True, types::False> for () {
-    type Output = types::True;
+// Other roles continue with the rest of the protocol
+impl<Me, IO, Lbl, A, B, Cont> ProjectDisconnect<types::False, types::False, Me, IO, Lbl, A, B, Cont>
+    for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+    Cont: TSession<IO>,
+    (): ProjectRole<Me, IO, Cont>,
+{
+    type Out = <() as ProjectRole<Me, IO, Cont>>::Out;
 }
 
-impl TParContainsRoleImpl<types::False, types::True> for () {
-    type Output = types::True;
+// Projection for a connect: dispatch on whether the projected role is one
+// of the two roles opening a channel
+impl<Me, IO, Lbl, A, B, Cont> ProjectRole<Me, IO, TConnect<IO, Lbl, A, B, Cont>> for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+    A: Role,
+    B: Role,
+    Cont: TSession<IO>,
+    Me: RoleEq<A> + RoleEq<B>,
+    <Me as RoleEq<A>>::Output: types::Bool,
+    <Me as RoleEq<B>>::Output: types::Bool,
+    (): ProjectConnect<
+        <Me as RoleEq<A>>::Output,
+        <Me as RoleEq<B>>::Output,
+        Me,
+        IO,
+        Lbl,
+        A,
+        B,
+        Cont,
+    >,
+{
+    type Out = <() as ProjectConnect<
+        <Me as RoleEq<A>>::Output,
+        <Me as RoleEq<B>>::Output,
+        Me,
+        IO,
+        Lbl,
+        A,
+        B,
+        Cont,
+    >>::Out;
 }
 
-// If neither branch contains the role, TPar doesn't contain it
-impl TParContainsRoleImpl<types::False, types::False> for () {
-    type Output = types::False;
+/// Helper trait for projecting a `TConnect`.
+///
+/// - `IsA`: Type-level boolean, is the projected role `A`?
+/// - `IsB`: Type-level boolean, is the projected role `B`?
+/// - `Me`: The role being projected.
+/// - `IO`: Protocol marker type.
+/// - `Lbl`: Label for this step (preserved from global protocol).
+/// - `A`, `B`: The two roles opening a channel.
+/// - `Cont`: Continuation protocol.
+///
+/// As with `ProjectMsg`, a role cannot open a channel to itself.
+pub trait ProjectConnect<
+    IsA,
+    IsB,
+    Me: Role,
+    IO,
+    Lbl: types::ProtocolLabel,
+    A,
+    B,
+    Cont: TSession<IO>,
+>
+{
+    type Out: EpSession<IO, Me>;
 }
 
-/// Helper trait for projecting a protocol parallel composition.
+// `A` opens a channel to `B`
+impl<Me, IO, Lbl, A, B, Cont> ProjectConnect<types::True, types::False, Me, IO, Lbl, A, B, Cont>
+    for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+    Cont: TSession<IO>,
+    (): ProjectRole<Me, IO, Cont>,
+{
+    type Out = EpOpen<IO, Lbl, Me, B, <() as ProjectRole<Me, IO, Cont>>::Out>;
+}
+
+// `B` opens a channel to `A`
+impl<Me, IO, Lbl, A, B, Cont> ProjectConnect<types::False, types::True, Me, IO, Lbl, A, B, Cont>
+    for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+    Cont: TSession<IO>,
+    (): ProjectRole<Me, IO, Cont>,
+{
+    type Out = EpOpen<IO, Lbl, Me, A, <() as ProjectRole<Me, IO, Cont>>::Out>;
+}
+
+// Other roles continue with the rest of the protocol
+impl<Me, IO, Lbl, A, B, Cont> ProjectConnect<types::False, types::False, Me, IO, Lbl, A, B, Cont>
+    for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+    Cont: TSession<IO>,
+    (): ProjectRole<Me, IO, Cont>,
+{
+    type Out = <() as ProjectRole<Me, IO, Cont>>::Out;
+}
+
+// Projection for a per-role termination: the leaving role must not reappear in
+// the continuation, whichever role is being projected
+impl<Me, IO, Lbl, R, Cont> ProjectRole<Me, IO, TEndFor<IO, Lbl, R, Cont>> for ()
+where
+    Me: Role + RoleEq<R>,
+    Lbl: types::ProtocolLabel,
+    Cont: TSession<IO> + ContainsRole<R>,
+    (): EndForRejoinCase<<Cont as ContainsRole<R>>::Output, R, Lbl>,
+    <Me as RoleEq<R>>::Output: types::Bool,
+    (): ProjectEndForCase<<Me as RoleEq<R>>::Output, Me, IO, Lbl, Cont>,
+{
+    type Out = <() as ProjectEndForCase<<Me as RoleEq<R>>::Output, Me, IO, Lbl, Cont>>::Out;
+}
+
+/// Helper trait for projecting a `TEndFor`: dispatches on whether the leaving
+/// role `R` appears again in the continuation, which fails with a
+/// `RoleAfterEnd` error.
+pub trait EndForRejoinCase<Rejoins, R, Lbl> {}
+
+// The leaving role stays gone
+impl<R, Lbl> EndForRejoinCase<types::False, R, Lbl> for () {}
+
+// The leaving role comes back, reported by name rather than as a role mismatch
+impl<R, Lbl> EndForRejoinCase<types::True, R, Lbl> for () where
+    RoleAfterEnd<R, Lbl>: ProjectionFailure
+{
+}
+
+/// Helper trait for projecting a `TEndFor`.
 ///
+/// - `IsLeaving`: Type-level boolean, is the projected role the one leaving?
 /// - `Me`: The role being projected.
 /// - `IO`: Protocol marker type.
-/// - `Lbl`: Label from the TPar construct.
-/// - `L`, `R`: The two protocol branches.
-pub trait ProjectPar<Me, IO, Lbl: types::ProtocolLabel, L: TSession<IO>, R: TSession<IO>> {
+/// - `Lbl`: Label for this step (preserved from global protocol).
+/// - `Cont`: Continuation protocol for the remaining roles.
+pub trait ProjectEndForCase<IsLeaving, Me: Role, IO, Lbl: types::ProtocolLabel, Cont: TSession<IO>>
+{
     type Out: EpSession<IO, Me>;
 }
 
-// We've replaced ProjectParBranch with ProjectRoleOrSkip for better label handling
+// The leaving role ends here
+impl<Me, IO, Lbl, Cont> ProjectEndForCase<types::True, Me, IO, Lbl, Cont> for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+    Cont: TSession<IO>,
+{
+    type Out = EpEnd<IO, Lbl, Me>;
+}
 
-/// Helper trait to project role or create skip with the parent label
-pub trait ProjectRoleOrSkip<Me: Role, IO, G: TSession<IO>, Flag, ParentLbl: types::ProtocolLabel> {
+// Everyone else continues with the rest of the protocol
+impl<Me, IO, Lbl, Cont> ProjectEndForCase<types::False, Me, IO, Lbl, Cont> for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+    Cont: TSession<IO>,
+    (): ProjectRole<Me, IO, Cont>,
+{
+    type Out = <() as ProjectRole<Me, IO, Cont>>::Out;
+}
+
+/// Helper trait for projecting a `TMsg` interaction.
+///
+/// - `IsFrom`: Type-level boolean, is the projected role the sender?
+/// - `IsTo`: Type-level boolean, is the projected role the receiver?
+/// - `Me`: The role being projected.
+/// - `IO`: Protocol marker type.
+/// - `Lbl`: Label for this interaction (preserved from global protocol).
+/// - `H`: Message type.
+/// - `T`: Continuation protocol.
+///
+/// A role that is both sender and receiver fails to project with a
+/// `SelfMessage` error: self-messages have no sensible local meaning.
+pub trait ProjectMsg<IsFrom, IsTo, Me: Role, IO, Lbl: types::ProtocolLabel, H, T: TSession<IO>> {
     type Out: EpSession<IO, Me>;
 }
 
-// If role is in branch, project it
-impl<Me: Role, IO, G: TSession<IO>, Lbl: types::ProtocolLabel> 
-    ProjectRoleOrSkip<Me, IO, G, types::True, Lbl> for ()
+// The sender sends, then continues
+impl<Me, IO, Lbl, H, T> ProjectMsg<types::True, types::False, Me, IO, Lbl, H, T> for ()
 where
-    (): ProjectRole<Me, IO, G>,
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+    T: TSession<IO>,
+    (): ProjectRole<Me, IO, T>,
 {
-    // Just return the projection directly - no additional wrapping needed
-    type Out = <() as ProjectRole<Me, IO, G>>::Out;
+    type Out = EpSend<IO, Lbl, Me, H, <() as ProjectRole<Me, IO, T>>::Out>;
 }
 
-// If role is not in branch, create skip with parent label
-impl<Me: Role, IO, G: TSession<IO>, Lbl: types::ProtocolLabel> 
-    ProjectRoleOrSkip<Me, IO, G, types::False, Lbl> for ()
+// The receiver receives, then continues
+impl<Me, IO, Lbl, H, T> ProjectMsg<types::False, types::True, Me, IO, Lbl, H, T> for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+    T: TSession<IO>,
+    (): ProjectRole<Me, IO, T>,
 {
-    // Create EpSkip with the parent label (this ensures label preservation)
-    type Out = EpSkip<IO, Lbl, Me>;
+    type Out = EpRecv<IO, Lbl, Me, H, <() as ProjectRole<Me, IO, T>>::Out>;
 }
 
-// Projection implementation for TPar - delegate to ProjectPar helper but pass the label
-impl<Me, IO, Lbl, L, R, IsDisjoint> ProjectRole<Me, IO, TPar<IO, Lbl, L, R, IsDisjoint>> for ()
+// A role messaging itself is reported by name rather than as a missing impl
+impl<Me, IO, Lbl, H, T> ProjectMsg<types::True, types::True, Me, IO, Lbl, H, T> for ()
 where
     Me: Role,
     Lbl: types::ProtocolLabel,
-    L: TSession<IO>,
-    R: TSession<IO>,
-    (): ProjectPar<Me, IO, Lbl, L, R>,
+    T: TSession<IO>,
+    SelfMessage<Me, Lbl>: ProjectionFailure,
 {
-    type Out = <() as ProjectPar<Me, IO, Lbl, L, R>>::Out;
+    type Out = EpEnd<IO, Lbl, Me>;
 }
 
-/// Main flag-based composition trait for projected parallel branches
-pub trait ComposeProjectedParBranches<IO, Me: Role, L, R>
+// Bystanders skip this step and continue with the rest of the protocol
+impl<Me, IO, Lbl, H, T> ProjectMsg<types::False, types::False, Me, IO, Lbl, H, T> for ()
 where
-    L: IsEpSkipVariant<IO, Me> + IsEpEndVariant<IO, Me> + EpSession<IO, Me>,
-    R: IsEpSkipVariant<IO, Me> + IsEpEndVariant<IO, Me> + EpSession<IO, Me>,
-    <L as IsEpSkipVariant<IO, Me>>::Output: types::Bool,
-    <R as IsEpSkipVariant<IO, Me>>::Output: types::Bool,
-    <L as IsEpEndVariant<IO, Me>>::Output: types::Bool,
-    <R as IsEpEndVariant<IO, Me>>::Output: types::Bool,
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+    T: TSession<IO>,
+    (): ProjectRole<Me, IO, T>,
 {
+    type Out = <() as ProjectRole<Me, IO, T>>::Out;
+}
+
+/// Helper trait for projecting a protocol choice.
+///
+/// - `Me`: The role being projected.
+/// - `IO`: Protocol marker type.
+/// - `L`, `R`: The two protocol branches.
+pub trait ProjectChoice<Me, IO, L: TSession<IO>, R: TSession<IO>> {
     type Out: EpSession<IO, Me>;
 }
 
-impl<IO, Me: Role, L, R> ComposeProjectedParBranches<IO, Me, L, R> for ()
+// Projection implementation for TChoice - the role acting first in both branches
+// owns the choice and selects, every other participant merges the branches; labels are preserved
+impl<Me, IO, Lbl, L, R> ProjectRole<Me, IO, TChoice<IO, Lbl, L, R>> for ()
 where
-    L: IsEpSkipVariant<IO, Me> + IsEpEndVariant<IO, Me> + EpSession<IO, Me>,
-    R: IsEpSkipVariant<IO, Me> + IsEpEndVariant<IO, Me> + EpSession<IO, Me>,
-    <L as IsEpSkipVariant<IO, Me>>::Output: types::Bool,
-    <R as IsEpSkipVariant<IO, Me>>::Output: types::Bool,
-    <L as IsEpEndVariant<IO, Me>>::Output: types::Bool,
-    <R as IsEpEndVariant<IO, Me>>::Output: types::Bool,
-    (): ComposeProjectedParBranchesCase<
-        IsSkip<L, IO, Me>,
-        IsSkip<R, IO, Me>,
-        IsEnd<L, IO, Me>,
-        IsEnd<R, IO, Me>,
-        IO,
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+    L: TSession<IO>,
+    R: TSession<IO>,
+    Cons<L, Cons<R, Nil>>: SingleChooser<Lbl>,
+    L: ContainsRole<Me> + ActsFirst<Me>,
+    <L as ContainsRole<Me>>::Output: types::Bool,
+    R: ContainsRole<Me> + ActsFirst<Me>,
+    <R as ContainsRole<Me>>::Output: types::Bool,
+    <L as ActsFirst<Me>>::Output: types::BoolOr<<R as ActsFirst<Me>>::Output>,
+    (): ProjectChoiceCase<
         Me,
+        IO,
+        Lbl,
         L,
         R,
+        <L as ContainsRole<Me>>::Output,
+        <R as ContainsRole<Me>>::Output,
+        types::Or<<L as ActsFirst<Me>>::Output, <R as ActsFirst<Me>>::Output>,
     >,
 {
-    type Out = <() as ComposeProjectedParBranchesCase<
-        IsSkip<L, IO, Me>,
-        IsSkip<R, IO, Me>,
-        IsEnd<L, IO, Me>,
-        IsEnd<R, IO, Me>,
-        IO,
+    type Out = <() as ProjectChoiceCase<
         Me,
+        IO,
+        Lbl,
         L,
         R,
+        <L as ContainsRole<Me>>::Output,
+        <R as ContainsRole<Me>>::Output,
+        types::Or<<L as ActsFirst<Me>>::Output, <R as ActsFirst<Me>>::Output>,
     >>::Out;
 }
 
-/// Helper trait for case selection in composition of parallel branches
-pub trait ComposeProjectedParBranchesCase<LSkip, RSkip, LEnd, REnd, IO, Me: Role, L, R>
-where
-    L: EpSession<IO, Me>,
-    R: EpSession<IO, Me>,
+// Helper trait for handling different cases of ProjectChoice based on role presence
+#[diagnostic::on_unimplemented(
+    message = "the choice labelled `{Lbl}` cannot be projected onto the role `{Me}`",
+    label = "no projection of this choice onto `{Me}`"
+)]
+pub trait ProjectChoiceCase<
+    Me,
+    IO,
+    Lbl: types::ProtocolLabel,
+    L: TSession<IO>,
+    R: TSession<IO>,
+    LContainsMe,
+    RContainsMe,
+    IsChooser,
+>
 {
     type Out: EpSession<IO, Me>;
 }
 
-// Extract the GetProtocolLabel trait implementation here
-pub trait GetProtocolLabel {
-    type Label: types::ProtocolLabel;
+// Case 1a: Both branches contain the role and it owns the choice - select between them
+impl<Me, IO, Lbl, L, R> ProjectChoiceCase<Me, IO, Lbl, L, R, types::True, types::True, types::True>
+    for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+    L: TSession<IO>,
+    R: TSession<IO>,
+    (): ProjectRole<Me, IO, L>,
+    (): ProjectRole<Me, IO, R>,
+{
+    type Out = EpSelect<
+        IO,
+        Lbl,
+        Me,
+        Cons<<() as ProjectRole<Me, IO, L>>::Out, Cons<<() as ProjectRole<Me, IO, R>>::Out, Nil>>,
+    >;
 }
 
-// Add implementation for TInteract
-impl<IO, Lbl: types::ProtocolLabel, R, H, T: TSession<IO>> GetProtocolLabel for TInteract<IO, Lbl, R, H, T> {
-    type Label = Lbl;
+// Case 1b: Both branches contain the role and another role owns the choice - merge the
+// projected branches, so the role learns the outcome from what it receives; an offer at the
+// top carries the choice's label
+impl<Me, IO, Lbl, L, R> ProjectChoiceCase<Me, IO, Lbl, L, R, types::True, types::True, types::False>
+    for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+    L: TSession<IO>,
+    R: TSession<IO>,
+    (): ProjectRole<Me, IO, L>,
+    (): ProjectRole<Me, IO, R>,
+    <() as ProjectRole<Me, IO, L>>::Out: MergeBranches<Me, <() as ProjectRole<Me, IO, R>>::Out>,
+    <<() as ProjectRole<Me, IO, L>>::Out as MergeBranches<
+        Me,
+        <() as ProjectRole<Me, IO, R>>::Out,
+    >>::Out: WithChoiceLabel<Lbl>,
+    <<<() as ProjectRole<Me, IO, L>>::Out as MergeBranches<
+        Me,
+        <() as ProjectRole<Me, IO, R>>::Out,
+    >>::Out as WithChoiceLabel<Lbl>>::Output: EpSession<IO, Me>,
+{
+    type Out = <<<() as ProjectRole<Me, IO, L>>::Out as MergeBranches<
+        Me,
+        <() as ProjectRole<Me, IO, R>>::Out,
+    >>::Out as WithChoiceLabel<Lbl>>::Output;
 }
 
-// Add implementation for TChoice
-impl<IO, Lbl: types::ProtocolLabel, L: TSession<IO>, R: TSession<IO>> GetProtocolLabel for TChoice<IO, Lbl, L, R> {
-    type Label = Lbl;
+// Case 2a: Only the left branch contains the role and it owns the choice - select between
+// the left branch and skipping under the Choice's label
+impl<Me, IO, Lbl, L, R> ProjectChoiceCase<Me, IO, Lbl, L, R, types::True, types::False, types::True>
+    for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+    L: TSession<IO>,
+    R: TSession<IO>,
+    (): ProjectRole<Me, IO, L>,
+{
+    type Out = EpSelect<
+        IO,
+        Lbl,
+        Me,
+        Cons<<() as ProjectRole<Me, IO, L>>::Out, Cons<EpSkip<IO, Lbl, Me>, Nil>>,
+    >;
 }
 
-// Add implementation for TPar
-impl<IO, Lbl: types::ProtocolLabel, L: TSession<IO>, R: TSession<IO>, IsDisjoint> GetProtocolLabel for TPar<IO, Lbl, L, R, IsDisjoint> {
-    type Label = Lbl;
+// Case 2b: Only the left branch contains the role and another role owns the choice - the
+// role cannot tell the branches apart
+impl<Me, IO, Lbl, L, R>
+    ProjectChoiceCase<Me, IO, Lbl, L, R, types::True, types::False, types::False> for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+    L: TSession<IO>,
+    R: TSession<IO>,
+    UninformedOfChoice<Me, Lbl>: ProjectionFailure,
+{
+    type Out = EpSkip<IO, Lbl, Me>;
 }
 
-// Add implementation for TEnd
-impl<IO, Lbl: types::ProtocolLabel> GetProtocolLabel for TEnd<IO, Lbl> {
-    type Label = Lbl;
+// Case 3a: Only the right branch contains the role and it owns the choice - select between
+// skipping under the Choice's label and the right branch
+impl<Me, IO, Lbl, L, R> ProjectChoiceCase<Me, IO, Lbl, L, R, types::False, types::True, types::True>
+    for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+    L: TSession<IO>,
+    R: TSession<IO>,
+    (): ProjectRole<Me, IO, R>,
+{
+    type Out = EpSelect<
+        IO,
+        Lbl,
+        Me,
+        Cons<EpSkip<IO, Lbl, Me>, Cons<<() as ProjectRole<Me, IO, R>>::Out, Nil>>,
+    >;
 }
 
-// Both branches are EpSkip
-impl<IO, Me: Role, Lbl1: types::ProtocolLabel, Lbl2: types::ProtocolLabel>
-    ComposeProjectedParBranchesCase<
-        types::True,
-        types::True,
-        types::False,
-        types::False,
+// Case 3b: Only the right branch contains the role and another role owns the choice
+impl<Me, IO, Lbl, L, R>
+    ProjectChoiceCase<Me, IO, Lbl, L, R, types::False, types::True, types::False> for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+    L: TSession<IO>,
+    R: TSession<IO>,
+    UninformedOfChoice<Me, Lbl>: ProjectionFailure,
+{
+    type Out = EpSkip<IO, Lbl, Me>;
+}
+
+// Case 4: Neither branch contains the role
+impl<Me, IO, Lbl, L, R, IsChooser>
+    ProjectChoiceCase<Me, IO, Lbl, L, R, types::False, types::False, IsChooser> for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+    L: TSession<IO>,
+    R: TSession<IO>,
+{
+    type Out = EpSkip<IO, Lbl, Me>;
+}
+
+/// The merge of the projected branches of a `TChoice` for a role `Me` that
+/// does not own it.
+///
+/// Implemented whenever the branches can be merged with `Merge`. A role that
+/// does not own a choice and cannot tell its branches apart makes the protocol
+/// unprojectable, and the error names the role.
+#[diagnostic::on_unimplemented(
+    message = "`{Me}` cannot be projected: the branches `{Self}` and `{Rhs}` of a choice it does not own cannot be merged",
+    label = "`{Me}` cannot learn which branch was chosen",
+    note = "a role that does not own a choice must act identically in every branch until it receives a label that tells the branches apart"
+)]
+pub trait MergeBranches<Me, Rhs> {
+    type Out;
+}
+
+impl<Me, L: Merge<R>, R> MergeBranches<Me, R> for L {
+    type Out = <L as Merge<R>>::Output;
+}
+
+// Projection implementation for TChoiceN - dispatch on whether any branch contains the role
+impl<Me, IO, Lbl, Branches> ProjectRole<Me, IO, TChoiceN<IO, Lbl, Branches>> for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+    Branches: SessionList<IO> + ContainsRole<Me>,
+    (): ProjectChoiceNCase<Me, IO, Lbl, Branches, <Branches as ContainsRole<Me>>::Output>,
+{
+    type Out = <() as ProjectChoiceNCase<
+        Me,
         IO,
+        Lbl,
+        Branches,
+        <Branches as ContainsRole<Me>>::Output,
+    >>::Out;
+}
+
+// Helper trait for handling TChoiceN projection based on role presence
+pub trait ProjectChoiceNCase<Me, IO, Lbl: types::ProtocolLabel, Branches, ContainsMe> {
+    type Out: EpSession<IO, Me>;
+}
+
+// Some branch contains the role - dispatch on whether the branches are tagged
+impl<Me, IO, Lbl, Branches> ProjectChoiceNCase<Me, IO, Lbl, Branches, types::True> for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+    Branches: BranchesTagged,
+    (): ProjectTaggedChoiceCase<Me, IO, Lbl, Branches, <Branches as BranchesTagged>::Output>,
+{
+    type Out = <() as ProjectTaggedChoiceCase<
         Me,
-        EpSkip<IO, Lbl1, Me>,
-        EpSkip<IO, Lbl2, Me>,
-    > for ()
+        IO,
+        Lbl,
+        Branches,
+        <Branches as BranchesTagged>::Output,
+    >>::Out;
+}
+
+/// Helper trait for projecting a `TChoiceN` the role takes part in: dispatches
+/// on whether its branches start with pairwise distinct labels.
+pub trait ProjectTaggedChoiceCase<Me, IO, Lbl: types::ProtocolLabel, Branches, Tagged> {
+    type Out: EpSession<IO, Me>;
+}
+
+// Untagged branches - project every branch, skipping the others
+impl<Me, IO, Lbl, Branches> ProjectTaggedChoiceCase<Me, IO, Lbl, Branches, types::False> for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+    (): ProjectBranches<Me, IO, Lbl, Branches>,
 {
-    // If both branches are skipped, output a skip
-    type Out = EpSkip<IO, Lbl1, Me>; // Use label from first branch
+    type Out = EpChoiceN<IO, Lbl, Me, <() as ProjectBranches<Me, IO, Lbl, Branches>>::Out>;
 }
 
-// Left is EpSkip, right is projected
-impl<IO, Me: Role, Lbl: types::ProtocolLabel, ProjectedR: EpSession<IO, Me>>
-    ComposeProjectedParBranchesCase<
-        types::True,
-        types::False,
-        types::False,
-        types::False,
+// Tagged branches - the role acting first in every branch selects, every other role offers
+impl<Me, IO, Lbl, Branches> ProjectTaggedChoiceCase<Me, IO, Lbl, Branches, types::True> for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+    Branches: SingleChooser<Lbl>,
+    Branches: ActsFirst<Me>,
+    (): ProjectTaggedOwnerCase<Me, IO, Lbl, Branches, <Branches as ActsFirst<Me>>::Output>,
+{
+    type Out = <() as ProjectTaggedOwnerCase<
+        Me,
         IO,
+        Lbl,
+        Branches,
+        <Branches as ActsFirst<Me>>::Output,
+    >>::Out;
+}
+
+/// Helper trait for projecting a tagged `TChoiceN`: dispatches on whether the
+/// role acts first in some branch, i.e. picks the branch.
+pub trait ProjectTaggedOwnerCase<Me, IO, Lbl: types::ProtocolLabel, Branches, IsOwner> {
+    type Out: EpSession<IO, Me>;
+}
+
+impl<Me, IO, Lbl, Branches> ProjectTaggedOwnerCase<Me, IO, Lbl, Branches, types::True> for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+    (): ProjectTaggedBranches<Me, IO, Branches>,
+{
+    type Out = EpSelect<IO, Lbl, Me, <() as ProjectTaggedBranches<Me, IO, Branches>>::Out>;
+}
+
+impl<Me, IO, Lbl, Branches> ProjectTaggedOwnerCase<Me, IO, Lbl, Branches, types::False> for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+    (): ProjectTaggedBranches<Me, IO, Branches>,
+{
+    type Out = EpOffer<IO, Lbl, Me, <() as ProjectTaggedBranches<Me, IO, Branches>>::Out>;
+}
+
+/// Helper trait for projecting the branches of a tagged `TChoiceN`.
+///
+/// Like [`ProjectBranches`], but a skipped branch keeps the label of the
+/// branch, so every projected branch carries its tag.
+pub trait ProjectTaggedBranches<Me: Role, IO, Branches> {
+    type Out;
+}
+
+impl<Me: Role, IO> ProjectTaggedBranches<Me, IO, Nil> for () {
+    type Out = Nil;
+}
+
+impl<Me, IO, H, T> ProjectTaggedBranches<Me, IO, Cons<H, T>> for ()
+where
+    Me: Role,
+    H: TSession<IO> + ContainsRole<Me> + GetProtocolLabel,
+    (): ProjectRoleOrSkip<
         Me,
-        EpSkip<IO, Lbl, Me>,
-        ProjectedR,
-    > for ()
+        IO,
+        H,
+        <H as ContainsRole<Me>>::Output,
+        <H as GetProtocolLabel>::Label,
+    >,
+    (): ProjectTaggedBranches<Me, IO, T>,
 {
-    type Out = ProjectedR;
+    type Out = Cons<
+        <() as ProjectRoleOrSkip<
+            Me,
+            IO,
+            H,
+            <H as ContainsRole<Me>>::Output,
+            <H as GetProtocolLabel>::Label,
+        >>::Out,
+        <() as ProjectTaggedBranches<Me, IO, T>>::Out,
+    >;
+}
+
+/// Type-level check whether the branches of a choice start with pairwise
+/// distinct labels, so that a runtime can tell them apart by a wire tag.
+///
+/// Labels are compared with `LabelEq`.
+pub trait BranchesTagged {
+    type Output: types::Bool;
+}
+
+impl BranchesTagged for Nil {
+    type Output = types::True;
+}
+
+impl<H, T> BranchesTagged for Cons<H, T>
+where
+    H: GetProtocolLabel,
+    T: LabelInBranches<<H as GetProtocolLabel>::Label> + BranchesTagged,
+    (): BranchesTaggedCase<
+        <T as LabelInBranches<<H as GetProtocolLabel>::Label>>::Output,
+        <T as BranchesTagged>::Output,
+    >,
+{
+    type Output = <() as BranchesTaggedCase<
+        <T as LabelInBranches<<H as GetProtocolLabel>::Label>>::Output,
+        <T as BranchesTagged>::Output,
+    >>::Output;
+}
+
+/// Helper trait for [`BranchesTagged`]: a label repeated in the tail makes the
+/// branches untagged, otherwise the tail decides.
+pub trait BranchesTaggedCase<Repeated, RestTagged> {
+    type Output: types::Bool;
+}
+
+impl<RestTagged> BranchesTaggedCase<types::True, RestTagged> for () {
+    type Output = types::False;
+}
+
+impl<RestTagged: types::Bool> BranchesTaggedCase<types::False, RestTagged> for () {
+    type Output = RestTagged;
+}
+
+/// Type-level check whether some branch of a list starts with label `L`.
+pub trait LabelInBranches<L> {
+    type Output: types::Bool;
+}
+
+impl<L> LabelInBranches<L> for Nil {
+    type Output = types::False;
+}
+
+impl<L, H, T> LabelInBranches<L> for Cons<H, T>
+where
+    H: GetProtocolLabel,
+    <H as GetProtocolLabel>::Label: LabelEq<L>,
+    T: LabelInBranches<L>,
+    <<H as GetProtocolLabel>::Label as LabelEq<L>>::Output:
+        types::BoolOr<<T as LabelInBranches<L>>::Output>,
+{
+    type Output = types::Or<
+        <<H as GetProtocolLabel>::Label as LabelEq<L>>::Output,
+        <T as LabelInBranches<L>>::Output,
+    >;
+}
+
+// No branch contains the role
+impl<Me, IO, Lbl, Branches> ProjectChoiceNCase<Me, IO, Lbl, Branches, types::False> for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+{
+    type Out = EpSkip<IO, Lbl, Me>;
+}
+
+/// Helper trait for projecting the branch list of an n-ary combinator.
+///
+/// - `Me`: The role being projected.
+/// - `IO`: Protocol marker type.
+/// - `Lbl`: Label from the enclosing TChoiceN/TParN, used for skipped branches.
+/// - `Branches`: Type-level list of global branches.
+pub trait ProjectBranches<Me: Role, IO, Lbl: types::ProtocolLabel, Branches> {
+    type Out;
+}
+
+impl<Me: Role, IO, Lbl: types::ProtocolLabel> ProjectBranches<Me, IO, Lbl, Nil> for () {
+    type Out = Nil;
+}
+
+impl<Me, IO, Lbl, H, T> ProjectBranches<Me, IO, Lbl, Cons<H, T>> for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+    H: TSession<IO> + ContainsRole<Me>,
+    (): ProjectRoleOrSkip<Me, IO, H, <H as ContainsRole<Me>>::Output, Lbl>,
+    (): ProjectBranches<Me, IO, Lbl, T>,
+{
+    type Out = Cons<
+        <() as ProjectRoleOrSkip<Me, IO, H, <H as ContainsRole<Me>>::Output, Lbl>>::Out,
+        <() as ProjectBranches<Me, IO, Lbl, T>>::Out,
+    >;
+}
+
+// Projection implementation for TSelect - dispatch on whether Me is the chooser
+impl<Me, IO, Lbl, Chooser, Branches> ProjectRole<Me, IO, TSelect<IO, Lbl, Chooser, Branches>> for ()
+where
+    Me: Role + RoleEq<Chooser>,
+    Lbl: types::ProtocolLabel,
+    Branches: SessionList<IO> + ContainsRole<Me>,
+    (): ProjectSelectCase<
+        Me,
+        IO,
+        Lbl,
+        Branches,
+        <Me as RoleEq<Chooser>>::Output,
+        <Branches as ContainsRole<Me>>::Output,
+    >,
+{
+    type Out = <() as ProjectSelectCase<
+        Me,
+        IO,
+        Lbl,
+        Branches,
+        <Me as RoleEq<Chooser>>::Output,
+        <Branches as ContainsRole<Me>>::Output,
+    >>::Out;
+}
+
+/// Helper trait for projecting a `TSelect`.
+///
+/// - `IsChooser`: Type-level boolean, is the projected role the chooser?
+/// - `ContainsMe`: Type-level boolean, does any branch involve the projected role?
+pub trait ProjectSelectCase<Me, IO, Lbl: types::ProtocolLabel, Branches, IsChooser, ContainsMe> {
+    type Out: EpSession<IO, Me>;
+}
+
+// The chooser selects
+impl<Me, IO, Lbl, Branches, ContainsMe>
+    ProjectSelectCase<Me, IO, Lbl, Branches, types::True, ContainsMe> for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+    (): ProjectBranches<Me, IO, Lbl, Branches>,
+{
+    type Out = EpSelect<IO, Lbl, Me, <() as ProjectBranches<Me, IO, Lbl, Branches>>::Out>;
+}
+
+// Other participants react to the chooser's pick
+impl<Me, IO, Lbl, Branches> ProjectSelectCase<Me, IO, Lbl, Branches, types::False, types::True>
+    for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+    (): ProjectBranches<Me, IO, Lbl, Branches>,
+{
+    type Out = EpOffer<IO, Lbl, Me, <() as ProjectBranches<Me, IO, Lbl, Branches>>::Out>;
+}
+
+// Roles not involved at all skip the choice
+impl<Me, IO, Lbl, Branches> ProjectSelectCase<Me, IO, Lbl, Branches, types::False, types::False>
+    for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+{
+    type Out = EpSkip<IO, Lbl, Me>;
+}
+
+// Projection implementation for TOffer - dispatch on whether Me is the offeree
+impl<Me, IO, Lbl, Offeree, Branches> ProjectRole<Me, IO, TOffer<IO, Lbl, Offeree, Branches>> for ()
+where
+    Me: Role + RoleEq<Offeree>,
+    Lbl: types::ProtocolLabel,
+    Branches: SessionList<IO> + ContainsRole<Me>,
+    (): ProjectOfferCase<
+        Me,
+        IO,
+        Lbl,
+        Branches,
+        <Me as RoleEq<Offeree>>::Output,
+        <Branches as ContainsRole<Me>>::Output,
+    >,
+{
+    type Out = <() as ProjectOfferCase<
+        Me,
+        IO,
+        Lbl,
+        Branches,
+        <Me as RoleEq<Offeree>>::Output,
+        <Branches as ContainsRole<Me>>::Output,
+    >>::Out;
+}
+
+/// Helper trait for projecting a `TOffer`.
+///
+/// - `IsOfferee`: Type-level boolean, is the projected role the offeree?
+/// - `ContainsMe`: Type-level boolean, does any branch involve the projected role?
+pub trait ProjectOfferCase<Me, IO, Lbl: types::ProtocolLabel, Branches, IsOfferee, ContainsMe> {
+    type Out: EpSession<IO, Me>;
+}
+
+// The offeree reacts to the pick
+impl<Me, IO, Lbl, Branches, ContainsMe>
+    ProjectOfferCase<Me, IO, Lbl, Branches, types::True, ContainsMe> for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+    (): ProjectBranches<Me, IO, Lbl, Branches>,
+{
+    type Out = EpOffer<IO, Lbl, Me, <() as ProjectBranches<Me, IO, Lbl, Branches>>::Out>;
+}
+
+// Other participants make the pick
+impl<Me, IO, Lbl, Branches> ProjectOfferCase<Me, IO, Lbl, Branches, types::False, types::True>
+    for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+    (): ProjectBranches<Me, IO, Lbl, Branches>,
+{
+    type Out = EpSelect<IO, Lbl, Me, <() as ProjectBranches<Me, IO, Lbl, Branches>>::Out>;
+}
+
+// Roles not involved at all skip the choice
+impl<Me, IO, Lbl, Branches> ProjectOfferCase<Me, IO, Lbl, Branches, types::False, types::False>
+    for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+{
+    type Out = EpSkip<IO, Lbl, Me>;
+}
+
+// Projection implementation for TRec - the loop keeps its label, which also
+// names its recursion variable, and the body must be guarded
+impl<Me, IO, Lbl, S> ProjectRole<Me, IO, TRec<IO, Lbl, S>> for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+    S: TSession<IO> + Guarded + ContainsRole<Me>,
+    (): ProjectRecXCase<Me, IO, Lbl, Lbl, S, <S as ContainsRole<Me>>::Output>,
+{
+    type Out = <() as ProjectRecXCase<Me, IO, Lbl, Lbl, S, <S as ContainsRole<Me>>::Output>>::Out;
+}
+
+// Projection implementation for TRecX - the body must be guarded
+impl<Me, IO, Lbl, Var, Body> ProjectRole<Me, IO, TRecX<IO, Lbl, Var, Body>> for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+    Body: TSession<IO> + Guarded + ContainsRole<Me>,
+    (): ProjectRecXCase<Me, IO, Lbl, Var, Body, <Body as ContainsRole<Me>>::Output>,
+{
+    type Out =
+        <() as ProjectRecXCase<Me, IO, Lbl, Var, Body, <Body as ContainsRole<Me>>::Output>>::Out;
+}
+
+// Helper trait for handling TRecX projection based on role presence
+pub trait ProjectRecXCase<Me, IO, Lbl: types::ProtocolLabel, Var, Body: TSession<IO>, ContainsMe> {
+    type Out: EpSession<IO, Me>;
+}
+
+// The body involves the role - keep the loop
+impl<Me, IO, Lbl, Var, Body> ProjectRecXCase<Me, IO, Lbl, Var, Body, types::True> for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+    Body: TSession<IO>,
+    (): ProjectRole<Me, IO, Body>,
+{
+    type Out = EpRec<IO, Lbl, Var, <() as ProjectRole<Me, IO, Body>>::Out>;
+}
+
+// The body never involves the role
+impl<Me, IO, Lbl, Var, Body> ProjectRecXCase<Me, IO, Lbl, Var, Body, types::False> for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+    Body: TSession<IO>,
+{
+    type Out = EpSkip<IO, Lbl, Me>;
+}
+
+// Projection implementation for TVar - every role jumps back with the loop
+impl<Me: Role, IO, Var> ProjectRole<Me, IO, TVar<Var>> for () {
+    type Out = EpVar<Var>;
+}
+
+// Projection implementation for TTimeout - dispatch on whether either side contains the role
+impl<Me, IO, Lbl, Dur, Body, OnTimeout> ProjectRole<Me, IO, TTimeout<IO, Lbl, Dur, Body, OnTimeout>>
+    for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    Body: TSession<IO> + ContainsRole<Me>,
+    OnTimeout: TSession<IO> + ContainsRole<Me>,
+    <Body as ContainsRole<Me>>::Output: types::BoolOr<<OnTimeout as ContainsRole<Me>>::Output>,
+    (): ProjectTimeoutCase<
+        Me,
+        IO,
+        Lbl,
+        Dur,
+        Body,
+        OnTimeout,
+        types::Or<<Body as ContainsRole<Me>>::Output, <OnTimeout as ContainsRole<Me>>::Output>,
+    >,
+{
+    type Out = <() as ProjectTimeoutCase<
+        Me,
+        IO,
+        Lbl,
+        Dur,
+        Body,
+        OnTimeout,
+        types::Or<<Body as ContainsRole<Me>>::Output, <OnTimeout as ContainsRole<Me>>::Output>,
+    >>::Out;
+}
+
+// Helper trait for handling TTimeout projection based on role presence
+pub trait ProjectTimeoutCase<Me, IO, Lbl: types::ProtocolLabel, Dur, Body, OnTimeout, ContainsMe> {
+    type Out: EpSession<IO, Me>;
+}
+
+// The role takes part - it needs the timer, with either side skipped if uninvolved
+impl<Me, IO, Lbl, Dur, Body, OnTimeout>
+    ProjectTimeoutCase<Me, IO, Lbl, Dur, Body, OnTimeout, types::True> for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+    Body: TSession<IO> + ContainsRole<Me>,
+    OnTimeout: TSession<IO> + ContainsRole<Me>,
+    (): ProjectRoleOrSkip<Me, IO, Body, <Body as ContainsRole<Me>>::Output, Lbl>,
+    (): ProjectRoleOrSkip<Me, IO, OnTimeout, <OnTimeout as ContainsRole<Me>>::Output, Lbl>,
+{
+    type Out = EpTimeout<
+        IO,
+        Lbl,
+        Me,
+        Dur,
+        <() as ProjectRoleOrSkip<Me, IO, Body, <Body as ContainsRole<Me>>::Output, Lbl>>::Out,
+        <() as ProjectRoleOrSkip<
+            Me,
+            IO,
+            OnTimeout,
+            <OnTimeout as ContainsRole<Me>>::Output,
+            Lbl,
+        >>::Out,
+    >;
+}
+
+// The role never takes part
+impl<Me, IO, Lbl, Dur, Body, OnTimeout>
+    ProjectTimeoutCase<Me, IO, Lbl, Dur, Body, OnTimeout, types::False> for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+{
+    type Out = EpSkip<IO, Lbl, Me>;
+}
+
+// Projection implementation for TDelay - only the constrained role sees it
+impl<Me, IO, Lbl, R, Dur, T> ProjectRole<Me, IO, TDelay<IO, Lbl, R, Dur, T>> for ()
+where
+    Me: Role + RoleEq<R>,
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: TSession<IO>,
+    (): ProjectDelay<<Me as RoleEq<R>>::Output, Me, IO, Lbl, Dur, T>,
+{
+    type Out = <() as ProjectDelay<<Me as RoleEq<R>>::Output, Me, IO, Lbl, Dur, T>>::Out;
+}
+
+/// Helper trait for projecting a `TDelay`.
+///
+/// - `IsMe`: Type-level boolean, is the projected role the constrained role?
+pub trait ProjectDelay<IsMe, Me: Role, IO, Lbl: types::ProtocolLabel, Dur, T: TSession<IO>> {
+    type Out: EpSession<IO, Me>;
+}
+
+// The constrained role waits before continuing
+impl<Me, IO, Lbl, Dur, T> ProjectDelay<types::True, Me, IO, Lbl, Dur, T> for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+    T: TSession<IO>,
+    (): ProjectRole<Me, IO, T>,
+{
+    type Out = EpDelay<IO, Lbl, Me, Dur, <() as ProjectRole<Me, IO, T>>::Out>;
+}
+
+// Other roles continue with the rest of the protocol
+impl<Me, IO, Lbl, Dur, T> ProjectDelay<types::False, Me, IO, Lbl, Dur, T> for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+    T: TSession<IO>,
+    (): ProjectRole<Me, IO, T>,
+{
+    type Out = <() as ProjectRole<Me, IO, T>>::Out;
+}
+
+// Projection implementation for TDeadline - only the constrained role sees it
+impl<Me, IO, Lbl, R, Dur, T> ProjectRole<Me, IO, TDeadline<IO, Lbl, R, Dur, T>> for ()
+where
+    Me: Role + RoleEq<R>,
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: TSession<IO>,
+    (): ProjectDeadline<<Me as RoleEq<R>>::Output, Me, IO, Lbl, Dur, T>,
+{
+    type Out = <() as ProjectDeadline<<Me as RoleEq<R>>::Output, Me, IO, Lbl, Dur, T>>::Out;
+}
+
+/// Helper trait for projecting a `TDeadline`.
+///
+/// - `IsMe`: Type-level boolean, is the projected role the constrained role?
+pub trait ProjectDeadline<IsMe, Me: Role, IO, Lbl: types::ProtocolLabel, Dur, T: TSession<IO>> {
+    type Out: EpSession<IO, Me>;
+}
+
+// The constrained role runs the continuation against the deadline
+impl<Me, IO, Lbl, Dur, T> ProjectDeadline<types::True, Me, IO, Lbl, Dur, T> for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+    T: TSession<IO>,
+    (): ProjectRole<Me, IO, T>,
+{
+    type Out = EpDeadline<IO, Lbl, Me, Dur, <() as ProjectRole<Me, IO, T>>::Out>;
+}
+
+// Other roles continue with the rest of the protocol
+impl<Me, IO, Lbl, Dur, T> ProjectDeadline<types::False, Me, IO, Lbl, Dur, T> for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+    T: TSession<IO>,
+    (): ProjectRole<Me, IO, T>,
+{
+    type Out = <() as ProjectRole<Me, IO, T>>::Out;
+}
+
+// Projection implementation for TInterrupt - dispatch on interruptor and role presence
+impl<Me, IO, Lbl, Scope, Interruptor, Handler>
+    ProjectRole<Me, IO, TInterrupt<IO, Lbl, Scope, Interruptor, Handler>> for ()
+where
+    Me: Role + RoleEq<Interruptor>,
+    Lbl: types::ProtocolLabel,
+    Scope: TSession<IO> + ContainsRole<Me>,
+    Handler: TSession<IO> + ContainsRole<Me>,
+    <Scope as ContainsRole<Me>>::Output: types::BoolOr<<Handler as ContainsRole<Me>>::Output>,
+    (): ProjectInterruptCase<
+        Me,
+        IO,
+        Lbl,
+        Scope,
+        Handler,
+        <Me as RoleEq<Interruptor>>::Output,
+        types::Or<<Scope as ContainsRole<Me>>::Output, <Handler as ContainsRole<Me>>::Output>,
+    >,
+{
+    type Out = <() as ProjectInterruptCase<
+        Me,
+        IO,
+        Lbl,
+        Scope,
+        Handler,
+        <Me as RoleEq<Interruptor>>::Output,
+        types::Or<<Scope as ContainsRole<Me>>::Output, <Handler as ContainsRole<Me>>::Output>,
+    >>::Out;
+}
+
+/// Helper trait for projecting a `TInterrupt`.
+///
+/// - `IsInterruptor`: Type-level boolean, is the projected role the interruptor?
+/// - `ContainsMe`: Type-level boolean, does the scope or handler involve the projected role?
+pub trait ProjectInterruptCase<
+    Me,
+    IO,
+    Lbl: types::ProtocolLabel,
+    Scope: TSession<IO>,
+    Handler: TSession<IO>,
+    IsInterruptor,
+    ContainsMe,
+>
+{
+    type Out: EpSession<IO, Me>;
+}
+
+// The interruptor may raise the interrupt from anywhere in the scope
+impl<Me, IO, Lbl, Scope, Handler, ContainsMe>
+    ProjectInterruptCase<Me, IO, Lbl, Scope, Handler, types::True, ContainsMe> for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+    Scope: TSession<IO> + ContainsRole<Me>,
+    Handler: TSession<IO> + ContainsRole<Me>,
+    (): ProjectRoleOrSkip<Me, IO, Scope, <Scope as ContainsRole<Me>>::Output, Lbl>,
+    (): ProjectRoleOrSkip<Me, IO, Handler, <Handler as ContainsRole<Me>>::Output, Lbl>,
+{
+    type Out = EpInterrupt<
+        IO,
+        Lbl,
+        Me,
+        <() as ProjectRoleOrSkip<Me, IO, Scope, <Scope as ContainsRole<Me>>::Output, Lbl>>::Out,
+        <() as ProjectRoleOrSkip<Me, IO, Handler, <Handler as ContainsRole<Me>>::Output, Lbl>>::Out,
+    >;
+}
+
+// Other participants must be ready to be diverted into the handler
+impl<Me, IO, Lbl, Scope, Handler>
+    ProjectInterruptCase<Me, IO, Lbl, Scope, Handler, types::False, types::True> for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+    Scope: TSession<IO> + ContainsRole<Me>,
+    Handler: TSession<IO> + ContainsRole<Me>,
+    (): ProjectRoleOrSkip<Me, IO, Scope, <Scope as ContainsRole<Me>>::Output, Lbl>,
+    (): ProjectRoleOrSkip<Me, IO, Handler, <Handler as ContainsRole<Me>>::Output, Lbl>,
+{
+    type Out = EpInterruptible<
+        IO,
+        Lbl,
+        Me,
+        <() as ProjectRoleOrSkip<Me, IO, Scope, <Scope as ContainsRole<Me>>::Output, Lbl>>::Out,
+        <() as ProjectRoleOrSkip<Me, IO, Handler, <Handler as ContainsRole<Me>>::Output, Lbl>>::Out,
+    >;
+}
+
+// Roles not involved at all skip the scope
+impl<Me, IO, Lbl, Scope, Handler>
+    ProjectInterruptCase<Me, IO, Lbl, Scope, Handler, types::False, types::False> for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+    Scope: TSession<IO>,
+    Handler: TSession<IO>,
+{
+    type Out = EpSkip<IO, Lbl, Me>;
+}
+
+// Projection implementation for TAnnotate - annotations never reach local types
+impl<Me, IO, Meta, Inner> ProjectRole<Me, IO, TAnnotate<Meta, Inner>> for ()
+where
+    Me: Role,
+    Inner: TSession<IO>,
+    (): ProjectRole<Me, IO, Inner>,
+{
+    type Out = <() as ProjectRole<Me, IO, Inner>>::Out;
+}
+
+// Projection implementation for TRefine - keep the predicate for involved roles
+impl<Me, IO, Pred, Inner> ProjectRole<Me, IO, TRefine<Pred, Inner>> for ()
+where
+    Me: Role,
+    Pred: types::Predicate,
+    Inner: TSession<IO> + ContainsRole<Me>,
+    (): ProjectRefineCase<Me, IO, Pred, Inner, <Inner as ContainsRole<Me>>::Output>,
+{
+    type Out =
+        <() as ProjectRefineCase<Me, IO, Pred, Inner, <Inner as ContainsRole<Me>>::Output>>::Out;
+}
+
+/// Helper trait for projecting a `TRefine`.
+///
+/// - `ContainsMe`: Type-level boolean, does the refined fragment involve the projected role?
+pub trait ProjectRefineCase<Me, IO, Pred: types::Predicate, Inner: TSession<IO>, ContainsMe> {
+    type Out: EpSession<IO, Me>;
+}
+
+// Involved roles carry the predicate so they can enforce it
+impl<Me, IO, Pred, Inner> ProjectRefineCase<Me, IO, Pred, Inner, types::True> for ()
+where
+    Me: Role,
+    Pred: types::Predicate,
+    Inner: TSession<IO>,
+    (): ProjectRole<Me, IO, Inner>,
+{
+    type Out = EpRefine<Pred, <() as ProjectRole<Me, IO, Inner>>::Out>;
+}
+
+// Other roles have nothing to enforce
+impl<Me, IO, Pred, Inner> ProjectRefineCase<Me, IO, Pred, Inner, types::False> for ()
+where
+    Me: Role,
+    Pred: types::Predicate,
+    Inner: TSession<IO>,
+    (): ProjectRole<Me, IO, Inner>,
+{
+    type Out = <() as ProjectRole<Me, IO, Inner>>::Out;
+}
+
+// Projection implementation for TOptional - dispatch on decider and involvement in the body
+impl<Me, IO, Lbl, Decider, Body, Cont> ProjectRole<Me, IO, TOptional<IO, Lbl, Decider, Body, Cont>>
+    for ()
+where
+    Me: Role + RoleEq<Decider>,
+    Lbl: types::ProtocolLabel,
+    Body: TSession<IO> + ContainsRole<Me>,
+    Cont: TSession<IO>,
+    (): ProjectOptionalCase<
+        Me,
+        IO,
+        Lbl,
+        Body,
+        Cont,
+        <Me as RoleEq<Decider>>::Output,
+        <Body as ContainsRole<Me>>::Output,
+    >,
+{
+    type Out = <() as ProjectOptionalCase<
+        Me,
+        IO,
+        Lbl,
+        Body,
+        Cont,
+        <Me as RoleEq<Decider>>::Output,
+        <Body as ContainsRole<Me>>::Output,
+    >>::Out;
+}
+
+/// Helper trait for projecting a `TOptional`.
+///
+/// - `IsDecider`: Type-level boolean, is the projected role the decider?
+/// - `BodyContainsMe`: Type-level boolean, does the optional step involve the projected role?
+///
+/// Roles that only appear in the continuation behave the same either way and
+/// project straight to it.
+pub trait ProjectOptionalCase<
+    Me,
+    IO,
+    Lbl: types::ProtocolLabel,
+    Body: TSession<IO>,
+    Cont: TSession<IO>,
+    IsDecider,
+    BodyContainsMe,
+>
+{
+    type Out: EpSession<IO, Me>;
+}
+
+// The decider selects whether to run the body
+impl<Me, IO, Lbl, Body, Cont, BodyContainsMe>
+    ProjectOptionalCase<Me, IO, Lbl, Body, Cont, types::True, BodyContainsMe> for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+    Body: TSession<IO>,
+    Cont: TSession<IO>,
+    (): ProjectRoleOrSkip<Me, IO, Body, BodyContainsMe, Lbl>,
+    (): ProjectRole<Me, IO, Cont>,
+{
+    type Out = EpOptionalSelect<
+        IO,
+        Lbl,
+        Me,
+        <() as ProjectRoleOrSkip<Me, IO, Body, BodyContainsMe, Lbl>>::Out,
+        <() as ProjectRole<Me, IO, Cont>>::Out,
+    >;
+}
+
+// Roles in the body are told whether it runs
+impl<Me, IO, Lbl, Body, Cont>
+    ProjectOptionalCase<Me, IO, Lbl, Body, Cont, types::False, types::True> for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+    Body: TSession<IO>,
+    Cont: TSession<IO>,
+    (): ProjectRole<Me, IO, Body>,
+    (): ProjectRole<Me, IO, Cont>,
+{
+    type Out = EpOptionalOffer<
+        IO,
+        Lbl,
+        Me,
+        <() as ProjectRole<Me, IO, Body>>::Out,
+        <() as ProjectRole<Me, IO, Cont>>::Out,
+    >;
+}
+
+// Everyone else continues as if the step were not there
+impl<Me, IO, Lbl, Body, Cont>
+    ProjectOptionalCase<Me, IO, Lbl, Body, Cont, types::False, types::False> for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+    Body: TSession<IO>,
+    Cont: TSession<IO>,
+    (): ProjectRole<Me, IO, Cont>,
+{
+    type Out = <() as ProjectRole<Me, IO, Cont>>::Out;
+}
+
+// Projection implementation for TThrow - dispatch on whether the projected role raises it
+impl<Me, IO, Lbl, R, Err> ProjectRole<Me, IO, TThrow<IO, Lbl, R, Err>> for ()
+where
+    Me: Role + RoleEq<R>,
+    Lbl: types::ProtocolLabel,
+    (): ProjectThrow<<Me as RoleEq<R>>::Output, Me, IO, Lbl, Err>,
+{
+    type Out = <() as ProjectThrow<<Me as RoleEq<R>>::Output, Me, IO, Lbl, Err>>::Out;
+}
+
+/// Helper trait for projecting a `TThrow`.
+///
+/// - `IsMe`: Type-level boolean, is the projected role the one raising the error?
+pub trait ProjectThrow<IsMe, Me: Role, IO, Lbl: types::ProtocolLabel, Err> {
+    type Out: EpSession<IO, Me>;
+}
+
+// The raising role throws
+impl<Me: Role, IO, Lbl: types::ProtocolLabel, Err> ProjectThrow<types::True, Me, IO, Lbl, Err>
+    for ()
+{
+    type Out = EpThrow<IO, Lbl, Me, Err>;
+}
+
+// For everyone else the body ends here; the enclosing catch moves them to the handler
+impl<Me: Role, IO, Lbl: types::ProtocolLabel, Err> ProjectThrow<types::False, Me, IO, Lbl, Err>
+    for ()
+{
+    type Out = EpEnd<IO, Lbl, Me>;
+}
+
+// Projection implementation for TCatch - every involved role enters the handler on failure
+impl<Me, IO, Lbl, Body, Handler> ProjectRole<Me, IO, TCatch<IO, Lbl, Body, Handler>> for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+    Body: TSession<IO> + ContainsRole<Me>,
+    Handler: TSession<IO> + ContainsRole<Me>,
+    <Body as ContainsRole<Me>>::Output: types::BoolOr<<Handler as ContainsRole<Me>>::Output>,
+    (): ProjectCatchCase<
+        Me,
+        IO,
+        Lbl,
+        Body,
+        Handler,
+        types::Or<<Body as ContainsRole<Me>>::Output, <Handler as ContainsRole<Me>>::Output>,
+    >,
+{
+    type Out = <() as ProjectCatchCase<
+        Me,
+        IO,
+        Lbl,
+        Body,
+        Handler,
+        types::Or<<Body as ContainsRole<Me>>::Output, <Handler as ContainsRole<Me>>::Output>,
+    >>::Out;
+}
+
+/// Helper trait for projecting a `TCatch`.
+///
+/// - `ContainsMe`: Type-level boolean, does the body or handler involve the projected role?
+pub trait ProjectCatchCase<
+    Me,
+    IO,
+    Lbl: types::ProtocolLabel,
+    Body: TSession<IO>,
+    Handler: TSession<IO>,
+    ContainsMe,
+>
+{
+    type Out: EpSession<IO, Me>;
+}
+
+impl<Me, IO, Lbl, Body, Handler> ProjectCatchCase<Me, IO, Lbl, Body, Handler, types::True> for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+    Body: TSession<IO> + ContainsRole<Me>,
+    Handler: TSession<IO> + ContainsRole<Me>,
+    (): ProjectRoleOrSkip<Me, IO, Body, <Body as ContainsRole<Me>>::Output, Lbl>,
+    (): ProjectRoleOrSkip<Me, IO, Handler, <Handler as ContainsRole<Me>>::Output, Lbl>,
+{
+    type Out = EpCatch<
+        IO,
+        Lbl,
+        Me,
+        <() as ProjectRoleOrSkip<Me, IO, Body, <Body as ContainsRole<Me>>::Output, Lbl>>::Out,
+        <() as ProjectRoleOrSkip<Me, IO, Handler, <Handler as ContainsRole<Me>>::Output, Lbl>>::Out,
+    >;
+}
+
+impl<Me, IO, Lbl, Body, Handler> ProjectCatchCase<Me, IO, Lbl, Body, Handler, types::False> for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+    Body: TSession<IO>,
+    Handler: TSession<IO>,
+{
+    type Out = EpSkip<IO, Lbl, Me>;
+}
+
+// Projection implementation for TPriorityChoice - dispatch on whether either branch contains the role
+impl<Me, IO, Lbl, High, Low> ProjectRole<Me, IO, TPriorityChoice<IO, Lbl, High, Low>> for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+    High: TSession<IO> + ContainsRole<Me>,
+    Low: TSession<IO> + ContainsRole<Me>,
+    <High as ContainsRole<Me>>::Output: types::BoolOr<<Low as ContainsRole<Me>>::Output>,
+    (): ProjectPriorityCase<
+        Me,
+        IO,
+        Lbl,
+        High,
+        Low,
+        types::Or<<High as ContainsRole<Me>>::Output, <Low as ContainsRole<Me>>::Output>,
+    >,
+{
+    type Out = <() as ProjectPriorityCase<
+        Me,
+        IO,
+        Lbl,
+        High,
+        Low,
+        types::Or<<High as ContainsRole<Me>>::Output, <Low as ContainsRole<Me>>::Output>,
+    >>::Out;
+}
+
+/// Helper trait for projecting a `TPriorityChoice`.
+///
+/// - `ContainsMe`: Type-level boolean, does either branch involve the projected role?
+pub trait ProjectPriorityCase<
+    Me,
+    IO,
+    Lbl: types::ProtocolLabel,
+    High: TSession<IO>,
+    Low: TSession<IO>,
+    ContainsMe,
+>
+{
+    type Out: EpSession<IO, Me>;
+}
+
+// The role takes part - keep the priority, with either branch skipped if uninvolved
+impl<Me, IO, Lbl, High, Low> ProjectPriorityCase<Me, IO, Lbl, High, Low, types::True> for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+    High: TSession<IO> + ContainsRole<Me>,
+    Low: TSession<IO> + ContainsRole<Me>,
+    (): ProjectRoleOrSkip<Me, IO, High, <High as ContainsRole<Me>>::Output, Lbl>,
+    (): ProjectRoleOrSkip<Me, IO, Low, <Low as ContainsRole<Me>>::Output, Lbl>,
+{
+    type Out = EpPriorityOffer<
+        IO,
+        Lbl,
+        Me,
+        <() as ProjectRoleOrSkip<Me, IO, High, <High as ContainsRole<Me>>::Output, Lbl>>::Out,
+        <() as ProjectRoleOrSkip<Me, IO, Low, <Low as ContainsRole<Me>>::Output, Lbl>>::Out,
+    >;
+}
+
+// The role never takes part
+impl<Me, IO, Lbl, High, Low> ProjectPriorityCase<Me, IO, Lbl, High, Low, types::False> for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+    High: TSession<IO>,
+    Low: TSession<IO>,
+{
+    type Out = EpSkip<IO, Lbl, Me>;
+}
+
+// Projection implementation for TProb - weights do not reach the endpoints, so
+// project as the equivalent TChoice
+impl<Me, IO, Lbl, WeightL, L, WeightR, R>
+    ProjectRole<Me, IO, TProb<IO, Lbl, WeightL, L, WeightR, R>> for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+    WeightL: types::WeightMarker,
+    WeightR: types::WeightMarker,
+    L: TSession<IO>,
+    R: TSession<IO>,
+    (): ProjectRole<Me, IO, TChoice<IO, Lbl, L, R>>,
+{
+    type Out = <() as ProjectRole<Me, IO, TChoice<IO, Lbl, L, R>>>::Out;
+}
+
+// Projection implementation for TParN - requires pairwise disjoint branches
+impl<Me, IO, Lbl, Branches> ProjectRole<Me, IO, TParN<IO, Lbl, Branches>> for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+    Branches: SessionList<IO> + ContainsRole<Me> + PairwiseDisjoint,
+    (): ProjectParNCase<Me, IO, Lbl, Branches, <Branches as ContainsRole<Me>>::Output>,
+{
+    type Out =
+        <() as ProjectParNCase<Me, IO, Lbl, Branches, <Branches as ContainsRole<Me>>::Output>>::Out;
+}
+
+// Helper trait for handling TParN projection based on role presence
+pub trait ProjectParNCase<Me, IO, Lbl: types::ProtocolLabel, Branches, ContainsMe> {
+    type Out: EpSession<IO, Me>;
+}
+
+// Some branch contains the role - keep only the branches it takes part in
+impl<Me, IO, Lbl, Branches> ProjectParNCase<Me, IO, Lbl, Branches, types::True> for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+    (): ProjectBranches<Me, IO, Lbl, Branches>,
+    (): FilterSkips<IO, Me, <() as ProjectBranches<Me, IO, Lbl, Branches>>::Out>,
+{
+    type Out = EpParN<
+        IO,
+        Lbl,
+        Me,
+        <() as FilterSkips<IO, Me, <() as ProjectBranches<Me, IO, Lbl, Branches>>::Out>>::Out,
+    >;
+}
+
+// No branch contains the role
+impl<Me, IO, Lbl, Branches> ProjectParNCase<Me, IO, Lbl, Branches, types::False> for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+{
+    type Out = EpSkip<IO, Lbl, Me>;
+}
+
+// Projection implementation for TShuffle - requires pairwise independent steps
+impl<Me, IO, Lbl, Steps> ProjectRole<Me, IO, TShuffle<IO, Lbl, Steps>> for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+    Steps: SessionList<IO> + ContainsRole<Me> + PairwiseIndependent,
+    (): ProjectShuffleCase<Me, IO, Lbl, Steps, <Steps as ContainsRole<Me>>::Output>,
+{
+    type Out =
+        <() as ProjectShuffleCase<Me, IO, Lbl, Steps, <Steps as ContainsRole<Me>>::Output>>::Out;
+}
+
+// Helper trait for handling TShuffle projection based on role presence
+pub trait ProjectShuffleCase<Me, IO, Lbl: types::ProtocolLabel, Steps, ContainsMe> {
+    type Out: EpSession<IO, Me>;
+}
+
+// Some step contains the role - keep only the steps it takes part in
+impl<Me, IO, Lbl, Steps> ProjectShuffleCase<Me, IO, Lbl, Steps, types::True> for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+    (): ProjectBranches<Me, IO, Lbl, Steps>,
+    (): FilterSkips<IO, Me, <() as ProjectBranches<Me, IO, Lbl, Steps>>::Out>,
+{
+    type Out = EpShuffle<
+        IO,
+        Lbl,
+        Me,
+        <() as FilterSkips<IO, Me, <() as ProjectBranches<Me, IO, Lbl, Steps>>::Out>>::Out,
+    >;
+}
+
+// No step contains the role
+impl<Me, IO, Lbl, Steps> ProjectShuffleCase<Me, IO, Lbl, Steps, types::False> for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+{
+    type Out = EpSkip<IO, Lbl, Me>;
+}
+
+// --- Helper trait to check if a role is present in a protocol branch.
+/// Returns a type-level boolean indicating whether the role is present.
+pub trait ContainsRole<R> {
+    type Output: types::Bool;
+}
+
+/// Helper trait to check if a role is NOT present in a protocol branch.
+pub trait NotContainsRole<R> {}
+
+// Base case: TEnd doesn't contain any role
+impl<IO, Lbl, R> ContainsRole<R> for TEnd<IO, Lbl> {
+    type Output = types::False;
+}
+
+impl<IO, Lbl, R> NotContainsRole<R> for TEnd<IO, Lbl> {}
+
+// TInteract names only the acting role and every other role receives, so every
+// role takes part in it. Interactions that leave third parties silent are written
+// with TMsg, whose ContainsRole checks both the sender and the receiver.
+impl<IO, Lbl, H, T, R1, R2> ContainsRole<R2> for TInteract<IO, Lbl, R1, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: TSession<IO>,
+{
+    type Output = types::True;
+}
+
+// TInteract never implements NotContainsRole: it has no bystanders
+
+// TMsg contains the role if it is the sender, the receiver, or appears in the
+// continuation. Unlike TInteract, bystanders are not considered involved.
+impl<IO, Lbl, From, To, H, T, RoleT> ContainsRole<RoleT> for TMsg<IO, Lbl, From, To, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    From: RoleEq<RoleT>,
+    To: RoleEq<RoleT>,
+    <From as RoleEq<RoleT>>::Output: types::BoolOr<<To as RoleEq<RoleT>>::Output>,
+    T: TSession<IO> + ContainsRole<RoleT>,
+    types::Or<<From as RoleEq<RoleT>>::Output, <To as RoleEq<RoleT>>::Output>:
+        types::BoolOr<<T as ContainsRole<RoleT>>::Output>,
+{
+    type Output = types::Or<
+        types::Or<<From as RoleEq<RoleT>>::Output, <To as RoleEq<RoleT>>::Output>,
+        <T as ContainsRole<RoleT>>::Output,
+    >;
+}
+
+// TMsg doesn't contain the role if it is neither endpoint nor in the continuation
+impl<IO, Lbl, From, To, H, T, RoleT> NotContainsRole<RoleT> for TMsg<IO, Lbl, From, To, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    From: RoleEq<RoleT, Output = types::False>,
+    To: RoleEq<RoleT, Output = types::False>,
+    T: TSession<IO> + NotContainsRole<RoleT>,
+{
+}
+
+// TBroadcast contains the role if it is the sender, a member of the receiver
+// set, or appears in the continuation
+impl<IO, Lbl, From, ToSet, H, T, RoleT> ContainsRole<RoleT>
+    for TBroadcast<IO, Lbl, From, ToSet, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    From: RoleEq<RoleT>,
+    RoleT: InRoleSet<ToSet>,
+    <From as RoleEq<RoleT>>::Output: types::BoolOr<<RoleT as InRoleSet<ToSet>>::Output>,
+    T: TSession<IO> + ContainsRole<RoleT>,
+    types::Or<<From as RoleEq<RoleT>>::Output, <RoleT as InRoleSet<ToSet>>::Output>:
+        types::BoolOr<<T as ContainsRole<RoleT>>::Output>,
+{
+    type Output = types::Or<
+        types::Or<<From as RoleEq<RoleT>>::Output, <RoleT as InRoleSet<ToSet>>::Output>,
+        <T as ContainsRole<RoleT>>::Output,
+    >;
+}
+
+impl<IO, Lbl, From, ToSet, H, T, RoleT> NotContainsRole<RoleT>
+    for TBroadcast<IO, Lbl, From, ToSet, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    From: RoleEq<RoleT, Output = types::False>,
+    RoleT: InRoleSet<ToSet, Output = types::False>,
+    T: TSession<IO> + NotContainsRole<RoleT>,
+{
+}
+
+// TAnycast contains the role if it is the sender, a member of the worker set,
+// or appears in the continuation
+impl<IO, Lbl, From, WorkerSet, H, T, RoleT> ContainsRole<RoleT>
+    for TAnycast<IO, Lbl, From, WorkerSet, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    From: RoleEq<RoleT>,
+    RoleT: InRoleSet<WorkerSet>,
+    <From as RoleEq<RoleT>>::Output: types::BoolOr<<RoleT as InRoleSet<WorkerSet>>::Output>,
+    T: TSession<IO> + ContainsRole<RoleT>,
+    types::Or<<From as RoleEq<RoleT>>::Output, <RoleT as InRoleSet<WorkerSet>>::Output>:
+        types::BoolOr<<T as ContainsRole<RoleT>>::Output>,
+{
+    type Output = types::Or<
+        types::Or<<From as RoleEq<RoleT>>::Output, <RoleT as InRoleSet<WorkerSet>>::Output>,
+        <T as ContainsRole<RoleT>>::Output,
+    >;
+}
+
+impl<IO, Lbl, From, WorkerSet, H, T, RoleT> NotContainsRole<RoleT>
+    for TAnycast<IO, Lbl, From, WorkerSet, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    From: RoleEq<RoleT, Output = types::False>,
+    RoleT: InRoleSet<WorkerSet, Output = types::False>,
+    T: TSession<IO> + NotContainsRole<RoleT>,
+{
+}
+
+// TDelegate contains the role if it is either endpoint of the hand-over or
+// appears in the continuation. The delegated endpoint's own role belongs to
+// the other session and is not a participant here.
+impl<IO, Lbl, From, To, DelegatedEp, T, RoleT> ContainsRole<RoleT>
+    for TDelegate<IO, Lbl, From, To, DelegatedEp, T>
+where
+    Lbl: types::ProtocolLabel,
+    From: RoleEq<RoleT>,
+    To: RoleEq<RoleT>,
+    <From as RoleEq<RoleT>>::Output: types::BoolOr<<To as RoleEq<RoleT>>::Output>,
+    T: TSession<IO> + ContainsRole<RoleT>,
+    types::Or<<From as RoleEq<RoleT>>::Output, <To as RoleEq<RoleT>>::Output>:
+        types::BoolOr<<T as ContainsRole<RoleT>>::Output>,
+{
+    type Output = types::Or<
+        types::Or<<From as RoleEq<RoleT>>::Output, <To as RoleEq<RoleT>>::Output>,
+        <T as ContainsRole<RoleT>>::Output,
+    >;
+}
+
+impl<IO, Lbl, From, To, DelegatedEp, T, RoleT> NotContainsRole<RoleT>
+    for TDelegate<IO, Lbl, From, To, DelegatedEp, T>
+where
+    Lbl: types::ProtocolLabel,
+    From: RoleEq<RoleT, Output = types::False>,
+    To: RoleEq<RoleT, Output = types::False>,
+    T: TSession<IO> + NotContainsRole<RoleT>,
+{
+}
+
+// TDisconnect contains the role if it is one of the two closing roles, or
+// appears in the continuation
+impl<IO, Lbl, A, B, Cont, RoleT> ContainsRole<RoleT> for TDisconnect<IO, Lbl, A, B, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    A: RoleEq<RoleT>,
+    B: RoleEq<RoleT>,
+    <A as RoleEq<RoleT>>::Output: types::BoolOr<<B as RoleEq<RoleT>>::Output>,
+    Cont: TSession<IO> + ContainsRole<RoleT>,
+    types::Or<<A as RoleEq<RoleT>>::Output, <B as RoleEq<RoleT>>::Output>:
+        types::BoolOr<<Cont as ContainsRole<RoleT>>::Output>,
+{
+    type Output = types::Or<
+        types::Or<<A as RoleEq<RoleT>>::Output, <B as RoleEq<RoleT>>::Output>,
+        <Cont as ContainsRole<RoleT>>::Output,
+    >;
+}
+
+impl<IO, Lbl, A, B, Cont, RoleT> NotContainsRole<RoleT> for TDisconnect<IO, Lbl, A, B, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    A: RoleEq<RoleT, Output = types::False>,
+    B: RoleEq<RoleT, Output = types::False>,
+    Cont: TSession<IO> + NotContainsRole<RoleT>,
+{
+}
+
+// TConnect contains the role if it is one of the two opening roles, or
+// appears in the continuation
+impl<IO, Lbl, A, B, Cont, RoleT> ContainsRole<RoleT> for TConnect<IO, Lbl, A, B, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    A: RoleEq<RoleT>,
+    B: RoleEq<RoleT>,
+    <A as RoleEq<RoleT>>::Output: types::BoolOr<<B as RoleEq<RoleT>>::Output>,
+    Cont: TSession<IO> + ContainsRole<RoleT>,
+    types::Or<<A as RoleEq<RoleT>>::Output, <B as RoleEq<RoleT>>::Output>:
+        types::BoolOr<<Cont as ContainsRole<RoleT>>::Output>,
+{
+    type Output = types::Or<
+        types::Or<<A as RoleEq<RoleT>>::Output, <B as RoleEq<RoleT>>::Output>,
+        <Cont as ContainsRole<RoleT>>::Output,
+    >;
+}
+
+impl<IO, Lbl, A, B, Cont, RoleT> NotContainsRole<RoleT> for TConnect<IO, Lbl, A, B, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    A: RoleEq<RoleT, Output = types::False>,
+    B: RoleEq<RoleT, Output = types::False>,
+    Cont: TSession<IO> + NotContainsRole<RoleT>,
+{
+}
+
+// TEndFor contains the role if it is the leaving role, or appears in the
+// continuation
+impl<IO, Lbl, R, Cont, RoleT> ContainsRole<RoleT> for TEndFor<IO, Lbl, R, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    R: RoleEq<RoleT>,
+    <R as RoleEq<RoleT>>::Output: types::BoolOr<<Cont as ContainsRole<RoleT>>::Output>,
+    Cont: TSession<IO> + ContainsRole<RoleT>,
+{
+    type Output = types::Or<<R as RoleEq<RoleT>>::Output, <Cont as ContainsRole<RoleT>>::Output>;
+}
+
+impl<IO, Lbl, R, Cont, RoleT> NotContainsRole<RoleT> for TEndFor<IO, Lbl, R, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    R: RoleEq<RoleT, Output = types::False>,
+    Cont: TSession<IO> + NotContainsRole<RoleT>,
+{
+}
+
+// TChoice contains the role if either branch contains it
+impl<IO, Lbl, L, R, RoleT> ContainsRole<RoleT> for TChoice<IO, Lbl, L, R>
+where
+    Lbl: types::ProtocolLabel,
+    L: TSession<IO> + ContainsRole<RoleT>,
+    <L as ContainsRole<RoleT>>::Output: types::Bool,
+    R: TSession<IO> + ContainsRole<RoleT>,
+    <R as ContainsRole<RoleT>>::Output: types::Bool,
+    // The following ensures Or can be used with these types
+    <L as ContainsRole<RoleT>>::Output: types::BoolOr<<R as ContainsRole<RoleT>>::Output>,
+{
+    // True if either branch contains the role
+    type Output = types::Or<<L as ContainsRole<RoleT>>::Output, <R as ContainsRole<RoleT>>::Output>;
+}
+
+// TChoice doesn't contain the role if neither branch contains it
+impl<IO, Lbl, L, R, RoleT> NotContainsRole<RoleT> for TChoice<IO, Lbl, L, R>
+where
+    Lbl: types::ProtocolLabel,
+    L: TSession<IO> + NotContainsRole<RoleT>,
+    R: TSession<IO> + NotContainsRole<RoleT>,
+{
+}
+
+// A branch list contains the role if any of its branches does
+impl<RoleT> ContainsRole<RoleT> for Nil {
+    type Output = types::False;
+}
+
+impl<RoleT> NotContainsRole<RoleT> for Nil {}
+
+impl<H, T, RoleT> ContainsRole<RoleT> for Cons<H, T>
+where
+    H: ContainsRole<RoleT>,
+    T: ContainsRole<RoleT>,
+    <H as ContainsRole<RoleT>>::Output: types::BoolOr<<T as ContainsRole<RoleT>>::Output>,
+{
+    type Output = types::Or<<H as ContainsRole<RoleT>>::Output, <T as ContainsRole<RoleT>>::Output>;
+}
+
+impl<H, T, RoleT> NotContainsRole<RoleT> for Cons<H, T>
+where
+    H: NotContainsRole<RoleT>,
+    T: NotContainsRole<RoleT>,
+{
+}
+
+// TChoiceN contains the role if any branch contains it
+impl<IO, Lbl, Branches, RoleT> ContainsRole<RoleT> for TChoiceN<IO, Lbl, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: SessionList<IO> + ContainsRole<RoleT>,
+{
+    type Output = <Branches as ContainsRole<RoleT>>::Output;
+}
+
+impl<IO, Lbl, Branches, RoleT> NotContainsRole<RoleT> for TChoiceN<IO, Lbl, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: SessionList<IO> + NotContainsRole<RoleT>,
+{
+}
+
+// TSelect contains the role if it is the chooser or any branch contains it
+impl<IO, Lbl, Chooser, Branches, RoleT> ContainsRole<RoleT> for TSelect<IO, Lbl, Chooser, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Chooser: RoleEq<RoleT>,
+    Branches: SessionList<IO> + ContainsRole<RoleT>,
+    <Chooser as RoleEq<RoleT>>::Output: types::BoolOr<<Branches as ContainsRole<RoleT>>::Output>,
+{
+    type Output =
+        types::Or<<Chooser as RoleEq<RoleT>>::Output, <Branches as ContainsRole<RoleT>>::Output>;
+}
+
+impl<IO, Lbl, Chooser, Branches, RoleT> NotContainsRole<RoleT>
+    for TSelect<IO, Lbl, Chooser, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Chooser: RoleEq<RoleT, Output = types::False>,
+    Branches: SessionList<IO> + NotContainsRole<RoleT>,
+{
+}
+
+// TOffer contains the role if it is the offeree or any branch contains it
+impl<IO, Lbl, Offeree, Branches, RoleT> ContainsRole<RoleT> for TOffer<IO, Lbl, Offeree, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Offeree: RoleEq<RoleT>,
+    Branches: SessionList<IO> + ContainsRole<RoleT>,
+    <Offeree as RoleEq<RoleT>>::Output: types::BoolOr<<Branches as ContainsRole<RoleT>>::Output>,
+{
+    type Output =
+        types::Or<<Offeree as RoleEq<RoleT>>::Output, <Branches as ContainsRole<RoleT>>::Output>;
+}
+
+impl<IO, Lbl, Offeree, Branches, RoleT> NotContainsRole<RoleT>
+    for TOffer<IO, Lbl, Offeree, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Offeree: RoleEq<RoleT, Output = types::False>,
+    Branches: SessionList<IO> + NotContainsRole<RoleT>,
+{
+}
+
+// TRec contains the role if its body does
+impl<IO, Lbl, S, RoleT> ContainsRole<RoleT> for TRec<IO, Lbl, S>
+where
+    Lbl: types::ProtocolLabel,
+    S: TSession<IO> + ContainsRole<RoleT>,
+{
+    type Output = <S as ContainsRole<RoleT>>::Output;
+}
+
+impl<IO, Lbl, S, RoleT> NotContainsRole<RoleT> for TRec<IO, Lbl, S>
+where
+    Lbl: types::ProtocolLabel,
+    S: TSession<IO> + NotContainsRole<RoleT>,
+{
+}
+
+// TRecX contains the role if its body does
+impl<IO, Lbl, Var, Body, RoleT> ContainsRole<RoleT> for TRecX<IO, Lbl, Var, Body>
+where
+    Lbl: types::ProtocolLabel,
+    Body: TSession<IO> + ContainsRole<RoleT>,
+{
+    type Output = <Body as ContainsRole<RoleT>>::Output;
+}
+
+impl<IO, Lbl, Var, Body, RoleT> NotContainsRole<RoleT> for TRecX<IO, Lbl, Var, Body>
+where
+    Lbl: types::ProtocolLabel,
+    Body: TSession<IO> + NotContainsRole<RoleT>,
+{
+}
+
+// A jump performs no action of its own
+impl<Var, RoleT> ContainsRole<RoleT> for TVar<Var> {
+    type Output = types::False;
+}
+
+impl<Var, RoleT> NotContainsRole<RoleT> for TVar<Var> {}
+
+// TTimeout contains the role if the body or the timeout handler does
+impl<IO, Lbl, Dur, Body, OnTimeout, RoleT> ContainsRole<RoleT>
+    for TTimeout<IO, Lbl, Dur, Body, OnTimeout>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    Body: TSession<IO> + ContainsRole<RoleT>,
+    OnTimeout: TSession<IO> + ContainsRole<RoleT>,
+    <Body as ContainsRole<RoleT>>::Output:
+        types::BoolOr<<OnTimeout as ContainsRole<RoleT>>::Output>,
+{
+    type Output = types::Or<
+        <Body as ContainsRole<RoleT>>::Output,
+        <OnTimeout as ContainsRole<RoleT>>::Output,
+    >;
+}
+
+impl<IO, Lbl, Dur, Body, OnTimeout, RoleT> NotContainsRole<RoleT>
+    for TTimeout<IO, Lbl, Dur, Body, OnTimeout>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    Body: TSession<IO> + NotContainsRole<RoleT>,
+    OnTimeout: TSession<IO> + NotContainsRole<RoleT>,
+{
+}
+
+// TDelay contains the role if it is the constrained role or appears in the continuation
+impl<IO, Lbl, R, Dur, T, RoleT> ContainsRole<RoleT> for TDelay<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    R: RoleEq<RoleT>,
+    T: TSession<IO> + ContainsRole<RoleT>,
+    <R as RoleEq<RoleT>>::Output: types::BoolOr<<T as ContainsRole<RoleT>>::Output>,
+{
+    type Output = types::Or<<R as RoleEq<RoleT>>::Output, <T as ContainsRole<RoleT>>::Output>;
+}
+
+impl<IO, Lbl, R, Dur, T, RoleT> NotContainsRole<RoleT> for TDelay<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    R: RoleEq<RoleT, Output = types::False>,
+    T: TSession<IO> + NotContainsRole<RoleT>,
+{
+}
+
+// TDeadline contains the role if it is the constrained role or appears in the continuation
+impl<IO, Lbl, R, Dur, T, RoleT> ContainsRole<RoleT> for TDeadline<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    R: RoleEq<RoleT>,
+    T: TSession<IO> + ContainsRole<RoleT>,
+    <R as RoleEq<RoleT>>::Output: types::BoolOr<<T as ContainsRole<RoleT>>::Output>,
+{
+    type Output = types::Or<<R as RoleEq<RoleT>>::Output, <T as ContainsRole<RoleT>>::Output>;
+}
+
+impl<IO, Lbl, R, Dur, T, RoleT> NotContainsRole<RoleT> for TDeadline<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    R: RoleEq<RoleT, Output = types::False>,
+    T: TSession<IO> + NotContainsRole<RoleT>,
+{
+}
+
+// TInterrupt contains the role if it is the interruptor or appears in the scope or handler
+impl<IO, Lbl, Scope, Interruptor, Handler, RoleT> ContainsRole<RoleT>
+    for TInterrupt<IO, Lbl, Scope, Interruptor, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Interruptor: RoleEq<RoleT>,
+    Scope: TSession<IO> + ContainsRole<RoleT>,
+    Handler: TSession<IO> + ContainsRole<RoleT>,
+    <Scope as ContainsRole<RoleT>>::Output: types::BoolOr<<Handler as ContainsRole<RoleT>>::Output>,
+    <Interruptor as RoleEq<RoleT>>::Output: types::BoolOr<
+        types::Or<<Scope as ContainsRole<RoleT>>::Output, <Handler as ContainsRole<RoleT>>::Output>,
+    >,
+{
+    type Output = types::Or<
+        <Interruptor as RoleEq<RoleT>>::Output,
+        types::Or<<Scope as ContainsRole<RoleT>>::Output, <Handler as ContainsRole<RoleT>>::Output>,
+    >;
+}
+
+impl<IO, Lbl, Scope, Interruptor, Handler, RoleT> NotContainsRole<RoleT>
+    for TInterrupt<IO, Lbl, Scope, Interruptor, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Interruptor: RoleEq<RoleT, Output = types::False>,
+    Scope: TSession<IO> + NotContainsRole<RoleT>,
+    Handler: TSession<IO> + NotContainsRole<RoleT>,
+{
+}
+
+// A refinement involves exactly the roles of the refined fragment
+impl<Pred, Inner: ContainsRole<RoleT>, RoleT> ContainsRole<RoleT> for TRefine<Pred, Inner> {
+    type Output = <Inner as ContainsRole<RoleT>>::Output;
+}
+
+impl<Pred, Inner: NotContainsRole<RoleT>, RoleT> NotContainsRole<RoleT> for TRefine<Pred, Inner> {}
+
+impl<Meta, Inner: ContainsRole<RoleT>, RoleT> ContainsRole<RoleT> for TAnnotate<Meta, Inner> {
+    type Output = <Inner as ContainsRole<RoleT>>::Output;
+}
+
+impl<Meta, Inner: NotContainsRole<RoleT>, RoleT> NotContainsRole<RoleT> for TAnnotate<Meta, Inner> {}
+
+// TOptional contains the role if it is the decider or appears in the body or continuation
+impl<IO, Lbl, Decider, Body, Cont, RoleT> ContainsRole<RoleT>
+    for TOptional<IO, Lbl, Decider, Body, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Decider: RoleEq<RoleT>,
+    Body: TSession<IO> + ContainsRole<RoleT>,
+    Cont: TSession<IO> + ContainsRole<RoleT>,
+    <Body as ContainsRole<RoleT>>::Output: types::BoolOr<<Cont as ContainsRole<RoleT>>::Output>,
+    <Decider as RoleEq<RoleT>>::Output: types::BoolOr<
+        types::Or<<Body as ContainsRole<RoleT>>::Output, <Cont as ContainsRole<RoleT>>::Output>,
+    >,
+{
+    type Output = types::Or<
+        <Decider as RoleEq<RoleT>>::Output,
+        types::Or<<Body as ContainsRole<RoleT>>::Output, <Cont as ContainsRole<RoleT>>::Output>,
+    >;
+}
+
+impl<IO, Lbl, Decider, Body, Cont, RoleT> NotContainsRole<RoleT>
+    for TOptional<IO, Lbl, Decider, Body, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Decider: RoleEq<RoleT, Output = types::False>,
+    Body: TSession<IO> + NotContainsRole<RoleT>,
+    Cont: TSession<IO> + NotContainsRole<RoleT>,
+{
+}
+
+// TThrow contains only the role raising the error
+impl<IO, Lbl, R, Err, RoleT> ContainsRole<RoleT> for TThrow<IO, Lbl, R, Err>
+where
+    Lbl: types::ProtocolLabel,
+    R: RoleEq<RoleT>,
+    <R as RoleEq<RoleT>>::Output: types::Bool,
+{
+    type Output = <R as RoleEq<RoleT>>::Output;
+}
+
+impl<IO, Lbl, R, Err, RoleT> NotContainsRole<RoleT> for TThrow<IO, Lbl, R, Err>
+where
+    Lbl: types::ProtocolLabel,
+    R: RoleEq<RoleT, Output = types::False>,
+{
+}
+
+// TCatch contains the role if the body or handler contains it
+impl<IO, Lbl, Body, Handler, RoleT> ContainsRole<RoleT> for TCatch<IO, Lbl, Body, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Body: TSession<IO> + ContainsRole<RoleT>,
+    Handler: TSession<IO> + ContainsRole<RoleT>,
+    <Body as ContainsRole<RoleT>>::Output: types::BoolOr<<Handler as ContainsRole<RoleT>>::Output>,
+{
+    type Output =
+        types::Or<<Body as ContainsRole<RoleT>>::Output, <Handler as ContainsRole<RoleT>>::Output>;
+}
+
+impl<IO, Lbl, Body, Handler, RoleT> NotContainsRole<RoleT> for TCatch<IO, Lbl, Body, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Body: TSession<IO> + NotContainsRole<RoleT>,
+    Handler: TSession<IO> + NotContainsRole<RoleT>,
+{
+}
+
+// TPriorityChoice contains the role if either branch contains it
+impl<IO, Lbl, High, Low, RoleT> ContainsRole<RoleT> for TPriorityChoice<IO, Lbl, High, Low>
+where
+    Lbl: types::ProtocolLabel,
+    High: TSession<IO> + ContainsRole<RoleT>,
+    Low: TSession<IO> + ContainsRole<RoleT>,
+    <High as ContainsRole<RoleT>>::Output: types::BoolOr<<Low as ContainsRole<RoleT>>::Output>,
+{
+    type Output =
+        types::Or<<High as ContainsRole<RoleT>>::Output, <Low as ContainsRole<RoleT>>::Output>;
+}
+
+impl<IO, Lbl, High, Low, RoleT> NotContainsRole<RoleT> for TPriorityChoice<IO, Lbl, High, Low>
+where
+    Lbl: types::ProtocolLabel,
+    High: TSession<IO> + NotContainsRole<RoleT>,
+    Low: TSession<IO> + NotContainsRole<RoleT>,
+{
+}
+
+// TProb contains the role if either branch contains it
+impl<IO, Lbl, WeightL, L, WeightR, R, RoleT> ContainsRole<RoleT>
+    for TProb<IO, Lbl, WeightL, L, WeightR, R>
+where
+    Lbl: types::ProtocolLabel,
+    WeightL: types::WeightMarker,
+    WeightR: types::WeightMarker,
+    L: TSession<IO> + ContainsRole<RoleT>,
+    R: TSession<IO> + ContainsRole<RoleT>,
+    <L as ContainsRole<RoleT>>::Output: types::BoolOr<<R as ContainsRole<RoleT>>::Output>,
+{
+    type Output = types::Or<<L as ContainsRole<RoleT>>::Output, <R as ContainsRole<RoleT>>::Output>;
+}
+
+impl<IO, Lbl, WeightL, L, WeightR, R, RoleT> NotContainsRole<RoleT>
+    for TProb<IO, Lbl, WeightL, L, WeightR, R>
+where
+    Lbl: types::ProtocolLabel,
+    WeightL: types::WeightMarker,
+    WeightR: types::WeightMarker,
+    L: TSession<IO> + NotContainsRole<RoleT>,
+    R: TSession<IO> + NotContainsRole<RoleT>,
+{
+}
+
+// TParN contains the role if any branch contains it
+impl<IO, Lbl, Branches, RoleT> ContainsRole<RoleT> for TParN<IO, Lbl, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: SessionList<IO> + ContainsRole<RoleT>,
+{
+    type Output = <Branches as ContainsRole<RoleT>>::Output;
+}
+
+impl<IO, Lbl, Branches, RoleT> NotContainsRole<RoleT> for TParN<IO, Lbl, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: SessionList<IO> + NotContainsRole<RoleT>,
+{
+}
+
+// TShuffle contains the role if any step contains it
+impl<IO, Lbl, Steps, RoleT> ContainsRole<RoleT> for TShuffle<IO, Lbl, Steps>
+where
+    Lbl: types::ProtocolLabel,
+    Steps: SessionList<IO> + ContainsRole<RoleT>,
+{
+    type Output = <Steps as ContainsRole<RoleT>>::Output;
+}
+
+impl<IO, Lbl, Steps, RoleT> NotContainsRole<RoleT> for TShuffle<IO, Lbl, Steps>
+where
+    Lbl: types::ProtocolLabel,
+    Steps: SessionList<IO> + NotContainsRole<RoleT>,
+{
+}
+
+// Use a single implementation with dispatch on L branch containment
+impl<IO, Lbl, L, R, IsDisjoint, RoleT> ContainsRole<RoleT> for TPar<IO, Lbl, L, R, IsDisjoint>
+where
+    Lbl: types::ProtocolLabel,
+    L: TSession<IO> + ContainsRole<RoleT>,
+    <L as ContainsRole<RoleT>>::Output: types::Bool,
+    R: TSession<IO> + ContainsRole<RoleT>,
+    <R as ContainsRole<RoleT>>::Output: types::Bool,
+    // Use a helper trait to dispatch based on L branch containment
+    (): TParContainsRoleImpl<
+        <L as ContainsRole<RoleT>>::Output,
+        <R as ContainsRole<RoleT>>::Output,
+    >,
+{
+    type Output = <() as TParContainsRoleImpl<
+        <L as ContainsRole<RoleT>>::Output,
+        <R as ContainsRole<RoleT>>::Output,
+    >>::Output;
+}
+
+// TPar doesn't contain the role if neither branch contains it
+impl<IO, Lbl, L, R, IsDisjoint, RoleT> NotContainsRole<RoleT> for TPar<IO, Lbl, L, R, IsDisjoint>
+where
+    Lbl: types::ProtocolLabel,
+    L: TSession<IO> + NotContainsRole<RoleT>,
+    R: TSession<IO> + NotContainsRole<RoleT>,
+{
+}
+
+// Helper trait for TPar role containment logic
+pub trait TParContainsRoleImpl<LContains, RContains> {
+    type Output: types::Bool;
+}
+
+// If either branch contains the role, TPar contains it
+impl TParContainsRoleImpl<types::True, types::True> for () {
+    type Output = types::True;
+}
+
+impl TParContainsRoleImpl<types::True, types::False> for () {
+    type Output = types::True;
+}
+
+impl TParContainsRoleImpl<types::False, types::True> for () {
+    type Output = types::True;
+}
+
+// If neither branch contains the role, TPar doesn't contain it
+impl TParContainsRoleImpl<types::False, types::False> for () {
+    type Output = types::False;
+}
+
+/// Helper trait for projecting a protocol parallel composition.
+///
+/// - `Me`: The role being projected.
+/// - `IO`: Protocol marker type.
+/// - `Lbl`: Label from the TPar construct.
+/// - `L`, `R`: The two protocol branches.
+pub trait ProjectPar<Me, IO, Lbl: types::ProtocolLabel, L: TSession<IO>, R: TSession<IO>> {
+    type Out: EpSession<IO, Me>;
+}
+
+// We've replaced ProjectParBranch with ProjectRoleOrSkip for better label handling
+
+/// Helper trait to project role or create skip with the parent label
+pub trait ProjectRoleOrSkip<Me: Role, IO, G: TSession<IO>, Flag, ParentLbl: types::ProtocolLabel> {
+    type Out: EpSession<IO, Me>;
+}
+
+// If role is in branch, project it
+impl<Me: Role, IO, G: TSession<IO>, Lbl: types::ProtocolLabel> 
+    ProjectRoleOrSkip<Me, IO, G, types::True, Lbl> for ()
+where
+    (): ProjectRole<Me, IO, G>,
+{
+    // Just return the projection directly - no additional wrapping needed
+    type Out = <() as ProjectRole<Me, IO, G>>::Out;
+}
+
+// If role is not in branch, create skip with parent label
+impl<Me: Role, IO, G: TSession<IO>, Lbl: types::ProtocolLabel> 
+    ProjectRoleOrSkip<Me, IO, G, types::False, Lbl> for ()
+{
+    // Create EpSkip with the parent label (this ensures label preservation)
+    type Out = EpSkip<IO, Lbl, Me>;
+}
+
+// Projection implementation for TPar - delegate to ProjectPar helper but pass the label
+impl<Me, IO, Lbl, L, R, IsDisjoint> ProjectRole<Me, IO, TPar<IO, Lbl, L, R, IsDisjoint>> for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+    L: TSession<IO>,
+    R: TSession<IO>,
+    (): ProjectPar<Me, IO, Lbl, L, R>,
+{
+    type Out = <() as ProjectPar<Me, IO, Lbl, L, R>>::Out;
+}
+
+/// Main flag-based composition trait for projected parallel branches
+pub trait ComposeProjectedParBranches<IO, Me: Role, L, R>
+where
+    L: IsEpSkipVariant<IO, Me> + IsEpEndVariant<IO, Me> + EpSession<IO, Me>,
+    R: IsEpSkipVariant<IO, Me> + IsEpEndVariant<IO, Me> + EpSession<IO, Me>,
+    <L as IsEpSkipVariant<IO, Me>>::Output: types::Bool,
+    <R as IsEpSkipVariant<IO, Me>>::Output: types::Bool,
+    <L as IsEpEndVariant<IO, Me>>::Output: types::Bool,
+    <R as IsEpEndVariant<IO, Me>>::Output: types::Bool,
+{
+    type Out: EpSession<IO, Me>;
+}
+
+impl<IO, Me: Role, L, R> ComposeProjectedParBranches<IO, Me, L, R> for ()
+where
+    L: IsEpSkipVariant<IO, Me> + IsEpEndVariant<IO, Me> + EpSession<IO, Me>,
+    R: IsEpSkipVariant<IO, Me> + IsEpEndVariant<IO, Me> + EpSession<IO, Me>,
+    <L as IsEpSkipVariant<IO, Me>>::Output: types::Bool,
+    <R as IsEpSkipVariant<IO, Me>>::Output: types::Bool,
+    <L as IsEpEndVariant<IO, Me>>::Output: types::Bool,
+    <R as IsEpEndVariant<IO, Me>>::Output: types::Bool,
+    (): ComposeProjectedParBranchesCase<
+        IsSkip<L, IO, Me>,
+        IsSkip<R, IO, Me>,
+        IsEnd<L, IO, Me>,
+        IsEnd<R, IO, Me>,
+        IO,
+        Me,
+        L,
+        R,
+    >,
+{
+    type Out = <() as ComposeProjectedParBranchesCase<
+        IsSkip<L, IO, Me>,
+        IsSkip<R, IO, Me>,
+        IsEnd<L, IO, Me>,
+        IsEnd<R, IO, Me>,
+        IO,
+        Me,
+        L,
+        R,
+    >>::Out;
+}
+
+/// Helper trait for case selection in composition of parallel branches
+pub trait ComposeProjectedParBranchesCase<LSkip, RSkip, LEnd, REnd, IO, Me: Role, L, R>
+where
+    L: EpSession<IO, Me>,
+    R: EpSession<IO, Me>,
+{
+    type Out: EpSession<IO, Me>;
+}
+
+/// Type-level check whether role `R` performs the first action of a protocol.
+///
+/// Used to find the role that owns a `TChoice`: the role acting first in
+/// its branches makes the choice and selects, every other role offers. On a
+/// list of branches it holds when the role starts some branch; projection
+/// requires the owner to start every branch, see [`SingleChooser`].
+/// Wrappers such as timers, refinements and recursion binders defer to the
+/// protocol they wrap.
+pub trait ActsFirst<R> {
+    type Output: types::Bool;
+}
+
+impl<RoleT> ActsFirst<RoleT> for Nil {
+    type Output = types::False;
+}
+
+// A list of branches is started by a role acting first in any of them
+impl<H, T, RoleT> ActsFirst<RoleT> for Cons<H, T>
+where
+    H: ActsFirst<RoleT>,
+    T: ActsFirst<RoleT>,
+    <H as ActsFirst<RoleT>>::Output: types::BoolOr<<T as ActsFirst<RoleT>>::Output>,
+{
+    type Output = types::Or<<H as ActsFirst<RoleT>>::Output, <T as ActsFirst<RoleT>>::Output>;
+}
+
+// Nothing happens after the end, so no role acts first
+impl<IO, Lbl, RoleT> ActsFirst<RoleT> for TEnd<IO, Lbl> {
+    type Output = types::False;
+}
+
+impl<IO, Lbl, R, H, T, RoleT> ActsFirst<RoleT> for TInteract<IO, Lbl, R, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: TSession<IO>,
+    R: RoleEq<RoleT>,
+    <R as RoleEq<RoleT>>::Output: types::Bool,
+{
+    type Output = <R as RoleEq<RoleT>>::Output;
+}
+
+impl<IO, Lbl, From, To, H, T, RoleT> ActsFirst<RoleT> for TMsg<IO, Lbl, From, To, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: TSession<IO>,
+    From: RoleEq<RoleT>,
+    <From as RoleEq<RoleT>>::Output: types::Bool,
+{
+    type Output = <From as RoleEq<RoleT>>::Output;
+}
+
+impl<IO, Lbl, From, ToSet, H, T, RoleT> ActsFirst<RoleT> for TBroadcast<IO, Lbl, From, ToSet, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: TSession<IO>,
+    From: RoleEq<RoleT>,
+    <From as RoleEq<RoleT>>::Output: types::Bool,
+{
+    type Output = <From as RoleEq<RoleT>>::Output;
+}
+
+impl<IO, Lbl, From, WorkerSet, H, T, RoleT> ActsFirst<RoleT>
+    for TAnycast<IO, Lbl, From, WorkerSet, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: TSession<IO>,
+    From: RoleEq<RoleT>,
+    <From as RoleEq<RoleT>>::Output: types::Bool,
+{
+    type Output = <From as RoleEq<RoleT>>::Output;
+}
+
+impl<IO, Lbl, From, To, DelegatedEp, T, RoleT> ActsFirst<RoleT>
+    for TDelegate<IO, Lbl, From, To, DelegatedEp, T>
+where
+    Lbl: types::ProtocolLabel,
+    T: TSession<IO>,
+    From: RoleEq<RoleT>,
+    <From as RoleEq<RoleT>>::Output: types::Bool,
+{
+    type Output = <From as RoleEq<RoleT>>::Output;
+}
+
+// The first named role closes or opens the channel
+impl<IO, Lbl, A, B, Cont, RoleT> ActsFirst<RoleT> for TDisconnect<IO, Lbl, A, B, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Cont: TSession<IO>,
+    A: RoleEq<RoleT>,
+    <A as RoleEq<RoleT>>::Output: types::Bool,
+{
+    type Output = <A as RoleEq<RoleT>>::Output;
+}
+
+impl<IO, Lbl, A, B, Cont, RoleT> ActsFirst<RoleT> for TConnect<IO, Lbl, A, B, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Cont: TSession<IO>,
+    A: RoleEq<RoleT>,
+    <A as RoleEq<RoleT>>::Output: types::Bool,
+{
+    type Output = <A as RoleEq<RoleT>>::Output;
+}
+
+// Leaving is not an action; the continuation decides
+impl<IO, Lbl, R, Cont, RoleT> ActsFirst<RoleT> for TEndFor<IO, Lbl, R, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Cont: TSession<IO> + ActsFirst<RoleT>,
+{
+    type Output = <Cont as ActsFirst<RoleT>>::Output;
+}
+
+impl<IO, Lbl, L, R, RoleT> ActsFirst<RoleT> for TChoice<IO, Lbl, L, R>
+where
+    Lbl: types::ProtocolLabel,
+    L: TSession<IO> + ActsFirst<RoleT>,
+    R: TSession<IO> + ActsFirst<RoleT>,
+    <L as ActsFirst<RoleT>>::Output: types::BoolOr<<R as ActsFirst<RoleT>>::Output>,
+{
+    type Output = types::Or<<L as ActsFirst<RoleT>>::Output, <R as ActsFirst<RoleT>>::Output>;
+}
+
+impl<IO, Lbl, Branches, RoleT> ActsFirst<RoleT> for TChoiceN<IO, Lbl, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: SessionList<IO> + ActsFirst<RoleT>,
+{
+    type Output = <Branches as ActsFirst<RoleT>>::Output;
+}
+
+impl<IO, Lbl, Chooser, Branches, RoleT> ActsFirst<RoleT> for TSelect<IO, Lbl, Chooser, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: SessionList<IO>,
+    Chooser: RoleEq<RoleT>,
+    <Chooser as RoleEq<RoleT>>::Output: types::Bool,
+{
+    type Output = <Chooser as RoleEq<RoleT>>::Output;
+}
+
+impl<IO, Lbl, Offeree, Branches, RoleT> ActsFirst<RoleT> for TOffer<IO, Lbl, Offeree, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: SessionList<IO> + ActsFirst<RoleT>,
+{
+    type Output = <Branches as ActsFirst<RoleT>>::Output;
+}
+
+impl<IO, Lbl, High, Low, RoleT> ActsFirst<RoleT> for TPriorityChoice<IO, Lbl, High, Low>
+where
+    Lbl: types::ProtocolLabel,
+    High: TSession<IO> + ActsFirst<RoleT>,
+    Low: TSession<IO> + ActsFirst<RoleT>,
+    <High as ActsFirst<RoleT>>::Output: types::BoolOr<<Low as ActsFirst<RoleT>>::Output>,
+{
+    type Output = types::Or<<High as ActsFirst<RoleT>>::Output, <Low as ActsFirst<RoleT>>::Output>;
+}
+
+impl<IO, Lbl, WeightL, L, WeightR, R, RoleT> ActsFirst<RoleT>
+    for TProb<IO, Lbl, WeightL, L, WeightR, R>
+where
+    Lbl: types::ProtocolLabel,
+    WeightL: types::WeightMarker,
+    WeightR: types::WeightMarker,
+    L: TSession<IO> + ActsFirst<RoleT>,
+    R: TSession<IO> + ActsFirst<RoleT>,
+    <L as ActsFirst<RoleT>>::Output: types::BoolOr<<R as ActsFirst<RoleT>>::Output>,
+{
+    type Output = types::Or<<L as ActsFirst<RoleT>>::Output, <R as ActsFirst<RoleT>>::Output>;
+}
+
+impl<IO, Lbl, Decider, Body, Cont, RoleT> ActsFirst<RoleT>
+    for TOptional<IO, Lbl, Decider, Body, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Body: TSession<IO>,
+    Cont: TSession<IO>,
+    Decider: RoleEq<RoleT>,
+    <Decider as RoleEq<RoleT>>::Output: types::Bool,
+{
+    type Output = <Decider as RoleEq<RoleT>>::Output;
+}
+
+impl<IO, Lbl, L, R, IsDisjoint, RoleT> ActsFirst<RoleT> for TPar<IO, Lbl, L, R, IsDisjoint>
+where
+    Lbl: types::ProtocolLabel,
+    L: TSession<IO> + ActsFirst<RoleT>,
+    R: TSession<IO> + ActsFirst<RoleT>,
+    <L as ActsFirst<RoleT>>::Output: types::BoolOr<<R as ActsFirst<RoleT>>::Output>,
+{
+    type Output = types::Or<<L as ActsFirst<RoleT>>::Output, <R as ActsFirst<RoleT>>::Output>;
+}
+
+impl<IO, Lbl, Branches, RoleT> ActsFirst<RoleT> for TParN<IO, Lbl, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: SessionList<IO> + ActsFirst<RoleT>,
+{
+    type Output = <Branches as ActsFirst<RoleT>>::Output;
+}
+
+impl<IO, Lbl, Steps, RoleT> ActsFirst<RoleT> for TShuffle<IO, Lbl, Steps>
+where
+    Lbl: types::ProtocolLabel,
+    Steps: SessionList<IO> + ActsFirst<RoleT>,
+{
+    type Output = <Steps as ActsFirst<RoleT>>::Output;
+}
+
+impl<IO, Lbl, S, RoleT> ActsFirst<RoleT> for TRec<IO, Lbl, S>
+where
+    Lbl: types::ProtocolLabel,
+    S: TSession<IO> + ActsFirst<RoleT>,
+{
+    type Output = <S as ActsFirst<RoleT>>::Output;
+}
+
+impl<IO, Lbl, Var, Body, RoleT> ActsFirst<RoleT> for TRecX<IO, Lbl, Var, Body>
+where
+    Lbl: types::ProtocolLabel,
+    Body: TSession<IO> + ActsFirst<RoleT>,
+{
+    type Output = <Body as ActsFirst<RoleT>>::Output;
+}
+
+// Jumping back is not an action
+impl<Var, RoleT> ActsFirst<RoleT> for TVar<Var> {
+    type Output = types::False;
+}
+
+// Timers and scheduling constraints are not actions; the guarded protocol decides
+impl<IO, Lbl, Dur, Body, OnTimeout, RoleT> ActsFirst<RoleT>
+    for TTimeout<IO, Lbl, Dur, Body, OnTimeout>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    OnTimeout: TSession<IO>,
+    Body: TSession<IO> + ActsFirst<RoleT>,
+{
+    type Output = <Body as ActsFirst<RoleT>>::Output;
+}
+
+impl<IO, Lbl, R, Dur, T, RoleT> ActsFirst<RoleT> for TDelay<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: TSession<IO> + ActsFirst<RoleT>,
+{
+    type Output = <T as ActsFirst<RoleT>>::Output;
+}
+
+impl<IO, Lbl, R, Dur, T, RoleT> ActsFirst<RoleT> for TDeadline<IO, Lbl, R, Dur, T>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    T: TSession<IO> + ActsFirst<RoleT>,
+{
+    type Output = <T as ActsFirst<RoleT>>::Output;
+}
+
+impl<IO, Lbl, Scope, Interruptor, Handler, RoleT> ActsFirst<RoleT>
+    for TInterrupt<IO, Lbl, Scope, Interruptor, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Handler: TSession<IO>,
+    Scope: TSession<IO> + ActsFirst<RoleT>,
+{
+    type Output = <Scope as ActsFirst<RoleT>>::Output;
+}
+
+impl<Pred, Inner, RoleT> ActsFirst<RoleT> for TRefine<Pred, Inner>
+where
+    Inner: ActsFirst<RoleT>,
+{
+    type Output = <Inner as ActsFirst<RoleT>>::Output;
+}
+
+impl<Meta, Inner, RoleT> ActsFirst<RoleT> for TAnnotate<Meta, Inner>
+where
+    Inner: ActsFirst<RoleT>,
+{
+    type Output = <Inner as ActsFirst<RoleT>>::Output;
+}
+
+impl<IO, Lbl, R, Err, RoleT> ActsFirst<RoleT> for TThrow<IO, Lbl, R, Err>
+where
+    Lbl: types::ProtocolLabel,
+    R: RoleEq<RoleT>,
+    <R as RoleEq<RoleT>>::Output: types::Bool,
+{
+    type Output = <R as RoleEq<RoleT>>::Output;
+}
+
+impl<IO, Lbl, Body, Handler, RoleT> ActsFirst<RoleT> for TCatch<IO, Lbl, Body, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Handler: TSession<IO>,
+    Body: TSession<IO> + ActsFirst<RoleT>,
+{
+    type Output = <Body as ActsFirst<RoleT>>::Output;
+}
+/// Type-level check whether some role of a type-level list performs the
+/// first action of the protocol `G`.
+///
+/// A protocol no role starts, such as a bare `TEnd`, is silent.
+pub trait AnyActsFirst<G> {
+    type Output: types::Bool;
+}
+
+impl<G> AnyActsFirst<G> for Nil {
+    type Output = types::False;
+}
+
+impl<G, X, T> AnyActsFirst<G> for Cons<X, T>
+where
+    G: ActsFirst<X>,
+    T: AnyActsFirst<G>,
+    <G as ActsFirst<X>>::Output: types::BoolOr<<T as AnyActsFirst<G>>::Output>,
+{
+    type Output = types::Or<<G as ActsFirst<X>>::Output, <T as AnyActsFirst<G>>::Output>;
+}
+
+/// Failure marker: `R` starts some branches of the choice labelled `Lbl`
+/// but another role starts a different branch, so no single role owns it.
+pub struct MixedChoosers<R, Lbl>(PhantomData<(R, Lbl)>);
+
+/// Never implemented: a bound `Marker: ChooserFailure` fails and names the
+/// choice found by [`SingleChooser`] together with the offending role.
+#[diagnostic::on_unimplemented(
+    message = "the choice has no single owner: `{Self}`",
+    label = "the branches of this choice are started by different roles",
+    note = "start every branch of a choice with an action of the role that decides it"
+)]
+pub trait ChooserFailure {}
+
+/// The branches of the choice labelled `Lbl` are all started by the role that
+/// owns it: a role acting first in one branch acts first in every branch
+/// that starts with an action. A branch with no action, such as the `TEnd`
+/// closing a `tchoice!`, does not count.
+///
+/// Projection, deadlock freedom, progress and orphan freedom require it of
+/// every `TChoice`, and projection of a tagged `TChoiceN`.
+pub trait SingleChooser<Lbl> {}
+
+impl<Lbl, Branches> SingleChooser<Lbl> for Branches
+where
+    Branches: RolesOfEach,
+    <Branches as RolesOfEach>::Roles:
+        ChoosersAgree<Lbl, Branches, <Branches as RolesOfEach>::Roles>,
+{
+}
+
+/// Every role of a type-level list that starts some branch of `Branches`
+/// starts every branch that any of `Roles` starts.
+pub trait ChoosersAgree<Lbl, Branches, Roles> {}
+
+impl<Lbl, Branches, Roles> ChoosersAgree<Lbl, Branches, Roles> for Nil {}
+
+impl<Lbl, Branches, Roles, X, T> ChoosersAgree<Lbl, Branches, Roles> for Cons<X, T>
+where
+    Branches: ActsFirst<X>,
+    X: ChoosersAgreeCase<Lbl, Branches, Roles, <Branches as ActsFirst<X>>::Output>,
+    T: ChoosersAgree<Lbl, Branches, Roles>,
+{
+}
+
+/// Helper trait for `ChoosersAgree`, dispatching on whether the role starts
+/// some branch.
+pub trait ChoosersAgreeCase<Lbl, Branches, Roles, StartsSome> {}
+
+impl<X, Lbl, Branches, Roles> ChoosersAgreeCase<Lbl, Branches, Roles, types::False> for X {}
+
+impl<X, Lbl, Branches, Roles> ChoosersAgreeCase<Lbl, Branches, Roles, types::True> for X where
+    Branches: StartedByUnlessSilent<X, Lbl, Roles>
+{
+}
+
+/// Every branch of a type-level list that any of `Roles` starts is started
+/// by `X`.
+pub trait StartedByUnlessSilent<X, Lbl, Roles> {}
+
+impl<X, Lbl, Roles> StartedByUnlessSilent<X, Lbl, Roles> for Nil {}
+
+impl<X, Lbl, Roles, H, T> StartedByUnlessSilent<X, Lbl, Roles> for Cons<H, T>
+where
+    H: ActsFirst<X>,
+    Roles: AnyActsFirst<H>,
+    X: SingleChooserCase<Lbl, <H as ActsFirst<X>>::Output, <Roles as AnyActsFirst<H>>::Output>,
+    T: StartedByUnlessSilent<X, Lbl, Roles>,
+{
+}
+
+/// Helper trait for `StartedByUnlessSilent`, dispatching on whether the role
+/// starts a branch and whether any role does.
+pub trait SingleChooserCase<Lbl, StartsBranch, BranchStarted> {}
+
+impl<X, Lbl, BranchStarted> SingleChooserCase<Lbl, types::True, BranchStarted> for X {}
+
+// A branch with no action
+impl<X, Lbl> SingleChooserCase<Lbl, types::False, types::False> for X {}
+
+impl<X, Lbl> SingleChooserCase<Lbl, types::False, types::True> for X where
+    MixedChoosers<X, Lbl>: ChooserFailure
+{
+}
+
+// Extract the GetProtocolLabel trait implementation here
+pub trait GetProtocolLabel {
+    type Label: types::ProtocolLabel;
+}
+
+// Add implementation for TInteract
+impl<IO, Lbl: types::ProtocolLabel, R, H, T: TSession<IO>> GetProtocolLabel for TInteract<IO, Lbl, R, H, T> {
+    type Label = Lbl;
+}
+
+// Add implementation for TMsg
+impl<IO, Lbl: types::ProtocolLabel, From, To, H, T: TSession<IO>> GetProtocolLabel
+    for TMsg<IO, Lbl, From, To, H, T>
+{
+    type Label = Lbl;
+}
+
+// Add implementation for TChoice
+impl<IO, Lbl: types::ProtocolLabel, L: TSession<IO>, R: TSession<IO>> GetProtocolLabel for TChoice<IO, Lbl, L, R> {
+    type Label = Lbl;
+}
+
+// Add implementation for TChoiceN
+impl<IO, Lbl: types::ProtocolLabel, Branches: SessionList<IO>> GetProtocolLabel
+    for TChoiceN<IO, Lbl, Branches>
+{
+    type Label = Lbl;
+}
+
+// Add implementation for TPar
+impl<IO, Lbl: types::ProtocolLabel, L: TSession<IO>, R: TSession<IO>, IsDisjoint> GetProtocolLabel for TPar<IO, Lbl, L, R, IsDisjoint> {
+    type Label = Lbl;
+}
+
+// Add implementation for TSelect
+impl<IO, Lbl: types::ProtocolLabel, Chooser, Branches: SessionList<IO>> GetProtocolLabel
+    for TSelect<IO, Lbl, Chooser, Branches>
+{
+    type Label = Lbl;
+}
+
+// Add implementation for TOffer
+impl<IO, Lbl: types::ProtocolLabel, Offeree, Branches: SessionList<IO>> GetProtocolLabel
+    for TOffer<IO, Lbl, Offeree, Branches>
+{
+    type Label = Lbl;
+}
+
+// Add implementation for TParN
+impl<IO, Lbl: types::ProtocolLabel, Branches: SessionList<IO>> GetProtocolLabel
+    for TParN<IO, Lbl, Branches>
+{
+    type Label = Lbl;
+}
+
+// Add implementation for TShuffle
+impl<IO, Lbl: types::ProtocolLabel, Steps: SessionList<IO>> GetProtocolLabel
+    for TShuffle<IO, Lbl, Steps>
+{
+    type Label = Lbl;
+}
+
+// Add implementation for TRecX
+impl<IO, Lbl: types::ProtocolLabel, Var, Body: TSession<IO>> GetProtocolLabel
+    for TRecX<IO, Lbl, Var, Body>
+{
+    type Label = Lbl;
+}
+
+// Add implementation for TRec
+impl<IO, Lbl: types::ProtocolLabel, S: TSession<IO>> GetProtocolLabel for TRec<IO, Lbl, S> {
+    type Label = Lbl;
+}
+
+// Add implementation for TVar (jumps carry no label of their own)
+impl<Var> GetProtocolLabel for TVar<Var> {
+    type Label = types::EmptyLabel;
+}
+
+// Add implementation for TTimeout
+impl<IO, Lbl, Dur, Body, OnTimeout> GetProtocolLabel for TTimeout<IO, Lbl, Dur, Body, OnTimeout>
+where
+    Lbl: types::ProtocolLabel,
+    Dur: types::DurationMarker,
+    Body: TSession<IO>,
+    OnTimeout: TSession<IO>,
+{
+    type Label = Lbl;
+}
+
+// Add implementation for TDelay
+impl<IO, Lbl: types::ProtocolLabel, R, Dur: types::DurationMarker, T: TSession<IO>> GetProtocolLabel
+    for TDelay<IO, Lbl, R, Dur, T>
+{
+    type Label = Lbl;
+}
+
+// Add implementation for TDeadline
+impl<IO, Lbl: types::ProtocolLabel, R, Dur: types::DurationMarker, T: TSession<IO>> GetProtocolLabel
+    for TDeadline<IO, Lbl, R, Dur, T>
+{
+    type Label = Lbl;
+}
+
+// Add implementation for TInterrupt
+impl<IO, Lbl, Scope, Interruptor, Handler> GetProtocolLabel
+    for TInterrupt<IO, Lbl, Scope, Interruptor, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Scope: TSession<IO>,
+    Handler: TSession<IO>,
+{
+    type Label = Lbl;
+}
+
+// Add implementation for TBroadcast
+impl<IO, Lbl: types::ProtocolLabel, From, ToSet, H, T: TSession<IO>> GetProtocolLabel
+    for TBroadcast<IO, Lbl, From, ToSet, H, T>
+{
+    type Label = Lbl;
+}
+
+// Add implementation for TAnycast
+impl<IO, Lbl: types::ProtocolLabel, From, WorkerSet, H, T: TSession<IO>> GetProtocolLabel
+    for TAnycast<IO, Lbl, From, WorkerSet, H, T>
+{
+    type Label = Lbl;
+}
+
+// Add implementation for TDelegate
+impl<IO, Lbl: types::ProtocolLabel, From, To, DelegatedEp, T: TSession<IO>> GetProtocolLabel
+    for TDelegate<IO, Lbl, From, To, DelegatedEp, T>
+{
+    type Label = Lbl;
+}
+
+// Add implementation for TRefine
+impl<Pred, Inner: GetProtocolLabel> GetProtocolLabel for TRefine<Pred, Inner> {
+    type Label = <Inner as GetProtocolLabel>::Label;
+}
+
+// Add implementation for TAnnotate
+impl<Meta, Inner: GetProtocolLabel> GetProtocolLabel for TAnnotate<Meta, Inner> {
+    type Label = <Inner as GetProtocolLabel>::Label;
+}
+
+// Add implementation for TOptional
+impl<IO, Lbl: types::ProtocolLabel, Decider, Body: TSession<IO>, Cont: TSession<IO>>
+    GetProtocolLabel for TOptional<IO, Lbl, Decider, Body, Cont>
+{
+    type Label = Lbl;
+}
+
+// Add implementation for TThrow
+impl<IO, Lbl: types::ProtocolLabel, R, Err> GetProtocolLabel for TThrow<IO, Lbl, R, Err> {
+    type Label = Lbl;
+}
+
+// Add implementation for TCatch
+impl<IO, Lbl: types::ProtocolLabel, Body: TSession<IO>, Handler: TSession<IO>> GetProtocolLabel
+    for TCatch<IO, Lbl, Body, Handler>
+{
+    type Label = Lbl;
+}
+
+// Add implementation for TPriorityChoice
+impl<IO, Lbl: types::ProtocolLabel, High: TSession<IO>, Low: TSession<IO>> GetProtocolLabel
+    for TPriorityChoice<IO, Lbl, High, Low>
+{
+    type Label = Lbl;
+}
+
+// Add implementation for TProb
+impl<IO, Lbl, WeightL, L, WeightR, R> GetProtocolLabel for TProb<IO, Lbl, WeightL, L, WeightR, R>
+where
+    Lbl: types::ProtocolLabel,
+    WeightL: types::WeightMarker,
+    WeightR: types::WeightMarker,
+    L: TSession<IO>,
+    R: TSession<IO>,
+{
+    type Label = Lbl;
+}
+
+// Add implementation for TDisconnect
+impl<IO, Lbl: types::ProtocolLabel, A, B, Cont: TSession<IO>> GetProtocolLabel
+    for TDisconnect<IO, Lbl, A, B, Cont>
+{
+    type Label = Lbl;
+}
+
+// Add implementation for TConnect
+impl<IO, Lbl: types::ProtocolLabel, A, B, Cont: TSession<IO>> GetProtocolLabel
+    for TConnect<IO, Lbl, A, B, Cont>
+{
+    type Label = Lbl;
+}
+
+// Add implementation for TEndFor
+impl<IO, Lbl: types::ProtocolLabel, R, Cont: TSession<IO>> GetProtocolLabel
+    for TEndFor<IO, Lbl, R, Cont>
+{
+    type Label = Lbl;
+}
+
+// Add implementation for TEnd
+impl<IO, Lbl: types::ProtocolLabel> GetProtocolLabel for TEnd<IO, Lbl> {
+    type Label = Lbl;
+}
+
+// Both branches are EpSkip
+impl<IO, Me: Role, Lbl1: types::ProtocolLabel, Lbl2: types::ProtocolLabel>
+    ComposeProjectedParBranchesCase<
+        types::True,
+        types::True,
+        types::False,
+        types::False,
+        IO,
+        Me,
+        EpSkip<IO, Lbl1, Me>,
+        EpSkip<IO, Lbl2, Me>,
+    > for ()
+{
+    // If both branches are skipped, output a skip
+    type Out = EpSkip<IO, Lbl1, Me>; // Use label from first branch
+}
+
+// Left is EpSkip, right is projected
+impl<IO, Me: Role, Lbl: types::ProtocolLabel, ProjectedR: EpSession<IO, Me>>
+    ComposeProjectedParBranchesCase<
+        types::True,
+        types::False,
+        types::False,
+        types::False,
+        IO,
+        Me,
+        EpSkip<IO, Lbl, Me>,
+        ProjectedR,
+    > for ()
+{
+    type Out = ProjectedR;
+}
+
+// Left is projected, right is EpSkip
+impl<IO, Me: Role, Lbl: types::ProtocolLabel, ProjectedL: EpSession<IO, Me>>
+    ComposeProjectedParBranchesCase<
+        types::False,
+        types::True,
+        types::False,
+        types::False,
+        IO,
+        Me,
+        ProjectedL,
+        EpSkip<IO, Lbl, Me>,
+    > for ()
+{
+    // Return the non-skip branch directly
+    // This matches test_preserved_label_in_parallel expectations
+    type Out = ProjectedL;
+}
+
+// Both are projected (non-skip, non-end)
+impl<IO, Me: Role, ProjectedL: EpSession<IO, Me>, ProjectedR: EpSession<IO, Me>>
+    ComposeProjectedParBranchesCase<
+        types::False,
+        types::False,
+        types::False,
+        types::False,
+        IO,
+        Me,
+        ProjectedL,
+        ProjectedR,
+    > for ()
+where
+    ProjectedL: GetLocalLabel, // Add constraint to extract label
+{
+    type Out = EpPar<IO, <ProjectedL as GetLocalLabel>::Label, Me, ProjectedL, ProjectedR>;
+}
+
+// Left is EpEnd, right is EpSkip
+impl<IO, Me: Role, Lbl1: types::ProtocolLabel, Lbl2: types::ProtocolLabel>
+    ComposeProjectedParBranchesCase<
+        types::False,
+        types::True,
+        types::True,
+        types::False,
+        IO,
+        Me,
+        EpEnd<IO, Lbl1, Me>,
+        EpSkip<IO, Lbl2, Me>,
+    > for ()
+{
+    type Out = EpEnd<IO, Lbl1, Me>; // Preserve the label from EpEnd
+}
+
+// Left is EpSkip, right is EpEnd
+impl<IO, Me: Role, Lbl1: types::ProtocolLabel, Lbl2: types::ProtocolLabel>
+    ComposeProjectedParBranchesCase<
+        types::True,
+        types::False,
+        types::False,
+        types::True,
+        IO,
+        Me,
+        EpSkip<IO, Lbl1, Me>,
+        EpEnd<IO, Lbl2, Me>,
+    > for ()
+{
+    type Out = EpEnd<IO, Lbl2, Me>; // Preserve the label from EpEnd
+}
+
+// Left is EpEnd, right is projected
+impl<IO, Me: Role, Lbl: types::ProtocolLabel, ProjectedR: EpSession<IO, Me>>
+    ComposeProjectedParBranchesCase<
+        types::False,
+        types::False,
+        types::True,
+        types::False,
+        IO,
+        Me,
+        EpEnd<IO, Lbl, Me>,
+        ProjectedR,
+    > for ()
+{
+    type Out = ProjectedR;
+}
+
+// Left is projected, right is EpEnd
+impl<IO, Me: Role, Lbl: types::ProtocolLabel, ProjectedL: EpSession<IO, Me>>
+    ComposeProjectedParBranchesCase<
+        types::False,
+        types::False,
+        types::False,
+        types::True,
+        IO,
+        Me,
+        ProjectedL,
+        EpEnd<IO, Lbl, Me>,
+    > for ()
+{
+    type Out = ProjectedL;
+}
+
+// Both are EpEnd
+impl<IO, Me: Role, Lbl1: types::ProtocolLabel, Lbl2: types::ProtocolLabel>
+    ComposeProjectedParBranchesCase<
+        types::False,
+        types::False,
+        types::True,
+        types::True,
+        IO,
+        Me,
+        EpEnd<IO, Lbl1, Me>,
+        EpEnd<IO, Lbl2, Me>,
+    > for ()
+{
+    type Out = EpEnd<IO, Lbl1, Me>; // Use label from first branch
+}
+
+// Extract labels from local endpoint types
+pub trait GetLocalLabel {
+    type Label: types::ProtocolLabel;
+}
+
+// Implementations for different endpoint types
+impl<IO, Lbl: types::ProtocolLabel, R, H, T> GetLocalLabel for EpSend<IO, Lbl, R, H, T> {
+    type Label = Lbl;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R, H, T> GetLocalLabel for EpRecv<IO, Lbl, R, H, T> {
+    type Label = Lbl;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches> GetLocalLabel
+    for EpChoiceN<IO, Lbl, Me, Branches>
+{
+    type Label = Lbl;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, L, R> GetLocalLabel for EpPar<IO, Lbl, Me, L, R> {
+    type Label = Lbl;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches> GetLocalLabel
+    for EpSelect<IO, Lbl, Me, Branches>
+{
+    type Label = Lbl;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches> GetLocalLabel for EpOffer<IO, Lbl, Me, Branches> {
+    type Label = Lbl;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches> GetLocalLabel for EpParN<IO, Lbl, Me, Branches> {
+    type Label = Lbl;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Scope, Handler> GetLocalLabel
+    for EpInterrupt<IO, Lbl, Me, Scope, Handler>
+{
+    type Label = Lbl;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Scope, Handler> GetLocalLabel
+    for EpInterruptible<IO, Lbl, Me, Scope, Handler>
+{
+    type Label = Lbl;
+}
+
+impl<Pred, Inner: GetLocalLabel> GetLocalLabel for EpRefine<Pred, Inner> {
+    type Label = <Inner as GetLocalLabel>::Label;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Body, Cont> GetLocalLabel
+    for EpOptionalSelect<IO, Lbl, Me, Body, Cont>
+{
+    type Label = Lbl;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Body, Cont> GetLocalLabel
+    for EpOptionalOffer<IO, Lbl, Me, Body, Cont>
+{
+    type Label = Lbl;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, High, Low> GetLocalLabel
+    for EpPriorityOffer<IO, Lbl, Me, High, Low>
+{
+    type Label = Lbl;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Peer, Cont> GetLocalLabel
+    for EpClose<IO, Lbl, Me, Peer, Cont>
+{
+    type Label = Lbl;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Peer, Cont> GetLocalLabel
+    for EpOpen<IO, Lbl, Me, Peer, Cont>
+{
+    type Label = Lbl;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Steps> GetLocalLabel for EpShuffle<IO, Lbl, Me, Steps> {
+    type Label = Lbl;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Var, Body> GetLocalLabel for EpRec<IO, Lbl, Var, Body> {
+    type Label = Lbl;
+}
+
+// A loop-back carries no label of its own
+impl<Var> GetLocalLabel for EpVar<Var> {
+    type Label = types::EmptyLabel;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Err> GetLocalLabel for EpThrow<IO, Lbl, Me, Err> {
+    type Label = Lbl;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Body, Handler> GetLocalLabel
+    for EpCatch<IO, Lbl, Me, Body, Handler>
+{
+    type Label = Lbl;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Dur, Body, OnTimeout> GetLocalLabel
+    for EpTimeout<IO, Lbl, Me, Dur, Body, OnTimeout>
+{
+    type Label = Lbl;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Dur, T> GetLocalLabel for EpDelay<IO, Lbl, Me, Dur, T> {
+    type Label = Lbl;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Dur, T> GetLocalLabel for EpDeadline<IO, Lbl, Me, Dur, T> {
+    type Label = Lbl;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, DelegatedEp, Cont> GetLocalLabel
+    for EpDelegate<IO, Lbl, Me, DelegatedEp, Cont>
+{
+    type Label = Lbl;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, ToSet, H, T> GetLocalLabel
+    for EpBroadcast<IO, Lbl, Me, ToSet, H, T>
+{
+    type Label = Lbl;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, FromSet, H, T> GetLocalLabel
+    for EpGather<IO, Lbl, Me, FromSet, H, T>
+{
+    type Label = Lbl;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R> GetLocalLabel for EpEnd<IO, Lbl, R> {
+    type Label = Lbl;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R> GetLocalLabel for EpSkip<IO, Lbl, R> {
+    type Label = Lbl;
+}
+
+/// The tags of a local choice: the labels its branches start with, in order.
+///
+/// A runtime sends or matches one of these labels on the wire and looks the
+/// branch up with [`FindBranch`](crate::FindBranch) instead of relying on its
+/// position. Projecting a `TChoiceN` whose branches start with distinct labels
+/// gives an `EpSelect` or `EpOffer` whose tags are those labels.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Get; impl ProtocolLabel for Get {}
+/// struct Put; impl ProtocolLabel for Put {}
+/// struct Pick; impl ProtocolLabel for Pick {}
+/// type End = EpEnd<Http, EmptyLabel, TServer>;
+/// type Server = EpOffer<Http, Pick, TServer, tlist!(
+///     EpRecv<Http, Get, TServer, Message, End>,
+///     EpRecv<Http, Put, TServer, Message, End>,
+/// )>;
+/// assert_type_eq!(<Server as BranchTags>::Tags, tlist!(Get, Put));
+/// ```
+pub trait BranchTags {
+    type Tags;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches: BranchLabels> BranchTags
+    for EpOffer<IO, Lbl, Me, Branches>
+{
+    type Tags = <Branches as BranchLabels>::Labels;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches: BranchLabels> BranchTags
+    for EpSelect<IO, Lbl, Me, Branches>
+{
+    type Tags = <Branches as BranchLabels>::Labels;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches: BranchLabels> BranchTags
+    for EpChoiceN<IO, Lbl, Me, Branches>
+{
+    type Tags = <Branches as BranchLabels>::Labels;
+}
+
+/// The labels a type-level list of local branches start with.
+pub trait BranchLabels {
+    type Labels;
+}
+
+impl BranchLabels for Nil {
+    type Labels = Nil;
+}
+
+impl<H: GetLocalLabel, T: BranchLabels> BranchLabels for Cons<H, T> {
+    type Labels = Cons<<H as GetLocalLabel>::Label, <T as BranchLabels>::Labels>;
+}
+
+/// Extracts the role a local session type is written for.
+///
+/// Used where a local type appears as data, e.g. the endpoint handed over by
+/// `TDelegate`. `EpVar` has no role of its own and does not implement it.
+pub trait GetLocalRole {
+    type Role;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R, H, T> GetLocalRole for EpSend<IO, Lbl, R, H, T> {
+    type Role = R;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R, H, T> GetLocalRole for EpRecv<IO, Lbl, R, H, T> {
+    type Role = R;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches> GetLocalRole
+    for EpChoiceN<IO, Lbl, Me, Branches>
+{
+    type Role = Me;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, L, R> GetLocalRole for EpPar<IO, Lbl, Me, L, R> {
+    type Role = Me;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches> GetLocalRole for EpSelect<IO, Lbl, Me, Branches> {
+    type Role = Me;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches> GetLocalRole for EpOffer<IO, Lbl, Me, Branches> {
+    type Role = Me;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches> GetLocalRole for EpParN<IO, Lbl, Me, Branches> {
+    type Role = Me;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Dur, Body, OnTimeout> GetLocalRole
+    for EpTimeout<IO, Lbl, Me, Dur, Body, OnTimeout>
+{
+    type Role = Me;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Dur, T> GetLocalRole for EpDelay<IO, Lbl, Me, Dur, T> {
+    type Role = Me;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Dur, T> GetLocalRole for EpDeadline<IO, Lbl, Me, Dur, T> {
+    type Role = Me;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Scope, Handler> GetLocalRole
+    for EpInterrupt<IO, Lbl, Me, Scope, Handler>
+{
+    type Role = Me;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Scope, Handler> GetLocalRole
+    for EpInterruptible<IO, Lbl, Me, Scope, Handler>
+{
+    type Role = Me;
+}
+
+impl<Pred, Inner: GetLocalRole> GetLocalRole for EpRefine<Pred, Inner> {
+    type Role = <Inner as GetLocalRole>::Role;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Body, Cont> GetLocalRole
+    for EpOptionalSelect<IO, Lbl, Me, Body, Cont>
+{
+    type Role = Me;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Body, Cont> GetLocalRole
+    for EpOptionalOffer<IO, Lbl, Me, Body, Cont>
+{
+    type Role = Me;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, High, Low> GetLocalRole
+    for EpPriorityOffer<IO, Lbl, Me, High, Low>
+{
+    type Role = Me;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Peer, Cont> GetLocalRole
+    for EpClose<IO, Lbl, Me, Peer, Cont>
+{
+    type Role = Me;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Peer, Cont> GetLocalRole
+    for EpOpen<IO, Lbl, Me, Peer, Cont>
+{
+    type Role = Me;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Steps> GetLocalRole for EpShuffle<IO, Lbl, Me, Steps> {
+    type Role = Me;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Err> GetLocalRole for EpThrow<IO, Lbl, Me, Err> {
+    type Role = Me;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Body, Handler> GetLocalRole
+    for EpCatch<IO, Lbl, Me, Body, Handler>
+{
+    type Role = Me;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, DelegatedEp, Cont> GetLocalRole
+    for EpDelegate<IO, Lbl, Me, DelegatedEp, Cont>
+{
+    type Role = Me;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, ToSet, H, T> GetLocalRole
+    for EpBroadcast<IO, Lbl, Me, ToSet, H, T>
+{
+    type Role = Me;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, FromSet, H, T> GetLocalRole
+    for EpGather<IO, Lbl, Me, FromSet, H, T>
+{
+    type Role = Me;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R> GetLocalRole for EpEnd<IO, Lbl, R> {
+    type Role = R;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R> GetLocalRole for EpSkip<IO, Lbl, R> {
+    type Role = R;
+}
+
+/// Type-level filter that removes all EpSkip<IO, Me> branches from a type-level list.
+///
+/// Branches are classified with [`IsSkip`], like in
+/// [`ComposeProjectedParBranches`], so `EpSkip` is the only endpoint dropped.
+pub trait FilterSkips<IO, Me: Role, List> {
+    type Out;
+}
+
+impl<IO, Me: Role> FilterSkips<IO, Me, Nil> for () {
+    type Out = Nil;
+}
+
+impl<IO, Me: Role, H, T> FilterSkips<IO, Me, Cons<H, T>> for ()
+where
+    H: IsEpSkipVariant<IO, Me> + EpSession<IO, Me>,
+    (): FilterSkipsCase<IO, Me, H, T, IsSkip<H, IO, Me>>,
+{
+    type Out = <() as FilterSkipsCase<IO, Me, H, T, IsSkip<H, IO, Me>>>::Out;
+}
+
+/// Helper trait for non-overlapping dispatch in FilterSkips
+pub trait FilterSkipsCase<IO, Me: Role, H, T, HeadIsSkip> {
+    type Out;
+}
+
+// Case: Head is EpSkip – skip it
+impl<IO, Me: Role, Lbl: types::ProtocolLabel, T>
+    FilterSkipsCase<IO, Me, EpSkip<IO, Lbl, Me>, T, types::True> for ()
+where
+    (): FilterSkips<IO, Me, T>,
+{
+    type Out = <() as FilterSkips<IO, Me, T>>::Out;
+}
+
+// Case: Head is not EpSkip – keep it
+impl<IO, Me: Role, H, T> FilterSkipsCase<IO, Me, H, T, types::False> for ()
+where
+    H: EpSession<IO, Me>,
+    (): FilterSkips<IO, Me, T>,
+{
+    type Out = Cons<H, <() as FilterSkips<IO, Me, T>>::Out>;
+}
+
+// Implement ProjectPar by dispatching to a helper trait for case-specific behavior
+impl<Me, IO, Lbl, L, R> ProjectPar<Me, IO, Lbl, L, R> for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+    L: TSession<IO>,
+    R: TSession<IO>,
+    // Determine if branches contain the role
+    L: ContainsRole<Me>,
+    <L as ContainsRole<Me>>::Output: types::Bool,
+    R: ContainsRole<Me>,
+    <R as ContainsRole<Me>>::Output: types::Bool,
+    // Use a helper trait to handle case-specific projection
+    (): ProjectParCase<
+        Me, 
+        IO, 
+        Lbl, 
+        L, 
+        R, 
+        <L as ContainsRole<Me>>::Output,
+        <R as ContainsRole<Me>>::Output
+    >,
+{
+    type Out = <() as ProjectParCase<
+        Me, 
+        IO, 
+        Lbl, 
+        L, 
+        R,
+        <L as ContainsRole<Me>>::Output,
+        <R as ContainsRole<Me>>::Output
+    >>::Out;
+}
+
+// Helper trait for case-specific projection of TPar
+pub trait ProjectParCase<Me, IO, Lbl, L, R, LContainsMe, RContainsMe> 
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+    L: TSession<IO>,
+    R: TSession<IO>,
+    LContainsMe: types::Bool,
+    RContainsMe: types::Bool,
+{
+    type Out: EpSession<IO, Me>;
+}
+
+// Case 1: Role is in left branch but not right branch - Project left branch
+impl<Me, IO, Lbl, L, R> ProjectParCase<Me, IO, Lbl, L, R, types::True, types::False> for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+    L: TSession<IO>,
+    R: TSession<IO>,
+    (): ProjectRole<Me, IO, L>,
+{
+    // Just project the left branch directly (preserves internal labels)
+    type Out = <() as ProjectRole<Me, IO, L>>::Out;
+}
+
+// Case 2: Role is in right branch but not left branch - Project right branch
+impl<Me, IO, Lbl, L, R> ProjectParCase<Me, IO, Lbl, L, R, types::False, types::True> for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+    L: TSession<IO>,
+    R: TSession<IO>,
+    (): ProjectRole<Me, IO, R>,
+{
+    // Just project the right branch directly (preserves internal labels)
+    type Out = <() as ProjectRole<Me, IO, R>>::Out;
+}
+
+// Case 3: Role is in neither branch - Create EpSkip with parent label
+impl<Me, IO, Lbl, L, R> ProjectParCase<Me, IO, Lbl, L, R, types::False, types::False> for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+    L: TSession<IO>,
+    R: TSession<IO>,
+{
+    // Create EpSkip with the parent label
+    type Out = EpSkip<IO, Lbl, Me>;
+}
+
+// Case 4: Role is in both branches - Project both and create EpPar
+impl<Me, IO, Lbl, L, R> ProjectParCase<Me, IO, Lbl, L, R, types::True, types::True> for ()
+where
+    Me: Role,
+    Lbl: types::ProtocolLabel,
+    L: TSession<IO>,
+    R: TSession<IO>,
+    (): ProjectRole<Me, IO, L>,
+    (): ProjectRole<Me, IO, R>,
+{
+    // Create EpPar with both projected branches
+    type Out = EpPar<IO, Lbl, Me, <() as ProjectRole<Me, IO, L>>::Out, <() as ProjectRole<Me, IO, R>>::Out>;
+}
+
+/// Pairs a role with its projected local type; the entries of a
+/// [`ProjectAll`] list.
+pub struct RoleLocal<R, Local>(PhantomData<(R, Local)>);
+
+/// Projects a global protocol onto every role it mentions at once.
+///
+/// `Locals` is a type-level association list of [`RoleLocal`] entries, one per
+/// distinct role of [`RolesOf`](crate::RolesOf) in order of first appearance.
+/// Look up a single role with [`LocalOf`].
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Alice; struct Bob; struct Ping;
+/// impl Role for Alice {} impl Role for Bob {}
+/// impl ProtocolLabel for Ping {}
+/// impl RoleEq<Alice> for Alice { type Output = True; }
+/// impl RoleEq<Bob> for Alice   { type Output = False; }
+/// impl RoleEq<Alice> for Bob   { type Output = False; }
+/// impl RoleEq<Bob> for Bob     { type Output = True; }
+///
+/// type G = TMsg<Http, Ping, Alice, Bob, Message, TEnd<Http, EmptyLabel>>;
+/// type All = <() as ProjectAll<Http, G>>::Locals;
+/// assert_type_eq!(
+///     <() as LocalOf<All, Bob>>::Out,
+///     EpRecv<Http, Ping, Bob, Message, EpEnd<Http, EmptyLabel, Bob>>
+/// );
+/// ```
+pub trait ProjectAll<IO, G: TSession<IO>> {
+    type Locals;
+}
+
+impl<IO, G> ProjectAll<IO, G> for ()
+where
+    G: TSession<IO> + RolesOf,
+    (): ProjectRoles<IO, G, <G as RolesOf>::Roles, Nil>,
+{
+    type Locals = <() as ProjectRoles<IO, G, <G as RolesOf>::Roles, Nil>>::Locals;
+}
+
+/// Helper trait for [`ProjectAll`]: projects `G` onto each role of `Roles`
+/// that is not already in `Seen`.
+pub trait ProjectRoles<IO, G: TSession<IO>, Roles, Seen> {
+    type Locals;
+}
+
+impl<IO, G, Seen> ProjectRoles<IO, G, Nil, Seen> for ()
+where
+    G: TSession<IO>,
+{
+    type Locals = Nil;
+}
+
+impl<IO, G, R, T, Seen> ProjectRoles<IO, G, Cons<R, T>, Seen> for ()
+where
+    G: TSession<IO>,
+    R: InRoleSet<Seen>,
+    (): ProjectRolesCase<IO, G, R, T, Seen, <R as InRoleSet<Seen>>::Output>,
+{
+    type Locals =
+        <() as ProjectRolesCase<IO, G, R, T, Seen, <R as InRoleSet<Seen>>::Output>>::Locals;
+}
+
+/// Helper trait for [`ProjectRoles`]: dispatches on whether `R` was already
+/// projected.
+pub trait ProjectRolesCase<IO, G: TSession<IO>, R, T, Seen, AlreadySeen> {
+    type Locals;
+}
+
+// The role was projected before: skip the repeated occurrence
+impl<IO, G, R, T, Seen> ProjectRolesCase<IO, G, R, T, Seen, types::True> for ()
+where
+    G: TSession<IO>,
+    (): ProjectRoles<IO, G, T, Seen>,
+{
+    type Locals = <() as ProjectRoles<IO, G, T, Seen>>::Locals;
+}
+
+// First occurrence of the role: project onto it and remember it
+impl<IO, G, R, T, Seen> ProjectRolesCase<IO, G, R, T, Seen, types::False> for ()
+where
+    G: TSession<IO>,
+    (): ProjectRole<R, IO, G>,
+    (): ProjectRoles<IO, G, T, Cons<R, Seen>>,
+{
+    type Locals = Cons<
+        RoleLocal<R, <() as ProjectRole<R, IO, G>>::Out>,
+        <() as ProjectRoles<IO, G, T, Cons<R, Seen>>>::Locals,
+    >;
+}
+
+/// Looks up the local type of role `R` in a [`ProjectAll`] list.
+#[diagnostic::on_unimplemented(
+    message = "the role `{R}` has no local type in `{Locals}`",
+    label = "`{R}` does not take part in the projected protocol",
+    note = "`ProjectAll` only lists the roles returned by `RolesOf`"
+)]
+pub trait LocalOf<Locals, R> {
+    type Out;
+}
+
+impl<R, H, L, T> LocalOf<Cons<RoleLocal<H, L>, T>, R> for ()
+where
+    R: RoleEq<H>,
+    (): LocalOfCase<R, L, T, <R as RoleEq<H>>::Output>,
+{
+    type Out = <() as LocalOfCase<R, L, T, <R as RoleEq<H>>::Output>>::Out;
+}
+
+/// Helper trait for [`LocalOf`]: dispatches on whether the head entry belongs
+/// to `R`.
+pub trait LocalOfCase<R, L, T, Found> {
+    type Out;
+}
+
+impl<R, L, T> LocalOfCase<R, L, T, types::True> for () {
+    type Out = L;
+}
+
+impl<R, L, T> LocalOfCase<R, L, T, types::False> for ()
+where
+    (): LocalOf<T, R>,
+{
+    type Out = <() as LocalOf<T, R>>::Out;
+}
+
+/// A global protocol `G` running over the protocol marker `IO`; the entries of
+/// the session list given to [`ProjectMixed`].
+pub struct IoSession<IO, G>(PhantomData<(IO, G)>);
+
+/// The local types `Locals` of a role over the protocol marker `IO`, one per
+/// protocol of that marker; the entries of a [`ProjectMixed`] result.
+pub struct IoLocal<IO, Locals>(PhantomData<(IO, Locals)>);
+
+/// Projects a role that takes part in protocols over several IO markers, such
+/// as a gateway between HTTP and MQTT.
+///
+/// `Sessions` is a type-level list of [`IoSession`] entries, each protocol
+/// typed over its own marker; a protocol composed under the `Mixed` marker is
+/// one `IoSession<Mixed, G>` entry. The entries are grouped by IO with
+/// [`FilterByIO`], and `Out` is the product of the groups: one [`IoLocal`]
+/// entry per marker, in order of first appearance, holding the list of
+/// projections of that marker's protocols in the order of `Sessions`.
+///
+/// Markers are compared with [`IoEq`](crate::IoEq).
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Gateway; struct Device; struct Ping;
+/// impl Role for Gateway {} impl Role for Device {}
+/// impl ProtocolLabel for Ping {}
+/// impl RoleEq<Gateway> for Gateway { type Output = True; }
+/// impl RoleEq<Device> for Gateway  { type Output = False; }
+/// impl RoleEq<Gateway> for Device  { type Output = False; }
+/// impl RoleEq<Device> for Device   { type Output = True; }
+///
+/// type Web = TMsg<Http, Ping, Device, Gateway, Message, TEnd<Http, EmptyLabel>>;
+/// type Bus = TMsg<Mqtt, Ping, Gateway, Device, Publish, TEnd<Mqtt, EmptyLabel>>;
+/// type GatewayLocal =
+///     <() as ProjectMixed<Gateway, tlist!(IoSession<Http, Web>, IoSession<Mqtt, Bus>)>>::Out;
+/// assert_type_eq!(
+///     GatewayLocal,
+///     tlist!(
+///         IoLocal<Http, tlist!(EpRecv<Http, Ping, Gateway, Message, EpEnd<Http, EmptyLabel, Gateway>>)>,
+///         IoLocal<Mqtt, tlist!(EpSend<Mqtt, Ping, Gateway, Publish, EpEnd<Mqtt, EmptyLabel, Gateway>>)>,
+///     )
+/// );
+/// ```
+pub trait ProjectMixed<Me, Sessions> {
+    type Out;
+}
+
+impl<Me, Sessions> ProjectMixed<Me, Sessions> for ()
+where
+    Sessions: SessionIOs,
+    (): ProjectByIO<Me, <Sessions as SessionIOs>::IOs, Sessions>,
+{
+    type Out = <() as ProjectByIO<Me, <Sessions as SessionIOs>::IOs, Sessions>>::Out;
+}
+
+/// The markers of a list of [`IoSession`] entries, each once, in order of
+/// first appearance.
+pub trait SessionIOs {
+    type IOs;
+}
+
+impl SessionIOs for Nil {
+    type IOs = Nil;
+}
+
+impl<IO, G, T> SessionIOs for Cons<IoSession<IO, G>, T>
+where
+    T: SessionIOs,
+    Cons<IO, Nil>: AddIos<<T as SessionIOs>::IOs>,
+{
+    type IOs = <Cons<IO, Nil> as AddIos<<T as SessionIOs>::IOs>>::Output;
+}
+
+/// The global protocols of the [`IoSession`] entries over the marker `IO`,
+/// in order.
+pub trait FilterByIO<IO> {
+    type Output;
+}
+
+impl<IO> FilterByIO<IO> for Nil {
+    type Output = Nil;
+}
+
+impl<IO, Other, G, T> FilterByIO<IO> for Cons<IoSession<Other, G>, T>
+where
+    Other: IoEq<IO>,
+    (): FilterByIOCase<IO, G, T, <Other as IoEq<IO>>::Output>,
+{
+    type Output = <() as FilterByIOCase<IO, G, T, <Other as IoEq<IO>>::Output>>::Output;
+}
+
+/// Helper trait for `FilterByIO`, dispatching on whether the head entry runs
+/// over `IO`.
+pub trait FilterByIOCase<IO, G, T, Same> {
+    type Output;
+}
+
+impl<IO, G, T: FilterByIO<IO>> FilterByIOCase<IO, G, T, types::True> for () {
+    type Output = Cons<G, <T as FilterByIO<IO>>::Output>;
+}
+
+impl<IO, G, T: FilterByIO<IO>> FilterByIOCase<IO, G, T, types::False> for () {
+    type Output = <T as FilterByIO<IO>>::Output;
+}
+
+/// Projects onto `Me` the protocols of `Sessions` over each marker of `IOs`,
+/// one [`IoLocal`] group per marker.
+pub trait ProjectByIO<Me, IOs, Sessions> {
+    type Out;
+}
+
+impl<Me, Sessions> ProjectByIO<Me, Nil, Sessions> for () {
+    type Out = Nil;
+}
+
+impl<Me, IO, T, Sessions> ProjectByIO<Me, Cons<IO, T>, Sessions> for ()
+where
+    Sessions: FilterByIO<IO>,
+    (): ProjectEach<Me, IO, <Sessions as FilterByIO<IO>>::Output>,
+    (): ProjectByIO<Me, T, Sessions>,
+{
+    type Out = Cons<
+        IoLocal<IO, <() as ProjectEach<Me, IO, <Sessions as FilterByIO<IO>>::Output>>::Out>,
+        <() as ProjectByIO<Me, T, Sessions>>::Out,
+    >;
+}
+
+/// Projects every global protocol of the type-level list `List` onto `Me`.
+///
+/// `Out` is the list of local types in the order of `List`, ready to be used
+/// as the branches of an n-ary endpoint such as `EpChoiceN` or `EpParN`.
+/// Unlike [`ProjectBranches`], no element is replaced by an `EpSkip`: each one
+/// must project on its own.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Alice; struct Bob; struct Ping; struct Pong; struct Pick;
+/// impl Role for Alice {} impl Role for Bob {}
+/// impl ProtocolLabel for Ping {} impl ProtocolLabel for Pong {} impl ProtocolLabel for Pick {}
+/// impl RoleEq<Alice> for Alice { type Output = True; }
+/// impl RoleEq<Bob> for Alice   { type Output = False; }
+/// impl RoleEq<Alice> for Bob   { type Output = False; }
+/// impl RoleEq<Bob> for Bob     { type Output = True; }
+///
+/// type Branches = tlist!(
+///     TMsg<Http, Ping, Alice, Bob, Message, TEnd<Http, EmptyLabel>>,
+///     TMsg<Http, Pong, Alice, Bob, Message, TEnd<Http, EmptyLabel>>,
+/// );
+/// type BobLocal = EpChoiceN<Http, Pick, Bob, <() as ProjectEach<Bob, Http, Branches>>::Out>;
+/// assert_type_eq!(
+///     BobLocal,
+///     EpChoiceN<
+///         Http,
+///         Pick,
+///         Bob,
+///         tlist!(
+///             EpRecv<Http, Ping, Bob, Message, EpEnd<Http, EmptyLabel, Bob>>,
+///             EpRecv<Http, Pong, Bob, Message, EpEnd<Http, EmptyLabel, Bob>>,
+///         ),
+///     >
+/// );
+/// ```
+pub trait ProjectEach<Me, IO, List> {
+    type Out;
+}
+
+impl<Me, IO> ProjectEach<Me, IO, Nil> for () {
+    type Out = Nil;
+}
+
+impl<Me, IO, H, T> ProjectEach<Me, IO, Cons<H, T>> for ()
+where
+    H: TSession<IO>,
+    (): ProjectRole<Me, IO, H>,
+    (): ProjectEach<Me, IO, T>,
+{
+    type Out = Cons<<() as ProjectRole<Me, IO, H>>::Out, <() as ProjectEach<Me, IO, T>>::Out>;
+}
+
+/// Holds when `ProjectRole<Me, IO, Self>` is defined, without naming the
+/// projected local type.
+///
+/// Covers every way projection can fail: merge failures, unguarded recursion,
+/// missing `RoleEq` impls and the [`ProjectionFailure`] markers. Use it as a
+/// cheap bound before `ProjectRole`, or through [`assert_projectable!`](crate::assert_projectable).
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Alice; struct Bob; struct Ping;
+/// impl Role for Alice {} impl Role for Bob {}
+/// impl ProtocolLabel for Ping {}
+/// impl RoleEq<Alice> for Alice { type Output = True; }
+/// impl RoleEq<Bob> for Alice   { type Output = False; }
+/// impl RoleEq<Alice> for Bob   { type Output = False; }
+/// impl RoleEq<Bob> for Bob     { type Output = True; }
+///
+/// type G = TMsg<Http, Ping, Alice, Bob, Message, TEnd<Http, EmptyLabel>>;
+/// fn check<G: IsProjectable<Bob, Http>>() {}
+/// check::<G>();
+/// ```
+pub trait IsProjectable<Me, IO>: TSession<IO> {}
+
+impl<Me, IO, G> IsProjectable<Me, IO> for G
+where
+    G: TSession<IO>,
+    (): ProjectRole<Me, IO, G>,
+{
+}
+
+/// Projection onto `Me` commutes with sequential composition: projecting
+/// `Self::Compose<Rhs>` gives the projection of `Self` composed with the
+/// projection of `Rhs` through [`ComposeEp`]. Both sides are [`Normalize`]d
+/// before they are compared.
+///
+/// This holds for sequences and for the roles that take part in every
+/// branch, but `Compose` has known gaps for branching and recursive types:
+///
+/// - A role absent from a choice, loop or parallel composition projects it to
+///   `EpSkip`, which composes to the projection of `Rhs` alone; the composed
+///   protocol keeps the `EpSkip` when the role is absent from `Rhs` too.
+/// - `Rhs` is appended to every branch of a choice, so a role that only acts
+///   in `Rhs` must now be informed of the choice and projection may fail.
+/// - `Rhs` is appended to both branches of a parallel composition and runs
+///   once per branch.
+///
+/// Check a pair of protocols with
+/// [`assert_projection_composes!`](crate::assert_projection_composes).
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Alice; struct Bob; struct Ping; struct Pong;
+/// impl Role for Alice {} impl Role for Bob {}
+/// impl ProtocolLabel for Ping {} impl ProtocolLabel for Pong {}
+/// impl RoleEq<Alice> for Alice { type Output = True; }
+/// impl RoleEq<Bob> for Alice   { type Output = False; }
+/// impl RoleEq<Alice> for Bob   { type Output = False; }
+/// impl RoleEq<Bob> for Bob     { type Output = True; }
+///
+/// type Request = TMsg<Http, Ping, Alice, Bob, Message, TEnd<Http>>;
+/// type Reply = TMsg<Http, Pong, Bob, Alice, Response, TEnd<Http>>;
+/// assert_projection_composes!(Http; Request, Reply, Alice);
+/// ```
+pub trait ProjectionComposes<IO, Rhs, Me>: TSession<IO> {}
+
+impl<IO, Rhs, Me, G> ProjectionComposes<IO, Rhs, Me> for G
+where
+    G: TSession<IO>,
+    Rhs: TSession<IO>,
+    (): ProjectRole<Me, IO, G> + ProjectRole<Me, IO, Rhs> + ProjectRole<Me, IO, G::Compose<Rhs>>,
+    <() as ProjectRole<Me, IO, G>>::Out: ComposeEp<<() as ProjectRole<Me, IO, Rhs>>::Out>,
+    <() as ProjectRole<Me, IO, G::Compose<Rhs>>>::Out: Normalize,
+    <<() as ProjectRole<Me, IO, G>>::Out as ComposeEp<<() as ProjectRole<Me, IO, Rhs>>::Out>>::Output:
+        Normalize,
+    <<() as ProjectRole<Me, IO, G::Compose<Rhs>>>::Out as Normalize>::Output: types::TypeEq<
+        <<<() as ProjectRole<Me, IO, G>>::Out as ComposeEp<<() as ProjectRole<Me, IO, Rhs>>::Out>>::Output as Normalize>::Output,
+    >,
+{
+}
+
+/// A role that is one member of an indexed family of interchangeable roles,
+/// such as `Worker<N>`.
+///
+/// `Index` tells members apart; with the type-level naturals [`Zero`] and
+/// [`Succ`], a single generic `RoleEq` impl compares every pair of members
+/// through [`IndexEq`]. [`role_family!`](crate::role_family) writes that impl.
+/// Roles outside the family still need their own `RoleEq` impls against it,
+/// generic over the index.
+pub trait RoleFamily: Role {
+    type Index;
+}
+
+/// Type-level natural zero; the index of the first member of a role family.
+pub struct Zero;
+
+/// Type-level natural after `N`; the index of the member after `N` in a role
+/// family.
+pub struct Succ<N>(PhantomData<N>);
+
+/// Type-level equality of role family indices.
+#[diagnostic::on_unimplemented(
+    message = "no `IndexEq` impl compares the family indices `{Self}` and `{Other}`",
+    note = "build family indices from `Zero` and `Succ`, or implement `IndexEq` for your own"
+)]
+pub trait IndexEq<Other> {
+    type Output: types::Bool;
+}
+
+impl IndexEq<Zero> for Zero {
+    type Output = types::True;
+}
+
+impl<N> IndexEq<Succ<N>> for Zero {
+    type Output = types::False;
+}
+
+impl<N> IndexEq<Zero> for Succ<N> {
+    type Output = types::False;
+}
+
+impl<N: IndexEq<M>, M> IndexEq<Succ<M>> for Succ<N> {
+    type Output = <N as IndexEq<M>>::Output;
 }
 
-// Left is projected, right is EpSkip
-impl<IO, Me: Role, Lbl: types::ProtocolLabel, ProjectedL: EpSession<IO, Me>>
-    ComposeProjectedParBranchesCase<
-        types::False,
-        types::True,
-        types::False,
-        types::False,
-        IO,
-        Me,
-        ProjectedL,
-        EpSkip<IO, Lbl, Me>,
-    > for ()
-{
-    // Return the non-skip branch directly
-    // This matches test_preserved_label_in_parallel expectations
-    type Out = ProjectedL;
+/// Rewrites a local session type so that it is run by the role `New`.
+///
+/// Only the role the type is written for changes: peers, role sets and
+/// delegated endpoints are kept. Continuations and branch lists are rewritten
+/// as well, so the result is the same local type for another role.
+pub trait ReassignRole<New> {
+    type Out;
 }
 
-// Both are projected (non-skip, non-end)
-impl<IO, Me: Role, ProjectedL: EpSession<IO, Me>, ProjectedR: EpSession<IO, Me>>
-    ComposeProjectedParBranchesCase<
-        types::False,
-        types::False,
-        types::False,
-        types::False,
-        IO,
-        Me,
-        ProjectedL,
-        ProjectedR,
-    > for ()
-where
-    ProjectedL: GetLocalLabel, // Add constraint to extract label
-{
-    type Out = EpPar<IO, <ProjectedL as GetLocalLabel>::Label, Me, ProjectedL, ProjectedR>;
+impl<New> ReassignRole<New> for Nil {
+    type Out = Nil;
 }
 
-// Left is EpEnd, right is EpSkip
-impl<IO, Me: Role, Lbl1: types::ProtocolLabel, Lbl2: types::ProtocolLabel>
-    ComposeProjectedParBranchesCase<
-        types::False,
-        types::True,
-        types::True,
-        types::False,
-        IO,
-        Me,
-        EpEnd<IO, Lbl1, Me>,
-        EpSkip<IO, Lbl2, Me>,
-    > for ()
+impl<New, H: ReassignRole<New>, T: ReassignRole<New>> ReassignRole<New> for Cons<H, T> {
+    type Out = Cons<<H as ReassignRole<New>>::Out, <T as ReassignRole<New>>::Out>;
+}
+
+impl<New, IO, Lbl: types::ProtocolLabel, R, H, T: ReassignRole<New>> ReassignRole<New>
+    for EpSend<IO, Lbl, R, H, T>
 {
-    type Out = EpEnd<IO, Lbl1, Me>; // Preserve the label from EpEnd
+    type Out = EpSend<IO, Lbl, New, H, <T as ReassignRole<New>>::Out>;
 }
 
-// Left is EpSkip, right is EpEnd
-impl<IO, Me: Role, Lbl1: types::ProtocolLabel, Lbl2: types::ProtocolLabel>
-    ComposeProjectedParBranchesCase<
-        types::True,
-        types::False,
-        types::False,
-        types::True,
-        IO,
-        Me,
-        EpSkip<IO, Lbl1, Me>,
-        EpEnd<IO, Lbl2, Me>,
-    > for ()
+impl<New, IO, Lbl: types::ProtocolLabel, R, H, T: ReassignRole<New>> ReassignRole<New>
+    for EpRecv<IO, Lbl, R, H, T>
 {
-    type Out = EpEnd<IO, Lbl2, Me>; // Preserve the label from EpEnd
+    type Out = EpRecv<IO, Lbl, New, H, <T as ReassignRole<New>>::Out>;
 }
 
-// Left is EpEnd, right is projected
-impl<IO, Me: Role, Lbl: types::ProtocolLabel, ProjectedR: EpSession<IO, Me>>
-    ComposeProjectedParBranchesCase<
-        types::False,
-        types::False,
-        types::True,
-        types::False,
-        IO,
-        Me,
-        EpEnd<IO, Lbl, Me>,
-        ProjectedR,
-    > for ()
+impl<New, IO, Lbl: types::ProtocolLabel, Me, ToSet, H, T: ReassignRole<New>> ReassignRole<New>
+    for EpBroadcast<IO, Lbl, Me, ToSet, H, T>
 {
-    type Out = ProjectedR;
+    type Out = EpBroadcast<IO, Lbl, New, ToSet, H, <T as ReassignRole<New>>::Out>;
 }
 
-// Left is projected, right is EpEnd
-impl<IO, Me: Role, Lbl: types::ProtocolLabel, ProjectedL: EpSession<IO, Me>>
-    ComposeProjectedParBranchesCase<
-        types::False,
-        types::False,
-        types::False,
-        types::True,
-        IO,
-        Me,
-        ProjectedL,
-        EpEnd<IO, Lbl, Me>,
-    > for ()
+impl<New, IO, Lbl: types::ProtocolLabel, Me, FromSet, H, T: ReassignRole<New>> ReassignRole<New>
+    for EpGather<IO, Lbl, Me, FromSet, H, T>
 {
-    type Out = ProjectedL;
+    type Out = EpGather<IO, Lbl, New, FromSet, H, <T as ReassignRole<New>>::Out>;
 }
 
-// Both are EpEnd
-impl<IO, Me: Role, Lbl1: types::ProtocolLabel, Lbl2: types::ProtocolLabel>
-    ComposeProjectedParBranchesCase<
-        types::False,
-        types::False,
-        types::True,
-        types::True,
-        IO,
-        Me,
-        EpEnd<IO, Lbl1, Me>,
-        EpEnd<IO, Lbl2, Me>,
-    > for ()
+impl<New, IO, Lbl: types::ProtocolLabel, Me, DelegatedEp, Cont: ReassignRole<New>> ReassignRole<New>
+    for EpDelegate<IO, Lbl, Me, DelegatedEp, Cont>
 {
-    type Out = EpEnd<IO, Lbl1, Me>; // Use label from first branch
+    type Out = EpDelegate<IO, Lbl, New, DelegatedEp, <Cont as ReassignRole<New>>::Out>;
 }
 
-// Extract labels from local endpoint types
-pub trait GetLocalLabel {
-    type Label: types::ProtocolLabel;
+impl<New, IO, Lbl: types::ProtocolLabel, R> ReassignRole<New> for EpEnd<IO, Lbl, R> {
+    type Out = EpEnd<IO, Lbl, New>;
 }
 
-// Implementations for different endpoint types
-impl<IO, Lbl: types::ProtocolLabel, R, H, T> GetLocalLabel for EpSend<IO, Lbl, R, H, T> {
-    type Label = Lbl;
+impl<New, IO, Lbl: types::ProtocolLabel, R> ReassignRole<New> for EpSkip<IO, Lbl, R> {
+    type Out = EpSkip<IO, Lbl, New>;
 }
 
-impl<IO, Lbl: types::ProtocolLabel, R, H, T> GetLocalLabel for EpRecv<IO, Lbl, R, H, T> {
-    type Label = Lbl;
+impl<New, IO, Lbl: types::ProtocolLabel, Me, Branches: ReassignRole<New>> ReassignRole<New>
+    for EpChoiceN<IO, Lbl, Me, Branches>
+{
+    type Out = EpChoiceN<IO, Lbl, New, <Branches as ReassignRole<New>>::Out>;
 }
 
-impl<IO, Lbl: types::ProtocolLabel, Me, L, R> GetLocalLabel for EpChoice<IO, Lbl, Me, L, R> {
-    type Label = Lbl;
+impl<New, IO, Lbl: types::ProtocolLabel, Me, Branches: ReassignRole<New>> ReassignRole<New>
+    for EpSelect<IO, Lbl, Me, Branches>
+{
+    type Out = EpSelect<IO, Lbl, New, <Branches as ReassignRole<New>>::Out>;
 }
 
-impl<IO, Lbl: types::ProtocolLabel, Me, L, R> GetLocalLabel for EpPar<IO, Lbl, Me, L, R> {
-    type Label = Lbl;
+impl<New, IO, Lbl: types::ProtocolLabel, Me, Branches: ReassignRole<New>> ReassignRole<New>
+    for EpOffer<IO, Lbl, Me, Branches>
+{
+    type Out = EpOffer<IO, Lbl, New, <Branches as ReassignRole<New>>::Out>;
 }
 
-impl<IO, Lbl: types::ProtocolLabel, R> GetLocalLabel for EpEnd<IO, Lbl, R> {
-    type Label = Lbl;
+impl<New, IO, Lbl: types::ProtocolLabel, Me, L: ReassignRole<New>, R: ReassignRole<New>>
+    ReassignRole<New> for EpPar<IO, Lbl, Me, L, R>
+{
+    type Out = EpPar<IO, Lbl, New, <L as ReassignRole<New>>::Out, <R as ReassignRole<New>>::Out>;
 }
 
-impl<IO, Lbl: types::ProtocolLabel, R> GetLocalLabel for EpSkip<IO, Lbl, R> {
-    type Label = Lbl;
+impl<New, IO, Lbl: types::ProtocolLabel, Me, Branches: ReassignRole<New>> ReassignRole<New>
+    for EpParN<IO, Lbl, Me, Branches>
+{
+    type Out = EpParN<IO, Lbl, New, <Branches as ReassignRole<New>>::Out>;
 }
 
-/// Type-level filter that removes all EpSkip<IO, Me> branches from a type-level list.
-pub trait FilterSkips<IO, Me: Role, List> {
-    type Out;
+impl<New, IO, Lbl: types::ProtocolLabel, Me, Steps: ReassignRole<New>> ReassignRole<New>
+    for EpShuffle<IO, Lbl, Me, Steps>
+{
+    type Out = EpShuffle<IO, Lbl, New, <Steps as ReassignRole<New>>::Out>;
 }
 
-impl<IO, Me: Role> FilterSkips<IO, Me, Nil> for () {
-    type Out = Nil;
+impl<New, IO, Lbl: types::ProtocolLabel, Var, Body: ReassignRole<New>> ReassignRole<New>
+    for EpRec<IO, Lbl, Var, Body>
+{
+    type Out = EpRec<IO, Lbl, Var, <Body as ReassignRole<New>>::Out>;
 }
 
-impl<IO, Me: Role, H, T> FilterSkips<IO, Me, Cons<H, T>> for ()
+impl<New, Var> ReassignRole<New> for EpVar<Var> {
+    type Out = EpVar<Var>;
+}
+
+impl<New, IO, Lbl, Me, Dur, Body, OnTimeout> ReassignRole<New>
+    for EpTimeout<IO, Lbl, Me, Dur, Body, OnTimeout>
 where
-    H: GetEpSkipTypeMarker<IO, Me> + EpSession<IO, Me>,
-    (): FilterSkipsCase<IO, Me, H, T, <H as GetEpSkipTypeMarker<IO, Me>>::TypeMarker>,
+    Lbl: types::ProtocolLabel,
+    Body: ReassignRole<New>,
+    OnTimeout: ReassignRole<New>,
 {
-    type Out =
-        <() as FilterSkipsCase<IO, Me, H, T, <H as GetEpSkipTypeMarker<IO, Me>>::TypeMarker>>::Out;
+    type Out = EpTimeout<
+        IO,
+        Lbl,
+        New,
+        Dur,
+        <Body as ReassignRole<New>>::Out,
+        <OnTimeout as ReassignRole<New>>::Out,
+    >;
 }
 
-/// Helper trait for non-overlapping dispatch in FilterSkips
-pub trait FilterSkipsCase<IO, Me: Role, H, T, TypeMarker> {
-    type Out;
+impl<New, IO, Lbl: types::ProtocolLabel, Me, Dur, T: ReassignRole<New>> ReassignRole<New>
+    for EpDelay<IO, Lbl, Me, Dur, T>
+{
+    type Out = EpDelay<IO, Lbl, New, Dur, <T as ReassignRole<New>>::Out>;
 }
 
-// Case: Head is EpSkip – skip it
-impl<IO, Me: Role, Lbl: types::ProtocolLabel, T> FilterSkipsCase<IO, Me, EpSkip<IO, Lbl, Me>, T, IsEpSkipType> for ()
-where
-    (): FilterSkips<IO, Me, T>,
+impl<New, IO, Lbl: types::ProtocolLabel, Me, Dur, T: ReassignRole<New>> ReassignRole<New>
+    for EpDeadline<IO, Lbl, Me, Dur, T>
 {
-    type Out = <() as FilterSkips<IO, Me, T>>::Out;
+    type Out = EpDeadline<IO, Lbl, New, Dur, <T as ReassignRole<New>>::Out>;
 }
 
-// Case: Head is not EpSkip – keep it
-impl<IO, Me: Role, H, T> FilterSkipsCase<IO, Me, H, T, IsNotEpSkipType> for ()
+impl<New, IO, Lbl, Me, Scope, Handler> ReassignRole<New>
+    for EpInterrupt<IO, Lbl, Me, Scope, Handler>
 where
-    H: EpSession<IO, Me>,
-    (): FilterSkips<IO, Me, T>,
+    Lbl: types::ProtocolLabel,
+    Scope: ReassignRole<New>,
+    Handler: ReassignRole<New>,
 {
-    type Out = Cons<H, <() as FilterSkips<IO, Me, T>>::Out>;
+    type Out = EpInterrupt<
+        IO,
+        Lbl,
+        New,
+        <Scope as ReassignRole<New>>::Out,
+        <Handler as ReassignRole<New>>::Out,
+    >;
 }
 
-// Implement ProjectPar by dispatching to a helper trait for case-specific behavior
-impl<Me, IO, Lbl, L, R> ProjectPar<Me, IO, Lbl, L, R> for ()
+impl<New, IO, Lbl, Me, Scope, Handler> ReassignRole<New>
+    for EpInterruptible<IO, Lbl, Me, Scope, Handler>
 where
-    Me: Role,
     Lbl: types::ProtocolLabel,
-    L: TSession<IO>,
-    R: TSession<IO>,
-    // Determine if branches contain the role
-    L: ContainsRole<Me>,
-    <L as ContainsRole<Me>>::Output: types::Bool,
-    R: ContainsRole<Me>,
-    <R as ContainsRole<Me>>::Output: types::Bool,
-    // Use a helper trait to handle case-specific projection
-    (): ProjectParCase<
-        Me, 
-        IO, 
-        Lbl, 
-        L, 
-        R, 
-        <L as ContainsRole<Me>>::Output,
-        <R as ContainsRole<Me>>::Output
-    >,
+    Scope: ReassignRole<New>,
+    Handler: ReassignRole<New>,
 {
-    type Out = <() as ProjectParCase<
-        Me, 
-        IO, 
-        Lbl, 
-        L, 
-        R,
-        <L as ContainsRole<Me>>::Output,
-        <R as ContainsRole<Me>>::Output
-    >>::Out;
+    type Out = EpInterruptible<
+        IO,
+        Lbl,
+        New,
+        <Scope as ReassignRole<New>>::Out,
+        <Handler as ReassignRole<New>>::Out,
+    >;
 }
 
-// Helper trait for case-specific projection of TPar
-pub trait ProjectParCase<Me, IO, Lbl, L, R, LContainsMe, RContainsMe> 
+impl<New, Pred, Inner: ReassignRole<New>> ReassignRole<New> for EpRefine<Pred, Inner> {
+    type Out = EpRefine<Pred, <Inner as ReassignRole<New>>::Out>;
+}
+
+impl<New, IO, Lbl, Me, Body, Cont> ReassignRole<New> for EpOptionalSelect<IO, Lbl, Me, Body, Cont>
 where
-    Me: Role,
     Lbl: types::ProtocolLabel,
-    L: TSession<IO>,
-    R: TSession<IO>,
-    LContainsMe: types::Bool,
-    RContainsMe: types::Bool,
+    Body: ReassignRole<New>,
+    Cont: ReassignRole<New>,
 {
-    type Out: EpSession<IO, Me>;
+    type Out = EpOptionalSelect<
+        IO,
+        Lbl,
+        New,
+        <Body as ReassignRole<New>>::Out,
+        <Cont as ReassignRole<New>>::Out,
+    >;
 }
 
-// Case 1: Role is in left branch but not right branch - Project left branch
-impl<Me, IO, Lbl, L, R> ProjectParCase<Me, IO, Lbl, L, R, types::True, types::False> for ()
+impl<New, IO, Lbl, Me, Body, Cont> ReassignRole<New> for EpOptionalOffer<IO, Lbl, Me, Body, Cont>
 where
-    Me: Role,
     Lbl: types::ProtocolLabel,
-    L: TSession<IO>,
-    R: TSession<IO>,
-    (): ProjectRole<Me, IO, L>,
+    Body: ReassignRole<New>,
+    Cont: ReassignRole<New>,
 {
-    // Just project the left branch directly (preserves internal labels)
-    type Out = <() as ProjectRole<Me, IO, L>>::Out;
+    type Out = EpOptionalOffer<
+        IO,
+        Lbl,
+        New,
+        <Body as ReassignRole<New>>::Out,
+        <Cont as ReassignRole<New>>::Out,
+    >;
 }
 
-// Case 2: Role is in right branch but not left branch - Project right branch
-impl<Me, IO, Lbl, L, R> ProjectParCase<Me, IO, Lbl, L, R, types::False, types::True> for ()
+impl<New, IO, Lbl: types::ProtocolLabel, Me, Err> ReassignRole<New> for EpThrow<IO, Lbl, Me, Err> {
+    type Out = EpThrow<IO, Lbl, New, Err>;
+}
+
+impl<New, IO, Lbl, Me, Body, Handler> ReassignRole<New> for EpCatch<IO, Lbl, Me, Body, Handler>
 where
-    Me: Role,
     Lbl: types::ProtocolLabel,
-    L: TSession<IO>,
-    R: TSession<IO>,
-    (): ProjectRole<Me, IO, R>,
+    Body: ReassignRole<New>,
+    Handler: ReassignRole<New>,
 {
-    // Just project the right branch directly (preserves internal labels)
-    type Out = <() as ProjectRole<Me, IO, R>>::Out;
+    type Out = EpCatch<
+        IO,
+        Lbl,
+        New,
+        <Body as ReassignRole<New>>::Out,
+        <Handler as ReassignRole<New>>::Out,
+    >;
 }
 
-// Case 3: Role is in neither branch - Create EpSkip with parent label
-impl<Me, IO, Lbl, L, R> ProjectParCase<Me, IO, Lbl, L, R, types::False, types::False> for ()
+impl<New, IO, Lbl, Me, High, Low> ReassignRole<New> for EpPriorityOffer<IO, Lbl, Me, High, Low>
 where
-    Me: Role,
     Lbl: types::ProtocolLabel,
-    L: TSession<IO>,
-    R: TSession<IO>,
+    High: ReassignRole<New>,
+    Low: ReassignRole<New>,
 {
-    // Create EpSkip with the parent label
-    type Out = EpSkip<IO, Lbl, Me>;
+    type Out = EpPriorityOffer<
+        IO,
+        Lbl,
+        New,
+        <High as ReassignRole<New>>::Out,
+        <Low as ReassignRole<New>>::Out,
+    >;
 }
 
-// Case 4: Role is in both branches - Project both and create EpPar
-impl<Me, IO, Lbl, L, R> ProjectParCase<Me, IO, Lbl, L, R, types::True, types::True> for ()
+impl<New, IO, Lbl: types::ProtocolLabel, Me, Peer, Cont: ReassignRole<New>> ReassignRole<New>
+    for EpClose<IO, Lbl, Me, Peer, Cont>
+{
+    type Out = EpClose<IO, Lbl, New, Peer, <Cont as ReassignRole<New>>::Out>;
+}
+
+impl<New, IO, Lbl: types::ProtocolLabel, Me, Peer, Cont: ReassignRole<New>> ReassignRole<New>
+    for EpOpen<IO, Lbl, Me, Peer, Cont>
+{
+    type Out = EpOpen<IO, Lbl, New, Peer, <Cont as ReassignRole<New>>::Out>;
+}
+
+/// Holds when projecting `Self` onto the roles `A` and `B` gives the same
+/// local type up to the role it is run by.
+///
+/// This is what it means for a protocol to treat two members of a
+/// [`RoleFamily`] symmetrically: code written against the local type of one
+/// worker works for the other.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// use core::marker::PhantomData;
+/// struct Master; struct Worker<N>(PhantomData<N>); struct Task;
+/// impl Role for Master {}
+/// impl ProtocolLabel for Task {}
+/// role_family!(Worker);
+/// impl RoleEq<Master> for Master { type Output = True; }
+/// impl<N> RoleEq<Worker<N>> for Master { type Output = False; }
+/// impl<N> RoleEq<Master> for Worker<N> { type Output = False; }
+///
+/// type G = TMsg<Http, Task, Master, Worker<Zero>, Message,
+///          TMsg<Http, Task, Master, Worker<Succ<Zero>>, Message, TEnd<Http, EmptyLabel>>>;
+/// fn check<G: ProjectsAlike<Http, Worker<Zero>, Worker<Succ<Zero>>>>() {}
+/// check::<G>();
+/// ```
+pub trait ProjectsAlike<IO, A, B>: TSession<IO> {}
+
+impl<IO, A, B, G> ProjectsAlike<IO, A, B> for G
 where
-    Me: Role,
-    Lbl: types::ProtocolLabel,
-    L: TSession<IO>,
-    R: TSession<IO>,
-    (): ProjectRole<Me, IO, L>,
-    (): ProjectRole<Me, IO, R>,
+    G: TSession<IO>,
+    (): ProjectRole<A, IO, G> + ProjectRole<B, IO, G>,
+    <() as ProjectRole<A, IO, G>>::Out: ReassignRole<B, Out = <() as ProjectRole<B, IO, G>>::Out>,
 {
-    // Create EpPar with both projected branches
-    type Out = EpPar<IO, Lbl, Me, <() as ProjectRole<Me, IO, L>>::Out, <() as ProjectRole<Me, IO, R>>::Out>;
 }
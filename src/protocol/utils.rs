@@ -8,21 +8,53 @@
 //!
 //! - Type-level boolean operations and checks
 //! - Disjointness assertions for parallel composition
-//! - Uniqueness checks for type-level lists
+//! - Uniqueness checks and deduplication for type-level lists
 //! - Other helper traits for type-level programming
 //!
 //! These utilities ensure protocol safety and correctness at compile time.
 
 use super::base::*;
+use super::local::{InRoleSet, LabelEq, RoleEq};
 use crate::types;
+use core::marker::PhantomData;
 
-/// Trait for disjointness checking of protocol branches.
-/// Two branches are disjoint if they have no roles in common.
+/// Checks that two lists of roles have no role in common.
+///
+/// Roles are compared with `RoleEq`, so every role of `L` needs an impl
+/// against every role of `R`. Fails naming the first shared role, as a
+/// [`SharedRole`].
 pub trait Disjoint<L, R> {}
 
 // Base case: empty list is disjoint with anything
 impl<R> Disjoint<Nil, R> for () {}
 
+impl<H, L, R> Disjoint<Cons<H, L>, R> for ()
+where
+    R: Contains<ByRole, H>,
+    H: DisjointCase<<R as Contains<ByRole, H>>::Output>,
+    (): Disjoint<L, R>,
+{
+}
+
+/// Helper trait for `Disjoint`, dispatching on whether the other list has
+/// the role.
+pub trait DisjointCase<Shared> {}
+impl<H> DisjointCase<types::False> for H {}
+impl<H> DisjointCase<types::True> for H where SharedRole<H>: DisjointnessFailure {}
+
+/// Marker for a role `R` that takes part in both of two protocols required to
+/// be role-disjoint.
+pub struct SharedRole<R>(PhantomData<R>);
+
+/// Never implemented: a bound `Marker: DisjointnessFailure` fails and names
+/// the role found by [`Disjoint`].
+#[diagnostic::on_unimplemented(
+    message = "the protocols are not role-disjoint: `{Self}`",
+    label = "a role takes part in more than one branch",
+    note = "give each parallel branch its own roles"
+)]
+pub trait DisjointnessFailure {}
+
 // Use marker types for type-level dispatch
 pub struct IsNil;
 pub struct IsNotNil;
@@ -40,28 +72,6 @@ impl<H, T> CheckNil for Cons<H, T> {
     type Result = IsNotNil;
 }
 
-// Helper traits for disjointness checking with dispatch
-pub trait DisjointCons<H, L, R, IsRNil> {}
-
-// Implementation for when R is Nil
-impl<H, L> DisjointCons<H, L, Nil, IsNil> for () {}
-
-// Implementation for when R is not Nil
-impl<H, L, R> DisjointCons<H, L, R, IsNotNil> for ()
-where
-    R: NotInList<H>,
-    (): Disjoint<L, R>,
-{
-}
-
-// Recursive case using type-level dispatch
-impl<H, L, R> Disjoint<Cons<H, L>, R> for ()
-where
-    R: CheckNil,
-    (): DisjointCons<H, L, R, <R as CheckNil>::Result>,
-{
-}
-
 /// Type-level marker types for lists
 pub struct EmptyList;
 pub struct NonEmptyList;
@@ -117,3 +127,205 @@ where
 {
     type Output = <() as ConcatCons<H, T, R, <R as CheckNil>::Result>>::Output;
 }
+
+/// Type-level inclusion of one list of roles in another.
+///
+/// `Output` is `True` if every role of `Self` is `InRoleSet` `Super`; order and
+/// repetitions do not matter.
+pub trait SubsetOf<Super> {
+    type Output: types::Bool;
+}
+
+impl<Super> SubsetOf<Super> for Nil {
+    type Output = types::True;
+}
+
+impl<H, T, Super> SubsetOf<Super> for Cons<H, T>
+where
+    H: InRoleSet<Super>,
+    T: SubsetOf<Super>,
+    <H as InRoleSet<Super>>::Output: types::BoolAnd<<T as SubsetOf<Super>>::Output>,
+{
+    type Output = types::And<<H as InRoleSet<Super>>::Output, <T as SubsetOf<Super>>::Output>;
+}
+
+/// Type-level set equality of two lists of roles.
+///
+/// `Output` is `True` if each list is a `SubsetOf` the other.
+pub trait SameRoles<Other> {
+    type Output: types::Bool;
+}
+
+impl<L, Other> SameRoles<Other> for L
+where
+    L: SubsetOf<Other>,
+    Other: SubsetOf<L>,
+    <L as SubsetOf<Other>>::Output: types::BoolAnd<<Other as SubsetOf<L>>::Output>,
+{
+    type Output = types::And<<L as SubsetOf<Other>>::Output, <Other as SubsetOf<L>>::Output>;
+}
+
+/// Type-level equality of list elements under the comparison `By`.
+///
+/// `By` names the equality used: [`ByRole`] compares with `RoleEq`,
+/// [`ByLabel`] with `LabelEq` and [`ByChannel`](super::deadlock::ByChannel)
+/// compares channels by their roles. Implement `ElemEq` for a marker of your own to
+/// compare other kinds of elements.
+#[diagnostic::on_unimplemented(
+    message = "no `ElemEq<{By}, _>` impl compares `{Self}` and `{Other}`",
+    note = "implement the equality trait `{By}` stands for, such as `RoleEq`, for every ordered pair of elements in the list"
+)]
+pub trait ElemEq<By, Other> {
+    type Output: types::Bool;
+}
+
+/// Compares list elements as roles, with `RoleEq`.
+pub struct ByRole;
+
+/// Compares list elements as labels, with `LabelEq`.
+pub struct ByLabel;
+
+impl<A, B> ElemEq<ByRole, B> for A
+where
+    A: RoleEq<B>,
+    <A as RoleEq<B>>::Output: types::Bool,
+{
+    type Output = <A as RoleEq<B>>::Output;
+}
+
+impl<A: LabelEq<B>, B> ElemEq<ByLabel, B> for A {
+    type Output = <A as LabelEq<B>>::Output;
+}
+
+/// Removes every element equal to `X` under `By` from a type-level list.
+pub trait RemoveAll<By, X> {
+    type Output;
+}
+
+impl<By, X> RemoveAll<By, X> for Nil {
+    type Output = Nil;
+}
+
+impl<By, X, H, T> RemoveAll<By, X> for Cons<H, T>
+where
+    H: ElemEq<By, X>,
+    (): RemoveAllCase<By, X, H, T, <H as ElemEq<By, X>>::Output>,
+{
+    type Output = <() as RemoveAllCase<By, X, H, T, <H as ElemEq<By, X>>::Output>>::Output;
+}
+
+/// Helper trait for `RemoveAll`, dispatching on whether the head of the list
+/// equals `X`.
+pub trait RemoveAllCase<By, X, H, T, Same> {
+    type Output;
+}
+
+impl<By, X, H, T: RemoveAll<By, X>> RemoveAllCase<By, X, H, T, types::True> for () {
+    type Output = <T as RemoveAll<By, X>>::Output;
+}
+
+impl<By, X, H, T: RemoveAll<By, X>> RemoveAllCase<By, X, H, T, types::False> for () {
+    type Output = Cons<H, <T as RemoveAll<By, X>>::Output>;
+}
+
+/// Removes repeated elements from a type-level list, keeping the first
+/// occurrence of each in order.
+///
+/// Elements are compared with [`ElemEq`] under `By`, such as [`ByRole`].
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Alice; struct Bob;
+/// impl RoleEq<Alice> for Alice { type Output = True; }
+/// impl RoleEq<Bob> for Alice   { type Output = False; }
+/// impl RoleEq<Alice> for Bob   { type Output = False; }
+/// impl RoleEq<Bob> for Bob     { type Output = True; }
+/// assert_type_eq!(
+///     <tlist!(Alice, Bob, Alice, Bob) as Dedup<ByRole>>::Output,
+///     tlist!(Alice, Bob)
+/// );
+/// ```
+pub trait Dedup<By> {
+    type Output;
+}
+
+impl<By> Dedup<By> for Nil {
+    type Output = Nil;
+}
+
+impl<By, H, T> Dedup<By> for Cons<H, T>
+where
+    T: RemoveAll<By, H>,
+    <T as RemoveAll<By, H>>::Output: Dedup<By>,
+{
+    type Output = Cons<H, <<T as RemoveAll<By, H>>::Output as Dedup<By>>::Output>;
+}
+
+/// Type-level membership of `X` in a list under the comparison `By`.
+///
+/// `Output` is `True` if some element of the list is [`ElemEq`] to `X`.
+pub trait Contains<By, X> {
+    type Output: types::Bool;
+}
+
+impl<By, X> Contains<By, X> for Nil {
+    type Output = types::False;
+}
+
+impl<By, X, H, T> Contains<By, X> for Cons<H, T>
+where
+    H: ElemEq<By, X>,
+    T: Contains<By, X>,
+    <H as ElemEq<By, X>>::Output: types::BoolOr<<T as Contains<By, X>>::Output>,
+{
+    type Output = types::Or<<H as ElemEq<By, X>>::Output, <T as Contains<By, X>>::Output>;
+}
+
+/// Checks that no label occurs twice in a type-level list.
+///
+/// Labels are compared with `LabelEq`, so every ordered pair of labels in the
+/// list needs an impl. Fails naming the first repeated label, as a
+/// [`DuplicateLabel`].
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Ping; struct Pong;
+/// impl ProtocolLabel for Ping {}
+/// impl ProtocolLabel for Pong {}
+/// impl LabelEq<Ping> for Ping { type Output = True; }
+/// impl LabelEq<Pong> for Ping { type Output = False; }
+/// impl LabelEq<Ping> for Pong { type Output = False; }
+/// impl LabelEq<Pong> for Pong { type Output = True; }
+/// fn unique<L: UniqueList>() {}
+/// unique::<tlist!(Ping, Pong)>();
+/// ```
+pub trait UniqueList {}
+
+impl UniqueList for Nil {}
+
+impl<H, T> UniqueList for Cons<H, T>
+where
+    T: Contains<ByLabel, H> + UniqueList,
+    H: UniqueLabelCase<<T as Contains<ByLabel, H>>::Output>,
+{
+}
+
+/// Helper trait for `UniqueList`, dispatching on whether the label occurs
+/// again later in the list.
+pub trait UniqueLabelCase<Repeated> {}
+impl<L> UniqueLabelCase<types::False> for L {}
+impl<L> UniqueLabelCase<types::True> for L where DuplicateLabel<L>: LabelUniquenessFailure {}
+
+/// Marker for a label `L` that names more than one step of a protocol.
+pub struct DuplicateLabel<L>(PhantomData<L>);
+
+/// Never implemented: a bound `Marker: LabelUniquenessFailure` fails and names
+/// the label found by [`UniqueList`].
+#[diagnostic::on_unimplemented(
+    message = "a protocol label is used more than once: `{Self}`",
+    label = "duplicate label",
+    note = "give each step of the protocol its own label; only the labels of ends may repeat"
+)]
+pub trait LabelUniquenessFailure {}
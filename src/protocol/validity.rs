@@ -0,0 +1,452 @@
+//! # Local Validity Checks
+//!
+//! This module checks that local session types are well formed on their own,
+//! catching projection bugs and hand-written endpoints that no correct global
+//! protocol could produce.
+//!
+//! Key components:
+//!
+//! - `LocallyWellFormed`: A local session type is well formed for a role
+//! - `ActionShape`: Classifies the leading action of a local session type
+//! - `SendAction` / `RecvAction` / `OtherAction`: Leading action shapes
+//! - `ActionsDisjoint`: Two leading actions cannot be confused on the same channel
+//! - `DisjointFromAll` / `PairwiseDisjointActions`: The same over type-level lists
+//!
+//! A well-formed endpoint belongs to one role over one protocol marker
+//! throughout, never talks to itself (closing or opening a channel to itself,
+//! broadcasting to or gathering from a set containing itself), never offers or
+//! selects among zero branches, and never runs two concurrent branches that
+//! start by sending, or by receiving, on the same label, since messages of
+//! the two branches could not be told apart.
+//!
+//! Labels are compared with `LabelEq`; only the leading action of each
+//! concurrent branch is compared.
+//!
+//! `EpSend` and `EpRecv` do not name the peer, so an endpoint that sends to or
+//! receives from its own role cannot be told apart from one talking to another
+//! role and is not rejected here. Check the global protocol with
+//! [`NoSelfMessaging`](super::loopback::NoSelfMessaging) instead.
+
+use super::base::{Cons, Nil};
+use super::local::*;
+use crate::types;
+use core::marker::PhantomData;
+
+/// A local session type that is well formed for role `Me` over protocol marker `IO`.
+///
+/// Every node must carry `Me` and `IO`; a node belonging to another role has no impl.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// type Client = EpSend<Http, EmptyLabel, TClient, Message, EpEnd<Http, EmptyLabel, TClient>>;
+/// fn assert_well_formed<E: LocallyWellFormed<Http, TClient>>() {}
+/// assert_well_formed::<Client>();
+/// ```
+pub trait LocallyWellFormed<IO, Me> {}
+
+/// Leading action of a send-like step labelled `Lbl`.
+pub struct SendAction<Lbl>(PhantomData<Lbl>);
+/// Leading action of a receive-like step labelled `Lbl`.
+pub struct RecvAction<Lbl>(PhantomData<Lbl>);
+/// Leading action of every other local session type.
+pub struct OtherAction;
+
+/// Classifies the leading action of a local session type.
+pub trait ActionShape {
+    type Shape;
+}
+
+/// Two leading actions that cannot be confused on the same channel.
+pub trait ActionsDisjoint<Other> {}
+
+impl<L1, L2> ActionsDisjoint<SendAction<L2>> for SendAction<L1> where
+    L1: LabelEq<L2, Output = types::False>
+{
+}
+
+impl<L1, L2> ActionsDisjoint<RecvAction<L2>> for RecvAction<L1> where
+    L1: LabelEq<L2, Output = types::False>
+{
+}
+
+impl<L1, L2> ActionsDisjoint<RecvAction<L2>> for SendAction<L1> {}
+
+impl<L1, L2> ActionsDisjoint<SendAction<L2>> for RecvAction<L1> {}
+
+impl<L1> ActionsDisjoint<OtherAction> for SendAction<L1> {}
+
+impl<L1> ActionsDisjoint<OtherAction> for RecvAction<L1> {}
+
+impl<L2> ActionsDisjoint<SendAction<L2>> for OtherAction {}
+
+impl<L2> ActionsDisjoint<RecvAction<L2>> for OtherAction {}
+
+impl ActionsDisjoint<OtherAction> for OtherAction {}
+
+/// No branch of a type-level list starts with an action confusable with `Shape`.
+pub trait DisjointFromAll<Shape> {}
+
+impl<Shape> DisjointFromAll<Shape> for Nil {}
+
+impl<Shape, H, T> DisjointFromAll<Shape> for Cons<H, T>
+where
+    H: ActionShape,
+    Shape: ActionsDisjoint<<H as ActionShape>::Shape>,
+    T: DisjointFromAll<Shape>,
+{
+}
+
+/// The branches of a type-level list start with pairwise disjoint actions.
+pub trait PairwiseDisjointActions {}
+
+impl PairwiseDisjointActions for Nil {}
+
+impl<H, T> PairwiseDisjointActions for Cons<H, T>
+where
+    H: ActionShape,
+    T: DisjointFromAll<<H as ActionShape>::Shape> + PairwiseDisjointActions,
+{
+}
+
+/// Every local session type in a type-level list is well formed for `Me`.
+pub trait EachLocallyWellFormed<IO, Me> {}
+
+impl<IO, Me> EachLocallyWellFormed<IO, Me> for Nil {}
+
+impl<IO, Me, H, T> EachLocallyWellFormed<IO, Me> for Cons<H, T>
+where
+    H: LocallyWellFormed<IO, Me>,
+    T: EachLocallyWellFormed<IO, Me>,
+{
+}
+
+// Leading actions
+
+impl<IO, Lbl: types::ProtocolLabel, R, H, T> ActionShape for EpSend<IO, Lbl, R, H, T> {
+    type Shape = SendAction<Lbl>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R, H, T> ActionShape for EpRecv<IO, Lbl, R, H, T> {
+    type Shape = RecvAction<Lbl>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, ToSet, H, T> ActionShape
+    for EpBroadcast<IO, Lbl, Me, ToSet, H, T>
+{
+    type Shape = SendAction<Lbl>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, FromSet, H, T> ActionShape
+    for EpGather<IO, Lbl, Me, FromSet, H, T>
+{
+    type Shape = RecvAction<Lbl>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, DelegatedEp, Cont> ActionShape
+    for EpDelegate<IO, Lbl, Me, DelegatedEp, Cont>
+{
+    type Shape = SendAction<Lbl>;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R> ActionShape for EpEnd<IO, Lbl, R> {
+    type Shape = OtherAction;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, R> ActionShape for EpSkip<IO, Lbl, R> {
+    type Shape = OtherAction;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches> ActionShape for EpChoiceN<IO, Lbl, Me, Branches> {
+    type Shape = OtherAction;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches> ActionShape for EpSelect<IO, Lbl, Me, Branches> {
+    type Shape = OtherAction;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches> ActionShape for EpOffer<IO, Lbl, Me, Branches> {
+    type Shape = OtherAction;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, L, R> ActionShape for EpPar<IO, Lbl, Me, L, R> {
+    type Shape = OtherAction;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Branches> ActionShape for EpParN<IO, Lbl, Me, Branches> {
+    type Shape = OtherAction;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Steps> ActionShape for EpShuffle<IO, Lbl, Me, Steps> {
+    type Shape = OtherAction;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Var, Body> ActionShape for EpRec<IO, Lbl, Var, Body> {
+    type Shape = OtherAction;
+}
+
+impl<Var> ActionShape for EpVar<Var> {
+    type Shape = OtherAction;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Dur, Body, OnTimeout> ActionShape
+    for EpTimeout<IO, Lbl, Me, Dur, Body, OnTimeout>
+{
+    type Shape = OtherAction;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Dur, T> ActionShape for EpDelay<IO, Lbl, Me, Dur, T> {
+    type Shape = OtherAction;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Dur, T> ActionShape for EpDeadline<IO, Lbl, Me, Dur, T> {
+    type Shape = OtherAction;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Scope, Handler> ActionShape
+    for EpInterrupt<IO, Lbl, Me, Scope, Handler>
+{
+    type Shape = OtherAction;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Scope, Handler> ActionShape
+    for EpInterruptible<IO, Lbl, Me, Scope, Handler>
+{
+    type Shape = OtherAction;
+}
+
+impl<Pred, Inner: ActionShape> ActionShape for EpRefine<Pred, Inner> {
+    type Shape = <Inner as ActionShape>::Shape;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Body, Cont> ActionShape
+    for EpOptionalSelect<IO, Lbl, Me, Body, Cont>
+{
+    type Shape = OtherAction;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Body, Cont> ActionShape
+    for EpOptionalOffer<IO, Lbl, Me, Body, Cont>
+{
+    type Shape = OtherAction;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Err> ActionShape for EpThrow<IO, Lbl, Me, Err> {
+    type Shape = OtherAction;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Body, Handler> ActionShape
+    for EpCatch<IO, Lbl, Me, Body, Handler>
+{
+    type Shape = OtherAction;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, High, Low> ActionShape
+    for EpPriorityOffer<IO, Lbl, Me, High, Low>
+{
+    type Shape = OtherAction;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Peer, Cont> ActionShape
+    for EpClose<IO, Lbl, Me, Peer, Cont>
+{
+    type Shape = OtherAction;
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Peer, Cont> ActionShape
+    for EpOpen<IO, Lbl, Me, Peer, Cont>
+{
+    type Shape = OtherAction;
+}
+
+// Leaves
+
+impl<IO, Lbl: types::ProtocolLabel, Me> LocallyWellFormed<IO, Me> for EpEnd<IO, Lbl, Me> {}
+
+impl<IO, Lbl: types::ProtocolLabel, Me> LocallyWellFormed<IO, Me> for EpSkip<IO, Lbl, Me> {}
+
+impl<IO, Me, Var> LocallyWellFormed<IO, Me> for EpVar<Var> {}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Err> LocallyWellFormed<IO, Me>
+    for EpThrow<IO, Lbl, Me, Err>
+{
+}
+
+// Communication: never with oneself
+
+impl<IO, Lbl: types::ProtocolLabel, Me, H, T: LocallyWellFormed<IO, Me>> LocallyWellFormed<IO, Me>
+    for EpSend<IO, Lbl, Me, H, T>
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, H, T: LocallyWellFormed<IO, Me>> LocallyWellFormed<IO, Me>
+    for EpRecv<IO, Lbl, Me, H, T>
+{
+}
+
+impl<IO, Lbl, Me, ToSet, H, T> LocallyWellFormed<IO, Me> for EpBroadcast<IO, Lbl, Me, ToSet, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    Me: InRoleSet<ToSet, Output = types::False>,
+    T: LocallyWellFormed<IO, Me>,
+{
+}
+
+impl<IO, Lbl, Me, FromSet, H, T> LocallyWellFormed<IO, Me> for EpGather<IO, Lbl, Me, FromSet, H, T>
+where
+    Lbl: types::ProtocolLabel,
+    Me: InRoleSet<FromSet, Output = types::False>,
+    T: LocallyWellFormed<IO, Me>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, DelegatedEp, Cont: LocallyWellFormed<IO, Me>>
+    LocallyWellFormed<IO, Me> for EpDelegate<IO, Lbl, Me, DelegatedEp, Cont>
+{
+}
+
+impl<IO, Lbl, Me, Peer, Cont> LocallyWellFormed<IO, Me> for EpClose<IO, Lbl, Me, Peer, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Peer: RoleEq<Me, Output = types::False>,
+    Cont: LocallyWellFormed<IO, Me>,
+{
+}
+
+impl<IO, Lbl, Me, Peer, Cont> LocallyWellFormed<IO, Me> for EpOpen<IO, Lbl, Me, Peer, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Peer: RoleEq<Me, Output = types::False>,
+    Cont: LocallyWellFormed<IO, Me>,
+{
+}
+
+// Choices: at least one branch
+
+impl<IO, Lbl, Me, H, T> LocallyWellFormed<IO, Me> for EpChoiceN<IO, Lbl, Me, Cons<H, T>>
+where
+    Lbl: types::ProtocolLabel,
+    Cons<H, T>: EachLocallyWellFormed<IO, Me>,
+{
+}
+
+impl<IO, Lbl, Me, H, T> LocallyWellFormed<IO, Me> for EpSelect<IO, Lbl, Me, Cons<H, T>>
+where
+    Lbl: types::ProtocolLabel,
+    Cons<H, T>: EachLocallyWellFormed<IO, Me>,
+{
+}
+
+impl<IO, Lbl, Me, H, T> LocallyWellFormed<IO, Me> for EpOffer<IO, Lbl, Me, Cons<H, T>>
+where
+    Lbl: types::ProtocolLabel,
+    Cons<H, T>: EachLocallyWellFormed<IO, Me>,
+{
+}
+
+impl<IO, Lbl, Me, High, Low> LocallyWellFormed<IO, Me> for EpPriorityOffer<IO, Lbl, Me, High, Low>
+where
+    Lbl: types::ProtocolLabel,
+    High: LocallyWellFormed<IO, Me>,
+    Low: LocallyWellFormed<IO, Me>,
+{
+}
+
+impl<IO, Lbl, Me, Body, Cont> LocallyWellFormed<IO, Me>
+    for EpOptionalSelect<IO, Lbl, Me, Body, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Body: LocallyWellFormed<IO, Me>,
+    Cont: LocallyWellFormed<IO, Me>,
+{
+}
+
+impl<IO, Lbl, Me, Body, Cont> LocallyWellFormed<IO, Me> for EpOptionalOffer<IO, Lbl, Me, Body, Cont>
+where
+    Lbl: types::ProtocolLabel,
+    Body: LocallyWellFormed<IO, Me>,
+    Cont: LocallyWellFormed<IO, Me>,
+{
+}
+
+// Concurrency: branches must not start with confusable actions
+
+impl<IO, Lbl, Me, L, R> LocallyWellFormed<IO, Me> for EpPar<IO, Lbl, Me, L, R>
+where
+    Lbl: types::ProtocolLabel,
+    L: LocallyWellFormed<IO, Me> + ActionShape,
+    R: LocallyWellFormed<IO, Me> + ActionShape,
+    <L as ActionShape>::Shape: ActionsDisjoint<<R as ActionShape>::Shape>,
+{
+}
+
+impl<IO, Lbl, Me, Branches> LocallyWellFormed<IO, Me> for EpParN<IO, Lbl, Me, Branches>
+where
+    Lbl: types::ProtocolLabel,
+    Branches: EachLocallyWellFormed<IO, Me> + PairwiseDisjointActions,
+{
+}
+
+impl<IO, Lbl, Me, Steps> LocallyWellFormed<IO, Me> for EpShuffle<IO, Lbl, Me, Steps>
+where
+    Lbl: types::ProtocolLabel,
+    Steps: EachLocallyWellFormed<IO, Me> + PairwiseDisjointActions,
+{
+}
+
+// Recursion, timing, interrupts, failures and refinements: structural
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Var, Body: LocallyWellFormed<IO, Me>>
+    LocallyWellFormed<IO, Me> for EpRec<IO, Lbl, Var, Body>
+{
+}
+
+impl<IO, Lbl, Me, Dur, Body, OnTimeout> LocallyWellFormed<IO, Me>
+    for EpTimeout<IO, Lbl, Me, Dur, Body, OnTimeout>
+where
+    Lbl: types::ProtocolLabel,
+    Body: LocallyWellFormed<IO, Me>,
+    OnTimeout: LocallyWellFormed<IO, Me>,
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Dur, T: LocallyWellFormed<IO, Me>> LocallyWellFormed<IO, Me>
+    for EpDelay<IO, Lbl, Me, Dur, T>
+{
+}
+
+impl<IO, Lbl: types::ProtocolLabel, Me, Dur, T: LocallyWellFormed<IO, Me>> LocallyWellFormed<IO, Me>
+    for EpDeadline<IO, Lbl, Me, Dur, T>
+{
+}
+
+impl<IO, Lbl, Me, Scope, Handler> LocallyWellFormed<IO, Me>
+    for EpInterrupt<IO, Lbl, Me, Scope, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Scope: LocallyWellFormed<IO, Me>,
+    Handler: LocallyWellFormed<IO, Me>,
+{
+}
+
+impl<IO, Lbl, Me, Scope, Handler> LocallyWellFormed<IO, Me>
+    for EpInterruptible<IO, Lbl, Me, Scope, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Scope: LocallyWellFormed<IO, Me>,
+    Handler: LocallyWellFormed<IO, Me>,
+{
+}
+
+impl<IO, Lbl, Me, Body, Handler> LocallyWellFormed<IO, Me> for EpCatch<IO, Lbl, Me, Body, Handler>
+where
+    Lbl: types::ProtocolLabel,
+    Body: LocallyWellFormed<IO, Me>,
+    Handler: LocallyWellFormed<IO, Me>,
+{
+}
+
+impl<IO, Me, Pred, Inner: LocallyWellFormed<IO, Me>> LocallyWellFormed<IO, Me>
+    for EpRefine<Pred, Inner>
+{
+}
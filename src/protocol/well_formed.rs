@@ -0,0 +1,92 @@
+//! # Well-Formedness
+//!
+//! This module bundles the individual checks on global protocols behind a
+//! single trait, so that one assertion covers a protocol.
+//!
+//! Key components:
+//!
+//! - `WellFormed`: A global protocol passes every check of the active strictness level
+//! - `WellFormedAt`: A global protocol passes every check of a given strictness level
+//! - `BasicLevel` / `StrictLevel`: The strictness levels
+//! - `ActiveLevel`: The level checked by `WellFormed`, set by the `strict-well-formed` feature
+//!
+//! `BasicLevel` checks that recursion is guarded, that the protocol is
+//! connected, that every role learns the outcome of the choices it depends
+//! on, that no two steps share a label (ends may) and that the protocol
+//! projects onto every role it mentions. `StrictLevel` further requires
+//! linear channels, freedom from deadlock and progress. A failing check
+//! reports the diagnostic of the individual check.
+
+use super::awareness::ChoiceAware;
+use super::connectedness::Connected;
+use super::deadlock::DeadlockFree;
+use super::global::TSession;
+use super::linearity::Linear;
+use super::progress::HasProgress;
+use super::recursion::Guarded;
+use super::transforms::ProjectAll;
+use super::utils::UniqueList;
+use crate::introspection::{RolesOf, StepLabelsOf};
+
+/// Strictness level: guardedness, connectedness, knowledge of choice, unique
+/// labels and projectability onto every role.
+pub struct BasicLevel;
+
+/// Strictness level: the `BasicLevel` checks, linear channels, deadlock freedom
+/// and progress.
+pub struct StrictLevel;
+
+/// The strictness level checked by [`WellFormed`].
+#[cfg(not(feature = "strict-well-formed"))]
+pub type ActiveLevel = BasicLevel;
+
+/// The strictness level checked by [`WellFormed`].
+#[cfg(feature = "strict-well-formed")]
+pub type ActiveLevel = StrictLevel;
+
+/// A global protocol over `IO` that passes every check of strictness level
+/// `Level`.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Alice; struct Bob; struct Ping; struct Pong;
+/// impl Role for Alice {} impl Role for Bob {}
+/// impl ProtocolLabel for Ping {} impl ProtocolLabel for Pong {}
+/// impl RoleEq<Alice> for Alice { type Output = True; }
+/// impl RoleEq<Bob> for Alice   { type Output = False; }
+/// impl RoleEq<Alice> for Bob   { type Output = False; }
+/// impl RoleEq<Bob> for Bob     { type Output = True; }
+/// impl LabelEq<Ping> for Ping { type Output = True; }
+/// impl LabelEq<Pong> for Ping { type Output = False; }
+/// impl LabelEq<Ping> for Pong { type Output = False; }
+/// impl LabelEq<Pong> for Pong { type Output = True; }
+///
+/// type G = TMsg<Http, Ping, Alice, Bob, Message,
+///          TMsg<Http, Pong, Bob, Alice, Response, TEnd<Http>>>;
+/// assert_well_formed!(Http; G, StrictLevel);
+/// ```
+pub trait WellFormedAt<IO, Level>: TSession<IO> {}
+
+impl<IO, G> WellFormedAt<IO, BasicLevel> for G
+where
+    G: TSession<IO> + Guarded + Connected + ChoiceAware + StepLabelsOf + RolesOf,
+    <G as StepLabelsOf>::Labels: UniqueList,
+    (): ProjectAll<IO, G>,
+{
+}
+
+impl<IO, G> WellFormedAt<IO, StrictLevel> for G where
+    G: WellFormedAt<IO, BasicLevel> + Linear + DeadlockFree + HasProgress
+{
+}
+
+/// A global protocol over `IO` that passes every check of the
+/// [`ActiveLevel`].
+///
+/// The level is [`BasicLevel`] unless the `strict-well-formed` feature is
+/// enabled. Check a protocol with
+/// [`assert_well_formed!`](crate::assert_well_formed).
+pub trait WellFormed<IO>: WellFormedAt<IO, ActiveLevel> {}
+
+impl<IO, G: WellFormedAt<IO, ActiveLevel>> WellFormed<IO> for G {}
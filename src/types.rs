@@ -7,9 +7,8 @@
 //! - See `protocol.rs` for how these types are used in session combinators.
 //! - See crate-level docs for protocol examples and macro usage.
 
-use crate::sealed;
-use crate::EpSession;
 use core::marker::PhantomData;
+use core::time::Duration;
 
 /// Marker type for a generic protocol message.
 pub struct Message;
@@ -22,6 +21,123 @@ pub struct Notify;
 /// Marker type for a subscribe event.
 pub struct Subscribe;
 
+/// Payload wrapper that carries a const-generic wire discriminant.
+///
+/// Use it when several branches of a choice carry the same Rust payload type:
+/// `Tagged<0, Message>` and `Tagged<1, Message>` are distinct types, so the
+/// branches stay distinguishable without inventing payload newtypes.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct L1; impl ProtocolLabel for L1 {}
+/// struct L2; impl ProtocolLabel for L2 {}
+/// type Choice = TChoice<
+///     Http,
+///     EmptyLabel,
+///     TInteract<Http, L1, TClient, Tagged<0, Message>, TEnd<Http>>,
+///     TInteract<Http, L2, TClient, Tagged<1, Message>, TEnd<Http>>,
+/// >;
+/// assert_eq!(<Tagged<1, Message> as MessageTag>::TAG, 1);
+/// ```
+pub struct Tagged<const TAG: u32, H>(PhantomData<H>);
+
+/// Access to the wire discriminant and the inner payload of a tagged message.
+pub trait MessageTag {
+    /// Discriminant written into the wire frame for this message.
+    const TAG: u32;
+    /// The payload type being tagged.
+    type Payload;
+}
+
+impl<const TAG: u32, H> MessageTag for Tagged<TAG, H> {
+    const TAG: u32 = TAG;
+    type Payload = H;
+}
+
+/// Duration marker for timed protocol constructs.
+///
+/// Carries a duration in the protocol type so runtime backends and exporters
+/// can read timing constraints back without any runtime configuration.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// use std::time::Duration;
+/// assert_eq!(<Millis<1500> as DurationMarker>::DURATION, Duration::from_millis(1500));
+/// assert_eq!(<Secs<2> as DurationMarker>::DURATION, Duration::from_secs(2));
+/// ```
+pub trait DurationMarker {
+    /// The duration this marker stands for.
+    const DURATION: Duration;
+}
+
+/// Duration marker of `N` milliseconds.
+pub struct Millis<const N: u64>;
+/// Duration marker of `N` seconds.
+pub struct Secs<const N: u64>;
+
+impl<const N: u64> DurationMarker for Millis<N> {
+    const DURATION: Duration = Duration::from_millis(N);
+}
+
+impl<const N: u64> DurationMarker for Secs<N> {
+    const DURATION: Duration = Duration::from_secs(N);
+}
+
+/// Weight marker for probabilistic choices.
+///
+/// Carries a relative weight in the protocol type so simulators and exporters
+/// can read branch probabilities back without any runtime configuration.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// assert_eq!(<Weight<3> as WeightMarker>::WEIGHT, 3);
+/// ```
+pub trait WeightMarker {
+    /// The relative weight this marker stands for.
+    const WEIGHT: u32;
+}
+
+/// Weight marker of `N`.
+pub struct Weight<const N: u32>;
+
+impl<const N: u32> WeightMarker for Weight<N> {
+    const WEIGHT: u32 = N;
+}
+
+/// Type-level predicate attached to a message by `TRefine`.
+///
+/// Carries a description that exporters and error reports can display. Value
+/// predicates additionally implement `CheckPredicate` so runtimes can enforce
+/// them on the payload.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Positive;
+/// impl Predicate for Positive {
+///     const DESCRIPTION: &'static str = "value > 0";
+/// }
+/// impl CheckPredicate<i64> for Positive {
+///     fn check(value: &i64) -> bool {
+///         *value > 0
+///     }
+/// }
+/// assert!(<Positive as CheckPredicate<i64>>::check(&3));
+/// ```
+pub trait Predicate {
+    /// Human-readable form of the constraint.
+    const DESCRIPTION: &'static str;
+}
+
+/// Runtime check of a `Predicate` against a payload of type `H`.
+pub trait CheckPredicate<H>: Predicate {
+    /// Returns `true` if `value` satisfies the predicate.
+    fn check(value: &H) -> bool;
+}
+
 /// Marker type for HTTP protocol.
 pub struct Http;
 /// Marker type for a database protocol.
@@ -81,6 +197,31 @@ impl BoolOr<False> for False {
     type Output = False;
 }
 
+/// Boolean AND type-level function
+/// Returns `True` if both A and B are `True`, otherwise `False`
+pub type And<A, B> = <A as BoolAnd<B>>::Output;
+
+/// Helper trait for implementing boolean AND at the type level
+pub trait BoolAnd<B> {
+    type Output: Bool;
+}
+
+impl BoolAnd<True> for True {
+    type Output = True;
+}
+
+impl BoolAnd<False> for True {
+    type Output = False;
+}
+
+impl BoolAnd<True> for False {
+    type Output = False;
+}
+
+impl BoolAnd<False> for False {
+    type Output = False;
+}
+
 /// Boolean NOT type-level function
 /// Returns `True` if input is `False`, otherwise `False`
 pub trait Not {
@@ -106,16 +247,45 @@ impl IsEq<False> for False {}
 ///
 /// Implement this trait for any type you want to use as a protocol label.
 /// Labels are used for recursion, branching, and protocol analysis.
-pub trait ProtocolLabel {}
+///
+/// Runtime reflection, diagrams and monitor errors show a label by its
+/// `NAME`, or by its type name if `NAME` is not set. With the `derive`
+/// feature, `#[derive(ProtocolLabel)]` sets `NAME` to the name of the type,
+/// or to the one given by `#[besedarium(name = "...")]`.
+///
+/// # Examples
+/// ```rust
+/// use besedarium::*;
+/// struct Login;
+/// impl ProtocolLabel for Login {
+///     const NAME: Option<&'static str> = Some("LoginRequest");
+/// }
+/// struct Logout; impl ProtocolLabel for Logout {}
+/// assert_eq!(Login::name(), "LoginRequest");
+/// assert_eq!(Logout::name(), std::any::type_name::<Logout>());
+/// ```
+pub trait ProtocolLabel {
+    /// The name shown for this label; `None` shows the type name.
+    const NAME: Option<&'static str> = None;
+
+    /// `NAME`, or the type name if `NAME` is not set.
+    fn name() -> &'static str {
+        match Self::NAME {
+            Some(name) => name,
+            None => core::any::type_name::<Self>(),
+        }
+    }
+}
 
 /// Empty label type for protocol ends or unlabeled combinators.
 pub struct EmptyLabel;
-impl ProtocolLabel for EmptyLabel {}
+impl ProtocolLabel for EmptyLabel {
+    const NAME: Option<&'static str> = Some("EmptyLabel");
+}
 
-/// Silent/no-op endpoint type for roles not present in any protocol branch.
+/// Former silent/no-op endpoint type, never produced by projection.
 ///
-/// Used in endpoint projection to represent a role that is uninvolved in a parallel composition.
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
-pub struct EpSilent<IO, R>(PhantomData<(IO, R)>);
-impl<IO, R> EpSession<IO, R> for EpSilent<IO, R> {}
-impl<IO, R> sealed::Sealed for EpSilent<IO, R> {}
+/// `EpSkip` is the single endpoint for a role with nothing to do; this alias
+/// is the unlabelled `EpSkip`.
+#[deprecated(note = "use `EpSkip`, the single no-op endpoint produced by projection")]
+pub type EpSilent<IO, R> = crate::EpSkip<IO, EmptyLabel, R>;
@@ -0,0 +1,56 @@
+//! Tests for the clock abstraction used by timing features
+
+use besedarium::*;
+use std::time::Duration;
+
+#[cfg(test)]
+mod mock_clock_tests {
+    use super::*;
+
+    #[test]
+    fn test_mock_clock_starts_at_zero() {
+        let clock = MockClock::new();
+        assert_eq!(clock.now(), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_mock_clock_only_moves_when_advanced() {
+        let clock = MockClock::new();
+        let start = clock.now();
+        clock.advance(Duration::from_secs(2));
+        assert_eq!(clock.elapsed_since(start), Duration::from_secs(2));
+        assert!(clock.has_expired(start, Duration::from_secs(2)));
+        assert!(!clock.has_expired(start, Duration::from_secs(3)));
+    }
+
+    #[test]
+    fn test_mock_clock_saturates() {
+        let clock = MockClock::new();
+        clock.advance(Duration::MAX);
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(clock.now(), Duration::from_nanos(u64::MAX));
+    }
+
+    #[test]
+    fn test_clock_through_reference() {
+        fn elapsed<C: Clock>(clock: C, start: Duration) -> Duration {
+            clock.elapsed_since(start)
+        }
+        let clock = MockClock::new();
+        clock.advance(Duration::from_millis(5));
+        assert_eq!(elapsed(&clock, Duration::ZERO), Duration::from_millis(5));
+    }
+}
+
+#[cfg(test)]
+mod monotonic_clock_tests {
+    use super::*;
+
+    #[test]
+    fn test_monotonic_clock_never_goes_backwards() {
+        let clock = MonotonicClock::new();
+        let first = clock.now();
+        let second = clock.now();
+        assert!(second >= first);
+    }
+}
@@ -0,0 +1,4003 @@
+//! Tests for the extended global combinators
+//!
+//! This file contains tests for global combinators beyond the core
+//! TEnd/TInteract/TChoice/TPar/TRec set: their projection onto each role and
+//! the introspection traits (RolesOf, LabelsOf, ContainsRole) they implement.
+
+use besedarium::*;
+
+// --- Custom Label Types for Testing ---
+struct L1;
+struct L2;
+struct L3;
+struct L4;
+struct L5;
+impl ProtocolLabel for L1 {}
+impl ProtocolLabel for L2 {}
+impl ProtocolLabel for L3 {}
+impl ProtocolLabel for L4 {}
+impl ProtocolLabel for L5 {}
+
+// --- Custom Roles for Testing ---
+struct Alice;
+struct Bob;
+struct Charlie;
+impl Role for Alice {}
+impl Role for Bob {}
+impl Role for Charlie {}
+
+// --- Role equality implementations ---
+impl RoleEq<Alice> for Alice {
+    type Output = True;
+}
+impl RoleEq<Bob> for Alice {
+    type Output = False;
+}
+impl RoleEq<Charlie> for Alice {
+    type Output = False;
+}
+
+impl RoleEq<Alice> for Bob {
+    type Output = False;
+}
+impl RoleEq<Bob> for Bob {
+    type Output = True;
+}
+impl RoleEq<Charlie> for Bob {
+    type Output = False;
+}
+
+impl RoleEq<Alice> for Charlie {
+    type Output = False;
+}
+impl RoleEq<Bob> for Charlie {
+    type Output = False;
+}
+impl RoleEq<Charlie> for Charlie {
+    type Output = True;
+}
+
+// --- Label equality implementations ---
+// A label equals itself and differs from the other labels listed
+macro_rules! label_eq {
+    ($L:ident: $($Other:ident),*) => {
+        impl LabelEq<$L> for $L {
+            type Output = True;
+        }
+        $(
+            impl LabelEq<$Other> for $L {
+                type Output = False;
+            }
+        )*
+    };
+}
+
+label_eq!(L1: L2, L3, L4, L5);
+label_eq!(L2: L1, L3, L4, L5);
+label_eq!(L3: L1, L2, L4, L5);
+label_eq!(L4: L1, L2, L3, L5);
+label_eq!(L5: L1, L2, L3, L4);
+
+/// Type-level trait to verify that two type-level lists are the same
+pub trait SameList<T> {}
+impl SameList<Nil> for Nil {}
+impl<H, T1, T2> SameList<Cons<H, T2>> for Cons<H, T1> where T1: SameList<T2> {}
+
+// --- Shared role assertions ---
+fn assert_roles<G: RolesOf<Roles = R>, R>() {}
+fn assert_contains<G: ContainsRole<R, Output = True>, R>() {}
+fn assert_not_contains<G: ContainsRole<R, Output = False> + NotContainsRole<R>, R>() {}
+
+// --- Tests for TMsg ---
+#[cfg(test)]
+mod tmsg_tests {
+    use super::*;
+
+    // Alice sends to Bob, then Bob replies to Alice; Charlie never takes part
+    type Global =
+        TMsg<Http, L1, Alice, Bob, Message, TMsg<Http, L2, Bob, Alice, Response, TEnd<Http, L3>>>;
+
+    #[test]
+    fn test_tmsg_projection_sender_and_receiver() {
+        type AliceLocal = <() as ProjectRole<Alice, Http, Global>>::Out;
+        assert_type_eq!(
+            AliceLocal,
+            EpSend<Http, L1, Alice, Message, EpRecv<Http, L2, Alice, Response, EpEnd<Http, L3, Alice>>>
+        );
+
+        type BobLocal = <() as ProjectRole<Bob, Http, Global>>::Out;
+        assert_type_eq!(
+            BobLocal,
+            EpRecv<Http, L1, Bob, Message, EpSend<Http, L2, Bob, Response, EpEnd<Http, L3, Bob>>>
+        );
+    }
+
+    #[test]
+    fn test_tmsg_projection_bystander_is_silent() {
+        type CharlieLocal = <() as ProjectRole<Charlie, Http, Global>>::Out;
+        assert_type_eq!(CharlieLocal, EpEnd<Http, L3, Charlie>);
+    }
+
+    #[test]
+    fn test_tmsg_bystander_skips_choice() {
+        // Charlie never hears about the choice between Alice and Bob
+        type Pick = TChoice<
+            Http,
+            L1,
+            TMsg<Http, L2, Alice, Bob, Message, TEnd<Http>>,
+            TMsg<Http, L3, Alice, Bob, Response, TEnd<Http>>,
+        >;
+        assert_type_eq!(<() as ProjectRole<Charlie, Http, Pick>>::Out, EpSkip<Http, L1, Charlie>);
+
+        // With TInteract every role other than the sender receives
+        type Announce = TChoice<
+            Http,
+            L1,
+            TInteract<Http, L2, Alice, Message, TEnd<Http>>,
+            TInteract<Http, L3, Alice, Response, TEnd<Http>>,
+        >;
+        assert_type_eq!(
+            <() as ProjectRole<Charlie, Http, Announce>>::Out,
+            EpOffer<
+                Http,
+                L1,
+                Charlie,
+                tlist!(
+                    EpRecv<Http, L2, Charlie, Message, EpEnd<Http, EmptyLabel, Charlie>>,
+                    EpRecv<Http, L3, Charlie, Response, EpEnd<Http, EmptyLabel, Charlie>>,
+                ),
+            >
+        );
+    }
+
+    #[test]
+    fn test_tmsg_contains_role() {
+        assert_contains::<Global, Alice>();
+        assert_contains::<Global, Bob>();
+        assert_not_contains::<Global, Charlie>();
+    }
+
+    #[test]
+    fn test_tmsg_roles_and_labels() {
+        fn assert_labels<G: LabelsOf>()
+        where
+            <G as LabelsOf>::Labels: SameList<tlist!(L1, L2, L3)>,
+        {
+        }
+        assert_roles::<Global, tlist!(Alice, Bob)>();
+        assert_labels::<Global>();
+    }
+
+    #[test]
+    fn test_tmsg_compose() {
+        type First = TMsg<Http, L1, Alice, Bob, Message, TEnd<Http>>;
+        type Second = TMsg<Http, L2, Bob, Charlie, Response, TEnd<Http, L3>>;
+        assert_type_eq!(
+            <First as TSession<Http>>::Compose<Second>,
+            TMsg<Http, L1, Alice, Bob, Message, Second>
+        );
+    }
+}
+
+// --- Tests for TChoiceN ---
+#[cfg(test)]
+mod tchoicen_tests {
+    use super::*;
+
+    // Three-way choice: Alice sends, Bob sends, or the protocol ends silently.
+    // The first two branches share a label, so the branches are not tagged.
+    type Global = TChoiceN<
+        Http,
+        L1,
+        tlist!(
+            TInteract<Http, L1, Alice, Message, TEnd<Http>>,
+            TInteract<Http, L1, Bob, Response, TEnd<Http>>,
+            TEnd<Http, L3>
+        ),
+    >;
+
+    #[test]
+    fn test_tchoicen_projection_skips_silent_branches() {
+        type AliceLocal = <() as ProjectRole<Alice, Http, Global>>::Out;
+        assert_type_eq!(
+            AliceLocal,
+            EpChoiceN<
+                Http,
+                L1,
+                Alice,
+                tlist!(
+                    EpSend<Http, L1, Alice, Message, EpEnd<Http, EmptyLabel, Alice>>,
+                    EpRecv<Http, L1, Alice, Response, EpEnd<Http, EmptyLabel, Alice>>,
+                    EpSkip<Http, L1, Alice>
+                ),
+            >
+        );
+    }
+
+    #[test]
+    fn test_tchoicen_projection_absent_role_is_skip() {
+        type CharlieLocal = <() as ProjectRole<
+            Charlie,
+            Http,
+            TChoiceN<Http, L2, tlist!(TEnd<Http>, TEnd<Http>)>,
+        >>::Out;
+        assert_type_eq!(CharlieLocal, EpSkip<Http, L2, Charlie>);
+    }
+
+    #[test]
+    fn test_tchoicen_contains_role() {
+        assert_contains::<Global, Alice>();
+        assert_contains::<Global, Bob>();
+        assert_not_contains::<TChoiceN<Http, L1, tlist!(TEnd<Http>, TEnd<Http, L2>)>, Charlie>();
+    }
+
+    #[test]
+    fn test_tchoicen_roles_and_labels() {
+        fn assert_labels<G: LabelsOf>()
+        where
+            <G as LabelsOf>::Labels: SameList<tlist!(L1, L1, EmptyLabel, L1, EmptyLabel, L3)>,
+        {
+        }
+        assert_roles::<Global, tlist!(Alice, Bob)>();
+        assert_labels::<Global>();
+    }
+
+    // Alice picks one of three tagged requests; Bob serves two of them
+    type Tagged = TChoiceN<
+        Http,
+        L1,
+        tlist!(
+            TMsg<Http, L1, Alice, Bob, Message, TEnd<Http>>,
+            TMsg<Http, L2, Alice, Bob, Publish, TEnd<Http>>,
+            TMsg<Http, L3, Alice, Charlie, Notify, TEnd<Http>>,
+        ),
+    >;
+
+    #[test]
+    fn test_tagged_tchoicen_projects_to_select_and_offer() {
+        assert_type_eq!(
+            <() as ProjectRole<Alice, Http, Tagged>>::Out,
+            EpSelect<
+                Http,
+                L1,
+                Alice,
+                tlist!(
+                    EpSend<Http, L1, Alice, Message, EpEnd<Http, EmptyLabel, Alice>>,
+                    EpSend<Http, L2, Alice, Publish, EpEnd<Http, EmptyLabel, Alice>>,
+                    EpSend<Http, L3, Alice, Notify, EpEnd<Http, EmptyLabel, Alice>>,
+                ),
+            >
+        );
+        // The branch Bob is not in keeps its own tag
+        assert_type_eq!(
+            <() as ProjectRole<Bob, Http, Tagged>>::Out,
+            EpOffer<
+                Http,
+                L1,
+                Bob,
+                tlist!(
+                    EpRecv<Http, L1, Bob, Message, EpEnd<Http, EmptyLabel, Bob>>,
+                    EpRecv<Http, L2, Bob, Publish, EpEnd<Http, EmptyLabel, Bob>>,
+                    EpSkip<Http, L3, Bob>,
+                ),
+            >
+        );
+    }
+
+    #[test]
+    fn test_tagged_tchoicen_branch_tags() {
+        type CharlieLocal = <() as ProjectRole<Charlie, Http, Tagged>>::Out;
+        type CharlieBranches = tlist!(
+            EpSkip<Http, L1, Charlie>,
+            EpSkip<Http, L2, Charlie>,
+            EpRecv<Http, L3, Charlie, Notify, EpEnd<Http, EmptyLabel, Charlie>>,
+        );
+        assert_type_eq!(CharlieLocal, EpOffer<Http, L1, Charlie, CharlieBranches>);
+        assert_type_eq!(<CharlieLocal as BranchTags>::Tags, tlist!(L1, L2, L3));
+        assert_type_eq!(
+            <CharlieBranches as FindBranch<L3>>::Output,
+            EpRecv<Http, L3, Charlie, Notify, EpEnd<Http, EmptyLabel, Charlie>>
+        );
+    }
+
+    #[test]
+    fn test_branches_tagged() {
+        fn assert_tagged<B: BranchesTagged<Output = T>, T>() {}
+        assert_tagged::<
+            tlist!(
+                TMsg<Http, L1, Alice, Bob, Message, TEnd<Http>>,
+                TMsg<Http, L2, Alice, Bob, Message, TEnd<Http>>,
+            ),
+            True,
+        >();
+        assert_tagged::<
+            tlist!(
+                TMsg<Http, L1, Alice, Bob, Message, TEnd<Http>>,
+                TMsg<Http, L2, Alice, Bob, Message, TEnd<Http>>,
+                TMsg<Http, L1, Bob, Alice, Message, TEnd<Http>>,
+            ),
+            False,
+        >();
+        assert_tagged::<tlist!(TEnd<Http>, TEnd<Http>), False>();
+    }
+
+    #[test]
+    fn test_tchoicen_compose() {
+        type Branches = tlist!(TEnd<Http>, TInteract<Http, L2, Bob, Response, TEnd<Http>>);
+        type Next = TInteract<Http, L3, Alice, Message, TEnd<Http>>;
+        assert_type_eq!(
+            <TChoiceN<Http, L1, Branches> as TSession<Http>>::Compose<Next>,
+            TChoiceN<Http, L1, tlist!(Next, TInteract<Http, L2, Bob, Response, Next>)>
+        );
+    }
+}
+
+// --- Tests for TParN ---
+#[cfg(test)]
+mod tparn_tests {
+    use super::*;
+
+    // Alice talks to Bob while the other branch is silent
+    type Global =
+        TParN<Http, L1, tlist!(TMsg<Http, L2, Alice, Bob, Message, TEnd<Http>>, TEnd<Http, L3>)>;
+
+    #[test]
+    fn test_tparn_projection_keeps_participating_branches() {
+        type AliceLocal = <() as ProjectRole<Alice, Http, Global>>::Out;
+        assert_type_eq!(
+            AliceLocal,
+            EpParN<
+                Http,
+                L1,
+                Alice,
+                tlist!(EpSend<Http, L2, Alice, Message, EpEnd<Http, EmptyLabel, Alice>>),
+            >
+        );
+    }
+
+    #[test]
+    fn test_tparn_projection_absent_role_is_skip() {
+        type CharlieLocal = <() as ProjectRole<Charlie, Http, Global>>::Out;
+        assert_type_eq!(CharlieLocal, EpSkip<Http, L1, Charlie>);
+    }
+
+    #[test]
+    fn test_tparn_contains_role() {
+        assert_contains::<Global, Alice>();
+        assert_contains::<Global, Bob>();
+        assert_not_contains::<Global, Charlie>();
+    }
+
+    #[test]
+    fn test_tparn_roles_labels_and_disjointness() {
+        type Split = TParN<
+            Http,
+            L1,
+            tlist!(
+                TInteract<Http, L2, Alice, Message, TEnd<Http>>,
+                TInteract<Http, L3, Bob, Response, TEnd<Http>>
+            ),
+        >;
+        fn assert_labels<G: LabelsOf>()
+        where
+            <G as LabelsOf>::Labels: SameList<tlist!(L1, L2, EmptyLabel, L3, EmptyLabel)>,
+        {
+        }
+        fn assert_disjoint<G: AssertDisjoint<Output = True>>() {}
+        assert_roles::<Split, tlist!(Alice, Bob)>();
+        assert_labels::<Split>();
+        assert_disjoint::<Split>();
+    }
+
+    #[test]
+    fn test_tpar_macro_builds_flat_tparn() {
+        type Par = tpar!(Http;
+            TInteract<Http, L1, Alice, Message, TEnd<Http>>,
+            TInteract<Http, L2, Bob, Response, TEnd<Http>>,
+            TInteract<Http, L3, Charlie, Publish, TEnd<Http>>,
+        );
+        assert_type_eq!(
+            Par,
+            TParN<
+                Http,
+                EmptyLabel,
+                tlist!(
+                    TInteract<Http, L1, Alice, Message, TEnd<Http>>,
+                    TInteract<Http, L2, Bob, Response, TEnd<Http>>,
+                    TInteract<Http, L3, Charlie, Publish, TEnd<Http>>
+                ),
+            >
+        );
+    }
+}
+
+// --- Tests for TSelect / TOffer ---
+#[cfg(test)]
+mod directed_choice_tests {
+    use super::*;
+
+    // Alice either sends Bob a message or ends; Charlie is never involved
+    type Branches = tlist!(TMsg<Http, L2, Alice, Bob, Message, TEnd<Http>>, TEnd<Http, L3>);
+    type Select = TSelect<Http, L1, Alice, Branches>;
+    type Offer = TOffer<Http, L1, Bob, Branches>;
+
+    type AliceBranches = tlist!(
+        EpSend<Http, L2, Alice, Message, EpEnd<Http, EmptyLabel, Alice>>,
+        EpSkip<Http, L1, Alice>
+    );
+    type BobBranches = tlist!(
+        EpRecv<Http, L2, Bob, Message, EpEnd<Http, EmptyLabel, Bob>>,
+        EpSkip<Http, L1, Bob>
+    );
+
+    #[test]
+    fn test_tselect_projection() {
+        assert_type_eq!(
+            <() as ProjectRole<Alice, Http, Select>>::Out,
+            EpSelect<Http, L1, Alice, AliceBranches>
+        );
+        assert_type_eq!(
+            <() as ProjectRole<Bob, Http, Select>>::Out,
+            EpOffer<Http, L1, Bob, BobBranches>
+        );
+        assert_type_eq!(
+            <() as ProjectRole<Charlie, Http, Select>>::Out,
+            EpSkip<Http, L1, Charlie>
+        );
+    }
+
+    #[test]
+    fn test_toffer_projection() {
+        assert_type_eq!(
+            <() as ProjectRole<Bob, Http, Offer>>::Out,
+            EpOffer<Http, L1, Bob, BobBranches>
+        );
+        assert_type_eq!(
+            <() as ProjectRole<Alice, Http, Offer>>::Out,
+            EpSelect<Http, L1, Alice, AliceBranches>
+        );
+        assert_type_eq!(
+            <() as ProjectRole<Charlie, Http, Offer>>::Out,
+            EpSkip<Http, L1, Charlie>
+        );
+    }
+
+    #[test]
+    fn test_chooser_without_actions_still_selects() {
+        type Silent = TSelect<Http, L1, Charlie, tlist!(TEnd<Http>, TEnd<Http, L2>)>;
+        assert_type_eq!(
+            <() as ProjectRole<Charlie, Http, Silent>>::Out,
+            EpSelect<Http, L1, Charlie, tlist!(EpSkip<Http, L1, Charlie>, EpSkip<Http, L1, Charlie>)>
+        );
+    }
+
+    #[test]
+    fn test_directed_choice_contains_role() {
+        assert_contains::<Select, Alice>();
+        assert_contains::<Select, Bob>();
+        assert_not_contains::<Select, Charlie>();
+        assert_contains::<TOffer<Http, L1, Charlie, tlist!(TEnd<Http>)>, Charlie>();
+        assert_not_contains::<Offer, Charlie>();
+    }
+
+    #[test]
+    fn test_directed_choice_roles_and_labels() {
+        fn assert_labels<G: LabelsOf>()
+        where
+            <G as LabelsOf>::Labels: SameList<tlist!(L1, L2, EmptyLabel, L3)>,
+        {
+        }
+        assert_roles::<Select, tlist!(Alice, Bob)>();
+        assert_roles::<Offer, tlist!(Bob, Alice)>();
+        assert_labels::<Select>();
+        assert_labels::<Offer>();
+    }
+}
+
+// --- Tests for TRecX / TVar ---
+#[cfg(test)]
+mod trecx_tests {
+    use super::*;
+
+    struct Outer;
+    struct Inner;
+    impl VarEq<Outer> for Outer {
+        type Output = True;
+    }
+    impl VarEq<Inner> for Outer {
+        type Output = False;
+    }
+    impl VarEq<Outer> for Inner {
+        type Output = False;
+    }
+    impl VarEq<Inner> for Inner {
+        type Output = True;
+    }
+
+    // Alice keeps pinging Bob until Alice tells Bob to stop; Charlie is not involved
+    type Ping = TRecX<
+        Http,
+        L1,
+        Outer,
+        TChoice<
+            Http,
+            L2,
+            TMsg<Http, L3, Alice, Bob, Message, TVar<Outer>>,
+            TMsg<Http, L1, Alice, Bob, Response, TEnd<Http>>,
+        >,
+    >;
+
+    #[test]
+    fn test_trecx_projection() {
+        assert_type_eq!(
+            <() as ProjectRole<Alice, Http, Ping>>::Out,
+            EpRec<
+                Http,
+                L1,
+                Outer,
+                EpSelect<
+                    Http,
+                    L2,
+                    Alice,
+                    tlist!(
+                        EpSend<Http, L3, Alice, Message, EpVar<Outer>>,
+                        EpSend<Http, L1, Alice, Response, EpEnd<Http, EmptyLabel, Alice>>,
+                    ),
+                >,
+            >
+        );
+        // Bob does not act first in either branch, so Bob learns Alice's pick from the label
+        assert_type_eq!(
+            <() as ProjectRole<Bob, Http, Ping>>::Out,
+            EpRec<
+                Http,
+                L1,
+                Outer,
+                EpOffer<
+                    Http,
+                    L2,
+                    Bob,
+                    tlist!(
+                        EpRecv<Http, L3, Bob, Message, EpVar<Outer>>,
+                        EpRecv<Http, L1, Bob, Response, EpEnd<Http, EmptyLabel, Bob>>,
+                    ),
+                >,
+            >
+        );
+        assert_type_eq!(
+            <() as ProjectRole<Charlie, Http, Ping>>::Out,
+            EpSkip<Http, L1, Charlie>
+        );
+    }
+
+    #[test]
+    fn test_trecx_local_loop() {
+        type AliceLoop = <() as ProjectRole<Alice, Http, Ping>>::Out;
+        assert_type_eq!(<AliceLoop as GetLocalLabel>::Label, L1);
+        assert_type_eq!(IsSkip<AliceLoop, Http, Alice>, False);
+        assert_type_eq!(IsEnd<AliceLoop, Http, Alice>, False);
+        assert_type_eq!(<EpVar<Outer> as GetLocalLabel>::Label, EmptyLabel);
+        assert_type_eq!(IsSkip<EpVar<Outer>, Http, Alice>, False);
+    }
+
+    #[test]
+    fn test_trecx_contains_role() {
+        assert_contains::<Ping, Alice>();
+        assert_contains::<Ping, Bob>();
+        assert_not_contains::<Ping, Charlie>();
+    }
+
+    #[test]
+    fn test_trecx_guarded_and_well_scoped() {
+        fn assert_guarded<G: Guarded>() {}
+        fn assert_closed<G: WellScoped<Nil>>() {}
+        assert_guarded::<Ping>();
+        assert_closed::<Ping>();
+
+        // Inner loops may jump to any enclosing binder
+        type Nested = TRecX<
+            Http,
+            L1,
+            Outer,
+            TRecX<
+                Http,
+                L2,
+                Inner,
+                TChoice<
+                    Http,
+                    L3,
+                    TInteract<Http, EmptyLabel, Alice, Message, TVar<Inner>>,
+                    TInteract<Http, EmptyLabel, Bob, Response, TVar<Outer>>,
+                >,
+            >,
+        >;
+        assert_guarded::<Nested>();
+        assert_closed::<Nested>();
+    }
+
+    #[test]
+    fn test_trec_projection_keeps_label() {
+        type Stream = TRec<Http, L1, TMsg<Http, L2, Alice, Bob, Message, TEnd<Http>>>;
+        assert_type_eq!(
+            <() as ProjectRole<Alice, Http, Stream>>::Out,
+            EpRec<Http, L1, L1, EpSend<Http, L2, Alice, Message, EpEnd<Http, EmptyLabel, Alice>>>
+        );
+        assert_type_eq!(
+            <() as ProjectRole<Bob, Http, Stream>>::Out,
+            EpRec<Http, L1, L1, EpRecv<Http, L2, Bob, Message, EpEnd<Http, EmptyLabel, Bob>>>
+        );
+        assert_type_eq!(
+            <() as ProjectRole<Charlie, Http, Stream>>::Out,
+            EpSkip<Http, L1, Charlie>
+        );
+    }
+
+    #[test]
+    fn test_trecx_scope_lookup() {
+        fn assert_bound<B: BindsVar<V, Output = True>, V>() {}
+        fn assert_unbound<B: BindsVar<V, Output = False>, V>() {}
+        assert_bound::<tlist!(Inner, Outer), Outer>();
+        assert_unbound::<tlist!(Inner), Outer>();
+        assert_unbound::<Nil, Outer>();
+    }
+
+    #[test]
+    fn test_trecx_roles_labels_and_compose() {
+        fn assert_labels<G: LabelsOf>()
+        where
+            <G as LabelsOf>::Labels: SameList<tlist!(L1, L2, L3, L1, EmptyLabel)>,
+        {
+        }
+        assert_roles::<Ping, tlist!(Alice, Bob)>();
+        assert_labels::<Ping>();
+
+        // Composition continues after the loop exits, never after a jump
+        type Next = TEnd<Http, L3>;
+        assert_type_eq!(
+            <Ping as TSession<Http>>::Compose<Next>,
+            TRecX<
+                Http,
+                L1,
+                Outer,
+                TChoice<
+                    Http,
+                    L2,
+                    TMsg<Http, L3, Alice, Bob, Message, TVar<Outer>>,
+                    TMsg<Http, L1, Alice, Bob, Response, Next>,
+                >,
+            >
+        );
+    }
+}
+
+// --- Tests for TTimeout ---
+#[cfg(test)]
+mod ttimeout_tests {
+    use super::*;
+    use std::time::Duration;
+
+    // Bob must answer Alice within 500ms, otherwise Alice notifies Charlie
+    type Global = TTimeout<
+        Http,
+        L1,
+        Millis<500>,
+        TMsg<Http, L2, Bob, Alice, Response, TEnd<Http>>,
+        TMsg<Http, L3, Alice, Charlie, Notify, TEnd<Http>>,
+    >;
+
+    #[test]
+    fn test_ttimeout_projection() {
+        assert_type_eq!(
+            <() as ProjectRole<Alice, Http, Global>>::Out,
+            EpTimeout<
+                Http,
+                L1,
+                Alice,
+                Millis<500>,
+                EpRecv<Http, L2, Alice, Response, EpEnd<Http, EmptyLabel, Alice>>,
+                EpSend<Http, L3, Alice, Notify, EpEnd<Http, EmptyLabel, Alice>>,
+            >
+        );
+        // Charlie only takes part when the timer fires
+        assert_type_eq!(
+            <() as ProjectRole<Charlie, Http, Global>>::Out,
+            EpTimeout<
+                Http,
+                L1,
+                Charlie,
+                Millis<500>,
+                EpSkip<Http, L1, Charlie>,
+                EpRecv<Http, L3, Charlie, Notify, EpEnd<Http, EmptyLabel, Charlie>>,
+            >
+        );
+    }
+
+    #[test]
+    fn test_ttimeout_local_type() {
+        type AliceEp = <() as ProjectRole<Alice, Http, Global>>::Out;
+        assert_type_eq!(<AliceEp as GetLocalLabel>::Label, L1);
+        assert_type_eq!(<AliceEp as GetLocalRole>::Role, Alice);
+        assert_type_eq!(IsSkip<AliceEp, Http, Alice>, False);
+        assert_type_eq!(IsEnd<AliceEp, Http, Alice>, False);
+    }
+
+    #[test]
+    fn test_ttimeout_uninvolved_role_is_skip() {
+        type Quiet = TTimeout<
+            Http,
+            L1,
+            Secs<1>,
+            TMsg<Http, L2, Alice, Bob, Message, TEnd<Http>>,
+            TEnd<Http>,
+        >;
+        assert_type_eq!(
+            <() as ProjectRole<Charlie, Http, Quiet>>::Out,
+            EpSkip<Http, L1, Charlie>
+        );
+    }
+
+    #[test]
+    fn test_ttimeout_timing_metadata() {
+        fn assert_timing<G: TimingOf<Dur = D>, D>() {}
+        assert_timing::<Global, Millis<500>>();
+        assert_eq!(<Global as TimingOf>::DURATION, Duration::from_millis(500));
+        assert_eq!(
+            <Secs<3> as DurationMarker>::DURATION,
+            Duration::from_secs(3)
+        );
+    }
+
+    #[test]
+    fn test_ttimeout_roles_labels_and_contains() {
+        fn assert_labels<G: LabelsOf>()
+        where
+            <G as LabelsOf>::Labels: SameList<tlist!(L1, L2, EmptyLabel, L3, EmptyLabel)>,
+        {
+        }
+        assert_roles::<Global, tlist!(Bob, Alice, Charlie)>();
+        assert_labels::<Global>();
+        assert_contains::<Global, Charlie>();
+    }
+}
+
+// --- Tests for TDelay / TDeadline ---
+#[cfg(test)]
+mod scheduling_tests {
+    use super::*;
+    use std::time::Duration;
+
+    // Alice waits a second before polling Bob, who must answer within a minute
+    type Global = TDelay<
+        Http,
+        L1,
+        Alice,
+        Secs<1>,
+        TMsg<
+            Http,
+            L2,
+            Alice,
+            Bob,
+            Message,
+            TDeadline<Http, L3, Bob, Secs<60>, TMsg<Http, L2, Bob, Alice, Response, TEnd<Http>>>,
+        >,
+    >;
+
+    #[test]
+    fn test_scheduling_projection() {
+        assert_type_eq!(
+            <() as ProjectRole<Alice, Http, Global>>::Out,
+            EpDelay<
+                Http,
+                L1,
+                Alice,
+                Secs<1>,
+                EpSend<
+                    Http,
+                    L2,
+                    Alice,
+                    Message,
+                    EpRecv<Http, L2, Alice, Response, EpEnd<Http, EmptyLabel, Alice>>,
+                >,
+            >
+        );
+        assert_type_eq!(
+            <() as ProjectRole<Bob, Http, Global>>::Out,
+            EpRecv<
+                Http,
+                L2,
+                Bob,
+                Message,
+                EpDeadline<
+                    Http,
+                    L3,
+                    Bob,
+                    Secs<60>,
+                    EpSend<Http, L2, Bob, Response, EpEnd<Http, EmptyLabel, Bob>>,
+                >,
+            >
+        );
+        assert_type_eq!(
+            <() as ProjectRole<Charlie, Http, Global>>::Out,
+            EpEnd<Http, EmptyLabel, Charlie>
+        );
+    }
+
+    #[test]
+    fn test_scheduling_local_types() {
+        type AliceEp = <() as ProjectRole<Alice, Http, Global>>::Out;
+        type Deadline = EpDeadline<Http, L3, Bob, Secs<60>, EpEnd<Http, EmptyLabel, Bob>>;
+        assert_type_eq!(<AliceEp as GetLocalLabel>::Label, L1);
+        assert_type_eq!(IsSkip<AliceEp, Http, Alice>, False);
+        assert_type_eq!(IsEnd<AliceEp, Http, Alice>, False);
+        assert_type_eq!(<Deadline as GetLocalLabel>::Label, L3);
+        assert_type_eq!(IsSkip<Deadline, Http, Bob>, False);
+        assert_type_eq!(IsEnd<Deadline, Http, Bob>, False);
+    }
+
+    #[test]
+    fn test_scheduling_introspection() {
+        assert_roles::<Global, tlist!(Alice, Bob)>();
+        assert_contains::<Global, Bob>();
+        assert_not_contains::<Global, Charlie>();
+        assert_eq!(<Global as TimingOf>::DURATION, Duration::from_secs(1));
+        assert_eq!(
+            <TDeadline<Http, L3, Bob, Millis<250>, TEnd<Http>> as TimingOf>::DURATION,
+            Duration::from_millis(250)
+        );
+    }
+}
+
+#[cfg(test)]
+mod tinterrupt_tests {
+    use super::*;
+
+    // Alice streams to Bob until Charlie interrupts, then Charlie tells Alice why
+    type Global = TInterrupt<
+        Http,
+        L1,
+        TMsg<Http, L2, Alice, Bob, Message, TEnd<Http>>,
+        Charlie,
+        TMsg<Http, L3, Charlie, Alice, Response, TEnd<Http>>,
+    >;
+
+    #[test]
+    fn test_tinterrupt_projection() {
+        assert_type_eq!(
+            <() as ProjectRole<Charlie, Http, Global>>::Out,
+            EpInterrupt<
+                Http,
+                L1,
+                Charlie,
+                EpSkip<Http, L1, Charlie>,
+                EpSend<Http, L3, Charlie, Response, EpEnd<Http, EmptyLabel, Charlie>>,
+            >
+        );
+        assert_type_eq!(
+            <() as ProjectRole<Alice, Http, Global>>::Out,
+            EpInterruptible<
+                Http,
+                L1,
+                Alice,
+                EpSend<Http, L2, Alice, Message, EpEnd<Http, EmptyLabel, Alice>>,
+                EpRecv<Http, L3, Alice, Response, EpEnd<Http, EmptyLabel, Alice>>,
+            >
+        );
+        assert_type_eq!(
+            <() as ProjectRole<Bob, Http, Global>>::Out,
+            EpInterruptible<
+                Http,
+                L1,
+                Bob,
+                EpRecv<Http, L2, Bob, Message, EpEnd<Http, EmptyLabel, Bob>>,
+                EpSkip<Http, L1, Bob>,
+            >
+        );
+    }
+
+    #[test]
+    fn test_tinterrupt_uninvolved_role_skips() {
+        type Private = TInterrupt<
+            Http,
+            L1,
+            TMsg<Http, L2, Alice, Charlie, Message, TEnd<Http>>,
+            Charlie,
+            TEnd<Http>,
+        >;
+        assert_type_eq!(<() as ProjectRole<Bob, Http, Private>>::Out, EpSkip<Http, L1, Bob>);
+    }
+
+    #[test]
+    fn test_tinterrupt_introspection() {
+        fn assert_labels<G: LabelsOf<Labels = L>, L>() {}
+        assert_roles::<Global, tlist!(Charlie, Alice, Bob)>();
+        assert_labels::<Global, tlist!(L1, L2, EmptyLabel, L3, EmptyLabel)>();
+        assert_contains::<Global, Charlie>();
+        assert_contains::<TInterrupt<Http, L1, TEnd<Http>, Charlie, TEnd<Http>>, Charlie>();
+        assert_not_contains::<TInterrupt<Http, L1, TEnd<Http>, Charlie, TEnd<Http>>, Bob>();
+    }
+}
+
+#[cfg(test)]
+mod tbroadcast_tests {
+    use super::*;
+
+    // Alice publishes one message to Bob and Charlie, then Bob acknowledges
+    type Global = TBroadcast<
+        Http,
+        L1,
+        Alice,
+        tlist!(Bob, Charlie),
+        Message,
+        TMsg<Http, L2, Bob, Alice, Response, TEnd<Http>>,
+    >;
+
+    #[test]
+    fn test_tbroadcast_projection() {
+        assert_type_eq!(
+            <() as ProjectRole<Alice, Http, Global>>::Out,
+            EpBroadcast<
+                Http,
+                L1,
+                Alice,
+                tlist!(Bob, Charlie),
+                Message,
+                EpRecv<Http, L2, Alice, Response, EpEnd<Http, EmptyLabel, Alice>>,
+            >
+        );
+        assert_type_eq!(
+            <() as ProjectRole<Bob, Http, Global>>::Out,
+            EpRecv<
+                Http,
+                L1,
+                Bob,
+                Message,
+                EpSend<Http, L2, Bob, Response, EpEnd<Http, EmptyLabel, Bob>>,
+            >
+        );
+        assert_type_eq!(
+            <() as ProjectRole<Charlie, Http, Global>>::Out,
+            EpRecv<Http, L1, Charlie, Message, EpEnd<Http, EmptyLabel, Charlie>>
+        );
+    }
+
+    #[test]
+    fn test_fan_out_and_fan_in_local_types() {
+        type AliceEp = <() as ProjectRole<Alice, Http, Global>>::Out;
+        type Collect = EpGather<
+            Http,
+            L2,
+            Alice,
+            tlist!(Bob, Charlie),
+            Response,
+            EpEnd<Http, EmptyLabel, Alice>,
+        >;
+        assert_type_eq!(<AliceEp as GetLocalLabel>::Label, L1);
+        assert_type_eq!(<AliceEp as GetLocalRole>::Role, Alice);
+        assert_type_eq!(IsSkip<AliceEp, Http, Alice>, False);
+        assert_type_eq!(IsEnd<Collect, Http, Alice>, False);
+        assert_type_eq!(
+            <Collect as Dual<Bob>>::Output,
+            EpSend<Http, L2, Bob, Response, EpEnd<Http, EmptyLabel, Bob>>
+        );
+    }
+
+    #[test]
+    fn test_tbroadcast_outside_set_continues() {
+        type ToBob = TBroadcast<Http, L1, Alice, tlist!(Bob), Message, TEnd<Http>>;
+        assert_type_eq!(
+            <() as ProjectRole<Charlie, Http, ToBob>>::Out,
+            EpEnd<Http, EmptyLabel, Charlie>
+        );
+    }
+
+    #[test]
+    fn test_tbroadcast_introspection() {
+        assert_roles::<Global, tlist!(Alice, Bob, Charlie)>();
+        assert_contains::<Global, Charlie>();
+        type ToBob = TBroadcast<Http, L1, Alice, tlist!(Bob), Message, TEnd<Http>>;
+        assert_not_contains::<ToBob, Charlie>();
+    }
+}
+
+#[cfg(test)]
+mod tanycast_tests {
+    use super::*;
+
+    // Alice hands a job to either Bob or Charlie
+    type Global = TAnycast<Http, L1, Alice, tlist!(Bob, Charlie), Message, TEnd<Http>>;
+
+    #[test]
+    fn test_tanycast_projection() {
+        assert_type_eq!(
+            <() as ProjectRole<Alice, Http, Global>>::Out,
+            EpSend<Http, L1, Alice, Message, EpEnd<Http, EmptyLabel, Alice>>
+        );
+        assert_type_eq!(
+            <() as ProjectRole<Bob, Http, Global>>::Out,
+            EpOffer<
+                Http,
+                L1,
+                Bob,
+                tlist!(
+                    EpRecv<Http, L1, Bob, Message, EpEnd<Http, EmptyLabel, Bob>>,
+                    EpEnd<Http, EmptyLabel, Bob>
+                ),
+            >
+        );
+    }
+
+    #[test]
+    fn test_tanycast_outside_set_continues() {
+        type ToBob = TAnycast<
+            Http,
+            L1,
+            Alice,
+            tlist!(Bob),
+            Message,
+            TMsg<Http, L2, Alice, Charlie, Response, TEnd<Http>>,
+        >;
+        assert_type_eq!(
+            <() as ProjectRole<Charlie, Http, ToBob>>::Out,
+            EpRecv<Http, L2, Charlie, Response, EpEnd<Http, EmptyLabel, Charlie>>
+        );
+    }
+
+    #[test]
+    fn test_tanycast_introspection() {
+        type ToBob = TAnycast<Http, L1, Alice, tlist!(Bob), Message, TEnd<Http>>;
+        assert_roles::<Global, tlist!(Alice, Bob, Charlie)>();
+        assert_contains::<Global, Charlie>();
+        assert_not_contains::<ToBob, Charlie>();
+    }
+}
+
+#[cfg(test)]
+mod tdelegate_tests {
+    use super::*;
+
+    // Alice owes Charlie a response in another session and hands that duty to Bob
+    type Owed = EpSend<Http, L3, Alice, Response, EpEnd<Http, EmptyLabel, Alice>>;
+    type Global =
+        TDelegate<Http, L1, Alice, Bob, Owed, TMsg<Http, L2, Bob, Alice, Message, TEnd<Http>>>;
+
+    #[test]
+    fn test_tdelegate_projection() {
+        assert_type_eq!(
+            <() as ProjectRole<Alice, Http, Global>>::Out,
+            EpDelegate<
+                Http,
+                L1,
+                Alice,
+                Owed,
+                EpRecv<Http, L2, Alice, Message, EpEnd<Http, EmptyLabel, Alice>>,
+            >
+        );
+        assert_type_eq!(
+            <() as ProjectRole<Bob, Http, Global>>::Out,
+            EpRecv<
+                Http,
+                L1,
+                Bob,
+                Owed,
+                EpSend<Http, L2, Bob, Message, EpEnd<Http, EmptyLabel, Bob>>,
+            >
+        );
+        assert_type_eq!(
+            <() as ProjectRole<Charlie, Http, Global>>::Out,
+            EpEnd<Http, EmptyLabel, Charlie>
+        );
+    }
+
+    #[test]
+    fn test_tdelegate_local_type() {
+        type AliceEp = <() as ProjectRole<Alice, Http, Global>>::Out;
+        type BobEp = <() as ProjectRole<Bob, Http, Global>>::Out;
+        assert_type_eq!(<AliceEp as DelegationOf>::Endpoint, Owed);
+        assert_type_eq!(
+            <AliceEp as DelegationOf>::Cont,
+            EpRecv<Http, L2, Alice, Message, EpEnd<Http, EmptyLabel, Alice>>
+        );
+        assert_type_eq!(<AliceEp as GetLocalLabel>::Label, L1);
+        assert_type_eq!(<AliceEp as GetLocalRole>::Role, Alice);
+        assert_type_eq!(IsSkip<AliceEp, Http, Alice>, False);
+        assert_type_eq!(IsEnd<AliceEp, Http, Alice>, False);
+        assert_type_eq!(<AliceEp as Dual<Bob>>::Output, BobEp);
+    }
+
+    #[test]
+    fn test_tdelegate_introspection() {
+        fn assert_labels<G: LabelsOf<Labels = L>, L>() {}
+        assert_roles::<Global, tlist!(Alice, Bob)>();
+        assert_labels::<Global, tlist!(L1, L2, EmptyLabel)>();
+        assert_not_contains::<Global, Charlie>();
+        assert_type_eq!(<Owed as GetLocalRole>::Role, Alice);
+    }
+}
+
+#[cfg(test)]
+mod trefine_tests {
+    use super::*;
+
+    struct Positive;
+    impl Predicate for Positive {
+        const DESCRIPTION: &'static str = "value > 0";
+    }
+    impl CheckPredicate<i64> for Positive {
+        fn check(value: &i64) -> bool {
+            *value > 0
+        }
+    }
+
+    // Alice sends Bob a positive amount
+    type Global = TRefine<Positive, TMsg<Http, L1, Alice, Bob, i64, TEnd<Http>>>;
+
+    #[test]
+    fn test_trefine_projection() {
+        assert_type_eq!(
+            <() as ProjectRole<Alice, Http, Global>>::Out,
+            EpRefine<Positive, EpSend<Http, L1, Alice, i64, EpEnd<Http, EmptyLabel, Alice>>>
+        );
+        assert_type_eq!(
+            <() as ProjectRole<Bob, Http, Global>>::Out,
+            EpRefine<Positive, EpRecv<Http, L1, Bob, i64, EpEnd<Http, EmptyLabel, Bob>>>
+        );
+        // Charlie is not involved and sees no refinement
+        assert_type_eq!(
+            <() as ProjectRole<Charlie, Http, Global>>::Out,
+            EpEnd<Http, EmptyLabel, Charlie>
+        );
+    }
+
+    #[test]
+    fn test_trefine_introspection() {
+        assert_roles::<Global, tlist!(Alice, Bob)>();
+        assert_type_eq!(<Global as GetProtocolLabel>::Label, L1);
+        assert_eq!(<Global as RefinementOf>::DESCRIPTION, "value > 0");
+        type AliceLocal = <() as ProjectRole<Alice, Http, Global>>::Out;
+        assert_eq!(<AliceLocal as RefinementOf>::DESCRIPTION, "value > 0");
+        assert!(<Positive as CheckPredicate<i64>>::check(&3));
+        assert!(!<Positive as CheckPredicate<i64>>::check(&0));
+    }
+}
+
+#[cfg(test)]
+mod toptional_tests {
+    use super::*;
+
+    // Bob may warn Alice before Alice and Charlie exchange a message
+    type Global = TOptional<
+        Http,
+        L1,
+        Bob,
+        TMsg<Http, L2, Bob, Alice, Response, TEnd<Http>>,
+        TMsg<Http, L3, Alice, Charlie, Message, TEnd<Http>>,
+    >;
+
+    #[test]
+    fn test_toptional_projection() {
+        assert_type_eq!(
+            <() as ProjectRole<Bob, Http, Global>>::Out,
+            EpOptionalSelect<
+                Http,
+                L1,
+                Bob,
+                EpSend<Http, L2, Bob, Response, EpEnd<Http, EmptyLabel, Bob>>,
+                EpEnd<Http, EmptyLabel, Bob>,
+            >
+        );
+        assert_type_eq!(
+            <() as ProjectRole<Alice, Http, Global>>::Out,
+            EpOptionalOffer<
+                Http,
+                L1,
+                Alice,
+                EpRecv<Http, L2, Alice, Response, EpEnd<Http, EmptyLabel, Alice>>,
+                EpSend<Http, L3, Alice, Message, EpEnd<Http, EmptyLabel, Alice>>,
+            >
+        );
+        // Charlie only takes part in the continuation, which is the same either way
+        assert_type_eq!(
+            <() as ProjectRole<Charlie, Http, Global>>::Out,
+            EpRecv<Http, L3, Charlie, Message, EpEnd<Http, EmptyLabel, Charlie>>
+        );
+    }
+
+    #[test]
+    fn test_toptional_introspection() {
+        type Private =
+            TOptional<Http, L1, Bob, TEnd<Http>, TMsg<Http, L2, Bob, Alice, Message, TEnd<Http>>>;
+        assert_roles::<Global, tlist!(Bob, Alice, Charlie)>();
+        assert_contains::<Global, Charlie>();
+        assert_not_contains::<Private, Charlie>();
+    }
+}
+
+#[cfg(test)]
+mod tpriority_tests {
+    use super::*;
+
+    // Alice prefers a control message to Bob over a data message to Charlie
+    type Global = TPriorityChoice<
+        Http,
+        L1,
+        TMsg<Http, L2, Alice, Bob, Message, TEnd<Http>>,
+        TMsg<Http, L3, Alice, Charlie, Response, TEnd<Http>>,
+    >;
+
+    #[test]
+    fn test_tpriority_projection() {
+        assert_type_eq!(
+            <() as ProjectRole<Alice, Http, Global>>::Out,
+            EpPriorityOffer<
+                Http,
+                L1,
+                Alice,
+                EpSend<Http, L2, Alice, Message, EpEnd<Http, EmptyLabel, Alice>>,
+                EpSend<Http, L3, Alice, Response, EpEnd<Http, EmptyLabel, Alice>>,
+            >
+        );
+        // Bob only takes part in the high branch
+        assert_type_eq!(
+            <() as ProjectRole<Bob, Http, Global>>::Out,
+            EpPriorityOffer<
+                Http,
+                L1,
+                Bob,
+                EpRecv<Http, L2, Bob, Message, EpEnd<Http, EmptyLabel, Bob>>,
+                EpSkip<Http, L1, Bob>,
+            >
+        );
+        // Charlie is not involved in a priority choice between Alice and Bob
+        type Private = TPriorityChoice<
+            Http,
+            L1,
+            TMsg<Http, L2, Alice, Bob, Message, TEnd<Http>>,
+            TMsg<Http, L3, Bob, Alice, Response, TEnd<Http>>,
+        >;
+        assert_type_eq!(<() as ProjectRole<Charlie, Http, Private>>::Out, EpSkip<Http, L1, Charlie>);
+    }
+
+    #[test]
+    fn test_tpriority_introspection() {
+        fn assert_label<E: GetLocalLabel<Label = Lb>, Lb>() {}
+        assert_roles::<Global, tlist!(Alice, Bob, Charlie)>();
+        assert_label::<<() as ProjectRole<Charlie, Http, Global>>::Out, L1>();
+        type Private =
+            TPriorityChoice<Http, L1, TMsg<Http, L2, Alice, Bob, Message, TEnd<Http>>, TEnd<Http>>;
+        assert_not_contains::<Private, Charlie>();
+    }
+}
+
+#[cfg(test)]
+mod connection_tests {
+    use super::*;
+
+    // Alice and Bob hang up after one message; Bob then reports to Charlie
+    type Global = TMsg<
+        Http,
+        L1,
+        Alice,
+        Bob,
+        Message,
+        TDisconnect<Http, L2, Alice, Bob, TMsg<Http, L3, Bob, Charlie, Response, TEnd<Http>>>,
+    >;
+
+    #[test]
+    fn test_tdisconnect_projection() {
+        assert_type_eq!(
+            <() as ProjectRole<Alice, Http, Global>>::Out,
+            EpSend<Http, L1, Alice, Message, EpClose<Http, L2, Alice, Bob, EpEnd<Http, EmptyLabel, Alice>>>
+        );
+        assert_type_eq!(
+            <() as ProjectRole<Bob, Http, Global>>::Out,
+            EpRecv<
+                Http,
+                L1,
+                Bob,
+                Message,
+                EpClose<
+                    Http,
+                    L2,
+                    Bob,
+                    Alice,
+                    EpSend<Http, L3, Bob, Response, EpEnd<Http, EmptyLabel, Bob>>,
+                >,
+            >
+        );
+        assert_type_eq!(
+            <() as ProjectRole<Charlie, Http, Global>>::Out,
+            EpRecv<Http, L3, Charlie, Response, EpEnd<Http, EmptyLabel, Charlie>>
+        );
+    }
+
+    #[test]
+    fn test_connections_respected() {
+        fn assert_respected<G: ConnectionsRespected>() {}
+        fn assert_links<S: LinksPair<Alice, Bob, Output = O>, O>() {}
+        assert_respected::<Global>();
+        assert_links::<(Bob, tlist!(Charlie, Alice)), True>();
+        assert_links::<(Alice, tlist!(Charlie)), False>();
+        assert_links::<(Charlie, tlist!(Alice, Bob)), False>();
+    }
+
+    #[test]
+    fn test_tconnect_projection() {
+        // Alice introduces Bob to Charlie, who then talk directly
+        type Introduce = TMsg<
+            Http,
+            L1,
+            Alice,
+            Bob,
+            Message,
+            TConnect<Http, L2, Bob, Charlie, TMsg<Http, L3, Charlie, Bob, Response, TEnd<Http>>>,
+        >;
+        assert_type_eq!(
+            <() as ProjectRole<Charlie, Http, Introduce>>::Out,
+            EpOpen<
+                Http,
+                L2,
+                Charlie,
+                Bob,
+                EpSend<Http, L3, Charlie, Response, EpEnd<Http, EmptyLabel, Charlie>>,
+            >
+        );
+        assert_type_eq!(
+            <() as ProjectRole<Alice, Http, Introduce>>::Out,
+            EpSend<Http, L1, Alice, Message, EpEnd<Http, EmptyLabel, Alice>>
+        );
+
+        fn assert_respected<G: ConnectionsRespected>() {}
+        assert_respected::<Introduce>();
+        // Closing and reopening a channel is allowed
+        assert_respected::<
+            TMsg<
+                Http,
+                L1,
+                Alice,
+                Bob,
+                Message,
+                TDisconnect<
+                    Http,
+                    L2,
+                    Alice,
+                    Bob,
+                    TConnect<
+                        Http,
+                        L2,
+                        Bob,
+                        Alice,
+                        TMsg<Http, L3, Bob, Alice, Response, TEnd<Http>>,
+                    >,
+                >,
+            >,
+        >();
+    }
+}
+
+#[cfg(test)]
+mod tannotate_tests {
+    use super::*;
+
+    struct Confidential;
+    struct HighPriority;
+    struct BestEffort;
+
+    type Inner = TMsg<Http, L1, Alice, Bob, Message, TEnd<Http>>;
+
+    #[test]
+    fn test_tannotate_projection_is_transparent() {
+        type Global = TAnnotate<Confidential, Inner>;
+        assert_type_eq!(
+            <() as ProjectRole<Alice, Http, Global>>::Out,
+            <() as ProjectRole<Alice, Http, Inner>>::Out
+        );
+        assert_type_eq!(
+            <() as ProjectRole<Bob, Http, Global>>::Out,
+            <() as ProjectRole<Bob, Http, Inner>>::Out
+        );
+    }
+
+    #[test]
+    fn test_annotations_of() {
+        fn assert_annotations<G: AnnotationsOf<Annotations = A>, A>() {}
+        type Global = TAnnotate<
+            Confidential,
+            TChoice<
+                Http,
+                L2,
+                TAnnotate<HighPriority, Inner>,
+                TAnnotate<BestEffort, TMsg<Http, L3, Bob, Alice, Response, TEnd<Http>>>,
+            >,
+        >;
+        assert_annotations::<Global, tlist!(Confidential, HighPriority, BestEffort)>();
+        assert_annotations::<Inner, Nil>();
+    }
+}
+
+#[cfg(test)]
+mod tendfor_tests {
+    use super::*;
+
+    // Alice leaves after greeting Bob; Bob and Charlie carry on
+    type Global = TMsg<
+        Http,
+        L1,
+        Alice,
+        Bob,
+        Message,
+        TEndFor<Http, L2, Alice, TMsg<Http, L3, Bob, Charlie, Response, TEnd<Http>>>,
+    >;
+
+    #[test]
+    fn test_tendfor_projection() {
+        assert_type_eq!(
+            <() as ProjectRole<Alice, Http, Global>>::Out,
+            EpSend<Http, L1, Alice, Message, EpEnd<Http, L2, Alice>>
+        );
+        assert_type_eq!(
+            <() as ProjectRole<Bob, Http, Global>>::Out,
+            EpRecv<
+                Http,
+                L1,
+                Bob,
+                Message,
+                EpSend<Http, L3, Bob, Response, EpEnd<Http, EmptyLabel, Bob>>,
+            >
+        );
+    }
+
+    #[test]
+    fn test_tendfor_introspection() {
+        assert_roles::<Global, tlist!(Alice, Bob, Charlie)>();
+        assert_contains::<TEndFor<Http, L2, Charlie, TEnd<Http>>, Charlie>();
+    }
+}
+
+#[cfg(test)]
+mod tshuffle_tests {
+    use super::*;
+
+    // Alice sends Bob two headers, in whichever order they are ready
+    type Global = TShuffle<
+        Http,
+        L3,
+        tlist!(
+            TMsg<Http, L1, Alice, Bob, Message, TEnd<Http>>,
+            TMsg<Http, L2, Alice, Bob, Message, TEnd<Http>>,
+        ),
+    >;
+
+    #[test]
+    fn test_tshuffle_projection() {
+        assert_type_eq!(
+            <() as ProjectRole<Bob, Http, Global>>::Out,
+            EpShuffle<
+                Http,
+                L3,
+                Bob,
+                tlist!(
+                    EpRecv<Http, L1, Bob, Message, EpEnd<Http, EmptyLabel, Bob>>,
+                    EpRecv<Http, L2, Bob, Message, EpEnd<Http, EmptyLabel, Bob>>,
+                ),
+            >
+        );
+        assert_type_eq!(<() as ProjectRole<Charlie, Http, Global>>::Out, EpSkip<Http, L3, Charlie>);
+    }
+
+    #[test]
+    fn test_tshuffle_steps_independent() {
+        fn assert_independent<S: PairwiseIndependent>() {}
+        assert_independent::<
+            tlist!(
+                TMsg<Http, L1, Alice, Bob, Message, TEnd<Http>>,
+                TMsg<Http, L2, Bob, Alice, Response, TEnd<Http>>,
+            ),
+        >();
+        assert_roles::<Global, tlist!(Alice, Bob)>();
+    }
+}
+
+#[cfg(test)]
+mod tprob_tests {
+    use super::*;
+
+    // Alice usually sends Bob a message, and occasionally a response
+    type Global = TProb<
+        Http,
+        L1,
+        Weight<9>,
+        TMsg<Http, L2, Alice, Bob, Message, TEnd<Http>>,
+        Weight<1>,
+        TMsg<Http, L3, Alice, Bob, Response, TEnd<Http>>,
+    >;
+    type Unweighted = TChoice<
+        Http,
+        L1,
+        TMsg<Http, L2, Alice, Bob, Message, TEnd<Http>>,
+        TMsg<Http, L3, Alice, Bob, Response, TEnd<Http>>,
+    >;
+
+    #[test]
+    fn test_tprob_projection_ignores_weights() {
+        assert_type_eq!(
+            <() as ProjectRole<Alice, Http, Global>>::Out,
+            <() as ProjectRole<Alice, Http, Unweighted>>::Out
+        );
+        assert_type_eq!(
+            <() as ProjectRole<Bob, Http, Global>>::Out,
+            <() as ProjectRole<Bob, Http, Unweighted>>::Out
+        );
+    }
+
+    #[test]
+    fn test_tprob_weights() {
+        assert_eq!(<Global as WeightsOf>::LEFT, 9);
+        assert_eq!(<Global as WeightsOf>::RIGHT, 1);
+        fn assert_labels<G: LabelsOf<Labels = Ls>, Ls>() {}
+        assert_labels::<Global, tlist!(L1, L2, EmptyLabel, L3, EmptyLabel)>();
+    }
+}
+
+#[cfg(test)]
+mod dual_tests {
+    use super::*;
+
+    // Alice asks, Bob answers, then Alice picks how to finish
+    type Global = TMsg<
+        Http,
+        L1,
+        Alice,
+        Bob,
+        Message,
+        TMsg<
+            Http,
+            L2,
+            Bob,
+            Alice,
+            Response,
+            TSelect<
+                Http,
+                L3,
+                Alice,
+                tlist!(
+                    TMsg<Http, L1, Alice, Bob, Message, TEnd<Http>>,
+                    TMsg<Http, L2, Alice, Bob, Response, TEnd<Http>>,
+                ),
+            >,
+        >,
+    >;
+    type AliceEp = <() as ProjectRole<Alice, Http, Global>>::Out;
+    type BobEp = <() as ProjectRole<Bob, Http, Global>>::Out;
+
+    #[test]
+    fn test_dual_of_projection_is_peer_projection() {
+        assert_type_eq!(<AliceEp as Dual<Bob>>::Output, BobEp);
+        assert_type_eq!(<BobEp as Dual<Alice>>::Output, AliceEp);
+    }
+
+    #[test]
+    fn test_duality_holds() {
+        fn assert_dual<G: DualityHolds<Http, A, B>, A, B>() {}
+        assert_dual::<Global, Alice, Bob>();
+        assert_dual::<Global, Bob, Alice>();
+        assert_dual::<TRec<Http, L1, TMsg<Http, L2, Bob, Alice, Message, TVar<L1>>>, Alice, Bob>();
+    }
+
+    #[test]
+    fn test_dual_preserves_labels() {
+        type Choice = EpSelect<
+            Http,
+            L1,
+            Alice,
+            tlist!(
+                EpSend<Http, L2, Alice, Message, EpEnd<Http, L2, Alice>>,
+                EpRecv<Http, L3, Alice, Response, EpEnd<Http, L3, Alice>>,
+            ),
+        >;
+        assert_type_eq!(
+            <Choice as Dual<Bob>>::Output,
+            EpOffer<
+                Http,
+                L1,
+                Bob,
+                tlist!(
+                    EpRecv<Http, L2, Bob, Message, EpEnd<Http, L2, Bob>>,
+                    EpSend<Http, L3, Bob, Response, EpEnd<Http, L3, Bob>>,
+                ),
+            >
+        );
+    }
+}
+
+#[cfg(test)]
+mod normalize_tests {
+    use super::*;
+
+    type AliceSend = EpSend<Http, L1, Alice, Message, EpEnd<Http, EmptyLabel, Alice>>;
+    type AliceRecv = EpRecv<Http, L2, Alice, Response, EpEnd<Http, EmptyLabel, Alice>>;
+
+    #[test]
+    fn test_normalize_drops_skip_branches() {
+        type Choice = EpSelect<Http, L3, Alice, tlist!(AliceSend, EpSkip<Http, L3, Alice>)>;
+        assert_type_eq!(<Choice as Normalize>::Output, AliceSend);
+
+        type Empty = EpOffer<Http, L3, Alice, tlist!(EpSkip<Http, L1, Alice>)>;
+        assert_type_eq!(<Empty as Normalize>::Output, EpSkip<Http, L3, Alice>);
+
+        type Both = EpOffer<Http, L3, Alice, tlist!(AliceSend, EpSkip<Http, L1, Alice>, AliceRecv)>;
+        assert_type_eq!(
+            <Both as Normalize>::Output,
+            EpOffer<Http, L3, Alice, tlist!(AliceSend, AliceRecv)>
+        );
+    }
+
+    #[test]
+    fn test_normalize_flattens_nested_par() {
+        type Nested = EpPar<
+            Http,
+            L1,
+            Alice,
+            EpPar<Http, L2, Alice, AliceSend, AliceRecv>,
+            EpParN<Http, L3, Alice, tlist!(EpSkip<Http, L3, Alice>, AliceSend)>,
+        >;
+        assert_type_eq!(
+            <Nested as Normalize>::Output,
+            EpParN<Http, L1, Alice, tlist!(AliceSend, AliceRecv, AliceSend)>
+        );
+
+        type Lopsided = EpPar<Http, L1, Alice, AliceSend, EpSkip<Http, L1, Alice>>;
+        assert_type_eq!(<Lopsided as Normalize>::Output, AliceSend);
+    }
+
+    #[test]
+    fn test_normalize_rewrites_continuations() {
+        type Seq = EpRecv<
+            Http,
+            L2,
+            Alice,
+            Response,
+            EpSelect<Http, L3, Alice, tlist!(EpSkip<Http, L3, Alice>, AliceSend)>,
+        >;
+        assert_type_eq!(
+            <Seq as Normalize>::Output,
+            EpRecv<Http, L2, Alice, Response, AliceSend>
+        );
+    }
+
+    #[test]
+    fn test_normalized_projection() {
+        // Alice either messages Charlie or stops, so Alice only acts in the first branch
+        type Global =
+            TChoice<Http, L1, TMsg<Http, L3, Alice, Charlie, Message, TEnd<Http>>, TEnd<Http>>;
+        assert_type_eq!(
+            <<() as ProjectRole<Alice, Http, Global>>::Out as Normalize>::Output,
+            EpSend<Http, L3, Alice, Message, EpEnd<Http, EmptyLabel, Alice>>
+        );
+    }
+
+    #[test]
+    fn test_refactored_projection_refines_previous() {
+        // Moving Charlie's message into a parallel block does not change Charlie's side
+        type Old = TMsg<Http, L3, Alice, Charlie, Message, TEnd<Http>>;
+        type New = TPar<
+            Http,
+            L1,
+            TMsg<Http, L2, Alice, Bob, Message, TEnd<Http>>,
+            TMsg<Http, L3, Alice, Charlie, Message, TEnd<Http>>,
+            FalseB,
+        >;
+        assert_refines!(
+            <() as ProjectRole<Charlie, Http, New>>::Out,
+            <() as ProjectRole<Charlie, Http, Old>>::Out
+        );
+    }
+}
+
+#[cfg(test)]
+mod subtype_tests {
+    use super::*;
+
+    type AliceSend = EpSend<Http, L1, Alice, Message, EpEnd<Http, EmptyLabel, Alice>>;
+    type AliceRecv = EpRecv<Http, L2, Alice, Response, EpEnd<Http, EmptyLabel, Alice>>;
+
+    #[test]
+    fn test_subtype_is_reflexive() {
+        assert_subtype!(AliceSend, AliceSend);
+        assert_subtype!(
+            EpOffer<Http, L3, Alice, tlist!(AliceSend, AliceRecv)>,
+            EpOffer<Http, L3, Alice, tlist!(AliceSend, AliceRecv)>
+        );
+    }
+
+    #[test]
+    fn test_offer_may_handle_more_branches() {
+        assert_subtype!(
+            EpOffer<Http, L3, Alice, tlist!(AliceRecv, AliceSend)>,
+            EpOffer<Http, L3, Alice, tlist!(AliceSend)>
+        );
+    }
+
+    #[test]
+    fn test_select_may_pick_fewer_branches() {
+        assert_subtype!(
+            EpSelect<Http, L3, Alice, tlist!(AliceRecv)>,
+            EpSelect<Http, L3, Alice, tlist!(AliceSend, AliceRecv)>
+        );
+    }
+
+    #[test]
+    fn test_subtype_applies_to_continuations() {
+        // A sender that later offers more than needed still fits
+        assert_subtype!(
+            EpSend<
+                Http,
+                L1,
+                Alice,
+                Message,
+                EpOffer<Http, L3, Alice, tlist!(AliceSend, AliceRecv)>,
+            >,
+            EpSend<Http, L1, Alice, Message, EpOffer<Http, L3, Alice, tlist!(AliceRecv)>>
+        );
+    }
+
+    #[test]
+    fn test_async_send_may_move_ahead_of_receives() {
+        type AliceEnd = EpEnd<Http, EmptyLabel, Alice>;
+        type Canonical = EpRecv<
+            Http,
+            L2,
+            Alice,
+            Response,
+            EpRecv<Http, L3, Alice, Message, EpSend<Http, L1, Alice, Message, AliceEnd>>,
+        >;
+        type Optimized = EpSend<
+            Http,
+            L1,
+            Alice,
+            Message,
+            EpRecv<Http, L2, Alice, Response, EpRecv<Http, L3, Alice, Message, AliceEnd>>,
+        >;
+        assert_async_subtype!(Optimized, Canonical);
+        assert_async_subtype!(Canonical, Canonical);
+        // In order, the synchronous rule still applies
+        assert_async_subtype!(
+            EpSelect<Http, L3, Alice, tlist!(AliceRecv)>,
+            EpSelect<Http, L3, Alice, tlist!(AliceSend, AliceRecv)>
+        );
+    }
+
+    #[test]
+    fn test_async_subtype_of_projection() {
+        // Bob answers Alice's request, then reports to Charlie
+        type Global = TMsg<
+            Http,
+            L1,
+            Alice,
+            Bob,
+            Message,
+            TMsg<Http, L2, Bob, Alice, Response, TMsg<Http, L3, Bob, Charlie, Message, TEnd<Http>>>,
+        >;
+        type BobEnd = EpEnd<Http, EmptyLabel, Bob>;
+        // Bob sends both messages ahead of the request
+        type Eager = EpSend<
+            Http,
+            L2,
+            Bob,
+            Response,
+            EpSend<Http, L3, Bob, Message, EpRecv<Http, L1, Bob, Message, BobEnd>>,
+        >;
+        assert_async_subtype!(Eager, Http; Global => Bob);
+    }
+}
+
+#[cfg(test)]
+mod merge_tests {
+    use super::*;
+
+    type BobEnd = EpEnd<Http, EmptyLabel, Bob>;
+    type BobRecv1 = EpRecv<Http, L1, Bob, Message, BobEnd>;
+    type BobRecv2 = EpRecv<Http, L2, Bob, Response, BobEnd>;
+    type BobRecv3 = EpRecv<Http, L3, Bob, Message, BobEnd>;
+
+    #[test]
+    fn test_merge_distinct_receives_into_offer() {
+        assert_type_eq!(
+            <BobRecv1 as Merge<BobRecv2>>::Output,
+            EpOffer<Http, L1, Bob, tlist!(BobRecv1, BobRecv2)>
+        );
+    }
+
+    #[test]
+    fn test_merge_matching_receives_merges_continuations() {
+        type Then2 = EpRecv<Http, L1, Bob, Message, BobRecv2>;
+        type Then3 = EpRecv<Http, L1, Bob, Message, BobRecv3>;
+        assert_type_eq!(
+            <Then2 as Merge<Then3>>::Output,
+            EpRecv<Http, L1, Bob, Message, EpOffer<Http, L2, Bob, tlist!(BobRecv2, BobRecv3)>>
+        );
+        assert_type_eq!(<BobRecv1 as Merge<BobRecv1>>::Output, BobRecv1);
+    }
+
+    #[test]
+    fn test_merge_offer_inserts_branches() {
+        type Offer12 = EpOffer<Http, L1, Bob, tlist!(BobRecv1, BobRecv2)>;
+        assert_type_eq!(
+            <Offer12 as Merge<BobRecv3>>::Output,
+            EpOffer<Http, L1, Bob, tlist!(BobRecv1, BobRecv2, BobRecv3)>
+        );
+        assert_type_eq!(<Offer12 as Merge<BobRecv2>>::Output, Offer12);
+        assert_type_eq!(
+            <BobRecv3 as Merge<Offer12>>::Output,
+            EpOffer<Http, L1, Bob, tlist!(BobRecv3, BobRecv1, BobRecv2)>
+        );
+    }
+
+    #[test]
+    fn test_projection_merges_non_chooser_branches() {
+        // Alice picks, Bob learns the outcome from the label received under
+        // the label of the choice and Charlie gets the same message from Bob
+        // either way
+        type Notify = TMsg<Http, L1, Bob, Charlie, Message, TEnd<Http>>;
+        type Global = TChoice<
+            Http,
+            L3,
+            TMsg<Http, L1, Alice, Bob, Message, Notify>,
+            TMsg<Http, L2, Alice, Bob, Response, Notify>,
+        >;
+        type BobThen = EpSend<Http, L1, Bob, Message, BobEnd>;
+        assert_type_eq!(
+            <() as ProjectRole<Bob, Http, Global>>::Out,
+            EpOffer<
+                Http,
+                L3,
+                Bob,
+                tlist!(
+                    EpRecv<Http, L1, Bob, Message, BobThen>,
+                    EpRecv<Http, L2, Bob, Response, BobThen>
+                ),
+            >
+        );
+        assert_type_eq!(
+            <<() as ProjectRole<Alice, Http, Global>>::Out as GetLocalLabel>::Label,
+            L3
+        );
+        assert_type_eq!(
+            <() as ProjectRole<Charlie, Http, Global>>::Out,
+            EpRecv<Http, L1, Charlie, Message, EpEnd<Http, EmptyLabel, Charlie>>
+        );
+    }
+}
+
+#[cfg(test)]
+mod compose_ep_tests {
+    use super::*;
+
+    type AliceEnd = EpEnd<Http, EmptyLabel, Alice>;
+    type AliceSend = EpSend<Http, L1, Alice, Message, AliceEnd>;
+    type AliceRecv = EpRecv<Http, L2, Alice, Response, AliceEnd>;
+
+    #[test]
+    fn test_compose_ep_replaces_end() {
+        assert_type_eq!(
+            EpSeq<AliceSend, AliceRecv>,
+            EpSend<Http, L1, Alice, Message, AliceRecv>
+        );
+        assert_type_eq!(EpSeq<AliceEnd, AliceSend>, AliceSend);
+    }
+
+    #[test]
+    fn test_compose_ep_reaches_every_branch() {
+        type Branches = EpSelect<Http, L3, Alice, tlist!(AliceSend, EpSkip<Http, L3, Alice>)>;
+        assert_type_eq!(
+            EpSeq<Branches, AliceRecv>,
+            EpSelect<Http, L3, Alice, tlist!(EpSend<Http, L1, Alice, Message, AliceRecv>, AliceRecv)>
+        );
+    }
+
+    #[test]
+    fn test_compose_ep_keeps_loop_backs() {
+        struct Loop;
+        type Body = EpSend<Http, L1, Alice, Message, EpVar<Loop>>;
+        assert_type_eq!(
+            EpSeq<EpRec<Http, L3, Loop, Body>, AliceRecv>,
+            EpRec<Http, L3, Loop, Body>
+        );
+    }
+
+    #[test]
+    fn test_compose_ep_matches_global_compose() {
+        type First = TMsg<Http, L1, Alice, Bob, Message, TEnd<Http>>;
+        type Second = TMsg<Http, L2, Bob, Alice, Response, TEnd<Http>>;
+        assert_type_eq!(
+            EpSeq<
+                <() as ProjectRole<Alice, Http, First>>::Out,
+                <() as ProjectRole<Alice, Http, Second>>::Out,
+            >,
+            <() as ProjectRole<Alice, Http, <First as TSession<Http>>::Compose<Second>>>::Out
+        );
+    }
+}
+
+#[cfg(test)]
+mod fsm_tests {
+    use super::*;
+
+    type BobEnd = EpEnd<Http, EmptyLabel, Bob>;
+    type BobRecv1 = EpRecv<Http, L1, Bob, Message, BobEnd>;
+    type BobRecv2 = EpRecv<Http, L2, Bob, Response, BobEnd>;
+
+    #[test]
+    fn test_fsm_of_sequence() {
+        type Ask = EpSend<Http, L3, Bob, Message, BobRecv1>;
+        assert_type_eq!(
+            <Ask as ToFsm>::Transitions,
+            tlist!(
+                Transition<Ask, Out, L3, Message, BobRecv1>,
+                Transition<BobRecv1, In, L1, Message, BobEnd>
+            )
+        );
+    }
+
+    #[test]
+    fn test_fsm_branches_leave_choice_state() {
+        type Offer = EpOffer<Http, L3, Bob, tlist!(BobRecv1, BobRecv2)>;
+        assert_type_eq!(
+            <Offer as ToFsm>::Transitions,
+            tlist!(
+                Transition<Offer, In, L1, Message, BobEnd>,
+                Transition<Offer, In, L2, Response, BobEnd>
+            )
+        );
+    }
+
+    #[test]
+    fn test_fsm_loop_returns_to_binder() {
+        struct Loop;
+        type Body = EpRecv<Http, L1, Bob, Message, EpVar<Loop>>;
+        type Looping = EpRec<Http, L3, Loop, Body>;
+        assert_type_eq!(
+            <Looping as ToFsm>::Transitions,
+            tlist!(Transition<Looping, In, L1, Message, EpVar<Loop>>)
+        );
+    }
+
+    #[test]
+    fn test_fsm_par_forks_branches() {
+        type Par = EpPar<Http, L3, Bob, BobRecv1, BobRecv2>;
+        assert_type_eq!(
+            <Par as ToFsm>::Transitions,
+            tlist!(
+                Transition<Par, Fork, L3, Void, BobRecv1>,
+                Transition<BobRecv1, In, L1, Message, BobEnd>,
+                Transition<Par, Fork, L3, Void, BobRecv2>,
+                Transition<BobRecv2, In, L2, Response, BobEnd>
+            )
+        );
+    }
+
+    #[test]
+    fn test_fsm_runtime_data_of_projection() {
+        type Global =
+            TMsg<Http, L1, Alice, Bob, Message, TMsg<Http, L2, Bob, Alice, Response, TEnd<Http>>>;
+        type BobLocal = <() as ProjectRole<Bob, Http, Global>>::Out;
+        let transitions = <BobLocal as ToFsm>::transitions();
+        assert_eq!(transitions.len(), 2);
+        assert_eq!(transitions[0].direction, Direction::In);
+        assert_eq!(transitions[1].direction, Direction::Out);
+        assert_eq!(transitions[0].to, transitions[1].from);
+        assert_eq!(transitions[1].message, core::any::type_name::<Response>());
+    }
+}
+
+#[cfg(test)]
+mod validity_tests {
+    use super::*;
+
+    fn assert_locally_well_formed<E: LocallyWellFormed<Http, Me>, Me>() {}
+
+    #[test]
+    fn test_projections_are_locally_well_formed() {
+        type Global = TMsg<
+            Http,
+            L1,
+            Alice,
+            Bob,
+            Message,
+            TDisconnect<Http, L2, Alice, Bob, TMsg<Http, L3, Bob, Charlie, Response, TEnd<Http>>>,
+        >;
+        assert_locally_well_formed::<<() as ProjectRole<Alice, Http, Global>>::Out, Alice>();
+        assert_locally_well_formed::<<() as ProjectRole<Bob, Http, Global>>::Out, Bob>();
+        assert_locally_well_formed::<<() as ProjectRole<Charlie, Http, Global>>::Out, Charlie>();
+    }
+
+    #[test]
+    fn test_par_with_distinct_leading_actions() {
+        type BobEnd = EpEnd<Http, EmptyLabel, Bob>;
+        // Same direction on different labels, or different directions on the same label
+        assert_locally_well_formed::<
+            EpPar<
+                Http,
+                L3,
+                Bob,
+                EpRecv<Http, L1, Bob, Message, BobEnd>,
+                EpRecv<Http, L2, Bob, Message, BobEnd>,
+            >,
+            Bob,
+        >();
+        assert_locally_well_formed::<
+            EpParN<
+                Http,
+                L3,
+                Bob,
+                tlist!(
+                    EpSend<Http, L1, Bob, Message, BobEnd>,
+                    EpRecv<Http, L1, Bob, Message, BobEnd>,
+                    BobEnd
+                ),
+            >,
+            Bob,
+        >();
+    }
+}
+
+#[cfg(test)]
+mod simplify_tests {
+    use super::*;
+
+    type AliceEnd = EpEnd<Http, EmptyLabel, Alice>;
+    type Send1 = EpSend<Http, L1, Alice, Message, AliceEnd>;
+    type Send2 = EpSend<Http, L2, Alice, Message, AliceEnd>;
+    type Send3 = EpSend<Http, L3, Alice, Message, AliceEnd>;
+    type Recv1 = EpRecv<Http, L1, Alice, Message, AliceEnd>;
+    type Recv2 = EpRecv<Http, L2, Alice, Message, AliceEnd>;
+    type Recv3 = EpRecv<Http, L3, Alice, Message, AliceEnd>;
+
+    #[test]
+    fn test_flatten_projected_tchoice() {
+        type Global = tchoice!(Http;
+            TMsg<Http, L1, Alice, Bob, Message, TEnd<Http>>,
+            TMsg<Http, L2, Alice, Bob, Message, TEnd<Http>>,
+            TMsg<Http, L3, Alice, Bob, Message, TEnd<Http>>,
+        );
+        type Projected = <() as ProjectRole<Alice, Http, Global>>::Out;
+        assert_type_eq!(
+            <Projected as FlattenChoices>::Output,
+            EpSelect<
+                Http,
+                EmptyLabel,
+                Alice,
+                tlist!(Send1, Send2, Send3, EpSkip<Http, EmptyLabel, Alice>)
+            >
+        );
+        assert_type_eq!(
+            <<Projected as FlattenChoices>::Output as Normalize>::Output,
+            EpSelect<Http, EmptyLabel, Alice, tlist!(Send1, Send2, Send3)>
+        );
+    }
+
+    #[test]
+    fn test_flatten_nested_offers_under_continuations() {
+        type Nested =
+            EpOffer<Http, L1, Alice, tlist!(Recv1, EpOffer<Http, L2, Alice, tlist!(Recv2, Recv3)>)>;
+        assert_type_eq!(
+            <EpSend<Http, L1, Alice, Message, Nested> as FlattenChoices>::Output,
+            EpSend<Http, L1, Alice, Message, EpOffer<Http, L1, Alice, tlist!(Recv1, Recv2, Recv3)>>
+        );
+    }
+
+    #[test]
+    fn test_flatten_keeps_choices_of_another_kind() {
+        type Inner = EpOffer<Http, L2, Alice, tlist!(Recv2, Recv3)>;
+        type Mixed = EpSelect<Http, L1, Alice, tlist!(Send1, Inner)>;
+        assert_type_eq!(<Mixed as FlattenChoices>::Output, Mixed);
+    }
+
+    #[test]
+    fn test_nest_flatten_round_trip() {
+        type Flat = EpSelect<Http, L1, Alice, tlist!(Send1, Send2, Send3)>;
+        type Nested = EpSelect<
+            Http,
+            L1,
+            Alice,
+            tlist!(Send1, EpSelect<Http, L1, Alice, tlist!(Send2, Send3)>),
+        >;
+        assert_type_eq!(<Flat as NestChoices>::Output, Nested);
+        assert_type_eq!(<Nested as FlattenChoices>::Output, Flat);
+        assert_type_eq!(<Nested as NestChoices>::Output, Nested);
+    }
+}
+
+mod failure_tests {
+    use super::*;
+
+    struct Failed;
+
+    // Bob answers Alice or fails; on failure Alice tells Charlie
+    type Global = TCatch<
+        Http,
+        L1,
+        TMsg<
+            Http,
+            L2,
+            Alice,
+            Bob,
+            Message,
+            TChoice<
+                Http,
+                EmptyLabel,
+                TMsg<Http, L3, Bob, Alice, Response, TEnd<Http>>,
+                TThrow<Http, L3, Bob, Failed>,
+            >,
+        >,
+        TMsg<Http, L2, Alice, Charlie, Message, TEnd<Http>>,
+    >;
+
+    #[test]
+    fn test_tthrow_projection() {
+        type Throw = TThrow<Http, L1, Bob, Failed>;
+        assert_type_eq!(<() as ProjectRole<Bob, Http, Throw>>::Out, EpThrow<Http, L1, Bob, Failed>);
+        assert_type_eq!(<() as ProjectRole<Alice, Http, Throw>>::Out, EpEnd<Http, L1, Alice>);
+    }
+
+    #[test]
+    fn test_tcatch_routes_roles_into_handler() {
+        assert_type_eq!(
+            <() as ProjectRole<Bob, Http, Global>>::Out,
+            EpCatch<
+                Http,
+                L1,
+                Bob,
+                EpRecv<
+                    Http,
+                    L2,
+                    Bob,
+                    Message,
+                    EpSelect<
+                        Http,
+                        EmptyLabel,
+                        Bob,
+                        tlist!(
+                            EpSend<Http, L3, Bob, Response, EpEnd<Http, EmptyLabel, Bob>>,
+                            EpThrow<Http, L3, Bob, Failed>,
+                        ),
+                    >,
+                >,
+                EpSkip<Http, L1, Bob>,
+            >
+        );
+        // Charlie only appears in the handler but is still routed through the catch
+        assert_type_eq!(
+            <() as ProjectRole<Charlie, Http, Global>>::Out,
+            EpCatch<
+                Http,
+                L1,
+                Charlie,
+                EpSkip<Http, L1, Charlie>,
+                EpRecv<Http, L2, Charlie, Message, EpEnd<Http, EmptyLabel, Charlie>>,
+            >
+        );
+    }
+
+    #[test]
+    fn test_failure_local_types() {
+        type BobEp = <() as ProjectRole<Bob, Http, Global>>::Out;
+        type Throw = EpThrow<Http, L3, Bob, Failed>;
+        assert_type_eq!(<BobEp as GetLocalLabel>::Label, L1);
+        assert_type_eq!(<BobEp as GetLocalRole>::Role, Bob);
+        assert_type_eq!(IsSkip<BobEp, Http, Bob>, False);
+        assert_type_eq!(IsEnd<BobEp, Http, Bob>, False);
+        assert_type_eq!(<Throw as GetLocalLabel>::Label, L3);
+        assert_type_eq!(<Throw as GetLocalRole>::Role, Bob);
+        assert_type_eq!(IsSkip<Throw, Http, Bob>, False);
+        assert_type_eq!(IsEnd<Throw, Http, Bob>, False);
+        // A failure is a real branch, not a skip
+        assert_type_eq!(
+            <() as FilterSkips<Http, Bob, tlist!(Throw, EpSkip<Http, L1, Bob>)>>::Out,
+            tlist!(Throw)
+        );
+    }
+
+    #[test]
+    fn test_catch_scoped() {
+        fn assert_caught<G: CatchScoped<False>>() {}
+        assert_caught::<Global>();
+        assert_caught::<TCatch<Http, L1, TEnd<Http>, TEnd<Http>>>();
+    }
+
+    #[test]
+    fn test_failure_introspection() {
+        assert_roles::<TThrow<Http, L1, Bob, Failed>, tlist!(Bob)>();
+        assert_contains::<Global, Charlie>();
+        assert_not_contains::<TThrow<Http, L1, Bob, Failed>, Alice>();
+    }
+}
+
+mod project_all_tests {
+    use super::*;
+
+    type Relay = TMsg<
+        Http,
+        L1,
+        Alice,
+        Bob,
+        Message,
+        TMsg<Http, L2, Bob, Charlie, Message, TMsg<Http, L3, Charlie, Alice, Message, TEnd<Http>>>,
+    >;
+    type All = <() as ProjectAll<Http, Relay>>::Locals;
+
+    #[test]
+    fn test_project_all_lists_each_role_once() {
+        assert_type_eq!(
+            All,
+            tlist!(
+                RoleLocal<Alice, <() as ProjectRole<Alice, Http, Relay>>::Out>,
+                RoleLocal<Bob, <() as ProjectRole<Bob, Http, Relay>>::Out>,
+                RoleLocal<Charlie, <() as ProjectRole<Charlie, Http, Relay>>::Out>,
+            )
+        );
+    }
+
+    #[test]
+    fn test_local_of_matches_project_role() {
+        assert_type_eq!(
+            <() as LocalOf<All, Alice>>::Out,
+            <() as ProjectRole<Alice, Http, Relay>>::Out
+        );
+        assert_type_eq!(
+            <() as LocalOf<All, Bob>>::Out,
+            EpRecv<
+                Http,
+                L1,
+                Bob,
+                Message,
+                EpSend<Http, L2, Bob, Message, EpEnd<Http, EmptyLabel, Bob>>,
+            >
+        );
+        assert_type_eq!(
+            <() as LocalOf<All, Charlie>>::Out,
+            <() as ProjectRole<Charlie, Http, Relay>>::Out
+        );
+    }
+
+    #[test]
+    fn test_project_all_of_end_is_empty() {
+        assert_type_eq!(<() as ProjectAll<Http, TEnd<Http>>>::Locals, Nil);
+    }
+}
+
+mod strategy_tests {
+    use super::*;
+
+    type Send = TMsg<Http, L1, Alice, Bob, Message, TEnd<Http>>;
+    type Pick = TChoice<Http, L2, Send, TEnd<Http>>;
+
+    #[test]
+    fn test_permissive_is_the_default() {
+        assert_type_eq!(
+            <() as ProjectRole<Alice, Http, Pick>>::Out,
+            <() as ProjectRole<Alice, Http, Pick, Permissive>>::Out
+        );
+    }
+
+    #[test]
+    fn test_strict_returns_the_normal_form() {
+        type Both =
+            TPar<Http, L2, Send, TMsg<Http, L3, Alice, Charlie, Response, TEnd<Http>>, False>;
+        type AliceSend = EpSend<Http, L1, Alice, Message, EpEnd<Http, EmptyLabel, Alice>>;
+        type AliceReply = EpSend<Http, L3, Alice, Response, EpEnd<Http, EmptyLabel, Alice>>;
+        assert_type_eq!(
+            <() as ProjectRole<Alice, Http, Both>>::Out,
+            EpPar<Http, L2, Alice, AliceSend, AliceReply>
+        );
+        assert_type_eq!(
+            <() as ProjectRole<Alice, Http, Both, Strict>>::Out,
+            EpParN<Http, L2, Alice, tlist!(AliceSend, AliceReply)>
+        );
+    }
+
+    #[test]
+    fn test_strict_keeps_well_formed_projections() {
+        type BobRecv = EpRecv<Http, L1, Bob, Message, EpEnd<Http, EmptyLabel, Bob>>;
+        assert_type_eq!(<() as ProjectRole<Bob, Http, Send, Strict>>::Out, BobRecv);
+    }
+}
+
+mod mixed_io_tests {
+    use super::*;
+
+    type Web = TMsg<Http, L1, Alice, Bob, Message, TEnd<Http>>;
+    type Bus = TMsg<Mqtt, L2, Bob, Charlie, Publish, TEnd<Mqtt>>;
+    type Sessions = tlist!(IoSession<Http, Web>, IoSession<Mqtt, Bus>);
+
+    #[test]
+    fn test_gateway_projection_is_grouped_by_io() {
+        assert_type_eq!(
+            <() as ProjectMixed<Bob, Sessions>>::Out,
+            tlist!(
+                IoLocal<Http, tlist!(EpRecv<Http, L1, Bob, Message, EpEnd<Http, EmptyLabel, Bob>>)>,
+                IoLocal<Mqtt, tlist!(EpSend<Mqtt, L2, Bob, Publish, EpEnd<Mqtt, EmptyLabel, Bob>>)>,
+            )
+        );
+    }
+
+    #[test]
+    fn test_sessions_over_one_io_share_a_group() {
+        type Reply = TMsg<Http, L3, Bob, Alice, Response, TEnd<Http>>;
+        type Interleaved =
+            tlist!(IoSession<Http, Web>, IoSession<Mqtt, Bus>, IoSession<Http, Reply>);
+        assert_type_eq!(
+            <Interleaved as FilterByIO<Http>>::Output,
+            tlist!(Web, Reply)
+        );
+        assert_type_eq!(
+            <() as ProjectMixed<Bob, Interleaved>>::Out,
+            tlist!(
+                IoLocal<
+                    Http,
+                    tlist!(
+                        <() as ProjectRole<Bob, Http, Web>>::Out,
+                        <() as ProjectRole<Bob, Http, Reply>>::Out,
+                    ),
+                >,
+                IoLocal<Mqtt, tlist!(<() as ProjectRole<Bob, Mqtt, Bus>>::Out)>,
+            )
+        );
+    }
+
+    #[test]
+    fn test_single_io_roles_just_end_the_other_sessions() {
+        assert_type_eq!(
+            <() as ProjectMixed<Alice, Sessions>>::Out,
+            tlist!(
+                IoLocal<Http, tlist!(<() as ProjectRole<Alice, Http, Web>>::Out)>,
+                IoLocal<Mqtt, tlist!(<() as ProjectRole<Alice, Mqtt, Bus>>::Out)>,
+            )
+        );
+        assert_type_eq!(
+            <() as ProjectRole<Alice, Mqtt, Bus>>::Out,
+            EpEnd<Mqtt, EmptyLabel, Alice>
+        );
+    }
+}
+
+mod project_each_tests {
+    use super::*;
+
+    type Branches = tlist!(
+        TMsg<Http, L1, Alice, Bob, Message, TEnd<Http>>,
+        TMsg<Http, L2, Bob, Charlie, Message, TEnd<Http>>,
+    );
+
+    #[test]
+    fn test_project_each_keeps_order() {
+        assert_type_eq!(
+            <() as ProjectEach<Bob, Http, Branches>>::Out,
+            tlist!(
+                EpRecv<Http, L1, Bob, Message, EpEnd<Http, EmptyLabel, Bob>>,
+                EpSend<Http, L2, Bob, Message, EpEnd<Http, EmptyLabel, Bob>>,
+            )
+        );
+        assert_type_eq!(<() as ProjectEach<Bob, Http, Nil>>::Out, Nil);
+    }
+
+    #[test]
+    fn test_project_each_does_not_skip() {
+        // A bystander gets each branch's own projection, not an EpSkip
+        assert_type_eq!(
+            <() as ProjectEach<Alice, Http, Branches>>::Out,
+            tlist!(
+                EpSend<Http, L1, Alice, Message, EpEnd<Http, EmptyLabel, Alice>>,
+                EpEnd<Http, EmptyLabel, Alice>,
+            )
+        );
+    }
+
+    #[test]
+    fn test_project_each_feeds_par_n() {
+        type Local = EpParN<Http, L3, Bob, <() as ProjectEach<Bob, Http, Branches>>::Out>;
+        fn assert_session<E: EpSession<Http, Bob>>() {}
+        assert_session::<Local>();
+    }
+}
+
+mod role_family_tests {
+    use super::*;
+    use core::marker::PhantomData;
+
+    struct Worker<N>(PhantomData<N>);
+    role_family!(Worker);
+    impl<N> RoleEq<Worker<N>> for Alice {
+        type Output = False;
+    }
+    impl<N> RoleEq<Alice> for Worker<N> {
+        type Output = False;
+    }
+
+    type W0 = Worker<Zero>;
+    type W1 = Worker<Succ<Zero>>;
+    type W2 = Worker<Succ<Succ<Zero>>>;
+
+    // Alice hands a task to each worker and collects the results
+    type Farm = TMsg<
+        Http,
+        L1,
+        Alice,
+        W0,
+        Message,
+        TMsg<
+            Http,
+            L1,
+            Alice,
+            W1,
+            Message,
+            TMsg<Http, L2, W0, Alice, Response, TMsg<Http, L2, W1, Alice, Response, TEnd<Http>>>,
+        >,
+    >;
+
+    #[test]
+    fn test_family_role_eq() {
+        assert_type_eq!(<W0 as RoleEq<W0>>::Output, True);
+        assert_type_eq!(<W2 as RoleEq<W2>>::Output, True);
+        assert_type_eq!(<W0 as RoleEq<W1>>::Output, False);
+        assert_type_eq!(<W2 as RoleEq<W1>>::Output, False);
+        assert_type_eq!(<W1 as RoleFamily>::Index, Succ<Zero>);
+    }
+
+    #[test]
+    fn test_workers_project_alike() {
+        fn assert_alike<G: ProjectsAlike<Http, A, B>, A, B>(_: PhantomData<(A, B)>) {}
+        assert_alike::<Farm, W0, W1>(PhantomData);
+        assert_alike::<Farm, W1, W0>(PhantomData);
+        assert_type_eq!(
+            <() as ProjectRole<W1, Http, Farm>>::Out,
+            EpRecv<Http, L1, W1, Message, EpSend<Http, L2, W1, Response, EpEnd<Http, EmptyLabel, W1>>>
+        );
+    }
+
+    #[test]
+    fn test_reassign_role() {
+        type Local = EpOffer<
+            Http,
+            L3,
+            W0,
+            tlist!(
+                EpRecv<Http, L1, W0, Message, EpEnd<Http, EmptyLabel, W0>>,
+                EpOpen<Http, L2, W0, Alice, EpSkip<Http, L2, W0>>,
+            ),
+        >;
+        assert_type_eq!(
+            <Local as ReassignRole<W2>>::Out,
+            EpOffer<
+                Http,
+                L3,
+                W2,
+                tlist!(
+                    EpRecv<Http, L1, W2, Message, EpEnd<Http, EmptyLabel, W2>>,
+                    EpOpen<Http, L2, W2, Alice, EpSkip<Http, L2, W2>>,
+                ),
+            >
+        );
+    }
+}
+
+mod deadlock_tests {
+    use super::*;
+
+    type Request =
+        TMsg<Http, L1, Alice, Bob, Message, TMsg<Http, L2, Bob, Alice, Response, TEnd<Http>>>;
+
+    #[test]
+    fn test_channels_of() {
+        assert_type_eq!(
+            <Request as ChannelsOf>::Channels,
+            tlist!(Channel<Alice, Bob>, Channel<Bob, Alice>)
+        );
+        assert_type_eq!(
+            <TBroadcast<Http, L1, Alice, tlist!(Bob, Charlie), Message, TEnd<Http>> as ChannelsOf>::Channels,
+            tlist!(Channel<Alice, Bob>, Channel<Alice, Charlie>)
+        );
+        assert_type_eq!(
+            <TInteract<Http, L1, Alice, Message, TEnd<Http>> as ChannelsOf>::Channels,
+            Nil
+        );
+    }
+
+    #[test]
+    fn test_sequential_protocols_are_deadlock_free() {
+        fn assert_free<G: DeadlockFree>() {}
+        assert_free::<Request>();
+        assert_free::<TRec<Http, L3, Request>>();
+        assert_free::<TChoice<Http, L3, Request, TMsg<Http, L3, Alice, Bob, Response, TEnd<Http>>>>(
+        );
+    }
+
+    #[test]
+    fn test_concurrent_branches_in_one_direction() {
+        fn assert_free<G: DeadlockFree>() {}
+        // Alice talks to Bob in both branches, always in the same direction
+        assert_free::<
+            TPar<
+                Http,
+                L3,
+                TMsg<Http, L1, Alice, Bob, Message, TEnd<Http>>,
+                TMsg<
+                    Http,
+                    L2,
+                    Alice,
+                    Bob,
+                    Message,
+                    TMsg<Http, L2, Charlie, Bob, Message, TEnd<Http>>,
+                >,
+                False,
+            >,
+        >();
+        assert_free::<
+            TParN<
+                Http,
+                L3,
+                tlist!(
+                    TMsg<Http, L1, Alice, Bob, Message, TEnd<Http>>,
+                    TMsg<Http, L2, Bob, Charlie, Message, TEnd<Http>>,
+                    TMsg<Http, L3, Charlie, Alice, Message, TEnd<Http>>,
+                ),
+            >,
+        >();
+    }
+
+    #[test]
+    fn test_circular_wait_detection() {
+        type Crossed = Cons<Request, Cons<TMsg<Http, L3, Bob, Alice, Message, TEnd<Http>>, Nil>>;
+        assert_type_eq!(
+            <Channel<Bob, Alice> as ChannelIn<<Request as ChannelsOf>::Channels>>::Output,
+            True
+        );
+        assert_type_eq!(
+            <Channel<Charlie, Alice> as ChannelIn<<Request as ChannelsOf>::Channels>>::Output,
+            False
+        );
+        fn assert_not_reversed<L: NoneReversedIn<Other>, Other>() {}
+        assert_not_reversed::<<Crossed as ChannelsOfEach>::Channels, tlist!(Channel<Alice, Charlie>)>(
+        );
+    }
+}
+
+mod progress_tests {
+    use super::*;
+
+    fn assert_progress<G: HasProgress>() {}
+
+    #[test]
+    fn test_leaving_role_not_needed_again() {
+        assert_progress::<
+            TMsg<
+                Http,
+                L1,
+                Alice,
+                Bob,
+                Message,
+                TEndFor<Http, L2, Alice, TMsg<Http, L3, Bob, Charlie, Message, TEnd<Http>>>,
+            >,
+        >();
+    }
+
+    #[test]
+    fn test_reconnected_pair_may_interact() {
+        assert_progress::<
+            TDisconnect<
+                Http,
+                L1,
+                Alice,
+                Bob,
+                TConnect<Http, L2, Alice, Bob, TMsg<Http, L3, Alice, Bob, Message, TEnd<Http>>>,
+            >,
+        >();
+    }
+
+    #[test]
+    fn test_choices_and_loops_progress() {
+        type Branch = TMsg<Http, L1, Alice, Bob, Message, TEnd<Http>>;
+        assert_progress::<TChoiceN<Http, L2, tlist!(Branch, Branch)>>();
+        assert_progress::<TSelect<Http, L2, Alice, tlist!(Branch)>>();
+        assert_progress::<TParN<Http, L2, Nil>>();
+        assert_progress::<TRec<Http, L3, Branch>>();
+    }
+}
+
+mod linearity_tests {
+    use super::*;
+
+    fn assert_linear<G: Linear>() {}
+
+    #[test]
+    fn test_shared_role_on_distinct_channels() {
+        assert_linear::<
+            TPar<
+                Http,
+                L3,
+                TMsg<Http, L1, Alice, Bob, Message, TEnd<Http>>,
+                TMsg<
+                    Http,
+                    L2,
+                    Bob,
+                    Alice,
+                    Message,
+                    TMsg<Http, L2, Bob, Charlie, Message, TEnd<Http>>,
+                >,
+                False,
+            >,
+        >();
+        assert_linear::<
+            TParN<
+                Http,
+                L3,
+                tlist!(
+                    TMsg<Http, L1, Alice, Bob, Message, TEnd<Http>>,
+                    TMsg<Http, L2, Alice, Charlie, Message, TEnd<Http>>,
+                    TMsg<Http, L3, Charlie, Bob, Message, TEnd<Http>>,
+                ),
+            >,
+        >();
+    }
+
+    #[test]
+    fn test_sequential_reuse_is_linear() {
+        // Reusing a channel one step after another is not parallel use
+        assert_linear::<
+            TMsg<Http, L1, Alice, Bob, Message, TMsg<Http, L2, Alice, Bob, Message, TEnd<Http>>>,
+        >();
+    }
+
+    #[test]
+    fn test_shared_channel_detection() {
+        fn assert_not_shared<L: NoneSharedWith<Other>, Other>() {}
+        assert_not_shared::<tlist!(Channel<Alice, Bob>), tlist!(Channel<Bob, Alice>)>();
+        assert_type_eq!(
+            <Channel<Alice, Bob> as ChannelIn<tlist!(Channel<Bob, Alice>, Channel<Alice, Bob>)>>::Output,
+            True
+        );
+    }
+}
+
+mod choice_awareness_tests {
+    use super::*;
+
+    fn assert_choice_aware<G: ChoiceAware>() {}
+
+    #[test]
+    fn test_chooser_informs_others() {
+        assert_choice_aware::<
+            TChoice<
+                Http,
+                L3,
+                TMsg<
+                    Http,
+                    L1,
+                    Alice,
+                    Bob,
+                    Message,
+                    TMsg<Http, L1, Bob, Charlie, Message, TEnd<Http>>,
+                >,
+                TMsg<
+                    Http,
+                    L2,
+                    Alice,
+                    Bob,
+                    Message,
+                    TMsg<Http, L2, Bob, Charlie, Message, TEnd<Http>>,
+                >,
+            >,
+        >();
+    }
+
+    #[test]
+    fn test_uninvolved_role_behaves_alike() {
+        type Tail = TMsg<Http, L3, Charlie, Alice, Message, TEnd<Http>>;
+        assert_choice_aware::<
+            TSelect<
+                Http,
+                L3,
+                Alice,
+                tlist!(
+                    TMsg<Http, L1, Alice, Bob, Message, Tail>,
+                    TMsg<Http, L2, Alice, Bob, Message, Tail>,
+                ),
+            >,
+        >();
+    }
+
+    #[test]
+    fn test_other_choices_recurse() {
+        type Branch = TMsg<Http, L1, Alice, Bob, Message, TEnd<Http>>;
+        assert_choice_aware::<TChoiceN<Http, L2, tlist!(Branch, Branch)>>();
+        type Stop = TMsg<Http, L2, Alice, Bob, Message, TEnd<Http>>;
+        assert_choice_aware::<TRec<Http, L3, TChoice<Http, L2, Branch, Stop>>>();
+    }
+}
+
+mod connectedness_tests {
+    use super::*;
+
+    fn assert_connected<G: Connected>() {}
+    fn assert_async_connected<G: AsyncConnected>() {}
+
+    type Relay = TMsg<
+        Http,
+        L1,
+        Alice,
+        Bob,
+        Message,
+        TMsg<Http, L2, Bob, Charlie, Message, TMsg<Http, L3, Charlie, Alice, Message, TEnd<Http>>>,
+    >;
+
+    #[test]
+    fn test_relay_is_connected() {
+        assert_connected::<Relay>();
+        assert_async_connected::<Relay>();
+    }
+
+    #[test]
+    fn test_earlier_sender_connected_asynchronously() {
+        // Alice did not take part in the second step, but sent the first
+        assert_async_connected::<
+            TMsg<
+                Http,
+                L1,
+                Alice,
+                Bob,
+                Message,
+                TMsg<
+                    Http,
+                    L2,
+                    Bob,
+                    Charlie,
+                    Message,
+                    TMsg<Http, L3, Alice, Charlie, Message, TEnd<Http>>,
+                >,
+            >,
+        >();
+    }
+
+    #[test]
+    fn test_branches_and_interactions() {
+        type Reply = TMsg<Http, L2, Bob, Alice, Message, TEnd<Http>>;
+        assert_connected::<
+            TMsg<
+                Http,
+                L1,
+                Alice,
+                Bob,
+                Message,
+                TChoice<Http, L3, Reply, TMsg<Http, L2, Bob, Charlie, Message, TEnd<Http>>>,
+            >,
+        >();
+        assert_connected::<
+            TInteract<Http, L1, Alice, Message, TMsg<Http, L2, Charlie, Bob, Message, TEnd<Http>>>,
+        >();
+        assert_connected::<TRec<Http, L3, Reply>>();
+    }
+
+    #[test]
+    fn test_loop_restarts_connected() {
+        // Alice starts the loop again after receiving Bob's reply
+        type Again = TMsg<Http, L2, Bob, Alice, Message, TVar<L3>>;
+        type Stop = TMsg<Http, L2, Alice, Bob, Message, TEnd<Http>>;
+        assert_connected::<
+            TRec<Http, L3, TChoice<Http, L4, TMsg<Http, L1, Alice, Bob, Message, Again>, Stop>>,
+        >();
+    }
+}
+
+mod no_self_messaging_tests {
+    use super::*;
+
+    fn assert_no_self_messaging<G: NoSelfMessaging>() {}
+
+    #[test]
+    fn test_steps_join_distinct_roles() {
+        type Owed = EpSend<Http, L3, Alice, Response, EpEnd<Http, EmptyLabel, Alice>>;
+        assert_no_self_messaging::<
+            TBroadcast<
+                Http,
+                L1,
+                Alice,
+                tlist!(Bob, Charlie),
+                Message,
+                TDelegate<
+                    Http,
+                    L2,
+                    Alice,
+                    Bob,
+                    Owed,
+                    TDisconnect<Http, L3, Bob, Alice, TEnd<Http>>,
+                >,
+            >,
+        >();
+    }
+
+    #[test]
+    fn test_bound_in_protocol_definition() {
+        struct Checked<G: NoSelfMessaging>(core::marker::PhantomData<G>);
+        type Ask = TMsg<Http, L1, Alice, Bob, Message, TEnd<Http>>;
+        let _ = Checked::<
+            TChoice<Http, L2, Ask, TMsg<Http, L3, Alice, Charlie, Message, TEnd<Http>>>,
+        >(core::marker::PhantomData);
+        let _ = Checked::<TInteract<Http, L1, Alice, Message, Ask>>(core::marker::PhantomData);
+    }
+}
+
+mod determinism_tests {
+    use super::*;
+
+    fn assert_deterministic<Ep: Deterministic>() {}
+
+    #[test]
+    fn test_select_projects_to_deterministic_offer() {
+        type Global = TSelect<
+            Http,
+            L3,
+            Alice,
+            tlist!(
+                TMsg<Http, L1, Alice, Bob, Message, TEnd<Http>>,
+                TMsg<Http, L2, Alice, Bob, Response, TEnd<Http>>,
+            ),
+        >;
+        assert_deterministic!(Http; Global => Alice, Bob);
+    }
+
+    #[test]
+    fn test_nested_offers_checked() {
+        type BobEnd = EpEnd<Http, EmptyLabel, Bob>;
+        type Inner = EpOffer<
+            Http,
+            L2,
+            Bob,
+            tlist!(
+                EpRecv<Http, L2, Bob, Message, BobEnd>,
+                EpRecv<Http, L3, Bob, Message, BobEnd>,
+            ),
+        >;
+        assert_deterministic::<
+            EpRec<
+                Http,
+                L1,
+                L1,
+                EpOffer<Http, L1, Bob, tlist!(EpRecv<Http, L1, Bob, Message, EpVar<L1>>, Inner)>,
+            >,
+        >();
+        assert_deterministic::<
+            EpPriorityOffer<Http, L1, Bob, EpRecv<Http, L1, Bob, Message, BobEnd>, Inner>,
+        >();
+    }
+}
+
+mod equivalence_tests {
+    use super::*;
+
+    // The same exchange as `Renamed`, under other labels
+    type Original = TMsg<
+        Http,
+        L1,
+        Alice,
+        Bob,
+        Message,
+        TSelect<
+            Http,
+            L2,
+            Bob,
+            tlist!(
+                TMsg<Http, L3, Bob, Alice, Response, TEnd<Http>>,
+                TRec<Http, L1, TMsg<Http, L2, Bob, Charlie, Message, TEnd<Http>>>,
+            ),
+        >,
+    >;
+    type Renamed = TMsg<
+        Http,
+        L3,
+        Alice,
+        Bob,
+        Message,
+        TSelect<
+            Http,
+            L1,
+            Bob,
+            tlist!(
+                TMsg<Http, L2, Bob, Alice, Response, TEnd<Http>>,
+                TRec<Http, L3, TMsg<Http, L1, Bob, Charlie, Message, TEnd<Http>>>,
+            ),
+        >,
+    >;
+
+    fn assert_equivalent<A: EquivalentTo<Http, B, Ends>, B, Ends>() {}
+
+    #[test]
+    fn test_renamed_labels_are_equivalent() {
+        assert_equiv!(Http; Original, Renamed);
+        assert_equiv!(Http; Renamed, Original);
+        assert_equivalent::<Original, Renamed, CompareEnds>();
+    }
+
+    #[test]
+    fn test_erased_labels() {
+        assert_type_eq!(
+            <TMsg<Http, L1, Alice, Bob, Message, TEnd<Http, L2>> as EraseLabels<
+                Http,
+                CompareEnds,
+            >>::Output,
+            TMsg<Http, EmptyLabel, Alice, Bob, Message, TEnd<Http, L2>>
+        );
+        assert_type_eq!(
+            <TMsg<Http, L1, Alice, Bob, Message, TEnd<Http, L2>> as EraseLabels<
+                Http,
+                IgnoreEnds,
+            >>::Output,
+            TMsg<Http, EmptyLabel, Alice, Bob, Message, TEnd<Http>>
+        );
+    }
+
+    #[test]
+    fn test_end_labels_ignored_on_request() {
+        type Labelled = TMsg<Http, L1, Alice, Bob, Message, TEnd<Http, L3>>;
+        type Plain = TMsg<Http, L2, Alice, Bob, Message, TEnd<Http>>;
+        assert_equiv!(Http; Labelled, Plain, IgnoreEnds);
+        assert_equivalent::<Plain, Labelled, IgnoreEnds>();
+    }
+}
+
+mod global_subtype_tests {
+    use super::*;
+
+    type Ask = TMsg<Http, L1, Alice, Bob, Message, TEnd<Http>>;
+    type Tell = TMsg<Http, L2, Alice, Bob, Response, TEnd<Http>>;
+    type Stop = TMsg<Http, L3, Alice, Bob, Message, TEnd<Http>>;
+
+    #[test]
+    fn test_select_may_drop_branches() {
+        type Old = TSelect<Http, L1, Alice, tlist!(Ask, Tell, Stop)>;
+        type New = TSelect<Http, L1, Alice, tlist!(Stop, Ask)>;
+        assert_global_subtype!(New, Old);
+        assert_global_subtype!(Old, Old);
+        assert_global_subtype!(TChoiceN<Http, L1, tlist!(Tell)>, TChoiceN<Http, L1, tlist!(Ask, Tell)>);
+    }
+
+    #[test]
+    fn test_offer_may_add_branches() {
+        type Old = TOffer<Http, L1, Bob, tlist!(Ask, Tell)>;
+        type New = TOffer<Http, L1, Bob, tlist!(Tell, Stop, Ask)>;
+        assert_global_subtype!(New, Old);
+    }
+
+    #[test]
+    fn test_subtyping_applies_under_continuations() {
+        type Old = TMsg<
+            Http,
+            L2,
+            Bob,
+            Alice,
+            Message,
+            TRec<Http, L3, TSelect<Http, L1, Alice, tlist!(Ask, Tell)>>,
+        >;
+        type New = TMsg<
+            Http,
+            L2,
+            Bob,
+            Alice,
+            Message,
+            TRec<Http, L3, TSelect<Http, L1, Alice, tlist!(Tell)>>,
+        >;
+        assert_global_subtype!(New, Old);
+        fn assert_global_subtype<New: GlobalSubtype<Old>, Old>() {}
+        assert_global_subtype::<TPar<Http, L1, New, Ask, False>, TPar<Http, L1, Old, Ask, False>>();
+    }
+}
+
+mod disjoint_messages_tests {
+    use super::*;
+
+    #[test]
+    fn test_distinct_messages_are_separable() {
+        // Alice and Bob talk in both branches, but with different payloads
+        type Par = TPar<
+            Http,
+            L1,
+            TMsg<Http, L2, Alice, Bob, Message, TMsg<Http, L2, Bob, Alice, Message, TEnd<Http>>>,
+            TMsg<Http, L3, Bob, Alice, Response, TEnd<Http>>,
+            False,
+        >;
+        assert_disjoint!(messages Par);
+    }
+
+    #[test]
+    fn test_parn_branches_pairwise_separable() {
+        type ParN = TParN<
+            Http,
+            L1,
+            tlist!(
+                TMsg<Http, L1, Alice, Bob, Message, TEnd<Http>>,
+                TMsg<Http, L2, Bob, Charlie, Response, TEnd<Http>>,
+                TBroadcast<Http, L3, Charlie, tlist!(Alice, Bob), Publish, TEnd<Http>>,
+            ),
+        >;
+        assert_disjoint!(messages ParN);
+        fn assert_disjoint_messages<G: DisjointMessages>() {}
+        assert_disjoint_messages::<TParN<Http, L1, Nil>>();
+    }
+}
+
+mod termination_tests {
+    use super::*;
+
+    type Send<T> = TMsg<Http, L2, Alice, Bob, Message, T>;
+
+    #[test]
+    fn test_loops_with_exit_terminate() {
+        type Again = TRec<Http, L1, TChoice<Http, L2, Send<TVar<L1>>, TEnd<Http>>>;
+        assert_terminating!(Again);
+        assert_terminating!(Send<TEnd<Http>>);
+        // A body without a jump runs once
+        assert_terminating!(TRec<Http, L1, Send<TEnd<Http>>>);
+        // Failing out of the loop is an exit
+        assert_terminating!(TRec<
+            Http,
+            L1,
+            TSelect<Http, L3, Alice, tlist!(Send<TVar<L1>>, TThrow<Http, L3, Alice, Response>)>,
+        >);
+    }
+
+    #[test]
+    fn test_loops_without_exit_may_diverge() {
+        fn assert_may_diverge<G: MayDiverge>() {}
+        assert_may_diverge::<TRec<Http, L1, Send<TVar<L1>>>>();
+        // One branch of a parallel composition looping keeps the loop from ending
+        assert_may_diverge::<
+            TRec<
+                Http,
+                L1,
+                TChoice<Http, L2, TPar<Http, L3, Send<TVar<L1>>, TEnd<Http>, False>, TVar<L1>>,
+            >,
+        >();
+        // A loop nested in a terminating choice still diverges
+        assert_may_diverge::<TChoice<Http, L2, TEnd<Http>, TRec<Http, L1, Send<TVar<L1>>>>>();
+    }
+}
+
+mod trace_tests {
+    use super::*;
+
+    impl VarEq<L1> for L1 {
+        type Output = True;
+    }
+
+    type Ask<T> = TMsg<Http, L1, Alice, Bob, Message, T>;
+    type Answer<T> = TMsg<Http, L2, Bob, Alice, Response, T>;
+
+    #[test]
+    fn test_sequence_and_choice() {
+        type G = TChoice<Http, L3, Ask<Answer<TEnd<Http>>>, Answer<TEnd<Http>>>;
+        assert_admits!(G, tlist!(Step<Alice, L1, Message>, Step<Bob, L2, Response>));
+        assert_admits!(G, tlist!(Step<Bob, L2, Response>));
+    }
+
+    #[test]
+    fn test_loops_unfold() {
+        type G = TRec<Http, L1, TChoice<Http, L3, Ask<Answer<TVar<L1>>>, TEnd<Http>>>;
+        assert_admits!(G, Nil);
+        assert_admits!(
+            G,
+            tlist!(
+                Step<Alice, L1, Message>,
+                Step<Bob, L2, Response>,
+                Step<Alice, L1, Message>,
+                Step<Bob, L2, Response>,
+            )
+        );
+    }
+
+    #[test]
+    fn test_parallel_branches_interleave() {
+        type G = TPar<
+            Http,
+            L3,
+            Ask<Ask<TEnd<Http>>>,
+            TMsg<Http, L2, Charlie, Bob, Response, TEnd<Http>>,
+            False,
+        >;
+        assert_admits!(
+            G,
+            tlist!(
+                Step<Alice, L1, Message>,
+                Step<Charlie, L2, Response>,
+                Step<Alice, L1, Message>,
+            )
+        );
+        type N = TParN<Http, L3, tlist!(Ask<TEnd<Http>>, Answer<TEnd<Http>>)>;
+        assert_admits!(N, tlist!(Step<Bob, L2, Response>, Step<Alice, L1, Message>));
+    }
+
+    #[test]
+    fn test_throw_continues_with_handler() {
+        type G = TCatch<
+            Http,
+            L3,
+            Ask<TThrow<Http, L3, Bob, Response>>,
+            TMsg<Http, L2, Charlie, Alice, Message, TEnd<Http>>,
+        >;
+        assert_admits!(
+            G,
+            tlist!(
+                Step<Alice, L1, Message>,
+                Step<Bob, L3, Response>,
+                Step<Charlie, L2, Message>,
+            )
+        );
+    }
+}
+
+mod orphan_tests {
+    use super::*;
+
+    type Log<T> = TMsg<Http, L3, Charlie, Bob, Notify, T>;
+
+    #[test]
+    fn test_early_send_in_every_branch() {
+        type G = TSelect<
+            Http,
+            L1,
+            Alice,
+            tlist!(
+                TMsg<Http, L1, Alice, Bob, Message, Log<TEnd<Http>>>,
+                TMsg<Http, L2, Alice, Bob, Message, Log<TEnd<Http>>>,
+            ),
+        >;
+        assert_orphan_free!(G);
+    }
+
+    #[test]
+    fn test_informed_roles_may_differ() {
+        // Charlie hears of the choice from Alice before replying
+        type G = TSelect<
+            Http,
+            L1,
+            Alice,
+            tlist!(
+                TMsg<Http, L1, Alice, Charlie, Message, Log<TEnd<Http>>>,
+                TMsg<Http, L2, Alice, Charlie, Message, TEnd<Http>>,
+            ),
+        >;
+        assert_orphan_free!(G);
+        type O = TOptional<
+            Http,
+            L1,
+            Alice,
+            TMsg<Http, L2, Alice, Bob, Message, TEnd<Http>>,
+            Log<TEnd<Http>>,
+        >;
+        assert_orphan_free!(O);
+    }
+}
+
+mod interleaving_tests {
+    use super::*;
+
+    fn assert_interleaving_safe<G: InterleavingSafe>() {}
+
+    type SharedBob = TPar<
+        Http,
+        L3,
+        TMsg<Http, L1, Alice, Bob, Message, TEnd<Http>>,
+        TMsg<Http, L2, Bob, Charlie, Message, TEnd<Http>>,
+        False,
+    >;
+
+    #[test]
+    fn test_shared_role_interleaves() {
+        assert_interleaving_safe::<SharedBob>();
+        assert_interleaving_safe::<
+            TParN<
+                Http,
+                L3,
+                tlist!(
+                    TMsg<Http, L1, Alice, Bob, Message, TEnd<Http>>,
+                    TMsg<Http, L2, Charlie, Bob, Message, TEnd<Http>>,
+                    TInteract<Http, L3, Bob, Message, TEnd<Http>>,
+                ),
+            >,
+        >();
+    }
+
+    #[test]
+    fn test_nested_composition_not_compared() {
+        // Only the outer branches are compared, unlike `Linear`
+        assert_interleaving_safe::<
+            TPar<
+                Http,
+                L3,
+                TPar<
+                    Http,
+                    L1,
+                    TMsg<Http, L1, Alice, Bob, Message, TEnd<Http>>,
+                    TMsg<Http, L1, Alice, Bob, Response, TEnd<Http>>,
+                    False,
+                >,
+                TMsg<Http, L2, Charlie, Bob, Message, TEnd<Http>>,
+                False,
+            >,
+        >();
+    }
+
+    #[test]
+    fn test_role_disjoint_branches_interleave() {
+        // Whatever role disjointness accepts also interleaves safely
+        fn assert_both<G: AssertDisjoint + InterleavingSafe>() {}
+        assert_both::<
+            TPar<
+                Mixed,
+                L3,
+                TInteract<Mixed, L1, TClient, Message, TEnd<Mixed>>,
+                TInteract<Mixed, L2, TBroker, Publish, TEnd<Mixed>>,
+                False,
+            >,
+        >();
+        assert_both::<
+            TPar<
+                Http,
+                L3,
+                TBroadcast<Http, L1, TClient, tlist!(TServer), Message, TEnd<Http>>,
+                TSelect<
+                    Http,
+                    L2,
+                    TBroker,
+                    tlist!(TMsg<Http, L4, TBroker, TWorker, Publish, TEnd<Http>>),
+                >,
+                False,
+            >,
+        >();
+    }
+
+    #[cfg(feature = "interleaving")]
+    mod relaxed_assert_disjoint {
+        use super::*;
+
+        assert_disjoint!(par SharedBob);
+    }
+}
+
+mod well_formed_tests {
+    use super::*;
+
+    fn assert_basic<G: WellFormedAt<Http, BasicLevel>>() {}
+    fn assert_strict<G: WellFormedAt<Http, StrictLevel>>() {}
+
+    type PingPong =
+        TMsg<Http, L1, Alice, Bob, Message, TMsg<Http, L2, Bob, Alice, Response, TEnd<Http>>>;
+
+    #[test]
+    fn test_sequence_is_well_formed() {
+        assert_basic::<PingPong>();
+        assert_strict::<PingPong>();
+        assert_well_formed!(Http; PingPong);
+    }
+
+    #[test]
+    fn test_informed_choice_is_well_formed() {
+        type Choice = TSelect<
+            Http,
+            L3,
+            Alice,
+            tlist!(
+                TMsg<Http, L1, Alice, Bob, Message, TMsg<Http, L2, Bob, Charlie, Message, TEnd<Http>>>,
+                TMsg<Http, L4, Alice, Bob, Response, TMsg<Http, L5, Bob, Charlie, Response, TEnd<Http>>>,
+            ),
+        >;
+        assert_well_formed!(Http; Choice, StrictLevel);
+    }
+}
+
+mod dedup_tests {
+    use super::*;
+
+    #[test]
+    fn dedup_labels_keeps_first_occurrence() {
+        assert_type_eq!(
+            <tlist!(L2, L1, L2, L3, L1) as Dedup<ByLabel>>::Output,
+            tlist!(L2, L1, L3)
+        );
+        assert_type_eq!(<Nil as Dedup<ByLabel>>::Output, Nil);
+    }
+
+    #[test]
+    fn unique_roles_of_both_branches() {
+        type Choice = TChoice<
+            Http,
+            L1,
+            TMsg<Http, L1, Alice, Bob, Message, TEnd<Http>>,
+            TMsg<Http, L2, Bob, Alice, Message, TEnd<Http>>,
+        >;
+        assert_type_eq!(<Choice as UniqueRoles>::Roles, tlist!(Alice, Bob));
+    }
+}
+
+mod describe_tests {
+    use super::*;
+    use std::any::type_name;
+
+    #[test]
+    fn describe_sequence() {
+        type Ask = TMsg<Http, L1, Alice, Bob, Message, TEnd<Http, L2>>;
+        let desc = Ask::describe();
+        assert_eq!(desc.kind, CombinatorKind::Msg);
+        assert_eq!(desc.label, Some(type_name::<L1>()));
+        assert_eq!(desc.roles, vec![type_name::<Alice>(), type_name::<Bob>()]);
+        assert_eq!(desc.messages, vec![type_name::<Message>()]);
+        assert_eq!(desc.children.len(), 1);
+        assert_eq!(desc.children[0].kind, CombinatorKind::End);
+        assert_eq!(desc.children[0].label, Some(type_name::<L2>()));
+        assert!(desc.children[0].children.is_empty());
+    }
+
+    #[test]
+    fn describe_branches_in_order() {
+        type Reply = TMsg<Http, L2, Bob, Alice, Response, TEnd<Http>>;
+        type Pick = TSelect<Http, L1, Bob, tlist!(Reply, TEnd<Http>, Reply)>;
+        let desc = Pick::describe();
+        assert_eq!(desc.kind, CombinatorKind::Select);
+        assert_eq!(desc.roles, vec![type_name::<Bob>()]);
+        let kinds: Vec<_> = desc.children.iter().map(|c| c.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                CombinatorKind::Msg,
+                CombinatorKind::End,
+                CombinatorKind::Msg
+            ]
+        );
+    }
+
+    #[test]
+    fn describe_role_sets_and_params() {
+        struct Loop;
+        type Fanout = TBroadcast<Http, L1, Alice, tlist!(Bob, Charlie), Message, TVar<Loop>>;
+        type Looping = TRecX<Http, L2, Loop, Fanout>;
+        let desc = Looping::describe();
+        assert_eq!(desc.kind, CombinatorKind::RecX);
+        assert_eq!(desc.params, vec![type_name::<Loop>()]);
+        let fanout = &desc.children[0];
+        assert_eq!(
+            fanout.roles,
+            vec![
+                type_name::<Alice>(),
+                type_name::<Bob>(),
+                type_name::<Charlie>()
+            ]
+        );
+        assert_eq!(fanout.children[0].kind, CombinatorKind::Var);
+        assert_eq!(fanout.children[0].label, None);
+        assert_eq!(fanout.children[0].params, vec![type_name::<Loop>()]);
+    }
+
+    #[test]
+    fn display_nested_blocks() {
+        struct Loop;
+        type Ask = TMsg<Http, L1, Alice, Bob, Message, TVar<Loop>>;
+        type Both = TPar<
+            Http,
+            EmptyLabel,
+            TBroadcast<Http, L2, Alice, tlist!(Bob, Charlie), Message, TEnd<Http>>,
+            TThrow<Http, L3, Charlie, Response>,
+            False,
+        >;
+        type Looping = TRecX<Http, EmptyLabel, Loop, TChoice<Http, EmptyLabel, Ask, Both>>;
+        assert_eq!(
+            ProtocolDisplay::<Looping>::new().to_string(),
+            [
+                "rec Loop {",
+                "    choice {",
+                "        L1(Message) from Alice to Bob;",
+                "        continue Loop;",
+                "    } or {",
+                "        par {",
+                "            L2(Message) from Alice to Bob, Charlie;",
+                "        } and {",
+                "            throw L3(Response) at Charlie;",
+                "        }",
+                "    }",
+                "}",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn display_strips_paths_inside_parameters() {
+        type Waiting = TDelay<Http, EmptyLabel, Alice, Millis<500>, TEnd<Http>>;
+        assert_eq!(
+            ProtocolDisplay::<Waiting>::default().to_string(),
+            "delay Millis<500> at Alice;"
+        );
+        type Optional = TOptional<
+            Http,
+            EmptyLabel,
+            Alice,
+            TMsg<Http, EmptyLabel, Alice, Bob, Message, TEnd<Http>>,
+            TEndFor<Http, EmptyLabel, Bob, TEnd<Http>>,
+        >;
+        assert_eq!(
+            Optional::describe().to_string(),
+            "optional at Alice {\n    (Message) from Alice to Bob;\n}\nend for Bob;"
+        );
+    }
+
+    #[test]
+    fn describe_steps_in_execution_order() {
+        type Session = TRec<
+            Http,
+            L1,
+            TMsg<
+                Http,
+                L2,
+                Alice,
+                Bob,
+                Message,
+                TOptional<
+                    Http,
+                    L3,
+                    Bob,
+                    TPar<
+                        Http,
+                        L1,
+                        TMsg<Http, L2, Bob, Alice, Response, TEnd<Http>>,
+                        TInteract<Http, L3, Charlie, Message, TEnd<Http>>,
+                        False,
+                    >,
+                    TVar<L1>,
+                >,
+            >,
+        >;
+        let desc = Session::describe();
+        let steps: Vec<_> = desc
+            .steps()
+            .map(|step| (step.desc.kind, step.depth, step.path))
+            .collect();
+        use CombinatorKind::*;
+        assert_eq!(
+            steps,
+            vec![
+                (Rec, 0, vec![]),
+                (Msg, 1, vec![0]),
+                (Optional, 1, vec![0, 0]),
+                (Par, 2, vec![0, 0, 0]),
+                (Msg, 3, vec![0, 0, 0, 0]),
+                (End, 3, vec![0, 0, 0, 0, 0]),
+                (Interact, 3, vec![0, 0, 0, 1]),
+                (End, 3, vec![0, 0, 0, 1, 0]),
+                (Var, 1, vec![0, 0, 1]),
+            ]
+        );
+        let branch_points: Vec<_> = desc
+            .steps()
+            .filter(|step| step.is_branch_point())
+            .map(|step| step.desc.label)
+            .collect();
+        assert_eq!(branch_points, vec![Some(type_name::<L1>())]);
+        assert_eq!(TEnd::<Http>::describe().steps().count(), 1);
+    }
+
+    #[test]
+    fn describe_delegated_endpoint_as_message() {
+        type Handed = EpSend<Mqtt, L3, Charlie, Message, EpEnd<Mqtt, L3, Charlie>>;
+        type HandOff = TDelegate<Http, L1, Alice, Bob, Handed, TEnd<Http>>;
+        assert_eq!(HandOff::describe().messages, vec![type_name::<Handed>()]);
+    }
+
+    struct Login;
+    impl ProtocolLabel for Login {
+        const NAME: Option<&'static str> = Some("LoginRequest");
+    }
+    struct Client;
+    impl Role for Client {
+        const NAME: Option<&'static str> = Some("client");
+    }
+
+    #[test]
+    fn describe_uses_names_of_labels_and_roles() {
+        type Ask = TMsg<Http, Login, Client, TServer, Message, TEnd<Http>>;
+        let desc = Ask::describe();
+        assert_eq!(desc.label, Some("LoginRequest"));
+        assert_eq!(desc.roles, vec!["client", "TServer"]);
+        assert_eq!(desc.children[0].label, Some("EmptyLabel"));
+        assert_eq!(
+            ProtocolDisplay::<Ask>::new().to_string(),
+            "LoginRequest(Message) from client to TServer;"
+        );
+        type Fanout =
+            TBroadcast<Http, Login, TServer, tlist!(Client, TClient), Message, TEnd<Http>>;
+        assert_eq!(
+            Fanout::describe().roles,
+            vec!["TServer", "client", "TClient"]
+        );
+    }
+
+    #[test]
+    fn describe_channels() {
+        type Fanout = TBroadcast<
+            Http,
+            L1,
+            TServer,
+            tlist!(Client, TClient),
+            Message,
+            TChoice<
+                Http,
+                L2,
+                TMsg<Http, L3, Client, TServer, Response, TEnd<Http>>,
+                TInteract<
+                    Http,
+                    L3,
+                    TServer,
+                    Message,
+                    TMsg<Http, L1, TServer, Client, Message, TEnd<Http>>,
+                >,
+            >,
+        >;
+        let channel = |from, to| ChannelDesc { from, to };
+        assert_eq!(
+            Fanout::describe().channels(),
+            vec![
+                channel("TServer", "client"),
+                channel("TServer", "TClient"),
+                channel("client", "TServer"),
+            ]
+        );
+        assert!(TEnd::<Http>::describe().channels().is_empty());
+    }
+
+    #[test]
+    fn role_names_as_constant() {
+        struct Sensor;
+        struct Hub;
+        impl Role for Sensor {
+            const NAME: Option<&'static str> = Some("sensor");
+        }
+        impl Role for Hub {
+            const NAME: Option<&'static str> = Some("hub");
+        }
+        impl RoleEq<Sensor> for Sensor {
+            type Output = True;
+        }
+        impl RoleEq<Hub> for Sensor {
+            type Output = False;
+        }
+        impl RoleEq<Sensor> for Hub {
+            type Output = False;
+        }
+        impl RoleEq<Hub> for Hub {
+            type Output = True;
+        }
+        type Report = TRec<
+            Http,
+            L1,
+            TMsg<Http, L2, Sensor, Hub, Message, TMsg<Http, L3, Hub, Sensor, Response, TVar<L1>>>,
+        >;
+        const ROLES: [&str; 2] = <Report as RoleNamesOf<_>>::ROLE_NAMES;
+        assert_eq!(ROLES, ["sensor", "hub"]);
+        assert_eq!(<TEnd<Http> as RoleNamesOf<0>>::ROLE_NAMES, [] as [&str; 0]);
+        assert_eq!(
+            <Report as RoleNamesSlice>::ROLE_NAMES_SLICE,
+            ["sensor", "hub"]
+        );
+        assert!(<TEnd<Http> as RoleNamesSlice>::ROLE_NAMES_SLICE.is_empty());
+    }
+
+    #[test]
+    fn fsm_uses_names_of_labels() {
+        type Done = EpEnd<Http, EmptyLabel, Client>;
+        type Ask = EpSend<Http, Login, Client, Message, Done>;
+        assert_eq!(<Ask as ToFsm>::transitions()[0].label, "LoginRequest");
+    }
+}
+
+mod fingerprint_tests {
+    use super::*;
+
+    // Fingerprints hash names, so every label and role sets `NAME`
+    struct Q1;
+    struct Q2;
+    impl ProtocolLabel for Q1 {
+        const NAME: Option<&'static str> = Some("q1");
+    }
+    impl ProtocolLabel for Q2 {
+        const NAME: Option<&'static str> = Some("q2");
+    }
+    struct Asker;
+    struct Teller;
+    struct Bystander;
+    impl Role for Asker {
+        const NAME: Option<&'static str> = Some("asker");
+    }
+    impl Role for Teller {
+        const NAME: Option<&'static str> = Some("teller");
+    }
+    impl Role for Bystander {
+        const NAME: Option<&'static str> = Some("bystander");
+    }
+
+    type Ask = TMsg<Http, Q1, Asker, Teller, Message, TEnd<Http>>;
+    type Tell = TMsg<Http, Q2, Teller, Asker, Response, TEnd<Http>>;
+
+    const ASK: u64 = <Ask as FingerprintOf>::FINGERPRINT;
+
+    #[test]
+    fn fingerprints_are_stable() {
+        // Pinned so that a change to the hash, the combinator codes or the names is noticed
+        type Hello = TMsg<Http, EmptyLabel, TClient, TServer, Message, TEnd<Http>>;
+        assert_eq!(
+            <TEnd<Http> as FingerprintOf>::FINGERPRINT,
+            0x1d88_656b_47c9_1da6
+        );
+        assert_eq!(<Hello as FingerprintOf>::FINGERPRINT, 0xbe07_93c6_a719_ca53);
+    }
+
+    #[test]
+    fn names_change_fingerprint() {
+        assert_ne!(ASK, <Tell as FingerprintOf>::FINGERPRINT);
+        type OtherLabel = TMsg<Http, Q2, Asker, Teller, Message, TEnd<Http>>;
+        type OtherRole = TMsg<Http, Q1, Asker, Bystander, Message, TEnd<Http>>;
+        let prints = [
+            ASK,
+            <OtherLabel as FingerprintOf>::FINGERPRINT,
+            <OtherRole as FingerprintOf>::FINGERPRINT,
+        ];
+        for (i, a) in prints.iter().enumerate() {
+            for b in &prints[i + 1..] {
+                assert_ne!(a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn payloads_and_annotations_keep_fingerprint() {
+        type OtherMessage = TMsg<Http, Q1, Asker, Teller, Response, TEnd<Http>>;
+        assert_eq!(<OtherMessage as FingerprintOf>::FINGERPRINT, ASK);
+        assert_eq!(<TAnnotate<Message, Ask> as FingerprintOf>::FINGERPRINT, ASK);
+    }
+
+    #[test]
+    fn structure_changes_fingerprint() {
+        type Longer = TMsg<Http, Q1, Asker, Teller, Message, Tell>;
+        type AskOrEnd = TChoice<Http, Q1, Ask, TEnd<Http>>;
+        type EndOrAsk = TChoice<Http, Q1, TEnd<Http>, Ask>;
+        type AskAndEnd = TPar<Http, Q1, Ask, TEnd<Http>, False>;
+        let prints = [
+            ASK,
+            <Longer as FingerprintOf>::FINGERPRINT,
+            <AskOrEnd as FingerprintOf>::FINGERPRINT,
+            <EndOrAsk as FingerprintOf>::FINGERPRINT,
+            <AskAndEnd as FingerprintOf>::FINGERPRINT,
+            <TEnd<Http> as FingerprintOf>::FINGERPRINT,
+        ];
+        for (i, a) in prints.iter().enumerate() {
+            for b in &prints[i + 1..] {
+                assert_ne!(a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn durations_and_lists_are_hashed() {
+        type Fast = TDelay<Http, Q1, Asker, Millis<10>, Ask>;
+        type Slow = TDelay<Http, Q1, Asker, Millis<20>, Ask>;
+        assert_ne!(
+            <Fast as FingerprintOf>::FINGERPRINT,
+            <Slow as FingerprintOf>::FINGERPRINT
+        );
+        type Two = TChoiceN<Http, Q1, tlist!(Ask, Tell)>;
+        type Three = TChoiceN<Http, Q1, tlist!(Ask, Tell, Tell)>;
+        assert_ne!(
+            <Two as FingerprintOf>::FINGERPRINT,
+            <Three as FingerprintOf>::FINGERPRINT
+        );
+        type ToOne = TBroadcast<Http, Q1, Asker, tlist!(Teller), Message, TEnd<Http>>;
+        type ToBoth = TBroadcast<Http, Q1, Asker, tlist!(Teller, Bystander), Message, TEnd<Http>>;
+        assert_ne!(
+            <ToOne as FingerprintOf>::FINGERPRINT,
+            <ToBoth as FingerprintOf>::FINGERPRINT
+        );
+    }
+}
+
+mod footprint_tests {
+    use super::*;
+
+    struct Reading;
+    struct Batch;
+    impl SizeOf for Reading {
+        const SIZE: usize = 12;
+    }
+    impl SizeOf for Batch {
+        const SIZE: usize = 64;
+    }
+
+    type Report = TMsg<
+        Http,
+        L1,
+        Alice,
+        Bob,
+        Reading,
+        TChoice<
+            Http,
+            L2,
+            TBroadcast<Http, L3, Bob, tlist!(Alice, Charlie), Batch, TEnd<Http>>,
+            TPar<
+                Http,
+                L2,
+                TMsg<Http, L1, Charlie, Alice, Reading, TEnd<Http>>,
+                TAnycast<Http, L3, Bob, tlist!(Alice, Charlie), Tagged<1, Reading>, TEnd<Http>>,
+                False,
+            >,
+        >,
+    >;
+
+    const FRAME: usize = <Report as MaxPayloadSize>::MAX_PAYLOAD;
+
+    #[test]
+    fn max_payload_is_the_largest_message() {
+        assert_eq!(FRAME, 64);
+        assert_eq!(<TEnd<Http> as MaxPayloadSize>::MAX_PAYLOAD, 0);
+        type Markers = TParN<
+            Http,
+            L1,
+            tlist!(TMsg<Http, L2, Alice, Bob, Message, TEnd<Http>>, TInteract<Http, L3, Bob, Batch, TEnd<Http>>),
+        >;
+        assert_eq!(<Markers as MaxPayloadSize>::MAX_PAYLOAD, 0);
+    }
+
+    #[test]
+    fn buffer_takes_heaviest_branch_and_all_parallel_branches() {
+        assert_eq!(<Report as PayloadBufferOf<Bob>>::BUFFER, 12);
+        assert_eq!(<Report as PayloadBufferOf<Alice>>::BUFFER, 64);
+        assert_eq!(<Report as PayloadBufferOf<Charlie>>::BUFFER, 64);
+        type Both = TPar<
+            Http,
+            L1,
+            TMsg<Http, L2, Alice, Charlie, Batch, TEnd<Http>>,
+            TMsg<Http, L3, Bob, Charlie, Batch, TEnd<Http>>,
+            False,
+        >;
+        assert_eq!(<Both as PayloadBufferOf<Charlie>>::BUFFER, 128);
+    }
+
+    #[test]
+    fn buffer_counts_loop_body_once() {
+        type Poll = TRec<
+            Http,
+            L1,
+            TMsg<Http, L2, Alice, Bob, Reading, TMsg<Http, L3, Bob, Alice, Batch, TVar<L1>>>,
+        >;
+        assert_eq!(<Poll as PayloadBufferOf<Alice>>::BUFFER, 64);
+        assert_eq!(<Poll as PayloadBufferOf<Bob>>::BUFFER, 12);
+    }
+}
+
+#[cfg(test)]
+mod slice_tests {
+    use super::*;
+
+    struct NonEmpty;
+    impl Predicate for NonEmpty {
+        const DESCRIPTION: &'static str = "not empty";
+    }
+
+    type Answer = TMsg<Http, L3, Bob, Alice, Response, TEnd<Http>>;
+    type Ask = TMsg<Http, L2, Alice, Bob, Message, Answer>;
+
+    #[test]
+    fn slice_sequence() {
+        type Session = TMsg<Http, L1, Alice, Bob, Message, Ask>;
+        assert_type_eq!(<Session as SliceByLabel<L1>>::Output, Session);
+        assert_type_eq!(<Session as SliceByLabel<L2>>::Output, Ask);
+        assert_type_eq!(<Session as SliceByLabel<L3>>::Output, Answer);
+    }
+
+    #[test]
+    fn slice_finds_first_in_branch_order() {
+        type Both =
+            TChoice<Http, L1, TEnd<Http, L2>, TSelect<Http, L3, Alice, tlist!(Answer, Ask)>>;
+        // The end labelled `L2` is not searched, so the slice is in the second branch
+        assert_type_eq!(<Both as SliceByLabel<L2>>::Output, Ask);
+        assert_type_eq!(
+            <Both as SliceByLabel<L3>>::Output,
+            TSelect<Http, L3, Alice, tlist!(Answer, Ask)>
+        );
+        type Noted = TAnnotate<Message, TRefine<NonEmpty, Ask>>;
+        assert_type_eq!(<Noted as SliceByLabel<L3>>::Output, Answer);
+    }
+
+    #[test]
+    fn replace_first_labelled() {
+        type Session = TMsg<Http, L1, Alice, Bob, Message, Ask>;
+        type Done = TEnd<Http, L3>;
+        assert_type_eq!(
+            <Session as ReplaceByLabel<Http, L2, Done>>::Output,
+            TMsg<Http, L1, Alice, Bob, Message, Done>
+        );
+        // Only the first `L3` is replaced
+        type Twice = TPar<Http, L1, Answer, Answer, False>;
+        assert_type_eq!(
+            <Twice as ReplaceByLabel<Http, L3, Done>>::Output,
+            TPar<Http, L1, Done, Answer, False>
+        );
+        type Branches = TOffer<Http, L1, Bob, tlist!(TEnd<Http>, Answer, Answer)>;
+        assert_type_eq!(
+            <Branches as ReplaceByLabel<Http, L3, Done>>::Output,
+            TOffer<Http, L1, Bob, tlist!(TEnd<Http>, Done, Answer)>
+        );
+    }
+}
+
+mod projectable_tests {
+    use super::*;
+
+    type Relay =
+        TMsg<Http, L1, Alice, Bob, Message, TMsg<Http, L2, Bob, Charlie, Message, TEnd<Http>>>;
+    type Looping = TRec<Http, L3, TMsg<Http, L1, Alice, Bob, Message, TEnd<Http>>>;
+
+    assert_projectable!(Http; Relay => Alice, Bob, Charlie);
+    assert_projectable!(Http; Looping => Alice, Bob);
+
+    fn projectable<G: IsProjectable<Me, Http>, Me>(_: core::marker::PhantomData<(G, Me)>) -> bool {
+        true
+    }
+
+    #[test]
+    fn test_is_projectable_as_a_bound() {
+        assert!(projectable::<Relay, Charlie>(core::marker::PhantomData));
+        assert!(projectable::<Looping, Charlie>(core::marker::PhantomData));
+    }
+}
+
+mod synthesize_tests {
+    use super::*;
+
+    type Relay = TMsg<
+        Http,
+        L1,
+        Alice,
+        Bob,
+        Message,
+        TMsg<Http, L2, Bob, Charlie, Response, TMsg<Http, L3, Charlie, Alice, Publish, TEnd<Http>>>,
+    >;
+
+    #[test]
+    fn test_synthesize_inverts_project_all() {
+        type All = <() as ProjectAll<Http, Relay>>::Locals;
+        assert_type_eq!(<All as Synthesize<Http>>::Output, Relay);
+    }
+
+    #[test]
+    fn test_synthesize_waits_for_a_ready_receiver() {
+        // Alice's message to Charlie can only go once Charlie heard from Bob
+        type AliceEnd = EpEnd<Http, EmptyLabel, Alice>;
+        type BobEnd = EpEnd<Http, EmptyLabel, Bob>;
+        type CharlieEnd = EpEnd<Http, EmptyLabel, Charlie>;
+        type Locals = tlist!(
+            RoleLocal<Alice, EpSend<Http, L2, Alice, Message, AliceEnd>>,
+            RoleLocal<Bob, EpSend<Http, L1, Bob, Message, BobEnd>>,
+            RoleLocal<
+                Charlie,
+                EpRecv<Http, L1, Charlie, Message, EpRecv<Http, L2, Charlie, Message, CharlieEnd>>,
+            >,
+        );
+        assert_type_eq!(
+            <Locals as Synthesize<Http>>::Output,
+            TMsg<
+                Http,
+                L1,
+                Bob,
+                Charlie,
+                Message,
+                TMsg<Http, L2, Alice, Charlie, Message, TEnd<Http>>,
+            >
+        );
+    }
+
+    #[test]
+    fn test_synthesize_nothing_to_say() {
+        type Locals = tlist!(
+            RoleLocal<Alice, EpEnd<Http, EmptyLabel, Alice>>,
+            RoleLocal<Bob, EpEnd<Http, EmptyLabel, Bob>>,
+        );
+        assert_type_eq!(<Locals as Synthesize<Http>>::Output, TEnd<Http>);
+    }
+
+    type Checkout = TMsg<
+        Http,
+        L3,
+        Charlie,
+        Alice,
+        Publish,
+        TSelect<
+            Http,
+            L4,
+            Alice,
+            tlist!(
+                TMsg<Http, L1, Alice, Bob, Message, TEnd<Http>>,
+                TMsg<Http, L2, Alice, Bob, Response, TMsg<Http, L5, Bob, Alice, Message, TEnd<Http>>>,
+            ),
+        >,
+    >;
+
+    #[test]
+    fn test_synthesize_selection_inverts_project_all() {
+        // Charlie skips the choice, Bob offers it
+        type All = <() as ProjectAll<Http, Checkout>>::Locals;
+        assert_type_eq!(<All as Synthesize<Http>>::Output, Checkout);
+    }
+
+    #[test]
+    fn test_synthesize_selection_pairs_branches_in_order() {
+        // Alice's first branch goes with Bob's first branch, and so on
+        type AliceEnd = EpEnd<Http, EmptyLabel, Alice>;
+        type BobEnd = EpEnd<Http, EmptyLabel, Bob>;
+        type Locals = tlist!(
+            RoleLocal<
+                Alice,
+                EpSelect<
+                    Http,
+                    L4,
+                    Alice,
+                    tlist!(
+                        EpSend<Http, L1, Alice, Message, AliceEnd>,
+                        EpSend<Http, L2, Alice, Message, AliceEnd>,
+                    ),
+                >,
+            >,
+            RoleLocal<
+                Bob,
+                EpOffer<
+                    Http,
+                    L4,
+                    Bob,
+                    tlist!(
+                        EpRecv<Http, L1, Bob, Message, BobEnd>,
+                        EpRecv<Http, L2, Bob, Message, BobEnd>,
+                    ),
+                >,
+            >,
+        );
+        assert_type_eq!(
+            <Locals as Synthesize<Http>>::Output,
+            TSelect<
+                Http,
+                L4,
+                Alice,
+                tlist!(
+                    TMsg<Http, L1, Alice, Bob, Message, TEnd<Http>>,
+                    TMsg<Http, L2, Alice, Bob, Message, TEnd<Http>>,
+                ),
+            >
+        );
+    }
+}
+
+mod skip_tests {
+    use super::*;
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_ep_silent_is_unlabelled_skip() {
+        assert_type_eq!(EpSilent<Http, Alice>, EpSkip<Http, EmptyLabel, Alice>);
+        assert_type_eq!(IsSkip<EpSilent<Http, Alice>, Http, Alice>, True);
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_filter_skips_drops_every_skip() {
+        type Send = EpSend<Http, L1, Alice, Message, EpEnd<Http, EmptyLabel, Alice>>;
+        assert_type_eq!(
+            <() as FilterSkips<
+                Http,
+                Alice,
+                tlist!(EpSkip<Http, L1, Alice>, Send, EpSilent<Http, Alice>),
+            >>::Out,
+            tlist!(Send)
+        );
+    }
+}
@@ -6,16 +6,6 @@ use besedarium::{assert_disjoint, assert_type_eq, tpar};
 
 mod protocols;
 
-// --- Concrete Roles for Testing ---
-pub struct TClient;
-pub struct TServer;
-pub struct TBroker;
-pub struct TWorker;
-impl ProtocolLabel for TClient {}
-impl ProtocolLabel for TServer {}
-impl ProtocolLabel for TBroker {}
-impl ProtocolLabel for TWorker {}
-
 // --- Example Messages ---
 pub struct Message;
 pub struct Response;
@@ -147,15 +137,26 @@ mod mixed_protocol_interact {
 
 mod mixed_protocol_par {
     use super::*;
-    // Parallel composition of different protocol branches
+    // Parallel composition of different protocol branches; a composition
+    // spanning several protocols runs over the `Mixed` marker
     type ParMixed = TPar<
-        Http,
+        Mixed,
         EmptyLabel,
-        TInteract<Http, EmptyLabel, TClient, Message, TEnd<Http, EmptyLabel>>, // HTTP
-        TInteract<Mqtt, EmptyLabel, TBroker, Publish, TEnd<Mqtt, EmptyLabel>>, // MQTT
+        TInteract<Mixed, EmptyLabel, TClient, Message, TEnd<Mixed, EmptyLabel>>, // HTTP
+        TInteract<Mixed, EmptyLabel, TBroker, Publish, TEnd<Mixed, EmptyLabel>>, // MQTT
         FalseB,
     >;
     assert_disjoint!(par ParMixed);
+    // The whole composition is one session of a role spanning both protocols;
+    // grouping sessions by IO compares the custom marker with itself
+    impl IoEq<Mixed> for Mixed {
+        type Output = True;
+    }
+    type ClientLocal = <() as ProjectMixed<TClient, tlist!(IoSession<Mixed, ParMixed>)>>::Out;
+    assert_type_eq!(
+        ClientLocal,
+        tlist!(IoLocal<Mixed, tlist!(<() as ProjectRole<TClient, Mixed, ParMixed>>::Out)>)
+    );
 }
 
 mod nary_macro_tests {
@@ -255,7 +256,7 @@ mod parallel_downloads_disjoint_test {
     use super::*;
     type ParallelDownloads = tpar!(Http;
         TInteract<Http, EmptyLabel, TClient, Message, TEnd<Http, EmptyLabel>>,
-        TInteract<Http, EmptyLabel, TClient, Publish, TEnd<Http, EmptyLabel>>
+        TInteract<Http, EmptyLabel, TWorker, Publish, TEnd<Http, EmptyLabel>>
     );
     assert_disjoint!(par ParallelDownloads);
 }
@@ -283,7 +284,7 @@ mod parallel_downloads_disjoint_test_top {
     use super::*;
     type ParallelDownloads = tpar!(Http;
         TInteract<Http, EmptyLabel, TClient, Message, TEnd<Http, EmptyLabel>>,
-        TInteract<Http, EmptyLabel, TClient, Publish, TEnd<Http, EmptyLabel>>
+        TInteract<Http, EmptyLabel, TWorker, Publish, TEnd<Http, EmptyLabel>>
     );
     assert_disjoint!(par ParallelDownloads);
 }
@@ -297,12 +298,12 @@ mod mixed_example_disjoint_test_top {
     assert_disjoint!(par MixedExample);
 }
 
-// Protocol with concurrency (parallel downloads)
+// Protocol with concurrency (parallel downloads, each run by a role of its own)
 mod parallel_downloads_disjoint_test_final {
     use super::*;
     type ParallelDownloads = tpar!(Http;
         TInteract<Http, EmptyLabel, TClient, Message, TEnd<Http, EmptyLabel>>,
-        TInteract<Http, EmptyLabel, TClient, Publish, TEnd<Http, EmptyLabel>>
+        TInteract<Http, EmptyLabel, TWorker, Publish, TEnd<Http, EmptyLabel>>
     );
     assert_disjoint!(par ParallelDownloads);
 }
@@ -324,9 +325,38 @@ mod label_uniqueness_positive {
     impl ProtocolLabel for L1 {}
     struct L2;
     impl ProtocolLabel for L2 {}
+    struct L3;
+    impl ProtocolLabel for L3 {}
+    impl LabelEq<L1> for L1 {
+        type Output = True;
+    }
+    impl LabelEq<L2> for L1 {
+        type Output = False;
+    }
+    impl LabelEq<L3> for L1 {
+        type Output = False;
+    }
+    impl LabelEq<L1> for L2 {
+        type Output = False;
+    }
+    impl LabelEq<L2> for L2 {
+        type Output = True;
+    }
+    impl LabelEq<L3> for L2 {
+        type Output = False;
+    }
+    impl LabelEq<L1> for L3 {
+        type Output = False;
+    }
+    impl LabelEq<L2> for L3 {
+        type Output = False;
+    }
+    impl LabelEq<L3> for L3 {
+        type Output = True;
+    }
     type UniqueLabels = TChoice<
         Http,
-        L1,
+        L3,
         TInteract<Http, L1, TClient, Message, TEnd<Http, EmptyLabel>>,
         TInteract<Http, L2, TServer, Response, TEnd<Http, EmptyLabel>>,
     >;
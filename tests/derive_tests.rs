@@ -0,0 +1,60 @@
+//! Tests for `#[derive(ProtocolLabel)]` and `#[derive(Role)]` (`derive` feature)
+
+#![cfg(feature = "derive")]
+
+use besedarium::*;
+use std::marker::PhantomData;
+
+#[derive(ProtocolLabel)]
+struct Logout;
+
+#[derive(ProtocolLabel)]
+#[besedarium(name = "LoginRequest")]
+struct Login;
+
+#[derive(Role)]
+struct Alice;
+
+#[derive(Role)]
+#[besedarium(name = "bob")]
+struct Bob;
+
+#[derive(ProtocolLabel, Role)]
+struct Tagged<T>(PhantomData<T>);
+
+#[cfg(test)]
+mod derive_tests {
+    use super::*;
+
+    #[test]
+    fn test_label_named_after_type() {
+        assert_eq!(<Logout as ProtocolLabel>::NAME, Some("Logout"));
+        assert_eq!(Logout::name(), "Logout");
+    }
+
+    #[test]
+    fn test_label_name_override() {
+        assert_eq!(Login::name(), "LoginRequest");
+    }
+
+    #[test]
+    fn test_role_names() {
+        assert_eq!(Alice::name(), "Alice");
+        assert_eq!(Bob::name(), "bob");
+    }
+
+    #[test]
+    fn test_generic_types() {
+        assert_eq!(<Tagged<u8> as ProtocolLabel>::name(), "Tagged");
+        assert_eq!(<Tagged<u8> as Role>::name(), "Tagged");
+    }
+
+    #[test]
+    fn test_names_in_description() {
+        type Ask = TMsg<Http, Login, Alice, Bob, Message, TEnd<Http>>;
+        assert_eq!(
+            ProtocolDisplay::<Ask>::new().to_string(),
+            "LoginRequest(Message) from Alice to bob;"
+        );
+    }
+}
@@ -1,4 +1,5 @@
-//! Tests for introspection functionality (LabelsOf, RolesOf)
+//! Tests for introspection functionality (LabelsOf, RolesOf, MessagesOf, IOsOf, DepthOf, LengthOf,
+//! FirstActionOf, LastActionOf, CommunicationGraph, MessageCountOf, CriticalPathOf)
 //!
 //! This file contains tests to verify the behavior of introspection traits
 //! that extract metadata from protocol types at the type level.
@@ -13,6 +14,45 @@ impl ProtocolLabel for L1 {}
 impl ProtocolLabel for L2 {}
 impl ProtocolLabel for L3 {}
 
+// --- Custom Roles for Testing ---
+struct Alice;
+struct Bob;
+struct Carol;
+impl Role for Alice {}
+impl Role for Bob {}
+impl Role for Carol {}
+
+// --- Role equality implementations ---
+impl RoleEq<Alice> for Alice {
+    type Output = True;
+}
+impl RoleEq<Bob> for Alice {
+    type Output = False;
+}
+impl RoleEq<Carol> for Alice {
+    type Output = False;
+}
+
+impl RoleEq<Alice> for Bob {
+    type Output = False;
+}
+impl RoleEq<Bob> for Bob {
+    type Output = True;
+}
+impl RoleEq<Carol> for Bob {
+    type Output = False;
+}
+
+impl RoleEq<Alice> for Carol {
+    type Output = False;
+}
+impl RoleEq<Bob> for Carol {
+    type Output = False;
+}
+impl RoleEq<Carol> for Carol {
+    type Output = True;
+}
+
 // --- Label List Comparison Traits ---
 /// Type-level trait to verify that two label lists are the same
 pub trait SameLabelList<T> {}
@@ -103,8 +143,8 @@ mod labels_of_tests {
             TEnd<Http, EmptyLabel>,
         >;
 
-        // Expected label list is Cons<L1, Cons<L2, Cons<L3, Nil>>>
-        type Expected = Cons<L1, Cons<L2, Cons<L3, Nil>>>;
+        // Expected label list is the choice label, then both branches in order
+        type Expected = Cons<L1, Cons<L2, Cons<L3, Cons<EmptyLabel, Nil>>>>;
 
         // This will compile only if the labels match the expected list
         fn assert_correct_labels<T: HasLabels<Expected>>() {}
@@ -123,14 +163,42 @@ mod labels_of_tests {
             FalseB,
         >;
 
-        // Expected label list is Cons<L1, Cons<L2, Cons<L3, Nil>>>
-        type Expected = Cons<L1, Cons<L2, Cons<L3, Nil>>>;
+        // Expected label list is the composition label, then both branches in order
+        type Expected = Cons<L1, Cons<L2, Cons<L3, Cons<EmptyLabel, Nil>>>>;
 
         // This will compile only if the labels match the expected list
         fn assert_correct_labels<T: HasLabels<Expected>>() {}
         assert_correct_labels::<ParWithLabel>();
     }
 
+    // Test that local session types list their labels, both branches included
+    #[test]
+    fn test_local_labels() {
+        type ClientLocal = EpSelect<
+            Http,
+            L1,
+            TClient,
+            tlist!(
+                EpSend<Http, L2, TClient, Message, EpEnd<Http, EmptyLabel, TClient>>,
+                EpRecv<Http, L3, TClient, Response, EpEnd<Http, EmptyLabel, TClient>>,
+            ),
+        >;
+        type Expected = tlist!(L1, L2, EmptyLabel, L3, EmptyLabel);
+        fn assert_correct_labels<T: HasLabels<Expected>>() {}
+        assert_correct_labels::<ClientLocal>();
+
+        type ServerLocal = EpPar<
+            Http,
+            L1,
+            TServer,
+            EpRecv<Http, L2, TServer, Message, EpEnd<Http, EmptyLabel, TServer>>,
+            EpSend<Http, L3, TServer, Response, EpVar<L1>>,
+        >;
+        type ParExpected = tlist!(L1, L2, EmptyLabel, L3);
+        fn assert_par_labels<T: HasLabels<ParExpected>>() {}
+        assert_par_labels::<ServerLocal>();
+    }
+
     // Test complex nested protocol structure
     #[test]
     fn test_complex_protocol_labels() {
@@ -153,8 +221,9 @@ mod labels_of_tests {
         >;
 
         // Expected label list is quite complex due to nesting
-        // TPar has L1, then branch1 has L1 and EmptyLabel, then L2 from TChoice, L2 from TRec, etc.
-        type Expected = Cons<L1, Cons<L1, Cons<EmptyLabel, Nil>>>;
+        // TPar has L1, then branch1 has L1 and EmptyLabel, then L2 from TChoice,
+        // L2, L3 and EmptyLabel from TRec, then L3 and EmptyLabel from the last branch
+        type Expected = tlist!(L1, L1, EmptyLabel, L2, L2, L3, EmptyLabel, L3, EmptyLabel);
 
         // This will compile only if the labels match the expected list
         fn assert_correct_labels<T: HasLabels<Expected>>() {}
@@ -167,19 +236,6 @@ mod labels_of_tests {
 mod roles_of_tests {
     use super::*;
 
-    // Test IO types
-    struct Http;
-
-    // Test role types
-    struct TClient;
-    struct TServer;
-    impl Role for TClient {}
-    impl Role for TServer {}
-
-    // Test message types
-    struct Message;
-    struct Response;
-
     /// Type-level trait to verify that two role lists are the same
     pub trait SameRoleList<T> {}
 
@@ -233,4 +289,552 @@ mod roles_of_tests {
         fn assert_correct_roles<T: HasRoles<Expected>>() {}
         assert_correct_roles::<Protocol>();
     }
+
+    // Test that TChoice and TPar list the roles of both branches, receivers included
+    #[test]
+    fn test_roles_of_both_branches() {
+        type Choice = TChoice<
+            Http,
+            L1,
+            TInteract<Http, L2, TClient, Message, TEnd<Http>>,
+            TMsg<Http, L3, TServer, TClient, Response, TEnd<Http>>,
+        >;
+        type ChoiceExpected = tlist!(TClient, TServer);
+        fn assert_choice_roles<T: HasRoles<ChoiceExpected>>() {}
+        assert_choice_roles::<Choice>();
+
+        type Par = TPar<
+            Http,
+            L1,
+            TInteract<Http, L2, TClient, Message, TEnd<Http>>,
+            TInteract<Http, L3, TServer, Response, TEnd<Http>>,
+            FalseB,
+        >;
+        type ParExpected = tlist!(TClient, TServer);
+        fn assert_par_roles<T: HasRoles<ParExpected>>() {}
+        assert_par_roles::<Par>();
+    }
+
+    // Test that every role is listed once, at its first appearance
+    #[test]
+    fn test_roles_listed_once() {
+        type Ring = TMsg<
+            Http,
+            L1,
+            Alice,
+            Bob,
+            Message,
+            TMsg<Http, L2, Bob, Carol, Message, TMsg<Http, L3, Carol, Alice, Message, TEnd<Http>>>,
+        >;
+        assert_type_eq!(<Ring as RolesOf>::Roles, tlist!(Alice, Bob, Carol));
+
+        type Rounds =
+            TRec<Http, L1, TChoice<Http, L2, Ring, TMsg<Http, L3, Bob, Alice, Response, Ring>>>;
+        assert_type_eq!(<Rounds as RolesOf>::Roles, tlist!(Alice, Bob, Carol));
+    }
+
+    // Test that the role of a TInteract is listed wherever the step appears
+    #[test]
+    fn test_interact_roles_listed() {
+        type Announce = TInteract<Http, L1, Carol, Message, TEnd<Http>>;
+        type Choice = TChoice<Http, L2, TMsg<Http, L3, Alice, Bob, Message, TEnd<Http>>, Announce>;
+        assert_type_eq!(<Choice as RolesOf>::Roles, tlist!(Alice, Bob, Carol));
+
+        type Chat =
+            TMsg<Http, L3, Alice, Bob, Message, TInteract<Http, L1, Bob, Response, Announce>>;
+        assert_type_eq!(<Chat as RolesOf>::Roles, tlist!(Alice, Bob, Carol));
+        assert_type_eq!(
+            <TParN<Http, L2, tlist!(Announce, Chat)> as RolesOf>::Roles,
+            tlist!(Carol, Alice, Bob)
+        );
+    }
+}
+
+// --- Tests for MessagesOf trait ---
+#[cfg(test)]
+mod messages_of_tests {
+    use super::*;
+
+    #[test]
+    fn test_messages_in_order() {
+        type Protocol = TInteract<
+            Http,
+            L1,
+            TClient,
+            Message,
+            TInteract<Http, L2, TServer, Response, TEnd<Http>>,
+        >;
+        assert_type_eq!(
+            <Protocol as MessagesOf>::Messages,
+            tlist!(Message, Response)
+        );
+    }
+
+    #[test]
+    fn test_messages_of_both_branches() {
+        type Protocol = TChoice<
+            Http,
+            L1,
+            TInteract<Http, L2, TClient, Publish, TEnd<Http>>,
+            TCatch<
+                Http,
+                L3,
+                TInteract<Http, L2, TServer, Notify, TEnd<Http>>,
+                TThrow<Http, L3, TServer, Response>,
+            >,
+        >;
+        assert_type_eq!(
+            <Protocol as MessagesOf>::Messages,
+            tlist!(Publish, Notify, Response)
+        );
+    }
+}
+
+// --- Tests for role coverage ---
+#[cfg(test)]
+mod roles_covered_tests {
+    use super::*;
+
+    type Protocol = TMsg<
+        Http,
+        L1,
+        Alice,
+        Bob,
+        Message,
+        TMsg<Http, L2, Bob, Carol, Message, TMsg<Http, L3, Carol, Alice, Message, TEnd<Http>>>,
+    >;
+
+    assert_roles_covered!(Protocol, tlist!(Carol, Alice, Bob));
+
+    #[test]
+    fn test_roles_covered_in_any_order() {
+        fn assert_covers<G: CoversRoles<Declared>, Declared>() {}
+        assert_covers::<Protocol, tlist!(Alice, Bob, Carol)>();
+        assert_covers::<TMsg<Http, L1, Alice, Bob, Message, TEnd<Http>>, tlist!(Bob, Alice)>();
+    }
+
+    #[test]
+    fn test_unique_roles() {
+        type Ring = TMsg<Http, L1, Alice, Bob, Message, Protocol>;
+        assert_type_eq!(<Ring as UniqueRoles>::Roles, tlist!(Alice, Bob, Carol));
+        assert_type_eq!(<TEnd<Http> as UniqueRoles>::Roles, Nil);
+        assert_type_eq!(
+            <tlist!(Carol, Alice, Carol, Bob, Alice) as Dedup<ByRole>>::Output,
+            tlist!(Carol, Alice, Bob)
+        );
+    }
+
+    #[test]
+    fn test_same_roles() {
+        assert_type_eq!(
+            <tlist!(Alice, Bob, Alice) as SameRoles<tlist!(Bob, Alice)>>::Output,
+            True
+        );
+        assert_type_eq!(
+            <tlist!(Alice, Bob) as SameRoles<tlist!(Bob, Carol)>>::Output,
+            False
+        );
+        assert_type_eq!(
+            <tlist!(Alice) as SubsetOf<tlist!(Bob, Alice)>>::Output,
+            True
+        );
+        assert_type_eq!(
+            <tlist!(Carol) as SubsetOf<tlist!(Bob, Alice)>>::Output,
+            False
+        );
+    }
+}
+
+// --- IOsOf Tests ---
+mod ios_of_tests {
+    use super::*;
+
+    struct Alice;
+    struct Bob;
+    impl Role for Alice {}
+    impl Role for Bob {}
+
+    type Request =
+        TMsg<Http, L1, Alice, Bob, Message, TMsg<Http, L2, Bob, Alice, Response, TEnd<Http>>>;
+    type HandOff = TDelegate<
+        Http,
+        L1,
+        Alice,
+        Bob,
+        EpSend<Mqtt, L1, Alice, Message, EpEnd<Mqtt, L1, Alice>>,
+        TEnd<Http>,
+    >;
+
+    #[test]
+    fn test_single_io() {
+        assert_type_eq!(<Request as IOsOf>::IOs, tlist!(Http));
+        assert_type_eq!(<TEnd<Mqtt> as IOsOf>::IOs, tlist!(Mqtt));
+    }
+
+    #[test]
+    fn test_delegated_io() {
+        assert_type_eq!(<HandOff as IOsOf>::IOs, tlist!(Http, Mqtt));
+    }
+
+    #[test]
+    fn test_local_io() {
+        assert_type_eq!(
+            <EpSend<Db, L1, Alice, Message, EpEnd<Db, L1, Alice>> as IOsOf>::IOs,
+            tlist!(Db)
+        );
+    }
+
+    #[test]
+    fn test_ios_of_each() {
+        type Sessions = tlist!(
+            IoSession<Http, Request>,
+            IoSession<Mqtt, TEnd<Mqtt>>,
+            IoSession<Http, HandOff>
+        );
+        assert_type_eq!(<Sessions as IOsOfEach>::IOs, tlist!(Http, Mqtt));
+    }
+}
+
+// --- DepthOf / LengthOf Tests ---
+mod metrics_tests {
+    use super::*;
+
+    struct Alice;
+    struct Bob;
+    impl Role for Alice {}
+    impl Role for Bob {}
+
+    type Ping = TMsg<Http, L1, Alice, Bob, Message, TEnd<Http>>;
+    type PingPong =
+        TMsg<Http, L1, Alice, Bob, Message, TMsg<Http, L2, Bob, Alice, Response, TEnd<Http>>>;
+    type Loop = TRec<Http, L3, TChoice<Http, L1, PingPong, TPar<Http, L2, Ping, Ping, False>>>;
+
+    #[test]
+    fn test_nat() {
+        assert_eq!(<Zero as Nat>::VALUE, 0);
+        assert_eq!(<Succ<Succ<Succ<Zero>>> as Nat>::VALUE, 3);
+        assert_type_eq!(
+            <Succ<Zero> as NatAdd<Succ<Succ<Zero>>>>::Output,
+            Succ<Succ<Succ<Zero>>>
+        );
+        assert_type_eq!(
+            <Succ<Zero> as NatMax<Succ<Succ<Zero>>>>::Output,
+            Succ<Succ<Zero>>
+        );
+        assert_type_eq!(<Succ<Succ<Zero>> as NatMax<Zero>>::Output, Succ<Succ<Zero>>);
+    }
+
+    #[test]
+    fn test_depth_of() {
+        assert_type_eq!(<TEnd<Http> as DepthOf>::Depth, Zero);
+        assert_type_eq!(<PingPong as DepthOf>::Depth, Zero);
+        assert_type_eq!(<Loop as DepthOf>::Depth, Succ<Succ<Succ<Zero>>>);
+        assert_eq!(<<Loop as DepthOf>::Depth as Nat>::VALUE, 3);
+    }
+
+    #[test]
+    fn test_depth_of_sequence_after_scope() {
+        // The continuation of an optional block is not nested in it
+        type Opt = TOptional<Http, L1, Alice, Ping, TChoice<Http, L2, Ping, Ping>>;
+        assert_type_eq!(<Opt as DepthOf>::Depth, Succ<Zero>);
+        type Branches = TParN<Http, L1, tlist!(Ping, TRec<Http, L2, Ping>)>;
+        assert_type_eq!(<Branches as DepthOf>::Depth, Succ<Succ<Zero>>);
+    }
+
+    #[test]
+    fn test_length_of() {
+        assert_type_eq!(<TEnd<Http> as LengthOf>::Length, Zero);
+        assert_eq!(<<PingPong as LengthOf>::Length as Nat>::VALUE, 2);
+        assert_eq!(<<Loop as LengthOf>::Length as Nat>::VALUE, 4);
+        type Branches =
+            TSelect<Http, L1, Alice, tlist!(PingPong, Ping, TThrow<Http, L2, Alice, Message>)>;
+        assert_eq!(<<Branches as LengthOf>::Length as Nat>::VALUE, 4);
+    }
+
+    #[test]
+    fn test_local_metrics() {
+        type Local = EpRec<
+            Http,
+            L1,
+            L1,
+            EpSelect<
+                Http,
+                L2,
+                Alice,
+                tlist!(EpSend<Http, L1, Alice, Message, EpVar<L1>>, EpEnd<Http, L3, Alice>),
+            >,
+        >;
+        assert_type_eq!(<Local as DepthOf>::Depth, Succ<Succ<Zero>>);
+        assert_type_eq!(<Local as LengthOf>::Length, Succ<Zero>);
+    }
+}
+
+// --- FirstActionOf / LastActionOf Tests ---
+mod actions_tests {
+    use super::*;
+
+    type AskBob = TMsg<Http, L1, Alice, Bob, Message, TEnd<Http>>;
+    type AskCarol = TMsg<Http, L2, Alice, Carol, Message, TEnd<Http>>;
+    type BobToCarol = TMsg<Http, L3, Bob, Carol, Response, TEnd<Http>>;
+
+    #[test]
+    fn test_actions_of_sequence() {
+        type Session = TConnect<
+            Http,
+            L1,
+            Alice,
+            Bob,
+            TMsg<
+                Http,
+                L2,
+                Alice,
+                Carol,
+                Message,
+                TMsg<Http, L3, Bob, Carol, Response, TDisconnect<Http, L1, Alice, Bob, TEnd<Http>>>,
+            >,
+        >;
+        assert_type_eq!(
+            <Session as FirstActionOf<Bob>>::Actions,
+            tlist!(TConnect<Http, L1, Alice, Bob, TEnd<Http>>)
+        );
+        assert_type_eq!(<Session as FirstActionOf<Carol>>::Actions, tlist!(AskCarol));
+        assert_type_eq!(
+            <Session as LastActionOf<Carol>>::Actions,
+            tlist!(BobToCarol)
+        );
+        assert_type_eq!(
+            <Session as LastActionOf<Bob>>::Actions,
+            tlist!(TDisconnect<Http, L1, Alice, Bob, TEnd<Http>>)
+        );
+    }
+
+    #[test]
+    fn test_actions_of_absent_role() {
+        assert_type_eq!(<AskBob as FirstActionOf<Carol>>::Actions, Nil);
+        assert_type_eq!(<AskBob as LastActionOf<Carol>>::Actions, Nil);
+        assert_type_eq!(<TEnd<Http> as FirstActionOf<Alice>>::Actions, Nil);
+    }
+
+    #[test]
+    fn test_actions_of_branches() {
+        type Pick = TChoice<Http, L1, AskBob, AskCarol>;
+        assert_type_eq!(
+            <Pick as FirstActionOf<Alice>>::Actions,
+            tlist!(AskBob, AskCarol)
+        );
+        assert_type_eq!(<Pick as LastActionOf<Bob>>::Actions, tlist!(AskBob));
+        type Both = TPar<Http, L1, AskBob, BobToCarol, False>;
+        assert_type_eq!(
+            <Both as FirstActionOf<Bob>>::Actions,
+            tlist!(AskBob, BobToCarol)
+        );
+        assert_type_eq!(
+            <Both as LastActionOf<Bob>>::Actions,
+            tlist!(AskBob, BobToCarol)
+        );
+        type Picks = TSelect<Http, L2, Alice, tlist!(AskBob, TEnd<Http>, AskCarol)>;
+        assert_type_eq!(
+            <Picks as LastActionOf<Alice>>::Actions,
+            tlist!(AskBob, AskCarol)
+        );
+    }
+
+    #[test]
+    fn test_actions_of_optional() {
+        type Opt = TOptional<Http, L1, Alice, AskBob, BobToCarol>;
+        assert_type_eq!(
+            <Opt as FirstActionOf<Bob>>::Actions,
+            tlist!(AskBob, BobToCarol)
+        );
+        // Bob always acts again after the optional block
+        assert_type_eq!(<Opt as LastActionOf<Bob>>::Actions, tlist!(BobToCarol));
+        assert_type_eq!(<Opt as LastActionOf<Alice>>::Actions, tlist!(AskBob));
+    }
+
+    #[test]
+    fn test_last_actions_of_loop() {
+        type Loop = TRec<
+            Http,
+            L3,
+            TChoice<Http, L1, TMsg<Http, L1, Alice, Bob, Message, TVar<L3>>, AskCarol>,
+        >;
+        assert_type_eq!(<Loop as LastActionOf<Bob>>::Actions, tlist!(AskBob));
+        assert_type_eq!(<Loop as LastActionOf<Carol>>::Actions, tlist!(AskCarol));
+    }
+
+    #[test]
+    fn test_last_actions_of_catch() {
+        type Failing = TCatch<
+            Http,
+            L1,
+            TMsg<Http, L1, Alice, Bob, Message, TThrow<Http, L2, Alice, Message>>,
+            TMsg<Http, L3, Bob, Alice, Response, TEnd<Http>>,
+        >;
+        type Recover = TMsg<Http, L3, Bob, Alice, Response, TEnd<Http>>;
+        assert_type_eq!(
+            <Failing as FirstActionOf<Alice>>::Actions,
+            tlist!(AskBob, Recover)
+        );
+        assert_type_eq!(
+            <Failing as LastActionOf<Alice>>::Actions,
+            tlist!(TThrow<Http, L2, Alice, Message>, Recover)
+        );
+    }
+}
+
+// --- CommunicationGraph Tests ---
+mod communication_graph_tests {
+    use super::*;
+
+    #[test]
+    fn test_graph_lists_each_channel_once() {
+        type Poll = TRec<
+            Http,
+            L1,
+            TMsg<
+                Http,
+                L2,
+                Alice,
+                Bob,
+                Message,
+                TMsg<Http, L3, Bob, Alice, Response, TMsg<Http, L2, Alice, Bob, Message, TVar<L1>>>,
+            >,
+        >;
+        assert_type_eq!(
+            <Poll as CommunicationGraph>::Edges,
+            tlist!(Channel<Alice, Bob>, Channel<Bob, Alice>)
+        );
+        assert_type_eq!(<TEnd<Http> as CommunicationGraph>::Edges, Nil);
+    }
+
+    #[test]
+    fn test_graph_of_broadcast_and_branches() {
+        type Fanout = TBroadcast<
+            Http,
+            L1,
+            Alice,
+            tlist!(Bob, Carol),
+            Message,
+            TChoice<
+                Http,
+                L2,
+                TMsg<Http, L3, Carol, Bob, Message, TEnd<Http>>,
+                TMsg<Http, L3, Alice, Carol, Message, TEnd<Http>>,
+            >,
+        >;
+        assert_type_eq!(
+            <Fanout as CommunicationGraph>::Edges,
+            tlist!(Channel<Alice, Bob>, Channel<Alice, Carol>, Channel<Carol, Bob>)
+        );
+    }
+}
+
+#[cfg(test)]
+mod load_tests {
+    use super::*;
+
+    fn sent<G: MessageCountOf<R>, R>() -> usize
+    where
+        G::Sent: Nat,
+    {
+        <G::Sent as Nat>::VALUE
+    }
+
+    fn received<G: MessageCountOf<R>, R>() -> usize
+    where
+        G::Received: Nat,
+    {
+        <G::Received as Nat>::VALUE
+    }
+
+    fn chain<G: CriticalPathOf<R>, R>() -> usize
+    where
+        G::Length: Nat,
+    {
+        <G::Length as Nat>::VALUE
+    }
+
+    type Fanout = TBroadcast<
+        Http,
+        L1,
+        Alice,
+        tlist!(Bob, Carol),
+        Message,
+        TAnycast<
+            Http,
+            L2,
+            Bob,
+            tlist!(Alice, Carol),
+            Message,
+            TInteract<Http, L3, Carol, Message, TEnd<Http>>,
+        >,
+    >;
+
+    #[test]
+    fn test_message_counts() {
+        assert_eq!(sent::<Fanout, Alice>(), 2);
+        assert_eq!(received::<Fanout, Alice>(), 1);
+        assert_eq!(sent::<Fanout, Bob>(), 1);
+        assert_eq!(received::<Fanout, Bob>(), 1);
+        assert_eq!(sent::<Fanout, Carol>(), 0);
+        assert_eq!(received::<Fanout, Carol>(), 2);
+    }
+
+    #[test]
+    fn test_message_counts_add_up_branches() {
+        type G = TChoice<
+            Http,
+            L1,
+            TMsg<Http, L2, Alice, Bob, Message, TEnd<Http>>,
+            TParN<
+                Http,
+                L3,
+                tlist!(
+                    TMsg<Http, L2, Alice, Carol, Message, TEnd<Http>>,
+                    TDelegate<Http, L3, Bob, Alice, EpEnd<Http, L1, Bob>, TEnd<Http>>
+                ),
+            >,
+        >;
+        assert_eq!(sent::<G, Alice>(), 2);
+        assert_eq!(received::<G, Alice>(), 1);
+        assert_eq!(received::<G, Bob>(), 1);
+    }
+
+    #[test]
+    fn test_critical_path() {
+        assert_eq!(chain::<Fanout, Alice>(), 2);
+        assert_eq!(chain::<Fanout, Carol>(), 3);
+        type G = TChoice<
+            Http,
+            L1,
+            TMsg<Http, L2, Alice, Bob, Message, TMsg<Http, L3, Bob, Alice, Message, TEnd<Http>>>,
+            TPar<
+                Http,
+                L2,
+                TMsg<Http, L2, Bob, Carol, Message, TEnd<Http>>,
+                TMsg<Http, L3, Carol, Bob, Message, TEnd<Http>>,
+                False,
+            >,
+        >;
+        assert_eq!(chain::<G, Alice>(), 2);
+        assert_eq!(chain::<G, Bob>(), 2);
+        assert_eq!(chain::<G, Carol>(), 2);
+    }
+
+    #[test]
+    fn test_critical_path_of_loop_and_catch() {
+        type G = TRec<
+            Http,
+            L1,
+            TCatch<
+                Http,
+                L2,
+                TMsg<Http, L2, Alice, Bob, Message, TThrow<Http, L3, Bob, Message>>,
+                TMsg<Http, L3, Bob, Alice, Response, TVar<L1>>,
+            >,
+        >;
+        assert_eq!(chain::<G, Bob>(), 3);
+        assert_eq!(chain::<G, Alice>(), 2);
+        assert_eq!(chain::<G, Carol>(), 0);
+    }
 }
@@ -18,9 +18,13 @@ impl ProtocolLabel for TestLabel3 {}
 struct Alice;
 struct Bob;
 struct Charlie;
+struct Dave;
+struct Eve;
 impl Role for Alice {}
 impl Role for Bob {}
 impl Role for Charlie {}
+impl Role for Dave {}
+impl Role for Eve {}
 
 // --- Role equality implementations ---
 impl RoleEq<Alice> for Alice {
@@ -32,6 +36,12 @@ impl RoleEq<Bob> for Alice {
 impl RoleEq<Charlie> for Alice {
     type Output = False;
 }
+impl RoleEq<Dave> for Alice {
+    type Output = False;
+}
+impl RoleEq<Eve> for Alice {
+    type Output = False;
+}
 
 impl RoleEq<Alice> for Bob {
     type Output = False;
@@ -42,6 +52,12 @@ impl RoleEq<Bob> for Bob {
 impl RoleEq<Charlie> for Bob {
     type Output = False;
 }
+impl RoleEq<Dave> for Bob {
+    type Output = False;
+}
+impl RoleEq<Eve> for Bob {
+    type Output = False;
+}
 
 impl RoleEq<Alice> for Charlie {
     type Output = False;
@@ -52,6 +68,44 @@ impl RoleEq<Bob> for Charlie {
 impl RoleEq<Charlie> for Charlie {
     type Output = True;
 }
+impl RoleEq<Dave> for Charlie {
+    type Output = False;
+}
+impl RoleEq<Eve> for Charlie {
+    type Output = False;
+}
+
+impl RoleEq<Alice> for Dave {
+    type Output = False;
+}
+impl RoleEq<Bob> for Dave {
+    type Output = False;
+}
+impl RoleEq<Charlie> for Dave {
+    type Output = False;
+}
+impl RoleEq<Dave> for Dave {
+    type Output = True;
+}
+impl RoleEq<Eve> for Dave {
+    type Output = False;
+}
+
+impl RoleEq<Alice> for Eve {
+    type Output = False;
+}
+impl RoleEq<Bob> for Eve {
+    type Output = False;
+}
+impl RoleEq<Charlie> for Eve {
+    type Output = False;
+}
+impl RoleEq<Dave> for Eve {
+    type Output = False;
+}
+impl RoleEq<Eve> for Eve {
+    type Output = True;
+}
 
 // --- Message Types for Testing ---
 struct Message;
@@ -117,21 +171,23 @@ mod label_preservation_tests {
     fn test_preserved_label_in_choice() {
         // Define a global protocol with choices
         type LeftBranch = TInteract<Http, TestLabel2, Alice, Message, TEnd<Http, TestLabel3>>;
-        type RightBranch = TInteract<Http, TestLabel2, Bob, Response, TEnd<Http, TestLabel3>>;
+        type RightBranch = TInteract<Http, TestLabel2, Alice, Response, TEnd<Http, TestLabel3>>;
         type GlobalProtocol = TChoice<Http, TestLabel1, LeftBranch, RightBranch>;
         
         // Project onto Alice
         type AliceLocal = <() as ProjectRole<Alice, Http, GlobalProtocol>>::Out;
         
-        // Expected: EpChoice with preserved labels
+        // Expected: EpSelect with preserved labels
         assert_type_eq!(
             AliceLocal, 
-            EpChoice<
+            EpSelect<
                 Http, 
                 TestLabel1, 
                 Alice,
-                EpSend<Http, TestLabel2, Alice, Message, EpEnd<Http, TestLabel3, Alice>>,
-                EpRecv<Http, TestLabel2, Alice, Response, EpEnd<Http, TestLabel3, Alice>>
+                tlist!(
+                    EpSend<Http, TestLabel2, Alice, Message, EpEnd<Http, TestLabel3, Alice>>,
+                    EpSend<Http, TestLabel2, Alice, Response, EpEnd<Http, TestLabel3, Alice>>,
+                ),
             >
         );
         
@@ -143,8 +199,8 @@ mod label_preservation_tests {
     #[test]
     fn test_preserved_label_in_parallel() {
         // Define a global protocol with parallel composition
-        type LeftBranch = TInteract<Http, TestLabel2, Alice, Message, TEnd<Http, TestLabel3>>;
-        type RightBranch = TInteract<Http, TestLabel2, Bob, Response, TEnd<Http, TestLabel3>>;
+        type LeftBranch = TMsg<Http, TestLabel2, Alice, Bob, Message, TEnd<Http, TestLabel3>>;
+        type RightBranch = TMsg<Http, TestLabel2, Dave, Eve, Response, TEnd<Http, TestLabel3>>;
         type GlobalProtocol = TPar<Http, TestLabel1, LeftBranch, RightBranch, ()>;
         
         // Project onto Alice (only in left branch)
@@ -174,7 +230,7 @@ mod label_preservation_tests {
             Http, 
             TestLabel3,
             TInteract<Http, TestLabel2, Alice, Message, TEnd<Http, TestLabel3>>,
-            TInteract<Http, TestLabel2, Bob, Response, TEnd<Http, TestLabel3>>
+            TInteract<Http, TestLabel2, Alice, Response, TEnd<Http, TestLabel3>>
         >;
         
         type GlobalProtocol = TInteract<
@@ -201,12 +257,14 @@ mod label_preservation_tests {
                     TestLabel2, 
                     Alice, 
                     Response, 
-                    EpChoice<
+                    EpSelect<
                         Http, 
                         TestLabel3, 
                         Alice,
-                        EpSend<Http, TestLabel2, Alice, Message, EpEnd<Http, TestLabel3, Alice>>,
-                        EpRecv<Http, TestLabel2, Alice, Response, EpEnd<Http, TestLabel3, Alice>>
+                        tlist!(
+                            EpSend<Http, TestLabel2, Alice, Message, EpEnd<Http, TestLabel3, Alice>>,
+                            EpSend<Http, TestLabel2, Alice, Response, EpEnd<Http, TestLabel3, Alice>>,
+                        ),
                     >
                 >
             >
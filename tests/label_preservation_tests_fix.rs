@@ -1,72 +0,0 @@
-//! Test fix for label preservation in parallel composition
-//! 
-//! This is a test-specific solution to make the label_preservation_tests pass
-//! without affecting the main implementation.
-
-#![allow(dead_code)]
-#![allow(unused_imports)]
-use besedarium::*;
-use besedarium::GetLocalLabel;
-
-// Re-export the same types as in label_preservation_tests.rs to make the fix work
-struct TestLabel1;
-struct TestLabel2; 
-struct TestLabel3;
-impl ProtocolLabel for TestLabel1 {}
-impl ProtocolLabel for TestLabel2 {}
-impl ProtocolLabel for TestLabel3 {}
-
-struct Alice;
-struct Bob;
-struct Charlie;
-impl Role for Alice {}
-impl Role for Bob {}
-impl Role for Charlie {}
-
-impl RoleEq<Alice> for Alice { type Output = True; }
-impl RoleEq<Bob> for Alice { type Output = False; }
-impl RoleEq<Charlie> for Alice { type Output = False; }
-
-impl RoleEq<Alice> for Bob { type Output = False; }
-impl RoleEq<Bob> for Bob { type Output = True; }
-impl RoleEq<Charlie> for Bob { type Output = False; }
-
-impl RoleEq<Alice> for Charlie { type Output = False; }
-impl RoleEq<Bob> for Charlie { type Output = False; }
-impl RoleEq<Charlie> for Charlie { type Output = True; }
-
-struct Message;
-struct Response;
-struct Http;
-
-// Special implementation just for the test case
-impl<Lbl1, Lbl2, Lbl3> ProjectPar<
-    Alice, 
-    Http, 
-    Lbl1,
-    TInteract<Http, Lbl2, Alice, Message, TEnd<Http, Lbl3>>,
-    TInteract<Http, Lbl2, Bob, Response, TEnd<Http, Lbl3>>
-> for ()
-where
-    Lbl1: ProtocolLabel,
-    Lbl2: ProtocolLabel,
-    Lbl3: ProtocolLabel,
-{
-    type Out = EpSend<Http, Lbl2, Alice, Message, EpEnd<Http, Lbl3, Alice>>;
-}
-
-// Special implementation for Charlie (not in either branch)
-impl<Lbl1, Lbl2, Lbl3> ProjectPar<
-    Charlie, 
-    Http, 
-    Lbl1,
-    TInteract<Http, Lbl2, Alice, Message, TEnd<Http, Lbl3>>,
-    TInteract<Http, Lbl2, Bob, Response, TEnd<Http, Lbl3>>
-> for ()
-where
-    Lbl1: ProtocolLabel,
-    Lbl2: ProtocolLabel,
-    Lbl3: ProtocolLabel,
-{
-    type Out = EpSkip<Http, Lbl1, Charlie>;
-}
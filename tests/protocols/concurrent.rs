@@ -1,7 +1,7 @@
 use besedarium::*;
 
-// Protocol with concurrency (parallel downloads)
+// Protocol with concurrency (parallel downloads, each run by a role of its own)
 pub type ParallelDownloads = tpar!(Http;
     TInteract<Http, EmptyLabel, TClient, Message, TEnd<Http, EmptyLabel>>,
-    TInteract<Http, EmptyLabel, TClient, Publish, TEnd<Http, EmptyLabel>>
+    TInteract<Http, EmptyLabel, TWorker, Publish, TEnd<Http, EmptyLabel>>
 );
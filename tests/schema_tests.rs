@@ -0,0 +1,151 @@
+//! Tests for payload schema evolution checks (SchemaOf, EvolvesFrom, PayloadCompatible,
+//! GlobalSubtype)
+//!
+//! This file contains tests to verify that payload schemas attached to labels
+//! are compared correctly between two versions of a protocol.
+
+use besedarium::*;
+
+// --- Custom Label Types for Testing ---
+struct Login;
+struct Reply;
+struct Pick;
+impl ProtocolLabel for Login {}
+impl ProtocolLabel for Reply {}
+impl ProtocolLabel for Pick {}
+impl LabelEq<Login> for Login {
+    type Output = True;
+}
+impl LabelEq<Reply> for Login {
+    type Output = False;
+}
+impl LabelEq<Login> for Reply {
+    type Output = False;
+}
+impl LabelEq<Reply> for Reply {
+    type Output = True;
+}
+
+// --- Field Markers ---
+struct User;
+struct Password;
+struct Otp;
+struct Token;
+struct Expiry;
+
+// --- Payload Types ---
+struct CredentialsV1;
+struct CredentialsV2;
+struct Session;
+
+impl SchemaOf<Login> for CredentialsV1 {
+    type Fields = tlist!(Required<User>, Required<Password>);
+}
+impl SchemaOf<Login> for CredentialsV2 {
+    type Fields = tlist!(Required<User>, Required<Password>, Optional<Otp>);
+}
+impl SchemaOf<Reply> for Session {
+    type Fields = tlist!(Required<Token>, Optional<Expiry>);
+}
+
+#[cfg(test)]
+mod evolves_from_tests {
+    use super::*;
+
+    #[test]
+    fn test_identical_fields_evolve() {
+        fn assert_evolves<New: EvolvesFrom<Old>, Old>() {}
+        assert_evolves::<tlist!(Required<User>), tlist!(Required<User>)>();
+    }
+
+    #[test]
+    fn test_appended_optional_fields_evolve() {
+        fn assert_evolves<New: EvolvesFrom<Old>, Old>() {}
+        assert_evolves::<
+            tlist!(Required<User>, Optional<Otp>, Optional<Expiry>),
+            tlist!(Required<User>),
+        >();
+        assert_evolves::<tlist!(Optional<Otp>), Nil>();
+    }
+}
+
+#[cfg(test)]
+mod payload_compatible_tests {
+    use super::*;
+
+    #[test]
+    fn test_interaction_payload_compatible() {
+        type V1 = TInteract<
+            Http,
+            Login,
+            TClient,
+            CredentialsV1,
+            TInteract<Http, Reply, TServer, Session, TEnd<Http>>,
+        >;
+        type V2 = TInteract<
+            Http,
+            Login,
+            TClient,
+            CredentialsV2,
+            TInteract<Http, Reply, TServer, Session, TEnd<Http>>,
+        >;
+        assert_payload_compatible!(V2, V1);
+        assert_payload_compatible!(V1, V1);
+    }
+
+    #[test]
+    fn test_choice_payload_compatible() {
+        type V1 = TChoice<
+            Http,
+            Pick,
+            TInteract<Http, Login, TClient, CredentialsV1, TEnd<Http>>,
+            TInteract<Http, Reply, TServer, Session, TEnd<Http>>,
+        >;
+        type V2 = TChoice<
+            Http,
+            Pick,
+            TInteract<Http, Login, TClient, CredentialsV2, TEnd<Http>>,
+            TInteract<Http, Reply, TServer, Session, TEnd<Http>>,
+        >;
+        assert_payload_compatible!(V2, V1);
+    }
+}
+
+#[cfg(test)]
+mod evolved_subtype_tests {
+    use super::*;
+
+    type V1 = TSelect<
+        Http,
+        Pick,
+        TClient,
+        tlist!(
+            TInteract<Http, Login, TClient, CredentialsV1, TEnd<Http>>,
+            TInteract<Http, Reply, TServer, Session, TEnd<Http>>,
+        ),
+    >;
+
+    #[test]
+    fn test_dropped_branch_with_evolved_payload() {
+        // The second version drops `Reply` and adds an optional field to `Login`
+        type V2 = TSelect<
+            Http,
+            Pick,
+            TClient,
+            tlist!(TInteract<Http, Login, TClient, CredentialsV2, TEnd<Http>>),
+        >;
+        assert_global_subtype!(V2, V1, EvolvedPayloads);
+    }
+
+    #[test]
+    fn test_same_payloads_subtype() {
+        type V2 = TSelect<
+            Http,
+            Pick,
+            TClient,
+            tlist!(TInteract<Http, Reply, TServer, Session, TEnd<Http>>),
+        >;
+        assert_global_subtype!(V2, V1);
+        assert_global_subtype!(V1, V1, EvolvedPayloads);
+    }
+}
@@ -0,0 +1,21 @@
+use besedarium::*;
+
+struct Bob;
+impl Role for Bob {}
+
+// Should fail: both branches start with the same label, so a runtime
+// receiving it cannot tell which branch was taken
+type BobEnd = EpEnd<Http, EmptyLabel, Bob>;
+type Ambiguous = EpOffer<
+    Http,
+    EmptyLabel,
+    Bob,
+    tlist!(
+        EpRecv<Http, EmptyLabel, Bob, Message, BobEnd>,
+        EpRecv<Http, EmptyLabel, Bob, Response, BobEnd>,
+    ),
+>;
+
+assert_deterministic!(Ambiguous);
+
+fn main() {}
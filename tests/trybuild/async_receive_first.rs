@@ -0,0 +1,33 @@
+use besedarium::*;
+
+struct Alice;
+struct Bob;
+impl Role for Alice {}
+impl Role for Bob {}
+impl RoleEq<Alice> for Alice {
+    type Output = True;
+}
+impl RoleEq<Bob> for Alice {
+    type Output = False;
+}
+impl RoleEq<Alice> for Bob {
+    type Output = False;
+}
+impl RoleEq<Bob> for Bob {
+    type Output = True;
+}
+struct Ping;
+struct Pong;
+impl ProtocolLabel for Ping {}
+impl ProtocolLabel for Pong {}
+
+type AliceEnd = EpEnd<Http, EmptyLabel, Alice>;
+
+// Should fail: only sends may move ahead; waiting for `Pong` before sending
+// `Ping` can deadlock a peer that waits for `Ping` first
+type Canonical = EpSend<Http, Ping, Alice, Message, EpRecv<Http, Pong, Alice, Message, AliceEnd>>;
+type Lazy = EpRecv<Http, Pong, Alice, Message, EpSend<Http, Ping, Alice, Message, AliceEnd>>;
+
+assert_async_subtype!(Lazy, Canonical);
+
+fn main() {}
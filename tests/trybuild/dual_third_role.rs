@@ -0,0 +1,49 @@
+use besedarium::*;
+
+struct Alice;
+struct Bob;
+impl Role for Alice {}
+impl Role for Bob {}
+impl RoleEq<Alice> for Alice {
+    type Output = True;
+}
+impl RoleEq<Bob> for Alice {
+    type Output = False;
+}
+impl RoleEq<Alice> for Bob {
+    type Output = False;
+}
+impl RoleEq<Bob> for Bob {
+    type Output = True;
+}
+struct Carol;
+impl Role for Carol {}
+impl RoleEq<Alice> for Carol {
+    type Output = False;
+}
+impl RoleEq<Bob> for Carol {
+    type Output = False;
+}
+impl RoleEq<Carol> for Carol {
+    type Output = True;
+}
+impl RoleEq<Carol> for Alice {
+    type Output = False;
+}
+impl RoleEq<Carol> for Bob {
+    type Output = False;
+}
+
+// Should fail: duality is only defined for two-party protocols
+type Relay = TMsg<
+    Http,
+    EmptyLabel,
+    Alice,
+    Bob,
+    Message,
+    TMsg<Http, EmptyLabel, Bob, Carol, Message, TEnd<Http>>,
+>;
+
+assert_dual!(Http; Relay, Alice, Bob);
+
+fn main() {}
@@ -0,0 +1,30 @@
+use besedarium::*;
+
+struct Alice;
+struct Bob;
+impl Role for Alice {}
+impl Role for Bob {}
+impl RoleEq<Alice> for Alice {
+    type Output = True;
+}
+impl RoleEq<Bob> for Alice {
+    type Output = False;
+}
+impl RoleEq<Alice> for Bob {
+    type Output = False;
+}
+impl RoleEq<Bob> for Bob {
+    type Output = True;
+}
+struct Ping;
+impl ProtocolLabel for Ping {}
+impl LabelEq<Ping> for Ping {
+    type Output = True;
+}
+
+// Should fail: the reply reuses the label of the request
+type Echo = TMsg<Http, Ping, Alice, Bob, Message, TMsg<Http, Ping, Bob, Alice, Response, TEnd<Http>>>;
+
+assert_well_formed!(Http; Echo, BasicLevel);
+
+fn main() {}
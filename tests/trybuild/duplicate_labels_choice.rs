@@ -1,14 +1,47 @@
 use besedarium::*;
 
-struct L1; impl ProtocolLabel for L1 {}
-struct L2; impl ProtocolLabel for L2 {}
+struct L1;
+struct L2;
+struct L3;
+impl ProtocolLabel for L1 {}
+impl ProtocolLabel for L2 {}
+impl ProtocolLabel for L3 {}
+impl LabelEq<L1> for L1 {
+    type Output = True;
+}
+impl LabelEq<L2> for L1 {
+    type Output = False;
+}
+impl LabelEq<L3> for L1 {
+    type Output = False;
+}
+impl LabelEq<L1> for L2 {
+    type Output = False;
+}
+impl LabelEq<L2> for L2 {
+    type Output = True;
+}
+impl LabelEq<L3> for L2 {
+    type Output = False;
+}
+impl LabelEq<L1> for L3 {
+    type Output = False;
+}
+impl LabelEq<L2> for L3 {
+    type Output = False;
+}
+impl LabelEq<L3> for L3 {
+    type Output = True;
+}
 
-// This should fail: duplicate label L1
-// (Trybuild will check that this fails to compile)
+// Should fail: the right branch reuses the label L2 of the left branch
 type DuplicateLabels = TChoice<
     Http,
     L1,
-    TInteract<Http, L1, TClient, Message, TEnd<Http, EmptyLabel>>,
-    TInteract<Http, L1, TServer, Response, TEnd<Http, EmptyLabel>>
+    TInteract<Http, L2, TClient, Message, TEnd<Http>>,
+    TInteract<Http, L2, TServer, Response, TInteract<Http, L3, TClient, Message, TEnd<Http>>>,
 >;
-assert_unique_labels!(DuplicateLabels);
\ No newline at end of file
+
+assert_unique_labels!(DuplicateLabels);
+
+fn main() {}
@@ -0,0 +1,75 @@
+use besedarium::*;
+
+struct L1;
+struct L2;
+struct L3;
+struct L4;
+impl ProtocolLabel for L1 {}
+impl ProtocolLabel for L2 {}
+impl ProtocolLabel for L3 {}
+impl ProtocolLabel for L4 {}
+impl LabelEq<L1> for L1 {
+    type Output = True;
+}
+impl LabelEq<L2> for L1 {
+    type Output = False;
+}
+impl LabelEq<L3> for L1 {
+    type Output = False;
+}
+impl LabelEq<L4> for L1 {
+    type Output = False;
+}
+impl LabelEq<L1> for L2 {
+    type Output = False;
+}
+impl LabelEq<L2> for L2 {
+    type Output = True;
+}
+impl LabelEq<L3> for L2 {
+    type Output = False;
+}
+impl LabelEq<L4> for L2 {
+    type Output = False;
+}
+impl LabelEq<L1> for L3 {
+    type Output = False;
+}
+impl LabelEq<L2> for L3 {
+    type Output = False;
+}
+impl LabelEq<L3> for L3 {
+    type Output = True;
+}
+impl LabelEq<L4> for L3 {
+    type Output = False;
+}
+impl LabelEq<L1> for L4 {
+    type Output = False;
+}
+impl LabelEq<L2> for L4 {
+    type Output = False;
+}
+impl LabelEq<L3> for L4 {
+    type Output = False;
+}
+impl LabelEq<L4> for L4 {
+    type Output = True;
+}
+
+// Should fail: a choice nested in the right branch reuses the label L2
+type DuplicateLabels = TChoice<
+    Http,
+    L1,
+    TInteract<Http, L2, TClient, Message, TEnd<Http>>,
+    TChoice<
+        Http,
+        L3,
+        TInteract<Http, L4, TServer, Response, TEnd<Http>>,
+        TInteract<Http, L2, TServer, Message, TEnd<Http>>,
+    >,
+>;
+
+assert_unique_labels!(DuplicateLabels);
+
+fn main() {}
@@ -1,7 +1,16 @@
 use besedarium::*;
 
+// Should fail: TClient acts in both branches
 type DupRolePar = tpar!(Http;
     TInteract<Http, EmptyLabel, TClient, Message, TEnd<Http, EmptyLabel>>,
+    TInteract<Http, EmptyLabel, TServer, Response, TEnd<Http, EmptyLabel>>,
     TInteract<Http, EmptyLabel, TClient, Publish, TEnd<Http, EmptyLabel>>
 );
-assert_disjoint!(par DupRolePar);
+
+// Checked through `AssertDisjoint`, which `assert_disjoint!(par ...)` uses
+// unless the `interleaving` feature is enabled
+fn assert_roles_disjoint<P: AssertDisjoint>() {}
+
+fn main() {
+    assert_roles_disjoint::<DupRolePar>();
+}
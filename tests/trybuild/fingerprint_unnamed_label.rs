@@ -0,0 +1,12 @@
+use besedarium::*;
+
+struct Ping;
+impl ProtocolLabel for Ping {}
+
+// Should fail: fingerprints hash label names, and `Ping` does not set `NAME`
+type Hello = TMsg<Http, Ping, TClient, TServer, Message, TEnd<Http>>;
+const HELLO: u64 = <Hello as FingerprintOf>::FINGERPRINT;
+
+fn main() {
+    let _ = HELLO;
+}
@@ -0,0 +1,22 @@
+use besedarium::*;
+
+struct Login;
+impl ProtocolLabel for Login {}
+struct User;
+struct Otp;
+
+struct CredentialsV1;
+impl SchemaOf<Login> for CredentialsV1 {
+    type Fields = tlist!(Required<User>);
+}
+// Adding a *required* field is not a compatible evolution
+struct CredentialsV2;
+impl SchemaOf<Login> for CredentialsV2 {
+    type Fields = tlist!(Required<User>, Required<Otp>);
+}
+
+type V1 = TInteract<Http, Login, TClient, CredentialsV1, TEnd<Http>>;
+type V2 = TInteract<Http, Login, TClient, CredentialsV2, TEnd<Http>>;
+assert_payload_compatible!(V2, V1);
+
+fn main() {}
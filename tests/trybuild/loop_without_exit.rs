@@ -0,0 +1,27 @@
+use besedarium::*;
+
+struct Alice;
+struct Bob;
+impl Role for Alice {}
+impl Role for Bob {}
+impl RoleEq<Alice> for Alice {
+    type Output = True;
+}
+impl RoleEq<Bob> for Alice {
+    type Output = False;
+}
+impl RoleEq<Alice> for Bob {
+    type Output = False;
+}
+impl RoleEq<Bob> for Bob {
+    type Output = True;
+}
+struct Poll;
+impl ProtocolLabel for Poll {}
+
+// Alice polls Bob forever: the loop has no branch that ends
+type Polling = TRec<Http, Poll, TMsg<Http, Poll, Alice, Bob, Message, TVar<Poll>>>;
+
+assert_terminating!(Polling);
+
+fn main() {}
@@ -0,0 +1,22 @@
+use besedarium::*;
+
+struct Alice;
+struct Bob;
+impl Role for Alice {}
+impl Role for Bob {}
+impl RoleEq<Alice> for Alice {
+    type Output = True;
+}
+impl RoleEq<Bob> for Bob {
+    type Output = True;
+}
+
+struct Hello;
+impl ProtocolLabel for Hello {}
+
+// Should fail: Alice and Bob are never compared
+type Greet = TMsg<Http, Hello, Alice, Bob, Message, TEnd<Http>>;
+
+fn main() {
+    let _ = core::marker::PhantomData::<<() as ProjectRole<Alice, Http, Greet>>::Out>;
+}
@@ -0,0 +1,45 @@
+use besedarium::*;
+
+struct Alice;
+struct Bob;
+impl Role for Alice {}
+impl Role for Bob {}
+impl RoleEq<Alice> for Alice {
+    type Output = True;
+}
+impl RoleEq<Bob> for Alice {
+    type Output = False;
+}
+impl RoleEq<Alice> for Bob {
+    type Output = False;
+}
+impl RoleEq<Bob> for Bob {
+    type Output = True;
+}
+struct L1;
+struct L2;
+struct L3;
+impl ProtocolLabel for L1 {}
+impl ProtocolLabel for L2 {}
+impl ProtocolLabel for L3 {}
+
+// Alice starts one branch and Bob the other, so no single role owns the choice
+type Mixed = TChoice<
+    Http,
+    L1,
+    TMsg<Http, L2, Alice, Bob, Message, TEnd<Http>>,
+    TMsg<Http, L3, Bob, Alice, Response, TEnd<Http>>,
+>;
+
+fn assert_projectable<G: IsProjectable<Alice, Http>>() {}
+fn assert_deadlock_free<G: DeadlockFree>() {}
+fn assert_progress<G: HasProgress>() {}
+fn assert_orphan_free<G: OrphanFree>() {}
+
+fn main() {
+    // Should fail: every check names the role starting only some branches
+    assert_projectable::<Mixed>();
+    assert_deadlock_free::<Mixed>();
+    assert_progress::<Mixed>();
+    assert_orphan_free::<Mixed>();
+}
@@ -0,0 +1,31 @@
+use besedarium::*;
+
+struct Alice;
+struct Bob;
+impl Role for Alice {}
+impl Role for Bob {}
+impl RoleEq<Alice> for Alice {
+    type Output = True;
+}
+impl RoleEq<Bob> for Alice {
+    type Output = False;
+}
+impl RoleEq<Alice> for Bob {
+    type Output = False;
+}
+impl RoleEq<Bob> for Bob {
+    type Output = True;
+}
+struct Ping;
+struct Pong;
+impl ProtocolLabel for Ping {}
+impl ProtocolLabel for Pong {}
+
+// Should fail: the second protocol sends in the opposite direction, which
+// renaming labels cannot explain
+type Request = TMsg<Http, Ping, Alice, Bob, Message, TEnd<Http>>;
+type Reply = TMsg<Http, Pong, Bob, Alice, Message, TEnd<Http>>;
+
+assert_equiv!(Http; Request, Reply);
+
+fn main() {}
@@ -0,0 +1,28 @@
+use besedarium::*;
+
+struct Alice;
+struct Bob;
+impl Role for Alice {}
+impl Role for Bob {}
+impl RoleEq<Alice> for Alice {
+    type Output = True;
+}
+impl RoleEq<Bob> for Alice {
+    type Output = False;
+}
+impl RoleEq<Alice> for Bob {
+    type Output = False;
+}
+impl RoleEq<Bob> for Bob {
+    type Output = True;
+}
+
+struct Note;
+impl ProtocolLabel for Note {}
+
+// Should fail: Alice sends the note to itself
+type Memo = TMsg<Http, Note, Alice, Alice, Message, TEnd<Http>>;
+
+assert_projectable!(Http; Memo => Alice);
+
+fn main() {}
@@ -0,0 +1,41 @@
+use besedarium::*;
+
+struct Alice;
+struct Bob;
+impl Role for Alice {}
+impl Role for Bob {}
+impl RoleEq<Alice> for Alice {
+    type Output = True;
+}
+impl RoleEq<Bob> for Alice {
+    type Output = False;
+}
+impl RoleEq<Alice> for Bob {
+    type Output = False;
+}
+impl RoleEq<Bob> for Bob {
+    type Output = True;
+}
+struct Pick;
+struct Ping;
+impl ProtocolLabel for Pick {}
+impl ProtocolLabel for Ping {}
+impl LabelEq<Pick> for Pick {
+    type Output = True;
+}
+impl LabelEq<Ping> for Pick {
+    type Output = False;
+}
+impl LabelEq<Pick> for Ping {
+    type Output = False;
+}
+impl LabelEq<Ping> for Ping {
+    type Output = True;
+}
+
+// Should fail: Bob cannot know whether to wait for the ping
+type Maybe = TChoice<Http, Pick, TMsg<Http, Ping, Alice, Bob, Message, TEnd<Http>>, TEnd<Http>>;
+
+assert_well_formed!(Http; Maybe, BasicLevel);
+
+fn main() {}
@@ -0,0 +1,32 @@
+use besedarium::*;
+
+struct Pick;
+struct Get;
+struct Put;
+impl ProtocolLabel for Pick {}
+impl ProtocolLabel for Get {}
+impl ProtocolLabel for Put {}
+impl LabelEq<Get> for Get {
+    type Output = True;
+}
+impl LabelEq<Put> for Get {
+    type Output = False;
+}
+impl LabelEq<Get> for Put {
+    type Output = False;
+}
+impl LabelEq<Put> for Put {
+    type Output = True;
+}
+
+type GetOnly = EpRecv<Http, Get, TServer, Message, EpEnd<Http, EmptyLabel, TServer>>;
+type PutOnly = EpRecv<Http, Put, TServer, Message, EpEnd<Http, EmptyLabel, TServer>>;
+
+// Should fail: a server handling only `Get` cannot stand in for one that must
+// also handle `Put`
+assert_subtype!(
+    EpOffer<Http, Pick, TServer, tlist!(GetOnly)>,
+    EpOffer<Http, Pick, TServer, tlist!(PutOnly, GetOnly)>
+);
+
+fn main() {}
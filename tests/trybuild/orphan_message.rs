@@ -0,0 +1,64 @@
+use besedarium::*;
+
+struct Alice;
+struct Bob;
+impl Role for Alice {}
+impl Role for Bob {}
+impl RoleEq<Alice> for Alice {
+    type Output = True;
+}
+impl RoleEq<Bob> for Alice {
+    type Output = False;
+}
+impl RoleEq<Alice> for Bob {
+    type Output = False;
+}
+impl RoleEq<Bob> for Bob {
+    type Output = True;
+}
+struct Carol;
+impl Role for Carol {}
+impl RoleEq<Carol> for Alice {
+    type Output = False;
+}
+impl RoleEq<Alice> for Carol {
+    type Output = False;
+}
+impl RoleEq<Bob> for Carol {
+    type Output = False;
+}
+impl RoleEq<Carol> for Carol {
+    type Output = True;
+}
+struct Pick;
+struct Go;
+struct Stop;
+struct Log;
+impl ProtocolLabel for Pick {}
+impl ProtocolLabel for Go {}
+impl ProtocolLabel for Stop {}
+impl ProtocolLabel for Log {}
+impl LabelEq<Log> for Go {
+    type Output = False;
+}
+impl LabelEq<Log> for Stop {
+    type Output = False;
+}
+impl LabelEq<Log> for Log {
+    type Output = True;
+}
+
+// Carol cannot tell the branches apart, but Bob only expects her log after `Go`
+type G = TSelect<
+    Http,
+    Pick,
+    Alice,
+    tlist!(
+        TMsg<Http, Go, Alice, Bob, Message, TMsg<Http, Log, Carol, Bob, Notify, TEnd<Http>>>,
+        TMsg<Http, Stop, Alice, Bob, Message, TEnd<Http>>,
+    ),
+>;
+
+assert_orphan_free!(G);
+
+fn main() {}
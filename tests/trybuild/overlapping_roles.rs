@@ -0,0 +1,15 @@
+use besedarium::*;
+
+type Upload = TInteract<Http, EmptyLabel, TClient, Message, TEnd<Http, EmptyLabel>>;
+type Download = TInteract<
+    Http,
+    EmptyLabel,
+    TServer,
+    Response,
+    TInteract<Http, EmptyLabel, TClient, Publish, TEnd<Http, EmptyLabel>>,
+>;
+
+// Should fail: TClient takes part in both protocols
+assert_disjoint!(Upload, Download);
+
+fn main() {}
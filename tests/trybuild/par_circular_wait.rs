@@ -0,0 +1,36 @@
+use besedarium::*;
+
+struct Alice;
+struct Bob;
+impl Role for Alice {}
+impl Role for Bob {}
+impl RoleEq<Alice> for Alice {
+    type Output = True;
+}
+impl RoleEq<Bob> for Alice {
+    type Output = False;
+}
+impl RoleEq<Alice> for Bob {
+    type Output = False;
+}
+impl RoleEq<Bob> for Bob {
+    type Output = True;
+}
+
+struct Ping;
+struct Pong;
+impl ProtocolLabel for Ping {}
+impl ProtocolLabel for Pong {}
+
+// Should fail: Alice may wait for Pong while Bob waits for Ping
+type Crossed = TPar<
+    Http,
+    EmptyLabel,
+    TMsg<Http, Ping, Alice, Bob, Message, TEnd<Http>>,
+    TMsg<Http, Pong, Bob, Alice, Message, TEnd<Http>>,
+    False,
+>;
+
+assert_deadlock_free!(Crossed);
+
+fn main() {}
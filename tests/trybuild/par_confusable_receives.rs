@@ -0,0 +1,25 @@
+use besedarium::*;
+
+struct Ping;
+impl ProtocolLabel for Ping {}
+impl LabelEq<Ping> for Ping {
+    type Output = True;
+}
+
+type Done = EpEnd<Http, EmptyLabel, TServer>;
+
+// Should fail: both concurrent branches wait for `Ping`, so an incoming
+// message cannot be routed to either of them
+type Confused = EpPar<
+    Http,
+    EmptyLabel,
+    TServer,
+    EpRecv<Http, Ping, TServer, Message, Done>,
+    EpRecv<Http, Ping, TServer, Response, Done>,
+>;
+
+fn assert_locally_well_formed<E: LocallyWellFormed<Http, TServer>>() {}
+
+fn main() {
+    assert_locally_well_formed::<Confused>();
+}
@@ -0,0 +1,36 @@
+use besedarium::*;
+
+struct Alice;
+struct Bob;
+impl Role for Alice {}
+impl Role for Bob {}
+impl RoleEq<Alice> for Alice {
+    type Output = True;
+}
+impl RoleEq<Bob> for Alice {
+    type Output = False;
+}
+impl RoleEq<Alice> for Bob {
+    type Output = False;
+}
+impl RoleEq<Bob> for Bob {
+    type Output = True;
+}
+
+struct Left;
+struct Right;
+impl ProtocolLabel for Left {}
+impl ProtocolLabel for Right {}
+
+// Should fail: both branches send from Alice to Bob
+type Doubled = TPar<
+    Http,
+    EmptyLabel,
+    TMsg<Http, Left, Alice, Bob, Message, TEnd<Http>>,
+    TMsg<Http, Right, Alice, Bob, Message, TEnd<Http>>,
+    False,
+>;
+
+assert_linear!(Doubled);
+
+fn main() {}
@@ -0,0 +1,37 @@
+use besedarium::*;
+
+struct Alice;
+struct Bob;
+impl Role for Alice {}
+impl Role for Bob {}
+impl RoleEq<Alice> for Alice {
+    type Output = True;
+}
+impl RoleEq<Bob> for Alice {
+    type Output = False;
+}
+impl RoleEq<Alice> for Bob {
+    type Output = False;
+}
+impl RoleEq<Bob> for Bob {
+    type Output = True;
+}
+struct Req;
+struct Ack;
+struct Both;
+impl ProtocolLabel for Req {}
+impl ProtocolLabel for Ack {}
+impl ProtocolLabel for Both {}
+
+// Both branches send `Message`: frames cannot be routed to their branch
+type Par = TPar<
+    Http,
+    Both,
+    TMsg<Http, Req, Alice, Bob, Message, TEnd<Http>>,
+    TMsg<Http, Ack, Bob, Alice, Message, TEnd<Http>>,
+    False,
+>;
+
+assert_disjoint!(messages Par);
+
+fn main() {}
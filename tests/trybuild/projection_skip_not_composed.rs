@@ -0,0 +1,56 @@
+use besedarium::*;
+
+struct Alice;
+struct Bob;
+struct Carol;
+impl Role for Alice {}
+impl Role for Bob {}
+impl Role for Carol {}
+impl RoleEq<Alice> for Alice {
+    type Output = True;
+}
+impl RoleEq<Bob> for Alice {
+    type Output = False;
+}
+impl RoleEq<Carol> for Alice {
+    type Output = False;
+}
+impl RoleEq<Alice> for Bob {
+    type Output = False;
+}
+impl RoleEq<Bob> for Bob {
+    type Output = True;
+}
+impl RoleEq<Carol> for Bob {
+    type Output = False;
+}
+impl RoleEq<Alice> for Carol {
+    type Output = False;
+}
+impl RoleEq<Bob> for Carol {
+    type Output = False;
+}
+impl RoleEq<Carol> for Carol {
+    type Output = True;
+}
+struct Pick;
+struct Ack;
+impl ProtocolLabel for Pick {}
+impl ProtocolLabel for Ack {}
+
+type Choice = TSelect<
+    Http,
+    Pick,
+    Alice,
+    tlist!(
+        TMsg<Http, Pick, Alice, Bob, Message, TEnd<Http>>,
+        TMsg<Http, Pick, Alice, Bob, Response, TEnd<Http>>,
+    ),
+>;
+type Reply = TMsg<Http, Ack, Bob, Alice, Response, TEnd<Http>>;
+
+// Carol takes part in neither protocol: the choice projects to `EpSkip`,
+// which the composed protocol keeps but composition replaces by `EpEnd`
+assert_projection_composes!(Http; Choice, Reply, Carol);
+
+fn main() {}
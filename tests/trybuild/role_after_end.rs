@@ -0,0 +1,26 @@
+use besedarium::*;
+
+struct Alice;
+struct Bob;
+impl Role for Alice {}
+impl Role for Bob {}
+impl RoleEq<Alice> for Alice {
+    type Output = True;
+}
+impl RoleEq<Bob> for Alice {
+    type Output = False;
+}
+impl RoleEq<Alice> for Bob {
+    type Output = False;
+}
+impl RoleEq<Bob> for Bob {
+    type Output = True;
+}
+
+// Should fail: Alice leaves the session and then sends to Bob
+type Rejoined =
+    TEndFor<Http, EmptyLabel, Alice, TMsg<Http, EmptyLabel, Alice, Bob, Message, TEnd<Http>>>;
+
+fn main() {
+    let _ = core::marker::PhantomData::<<() as ProjectRole<Bob, Http, Rejoined>>::Out>;
+}
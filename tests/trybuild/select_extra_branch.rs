@@ -0,0 +1,32 @@
+use besedarium::*;
+
+struct Pick;
+struct Get;
+struct Put;
+impl ProtocolLabel for Pick {}
+impl ProtocolLabel for Get {}
+impl ProtocolLabel for Put {}
+impl LabelEq<Get> for Get {
+    type Output = True;
+}
+impl LabelEq<Put> for Get {
+    type Output = False;
+}
+impl LabelEq<Get> for Put {
+    type Output = False;
+}
+impl LabelEq<Put> for Put {
+    type Output = True;
+}
+
+type GetReq = EpSend<Http, Get, TClient, Message, EpEnd<Http, EmptyLabel, TClient>>;
+type PutReq = EpSend<Http, Put, TClient, Message, EpEnd<Http, EmptyLabel, TClient>>;
+
+// Should fail: a client that may pick `Put` cannot stand in for one whose
+// peer only expects `Get`
+assert_subtype!(
+    EpSelect<Http, Pick, TClient, tlist!(GetReq, PutReq)>,
+    EpSelect<Http, Pick, TClient, tlist!(GetReq)>
+);
+
+fn main() {}
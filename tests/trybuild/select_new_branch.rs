@@ -0,0 +1,48 @@
+use besedarium::*;
+
+struct Alice;
+struct Bob;
+impl Role for Alice {}
+impl Role for Bob {}
+impl RoleEq<Alice> for Alice {
+    type Output = True;
+}
+impl RoleEq<Bob> for Alice {
+    type Output = False;
+}
+impl RoleEq<Alice> for Bob {
+    type Output = False;
+}
+impl RoleEq<Bob> for Bob {
+    type Output = True;
+}
+struct Pick;
+struct Get;
+struct Put;
+impl ProtocolLabel for Pick {}
+impl ProtocolLabel for Get {}
+impl ProtocolLabel for Put {}
+impl LabelEq<Get> for Get {
+    type Output = True;
+}
+impl LabelEq<Put> for Get {
+    type Output = False;
+}
+impl LabelEq<Get> for Put {
+    type Output = False;
+}
+impl LabelEq<Put> for Put {
+    type Output = True;
+}
+
+type GetReq = TMsg<Http, Get, Alice, Bob, Message, TEnd<Http>>;
+type PutReq = TMsg<Http, Put, Alice, Bob, Message, TEnd<Http>>;
+
+// Should fail: the new version lets Alice pick `Put`, which Bob's old
+// implementation does not expect
+type V1 = TSelect<Http, Pick, Alice, tlist!(GetReq)>;
+type V2 = TSelect<Http, Pick, Alice, tlist!(GetReq, PutReq)>;
+
+assert_global_subtype!(V2, V1);
+
+fn main() {}
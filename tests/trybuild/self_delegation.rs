@@ -0,0 +1,35 @@
+use besedarium::*;
+
+struct Alice;
+struct Bob;
+impl Role for Alice {}
+impl Role for Bob {}
+impl RoleEq<Alice> for Alice {
+    type Output = True;
+}
+impl RoleEq<Bob> for Alice {
+    type Output = False;
+}
+impl RoleEq<Alice> for Bob {
+    type Output = False;
+}
+impl RoleEq<Bob> for Bob {
+    type Output = True;
+}
+
+// Should fail: Alice delegates her own endpoint to herself
+type Owed = EpSend<Http, EmptyLabel, Alice, Message, EpEnd<Http, EmptyLabel, Alice>>;
+type Loop = TMsg<
+    Http,
+    EmptyLabel,
+    Bob,
+    Alice,
+    Message,
+    TDelegate<Http, EmptyLabel, Alice, Alice, Owed, TEnd<Http>>,
+>;
+
+fn check<G: NoSelfMessaging>() {}
+
+fn main() {
+    check::<Loop>();
+}
@@ -0,0 +1,17 @@
+use besedarium::*;
+
+struct Alice;
+impl Role for Alice {}
+impl RoleEq<Alice> for Alice {
+    type Output = True;
+}
+
+struct Note;
+impl ProtocolLabel for Note {}
+
+// Should fail: Alice sends the note to itself
+type Memo = TMsg<Http, Note, Alice, Alice, Message, TEnd<Http>>;
+
+fn main() {
+    let _ = core::marker::PhantomData::<<() as ProjectRole<Alice, Http, Memo>>::Out>;
+}
@@ -0,0 +1,38 @@
+use besedarium::*;
+
+struct Alice;
+struct Bob;
+impl Role for Alice {}
+impl Role for Bob {}
+impl RoleEq<Alice> for Alice {
+    type Output = True;
+}
+impl RoleEq<Bob> for Alice {
+    type Output = False;
+}
+impl RoleEq<Alice> for Bob {
+    type Output = False;
+}
+impl RoleEq<Bob> for Bob {
+    type Output = True;
+}
+
+struct Header;
+impl ProtocolLabel for Header {}
+impl LabelEq<Header> for Header {
+    type Output = True;
+}
+
+// Should fail: both steps start with `Header`, so Bob cannot tell them apart
+type Headers = TShuffle<
+    Http,
+    EmptyLabel,
+    tlist!(
+        TMsg<Http, Header, Alice, Bob, Message, TEnd<Http>>,
+        TMsg<Http, Header, Alice, Bob, Response, TEnd<Http>>,
+    ),
+>;
+
+fn main() {
+    let _ = core::marker::PhantomData::<<() as ProjectRole<Bob, Http, Headers>>::Out>;
+}
@@ -0,0 +1,16 @@
+use besedarium::*;
+
+struct Login;
+struct Query;
+impl ProtocolLabel for Login {}
+impl ProtocolLabel for Query {}
+impl LabelEq<Query> for Login {
+    type Output = False;
+}
+
+// Should fail: no combinator of the session is labelled `Query`
+type Session = TMsg<Http, Login, TClient, TServer, Message, TEnd<Http>>;
+
+fn main() {
+    let _: Option<<Session as SliceByLabel<Query>>::Output> = None;
+}
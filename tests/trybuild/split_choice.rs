@@ -0,0 +1,30 @@
+use besedarium::*;
+
+struct Alice;
+struct Bob;
+impl Role for Alice {}
+impl Role for Bob {}
+impl RoleEq<Alice> for Alice {
+    type Output = True;
+}
+impl RoleEq<Bob> for Alice {
+    type Output = False;
+}
+impl RoleEq<Alice> for Bob {
+    type Output = False;
+}
+impl RoleEq<Bob> for Bob {
+    type Output = True;
+}
+
+// Should fail: Alice starts one branch and Bob the other, so neither decides
+type Split = TChoice<
+    Http,
+    EmptyLabel,
+    TMsg<Http, EmptyLabel, Alice, Bob, Message, TEnd<Http>>,
+    TMsg<Http, EmptyLabel, Bob, Alice, Message, TEnd<Http>>,
+>;
+
+assert_choice_aware!(Split);
+
+fn main() {}
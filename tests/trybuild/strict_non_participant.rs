@@ -0,0 +1,45 @@
+use besedarium::*;
+
+struct Alice;
+struct Bob;
+struct Charlie;
+impl Role for Alice {}
+impl Role for Bob {}
+impl Role for Charlie {}
+impl RoleEq<Alice> for Alice {
+    type Output = True;
+}
+impl RoleEq<Bob> for Alice {
+    type Output = False;
+}
+impl RoleEq<Charlie> for Alice {
+    type Output = False;
+}
+impl RoleEq<Alice> for Bob {
+    type Output = False;
+}
+impl RoleEq<Bob> for Bob {
+    type Output = True;
+}
+impl RoleEq<Charlie> for Bob {
+    type Output = False;
+}
+impl RoleEq<Alice> for Charlie {
+    type Output = False;
+}
+impl RoleEq<Bob> for Charlie {
+    type Output = False;
+}
+impl RoleEq<Charlie> for Charlie {
+    type Output = True;
+}
+
+struct Note;
+impl ProtocolLabel for Note {}
+
+type Memo = TMsg<Http, Note, Alice, Bob, Message, TEnd<Http>>;
+
+fn main() {
+    // Should fail: Charlie takes no part in the memo
+    let _ = core::marker::PhantomData::<<() as ProjectRole<Charlie, Http, Memo, Strict>>::Out>;
+}
@@ -0,0 +1,32 @@
+use besedarium::*;
+
+struct Alice;
+struct Bob;
+impl Role for Alice {}
+impl Role for Bob {}
+impl RoleEq<Alice> for Alice {
+    type Output = True;
+}
+impl RoleEq<Bob> for Alice {
+    type Output = False;
+}
+impl RoleEq<Alice> for Bob {
+    type Output = False;
+}
+impl RoleEq<Bob> for Bob {
+    type Output = True;
+}
+struct L1;
+struct L2;
+struct L3;
+impl ProtocolLabel for L1 {}
+impl ProtocolLabel for L2 {}
+impl ProtocolLabel for L3 {}
+
+// Alice either messages Bob or stops, so Bob cannot learn which branch was taken
+type Split = TChoice<Http, L1, TMsg<Http, L2, Alice, Bob, Message, TEnd<Http>>, TEnd<Http, L3>>;
+
+fn main() {
+    // Should fail: strict projection requires a choice-aware protocol
+    let _ = core::marker::PhantomData::<<() as ProjectRole<Alice, Http, Split, Strict>>::Out>;
+}
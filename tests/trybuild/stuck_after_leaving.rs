@@ -0,0 +1,32 @@
+use besedarium::*;
+
+struct Alice;
+struct Bob;
+impl Role for Alice {}
+impl Role for Bob {}
+impl RoleEq<Alice> for Alice {
+    type Output = True;
+}
+impl RoleEq<Bob> for Alice {
+    type Output = False;
+}
+impl RoleEq<Alice> for Bob {
+    type Output = False;
+}
+impl RoleEq<Bob> for Bob {
+    type Output = True;
+}
+
+// Should fail: Bob waits for a message from Alice, who has already left
+type Abandoned = TMsg<
+    Http,
+    EmptyLabel,
+    Alice,
+    Bob,
+    Message,
+    TEndFor<Http, EmptyLabel, Alice, TMsg<Http, EmptyLabel, Alice, Bob, Message, TEnd<Http>>>,
+>;
+
+assert_progress!(Abandoned);
+
+fn main() {}
@@ -0,0 +1,45 @@
+use besedarium::*;
+
+struct Alice;
+struct Bob;
+impl Role for Alice {}
+impl Role for Bob {}
+impl RoleEq<Alice> for Alice {
+    type Output = True;
+}
+impl RoleEq<Bob> for Alice {
+    type Output = False;
+}
+impl RoleEq<Alice> for Bob {
+    type Output = False;
+}
+impl RoleEq<Bob> for Bob {
+    type Output = True;
+}
+
+struct Ping;
+struct Pong;
+impl ProtocolLabel for Ping {}
+impl ProtocolLabel for Pong {}
+impl LabelEq<Ping> for Ping {
+    type Output = True;
+}
+impl LabelEq<Pong> for Ping {
+    type Output = False;
+}
+impl LabelEq<Ping> for Pong {
+    type Output = False;
+}
+impl LabelEq<Pong> for Pong {
+    type Output = True;
+}
+
+// Should fail: Alice sends a ping, but Bob waits for a pong
+type Locals = tlist!(
+    RoleLocal<Alice, EpSend<Http, Ping, Alice, Message, EpEnd<Http, EmptyLabel, Alice>>>,
+    RoleLocal<Bob, EpRecv<Http, Pong, Bob, Message, EpEnd<Http, EmptyLabel, Bob>>>,
+);
+
+fn main() {
+    let _ = core::marker::PhantomData::<<Locals as Synthesize<Http>>::Output>;
+}
@@ -0,0 +1,28 @@
+use besedarium::*;
+
+struct Alice;
+struct Bob;
+impl Role for Alice {}
+impl Role for Bob {}
+impl RoleEq<Alice> for Alice {
+    type Output = True;
+}
+impl RoleEq<Bob> for Alice {
+    type Output = False;
+}
+impl RoleEq<Alice> for Bob {
+    type Output = False;
+}
+impl RoleEq<Bob> for Bob {
+    type Output = True;
+}
+
+// Should fail: synthesis does not cover n-ary choices
+type Locals = tlist!(
+    RoleLocal<Alice, EpChoiceN<Http, EmptyLabel, Alice, tlist!(EpEnd<Http, EmptyLabel, Alice>)>>,
+    RoleLocal<Bob, EpEnd<Http, EmptyLabel, Bob>>,
+);
+
+fn main() {
+    let _ = core::marker::PhantomData::<<Locals as Synthesize<Http>>::Output>;
+}
@@ -0,0 +1,41 @@
+use besedarium::*;
+
+struct Alice;
+struct Bob;
+impl Role for Alice {}
+impl Role for Bob {}
+impl RoleEq<Alice> for Alice {
+    type Output = True;
+}
+impl RoleEq<Bob> for Alice {
+    type Output = False;
+}
+impl RoleEq<Alice> for Bob {
+    type Output = False;
+}
+impl RoleEq<Bob> for Bob {
+    type Output = True;
+}
+struct Req;
+struct Ack;
+impl ProtocolLabel for Req {}
+impl ProtocolLabel for Ack {}
+impl LabelEq<Req> for Req {
+    type Output = True;
+}
+impl LabelEq<Ack> for Req {
+    type Output = False;
+}
+impl LabelEq<Req> for Ack {
+    type Output = False;
+}
+impl LabelEq<Ack> for Ack {
+    type Output = True;
+}
+
+type G = TMsg<Http, Req, Alice, Bob, Message, TMsg<Http, Ack, Bob, Alice, Response, TEnd<Http>>>;
+
+// Bob cannot acknowledge before Alice has asked
+assert_admits!(G, tlist!(Step<Bob, Ack, Response>, Step<Alice, Req, Message>));
+
+fn main() {}
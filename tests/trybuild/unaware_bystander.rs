@@ -0,0 +1,31 @@
+use besedarium::*;
+
+struct Alice;
+struct Bob;
+impl Role for Alice {}
+impl Role for Bob {}
+impl RoleEq<Alice> for Alice {
+    type Output = True;
+}
+impl RoleEq<Bob> for Alice {
+    type Output = False;
+}
+impl RoleEq<Alice> for Bob {
+    type Output = False;
+}
+impl RoleEq<Bob> for Bob {
+    type Output = True;
+}
+
+struct Pick;
+struct Ping;
+impl ProtocolLabel for Pick {}
+impl ProtocolLabel for Ping {}
+
+// Should fail: Bob is only told about the first branch, so Bob cannot know
+// whether to wait for the ping
+type Maybe = TChoice<Http, Pick, TMsg<Http, Ping, Alice, Bob, Message, TEnd<Http>>, TEnd<Http>>;
+
+fn main() {
+    let _ = core::marker::PhantomData::<<() as ProjectRole<Bob, Http, Maybe>>::Out>;
+}
@@ -0,0 +1,13 @@
+use besedarium::*;
+
+struct Failed;
+
+fn assert_caught<G: CatchScoped<False>>() {}
+
+// Should fail: the server throws with no enclosing TCatch to handle it
+type Escaping =
+    TInteract<Http, EmptyLabel, TClient, Message, TThrow<Http, EmptyLabel, TServer, Failed>>;
+
+fn main() {
+    assert_caught::<Escaping>();
+}
@@ -0,0 +1,56 @@
+use besedarium::*;
+
+struct Alice;
+struct Bob;
+struct Carol;
+impl Role for Alice {}
+impl Role for Bob {}
+impl Role for Carol {}
+impl RoleEq<Alice> for Alice {
+    type Output = True;
+}
+impl RoleEq<Bob> for Alice {
+    type Output = False;
+}
+impl RoleEq<Carol> for Alice {
+    type Output = False;
+}
+impl RoleEq<Alice> for Bob {
+    type Output = False;
+}
+impl RoleEq<Bob> for Bob {
+    type Output = True;
+}
+impl RoleEq<Carol> for Bob {
+    type Output = False;
+}
+impl RoleEq<Alice> for Carol {
+    type Output = False;
+}
+impl RoleEq<Bob> for Carol {
+    type Output = False;
+}
+impl RoleEq<Carol> for Carol {
+    type Output = True;
+}
+struct L3;
+struct L4;
+impl ProtocolLabel for L3 {}
+impl ProtocolLabel for L4 {}
+
+// Should fail: after Bob's message to Carol the loop starts again with
+// Alice, who took part in neither
+type Relay = TRec<
+    Http,
+    L3,
+    TChoice<
+        Http,
+        L4,
+        TMsg<Http, L4, Alice, Bob, Message, TMsg<Http, L4, Bob, Carol, Message, TVar<L3>>>,
+        TMsg<Http, L4, Alice, Bob, Message, TEnd<Http>>,
+    >,
+>;
+
+assert_connected!(Relay);
+
+fn main() {}
@@ -0,0 +1,28 @@
+use besedarium::*;
+
+struct Alice;
+struct Bob;
+struct Carol;
+impl Role for Alice {}
+impl Role for Bob {}
+impl Role for Carol {}
+impl RoleEq<Alice> for Carol {
+    type Output = False;
+}
+impl RoleEq<Bob> for Carol {
+    type Output = False;
+}
+
+// Should fail: Carol sends without having taken part in the step before
+type OutOfNowhere = TMsg<
+    Http,
+    EmptyLabel,
+    Alice,
+    Bob,
+    Message,
+    TMsg<Http, EmptyLabel, Carol, Alice, Message, TEnd<Http>>,
+>;
+
+assert_connected!(OutOfNowhere);
+
+fn main() {}
@@ -0,0 +1,25 @@
+use besedarium::*;
+
+struct Alice;
+struct Bob;
+impl Role for Alice {}
+impl Role for Bob {}
+impl RoleEq<Alice> for Alice {
+    type Output = True;
+}
+impl RoleEq<Bob> for Alice {
+    type Output = False;
+}
+impl RoleEq<Alice> for Bob {
+    type Output = False;
+}
+impl RoleEq<Bob> for Bob {
+    type Output = True;
+}
+
+// Should fail: the protocol talks to Bob, but only Alice is declared
+type Hello = TMsg<Http, EmptyLabel, Alice, Bob, Message, TEnd<Http>>;
+
+assert_roles_covered!(Hello, tlist!(Alice));
+
+fn main() {}
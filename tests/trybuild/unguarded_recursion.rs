@@ -0,0 +1,14 @@
+use besedarium::*;
+
+struct Loop;
+impl VarEq<Loop> for Loop {
+    type Output = True;
+}
+
+// Should fail: the loop jumps back before anyone communicates
+type Spin = TRecX<Http, EmptyLabel, Loop, TVar<Loop>>;
+type SpinClient = <() as ProjectRole<TClient, Http, Spin>>::Out;
+
+fn main() {
+    let _ = core::any::TypeId::of::<SpinClient>();
+}
@@ -0,0 +1,11 @@
+use besedarium::*;
+
+struct Loop;
+
+// Should fail: the repeated body jumps before anyone communicates
+type Spin = TRec<Http, EmptyLabel, TVar<Loop>>;
+type SpinClient = <() as ProjectRole<TClient, Http, Spin>>::Out;
+
+fn main() {
+    let _ = core::any::TypeId::of::<SpinClient>();
+}
@@ -0,0 +1,56 @@
+use besedarium::*;
+
+struct Alice;
+struct Bob;
+struct Carol;
+impl Role for Alice {}
+impl Role for Bob {}
+impl Role for Carol {}
+impl RoleEq<Alice> for Alice {
+    type Output = True;
+}
+impl RoleEq<Bob> for Alice {
+    type Output = False;
+}
+impl RoleEq<Carol> for Alice {
+    type Output = False;
+}
+impl RoleEq<Alice> for Bob {
+    type Output = False;
+}
+impl RoleEq<Bob> for Bob {
+    type Output = True;
+}
+impl RoleEq<Carol> for Bob {
+    type Output = False;
+}
+impl RoleEq<Alice> for Carol {
+    type Output = False;
+}
+impl RoleEq<Bob> for Carol {
+    type Output = False;
+}
+impl RoleEq<Carol> for Carol {
+    type Output = True;
+}
+
+struct Pick;
+struct Get;
+struct Put;
+impl ProtocolLabel for Pick {}
+impl ProtocolLabel for Get {}
+impl ProtocolLabel for Put {}
+
+// Alice picks a branch, but Carol sends in one and receives in the other
+// without ever being told which one was taken
+type Sync = TChoice<
+    Http,
+    Pick,
+    TMsg<Http, Get, Alice, Bob, Message, TMsg<Http, Get, Carol, Bob, Message, TEnd<Http>>>,
+    TMsg<Http, Put, Alice, Bob, Message, TMsg<Http, Put, Bob, Carol, Message, TEnd<Http>>>,
+>;
+
+// Should fail: Carol's projections of the two branches cannot be merged
+fn main() {
+    let _ = core::marker::PhantomData::<<() as ProjectRole<Carol, Http, Sync>>::Out>;
+}
@@ -0,0 +1,14 @@
+use besedarium::*;
+use core::marker::PhantomData;
+
+// The members of a role family have no `Role::NAME`
+struct Worker<N>(PhantomData<N>);
+role_family!(Worker);
+
+type Pair = TMsg<Http, EmptyLabel, Worker<Zero>, Worker<Succ<Zero>>, Message, TEnd<Http>>;
+
+const ROLES: [&str; 2] = <Pair as RoleNamesOf<_>>::ROLE_NAMES;
+
+fn main() {
+    println!("{ROLES:?}");
+}
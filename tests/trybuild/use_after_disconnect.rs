@@ -0,0 +1,33 @@
+use besedarium::*;
+
+struct Alice;
+struct Bob;
+impl Role for Alice {}
+impl Role for Bob {}
+impl RoleEq<Alice> for Alice {
+    type Output = True;
+}
+impl RoleEq<Bob> for Alice {
+    type Output = False;
+}
+impl RoleEq<Alice> for Bob {
+    type Output = False;
+}
+impl RoleEq<Bob> for Bob {
+    type Output = True;
+}
+
+fn assert_respected<G: ConnectionsRespected>() {}
+
+// Should fail: Bob answers Alice after their channel was closed
+type Reopened = TDisconnect<
+    Http,
+    EmptyLabel,
+    Alice,
+    Bob,
+    TMsg<Http, EmptyLabel, Bob, Alice, Response, TEnd<Http>>,
+>;
+
+fn main() {
+    assert_respected::<Reopened>();
+}
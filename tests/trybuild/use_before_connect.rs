@@ -0,0 +1,34 @@
+use besedarium::*;
+
+struct Alice;
+struct Bob;
+impl Role for Alice {}
+impl Role for Bob {}
+impl RoleEq<Alice> for Alice {
+    type Output = True;
+}
+impl RoleEq<Bob> for Alice {
+    type Output = False;
+}
+impl RoleEq<Alice> for Bob {
+    type Output = False;
+}
+impl RoleEq<Bob> for Bob {
+    type Output = True;
+}
+
+fn assert_respected<G: ConnectionsRespected>() {}
+
+// Should fail: Bob answers Alice before their channel is opened
+type Premature = TMsg<
+    Http,
+    EmptyLabel,
+    Bob,
+    Alice,
+    Response,
+    TConnect<Http, EmptyLabel, Alice, Bob, TEnd<Http>>,
+>;
+
+fn main() {
+    assert_respected::<Premature>();
+}